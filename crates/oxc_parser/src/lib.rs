@@ -273,7 +273,7 @@ mod parser_parse {
             pos: u32,
         ) -> std::result::Result<Expression<'a>, OxcDiagnostic> {
             let unique = UniquePromise::new();
-            let mut parser = ParserImpl::new_from_position(
+            let parser = ParserImpl::new_from_position(
                 self.allocator,
                 self.source_text,
                 self.source_type,
@@ -281,7 +281,7 @@ mod parser_parse {
                 pos,
                 unique,
             );
-            parser.bump_any();
+            // `parse_expression` moves onto the first token itself
             parser.parse_expression().map_err(|mut errors| errors.remove(0))
         }
 
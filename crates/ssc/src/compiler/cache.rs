@@ -0,0 +1,362 @@
+//! Content-hash compile cache
+//!
+//! Results are keyed on a SHA-256 hash of the source text, the compile options
+//! and the compiler version. Compilations without errors are stored, their
+//! owned artifacts and warnings encoded as MessagePack after a version header,
+//! so a hit never touches the parser.
+
+use std::{
+    fmt, fs,
+    hash::{Hash, Hasher},
+    io,
+    path::PathBuf,
+    process,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+use oxc_allocator::Allocator;
+use oxc_diagnostics::{Error, LabeledSpan, OxcDiagnostic, Severity};
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::{CompileOptions, Compiler, Emit};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CacheKey([u8; 32]);
+
+impl CacheKey {
+    pub fn new(source_text: &str, options: &CompileOptions) -> Self {
+        let mut hasher = DigestHasher(Sha256::new());
+        env!("CARGO_PKG_VERSION").hash(&mut hasher);
+        source_text.hash(&mut hasher);
+        options.hash(&mut hasher);
+        Self(hasher.0.finalize().into())
+    }
+}
+
+impl fmt::Display for CacheKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+/// Feeds the bytes of [`Hash`] implementations to a digest, so keys don't
+/// collide like 64-bit hashes can.
+struct DigestHasher(Sha256);
+
+impl Hasher for DigestHasher {
+    /// Only the digest is used, see [`CacheKey::new`].
+    fn finish(&self) -> u64 {
+        0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+}
+
+/// Storage for encoded compile results.
+///
+/// Stores are shared between compilations, so they take `&self`.
+/// Failing to store an entry is not an error, it only costs a recompile.
+pub trait CacheStore {
+    fn get(&self, key: CacheKey) -> Option<Vec<u8>>;
+
+    fn set(&self, key: CacheKey, value: Vec<u8>);
+}
+
+#[derive(Debug, Default)]
+pub struct MemoryCache {
+    entries: Mutex<FxHashMap<CacheKey, Vec<u8>>>,
+}
+
+impl MemoryCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().map_or(0, |entries| entries.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl CacheStore for MemoryCache {
+    fn get(&self, key: CacheKey) -> Option<Vec<u8>> {
+        self.entries.lock().ok()?.get(&key).cloned()
+    }
+
+    fn set(&self, key: CacheKey, value: Vec<u8>) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(key, value);
+        }
+    }
+}
+
+/// Stores every entry as a file named after its key.
+#[derive(Debug)]
+pub struct DiskCache {
+    dir: PathBuf,
+}
+
+impl DiskCache {
+    /// Creates `dir` if it doesn't exist yet.
+    ///
+    /// # Errors
+    ///
+    /// If `dir` can't be created.
+    pub fn new<P: Into<PathBuf>>(dir: P) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    fn path(&self, key: CacheKey) -> PathBuf {
+        self.dir.join(format!("{key}.bin"))
+    }
+}
+
+/// Numbers the temporary files of the entries written by this process.
+static TEMPORARY_FILES: AtomicUsize = AtomicUsize::new(0);
+
+impl CacheStore for DiskCache {
+    fn get(&self, key: CacheKey) -> Option<Vec<u8>> {
+        fs::read(self.path(key)).ok()
+    }
+
+    fn set(&self, key: CacheKey, value: Vec<u8>) {
+        // Write to a temporary file first, so that concurrent readers never
+        // see a partially written entry. Its name is unique, so concurrent
+        // writers of the same entry, in this process or another, don't write
+        // to the same file either
+        let number = TEMPORARY_FILES.fetch_add(1, Ordering::Relaxed);
+        let tmp = self.dir.join(format!("{key}.{}.{number}.tmp", process::id()));
+        if fs::write(&tmp, value).is_ok() {
+            let _ = fs::rename(&tmp, self.path(key));
+        }
+    }
+}
+
+/// The owned artifacts of a successful compilation.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompileOutput {
+    pub css: Option<String>,
+    /// The source map of `css`, as JSON.
+    pub css_map: Option<String>,
+    pub css_filename: Option<String>,
+    pub css_import: Option<String>,
+    /// The [`Hydration`](super::Hydration) metadata, as JSON.
+    pub hydration: Option<String>,
+    /// The server module of a static component.
+    pub server: Option<String>,
+    pub js: Option<String>,
+}
+
+/// Version of the format of cache entries, bumped when [`CompileOutput`]
+/// or [`CachedWarning`] change so that older entries are recompiled.
+const CACHE_VERSION: u32 = 3;
+
+/// A warning of a stored compilation.
+#[derive(Serialize, Deserialize)]
+struct CachedWarning {
+    message: String,
+    code: Option<String>,
+    advice: bool,
+    /// The offset, length and text of every label.
+    labels: Vec<(usize, usize, Option<String>)>,
+    help: Option<String>,
+}
+
+impl CachedWarning {
+    /// `None` if `error` is an error, or isn't an [`OxcDiagnostic`].
+    fn new(error: &Error) -> Option<Self> {
+        let diagnostic = error.downcast_ref::<OxcDiagnostic>()?;
+        let advice = match diagnostic.severity {
+            Severity::Warning => false,
+            Severity::Advice => true,
+            Severity::Error => return None,
+        };
+        let labels = diagnostic
+            .labels
+            .iter()
+            .flatten()
+            .map(|label| (label.offset(), label.len(), label.label().map(ToString::to_string)));
+        Some(Self {
+            message: diagnostic.message.to_string(),
+            code: diagnostic.code.number.as_deref().map(ToString::to_string),
+            advice,
+            labels: labels.collect(),
+            help: diagnostic.help.as_deref().map(ToString::to_string),
+        })
+    }
+
+    fn into_error(self) -> Error {
+        let labels = self
+            .labels
+            .into_iter()
+            .map(|(offset, len, label)| LabeledSpan::new(label, offset, len));
+        let mut diagnostic = OxcDiagnostic::warn(self.message).with_labels(labels);
+        if let Some(code) = self.code {
+            diagnostic = diagnostic.with_error_code("svelte", code);
+        }
+        if let Some(help) = self.help {
+            diagnostic = diagnostic.with_help(help);
+        }
+        if self.advice {
+            diagnostic = diagnostic.with_severity(Severity::Advice);
+        }
+        diagnostic.into()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    output: CompileOutput,
+    warnings: Vec<CachedWarning>,
+}
+
+impl CacheEntry {
+    fn encode(&self) -> Option<Vec<u8>> {
+        let mut bytes = CACHE_VERSION.to_le_bytes().to_vec();
+        rmp_serde::encode::write(&mut bytes, self).ok()?;
+        Some(bytes)
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        let version = u32::from_le_bytes(bytes.get(..4)?.try_into().ok()?);
+        if version != CACHE_VERSION {
+            return None;
+        }
+        rmp_serde::from_slice(&bytes[4..]).ok()
+    }
+}
+
+pub struct CachedCompileReturn {
+    pub output: CompileOutput,
+    pub errors: Vec<Error>,
+    /// Whether `output` came from the cache.
+    pub cached: bool,
+}
+
+/// Compiles `source_text`, reusing the result stored in `store` for the same
+/// source and options.
+///
+/// [`Emit::Ast`] is ignored, as the AST can't outlive the compilation.
+pub fn compile_cached<S: CacheStore + ?Sized>(
+    store: &S,
+    source_text: &str,
+    options: &CompileOptions,
+) -> CachedCompileReturn {
+    let options = CompileOptions { emit: options.emit - Emit::Ast, ..options.clone() };
+    let key = CacheKey::new(source_text, &options);
+    if let Some(entry) = store.get(key).as_deref().and_then(CacheEntry::decode) {
+        let errors = entry.warnings.into_iter().map(CachedWarning::into_error).collect();
+        return CachedCompileReturn { output: entry.output, errors, cached: true };
+    }
+
+    let allocator = Allocator::default();
+    let ret = Compiler::new(&allocator, source_text, options).compile();
+    let css_map = ret.css.as_ref().and_then(|css| css.source_map.as_ref()?.to_json_string().ok());
+    let output = CompileOutput {
+        css: ret.css.map(|css| css.source_text),
+        css_map,
+        css_filename: ret.css_filename,
+        css_import: ret.css_import,
+        hydration: ret.hydration.as_ref().map(super::Hydration::to_json),
+        server: ret.server.map(|server| server.source_text),
+        js: ret.js.map(|js| js.source_text),
+    };
+    // Compilations with errors, and warnings that can't be stored, are
+    // compiled again
+    if let Some(warnings) = ret.errors.iter().map(CachedWarning::new).collect::<Option<_>>() {
+        let entry = CacheEntry { output, warnings };
+        if let Some(bytes) = entry.encode() {
+            store.set(key, bytes);
+        }
+        return CachedCompileReturn { output: entry.output, errors: ret.errors, cached: false };
+    }
+    CachedCompileReturn { output, errors: ret.errors, cached: false }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::compiler::warning_code;
+
+    const SOURCE: &str = "<p>hi</p><style>p { color: red; }</style>";
+
+    #[test]
+    fn memory_cache() {
+        let cache = MemoryCache::new();
+        let options = CompileOptions::default();
+
+        let ret = compile_cached(&cache, SOURCE, &options);
+        assert!(!ret.cached);
+        assert!(ret.output.css_map.is_some());
+        assert_eq!(cache.len(), 1);
+
+        let cached = compile_cached(&cache, SOURCE, &options);
+        assert!(cached.cached);
+        assert_eq!(cached.output, ret.output);
+
+        let options = CompileOptions { emit: Emit::Css, ..CompileOptions::default() };
+        let ret = compile_cached(&cache, SOURCE, &options);
+        assert!(!ret.cached);
+        assert!(ret.output.css_map.is_none());
+
+        let ret = compile_cached(&cache, "<p>", &options);
+        assert!(!ret.errors.is_empty());
+        assert_eq!(cache.len(), 2);
+
+        // Warnings are stored along with the output
+        let source = "<div />";
+        let ret = compile_cached(&cache, source, &options);
+        assert_eq!(cache.len(), 3);
+        let cached = compile_cached(&cache, source, &options);
+        assert!(cached.cached);
+        assert_eq!(cached.errors.len(), 1);
+        let (warning, cached_warning) = (&ret.errors[0], &cached.errors[0]);
+        assert_eq!(cached_warning.to_string(), warning.to_string());
+        assert_eq!(warning_code(cached_warning), Some("element_invalid_self_closing_tag"));
+        assert_eq!(cached_warning.severity(), Some(Severity::Warning));
+        let offsets = |error: &Error| {
+            error.labels().unwrap().map(|label| (label.offset(), label.len())).collect::<Vec<_>>()
+        };
+        assert_eq!(offsets(cached_warning), offsets(warning));
+    }
+
+    #[test]
+    fn disk_cache() {
+        let dir = std::env::temp_dir().join(format!("ssc-cache-{}", std::process::id()));
+        let cache = DiskCache::new(&dir).unwrap();
+        let options = CompileOptions::default();
+
+        let ret = compile_cached(&cache, SOURCE, &options);
+        assert!(!ret.cached);
+        let cached = compile_cached(&cache, SOURCE, &options);
+        assert!(cached.cached);
+        assert_eq!(cached.output, ret.output);
+
+        // Corrupted entries and entries of another version are recompiled
+        let path = cache.path(CacheKey::new(SOURCE, &options));
+        fs::write(&path, CACHE_VERSION.to_le_bytes()).unwrap();
+        assert!(!compile_cached(&cache, SOURCE, &options).cached);
+        let mut entry = fs::read(&path).unwrap();
+        assert!(entry.starts_with(&CACHE_VERSION.to_le_bytes()));
+        entry[..4].copy_from_slice(&(CACHE_VERSION - 1).to_le_bytes());
+        fs::write(&path, entry).unwrap();
+        assert!(!compile_cached(&cache, SOURCE, &options).cached);
+        // No temporary file is left
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,284 @@
+//! `@import` resolution
+//!
+//! Inlines the stylesheets imported by a component's `<style>`, so they are
+//! scoped and emitted with it.
+
+use std::mem;
+
+use oxc_allocator::{Allocator, Vec};
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_span::Span;
+#[allow(clippy::wildcard_imports)]
+use ssc_css_ast::{ast::*, visit::walk_mut, VisitMut};
+use ssc_css_parser::Parser;
+
+/// What an `@import` resolves to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolvedImport {
+    /// CSS source to inline in place of the `@import`.
+    Inline(String),
+    /// Keep the `@import` in the output, e.g. for a stylesheet served from a CDN.
+    External,
+}
+
+/// Resolves the URLs of `@import` rules.
+///
+/// Implemented for closures, `|url, importer| Ok(ResolvedImport::External)`.
+pub trait ImportResolver {
+    /// Resolve `url`, imported by the stylesheet at the `importer` URL, or by
+    /// the component itself when `importer` is `None`.
+    ///
+    /// # Errors
+    ///
+    /// A message reported on the `@import` rule, which is then kept as is.
+    fn resolve(&self, url: &str, importer: Option<&str>) -> Result<ResolvedImport, String>;
+}
+
+impl<F> ImportResolver for F
+where
+    F: Fn(&str, Option<&str>) -> Result<ResolvedImport, String>,
+{
+    fn resolve(&self, url: &str, importer: Option<&str>) -> Result<ResolvedImport, String> {
+        self(url, importer)
+    }
+}
+
+/// Replace the `@import` rules of `stylesheet` the resolver inlines by the
+/// rules of the imported stylesheets, recursively.
+///
+/// Imports kept external are moved before every other rule, as `@import`
+/// rules after a style rule are ignored by browsers. Conditional imports,
+/// e.g. `@import "print.css" print`, are always kept external.
+pub(crate) fn inline_imports<'a>(
+    allocator: &'a Allocator,
+    stylesheet: &mut StyleSheet<'a>,
+    resolver: &dyn ImportResolver,
+) -> std::vec::Vec<OxcDiagnostic> {
+    let mut inliner = ImportInliner {
+        allocator,
+        resolver,
+        stack: vec![],
+        external: Vec::new_in(allocator),
+        errors: vec![],
+    };
+    let rules = mem::replace(&mut stylesheet.children, Vec::new_in(allocator));
+    let rules = inliner.inline(rules, None);
+    stylesheet.children = inliner.external;
+    stylesheet.children.extend(rules);
+    inliner.errors
+}
+
+struct ImportInliner<'a, 'r> {
+    allocator: &'a Allocator,
+    resolver: &'r dyn ImportResolver,
+    /// URLs of the stylesheets being inlined, to detect cycles.
+    stack: std::vec::Vec<String>,
+    external: Vec<'a, Rule<'a>>,
+    errors: std::vec::Vec<OxcDiagnostic>,
+}
+
+impl<'a, 'r> ImportInliner<'a, 'r> {
+    fn inline(&mut self, rules: Vec<'a, Rule<'a>>, importer: Option<&str>) -> Vec<'a, Rule<'a>> {
+        let mut inlined = Vec::with_capacity_in(rules.len(), self.allocator);
+        for rule in rules {
+            let Rule::AtRule(at_rule) = &rule else {
+                inlined.push(rule);
+                continue;
+            };
+            if at_rule.name != "import" {
+                inlined.push(rule);
+                continue;
+            }
+            let span = at_rule.span;
+            let Some(url) = unconditional_import_url(&at_rule.prelude) else {
+                self.external.push(rule);
+                continue;
+            };
+            match self.resolver.resolve(url, importer) {
+                Ok(ResolvedImport::External) => self.external.push(rule),
+                Ok(ResolvedImport::Inline(source)) => {
+                    if self.stack.iter().any(|importing| importing == url) {
+                        self.errors.push(circular_import(span, url));
+                        continue;
+                    }
+                    let Some(rules) = self.parse(span, url, &source) else { continue };
+                    self.stack.push(url.to_string());
+                    inlined.extend(self.inline(rules, Some(url)));
+                    self.stack.pop();
+                }
+                Err(message) => {
+                    self.errors.push(unresolved_import(span, url, &message));
+                    inlined.push(rule);
+                }
+            }
+        }
+        inlined
+    }
+
+    /// Parse an imported stylesheet. Its spans are those of the `@import` rule,
+    /// source maps and diagnostics point to it.
+    fn parse(&mut self, span: Span, url: &str, source: &str) -> Option<Vec<'a, Rule<'a>>> {
+        let source = self.allocator.alloc_str(source);
+        let mut ret = Parser::new(self.allocator, source).parse();
+        let panicked = ret.panicked;
+        self.errors.extend(ret.errors.into_iter().map(|error| invalid_import(span, url, &error)));
+        if panicked {
+            return None;
+        }
+        RelocateSpans(span).visit_stylesheet(&mut ret.stylesheet);
+        Some(ret.stylesheet.children)
+    }
+}
+
+/// The URL of `@import url("a.css")`, `@import "a.css"` and their variants,
+/// `None` when media queries or other conditions follow it.
+fn unconditional_import_url(prelude: &str) -> Option<&str> {
+    let prelude = prelude.trim();
+    let (url, rest) = if let Some(rest) = prelude.strip_prefix("url(") {
+        let end = rest.find(')')?;
+        (unquote(rest[..end].trim())?, &rest[end + 1..])
+    } else {
+        let quote = prelude.chars().next().filter(|ch| matches!(ch, '"' | '\''))?;
+        let end = prelude[1..].find(quote)? + 1;
+        (&prelude[1..end], &prelude[end + 1..])
+    };
+    (!url.is_empty() && rest.trim().is_empty()).then_some(url)
+}
+
+fn unquote(value: &str) -> Option<&str> {
+    match value.chars().next() {
+        Some(quote @ ('"' | '\'')) => value[1..].strip_suffix(quote),
+        _ => Some(value),
+    }
+}
+
+fn circular_import(span: Span, url: &str) -> OxcDiagnostic {
+    OxcDiagnostic::error(format!("`{url}` imports itself")).with_label(span)
+}
+
+fn unresolved_import(span: Span, url: &str, message: &str) -> OxcDiagnostic {
+    OxcDiagnostic::error(format!("Could not resolve `{url}`: {message}")).with_label(span)
+}
+
+fn invalid_import(span: Span, url: &str, error: &OxcDiagnostic) -> OxcDiagnostic {
+    let diagnostic = OxcDiagnostic::error(format!("In `{url}`: {}", error.message))
+        .with_severity(error.severity)
+        .with_label(span);
+    match &error.code.number {
+        Some(code) => diagnostic.with_error_code("svelte", code.clone()),
+        None => diagnostic,
+    }
+}
+
+/// Give every node the same span.
+struct RelocateSpans(Span);
+
+impl<'a> VisitMut<'a> for RelocateSpans {
+    fn visit_at_rule(&mut self, rule: &mut AtRule<'a>) {
+        rule.span = self.0;
+        walk_mut::walk_at_rule_mut(self, rule);
+    }
+
+    fn visit_style_rule(&mut self, rule: &mut StyleRule<'a>) {
+        rule.span = self.0;
+        walk_mut::walk_style_rule_mut(self, rule);
+    }
+
+    fn visit_block(&mut self, block: &mut Block<'a>) {
+        block.span = self.0;
+        walk_mut::walk_block_mut(self, block);
+    }
+
+    fn visit_declaration(&mut self, decl: &mut Declaration<'a>) {
+        decl.span = self.0;
+        walk_mut::walk_declaration_mut(self, decl);
+    }
+
+    fn visit_component_value(&mut self, component: &mut ComponentValue<'a>) {
+        match component {
+            ComponentValue::Ident(ident) => ident.span = self.0,
+            ComponentValue::Number(number) => number.span = self.0,
+            ComponentValue::Percentage(percentage) => percentage.span = self.0,
+            ComponentValue::Dimension(dimension) => dimension.span = self.0,
+            ComponentValue::Hash(hash) => hash.span = self.0,
+            ComponentValue::Operator(operator) => operator.span = self.0,
+            ComponentValue::Url(url) => url.span = self.0,
+            ComponentValue::UnicodeRange(range) => range.span = self.0,
+            ComponentValue::Function(function) => function.span = self.0,
+            ComponentValue::String(string) => string.span = self.0,
+            ComponentValue::Token(token) => token.span = self.0,
+        }
+        walk_mut::walk_component_value_mut(self, component);
+    }
+
+    fn visit_selector_list(&mut self, selector_list: &mut SelectorList<'a>) {
+        selector_list.span = self.0;
+        walk_mut::walk_selector_list_mut(self, selector_list);
+    }
+
+    fn visit_complex_selector(&mut self, selector: &mut ComplexSelector<'a>) {
+        selector.span = self.0;
+        walk_mut::walk_complex_selector_mut(self, selector);
+    }
+
+    fn visit_relative_selector(&mut self, selector: &mut RelativeSelector<'a>) {
+        selector.span = self.0;
+        walk_mut::walk_relative_selector_mut(self, selector);
+    }
+
+    fn visit_type_selector(&mut self, selector: &mut TypeSelector<'a>) {
+        selector.span = self.0;
+    }
+
+    fn visit_id_selector(&mut self, selector: &mut IdSelector<'a>) {
+        selector.span = self.0;
+    }
+
+    fn visit_class_selector(&mut self, selector: &mut ClassSelector<'a>) {
+        selector.span = self.0;
+    }
+
+    fn visit_attribute_selector(&mut self, selector: &mut AttributeSelector<'a>) {
+        selector.span = self.0;
+    }
+
+    fn visit_pseudo_element_selector(&mut self, selector: &mut PseudoElementSelector<'a>) {
+        selector.span = self.0;
+    }
+
+    fn visit_pseudo_class_selector(&mut self, selector: &mut PseudoClassSelector<'a>) {
+        selector.span = self.0;
+        walk_mut::walk_pseudo_class_selector_mut(self, selector);
+    }
+
+    fn visit_percentage_selector(&mut self, selector: &mut PercentageSelector<'a>) {
+        selector.span = self.0;
+    }
+
+    fn visit_nth_selector(&mut self, selector: &mut NthSelector<'a>) {
+        selector.span = self.0;
+    }
+
+    fn visit_nesting_selector(&mut self, selector: &mut NestingSelector) {
+        selector.span = self.0;
+    }
+
+    fn visit_combinator(&mut self, combinator: &mut Combinator) {
+        combinator.span = self.0;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn import_urls() {
+        assert_eq!(unconditional_import_url(r#""a.css""#), Some("a.css"));
+        assert_eq!(unconditional_import_url("url( 'a.css' )"), Some("a.css"));
+        assert_eq!(unconditional_import_url("url(a.css)"), Some("a.css"));
+        assert_eq!(unconditional_import_url(r#""print.css" print"#), None);
+        assert_eq!(unconditional_import_url("url(a.css) layer(base)"), None);
+        assert_eq!(unconditional_import_url(r#""""#), None);
+    }
+}
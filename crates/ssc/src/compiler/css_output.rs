@@ -0,0 +1,64 @@
+//! Emitted CSS file
+//!
+//! Bundlers emit the CSS of a component as a virtual file next to its JS
+//! module. Its name, banner and the import referencing it are decided here,
+//! so every integration follows the same conventions.
+
+use std::path::Path;
+
+use ssc_css_codegen::CodegenReturn;
+
+/// Options of the emitted CSS file, see [`CompileOptions::css_output`].
+///
+/// [`CompileOptions::css_output`]: super::CompileOptions::css_output
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CssOutputOptions {
+    /// Template of the name of the CSS file. `[name]` is replaced by the file
+    /// stem of [`CompileOptions::filename`], `[hash]` by a hash of the
+    /// emitted CSS, which only changes with it. Defaults to `[name].css`.
+    ///
+    /// [`CompileOptions::filename`]: super::CompileOptions::filename
+    pub filename: String,
+    /// Text of a comment prepended to the CSS, e.g. a license header.
+    pub banner: Option<String>,
+    /// Whether to return the `import` statement of the CSS file, for the JS
+    /// module of the component to load it.
+    pub import: bool,
+}
+
+impl Default for CssOutputOptions {
+    fn default() -> Self {
+        Self { filename: "[name].css".into(), banner: None, import: false }
+    }
+}
+
+impl CssOutputOptions {
+    /// The name of the CSS file of the component `filename`, emitting `css`.
+    pub fn css_filename(&self, filename: &str, css: &str) -> String {
+        let name = Path::new(filename).file_stem().and_then(|stem| stem.to_str()).unwrap_or("");
+        let mut css_filename = self.filename.replace("[name]", name);
+        if css_filename.contains("[hash]") {
+            css_filename = css_filename.replace("[hash]", &ssc_css_analyzer::hash(css));
+        }
+        css_filename
+    }
+
+    /// The statement importing `css_filename` from the JS module of the
+    /// component.
+    pub fn import_statement(css_filename: &str) -> String {
+        let path = if css_filename.starts_with("./") || css_filename.starts_with("../") {
+            css_filename.to_string()
+        } else {
+            format!("./{css_filename}")
+        };
+        format!("import {path:?};")
+    }
+
+    /// Prepend the banner, if any, to `ret`, shifting its source map.
+    pub(crate) fn add_banner(&self, ret: &mut CodegenReturn) {
+        let Some(banner) = &self.banner else { return };
+        // The banner can't end the comment early
+        let comment = format!("/* {} */\n", banner.replace("*/", "*\\/"));
+        super::inject::prepend(ret, &comment);
+    }
+}
@@ -0,0 +1,158 @@
+//! Unused CSS detection
+//!
+//! Marks the selectors of a component's `<style>` that may match an element
+//! of its template as used, so the transformer can drop the rules that
+//! can't, see [`CompileOptions::remove_unused_css`].
+//!
+//! Matching is conservative: combinators and most pseudo-classes are
+//! ignored, and spreads or dynamic `class`/`id` values match anything, so a
+//! selector is only left unused when no element can ever match it.
+//!
+//! [`CompileOptions::remove_unused_css`]: super::CompileOptions::remove_unused_css
+
+use ssc_ast::ast::{
+    AttributeSequenceValue, DirectiveAttribute, ElementAttribute, Fragment, RegularElement,
+    SvelteElement,
+};
+#[allow(clippy::wildcard_imports)]
+use ssc_css_ast::{
+    ast::*,
+    visit::walk::{walk_at_rule, walk_complex_selector},
+    Visit,
+};
+
+/// Mark the selectors of `stylesheet` that may match an element of
+/// `fragment`, in addition to the `:global` ones the analyzer marked.
+pub(crate) fn mark_used_selectors<'a>(fragment: &Fragment<'a>, stylesheet: &StyleSheet<'a>) {
+    let mut elements = fragment
+        .find_all::<RegularElement>()
+        .into_iter()
+        .map(|found| ElementInfo::new(Some(found.node.name.as_str()), &found.node.attributes))
+        .collect::<Vec<_>>();
+    elements.extend(
+        fragment
+            .find_all::<SvelteElement>()
+            .into_iter()
+            .map(|found| ElementInfo::new(None, &found.node.attributes)),
+    );
+    UsedSelectorMarker { elements, in_keyframes: false }.visit_stylesheet(stylesheet);
+}
+
+/// What selectors can know of an element.
+struct ElementInfo<'a> {
+    /// `None` for `<svelte:element>`, which can be any element.
+    name: Option<&'a str>,
+    classes: Vec<&'a str>,
+    ids: Vec<&'a str>,
+    attributes: Vec<&'a str>,
+    dynamic_class: bool,
+    dynamic_id: bool,
+    spread: bool,
+}
+
+impl<'a> ElementInfo<'a> {
+    fn new(name: Option<&'a str>, attributes: &[ElementAttribute<'a>]) -> Self {
+        let mut info = Self {
+            name,
+            classes: vec![],
+            ids: vec![],
+            attributes: vec![],
+            dynamic_class: false,
+            dynamic_id: false,
+            spread: false,
+        };
+        for attribute in attributes {
+            match attribute {
+                ElementAttribute::Attribute(attribute) => {
+                    let name = attribute.name.as_str();
+                    info.attributes.push(name);
+                    let value = attribute.value.as_ref().and_then(|value| {
+                        match value.sequence.as_slice() {
+                            [AttributeSequenceValue::Text(text)] => Some(text.data.as_str()),
+                            [] => Some(""),
+                            _ => None,
+                        }
+                    });
+                    if name.eq_ignore_ascii_case("class") {
+                        match value {
+                            Some(value) => info.classes.extend(value.split_ascii_whitespace()),
+                            None => info.dynamic_class = true,
+                        }
+                    } else if name.eq_ignore_ascii_case("id") {
+                        match value {
+                            Some(value) => info.ids.push(value),
+                            None => info.dynamic_id = true,
+                        }
+                    }
+                }
+                ElementAttribute::SpreadAttribute(_) => info.spread = true,
+                ElementAttribute::DirectiveAttribute(DirectiveAttribute::ClassDirective(
+                    directive,
+                )) => {
+                    info.attributes.push("class");
+                    info.classes.push(directive.name.as_str());
+                }
+                ElementAttribute::DirectiveAttribute(DirectiveAttribute::StyleDirective(_)) => {
+                    info.attributes.push("style");
+                }
+                ElementAttribute::DirectiveAttribute(DirectiveAttribute::BindDirective(
+                    directive,
+                )) => {
+                    info.attributes.push(directive.name.as_str());
+                }
+                ElementAttribute::DirectiveAttribute(_) => {}
+            }
+        }
+        info
+    }
+
+    /// Whether the element may match every simple selector of `selector`.
+    fn may_match(&self, selector: &RelativeSelector) -> bool {
+        selector.selectors.iter().all(|selector| match selector {
+            SimpleSelector::TypeSelector(selector) => {
+                selector.name.as_str() == "*"
+                    || self.name.map_or(true, |name| name.eq_ignore_ascii_case(&selector.name))
+            }
+            SimpleSelector::ClassSelector(selector) => {
+                self.spread || self.dynamic_class || self.classes.contains(&selector.name.as_str())
+            }
+            SimpleSelector::IdSelector(selector) => {
+                self.spread || self.dynamic_id || self.ids.contains(&selector.name.as_str())
+            }
+            SimpleSelector::AttributeSelector(selector) => {
+                self.spread
+                    || self.attributes.iter().any(|name| name.eq_ignore_ascii_case(&selector.name))
+            }
+            _ => true,
+        })
+    }
+}
+
+struct UsedSelectorMarker<'a> {
+    elements: Vec<ElementInfo<'a>>,
+    in_keyframes: bool,
+}
+
+impl<'a> Visit<'a> for UsedSelectorMarker<'a> {
+    fn visit_at_rule(&mut self, rule: &AtRule<'a>) {
+        let in_keyframes = self.in_keyframes;
+        self.in_keyframes |= rule.name.ends_with("keyframes");
+        walk_at_rule(self, rule);
+        self.in_keyframes = in_keyframes;
+    }
+
+    fn visit_complex_selector(&mut self, selector: &ComplexSelector<'a>) {
+        walk_complex_selector(self, selector);
+        if self.in_keyframes || selector.used.get() {
+            selector.used.set(true);
+            return;
+        }
+        let used = selector.children.iter().all(|relative_selector| {
+            let flags = relative_selector.flags.get();
+            flags.has_global()
+                || flags.has_global_like()
+                || self.elements.iter().any(|element| element.may_match(relative_selector))
+        });
+        selector.used.set(used);
+    }
+}
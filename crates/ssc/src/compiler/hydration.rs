@@ -0,0 +1,357 @@
+//! Client interactivity detection
+//!
+//! A component whose output never changes after the server rendered it, and
+//! that doesn't listen to the user, doesn't need to be hydrated: bundlers can
+//! skip its client code and ship the server rendered HTML alone. The
+//! [`Hydration`] metadata lists what makes a component interactive, see
+//! [`Emit::Hydration`].
+//!
+//! Detection is conservative: anything that may run on the client, or change
+//! the output, counts as interactive. Child components are only listed, a
+//! component is server-only when it and all of its children are static.
+//!
+//! [`Emit::Hydration`]: super::Emit::Hydration
+
+use std::ops::ControlFlow;
+
+use oxc_ast::{
+    ast::{
+        CallExpression, Expression, FunctionBody, IdentifierReference, ImportDeclaration,
+        ImportDeclarationSpecifier, Program, SimpleAssignmentTarget, Statement,
+        VariableDeclarationKind,
+    },
+    visit::walk,
+    Visit as _,
+};
+use oxc_span::{GetSpan, Span};
+use serde::Serialize;
+#[allow(clippy::wildcard_imports)]
+use ssc_ast::{
+    ast::*,
+    visit::walk::{walk_block, walk_element, walk_tag},
+    Visit,
+};
+
+/// Something that needs the component to run on the client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClientFeature {
+    /// `on:click={...}` or `onclick={...}`
+    EventHandler,
+    /// `bind:value={...}`
+    Binding,
+    /// `transition:`, `in:` or `out:`
+    Transition,
+    /// `animate:`
+    Animation,
+    /// `use:action`
+    Action,
+    /// `$state(...)`, or a top-level `let` assigned by a function
+    State,
+    /// `$effect(...)`
+    Effect,
+    /// A `$store` subscription
+    Store,
+    /// `onMount`, `beforeUpdate` or `afterUpdate` from `svelte`
+    Lifecycle,
+    /// `<svelte:component>`, which can render any component
+    DynamicComponent,
+    /// `{#await}`, whose promise settles on the client
+    Await,
+}
+
+impl ClientFeature {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::EventHandler => "event_handler",
+            Self::Binding => "binding",
+            Self::Transition => "transition",
+            Self::Animation => "animation",
+            Self::Action => "action",
+            Self::State => "state",
+            Self::Effect => "effect",
+            Self::Store => "store",
+            Self::Lifecycle => "lifecycle",
+            Self::DynamicComponent => "dynamic_component",
+            Self::Await => "await",
+        }
+    }
+}
+
+/// Interactivity of a component, for the bundler to decide whether it needs
+/// to be hydrated.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Hydration {
+    /// What makes the component interactive, in source order.
+    pub features: Vec<(ClientFeature, Span)>,
+    /// Specifiers of the imported components it renders, or their names when
+    /// they're not imported.
+    pub components: Vec<String>,
+}
+
+impl Hydration {
+    /// Detect the client features of `root`.
+    pub fn new(root: &Root<'_>) -> Self {
+        let mut scanner = ScriptScanner::default();
+        for script in [&root.module, &root.instance].into_iter().flatten() {
+            scanner.scan_program(&script.program);
+        }
+        let mut template = TemplateScanner { script: scanner, components: vec![] };
+        let _ = template.visit_fragment(&root.fragment);
+
+        let TemplateScanner { script, components } = template;
+        let mut features = script.features;
+        features.sort_by_key(|(_, span)| span.start);
+        let mut hydration = Self { features, components: vec![] };
+        for name in components {
+            let component = script
+                .imports
+                .iter()
+                .find(|(local, _)| *local == name)
+                .map_or(name, |(_, source)| source.clone());
+            if !hydration.components.contains(&component) {
+                hydration.components.push(component);
+            }
+        }
+        hydration
+    }
+
+    /// Whether the component itself needs no client code. It's server-only
+    /// when its `components` are too.
+    pub fn is_static(&self) -> bool {
+        self.features.is_empty()
+    }
+
+    /// The metadata as JSON, e.g.
+    /// `{"static":false,"features":["event_handler"],"components":["./Card.svelte"]}`.
+    pub fn to_json(&self) -> String {
+        let mut features = vec![];
+        for (feature, _) in &self.features {
+            if !features.contains(&feature.as_str()) {
+                features.push(feature.as_str());
+            }
+        }
+        let json =
+            HydrationJson { r#static: self.is_static(), features, components: &self.components };
+        serde_json::to_string(&json).unwrap_or_default()
+    }
+}
+
+#[derive(Serialize)]
+struct HydrationJson<'h> {
+    r#static: bool,
+    features: Vec<&'static str>,
+    components: &'h [String],
+}
+
+struct TemplateScanner {
+    script: ScriptScanner,
+    components: Vec<String>,
+}
+
+impl TemplateScanner {
+    fn add(&mut self, feature: ClientFeature, span: Span) {
+        self.script.features.push((feature, span));
+    }
+
+    fn scan_attributes(&mut self, attributes: &[ElementAttribute<'_>], is_dom: bool) {
+        for attribute in attributes {
+            match attribute {
+                ElementAttribute::Attribute(attribute) => {
+                    let Some(value) = &attribute.value else { continue };
+                    for part in &value.sequence {
+                        let AttributeSequenceValue::ExpressionTag(tag) = part else { continue };
+                        if is_dom && attribute.name.starts_with("on") {
+                            self.add(ClientFeature::EventHandler, attribute.span);
+                        }
+                        self.script.visit_expression(&tag.expression);
+                    }
+                }
+                ElementAttribute::SpreadAttribute(spread) => {
+                    self.script.visit_expression(&spread.expression);
+                }
+                ElementAttribute::DirectiveAttribute(directive) => {
+                    let feature = match directive {
+                        DirectiveAttribute::OnDirective(_) => ClientFeature::EventHandler,
+                        DirectiveAttribute::BindDirective(_) => ClientFeature::Binding,
+                        DirectiveAttribute::TransitionDirective(_) => ClientFeature::Transition,
+                        DirectiveAttribute::AnimateDirective(_) => ClientFeature::Animation,
+                        DirectiveAttribute::UseDirective(_) => ClientFeature::Action,
+                        DirectiveAttribute::ClassDirective(directive) => {
+                            self.script.visit_expression(&directive.expression);
+                            continue;
+                        }
+                        DirectiveAttribute::StyleDirective(_)
+                        | DirectiveAttribute::LetDirective(_) => continue,
+                    };
+                    self.add(feature, directive.span());
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Visit<'a> for TemplateScanner {
+    fn visit_element(&mut self, element: &Element<'a>) -> ControlFlow<()> {
+        match element {
+            Element::Component(component) => {
+                self.components.push(component.name.to_string());
+            }
+            Element::SvelteComponent(component) => {
+                self.add(ClientFeature::DynamicComponent, component.span);
+            }
+            _ => {}
+        }
+        let is_dom = matches!(
+            element,
+            Element::RegularElement(_)
+                | Element::SvelteElement(_)
+                | Element::SvelteBody(_)
+                | Element::SvelteDocument(_)
+                | Element::SvelteWindow(_)
+        );
+        self.scan_attributes(element.attributes(), is_dom);
+        walk_element(self, element)
+    }
+
+    fn visit_tag(&mut self, tag: &Tag<'a>) -> ControlFlow<()> {
+        match tag {
+            Tag::ExpressionTag(tag) => self.script.visit_expression(&tag.expression),
+            Tag::HtmlTag(tag) => self.script.visit_expression(&tag.expression),
+            Tag::ConstTag(tag) => self.script.visit_variable_declaration(&tag.declaration),
+            Tag::RenderTag(_) | Tag::DebugTag(_) => {}
+        }
+        walk_tag(self, tag)
+    }
+
+    fn visit_block(&mut self, block: &Block<'a>) -> ControlFlow<()> {
+        match block {
+            Block::EachBlock(block) => self.script.visit_expression(&block.expression),
+            Block::IfBlock(block) => self.script.visit_expression(&block.test),
+            Block::AwaitBlock(block) => {
+                self.add(ClientFeature::Await, block.span);
+                self.script.visit_expression(&block.expression);
+            }
+            Block::KeyBlock(block) => self.script.visit_expression(&block.expression),
+            Block::SnippetBlock(_) => {}
+        }
+        walk_block(self, block)
+    }
+}
+
+#[derive(Default)]
+struct ScriptScanner {
+    features: Vec<(ClientFeature, Span)>,
+    /// Local names and specifiers of the default imports.
+    imports: Vec<(String, String)>,
+    /// Top-level `let`s, which are state in legacy mode once reassigned.
+    lets: Vec<String>,
+    /// Depth of the functions being visited.
+    function_depth: usize,
+}
+
+impl ScriptScanner {
+    fn scan_program(&mut self, program: &Program<'_>) {
+        for statement in &program.body {
+            if let Statement::VariableDeclaration(declaration) = statement {
+                if declaration.kind == VariableDeclarationKind::Let {
+                    self.lets.extend(
+                        declaration
+                            .declarations
+                            .iter()
+                            .filter_map(|declarator| declarator.id.get_identifier())
+                            .map(|name| name.to_string()),
+                    );
+                }
+            }
+        }
+        self.visit_program(program);
+    }
+}
+
+impl<'a> oxc_ast::Visit<'a> for ScriptScanner {
+    fn visit_import_declaration(&mut self, decl: &ImportDeclaration<'a>) {
+        for specifier in decl.specifiers.iter().flatten() {
+            match specifier {
+                ImportDeclarationSpecifier::ImportDefaultSpecifier(specifier) => {
+                    self.imports
+                        .push((specifier.local.name.to_string(), decl.source.value.to_string()));
+                }
+                ImportDeclarationSpecifier::ImportSpecifier(specifier)
+                    if decl.source.value == "svelte"
+                        && matches!(
+                            specifier.imported.name().as_str(),
+                            "onMount" | "beforeUpdate" | "afterUpdate"
+                        ) =>
+                {
+                    self.features.push((ClientFeature::Lifecycle, specifier.span));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn visit_function_body(&mut self, body: &FunctionBody<'a>) {
+        self.function_depth += 1;
+        walk::walk_function_body(self, body);
+        self.function_depth -= 1;
+    }
+
+    fn visit_call_expression(&mut self, expr: &CallExpression<'a>) {
+        let rune = match &expr.callee {
+            Expression::Identifier(ident) => Some((ident.name.as_str(), None)),
+            Expression::StaticMemberExpression(member) => match &member.object {
+                Expression::Identifier(object) => {
+                    Some((object.name.as_str(), Some(member.property.name.as_str())))
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+        match rune {
+            Some(("$state", None | Some("raw"))) => {
+                self.features.push((ClientFeature::State, expr.span));
+            }
+            Some(("$effect", None | Some("pre"))) => {
+                self.features.push((ClientFeature::Effect, expr.span));
+            }
+            _ => {}
+        }
+        walk::walk_call_expression(self, expr);
+    }
+
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        let name = ident.name.as_str();
+        let is_rune = matches!(
+            name,
+            "$state" | "$derived" | "$effect" | "$props" | "$bindable" | "$inspect" | "$host"
+        );
+        if name.len() > 1 && name.starts_with('$') && !name.starts_with("$$") && !is_rune {
+            self.features.push((ClientFeature::Store, ident.span));
+        }
+    }
+
+    fn visit_simple_assignment_target(&mut self, target: &SimpleAssignmentTarget<'a>) {
+        if self.function_depth > 0 {
+            let mut object = match target {
+                SimpleAssignmentTarget::AssignmentTargetIdentifier(ident) => {
+                    Some(ident.name.as_str())
+                }
+                _ => None,
+            };
+            let mut member = target.as_member_expression();
+            while let Some(expression) = member {
+                match expression.object() {
+                    Expression::Identifier(ident) => {
+                        object = Some(ident.name.as_str());
+                        member = None;
+                    }
+                    object => member = object.as_member_expression(),
+                }
+            }
+            if object.is_some_and(|name| self.lets.iter().any(|own| own == name)) {
+                self.features.push((ClientFeature::State, target.span()));
+            }
+        }
+        walk::walk_simple_assignment_target(self, target);
+    }
+}
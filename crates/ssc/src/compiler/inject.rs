@@ -0,0 +1,57 @@
+//! Code injected around emitted artifacts
+//!
+//! Banners and footers, e.g. license headers or `@generated` markers, are
+//! added to the generated code after the fact. Source maps of the code are
+//! shifted past a banner so they still point to the right positions.
+
+use oxc_sourcemap::SourceMapBuilder;
+use ssc_css_codegen::CodegenReturn;
+
+/// Prepend `code` to `ret`, shifting its source map.
+pub(crate) fn prepend(ret: &mut CodegenReturn, code: &str) {
+    if code.is_empty() {
+        return;
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    let lines = code.matches('\n').count() as u32;
+    // Code on the banner's last line is shifted right by it
+    let last_line = code.rsplit('\n').next().unwrap_or_default();
+    #[allow(clippy::cast_possible_truncation)]
+    let columns = last_line.encode_utf16().count() as u32;
+    ret.source_text.insert_str(0, code);
+
+    let Some(map) = &ret.source_map else { return };
+    let mut builder = SourceMapBuilder::default();
+    if let Some(file) = map.get_file() {
+        builder.set_file(file);
+    }
+    for (id, source) in map.get_sources().enumerate() {
+        #[allow(clippy::cast_possible_truncation)]
+        let content = map.get_source_content(id as u32).unwrap_or_default();
+        builder.add_source_and_content(source, content);
+    }
+    for name in map.get_names() {
+        builder.add_name(name);
+    }
+    for token in map.get_tokens() {
+        let dst_col = if token.get_dst_line() == 0 {
+            token.get_dst_col() + columns
+        } else {
+            token.get_dst_col()
+        };
+        builder.add_token(
+            token.get_dst_line() + lines,
+            dst_col,
+            token.get_src_line(),
+            token.get_src_col(),
+            token.get_source_id(),
+            token.get_name_id(),
+        );
+    }
+    ret.source_map = Some(builder.into_sourcemap());
+}
+
+/// Append `code` to `ret`, which leaves its source map as is.
+pub(crate) fn append(ret: &mut CodegenReturn, code: &str) {
+    ret.source_text.push_str(code);
+}
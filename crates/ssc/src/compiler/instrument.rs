@@ -0,0 +1,33 @@
+#[cfg(feature = "instrument")]
+use std::time::Duration;
+
+/// A phase of the compiler pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    Parse,
+    Analyze,
+    Transform,
+    Codegen,
+}
+
+impl Phase {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Parse => "parse",
+            Self::Analyze => "analyze",
+            Self::Transform => "transform",
+            Self::Codegen => "codegen",
+        }
+    }
+}
+
+/// Receives the start and end of every phase of a compilation, e.g. to build
+/// per-file flame graphs, or to forward them as `tracing` spans.
+///
+/// Phases don't overlap and are reported in order, on the compiling thread.
+#[cfg(feature = "instrument")]
+pub trait Instrument {
+    fn enter(&self, _phase: Phase, _filename: &str) {}
+
+    fn exit(&self, _phase: Phase, _filename: &str, _elapsed: Duration) {}
+}
@@ -0,0 +1,1060 @@
+//! Compiler pipeline
+//!
+//! Runs parse → semantic analysis → template transform → css analyze → css
+//! transform → css codegen, producing only the artifacts requested through
+//! [`CompileOptions::emit`]. The template is lowered before the scripts are
+//! printed and it's analyzed for [`Hydration`] metadata, along with the server
+//! module of static components.
+
+#[cfg(feature = "cache")]
+pub mod cache;
+mod css_import;
+mod css_output;
+mod css_prune;
+mod hydration;
+mod inject;
+mod instrument;
+mod report;
+mod server;
+mod source_map;
+mod stats;
+mod warnings;
+
+use std::{
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use bitflags::bitflags;
+use oxc_allocator::Allocator;
+use oxc_diagnostics::{Error, Severity};
+use ssc_ast::ast::Root;
+use ssc_css_analyzer::Analyzer;
+use ssc_css_codegen::{Codegen, CodegenOptions, CodegenReturn};
+use ssc_css_transformer::Transformer;
+use ssc_linter::Linter;
+use ssc_parser::Parser;
+use ssc_semantic::SemanticBuilder;
+
+#[cfg(feature = "instrument")]
+pub use self::instrument::Instrument;
+pub use self::{
+    css_import::{ImportResolver, ResolvedImport},
+    css_output::CssOutputOptions,
+    hydration::{ClientFeature, Hydration},
+    instrument::Phase,
+    report::{diagnostics_json, diagnostics_sarif, FileDiagnostics},
+    source_map::{InputSourceMap, OriginalPosition},
+    stats::CompileStats,
+    warnings::{warning_code, WarningFilter, WarningLevel},
+};
+pub use ssc_css_transformer::Scoping;
+
+bitflags! {
+    /// Artifacts produced by the [`Compiler`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Emit: u8 {
+        /// The parsed component.
+        const Ast = 1 << 0;
+        /// The scoped stylesheet.
+        const Css = 1 << 1;
+        /// Source maps of the emitted CSS.
+        const Map = 1 << 2;
+        /// The [`Hydration`] metadata, telling whether the component needs
+        /// client code at all, and the server module of static components.
+        const Hydration = 1 << 3;
+        /// The scripts of the component, the module script first.
+        const Js = 1 << 4;
+    }
+}
+
+impl Default for Emit {
+    fn default() -> Self {
+        Self::Css | Self::Map
+    }
+}
+
+impl FromStr for Emit {
+    type Err = String;
+
+    /// Parses a comma separated list of artifacts, e.g. `css,map`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',').map(str::trim).filter(|name| !name.is_empty()).try_fold(
+            Self::empty(),
+            |emit, name| {
+                let kind = match name {
+                    "ast" => Self::Ast,
+                    "css" => Self::Css,
+                    "map" => Self::Map,
+                    "hydration" => Self::Hydration,
+                    "js" => Self::Js,
+                    _ => return Err(format!("Unknown emit kind `{name}`")),
+                };
+                Ok(emit | kind)
+            },
+        )
+    }
+}
+
+#[derive(Debug, Default, Clone, Hash)]
+pub struct CompileOptions {
+    /// Name of the compiled file, used as the source in source maps.
+    pub filename: String,
+
+    /// Which artifacts to produce.
+    /// Phases only needed by artifacts that aren't requested are skipped.
+    pub emit: Emit,
+
+    /// Force runes mode on or off, `None` infers it from the use of runes.
+    /// `<svelte:options runes>` takes precedence over it.
+    pub runes: Option<bool>,
+
+    /// Keep the whitespace of the template as written instead of collapsing
+    /// it in the generated code. `<svelte:options preserveWhitespace>` takes
+    /// precedence over it.
+    pub preserve_whitespace: bool,
+
+    /// Keep the HTML comments of the template in the generated code, rather
+    /// than removing them once the component is analyzed.
+    pub preserve_comments: bool,
+
+    /// Levels of warning codes, e.g. `("element_invalid_self_closing_tag",
+    /// WarningLevel::Off)`. Later entries take precedence.
+    pub warnings: Vec<(String, WarningLevel)>,
+
+    /// Source map of the compiled source to its original sources, as JSON,
+    /// e.g. from a preprocessor. Output source maps are composed with it.
+    pub sourcemap: Option<String>,
+
+    /// Remove the scoped style rules that can't match any element of the
+    /// template from the emitted CSS. `:global` rules are always kept.
+    pub remove_unused_css: bool,
+
+    /// How the styles are scoped to the component, see [`Scoping`].
+    pub css_scoping: Scoping,
+
+    /// Name, banner and import of the emitted CSS file.
+    pub css_output: CssOutputOptions,
+
+    /// Code prepended to every emitted code artifact, as is, e.g. a
+    /// `/* @generated */` marker. It must be valid in each of their languages.
+    pub banner: Option<String>,
+
+    /// Code appended to every emitted code artifact, as is.
+    pub footer: Option<String>,
+}
+
+pub struct CompileReturn<'a> {
+    /// Only present when [`Emit::Ast`] is requested.
+    /// Styles in it have already been scoped if [`Emit::Css`] was requested too,
+    /// and its template lowered by the [`ssc_transformer::Transformer`] if
+    /// [`Emit::Hydration`] or [`Emit::Js`] was.
+    pub ast: Option<Root<'a>>,
+
+    /// Only present when [`Emit::Css`] is requested and the component has a
+    /// `<style>` element.
+    pub css: Option<CodegenReturn>,
+
+    /// Name of the file of `css`, see [`CssOutputOptions::filename`].
+    pub css_filename: Option<String>,
+
+    /// Statement importing `css_filename`, when [`CssOutputOptions::import`]
+    /// is set.
+    pub css_import: Option<String>,
+
+    /// Only present when [`Emit::Hydration`] is requested.
+    pub hydration: Option<Hydration>,
+
+    /// The module rendering the markup of the component on the server, with
+    /// no client code, see [`Hydration::is_static`]. Only present when
+    /// [`Emit::Hydration`] is requested, the component is static and only
+    /// uses what the module can render, e.g. no slots.
+    pub server: Option<CodegenReturn>,
+
+    /// Only present when [`Emit::Js`] is requested and the component has a
+    /// script. `lang="ts"` scripts are printed as TypeScript, and scripts of
+    /// an unsupported `lang` are left out. It has no source map.
+    pub js: Option<CodegenReturn>,
+
+    pub errors: Vec<Error>,
+
+    pub stats: CompileStats,
+
+    /// The decoded [`CompileOptions::sourcemap`], to locate `errors` in the
+    /// original sources.
+    pub input_source_map: Option<InputSourceMap>,
+}
+
+pub struct Compiler<'a> {
+    allocator: &'a Allocator,
+    source_text: &'a str,
+    options: CompileOptions,
+    import_resolver: Option<&'a dyn ImportResolver>,
+    linter: Option<&'a Linter>,
+    warning_filter: Option<&'a dyn WarningFilter>,
+    #[cfg(feature = "instrument")]
+    instrument: Option<&'a dyn Instrument>,
+}
+
+impl<'a> Compiler<'a> {
+    pub fn new(allocator: &'a Allocator, source_text: &'a str, options: CompileOptions) -> Self {
+        Self {
+            allocator,
+            source_text,
+            options,
+            import_resolver: None,
+            linter: None,
+            warning_filter: None,
+            #[cfg(feature = "instrument")]
+            instrument: None,
+        }
+    }
+
+    /// Inline the stylesheets `@import`ed by the component's `<style>` that
+    /// `resolver` resolves to CSS source. Without a resolver every `@import`
+    /// is kept as is.
+    #[must_use]
+    pub fn with_import_resolver(mut self, resolver: &'a dyn ImportResolver) -> Self {
+        self.import_resolver = Some(resolver);
+        self
+    }
+
+    /// Run the rules of `linter` on the component, once it's analyzed. Their
+    /// messages are reported with the diagnostics of the compiler, without
+    /// their fixes.
+    #[must_use]
+    pub fn with_linter(mut self, linter: &'a Linter) -> Self {
+        self.linter = Some(linter);
+        self
+    }
+
+    /// Drop the warnings `filter` doesn't keep. It is called after the levels
+    /// of [`CompileOptions::warnings`] are applied, for the warnings left.
+    #[must_use]
+    pub fn with_warning_filter(mut self, filter: &'a dyn WarningFilter) -> Self {
+        self.warning_filter = Some(filter);
+        self
+    }
+
+    /// Report the start and end of every phase to `instrument`.
+    #[cfg(feature = "instrument")]
+    #[must_use]
+    pub fn with_instrument(mut self, instrument: &'a dyn Instrument) -> Self {
+        self.instrument = Some(instrument);
+        self
+    }
+
+    /// Run one phase of the pipeline, adding the time it takes to `elapsed`.
+    #[cfg_attr(not(feature = "instrument"), allow(unused_variables, clippy::unused_self))]
+    fn phase<T, F: FnOnce() -> T>(&self, phase: Phase, elapsed: &mut Duration, f: F) -> T {
+        #[cfg(feature = "instrument")]
+        if let Some(instrument) = self.instrument {
+            instrument.enter(phase, &self.options.filename);
+        }
+        let start = Instant::now();
+        let ret = f();
+        let phase_elapsed = start.elapsed();
+        *elapsed += phase_elapsed;
+        #[cfg(feature = "instrument")]
+        if let Some(instrument) = self.instrument {
+            instrument.exit(phase, &self.options.filename, phase_elapsed);
+        }
+        ret
+    }
+
+    /// Add [`CompileOptions::banner`] and [`CompileOptions::footer`] to `ret`.
+    fn inject(&self, ret: &mut CodegenReturn) {
+        if let Some(banner) = &self.options.banner {
+            inject::prepend(ret, banner);
+        }
+        if let Some(footer) = &self.options.footer {
+            inject::append(ret, footer);
+        }
+    }
+
+    fn configure_warnings(&self, errors: Vec<Error>) -> Vec<Error> {
+        warnings::apply_warning_config(errors, &self.options.warnings, self.warning_filter)
+    }
+
+    pub fn compile(self) -> CompileReturn<'a> {
+        let emit = self.options.emit;
+        let mut stats = CompileStats {
+            files: 1,
+            source_size: self.source_text.len(),
+            ..CompileStats::default()
+        };
+
+        let ret = self.phase(Phase::Parse, &mut stats.parse, || {
+            Parser::new(self.allocator, self.source_text).parse()
+        });
+
+        let mut errors = ret.errors.into_iter().map(Error::from).collect::<Vec<_>>();
+        let input_source_map = self.options.sourcemap.as_ref().and_then(|json| {
+            InputSourceMap::new(self.source_text, json)
+                .map_err(|error| errors.push(error.into()))
+                .ok()
+        });
+        if errors.iter().any(|error| error.severity() != Some(Severity::Warning)) {
+            let errors = self.configure_warnings(errors);
+            stats.count_diagnostics(&errors);
+            return CompileReturn {
+                ast: None,
+                css: None,
+                css_filename: None,
+                css_import: None,
+                hydration: None,
+                server: None,
+                js: None,
+                errors,
+                stats,
+                input_source_map,
+            };
+        }
+        let mut root = ret.root;
+        let ignores = root.svelte_ignores();
+
+        let runes = self.phase(Phase::Analyze, &mut stats.analyze, || {
+            let ret =
+                SemanticBuilder::new(self.source_text).with_runes(self.options.runes).build(&root);
+            errors.extend(ret.errors);
+            if let Some(linter) = self.linter {
+                let messages = linter.run(&root, &ret.semantic);
+                errors.extend(messages.into_iter().map(|message| message.error));
+            }
+            ret.semantic.is_runes()
+        });
+
+        // The template is lowered for the code generated from it, the AST
+        // alone is returned as written
+        if emit.intersects(Emit::Hydration | Emit::Js) {
+            self.phase(Phase::Transform, &mut stats.transform, || {
+                let mut transformer = ssc_transformer::Transformer::new(self.allocator)
+                    .with_preserve_whitespace(self.options.preserve_whitespace)
+                    .with_preserve_comments(self.options.preserve_comments)
+                    .with_runes(runes);
+                if !self.options.filename.is_empty() {
+                    transformer =
+                        transformer.with_filename(self.allocator.alloc_str(&self.options.filename));
+                }
+                transformer.build(&mut root);
+            });
+        }
+
+        let hydration = emit
+            .contains(Emit::Hydration)
+            .then(|| self.phase(Phase::Analyze, &mut stats.analyze, || Hydration::new(&root)));
+        let server = hydration
+            .as_ref()
+            .filter(|hydration| hydration.is_static())
+            .and_then(|_| {
+                self.phase(Phase::Codegen, &mut stats.codegen, || {
+                    server::server_module(&root, self.source_text)
+                })
+            })
+            .map(|source_text| {
+                let mut ret = CodegenReturn { source_text, source_map: None };
+                self.inject(&mut ret);
+                stats.output_size += ret.source_text.len();
+                ret
+            });
+
+        let js = emit.contains(Emit::Js).then(|| self.print_scripts(&root, &mut stats)).flatten();
+
+        let css = if emit.contains(Emit::Css) {
+            // Styles of an unsupported `lang` are left to the preprocessor
+            root.css.as_mut().filter(|style| style.raw.is_none()).map(|style| {
+                let stylesheet = &mut style.stylesheet;
+
+                let ret = self.phase(Phase::Analyze, &mut stats.analyze, || {
+                    if let Some(resolver) = self.import_resolver {
+                        let diagnostics =
+                            css_import::inline_imports(self.allocator, stylesheet, resolver);
+                        errors.extend(diagnostics.into_iter().map(Error::from));
+                    }
+                    Analyzer::new().build(stylesheet)
+                });
+                errors.extend(ret.errors);
+
+                self.phase(Phase::Transform, &mut stats.transform, || {
+                    if self.options.remove_unused_css {
+                        css_prune::mark_used_selectors(&root.fragment, stylesheet);
+                    }
+                    let hash = self.allocator.alloc_str(&ret.analysis.hash);
+                    Transformer::new(self.allocator, hash)
+                        .with_keyframes(ret.analysis.keyframes.clone())
+                        .with_remove_unused(self.options.remove_unused_css)
+                        .with_scoping(self.options.css_scoping)
+                        .build(stylesheet);
+                });
+
+                let mut ret = self.phase(Phase::Codegen, &mut stats.codegen, || {
+                    let options = CodegenOptions {
+                        enable_source_map: emit.contains(Emit::Map),
+                        ..CodegenOptions::default()
+                    };
+                    Codegen::<false>::new(&self.options.filename, self.source_text, options)
+                        .build(stylesheet)
+                });
+                if let (Some(input), Some(map)) = (&input_source_map, &ret.source_map) {
+                    ret.source_map = Some(input.compose(map));
+                }
+                self.options.css_output.add_banner(&mut ret);
+                self.inject(&mut ret);
+                stats.output_size += ret.source_text.len();
+                ret
+            })
+        } else {
+            None
+        };
+
+        // The parser, the semantic analysis and the linter filter their own
+        // diagnostics, the stylesheet's are left
+        errors.retain(|error| !ignores.is_error_ignored(error));
+        let ast = emit.contains(Emit::Ast).then_some(root);
+        let css_filename = css.as_ref().map(|css| {
+            self.options.css_output.css_filename(&self.options.filename, &css.source_text)
+        });
+        let css_import = css_filename
+            .as_deref()
+            .filter(|_| self.options.css_output.import)
+            .map(CssOutputOptions::import_statement);
+
+        let errors = self.configure_warnings(errors);
+        stats.count_diagnostics(&errors);
+        CompileReturn {
+            ast,
+            css,
+            css_filename,
+            css_import,
+            hydration,
+            server,
+            js,
+            errors,
+            stats,
+            input_source_map,
+        }
+    }
+
+    /// Print the module and instance scripts of `root`, one after the other.
+    fn print_scripts(&self, root: &Root<'a>, stats: &mut CompileStats) -> Option<CodegenReturn> {
+        let scripts = [root.module.as_ref(), root.instance.as_ref()];
+        let programs = scripts.into_iter().flatten().filter(|script| script.raw.is_none());
+        let source_text = self.phase(Phase::Codegen, &mut stats.codegen, || {
+            programs
+                .map(|script| {
+                    oxc_codegen::Codegen::<false>::new().build(&script.program).source_text
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        });
+        if source_text.is_empty() {
+            return None;
+        }
+        let mut ret = CodegenReturn { source_text, source_map: None };
+        self.inject(&mut ret);
+        stats.output_size += ret.source_text.len();
+        Some(ret)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ssc_ast::ast::{Element, FragmentNode};
+    use ssc_linter::LintConfig;
+
+    use super::*;
+
+    fn compile(source_text: &str, emit: Emit) -> (bool, Option<CodegenReturn>) {
+        let allocator = Allocator::default();
+        let options = CompileOptions { emit, ..CompileOptions::default() };
+        let ret = Compiler::new(&allocator, source_text, options).compile();
+        assert!(ret.errors.is_empty());
+        (ret.ast.is_some(), ret.css)
+    }
+
+    #[test]
+    fn selective_emit() {
+        let source = "<p>hi</p><style>p { color: red; }</style>";
+
+        let (ast, css) = compile(source, Emit::Ast);
+        assert!(ast);
+        assert!(css.is_none());
+
+        let (ast, css) = compile(source, Emit::Css);
+        assert!(!ast);
+        let css = css.unwrap();
+        assert!(css.source_text.contains(".svelte-"));
+        assert!(css.source_map.is_none());
+
+        let (_, css) = compile(source, Emit::Css | Emit::Map);
+        assert!(css.unwrap().source_map.is_some());
+
+        let allocator = Allocator::default();
+        let source =
+            "<script>let b = a;</script><script context=\"module\">export const a = 1;</script>";
+        let options = CompileOptions { emit: Emit::Js, ..CompileOptions::default() };
+        let ret = Compiler::new(&allocator, source, options).compile();
+        let js = ret.js.unwrap().source_text;
+        assert!(js.find("export const a = 1;").unwrap() < js.find("let b = a;").unwrap(), "{js}");
+        assert!(ret.css.is_none());
+        assert_eq!("css,js".parse::<Emit>(), Ok(Emit::Css | Emit::Js));
+    }
+
+    #[test]
+    fn stats() {
+        let allocator = Allocator::default();
+        let source = "<p>hi</p><style>p { color: red; }</style>";
+        let ret = Compiler::new(&allocator, source, CompileOptions::default()).compile();
+        let mut stats = ret.stats;
+        assert_eq!(stats.files, 1);
+        assert_eq!(stats.source_size, source.len());
+        assert_eq!(stats.output_size, ret.css.unwrap().source_text.len());
+
+        stats += ret.stats;
+        assert_eq!(stats.files, 2);
+        assert_eq!(stats.total(), ret.stats.total() * 2);
+    }
+
+    #[test]
+    fn css_imports() {
+        let resolver = |url: &str, importer: Option<&str>| match (url, importer) {
+            ("./button.css", None) => {
+                Ok(ResolvedImport::Inline("@import './base.css'; .button { color: red; }".into()))
+            }
+            ("./base.css", Some("./button.css")) => Ok(ResolvedImport::Inline(
+                "@import 'https://cdn/fonts.css'; p { margin: 0; }".into(),
+            )),
+            ("./loop.css", _) => Ok(ResolvedImport::Inline("@import './loop.css';".into())),
+            _ => Ok(ResolvedImport::External),
+        };
+        let allocator = Allocator::default();
+        let source =
+            "<p class=\"button\">hi</p><style>@import './button.css'; p { color: blue; }</style>";
+        let options = CompileOptions { emit: Emit::Css, ..CompileOptions::default() };
+        let ret = Compiler::new(&allocator, source, options.clone())
+            .with_import_resolver(&resolver)
+            .compile();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let css = ret.css.unwrap().source_text;
+        let fonts = css.find("@import 'https://cdn/fonts.css'").unwrap();
+        let base = css.find("p.svelte-").unwrap();
+        let button = css.find(".button.svelte-").unwrap();
+        assert!(fonts < base && base < button, "{css}");
+
+        let source = "<style>@import './loop.css';</style>";
+        let ret =
+            Compiler::new(&allocator, source, options).with_import_resolver(&resolver).compile();
+        assert_eq!(ret.errors.len(), 1);
+        assert!(ret.errors[0].to_string().contains("imports itself"));
+    }
+
+    #[test]
+    fn svelte_ignore() {
+        let allocator = Allocator::default();
+        let compile = |source| {
+            Compiler::new(&allocator, source, CompileOptions::default()).compile().errors.len()
+        };
+        assert_eq!(compile("<section><div /></section>"), 1);
+        assert_eq!(
+            compile("<!-- svelte-ignore element-invalid-self-closing-tag -->\n<section><div /></section>"),
+            0
+        );
+        assert_eq!(compile("<!-- svelte-ignore a11y_autofocus --><section><div /></section>"), 1);
+    }
+
+    #[test]
+    fn keyframes() {
+        let allocator = Allocator::default();
+        let source = "<style>
+            @keyframes fade {}
+            @-webkit-keyframes -global-spin {}
+            p { animation: fade 1s var(--fade), 2s -global-spin; }
+            div { animation-name: slide, fade; }
+        </style>";
+        let options = CompileOptions { emit: Emit::Css, ..CompileOptions::default() };
+        let ret = Compiler::new(&allocator, source, options).compile();
+        assert_eq!(ret.errors.len(), 1);
+        assert_eq!(warning_code(&ret.errors[0]), Some("css_unknown_keyframes"));
+        assert!(ret.errors[0].to_string().contains("@keyframes slide"));
+
+        let css = ret.css.unwrap().source_text;
+        let hash = &css[css.find("svelte-").unwrap()..][..13];
+        assert!(css.contains(&format!("@keyframes {hash}-fade")), "{css}");
+        assert!(css.contains("@-webkit-keyframes spin"), "{css}");
+        assert!(css.contains(&format!("{hash}-fade 1s var(--fade), 2s spin")), "{css}");
+        assert!(css.contains(&format!("slide, {hash}-fade")), "{css}");
+    }
+
+    #[test]
+    fn container_queries() {
+        let allocator = Allocator::default();
+        let source = "<style>
+            .card { container: card / inline-size; }
+            aside { container-name: sidebar main; }
+            @container card (min-width: 20rem) { p { font-size: 5cqi; } }
+            @container main (width > 40cqw) {}
+            @container style(--dark: 1) {}
+            @container (min-width: 10rem) {}
+            @container toolbar (min-width: 10rem) {}
+        </style>";
+        let options = CompileOptions { emit: Emit::Css, ..CompileOptions::default() };
+        let ret = Compiler::new(&allocator, source, options).compile();
+        assert_eq!(ret.errors.len(), 1, "{:?}", ret.errors);
+        assert_eq!(warning_code(&ret.errors[0]), Some("css_unknown_container"));
+        assert!(ret.errors[0].to_string().contains("`toolbar`"));
+    }
+
+    #[test]
+    fn remove_unused_css() {
+        let allocator = Allocator::default();
+        let source = r#"<p class="a {b}" id="x">hi</p><div {...props}></div><span class:on={c}></span>
+        <style>
+            h1 { color: red; }
+            .a, #y { color: red; } /* `div` may have any id */
+            p#x.z, div.anything { color: red; }
+            span.on, span.off { color: red; }
+            :global(h2) { color: red; }
+            @media print { h1 { color: red; } }
+            @keyframes fade { from { opacity: 0; } }
+        </style>"#;
+        let options = CompileOptions {
+            emit: Emit::Css,
+            remove_unused_css: true,
+            ..CompileOptions::default()
+        };
+        let css = Compiler::new(&allocator, source, options).compile().css.unwrap().source_text;
+        assert!(!css.contains("h1"), "{css}");
+        assert!(!css.contains("span.off"), "{css}");
+        assert!(!css.contains("@media"), "{css}");
+        for kept in [".a.svelte-", "#y", "p#x.z", "div.anything", "span.on", "h2", "from"] {
+            assert!(css.contains(kept), "{kept} in {css}");
+        }
+    }
+
+    #[test]
+    fn where_scoping() {
+        let allocator = Allocator::default();
+        let source = "<p>hi</p><style>p .a, :global(div) span { color: red; }</style>";
+        let options = CompileOptions {
+            emit: Emit::Css,
+            css_scoping: Scoping::Where,
+            ..CompileOptions::default()
+        };
+        let css = Compiler::new(&allocator, source, options).compile().css.unwrap().source_text;
+        let hash = &css[css.find("svelte-").unwrap()..][..13];
+        assert!(
+            css.starts_with(&format!(
+                "p:where(.{hash}) .a:where(.{hash}), div span:where(.{hash})"
+            )),
+            "{css}"
+        );
+    }
+
+    #[test]
+    fn css_output() {
+        let allocator = Allocator::default();
+        let source = "<p>hi</p><style>p { color: red; }</style>";
+        let options = CompileOptions {
+            filename: "src/App.svelte".into(),
+            css_output: CssOutputOptions {
+                filename: "[name]-[hash].css".into(),
+                banner: Some("(c) */ Acme\nMIT".into()),
+                import: true,
+            },
+            ..CompileOptions::default()
+        };
+        let ret = Compiler::new(&allocator, source, options.clone()).compile();
+        let css = ret.css.unwrap();
+        assert!(css.source_text.starts_with("/* (c) *\\/ Acme\nMIT */\np.svelte-"));
+        let map = css.source_map.unwrap();
+        assert!(map.get_tokens().all(|token| token.get_dst_line() >= 2));
+
+        let filename = ret.css_filename.unwrap();
+        let hash = filename.strip_prefix("App-").and_then(|rest| rest.strip_suffix(".css"));
+        assert!(hash.is_some_and(|hash| !hash.is_empty()), "{filename}");
+        assert_eq!(ret.css_import.unwrap(), format!("import \"./{filename}\";"));
+        // The hash only depends on the output
+        let ret = Compiler::new(&allocator, source, options).compile();
+        assert_eq!(ret.css_filename.unwrap(), filename);
+
+        let ret = Compiler::new(&allocator, source, CompileOptions::default()).compile();
+        assert_eq!(ret.css_filename.as_deref(), Some(".css"));
+        assert!(ret.css_import.is_none());
+    }
+
+    #[test]
+    fn banner_footer() {
+        let allocator = Allocator::default();
+        let source = "<p>hi</p><style>p { color: red; }</style>";
+        let compile = |banner: &str| {
+            let options = CompileOptions {
+                banner: Some(banner.into()),
+                footer: Some("\n/* end */".into()),
+                ..CompileOptions::default()
+            };
+            Compiler::new(&allocator, source, options).compile().css.unwrap()
+        };
+        let css = compile("/* @generated */\n");
+        assert!(css.source_text.starts_with("/* @generated */\np.svelte-"), "{}", css.source_text);
+        assert!(css.source_text.ends_with("}\n/* end */"), "{}", css.source_text);
+        let map = css.source_map.unwrap();
+        assert!(map.get_tokens().all(|token| token.get_dst_line() >= 1));
+
+        // Code on the same line as the banner is shifted right
+        let css = compile("/*!*/");
+        let map = css.source_map.unwrap();
+        let first = map.get_tokens().next().unwrap();
+        assert_eq!((first.get_dst_line(), first.get_dst_col()), (0, 5));
+    }
+
+    #[test]
+    fn input_source_map() {
+        let allocator = Allocator::default();
+        let source = "<p>hi</p><style>p { animation: fade 1s; }</style>";
+        // Each line of `source` comes from two lines further down the
+        // original source
+        let sourcemap = r#"{"version":3,"sources":["App.pug"],"sourcesContent":["//\n//\n"],"names":[],"mappings":"AAEA"}"#;
+        let options = CompileOptions {
+            filename: "App.svelte".into(),
+            sourcemap: Some(sourcemap.into()),
+            ..CompileOptions::default()
+        };
+        let ret = Compiler::new(&allocator, source, options).compile();
+        let input = ret.input_source_map.as_ref().unwrap();
+        assert_eq!(ret.errors.len(), 1);
+        let position = input.diagnostic_position(&ret.errors[0]).unwrap();
+        let column = u32::try_from(source.find("animation").unwrap()).unwrap();
+        assert_eq!(position, OriginalPosition { source: Some("App.pug".into()), line: 2, column });
+
+        let map = ret.css.unwrap().source_map.unwrap();
+        assert_eq!(map.get_sources().collect::<Vec<_>>(), ["App.pug"]);
+        assert!(map.get_tokens().all(|token| token.get_src_line() == 2));
+
+        let options = CompileOptions { sourcemap: Some("{".into()), ..CompileOptions::default() };
+        let ret = Compiler::new(&allocator, source, options).compile();
+        assert!(ret.input_source_map.is_none());
+        assert!(ret.errors[0].to_string().starts_with("Invalid input source map"));
+    }
+
+    #[cfg(feature = "instrument")]
+    #[test]
+    fn instrument() {
+        use std::cell::RefCell;
+
+        #[derive(Default)]
+        struct Recorder(RefCell<Vec<String>>);
+
+        impl Instrument for Recorder {
+            fn enter(&self, phase: Phase, filename: &str) {
+                self.0.borrow_mut().push(format!("enter {} {filename}", phase.as_str()));
+            }
+
+            fn exit(&self, phase: Phase, _filename: &str, _elapsed: Duration) {
+                self.0.borrow_mut().push(format!("exit {}", phase.as_str()));
+            }
+        }
+
+        let allocator = Allocator::default();
+        let recorder = Recorder::default();
+        let options = CompileOptions { filename: "App.svelte".into(), ..CompileOptions::default() };
+        Compiler::new(&allocator, "<style>p { color: red; }</style>", options)
+            .with_instrument(&recorder)
+            .compile();
+        assert_eq!(
+            recorder.0.into_inner(),
+            [
+                "enter parse App.svelte",
+                "exit parse",
+                "enter analyze App.svelte",
+                "exit analyze",
+                "enter analyze App.svelte",
+                "exit analyze",
+                "enter transform App.svelte",
+                "exit transform",
+                "enter codegen App.svelte",
+                "exit codegen",
+            ]
+        );
+    }
+
+    #[test]
+    fn warning_config() {
+        let allocator = Allocator::default();
+        let source = r#"<div /><p style:color|important|important="red"></p>"#;
+        let compile = |warnings: Vec<(String, WarningLevel)>| {
+            let options = CompileOptions { warnings, ..CompileOptions::default() };
+            Compiler::new(&allocator, source, options).compile()
+        };
+
+        let ret = compile(vec![]);
+        assert_eq!(ret.stats.warnings, 2);
+        let codes = ret.errors.iter().map(warning_code).collect::<Vec<_>>();
+        assert_eq!(codes, [Some("element_invalid_self_closing_tag"), Some("duplicate_modifier")]);
+
+        let ret = compile(vec![
+            ("element_invalid_self_closing_tag".into(), WarningLevel::Off),
+            ("duplicate_modifier".into(), WarningLevel::Error),
+        ]);
+        assert_eq!(ret.errors.len(), 1);
+        assert_eq!((ret.stats.warnings, ret.stats.errors), (0, 1));
+
+        let ret = compile(vec![("duplicate_modifier".into(), WarningLevel::Advice)]);
+        assert_eq!((ret.stats.warnings, ret.stats.errors), (1, 0));
+        assert_eq!(ret.errors[1].severity(), Some(Severity::Advice));
+
+        let filter = |code: Option<&str>, _: &Error| code != Some("duplicate_modifier");
+        let ret = Compiler::new(&allocator, source, CompileOptions::default())
+            .with_warning_filter(&filter)
+            .compile();
+        assert_eq!(ret.errors.len(), 1);
+        assert_eq!(warning_code(&ret.errors[0]), Some("element_invalid_self_closing_tag"));
+
+        // Warnings of the semantic analysis and of the lint rules too
+        let source = "{#snippet row()}<td></td>{/snippet}{@html html}";
+        let linter = Linter::new(LintConfig::default());
+        let compile = |warnings: Vec<(String, WarningLevel)>| {
+            let options = CompileOptions { warnings, ..CompileOptions::default() };
+            Compiler::new(&allocator, source, options).with_linter(&linter).compile()
+        };
+        let ret = compile(vec![]);
+        let codes = ret.errors.iter().map(warning_code).collect::<Vec<_>>();
+        assert_eq!(codes, [Some("unused_snippet"), Some("no_at_html_tags")]);
+        let ret = compile(vec![
+            ("unused_snippet".into(), WarningLevel::Error),
+            ("no_at_html_tags".into(), WarningLevel::Off),
+        ]);
+        assert_eq!(ret.errors.len(), 1);
+        assert_eq!((ret.stats.warnings, ret.stats.errors), (0, 1));
+    }
+
+    #[test]
+    fn hydration() {
+        let allocator = Allocator::default();
+        let hydration = |source: &str| {
+            let options = CompileOptions { emit: Emit::Hydration, ..CompileOptions::default() };
+            Compiler::new(&allocator, allocator.alloc_str(source), options)
+                .compile()
+                .hydration
+                .unwrap()
+        };
+
+        let ret = hydration(
+            r#"<script>import Card from "./Card.svelte"; let { title } = $props(); const n = 1;</script>
+<h1 class:big={n > 0}>{title}</h1><Card {title} onclick={() => {}} /><Card />"#,
+        );
+        assert!(ret.is_static());
+        assert_eq!(ret.components, ["./Card.svelte"]);
+        assert_eq!(
+            ret.to_json(),
+            r#"{"static":true,"features":[],"components":["./Card.svelte"]}"#
+        );
+
+        let features = |source: &str| {
+            hydration(source).features.into_iter().map(|(feature, _)| feature).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            features("<button on:click={f}></button><input bind:value={v} /><p onclick={f}></p>"),
+            [ClientFeature::EventHandler, ClientFeature::Binding, ClientFeature::EventHandler]
+        );
+        assert_eq!(
+            features("<script>let a = $state(0); $effect(() => {});</script>"),
+            [ClientFeature::State, ClientFeature::Effect]
+        );
+        assert_eq!(
+            features(
+                "<script>import { onMount } from 'svelte'; let count = 0; setTimeout(() => count++);</script>{$store}"
+            ),
+            [ClientFeature::Lifecycle, ClientFeature::State, ClientFeature::Store]
+        );
+        assert!(features("<script>let count = 0; count++;</script>").is_empty());
+        assert_eq!(features("{#await promise then value}{value}{/await}"), [ClientFeature::Await]);
+    }
+
+    #[test]
+    fn server_module() {
+        let allocator = Allocator::default();
+        let server = |source: &str| {
+            let options = CompileOptions { emit: Emit::Hydration, ..CompileOptions::default() };
+            let ret = Compiler::new(&allocator, allocator.alloc_str(source), options).compile();
+            ret.server.map(|server| server.source_text)
+        };
+
+        let module = server(
+            r#"<script>import Card from "./Card.svelte"; let { title, items = [] } = $props();</script>
+<h1 class="title" class:big={items.length > 0}>{title}</h1>{#each items as item, i}<Card {item} index={i} />{:else}<p>none</p>{/each}"#,
+        )
+        .unwrap();
+        assert!(module.starts_with("import Card from \"./Card.svelte\";\n"), "{module}");
+        assert!(module.contains("\tlet { title, items = [] } = $$props;\n"), "{module}");
+        assert!(
+            module.contains(
+                r#"<h1${$$attr("class", $$class("title", (items.length > 0) && "big"), false)}>${$$escape(title)}</h1>${$$each(items, (item, i) => `${Card({ "item": item, "index": i })}`, () => `<p>none</p>`)}`;"#
+            ),
+            "{module}"
+        );
+
+        let module = server("<script>export let name = 'world';</script><p>Hello {name}!</p><style>p { color: red; }</style>").unwrap();
+        assert!(module.contains("\tlet { name = 'world' } = $$props;\n"), "{module}");
+        assert!(module.contains(r#"return `<p class="svelte-"#), "{module}");
+
+        let module = server(
+            r#"<script>let { b } = $props();</script><p title="a &amp; b" alt="&quot;{b}&quot;" class="x &lt; y">&copy; `${b}`</p>"#,
+        )
+        .unwrap();
+        assert!(
+            module.contains(
+                r#"return `<p title="a &amp; b"${$$attr("alt", ["\"", b, "\""].join(""), false)} class="x &lt; y">© \`$${$$escape(b)}\`</p>`;"#
+            ),
+            "{module}"
+        );
+
+        let module = server(
+            r#"{#snippet row(item)}<li>{item}</li>{/snippet}{#each items as item}{@const double = item * 2}{@render row(double)}{/each}{@render footer?.()}"#,
+        )
+        .unwrap();
+        assert!(
+            module.contains("\tfunction row(item) { return `<li>${$$escape(item)}</li>`; }\n"),
+            "{module}"
+        );
+        assert!(
+            module.contains(
+                r#"return `${$$each(items, (item) => (() => { const double = item * 2; return `${row(double)}`; })())}${footer?.() ?? ""}`;"#
+            ),
+            "{module}"
+        );
+
+        let module = server(
+            r#"<script>import Card from "./Card.svelte";</script><Card title="a">{#snippet header()}<h2>a</h2>{/snippet}<p>body</p></Card>"#,
+        )
+        .unwrap();
+        assert!(
+            module.contains(
+                r#"${Card({ "title": "a", "header": function header() { return `<h2>a</h2>`; }, "children": function children() { return `<p>body</p>`; } })}"#
+            ),
+            "{module}"
+        );
+
+        let module = server(
+            r#"{#key k}<p style="margin: 0" style:color={c} style:width|important="1px">a</p>{/key}<svelte:boundary><b>b</b>{#snippet failed()}x{/snippet}</svelte:boundary>"#,
+        )
+        .unwrap();
+        assert!(
+            module.contains(
+                r#"return `<p${$$attr("style", $$style("margin: 0", [["color", c, false], ["width", "1px", true]]), false)}>a</p><b>b</b>`;"#
+            ),
+            "{module}"
+        );
+
+        assert!(server("<button on:click={f}>+</button>").is_none());
+        assert!(server("<div><slot /></div>").is_none());
+
+        // What makes a component interactive, rendered as it is on the server
+        let render = |source: &str| {
+            let ret = Parser::new(&allocator, allocator.alloc_str(source)).parse();
+            server::server_module(&ret.root, source).unwrap()
+        };
+        let module = render(
+            r#"{#await promise}<p>...</p>{:then { value }}<p>{value}</p>{:catch}<p>error</p>{/await}{#await promise then value}{value}{/await}"#,
+        );
+        assert!(
+            module.contains(
+                r#"return `${$$await(promise, () => `<p>...</p>`, ({ value }) => `<p>${$$escape(value)}</p>`)}${$$await(promise, null, (value) => `${$$escape(value)}`)}`;"#
+            ),
+            "{module}"
+        );
+        let module = render(
+            r#"<input bind:value={name} on:input={f} use:a transition:fade><textarea bind:value={text}></textarea><input type="checkbox" value="a" bind:group={selected}><svelte:component this={View} bind:open {...rest} />"#,
+        );
+        assert!(
+            module.contains(
+                r#"return `<input${$$attr("value", name, false)}><textarea>${$$escape(text)}</textarea><input type="checkbox" value="a"${$$attr("checked", selected.includes("a"), true)}>${$$component(View, { "open": open, ...rest })}`;"#
+            ),
+            "{module}"
+        );
+    }
+
+    #[test]
+    fn template_transform() {
+        let allocator = Allocator::default();
+        let source = "<script>let { depth } = $props();</script>\n<ul>\n\t<li>  a  </li>\n</ul>\n{#key 1}<p>b</p>{/key}{#if depth}<svelte:self depth={depth - 1} />{/if}";
+        let options = CompileOptions {
+            filename: "src/lib/Tree.svelte".into(),
+            emit: Emit::Hydration | Emit::Js,
+            ..CompileOptions::default()
+        };
+        let ret = Compiler::new(&allocator, source, options).compile();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        assert!(ret.js.unwrap().source_text.contains("import Tree from"));
+        let module = ret.server.unwrap().source_text;
+        assert!(module.starts_with("import Tree from \"./Tree.svelte\";\n"), "{module}");
+        assert!(
+            module.contains(
+                r#"return `<ul><li>a</li></ul> <p>b</p>${depth ? `${Tree({ "depth": depth - 1 })}` : ""}`;"#
+            ),
+            "{module}"
+        );
+
+        let options = CompileOptions {
+            emit: Emit::Hydration,
+            preserve_whitespace: true,
+            ..CompileOptions::default()
+        };
+        let module = Compiler::new(&allocator, source, options).compile().server.unwrap();
+        assert!(
+            module.source_text.contains("return `\n<ul>\n\t<li>  a  </li>\n</ul>\n<p>b</p>"),
+            "{}",
+            module.source_text
+        );
+
+        let options = CompileOptions { emit: Emit::Hydration, ..CompileOptions::default() };
+        let html = r#"{@html "<b title='a'>a &amp; b</b>"}"#;
+        let module = Compiler::new(&allocator, html, options).compile().server.unwrap();
+        assert!(
+            module.source_text.contains(r#"return `<b title="a">a &amp; b</b>`;"#),
+            "{}",
+            module.source_text
+        );
+
+        let comments = |preserve_comments| {
+            let options = CompileOptions {
+                emit: Emit::Hydration,
+                preserve_comments,
+                ..CompileOptions::default()
+            };
+            let source = "<p>a <!-- b --> c</p>";
+            Compiler::new(&allocator, source, options).compile().server.unwrap().source_text
+        };
+        assert!(comments(true).contains("return `<p>a <!-- b --> c</p>`;"));
+        assert!(comments(false).contains("return `<p>a c</p>`;"));
+
+        // The AST alone is left as written
+        let options = CompileOptions { emit: Emit::Ast, ..CompileOptions::default() };
+        let ast = Compiler::new(&allocator, source, options).compile().ast.unwrap();
+        assert!(matches!(
+            ast.fragment.nodes.iter().find(|node| !matches!(node, FragmentNode::Text(_))),
+            Some(FragmentNode::Element(Element::RegularElement(ul))) if ul.fragment.nodes.len() == 3
+        ));
+    }
+
+    #[test]
+    fn runes() {
+        let allocator = Allocator::default();
+        let source = "<script>export let a;</script>{a}";
+        let compile = |runes| {
+            let options = CompileOptions { runes, ..CompileOptions::default() };
+            Compiler::new(&allocator, source, options).compile().errors
+        };
+        assert!(compile(None).is_empty());
+        assert!(compile(Some(false)).is_empty());
+        let errors = compile(Some(true));
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert_eq!(errors[0].severity(), Some(Severity::Error));
+    }
+
+    #[test]
+    fn parse_emit() {
+        assert_eq!("css, map".parse::<Emit>(), Ok(Emit::Css | Emit::Map));
+        assert_eq!("".parse::<Emit>(), Ok(Emit::empty()));
+        assert!("wasm".parse::<Emit>().is_err());
+    }
+}
@@ -0,0 +1,237 @@
+//! Machine readable diagnostics
+//!
+//! Renders the diagnostics of compiled or linted files as JSON, for scripts,
+//! or as [SARIF](https://sarifweb.azurewebsites.net), for code scanning
+//! services. Warning codes, see [`warning_code`], are used as rule ids, and
+//! the fixes of the messages as SARIF fixes.
+
+use oxc_diagnostics::{Error, Severity};
+use oxc_span::Span;
+use serde_json::{json, Value};
+use ssc_line_index::{Encoding, LineIndex};
+use ssc_linter::{Fix, Message};
+
+use super::warning_code;
+
+/// The diagnostics reported for a file.
+#[derive(Debug, Clone, Copy)]
+pub struct FileDiagnostics<'a> {
+    pub filename: &'a str,
+    pub source_text: &'a str,
+    pub messages: &'a [Message],
+}
+
+/// A range of the source text.
+struct Location {
+    start: usize,
+    end: usize,
+    /// 1-based line and column, in characters, of `start` and `end`.
+    start_position: (usize, usize),
+    end_position: (usize, usize),
+}
+
+impl Location {
+    /// The location of the primary label of `error`.
+    fn new(error: &Error, source_text: &str, line_index: &LineIndex) -> Option<Self> {
+        let label = error.labels()?.next()?;
+        Some(Self::of_range(label.offset(), label.offset() + label.len(), source_text, line_index))
+    }
+
+    fn of_span(span: Span, source_text: &str, line_index: &LineIndex) -> Self {
+        Self::of_range(span.start as usize, span.end as usize, source_text, line_index)
+    }
+
+    fn of_range(start: usize, end: usize, source_text: &str, line_index: &LineIndex) -> Self {
+        let start = start.min(source_text.len());
+        let end = end.min(source_text.len());
+        Self {
+            start,
+            end,
+            start_position: position(line_index, start),
+            end_position: position(line_index, end),
+        }
+    }
+
+    /// The location as a SARIF region, its columns in Unicode code points.
+    fn sarif_region(&self, source_text: &str) -> Value {
+        json!({
+            "startLine": self.start_position.0,
+            "startColumn": self.start_position.1,
+            "endLine": self.end_position.0,
+            "endColumn": self.end_position.1,
+            "charOffset": source_text[..self.start].chars().count(),
+            "charLength": source_text[self.start..self.end].chars().count(),
+        })
+    }
+}
+
+fn position(line_index: &LineIndex, offset: usize) -> (usize, usize) {
+    let position = line_index.line_col(u32::try_from(offset).unwrap_or(u32::MAX), Encoding::Utf32);
+    (position.line as usize + 1, position.column as usize + 1)
+}
+
+fn severity(error: &Error) -> &'static str {
+    match error.severity() {
+        Some(Severity::Warning) => "warning",
+        Some(Severity::Advice) => "advice",
+        Some(Severity::Error) | None => "error",
+    }
+}
+
+/// The diagnostics of `files` as a JSON array, with one object per
+/// diagnostic, e.g.
+/// `{"file":"App.svelte","code":"duplicate_modifier","severity":"warning",
+/// "message":"...","help":null,"start":3,"end":12,"line":1,"column":4,
+/// "fix":null}`.
+///
+/// Location fields are `null` for diagnostics without a label, and `fix` is
+/// `{"start":15,"end":17,"content":"..."}` for diagnostics with a fix.
+pub fn diagnostics_json(files: &[FileDiagnostics]) -> String {
+    let mut entries = vec![];
+    for file in files {
+        let line_index = LineIndex::new(file.source_text);
+        for Message { error, fix } in file.messages {
+            let location = Location::new(error, file.source_text, &line_index);
+            let location = location.as_ref();
+            entries.push(json!({
+                "file": file.filename,
+                "code": warning_code(error),
+                "severity": severity(error),
+                "message": error.to_string(),
+                "help": error.help().map(|help| help.to_string()),
+                "start": location.map(|location| location.start),
+                "end": location.map(|location| location.end),
+                "line": location.map(|location| location.start_position.0),
+                "column": location.map(|location| location.start_position.1),
+                "fix": fix.as_ref().map(|fix| json!({
+                    "start": fix.span.start,
+                    "end": fix.span.end,
+                    "content": fix.content,
+                })),
+            }));
+        }
+    }
+    Value::Array(entries).to_string()
+}
+
+/// The diagnostics of `files` as a SARIF 2.1.0 log with a single run.
+/// Diagnostics without a warning code use the `ssc` rule id.
+pub fn diagnostics_sarif(files: &[FileDiagnostics]) -> String {
+    let mut rules: Vec<&str> = vec![];
+    let mut results = vec![];
+    for file in files {
+        let line_index = LineIndex::new(file.source_text);
+        for Message { error, fix } in file.messages {
+            let rule_id = warning_code(error).unwrap_or("ssc");
+            if !rules.contains(&rule_id) {
+                rules.push(rule_id);
+            }
+            let level = match error.severity() {
+                Some(Severity::Warning) => "warning",
+                Some(Severity::Advice) => "note",
+                Some(Severity::Error) | None => "error",
+            };
+            let mut physical_location = json!({ "artifactLocation": { "uri": file.filename } });
+            if let Some(location) = Location::new(error, file.source_text, &line_index) {
+                physical_location["region"] = location.sarif_region(file.source_text);
+            }
+            let mut result = json!({
+                "ruleId": rule_id,
+                "level": level,
+                "message": { "text": error.to_string() },
+                "locations": [{ "physicalLocation": physical_location }],
+            });
+            if let Some(fix) = fix {
+                result["fixes"] = json!([sarif_fix(fix, file, &line_index)]);
+            }
+            results.push(result);
+        }
+    }
+    json!({
+        "version": "2.1.0",
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "ssc",
+                    "informationUri": "https://github.com/ssc-project/ssc",
+                    "rules": rules.iter().map(|id| json!({ "id": id })).collect::<Vec<_>>(),
+                },
+            },
+            "columnKind": "unicodeCodePoints",
+            "results": results,
+        }],
+    })
+    .to_string()
+}
+
+/// `fix` as a SARIF fix, replacing its span in the artifact of `file`.
+fn sarif_fix(fix: &Fix, file: &FileDiagnostics, line_index: &LineIndex) -> Value {
+    let region = Location::of_span(fix.span, file.source_text, line_index);
+    json!({
+        "artifactChanges": [{
+            "artifactLocation": { "uri": file.filename },
+            "replacements": [{
+                "deletedRegion": region.sarif_region(file.source_text),
+                "insertedContent": { "text": fix.content },
+            }],
+        }],
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_diagnostics::OxcDiagnostic;
+    use serde_json::Value;
+
+    use super::*;
+    use crate::compiler::{CompileOptions, Compiler};
+
+    #[test]
+    fn reports() {
+        let allocator = Allocator::default();
+        let source_text = "<p>hi</p>\n<div />";
+        let ret = Compiler::new(&allocator, source_text, CompileOptions::default()).compile();
+        let mut messages = ret.errors.into_iter().map(Message::from).collect::<Vec<_>>();
+        messages.push(Message::new(
+            OxcDiagnostic::warn("Self-closing")
+                .with_error_code("svelte", "self_closing_tags")
+                .with_label(Span::new(10, 17))
+                .into(),
+            Some(Fix::replace(Span::new(14, 17), "></div>")),
+        ));
+        let files = [FileDiagnostics { filename: "App.svelte", source_text, messages: &messages }];
+
+        let json = serde_json::from_str::<Value>(&diagnostics_json(&files)).unwrap();
+        assert_eq!(json[0]["file"], "App.svelte");
+        assert_eq!(json[0]["code"], "element_invalid_self_closing_tag");
+        assert_eq!(json[0]["severity"], "warning");
+        assert!(json[0]["message"].as_str().unwrap().starts_with("Self-closing"));
+        assert_eq!(json[0]["start"], 10);
+        assert_eq!(json[0]["end"], 17);
+        assert_eq!(json[0]["line"], 2);
+        assert_eq!(json[0]["column"], 1);
+        assert_eq!(json[0]["fix"], Value::Null);
+        assert_eq!(json[1]["fix"], serde_json::json!({"start":14,"end":17,"content":"></div>"}));
+
+        let sarif = diagnostics_sarif(&files);
+        let sarif = serde_json::from_str::<Value>(&sarif).unwrap();
+        let run = &sarif["runs"][0];
+        assert_eq!(run["columnKind"], "unicodeCodePoints");
+        assert_eq!(
+            run["tool"]["driver"]["rules"],
+            serde_json::json!([{"id":"element_invalid_self_closing_tag"},{"id":"self_closing_tags"}])
+        );
+        assert_eq!(
+            run["results"][0]["locations"][0]["physicalLocation"]["region"],
+            serde_json::json!({"startLine":2,"startColumn":1,"endLine":2,"endColumn":8,"charOffset":10,"charLength":7})
+        );
+        // Only the fixes carried by the messages are reported
+        assert_eq!(run["results"][0].get("fixes"), None);
+        assert_eq!(
+            run["results"][1]["fixes"],
+            serde_json::json!([{"artifactChanges":[{"artifactLocation":{"uri":"App.svelte"},"replacements":[{"deletedRegion":{"startLine":2,"startColumn":5,"endLine":2,"endColumn":8,"charOffset":14,"charLength":3},"insertedContent":{"text":"></div>"}}]}]}])
+        );
+    }
+}
@@ -0,0 +1,740 @@
+//! Server-only output
+//!
+//! A [static](super::Hydration::is_static) component ships no client code,
+//! its markup only has to be rendered on the server. Its server module renders
+//! it without a runtime: the default export takes the props and returns the
+//! HTML, e.g. `<h1>{title}</h1>` becomes
+//!
+//! ```js
+//! export default function render($$props = {}) {
+//! 	let { title } = $$props;
+//! 	return `<h1>${$$escape(title)}</h1>`;
+//! }
+//! ```
+//!
+//! Child components are rendered by calling the default export of their
+//! import, which the bundler resolves to their own server module, with the
+//! snippets passed to them as functions returning their HTML. Components
+//! using what the module can't render yet, e.g. slots or `<svelte:head>`,
+//! have none.
+
+use std::borrow::Cow;
+
+use oxc_ast::{
+    ast::{
+        BindingPatternKind, CallExpression, Declaration, EmptyStatement, Expression,
+        IdentifierReference, ImportDeclarationSpecifier, Statement, VariableDeclarationKind,
+    },
+    visit::walk,
+    Visit,
+};
+use oxc_span::{GetSpan, Span};
+#[allow(clippy::wildcard_imports)]
+use ssc_ast::{
+    ast::*,
+    html::{self, TemplateString},
+};
+
+/// Helpers of the module, escaping the way the Svelte runtime does, like
+/// [`html::escape_text`] and [`html::escape_attribute`] at compile time.
+const HELPERS: &str = r#"const $$escape = (value, attribute) =>
+	String(value ?? "").replace(attribute ? /[&"<]/g : /[&<]/g, (ch) =>
+		ch === "&" ? "&amp;" : ch === "<" ? "&lt;" : "&quot;",
+	);
+const $$attr = (name, value, boolean) =>
+	value == null || (boolean && !value) ? "" : boolean ? ` ${name}` : ` ${name}="${$$escape(value, true)}"`;
+const $$spread = (attributes) =>
+	Object.entries(attributes ?? {})
+		.map(([name, value]) => (typeof value === "function" ? "" : $$attr(name, value, false)))
+		.join("");
+const $$class = (...classes) => classes.filter(Boolean).join(" ");
+const $$style = (style, directives) =>
+	[
+		String(style ?? "").trim().replace(/;?$/, ";"),
+		...directives
+			.filter(([, value]) => value != null)
+			.map(([name, value, important]) => `${name}: ${value}${important ? " !important" : ""};`),
+	]
+		.filter((declaration) => declaration !== ";")
+		.join(" ") || null;
+const $$each = (items, render, fallback) => {
+	const array = Array.from(items ?? []);
+	return array.length > 0 ? array.map(render).join("") : fallback ? fallback() : "";
+};
+const $$await = (value, pending, then) =>
+	typeof value?.then === "function" ? (pending ? pending() : "") : then ? then(value) : "";
+const $$component = (component, props) => (component ? component(props) : "");
+"#;
+
+/// The server module of `root`, if its scripts and template only use what it
+/// can render. `root` must be static.
+pub fn server_module(root: &Root<'_>, source_text: &str) -> Option<String> {
+    let scripts = [&root.module, &root.instance];
+    if root.ts || scripts.into_iter().flatten().any(|script| script.raw.is_some()) {
+        return None;
+    }
+
+    let mut module = String::new();
+    if let Some(script) = &root.module {
+        if let (Some(first), Some(last)) = (script.program.body.first(), script.program.body.last())
+        {
+            module.push_str(&source_text[first.span().start as usize..last.span().end as usize]);
+            module.push('\n');
+        }
+    }
+
+    let mut statements = vec![];
+    if let Some(script) = &root.instance {
+        let mut rewriter = ScriptRewriter { source_text, edits: vec![], supported: true };
+        rewriter.visit_program(&script.program);
+        if !rewriter.supported {
+            return None;
+        }
+        rewriter.edits.sort_by_key(|(span, _)| (span.start, span.end));
+        for statement in &script.program.body {
+            match statement {
+                Statement::ImportDeclaration(import) if import.span.is_unspanned() => {
+                    // Added by the transformer, which imports the component
+                    // itself for a lowered `<svelte:self>`
+                    let specifiers = import.specifiers.iter().flatten().collect::<Vec<_>>();
+                    let [ImportDeclarationSpecifier::ImportDefaultSpecifier(default)] =
+                        specifiers[..]
+                    else {
+                        return None;
+                    };
+                    module.push_str(&format!(
+                        "import {} from {};\n",
+                        default.local.name,
+                        string_literal(import.source.value.as_str())
+                    ));
+                }
+                Statement::ImportDeclaration(import) => {
+                    module.push_str(rewriter.source(import.span));
+                    module.push('\n');
+                }
+                Statement::ExportNamedDeclaration(export) => {
+                    let Some(Declaration::VariableDeclaration(declaration)) = &export.declaration
+                    else {
+                        return None;
+                    };
+                    if declaration.kind == VariableDeclarationKind::Const {
+                        return None;
+                    }
+                    let mut props = vec![];
+                    for declarator in &declaration.declarations {
+                        let BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind
+                        else {
+                            return None;
+                        };
+                        props.push(match &declarator.init {
+                            Some(init) => {
+                                format!("{} = {}", ident.name, rewriter.rewrite(init.span()))
+                            }
+                            None => ident.name.to_string(),
+                        });
+                    }
+                    statements.push(format!("let {{ {} }} = $$props;", props.join(", ")));
+                }
+                Statement::LabeledStatement(statement) if statement.label.name == "$" => {
+                    return None;
+                }
+                Statement::ExportDefaultDeclaration(_) | Statement::ExportAllDeclaration(_) => {
+                    return None;
+                }
+                _ => statements.push(rewriter.rewrite(statement.span())),
+            }
+        }
+    }
+
+    let scope = root.css.as_ref().map(|style| {
+        format!("svelte-{}", ssc_css_analyzer::hash(style.stylesheet.source.as_str()))
+    });
+    let mut markup = Literal::default();
+    Renderer { source_text, scope: scope.as_deref() }.fragment(&mut markup, &root.fragment)?;
+    let (declarations, markup) = markup.into_parts();
+    statements.extend(declarations);
+
+    module.push_str(HELPERS);
+    module.push_str("\nexport default function render($$props = {}) {\n");
+    for statement in statements {
+        module.push('\t');
+        module.push_str(&statement);
+        module.push('\n');
+    }
+    module.push_str("\treturn ");
+    module.push_str(&markup);
+    module.push_str(";\n}\n");
+    Some(module)
+}
+
+/// Rewrites the runes of the instance script for the server: `$props()` is the
+/// props passed to the module, `$derived(a)` and `$bindable(a)` are `(a)` and
+/// `$derived.by(f)` is `(f)()`.
+struct ScriptRewriter<'s> {
+    source_text: &'s str,
+    /// Text replacing spans of the script, in source order once sorted.
+    edits: Vec<(Span, &'static str)>,
+    /// Whether the script only uses runes that can be rewritten.
+    supported: bool,
+}
+
+impl<'s> ScriptRewriter<'s> {
+    fn source(&self, span: Span) -> &'s str {
+        &self.source_text[span.start as usize..span.end as usize]
+    }
+
+    /// The source at `span`, with the edits inside of it applied.
+    fn rewrite(&self, span: Span) -> String {
+        let mut rewritten = String::new();
+        let mut end = span.start;
+        for (edit, text) in &self.edits {
+            if edit.start < span.start || span.end < edit.end {
+                continue;
+            }
+            rewritten.push_str(self.source(Span::new(end, edit.start)));
+            rewritten.push_str(text);
+            end = edit.end;
+        }
+        rewritten.push_str(self.source(Span::new(end, span.end)));
+        rewritten
+    }
+}
+
+impl<'a, 's> Visit<'a> for ScriptRewriter<'s> {
+    fn visit_call_expression(&mut self, expr: &CallExpression<'a>) {
+        let rune = match &expr.callee {
+            Expression::Identifier(ident) => Some((ident.name.as_str(), None)),
+            Expression::StaticMemberExpression(member) => match &member.object {
+                Expression::Identifier(object) => {
+                    Some((object.name.as_str(), Some(member.property.name.as_str())))
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+        match rune {
+            Some(("$props", None)) if expr.arguments.is_empty() => {
+                self.edits.push((expr.span, "$$props"));
+                return;
+            }
+            Some(("$bindable", None)) if expr.arguments.is_empty() => {
+                self.edits.push((expr.span, "undefined"));
+                return;
+            }
+            Some(("$derived" | "$bindable", None)) => {
+                self.edits.push((expr.callee.span(), ""));
+            }
+            Some(("$derived", Some("by"))) => {
+                self.edits.push((expr.callee.span(), ""));
+                self.edits.push((Span::new(expr.span.end, expr.span.end), "()"));
+            }
+            Some((
+                "$state" | "$derived" | "$effect" | "$props" | "$bindable" | "$inspect" | "$host",
+                _,
+            )) => self.supported = false,
+            _ => {}
+        }
+        walk::walk_call_expression(self, expr);
+    }
+
+    fn visit_empty_statement(&mut self, stmt: &EmptyStatement) {
+        // A statement the transformer removed, e.g. `$inspect(a)` in an `if`
+        if stmt.span.size() > 1 {
+            self.edits.push((stmt.span, ";"));
+        }
+    }
+
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        if matches!(ident.name.as_str(), "$$restProps" | "$$slots") {
+            self.supported = false;
+        }
+    }
+}
+
+/// A JS template literal rendering markup: the static markup is escaped by a
+/// [`TemplateString`], the expressions of the template are interpolated in
+/// between.
+#[derive(Default)]
+struct Literal {
+    /// The source of the literal so far, without its backticks.
+    source: String,
+    markup: TemplateString,
+    /// The snippets and `{@const}`s declared in the markup, hoisted to the
+    /// function returning it.
+    statements: Vec<String>,
+}
+
+impl Literal {
+    fn interpolate(&mut self, code: &str) {
+        self.flush();
+        self.source.push_str("${");
+        self.source.push_str(code);
+        self.source.push('}');
+    }
+
+    fn flush(&mut self) {
+        if !self.markup.is_empty() {
+            let literal = std::mem::take(&mut self.markup).to_literal();
+            self.source.push_str(&literal[1..literal.len() - 1]);
+        }
+    }
+
+    /// The declarations of the markup, and the literal.
+    fn into_parts(mut self) -> (Vec<String>, String) {
+        self.flush();
+        (self.statements, format!("`{}`", self.source))
+    }
+
+    /// The literal, called in an arrow function declaring what the markup
+    /// declares, if anything.
+    fn into_expression(self) -> String {
+        let (statements, literal) = self.into_parts();
+        if statements.is_empty() {
+            return literal;
+        }
+        format!("(() => {{ {} return {literal}; }})()", statements.join(" "))
+    }
+
+    /// `function name(parameters) { ... }`, returning the literal.
+    fn into_function(self, name: &str, parameters: &str) -> String {
+        let (mut statements, literal) = self.into_parts();
+        statements.push(format!("return {literal};"));
+        format!("function {name}({parameters}) {{ {} }}", statements.join(" "))
+    }
+}
+
+/// Renders markup as a JS template literal, interpolating the expressions of
+/// the template.
+struct Renderer<'s> {
+    source_text: &'s str,
+    /// The class scoping the styles of the component.
+    scope: Option<&'s str>,
+}
+
+impl<'s> Renderer<'s> {
+    /// The template literal rendering `fragment`.
+    fn literal(&self, fragment: &Fragment<'_>) -> Option<String> {
+        let mut literal = Literal::default();
+        self.fragment(&mut literal, fragment)?;
+        Some(literal.into_expression())
+    }
+
+    fn source(&self, span: Span) -> &'s str {
+        &self.source_text[span.start as usize..span.end as usize]
+    }
+
+    /// The source of `expression`, in parentheses unless it's a single token
+    /// or a call.
+    fn js(&self, expression: &Expression<'_>) -> String {
+        let source = self.source(expression.span());
+        match expression {
+            Expression::Identifier(_)
+            | Expression::StringLiteral(_)
+            | Expression::NumericLiteral(_)
+            | Expression::BooleanLiteral(_)
+            | Expression::NullLiteral(_)
+            | Expression::StaticMemberExpression(_)
+            | Expression::CallExpression(_) => source.to_string(),
+            _ => format!("({source})"),
+        }
+    }
+
+    fn fragment(&self, out: &mut Literal, fragment: &Fragment<'_>) -> Option<()> {
+        fragment.nodes.iter().try_for_each(|node| self.node(out, node))
+    }
+
+    fn node(&self, out: &mut Literal, node: &FragmentNode<'_>) -> Option<()> {
+        match node {
+            FragmentNode::Text(text) => out.markup.push_text(&html::decode_text(&text.data)),
+            // Only left in the template with `preserveComments`
+            FragmentNode::Comment(comment) => out.markup.push_comment(&comment.data),
+            FragmentNode::Tag(Tag::DebugTag(_)) => {}
+            FragmentNode::Tag(Tag::ExpressionTag(tag)) => {
+                out.interpolate(&format!("$$escape({})", self.js(&tag.expression)));
+            }
+            FragmentNode::Tag(Tag::HtmlTag(tag)) => {
+                out.interpolate(&format!("{} ?? \"\"", self.js(&tag.expression)));
+            }
+            FragmentNode::Tag(Tag::ConstTag(tag)) => {
+                out.statements.push(format!("{};", self.source(tag.declaration.span)));
+            }
+            FragmentNode::Tag(Tag::RenderTag(tag)) => out.interpolate(&match &tag.expression {
+                RenderTagExpression::Call(call) => self.source(call.span).to_string(),
+                // `{@render children?.()}` renders nothing without children
+                RenderTagExpression::Chain(call) => format!("{} ?? \"\"", self.source(call.span)),
+            }),
+            FragmentNode::Element(element) => self.element(out, element)?,
+            FragmentNode::Block(block) => self.block(out, block)?,
+        }
+        Some(())
+    }
+
+    /// The function declaration of a snippet, rendering its body.
+    fn snippet(&self, snippet: &SnippetBlock<'_>) -> Option<String> {
+        let parameters = snippet
+            .parameters
+            .iter()
+            .map(|parameter| self.source(parameter.span()))
+            .collect::<Vec<_>>();
+        let mut body = Literal::default();
+        self.fragment(&mut body, &snippet.body)?;
+        Some(body.into_function(snippet.expression.name.as_str(), &parameters.join(", ")))
+    }
+
+    fn element(&self, out: &mut Literal, element: &Element<'_>) -> Option<()> {
+        match element {
+            Element::RegularElement(element) => self.regular_element(out, element),
+            Element::Component(component) => {
+                let props = self.props(&component.attributes, &component.fragment)?;
+                out.interpolate(&format!("{}({props})", component.name));
+                Some(())
+            }
+            Element::SvelteComponent(component) => {
+                let props = self.props(&component.attributes, &component.fragment)?;
+                out.interpolate(&format!(
+                    "$$component({}, {props})",
+                    self.js(&component.expression)
+                ));
+                Some(())
+            }
+            // Errors are only caught on the client, its `failed` snippet is
+            // never rendered on the server
+            Element::SvelteBoundary(boundary) => self.fragment(out, &boundary.fragment),
+            // Their attributes can only be client features
+            Element::SvelteBody(_)
+            | Element::SvelteDocument(_)
+            | Element::SvelteWindow(_)
+            | Element::SvelteOptionsRaw(_) => Some(()),
+            _ => None,
+        }
+    }
+
+    fn regular_element(&self, out: &mut Literal, element: &RegularElement<'_>) -> Option<()> {
+        let name = element.name.as_str();
+        let raw_text = html::is_raw_text(name);
+        out.markup.open_start_tag(name);
+        let mut class = None;
+        let mut classes = vec![];
+        let mut style = None;
+        let mut styles = vec![];
+        let mut content = None;
+        for attribute in &element.attributes {
+            let directive = match attribute {
+                ElementAttribute::Attribute(attribute) if attribute.name == "class" => {
+                    class = Some(attribute);
+                    continue;
+                }
+                ElementAttribute::Attribute(attribute) if attribute.name == "style" => {
+                    style = Some(attribute);
+                    continue;
+                }
+                ElementAttribute::Attribute(attribute) => {
+                    self.attribute(out, attribute);
+                    continue;
+                }
+                ElementAttribute::SpreadAttribute(spread) => {
+                    out.interpolate(&format!("$$spread({})", self.js(&spread.expression)));
+                    continue;
+                }
+                ElementAttribute::DirectiveAttribute(directive) => directive,
+            };
+            match directive {
+                DirectiveAttribute::ClassDirective(directive) => {
+                    classes.push(format!(
+                        "{} && {}",
+                        self.js(&directive.expression),
+                        string_literal(directive.name.as_str())
+                    ));
+                }
+                DirectiveAttribute::StyleDirective(directive) => {
+                    let value = directive.value.as_ref().map_or_else(
+                        || directive.name.to_string(),
+                        |value| self.attribute_value(value),
+                    );
+                    let important = directive
+                        .modifiers
+                        .iter()
+                        .any(|modifier| matches!(modifier, StyleDirectiveModifier::Important));
+                    styles.push(format!(
+                        "[{}, {value}, {important}]",
+                        string_literal(directive.name.as_str())
+                    ));
+                }
+                DirectiveAttribute::BindDirective(directive) => {
+                    let value = self.source(match &directive.expression {
+                        BindDirectiveExpression::Identifier(ident) => ident.span,
+                        BindDirectiveExpression::MemberExpression(member) => member.span(),
+                    });
+                    match directive.name.as_str() {
+                        "value" if name == "textarea" => content = Some(value),
+                        // The client selects the bound option
+                        "value" if name == "select" => {}
+                        "value" => out.interpolate(&format!("$$attr(\"value\", {value}, false)")),
+                        "checked" | "open" => out.interpolate(&format!(
+                            "$$attr({}, {value}, true)",
+                            string_literal(directive.name.as_str())
+                        )),
+                        "group" => {
+                            let input =
+                                element.attributes.iter().find_map(|attribute| match attribute {
+                                    ElementAttribute::Attribute(attribute)
+                                        if attribute.name == "value" =>
+                                    {
+                                        attribute.value.as_ref()
+                                    }
+                                    _ => None,
+                                });
+                            let input = input.map_or_else(
+                                || "\"on\"".to_string(),
+                                |value| self.attribute_value(value),
+                            );
+                            let checked = if is_checkbox(element) {
+                                format!("{value}.includes({input})")
+                            } else {
+                                format!("{value} === {input}")
+                            };
+                            out.interpolate(&format!("$$attr(\"checked\", {checked}, true)"));
+                        }
+                        // `bind:this` and the bindings of what the element
+                        // measures or plays only have a value on the client
+                        _ => {}
+                    }
+                }
+                // Run on the client only
+                DirectiveAttribute::OnDirective(_)
+                | DirectiveAttribute::UseDirective(_)
+                | DirectiveAttribute::TransitionDirective(_)
+                | DirectiveAttribute::AnimateDirective(_) => {}
+                DirectiveAttribute::LetDirective(_) => return None,
+            }
+        }
+        let scope = self.scope.filter(|_| !raw_text);
+        let static_class = match class.map(|class| class.value.as_ref()) {
+            None | Some(None) => Some(Cow::Borrowed("")),
+            Some(Some(value)) => match value.sequence.as_slice() {
+                [AttributeSequenceValue::Text(text)] => Some(html::decode_attribute(&text.data)),
+                _ => None,
+            },
+        };
+        match static_class.filter(|_| classes.is_empty()) {
+            Some(_) if class.is_none() && scope.is_none() => {}
+            Some(static_class) => {
+                let class = [static_class.as_ref(), scope.unwrap_or_default()].join(" ");
+                out.markup.push_attribute("class", Some(class.trim()));
+            }
+            None => {
+                let mut parts = vec![];
+                if let Some(value) = class.and_then(|class| class.value.as_ref()) {
+                    parts.push(self.attribute_value(value));
+                }
+                parts.extend(classes);
+                parts.extend(scope.map(string_literal));
+                out.interpolate(&format!(
+                    "$$attr(\"class\", $$class({}), false)",
+                    parts.join(", ")
+                ));
+            }
+        }
+        match style {
+            Some(style) if styles.is_empty() => self.attribute(out, style),
+            _ if styles.is_empty() => {}
+            _ => {
+                let style = style
+                    .and_then(|style| style.value.as_ref())
+                    .map_or_else(|| "null".to_string(), |value| self.attribute_value(value));
+                out.interpolate(&format!(
+                    "$$attr(\"style\", $$style({style}, [{}]), false)",
+                    styles.join(", ")
+                ));
+            }
+        }
+        out.markup.close_start_tag();
+        if let Some(content) = content {
+            out.interpolate(&format!("$$escape({content})"));
+        } else if raw_text {
+            // The content of `<script>` and `<style>` isn't markup
+            for node in &element.fragment.nodes {
+                match node {
+                    FragmentNode::Text(text) => out.markup.push_raw_text(name, &text.data),
+                    _ => self.node(out, node)?,
+                }
+            }
+        } else if !html::is_void(name) {
+            self.fragment(out, &element.fragment)?;
+        }
+        out.markup.push_end_tag(name);
+        Some(())
+    }
+
+    fn attribute(&self, out: &mut Literal, attribute: &Attribute<'_>) {
+        let name = attribute.name.as_str();
+        let Some(value) = &attribute.value else {
+            out.markup.push_attribute(name, None);
+            return;
+        };
+        if let [AttributeSequenceValue::Text(text)] = value.sequence.as_slice() {
+            out.markup.push_attribute(name, Some(&html::decode_attribute(&text.data)));
+            return;
+        }
+        out.interpolate(&format!(
+            "$$attr({}, {}, {})",
+            string_literal(name),
+            self.attribute_value(value),
+            html::is_boolean_attribute(name)
+        ));
+    }
+
+    /// The JS value of an attribute: a string, the value of its only
+    /// expression, or its parts joined, `null` and `undefined` as empty
+    /// strings.
+    fn attribute_value(&self, value: &AttributeValue<'_>) -> String {
+        let part = |part: &AttributeSequenceValue<'_>| match part {
+            AttributeSequenceValue::Text(text) => {
+                string_literal(&html::decode_attribute(&text.data))
+            }
+            AttributeSequenceValue::ExpressionTag(tag) => self.js(&tag.expression),
+        };
+        match value.sequence.as_slice() {
+            [single] => part(single),
+            sequence => {
+                let parts = sequence.iter().map(part).collect::<Vec<_>>();
+                format!("[{}].join(\"\")", parts.join(", "))
+            }
+        }
+    }
+
+    /// The props object passed to a component: its attributes and bindings,
+    /// the snippets declared in it, and its other content as the `children`
+    /// snippet.
+    fn props(
+        &self,
+        attributes: &[ElementAttribute<'_>],
+        fragment: &Fragment<'_>,
+    ) -> Option<String> {
+        let mut props = vec![];
+        for attribute in attributes {
+            match attribute {
+                ElementAttribute::Attribute(attribute) => props.push(format!(
+                    "{}: {}",
+                    string_literal(attribute.name.as_str()),
+                    attribute
+                        .value
+                        .as_ref()
+                        .map_or_else(|| "true".to_string(), |value| self.attribute_value(value))
+                )),
+                ElementAttribute::SpreadAttribute(spread) => {
+                    props.push(format!("...{}", self.js(&spread.expression)));
+                }
+                ElementAttribute::DirectiveAttribute(DirectiveAttribute::BindDirective(bind)) => {
+                    let value = match &bind.expression {
+                        BindDirectiveExpression::Identifier(ident) => ident.span,
+                        BindDirectiveExpression::MemberExpression(member) => member.span(),
+                    };
+                    props.push(format!(
+                        "{}: {}",
+                        string_literal(bind.name.as_str()),
+                        self.source(value)
+                    ));
+                }
+                // Component events are only dispatched on the client
+                ElementAttribute::DirectiveAttribute(DirectiveAttribute::OnDirective(_)) => {}
+                ElementAttribute::DirectiveAttribute(_) => return None,
+            }
+        }
+        let mut children = Literal::default();
+        let mut has_children = false;
+        for node in &fragment.nodes {
+            match node {
+                FragmentNode::Block(Block::SnippetBlock(snippet)) => props.push(format!(
+                    "{}: {}",
+                    string_literal(snippet.expression.name.as_str()),
+                    self.snippet(snippet)?
+                )),
+                FragmentNode::Text(text) if text.data.trim().is_empty() => {}
+                FragmentNode::Comment(_) => self.node(&mut children, node)?,
+                _ => {
+                    has_children = true;
+                    self.node(&mut children, node)?;
+                }
+            }
+        }
+        if has_children {
+            props.push(format!("\"children\": {}", children.into_function("children", "")));
+        }
+        Some(format!("{{ {} }}", props.join(", ")))
+    }
+
+    fn block(&self, out: &mut Literal, block: &Block<'_>) -> Option<()> {
+        match block {
+            Block::IfBlock(block) => {
+                let alternate = match &block.alternate {
+                    Some(alternate) => self.literal(alternate)?,
+                    None => "\"\"".to_string(),
+                };
+                let consequent = self.literal(&block.consequent)?;
+                let test = self.js(&block.test);
+                out.interpolate(&format!("{test} ? {consequent} : {alternate}"));
+            }
+            Block::EachBlock(block) => {
+                let mut parameters = self.source(block.context.span()).to_string();
+                if let Some(index) = &block.index {
+                    parameters = format!("{parameters}, {}", index.name);
+                }
+                let mut each = format!(
+                    "$$each({}, ({parameters}) => {}",
+                    self.js(&block.expression),
+                    self.literal(&block.body)?
+                );
+                if let Some(fallback) = &block.fallback {
+                    each = format!("{each}, () => {}", self.literal(fallback)?);
+                }
+                each.push(')');
+                out.interpolate(&each);
+            }
+            // Rendered once, the key only matters when it changes
+            Block::KeyBlock(block) => self.fragment(out, &block.fragment)?,
+            // A promise renders the pending branch, it settles on the client,
+            // any other value renders the `then` branch right away
+            Block::AwaitBlock(block) => {
+                let pending = match &block.pending {
+                    Some(pending) => format!("() => {}", self.literal(pending)?),
+                    None => "null".to_string(),
+                };
+                let then = match &block.then {
+                    Some(then) => format!(
+                        "({}) => {}",
+                        block.value.as_ref().map_or("", |value| self.source(value.span())),
+                        self.literal(then)?
+                    ),
+                    None => "null".to_string(),
+                };
+                out.interpolate(&format!(
+                    "$$await({}, {pending}, {then})",
+                    self.js(&block.expression)
+                ));
+            }
+            Block::SnippetBlock(snippet) => out.statements.push(self.snippet(snippet)?),
+        }
+        Some(())
+    }
+}
+
+/// Whether `element` is an `<input type="checkbox">`, whose `bind:group` is
+/// an array of the values of the checked inputs.
+fn is_checkbox(element: &RegularElement<'_>) -> bool {
+    element.attributes.iter().any(|attribute| match attribute {
+        ElementAttribute::Attribute(attribute) if attribute.name == "type" => {
+            attribute.value.as_ref().is_some_and(|value| {
+                matches!(
+                    value.sequence.as_slice(),
+                    [AttributeSequenceValue::Text(text)] if text.data == "checkbox"
+                )
+            })
+        }
+        _ => false,
+    })
+}
+
+fn string_literal(value: &str) -> String {
+    serde_json::Value::from(value).to_string()
+}
@@ -0,0 +1,106 @@
+//! Input source maps
+//!
+//! Components produced by a preprocessor or a template generator come with a
+//! source map back to the file that was actually written. Output maps are
+//! composed with it, and diagnostics can be located in that file.
+
+use oxc_diagnostics::{Error, OxcDiagnostic};
+use oxc_sourcemap::{SourceMap, SourceMapBuilder};
+use ssc_line_index::{Encoding, LineIndex};
+
+/// A position in one of the original sources of an [`InputSourceMap`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OriginalPosition {
+    pub source: Option<String>,
+    /// 0-based.
+    pub line: u32,
+    /// 0-based, in UTF-16 code units.
+    pub column: u32,
+}
+
+/// The source map of the compiled source, see [`CompileOptions::sourcemap`].
+///
+/// [`CompileOptions::sourcemap`]: super::CompileOptions::sourcemap
+#[derive(Debug)]
+pub struct InputSourceMap {
+    /// Lines of the compiled source.
+    line_index: LineIndex,
+    map: SourceMap,
+    lookup_table: Vec<(u32, u32, u32)>,
+}
+
+impl InputSourceMap {
+    /// Decode `json`, the map from `source_text` to its original sources.
+    ///
+    /// # Errors
+    ///
+    /// If `json` isn't a valid source map.
+    pub fn new(source_text: &str, json: &str) -> Result<Self, OxcDiagnostic> {
+        let map = SourceMap::from_json_string(json)
+            .map_err(|error| OxcDiagnostic::error(format!("Invalid input source map: {error}")))?;
+        let lookup_table = map.generate_lookup_table();
+        Ok(Self { line_index: LineIndex::new(source_text), map, lookup_table })
+    }
+
+    /// The original position of the byte `offset` of the compiled source.
+    pub fn original_position(&self, offset: u32) -> Option<OriginalPosition> {
+        let position = self.line_index.line_col(offset, Encoding::Utf16);
+        let (source_id, line, column) = self.lookup(position.line, position.column)?;
+        let source = source_id.and_then(|id| self.map.get_source(id)).map(ToString::to_string);
+        Some(OriginalPosition { source, line, column })
+    }
+
+    /// The original position of the first label of `diagnostic`.
+    pub fn diagnostic_position(&self, diagnostic: &Error) -> Option<OriginalPosition> {
+        let label = diagnostic.labels()?.next()?;
+        self.original_position(u32::try_from(label.offset()).ok()?)
+    }
+
+    /// Compose `map`, whose sources are the compiled source, with this map so
+    /// it points to the original sources. Mappings of positions the input
+    /// map doesn't cover are dropped.
+    pub(crate) fn compose(&self, map: &SourceMap) -> SourceMap {
+        let mut builder = SourceMapBuilder::default();
+        if let Some(file) = map.get_file() {
+            builder.set_file(file);
+        }
+        for token in map.get_tokens() {
+            let Some((source_id, line, column)) =
+                self.lookup(token.get_src_line(), token.get_src_col())
+            else {
+                continue;
+            };
+            let source_id = source_id.and_then(|id| {
+                let source = self.map.get_source(id)?;
+                let content = self.map.get_source_content(id).unwrap_or_default();
+                Some(builder.add_source_and_content(source, content))
+            });
+            let name_id = token
+                .get_name_id()
+                .and_then(|id| map.get_name(id))
+                .map(|name| builder.add_name(name));
+            builder.add_token(
+                token.get_dst_line(),
+                token.get_dst_col(),
+                line,
+                column,
+                source_id,
+                name_id,
+            );
+        }
+        builder.into_sourcemap()
+    }
+
+    /// The original source id, line and column of a position of the compiled
+    /// source.
+    fn lookup(&self, line: u32, column: u32) -> Option<(Option<u32>, u32, u32)> {
+        let token = self.map.lookup_token(&self.lookup_table, line, column)?;
+        // The closest mapping is on a previous line, which says nothing of
+        // this one
+        if token.get_dst_line() != line {
+            return None;
+        }
+        let column = token.get_src_col() + (column - token.get_dst_col());
+        Some((token.get_source_id(), token.get_src_line(), column))
+    }
+}
@@ -0,0 +1,64 @@
+use std::{ops::AddAssign, time::Duration};
+
+use oxc_diagnostics::{Error, Severity};
+#[cfg(feature = "serialize")]
+use serde::Serialize;
+
+/// Statistics collected while compiling.
+///
+/// Stats of several compilations can be summed up with `+=`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[cfg_attr(feature = "serialize", serde(rename_all = "camelCase"))]
+pub struct CompileStats {
+    /// Number of compiled files.
+    pub files: usize,
+
+    /// Time spent in the parser.
+    pub parse: Duration,
+    /// Time spent in the analyzers.
+    pub analyze: Duration,
+    /// Time spent in the transformers.
+    pub transform: Duration,
+    /// Time spent in the code generators.
+    pub codegen: Duration,
+
+    pub errors: usize,
+    pub warnings: usize,
+
+    /// Size of the input in bytes.
+    pub source_size: usize,
+    /// Size of all emitted code in bytes, source maps excluded.
+    pub output_size: usize,
+}
+
+impl CompileStats {
+    /// Total time spent in all phases.
+    pub fn total(&self) -> Duration {
+        self.parse + self.analyze + self.transform + self.codegen
+    }
+
+    pub(crate) fn count_diagnostics(&mut self, diagnostics: &[Error]) {
+        for diagnostic in diagnostics {
+            match diagnostic.severity() {
+                Some(Severity::Warning) => self.warnings += 1,
+                Some(Severity::Advice) => {}
+                _ => self.errors += 1,
+            }
+        }
+    }
+}
+
+impl AddAssign for CompileStats {
+    fn add_assign(&mut self, rhs: Self) {
+        self.files += rhs.files;
+        self.parse += rhs.parse;
+        self.analyze += rhs.analyze;
+        self.transform += rhs.transform;
+        self.codegen += rhs.codegen;
+        self.errors += rhs.errors;
+        self.warnings += rhs.warnings;
+        self.source_size += rhs.source_size;
+        self.output_size += rhs.output_size;
+    }
+}
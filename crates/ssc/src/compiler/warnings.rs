@@ -0,0 +1,103 @@
+//! Warning configuration
+//!
+//! Codebase-wide policies for the warnings of the compiler, e.g. disabling a
+//! warning or failing the build on it, applied once all phases have run.
+
+use std::str::FromStr;
+
+use oxc_diagnostics::{Error, OxcDiagnostic, Severity};
+
+/// The code of a warning, e.g. `element_invalid_self_closing_tag`.
+///
+/// Warnings are created with their code, in the `svelte` scope, by
+/// [`OxcDiagnostic::with_error_code`].
+pub fn warning_code(warning: &Error) -> Option<&str> {
+    warning.downcast_ref::<OxcDiagnostic>()?.code.number.as_deref()
+}
+
+/// What to do with the warnings of a code, see [`CompileOptions::warnings`].
+///
+/// [`CompileOptions::warnings`]: super::CompileOptions::warnings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WarningLevel {
+    /// Drop the warning.
+    Off,
+    /// Report it as advice, which isn't counted as a warning.
+    Advice,
+    /// Report it as a warning, the default.
+    Warn,
+    /// Report it as an error.
+    Error,
+}
+
+impl FromStr for WarningLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(Self::Off),
+            "advice" => Ok(Self::Advice),
+            "warn" => Ok(Self::Warn),
+            "error" => Ok(Self::Error),
+            _ => Err(format!("Unknown warning level `{s}`")),
+        }
+    }
+}
+
+/// Decides whether a warning is reported.
+///
+/// Implemented for closures, `|code, warning| code != Some("a11y_autofocus")`.
+pub trait WarningFilter {
+    /// Whether to keep `warning`, of the given code if it has one.
+    fn keep(&self, code: Option<&str>, warning: &Error) -> bool;
+}
+
+impl<F> WarningFilter for F
+where
+    F: Fn(Option<&str>, &Error) -> bool,
+{
+    fn keep(&self, code: Option<&str>, warning: &Error) -> bool {
+        self(code, warning)
+    }
+}
+
+/// Apply the configured levels, then the filter, to the warnings of
+/// `diagnostics`. Errors are left as is.
+pub(crate) fn apply_warning_config(
+    diagnostics: Vec<Error>,
+    levels: &[(String, WarningLevel)],
+    filter: Option<&dyn WarningFilter>,
+) -> Vec<Error> {
+    if levels.is_empty() && filter.is_none() {
+        return diagnostics;
+    }
+    diagnostics
+        .into_iter()
+        .filter_map(|diagnostic| {
+            if diagnostic.severity() != Some(Severity::Warning) {
+                return Some(diagnostic);
+            }
+            let code = warning_code(&diagnostic);
+            // Later entries override earlier ones
+            let level = code
+                .and_then(|code| levels.iter().rev().find(|(name, _)| name == code))
+                .map_or(WarningLevel::Warn, |(_, level)| *level);
+            let severity = match level {
+                WarningLevel::Off => return None,
+                WarningLevel::Advice => Severity::Advice,
+                WarningLevel::Warn => Severity::Warning,
+                WarningLevel::Error => Severity::Error,
+            };
+            if filter.is_some_and(|filter| !filter.keep(code, &diagnostic)) {
+                return None;
+            }
+            if severity == Severity::Warning {
+                return Some(diagnostic);
+            }
+            Some(match diagnostic.downcast::<OxcDiagnostic>() {
+                Ok(diagnostic) => diagnostic.with_severity(severity).into(),
+                Err(diagnostic) => diagnostic,
+            })
+        })
+        .collect()
+}
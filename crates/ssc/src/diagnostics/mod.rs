@@ -0,0 +1,61 @@
+//! Diagnostics
+//!
+//! Re-exports [`oxc_diagnostics`], along with rendering for terminals.
+
+use std::sync::Arc;
+
+#[doc(inline)]
+pub use oxc_diagnostics::*;
+
+/// Render `diagnostics`, reported for the file `filename`, with miette's
+/// graphical handler.
+///
+/// Every labelled span of a diagnostic is shown in the source, followed by its
+/// help. ANSI colors are only used when `color` is set.
+pub fn render_diagnostics(
+    diagnostics: Vec<Error>,
+    filename: &str,
+    source_text: &str,
+    color: bool,
+) -> String {
+    let theme = if color { GraphicalTheme::unicode() } else { GraphicalTheme::unicode_nocolor() };
+    let handler = GraphicalReportHandler::new().with_theme(theme);
+    let source_text: Arc<str> = Arc::from(source_text);
+    let mut output = String::new();
+    for diagnostic in diagnostics {
+        let diagnostic =
+            diagnostic.with_source_code(NamedSource::new(filename, Arc::clone(&source_text)));
+        // Writing to a `String` can't fail
+        let _ = handler.render_report(&mut output, diagnostic.as_ref());
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_span::Span;
+
+    use super::*;
+
+    #[test]
+    fn render() {
+        let source_text = "let a = 1;\na = 2;";
+        let diagnostic = OxcDiagnostic::error("Cannot assign to constant `a`")
+            .with_labels([
+                Span::new(4, 5).label("`a` is declared as a constant here"),
+                Span::new(11, 12).label("It cannot be assigned here"),
+            ])
+            .with_help("Declare `a` with `let`");
+        let output = render_diagnostics(vec![diagnostic.into()], "App.svelte", source_text, false);
+        for expected in [
+            "Cannot assign to constant `a`",
+            "App.svelte:1:5",
+            "`a` is declared as a constant here",
+            "It cannot be assigned here",
+            "help: Declare `a` with `let`",
+        ] {
+            assert!(output.contains(expected), "{expected} in {output}");
+        }
+    }
+}
@@ -0,0 +1,267 @@
+//! Formatting
+//!
+//! Formats components by printing their AST back with the codegen, and
+//! reports what formatting would change as a unified diff, for pre-commit
+//! hooks and CI checks that must not write files.
+
+use oxc_allocator::Allocator;
+use oxc_diagnostics::{Error, Severity};
+use ssc_codegen::{Codegen, CodegenOptions, ScriptFormatter};
+use ssc_parser::Parser;
+
+/// Number of unchanged lines shown around the changes of a diff hunk.
+const CONTEXT_LINES: usize = 3;
+
+/// Format `source_text`, keeping its comments.
+///
+/// # Errors
+///
+/// The parse errors of `source_text`, warnings excluded. A component that
+/// doesn't parse isn't formatted.
+pub fn format(source_text: &str, options: CodegenOptions) -> Result<String, Vec<Error>> {
+    format_impl(source_text, options, |codegen| codegen)
+}
+
+/// Format `source_text` like [`format`], with the contents of its `<script>`
+/// elements formatted by `script_formatter`, e.g. to keep Prettier or Biome
+/// for JavaScript.
+///
+/// # Errors
+///
+/// See [`format`].
+pub fn format_with_script_formatter<F: ScriptFormatter + 'static>(
+    source_text: &str,
+    options: CodegenOptions,
+    script_formatter: F,
+) -> Result<String, Vec<Error>> {
+    format_impl(source_text, options, |codegen| {
+        codegen.with_script_formatter(script_formatter, source_text)
+    })
+}
+
+fn format_impl<F: FnOnce(Codegen<false>) -> Codegen<false>>(
+    source_text: &str,
+    options: CodegenOptions,
+    with: F,
+) -> Result<String, Vec<Error>> {
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text).parse();
+    let errors = ret
+        .errors
+        .into_iter()
+        .filter(|error| error.severity == Severity::Error)
+        .map(Error::from)
+        .collect::<Vec<_>>();
+    if !errors.is_empty() || ret.panicked {
+        return Err(errors);
+    }
+    let options = CodegenOptions { preserve_comments: true, ..options };
+    let codegen = Codegen::<false>::new("", source_text, options);
+    Ok(with(codegen).build(&ret.root).source_text)
+}
+
+/// The unified diff of formatting `source_text`, or `None` if it's already
+/// formatted. `path` names the file in the diff header.
+///
+/// # Errors
+///
+/// See [`format`].
+pub fn check(
+    path: &str,
+    source_text: &str,
+    options: CodegenOptions,
+) -> Result<Option<String>, Vec<Error>> {
+    let formatted = format(source_text, options)?;
+    Ok(unified_diff(path, source_text, &formatted))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edit {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// The unified diff from `original` to `modified`, with lines of context
+/// around changes, or `None` if they're equal.
+pub fn unified_diff(path: &str, original: &str, modified: &str) -> Option<String> {
+    if original == modified {
+        return None;
+    }
+    let old = original.split_inclusive('\n').collect::<Vec<_>>();
+    let new = modified.split_inclusive('\n').collect::<Vec<_>>();
+    // Every edit, with the index of its line in `old` and `new`. Insertions
+    // are at the old index they're inserted before, deletions at the new one.
+    let edits = diff_lines(&old, &new);
+
+    let changes = edits
+        .iter()
+        .enumerate()
+        .filter(|(_, (edit, ..))| *edit != Edit::Equal)
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+    let mut hunks: Vec<(usize, usize)> = vec![];
+    for change in changes {
+        let start = change.saturating_sub(CONTEXT_LINES);
+        let end = (change + CONTEXT_LINES + 1).min(edits.len());
+        match hunks.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut diff = format!("--- a/{path}\n+++ b/{path}\n");
+    for (start, end) in hunks {
+        let edits = &edits[start..end];
+        let (_, old_start, new_start) = edits[0];
+        let old_len = edits.iter().filter(|(edit, ..)| *edit != Edit::Insert).count();
+        let new_len = edits.iter().filter(|(edit, ..)| *edit != Edit::Delete).count();
+        // Empty ranges start at the line before them
+        let old_start = if old_len == 0 { old_start } else { old_start + 1 };
+        let new_start = if new_len == 0 { new_start } else { new_start + 1 };
+        diff.push_str(&format!("@@ -{old_start},{old_len} +{new_start},{new_len} @@\n"));
+        for &(edit, old_index, new_index) in edits {
+            let (prefix, line) = match edit {
+                Edit::Equal => (' ', old[old_index]),
+                Edit::Delete => ('-', old[old_index]),
+                Edit::Insert => ('+', new[new_index]),
+            };
+            diff.push(prefix);
+            diff.push_str(line);
+            if !line.ends_with('\n') {
+                diff.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+    }
+    Some(diff)
+}
+
+/// The shortest edit script from `old` to `new`, with Myers' algorithm.
+#[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss, clippy::many_single_char_names)]
+fn diff_lines(old: &[&str], new: &[&str]) -> Vec<(Edit, usize, usize)> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let max = n + m;
+    let offset = max as usize;
+    let mut v = vec![0isize; 2 * offset + 2];
+    // The furthest reaching paths before every step `d`, only of the diagonals
+    // `-d..=d` backtracking reads, which keeps it quadratic in the number of
+    // edits rather than in the length of the files
+    let mut trace = vec![];
+    'outer: for d in 0..=max {
+        trace.push(v[offset - d as usize..=offset + d as usize].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let index = (k + max) as usize;
+            let mut x = if k == -d || (k != d && v[index - 1] < v[index + 1]) {
+                v[index + 1]
+            } else {
+                v[index - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[index] = x;
+            if x >= n && y >= m {
+                break 'outer;
+            }
+        }
+    }
+
+    let mut edits = vec![];
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let k = x - y;
+        let (prev_x, prev_y) = if d == 0 {
+            (0, 0)
+        } else {
+            let at = |k: isize| v[(k + d) as usize];
+            let prev_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) { k + 1 } else { k - 1 };
+            (at(prev_k), at(prev_k) - prev_k)
+        };
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            edits.push((Edit::Equal, x as usize, y as usize));
+        }
+        if d > 0 {
+            if x == prev_x {
+                edits.push((Edit::Insert, x as usize, prev_y as usize));
+            } else {
+                edits.push((Edit::Delete, prev_x as usize, y as usize));
+            }
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+    edits.reverse();
+    edits
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_span::SourceType;
+    use ssc_codegen::FormattedScript;
+
+    use super::*;
+
+    #[test]
+    fn diff() {
+        assert_eq!(unified_diff("a.svelte", "a\nb\n", "a\nb\n"), None);
+        assert_eq!(
+            unified_diff(
+                "a.svelte",
+                "1\n2\n3\n4\n5\n6\n7\n8\n9\n",
+                "1\n2\n3\n4\nfive\n6\n7\n8\n9\n"
+            )
+            .unwrap(),
+            "--- a/a.svelte\n+++ b/a.svelte\n@@ -2,7 +2,7 @@\n 2\n 3\n 4\n-5\n+five\n 6\n 7\n 8\n"
+        );
+        assert_eq!(
+            unified_diff("a.svelte", "a", "b\na").unwrap(),
+            "--- a/a.svelte\n+++ b/a.svelte\n@@ -1,1 +1,2 @@\n+b\n a\n\\ No newline at end of file\n"
+        );
+    }
+
+    #[test]
+    fn check_formatting() {
+        let options = CodegenOptions::default();
+        let formatted = format("<p  class='a'>hi</p>", options.clone()).unwrap();
+        assert_eq!(check("a.svelte", &formatted, options.clone()).unwrap(), None);
+        let diff = check("a.svelte", "<p  class='a'>hi</p>", options.clone()).unwrap().unwrap();
+        assert!(
+            diff.starts_with("--- a/a.svelte\n+++ b/a.svelte\n@@ -1,1 +1,1 @@\n-<p  class='a'>")
+        );
+        assert!(check("a.svelte", "<p>{</p>", options.clone()).is_err());
+        let source = "<!-- a --><p>b <!-- c --></p>";
+        assert_eq!(format(source, options).unwrap(), source);
+    }
+
+    #[test]
+    fn script_formatter() {
+        let source = "<script lang=\"ts\">\nlet a:number=1 // one\n</script>\n<p>{a}</p>";
+        let formatted = format_with_script_formatter(
+            source,
+            CodegenOptions::default(),
+            |source_text: &str, source_type: SourceType| {
+                assert_eq!(source_text, "\nlet a:number=1 // one\n");
+                assert!(source_type.is_typescript());
+                Some(FormattedScript::new("let a: number = 1; // one\n".to_string()))
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            formatted,
+            "<script lang=\"ts\">\n\tlet a: number = 1; // one\n</script>\n\n<p>{a}</p>"
+        );
+        // Scripts the formatter declines are printed by the codegen
+        let formatted = format_with_script_formatter(
+            source,
+            CodegenOptions::default(),
+            |_: &str, _: SourceType| None,
+        )
+        .unwrap();
+        assert_eq!(formatted, format(source, CodegenOptions::default()).unwrap());
+    }
+}
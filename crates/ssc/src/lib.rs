@@ -12,11 +12,15 @@ pub mod span {
     pub use oxc_span::*;
 }
 
-pub mod diagnostics {
+pub mod line_index {
     #[doc(inline)]
-    pub use oxc_diagnostics::*;
+    pub use ssc_line_index::*;
 }
 
+pub mod diagnostics;
+
+pub mod format;
+
 pub mod ast {
     #[doc(inline)]
     pub use ssc_ast::*;
@@ -32,6 +36,11 @@ pub mod codegen {
     pub use ssc_codegen::*;
 }
 
+pub mod transformer {
+    #[doc(inline)]
+    pub use ssc_transformer::*;
+}
+
 #[cfg(feature = "css")]
 pub mod css_ast {
     #[doc(inline)]
@@ -61,3 +70,18 @@ pub mod css_analyzer {
     #[doc(inline)]
     pub use ssc_css_analyzer;
 }
+
+#[cfg(feature = "linter")]
+pub mod semantic {
+    #[doc(inline)]
+    pub use ssc_semantic::*;
+}
+
+#[cfg(feature = "linter")]
+pub mod linter {
+    #[doc(inline)]
+    pub use ssc_linter::*;
+}
+
+#[cfg(feature = "compiler")]
+pub mod compiler;
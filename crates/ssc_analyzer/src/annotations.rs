@@ -0,0 +1,207 @@
+//! Annotations of the AST
+//!
+//! The analyses annotate the AST they run on, the semantic analysis sets the
+//! symbol and reference ids of the identifiers, and the CSS analysis flags the
+//! rules and selectors, e.g. marking those that are used. Reusing an analysis
+//! for a reparsed component restores its annotations into the new AST: the
+//! sections the analysis depends on didn't change, so the new AST has the same
+//! shape and its nodes are visited in the same order.
+
+use std::cell::Cell;
+
+use oxc_ast::ast::{
+    ArrayExpression, BindingIdentifier, BindingPattern, CallExpression, Expression,
+    IdentifierReference, MemberExpression, ObjectExpression, Program, VariableDeclaration,
+};
+use oxc_syntax::{reference::ReferenceId, symbol::SymbolId};
+use ssc_ast::{ast::Root, Visit};
+use ssc_css_ast::{
+    ast::{
+        AstNodeId, ComplexSelector, RelativeSelector, RelativeSelectorFlags, RuleFlags, StyleRule,
+        StyleSheet,
+    },
+    visit::walk::{walk_complex_selector, walk_relative_selector, walk_style_rule},
+};
+
+/// The ids set by [`SemanticBuilder`] on the identifiers of a component, in
+/// visit order.
+///
+/// [`SemanticBuilder`]: ssc_semantic::SemanticBuilder
+#[derive(Debug, Default)]
+pub struct SemanticAnnotations {
+    symbol_ids: Vec<Option<SymbolId>>,
+    reference_ids: Vec<Option<ReferenceId>>,
+}
+
+impl SemanticAnnotations {
+    pub fn save(root: &Root) -> Self {
+        let mut annotations = Self::default();
+        let _ = Identifiers {
+            binding: |id: &Cell<Option<SymbolId>>| annotations.symbol_ids.push(id.get()),
+            reference: |id: &Cell<Option<ReferenceId>>| annotations.reference_ids.push(id.get()),
+        }
+        .visit_root(root);
+        annotations
+    }
+
+    pub fn restore(&self, root: &Root) {
+        let mut symbol_ids = self.symbol_ids.iter().copied();
+        let mut reference_ids = self.reference_ids.iter().copied();
+        let _ = Identifiers {
+            binding: |id: &Cell<Option<SymbolId>>| id.set(symbol_ids.next().flatten()),
+            reference: |id: &Cell<Option<ReferenceId>>| id.set(reference_ids.next().flatten()),
+        }
+        .visit_root(root);
+    }
+}
+
+/// The flags set by the CSS [`Analyzer`] on the rules and selectors of a
+/// stylesheet, in visit order.
+///
+/// [`Analyzer`]: ssc_css_analyzer::Analyzer
+#[derive(Debug, Default)]
+pub struct CssAnnotations {
+    rules: Vec<(Option<AstNodeId>, RuleFlags)>,
+    selectors: Vec<(Option<AstNodeId>, bool)>,
+    relative_selectors: Vec<RelativeSelectorFlags>,
+}
+
+impl CssAnnotations {
+    pub fn save(stylesheet: &StyleSheet) -> Self {
+        let mut annotations = Self::default();
+        ssc_css_ast::Visit::visit_stylesheet(
+            &mut Selectors {
+                rule: |rule: &StyleRule| {
+                    annotations.rules.push((rule.parent_rule.get(), rule.flags.get()));
+                },
+                selector: |selector: &ComplexSelector| {
+                    annotations.selectors.push((selector.rule.get(), selector.used.get()));
+                },
+                relative_selector: |selector: &RelativeSelector| {
+                    annotations.relative_selectors.push(selector.flags.get());
+                },
+            },
+            stylesheet,
+        );
+        annotations
+    }
+
+    pub fn restore(&self, stylesheet: &StyleSheet) {
+        let mut rules = self.rules.iter().copied();
+        let mut selectors = self.selectors.iter().copied();
+        let mut relative_selectors = self.relative_selectors.iter().copied();
+        ssc_css_ast::Visit::visit_stylesheet(
+            &mut Selectors {
+                rule: |rule: &StyleRule| {
+                    let (parent_rule, flags) = rules.next().unwrap_or((None, RuleFlags::empty()));
+                    rule.parent_rule.set(parent_rule);
+                    rule.flags.set(flags);
+                },
+                selector: |selector: &ComplexSelector| {
+                    let (rule, used) = selectors.next().unwrap_or_default();
+                    selector.rule.set(rule);
+                    selector.used.set(used);
+                },
+                relative_selector: |selector: &RelativeSelector| {
+                    selector
+                        .flags
+                        .set(relative_selectors.next().unwrap_or(RelativeSelectorFlags::empty()));
+                },
+            },
+            stylesheet,
+        );
+    }
+}
+
+/// Calls `binding` for the binding identifiers of the scripts and the template,
+/// and `reference` for their identifier references.
+struct Identifiers<B, R> {
+    binding: B,
+    reference: R,
+}
+
+impl<'a, B, R> Visit<'a> for Identifiers<B, R>
+where
+    B: FnMut(&Cell<Option<SymbolId>>),
+    R: FnMut(&Cell<Option<ReferenceId>>),
+{
+    fn visit_program(&mut self, program: &Program<'a>) {
+        oxc_ast::Visit::visit_program(self, program);
+    }
+
+    fn visit_expression(&mut self, expression: &Expression<'a>) {
+        oxc_ast::Visit::visit_expression(self, expression);
+    }
+
+    fn visit_binding_pattern(&mut self, pattern: &BindingPattern<'a>) {
+        oxc_ast::Visit::visit_binding_pattern(self, pattern);
+    }
+
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        oxc_ast::Visit::visit_identifier_reference(self, ident);
+    }
+
+    fn visit_variable_declaration(&mut self, declaration: &VariableDeclaration<'a>) {
+        oxc_ast::Visit::visit_variable_declaration(self, declaration);
+    }
+
+    fn visit_call_expression(&mut self, expr: &CallExpression<'a>) {
+        oxc_ast::Visit::visit_call_expression(self, expr);
+    }
+
+    fn visit_member_expression(&mut self, expr: &MemberExpression<'a>) {
+        oxc_ast::Visit::visit_member_expression(self, expr);
+    }
+
+    fn visit_array_expression(&mut self, expr: &ArrayExpression<'a>) {
+        oxc_ast::Visit::visit_array_expression(self, expr);
+    }
+
+    fn visit_object_expression(&mut self, expr: &ObjectExpression<'a>) {
+        oxc_ast::Visit::visit_object_expression(self, expr);
+    }
+}
+
+impl<'a, B, R> oxc_ast::Visit<'a> for Identifiers<B, R>
+where
+    B: FnMut(&Cell<Option<SymbolId>>),
+    R: FnMut(&Cell<Option<ReferenceId>>),
+{
+    fn visit_binding_identifier(&mut self, ident: &BindingIdentifier<'a>) {
+        (self.binding)(&ident.symbol_id);
+    }
+
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        (self.reference)(&ident.reference_id);
+    }
+}
+
+/// Calls `rule`, `selector` and `relative_selector` for the style rules, the
+/// complex selectors and the relative selectors of a stylesheet.
+struct Selectors<R, S, T> {
+    rule: R,
+    selector: S,
+    relative_selector: T,
+}
+
+impl<'a, R, S, T> ssc_css_ast::Visit<'a> for Selectors<R, S, T>
+where
+    R: FnMut(&StyleRule),
+    S: FnMut(&ComplexSelector),
+    T: FnMut(&RelativeSelector),
+{
+    fn visit_style_rule(&mut self, rule: &StyleRule<'a>) {
+        (self.rule)(rule);
+        walk_style_rule(self, rule);
+    }
+
+    fn visit_complex_selector(&mut self, selector: &ComplexSelector<'a>) {
+        (self.selector)(selector);
+        walk_complex_selector(self, selector);
+    }
+
+    fn visit_relative_selector(&mut self, selector: &RelativeSelector<'a>) {
+        (self.relative_selector)(selector);
+        walk_relative_selector(self, selector);
+    }
+}
@@ -1 +1,344 @@
+//! Incremental analysis
+//!
+//! Caches the analyses of each component, and only reruns those depending on
+//! the [`Sections`] that changed since the last analysis, e.g. editing the
+//! `<style>` of a component doesn't rebuild its scopes. The annotations the
+//! reused analyses wrote into the previous AST are restored into the new one.
+//!
+//! A [`Project`] ties the analyses of the components of a project together
+//! with their import graph, e.g. to find the unused components and exports
+//! of the project.
 
+mod annotations;
+mod project;
+mod sections;
+mod unused;
+
+use bitflags::bitflags;
+use oxc_ast::ast::Program;
+use oxc_diagnostics::Error;
+use rustc_hash::FxHashMap;
+use ssc_ast::ast::{Root, ScriptContext};
+use ssc_css_analyzer::Analyzer;
+use ssc_semantic::SemanticBuilder;
+
+use crate::annotations::{CssAnnotations, SemanticAnnotations};
+pub use crate::{
+    project::{Export, FileKind, Import, ImportedName, ModuleResolver, Project, ProjectFile},
+    sections::{Fingerprints, Sections},
+    unused::{unused_json, Unused, UnusedKind},
+};
+
+bitflags! {
+    /// Analyses run by the [`IncrementalAnalyzer`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Analyses: u8 {
+        /// Scopes, symbols and references, see [`ssc_semantic`].
+        const Semantic = 1 << 0;
+        /// Validation and hashing of the styles, see [`ssc_css_analyzer`].
+        const Css      = 1 << 1;
+    }
+}
+
+impl Analyses {
+    /// The sections the results of the analyses depend on.
+    pub fn dependencies(self) -> Sections {
+        let mut sections = Sections::empty();
+        if self.contains(Self::Semantic) {
+            sections |= Sections::Module | Sections::Instance | Sections::Template;
+        }
+        if self.contains(Self::Css) {
+            sections |= Sections::Style;
+        }
+        sections
+    }
+
+    /// The analyses to rerun when `sections` changed.
+    pub fn invalidated_by(sections: Sections) -> Self {
+        Self::all().iter().filter(|analysis| analysis.dependencies().intersects(sections)).collect()
+    }
+}
+
+/// The cached analyses of a component.
+#[derive(Debug)]
+pub struct ComponentAnalysis {
+    fingerprints: Fingerprints,
+    /// Sections whose program was replaced, their fingerprints don't
+    /// describe the analyzed code.
+    replaced: Sections,
+    /// Analyses rerun by the last [`IncrementalAnalyzer::analyze`], the
+    /// others were reused.
+    pub reanalyzed: Analyses,
+    semantic_annotations: SemanticAnnotations,
+    pub semantic_errors: Vec<Error>,
+    /// Class scoping the styles, `None` without a `<style>`.
+    pub css_hash: Option<String>,
+    /// Names of the keyframes declared by the styles.
+    pub keyframes: Vec<String>,
+    css_annotations: CssAnnotations,
+    pub css_errors: Vec<Error>,
+}
+
+impl ComponentAnalysis {
+    /// Diagnostics of every analysis.
+    pub fn errors(&self) -> impl Iterator<Item = &Error> {
+        self.semantic_errors.iter().chain(&self.css_errors)
+    }
+}
+
+/// Analyzes components, reusing the results of the analyses whose
+/// [`Analyses::dependencies`] didn't change since the component's last
+/// analysis.
+///
+/// Changes are detected by fingerprinting the sections of the component, so
+/// callers only need to reparse it.
+#[derive(Debug, Default)]
+pub struct IncrementalAnalyzer {
+    components: FxHashMap<String, ComponentAnalysis>,
+    runes: Option<bool>,
+}
+
+impl IncrementalAnalyzer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Force runes mode on or off for the components without
+    /// `<svelte:options runes>`, see [`SemanticBuilder::with_runes`].
+    #[must_use]
+    pub fn with_runes(mut self, runes: Option<bool>) -> Self {
+        self.runes = runes;
+        self
+    }
+
+    /// Analyze the component `filename`, parsed from `source_text` into
+    /// `root`.
+    ///
+    /// The AST is annotated as if every analysis ran, the reused ones restore
+    /// the symbols, references and selector flags they set into `root`.
+    pub fn analyze<'a>(
+        &mut self,
+        filename: &str,
+        source_text: &'a str,
+        root: &mut Root<'a>,
+    ) -> &ComponentAnalysis {
+        self.analyze_with(filename, source_text, root, Sections::empty())
+    }
+
+    /// Replace the program of the `context` script of `root`, e.g. by the
+    /// output of a TypeScript transform, and rerun the analyses depending on
+    /// it. The others, like the CSS analysis, are kept.
+    ///
+    /// Returns the previous program, `None` without such a script.
+    pub fn replace_program<'a>(
+        &mut self,
+        filename: &str,
+        source_text: &'a str,
+        root: &mut Root<'a>,
+        context: &ScriptContext,
+        program: Program<'a>,
+    ) -> Option<Program<'a>> {
+        let (script, section) = match context {
+            ScriptContext::Default => (root.instance.as_mut()?, Sections::Instance),
+            ScriptContext::Module => (root.module.as_mut()?, Sections::Module),
+        };
+        let previous = script.replace_program(program);
+        self.analyze_with(filename, source_text, root, section);
+        Some(previous)
+    }
+
+    /// Analyze, rerunning the analyses depending on `replaced` sections even
+    /// if their source didn't change.
+    fn analyze_with<'a>(
+        &mut self,
+        filename: &str,
+        source_text: &'a str,
+        root: &mut Root<'a>,
+        replaced: Sections,
+    ) -> &ComponentAnalysis {
+        let fingerprints = Fingerprints::new(source_text, root);
+        let previous = self.components.remove(filename);
+        let invalidated = previous.as_ref().map_or(Analyses::all(), |previous| {
+            // A previously replaced program was reparsed from the source
+            let changed = previous.fingerprints.changed(&fingerprints) | previous.replaced;
+            Analyses::invalidated_by(changed | replaced)
+        });
+        let mut analysis = match previous {
+            Some(mut previous) => {
+                previous.fingerprints = fingerprints;
+                previous.replaced = replaced;
+                previous
+            }
+            None => ComponentAnalysis {
+                fingerprints,
+                replaced,
+                reanalyzed: Analyses::empty(),
+                semantic_annotations: SemanticAnnotations::default(),
+                semantic_errors: vec![],
+                css_hash: None,
+                keyframes: vec![],
+                css_annotations: CssAnnotations::default(),
+                css_errors: vec![],
+            },
+        };
+        analysis.reanalyzed = invalidated;
+
+        if invalidated.contains(Analyses::Semantic) {
+            analysis.semantic_errors =
+                SemanticBuilder::new(source_text).with_runes(self.runes).build(root).errors;
+            analysis.semantic_annotations = SemanticAnnotations::save(root);
+        } else {
+            analysis.semantic_annotations.restore(root);
+        }
+        let style = root.css.as_mut().filter(|style| style.raw.is_none());
+        if invalidated.contains(Analyses::Css) {
+            let ret = style.map(|style| {
+                let ret = Analyzer::new().build(&mut style.stylesheet);
+                analysis.css_annotations = CssAnnotations::save(&style.stylesheet);
+                ret
+            });
+            analysis.css_hash = ret.as_ref().map(|ret| ret.analysis.hash.clone());
+            analysis.keyframes = ret.as_ref().map_or(vec![], |ret| {
+                ret.analysis.keyframes.iter().map(ToString::to_string).collect()
+            });
+            analysis.css_errors = ret.map_or(vec![], |ret| ret.errors);
+        } else if let Some(style) = style {
+            analysis.css_annotations.restore(&style.stylesheet);
+        }
+
+        self.components.entry(filename.to_string()).or_insert(analysis)
+    }
+
+    /// The cached analysis of `filename`.
+    pub fn get(&self, filename: &str) -> Option<&ComponentAnalysis> {
+        self.components.get(filename)
+    }
+
+    /// Drop the cached analysis of `filename`, e.g. once it is closed.
+    pub fn remove(&mut self, filename: &str) {
+        self.components.remove(filename);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_ast::ast::{Expression, Statement};
+    use ssc_ast::ast::{FragmentNode, Tag};
+    use ssc_css_ast::ast::Rule;
+    use ssc_parser::Parser;
+
+    use super::*;
+
+    fn analyze(analyzer: &mut IncrementalAnalyzer, source: &str) -> (Analyses, usize) {
+        let allocator = Allocator::default();
+        let mut root = Parser::new(&allocator, source).parse().root;
+        let analysis = analyzer.analyze("App.svelte", source, &mut root);
+        (analysis.reanalyzed, analysis.errors().count())
+    }
+
+    #[test]
+    fn invalidation() {
+        let mut analyzer = IncrementalAnalyzer::new();
+        let source = "<script>const a = 1;</script>{a = 2}<style>p { color: red; }</style>";
+        assert_eq!(analyze(&mut analyzer, source), (Analyses::all(), 1));
+        assert_eq!(analyze(&mut analyzer, source), (Analyses::empty(), 1));
+
+        let source = "<script>const a = 1;</script>{a = 2}<style>p { color: blue; }</style>";
+        assert_eq!(analyze(&mut analyzer, source), (Analyses::Css, 1));
+
+        let source = "<script>let a = 1;</script>{a = 2}<style>p { color: blue; }</style>";
+        assert_eq!(analyze(&mut analyzer, source), (Analyses::all(), 0));
+
+        // Same styles, but moved
+        let source = "<script>let a = 1;</script>{a = 3}<style>p { color: blue; }</style>";
+        assert_eq!(analyze(&mut analyzer, source), (Analyses::Semantic, 0));
+        let source = "<script>let a = 1;</script>{a = 33}<style>p { color: blue; }</style>";
+        assert_eq!(analyze(&mut analyzer, source), (Analyses::all(), 0));
+
+        let css_hash = analyzer.get("App.svelte").unwrap().css_hash.clone();
+        assert!(css_hash.is_some_and(|hash| hash.starts_with("svelte-")));
+        analyzer.remove("App.svelte");
+        assert!(analyzer.get("App.svelte").is_none());
+    }
+
+    #[test]
+    fn replace_program() {
+        let mut analyzer = IncrementalAnalyzer::new();
+        let source = "<script>const a = 1;</script>{a = 2}<style>p { color: red; }</style>";
+        let allocator = Allocator::default();
+        let mut root = Parser::new(&allocator, source).parse().root;
+        assert_eq!(analyzer.analyze("App.svelte", source, &mut root).errors().count(), 1);
+
+        // `let a = 1;`, as a transform would output it
+        let program = Parser::new(&allocator, "<script>let a = 1;</script>")
+            .parse()
+            .root
+            .instance
+            .unwrap()
+            .program;
+        let previous = analyzer
+            .replace_program("App.svelte", source, &mut root, &ScriptContext::Default, program)
+            .unwrap();
+        assert_eq!(previous.body.len(), 1);
+        let analysis = analyzer.get("App.svelte").unwrap();
+        assert_eq!(analysis.reanalyzed, Analyses::Semantic);
+        assert_eq!(analysis.errors().count(), 0);
+        assert!(analyzer
+            .replace_program("App.svelte", source, &mut root, &ScriptContext::Module, previous)
+            .is_none());
+
+        // Reparsing restores the original program
+        assert_eq!(analyze(&mut analyzer, source), (Analyses::Semantic, 1));
+        assert_eq!(analyze(&mut analyzer, source), (Analyses::empty(), 1));
+    }
+
+    #[test]
+    fn restore_annotations() {
+        let mut analyzer = IncrementalAnalyzer::new();
+        let source = "<script>let a = 1;</script>{a}<style>:global(p) { color: red; }</style>";
+        let allocator = Allocator::default();
+        let mut root = Parser::new(&allocator, source).parse().root;
+        analyzer.analyze("App.svelte", source, &mut root);
+
+        let mut root = Parser::new(&allocator, source).parse().root;
+        assert_eq!(analyzer.analyze("App.svelte", source, &mut root).reanalyzed, Analyses::empty());
+        let Statement::VariableDeclaration(declaration) =
+            &root.instance.as_ref().unwrap().program.body[0]
+        else {
+            unreachable!()
+        };
+        let binding = declaration.declarations[0].id.get_binding_identifier().unwrap();
+        let Some(symbol_id) = binding.symbol_id.get() else { panic!("`a` has no symbol") };
+        let FragmentNode::Tag(Tag::ExpressionTag(tag)) = &root.fragment.nodes[0] else {
+            unreachable!()
+        };
+        let Expression::Identifier(ident) = &tag.expression else { unreachable!() };
+        assert!(ident.reference_id.get().is_some());
+        let Rule::StyleRule(rule) = &root.css.as_ref().unwrap().stylesheet.children[0] else {
+            unreachable!()
+        };
+        let selector = &rule.prelude.children[0];
+        assert!(selector.used.get());
+        assert!(selector.children[0].flags.get().has_global());
+
+        // A reanalyzed section doesn't change the annotations of the others
+        let source = "<script>let a = 1;</script>{a}<style>:global(p) { color: blue; }</style>";
+        let mut root = Parser::new(&allocator, source).parse().root;
+        assert_eq!(analyzer.analyze("App.svelte", source, &mut root).reanalyzed, Analyses::Css);
+        let Statement::VariableDeclaration(declaration) =
+            &root.instance.as_ref().unwrap().program.body[0]
+        else {
+            unreachable!()
+        };
+        let binding = declaration.declarations[0].id.get_binding_identifier().unwrap();
+        assert_eq!(binding.symbol_id.get(), Some(symbol_id));
+    }
+
+    #[test]
+    fn dependencies() {
+        assert_eq!(Analyses::invalidated_by(Sections::Style), Analyses::Css);
+        assert_eq!(Analyses::invalidated_by(Sections::Module), Analyses::Semantic);
+        assert_eq!(Analyses::invalidated_by(Sections::empty()), Analyses::empty());
+    }
+}
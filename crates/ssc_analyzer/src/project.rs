@@ -0,0 +1,360 @@
+//! Project graph
+//!
+//! The import graph of the components and `.svelte.js` modules of a
+//! project, with the analysis of each component attached. Files are added and
+//! updated one at a time, each is reanalyzed incrementally, see
+//! [`IncrementalAnalyzer`].
+
+use oxc_allocator::Allocator;
+use oxc_ast::{
+    ast::{ImportDeclarationSpecifier, ModuleExportName, Program, Statement},
+    syntax_directed_operations::BoundNames,
+};
+use oxc_diagnostics::{Error, Severity};
+use oxc_span::{Atom, SourceType, Span};
+use rustc_hash::FxHashMap;
+use ssc_parser::Parser;
+
+use crate::{ComponentAnalysis, IncrementalAnalyzer};
+
+/// Resolves the specifiers imported by the files of a [`Project`] to the
+/// filenames they're added with.
+///
+/// Implemented for closures, `|specifier, importer| Some(join(importer, specifier))`.
+pub trait ModuleResolver {
+    /// The filename `specifier`, imported by `importer`, refers to, or `None`
+    /// for the modules outside the project, e.g. packages.
+    fn resolve(&self, specifier: &str, importer: &str) -> Option<String>;
+}
+
+impl<F> ModuleResolver for F
+where
+    F: Fn(&str, &str) -> Option<String>,
+{
+    fn resolve(&self, specifier: &str, importer: &str) -> Option<String> {
+        self(specifier, importer)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileKind {
+    /// A `.svelte` component.
+    Component,
+    /// A `.svelte.js` or `.svelte.ts` module, which can use runes.
+    Module,
+}
+
+impl FileKind {
+    /// The kind of the file `filename`, `None` if it isn't part of a project.
+    pub fn from_filename(filename: &str) -> Option<Self> {
+        if filename.ends_with(".svelte") {
+            Some(Self::Component)
+        } else if filename.ends_with(".svelte.js") || filename.ends_with(".svelte.ts") {
+            Some(Self::Module)
+        } else {
+            None
+        }
+    }
+}
+
+/// A binding imported from a module.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ImportedName {
+    /// `import Button from './Button.svelte'`, the component of a `.svelte`
+    /// file.
+    Default,
+    /// `import * as utils from './utils.svelte.js'` or
+    /// `export * from './utils.svelte.js'`, any of the exports.
+    Namespace,
+    /// `import { count } from './store.svelte.js'`.
+    Named(String),
+}
+
+/// An `import` declaration, or an `export ... from` re-export.
+#[derive(Debug, Clone)]
+pub struct Import {
+    pub specifier: String,
+    /// The filename of `specifier`, see [`ModuleResolver`].
+    pub resolved: Option<String>,
+    /// Empty for side effect imports, `import './polyfill.js'`.
+    pub names: Vec<ImportedName>,
+    pub span: Span,
+}
+
+/// A name exported by a `.svelte.js` module, or by the module script of a
+/// component.
+#[derive(Debug, Clone)]
+pub struct Export {
+    /// `default` for default exports.
+    pub name: String,
+    pub span: Span,
+}
+
+/// A file of a [`Project`].
+#[derive(Debug)]
+pub struct ProjectFile {
+    pub kind: FileKind,
+    /// Imports of all the scripts of the file, type-only imports excluded.
+    pub imports: Vec<Import>,
+    pub exports: Vec<Export>,
+    /// Diagnostics of the parser, warnings included. The file isn't
+    /// analyzed when it doesn't parse.
+    pub parse_errors: Vec<Error>,
+}
+
+impl ProjectFile {
+    /// The resolved filenames of the imports.
+    pub fn dependencies(&self) -> impl Iterator<Item = &str> {
+        self.imports.iter().filter_map(|import| import.resolved.as_deref())
+    }
+}
+
+/// The files of a project and their import graph.
+///
+/// Filenames are opaque, the [`ModuleResolver`] decides how specifiers map
+/// to them.
+pub struct Project<'r> {
+    resolver: &'r dyn ModuleResolver,
+    analyzer: IncrementalAnalyzer,
+    files: FxHashMap<String, ProjectFile>,
+}
+
+impl<'r> Project<'r> {
+    pub fn new(resolver: &'r dyn ModuleResolver) -> Self {
+        Self { resolver, analyzer: IncrementalAnalyzer::new(), files: FxHashMap::default() }
+    }
+
+    /// Force runes mode on or off for the components without
+    /// `<svelte:options runes>`, see [`IncrementalAnalyzer::with_runes`].
+    #[must_use]
+    pub fn with_runes(mut self, runes: Option<bool>) -> Self {
+        self.analyzer = self.analyzer.with_runes(runes);
+        self
+    }
+
+    /// Add `filename` to the project, or update it, parsing and analyzing
+    /// `source_text`. Returns `None`, leaving the project as is, when
+    /// `filename` isn't a component or a module, see
+    /// [`FileKind::from_filename`].
+    pub fn update(&mut self, filename: &str, source_text: &str) -> Option<&ProjectFile> {
+        let kind = FileKind::from_filename(filename)?;
+        let allocator = Allocator::default();
+        let mut file = ProjectFile { kind, imports: vec![], exports: vec![], parse_errors: vec![] };
+        match kind {
+            FileKind::Component => {
+                let ret = Parser::new(&allocator, source_text).parse();
+                let mut root = ret.root;
+                if let Some(module) = &root.module {
+                    self.collect(filename, &module.program, &mut file, true);
+                }
+                if let Some(instance) = &root.instance {
+                    self.collect(filename, &instance.program, &mut file, false);
+                }
+                file.parse_errors = ret.errors.into_iter().map(Error::from).collect();
+                let parsed = file
+                    .parse_errors
+                    .iter()
+                    .all(|error| error.severity() == Some(Severity::Warning));
+                if parsed {
+                    self.analyzer.analyze(filename, source_text, &mut root);
+                } else {
+                    self.analyzer.remove(filename);
+                }
+            }
+            FileKind::Module => {
+                let source_type = SourceType::from_path(filename).unwrap_or_default();
+                let ret = oxc_parser::Parser::new(&allocator, source_text, source_type).parse();
+                self.collect(filename, &ret.program, &mut file, true);
+                file.parse_errors = ret.errors.into_iter().map(Error::from).collect();
+            }
+        }
+        self.files.insert(filename.to_string(), file);
+        self.files.get(filename)
+    }
+
+    /// Remove `filename` from the project, e.g. once it's deleted. The
+    /// imports of it by other files are kept.
+    pub fn remove(&mut self, filename: &str) {
+        self.files.remove(filename);
+        self.analyzer.remove(filename);
+    }
+
+    pub fn file(&self, filename: &str) -> Option<&ProjectFile> {
+        self.files.get(filename)
+    }
+
+    /// The files of the project, in no particular order.
+    pub fn files(&self) -> impl Iterator<Item = (&str, &ProjectFile)> {
+        self.files.iter().map(|(filename, file)| (filename.as_str(), file))
+    }
+
+    /// The analysis of the component `filename`, `None` for modules and
+    /// components that don't parse.
+    pub fn analysis(&self, filename: &str) -> Option<&ComponentAnalysis> {
+        self.analyzer.get(filename)
+    }
+
+    /// The files importing `filename`, with their imports of it.
+    pub fn importers<'p>(
+        &'p self,
+        filename: &'p str,
+    ) -> impl Iterator<Item = (&'p str, &'p Import)> + 'p {
+        self.files().flat_map(move |(importer, file)| {
+            file.imports
+                .iter()
+                .filter(move |import| import.resolved.as_deref() == Some(filename))
+                .map(move |import| (importer, import))
+        })
+    }
+
+    /// Collect the imports of `program`, and its exports if `exports`.
+    fn collect(
+        &self,
+        filename: &str,
+        program: &Program<'_>,
+        file: &mut ProjectFile,
+        exports: bool,
+    ) {
+        let mut push_import = |specifier: &Atom<'_>, names: Vec<ImportedName>, span: Span| {
+            file.imports.push(Import {
+                specifier: specifier.to_string(),
+                resolved: self.resolver.resolve(specifier, filename),
+                names,
+                span,
+            });
+        };
+        let mut exported = vec![];
+        for statement in &program.body {
+            match statement {
+                Statement::ImportDeclaration(decl) if !decl.import_kind.is_type() => {
+                    let names = decl.specifiers.iter().flatten().filter_map(|specifier| {
+                        Some(match specifier {
+                            ImportDeclarationSpecifier::ImportSpecifier(specifier) => {
+                                if specifier.import_kind.is_type() {
+                                    return None;
+                                }
+                                ImportedName::Named(export_name(&specifier.imported).to_string())
+                            }
+                            ImportDeclarationSpecifier::ImportDefaultSpecifier(_) => {
+                                ImportedName::Default
+                            }
+                            ImportDeclarationSpecifier::ImportNamespaceSpecifier(_) => {
+                                ImportedName::Namespace
+                            }
+                        })
+                    });
+                    push_import(&decl.source.value, names.collect(), decl.span);
+                }
+                Statement::ExportNamedDeclaration(decl) if !decl.export_kind.is_type() => {
+                    if let Some(declaration) = &decl.declaration {
+                        declaration.bound_names(&mut |ident| {
+                            exported
+                                .push(Export { name: ident.name.to_string(), span: ident.span });
+                        });
+                    }
+                    let specifiers =
+                        decl.specifiers.iter().filter(|specifier| !specifier.export_kind.is_type());
+                    for specifier in specifiers.clone() {
+                        let name = export_name(&specifier.exported).to_string();
+                        exported.push(Export { name, span: specifier.span });
+                    }
+                    if let Some(source) = &decl.source {
+                        let names = specifiers
+                            .map(|specifier| {
+                                ImportedName::Named(export_name(&specifier.local).to_string())
+                            })
+                            .collect();
+                        push_import(&source.value, names, decl.span);
+                    }
+                }
+                Statement::ExportDefaultDeclaration(decl) => {
+                    exported.push(Export { name: "default".to_string(), span: decl.span });
+                }
+                Statement::ExportAllDeclaration(decl) if !decl.export_kind.is_type() => {
+                    if let Some(name) = &decl.exported {
+                        exported
+                            .push(Export { name: export_name(name).to_string(), span: decl.span });
+                    }
+                    push_import(&decl.source.value, vec![ImportedName::Namespace], decl.span);
+                }
+                _ => {}
+            }
+        }
+        if exports {
+            file.exports.extend(exported);
+        }
+    }
+}
+
+fn export_name<'a>(name: &'a ModuleExportName<'_>) -> &'a str {
+    match name {
+        ModuleExportName::IdentifierName(ident) => &ident.name,
+        ModuleExportName::IdentifierReference(ident) => &ident.name,
+        ModuleExportName::StringLiteral(literal) => &literal.value,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn resolve(specifier: &str, _: &str) -> Option<String> {
+        specifier.strip_prefix("./").map(ToString::to_string)
+    }
+
+    #[test]
+    fn import_graph() {
+        let mut project = Project::new(&resolve);
+        let app = r#"<script lang="ts">
+            import Button from './Button.svelte';
+            import { count, type Count } from './store.svelte.js';
+            import * as format from 'date-fns';
+        </script>
+        <Button>{count}</Button>"#;
+        let file = project.update("App.svelte", app).unwrap();
+        assert_eq!(file.kind, FileKind::Component);
+        assert_eq!(
+            file.dependencies().collect::<Vec<_>>(),
+            vec!["Button.svelte", "store.svelte.js"]
+        );
+        assert_eq!(file.imports[1].names, vec![ImportedName::Named("count".to_string())]);
+        assert_eq!(file.imports[2].names, vec![ImportedName::Namespace]);
+        assert!(file.exports.is_empty());
+        assert!(project.analysis("App.svelte").is_some());
+
+        let button = r#"<script module>
+            export const sizes = ['small', 'large'];
+            export { sizes as default_sizes };
+        </script>
+        <script>export let size = 'small';</script>
+        <button class={size}><slot /></button>"#;
+        let file = project.update("Button.svelte", button).unwrap();
+        let exports = file.exports.iter().map(|export| export.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(exports, vec!["sizes", "default_sizes"]);
+
+        let store = "export let count = $state(0);\nexport * from './utils.svelte.js';";
+        let file = project.update("store.svelte.js", store).unwrap();
+        assert_eq!(file.kind, FileKind::Module);
+        assert_eq!(file.dependencies().collect::<Vec<_>>(), vec!["utils.svelte.js"]);
+        assert!(project.analysis("store.svelte.js").is_none());
+
+        assert!(project.update("main.js", "").is_none());
+        let importers =
+            project.importers("Button.svelte").map(|(importer, _)| importer).collect::<Vec<_>>();
+        assert_eq!(importers, vec!["App.svelte"]);
+
+        // Parser warnings don't prevent the analysis
+        let file = project.update("App.svelte", "<div />").unwrap();
+        assert_eq!(file.parse_errors.len(), 1);
+        assert!(project.analysis("App.svelte").is_some());
+
+        // Components that don't parse aren't analyzed
+        project.update("App.svelte", "<p>{</p>").unwrap();
+        assert!(project.analysis("App.svelte").is_none());
+        assert_eq!(project.importers("Button.svelte").count(), 0);
+
+        project.remove("Button.svelte");
+        assert!(project.file("Button.svelte").is_none());
+        assert_eq!(project.files().count(), 2);
+    }
+}
@@ -0,0 +1,102 @@
+use std::hash::{Hash, Hasher};
+
+use bitflags::bitflags;
+use oxc_span::Span;
+use rustc_hash::FxHasher;
+use ssc_ast::ast::Root;
+
+bitflags! {
+    /// Parts of a component.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Sections: u8 {
+        /// The `<script context="module">` element.
+        const Module   = 1 << 0;
+        /// The instance `<script>` element.
+        const Instance = 1 << 1;
+        /// Everything but the scripts and the style.
+        const Template = 1 << 2;
+        /// The `<style>` element.
+        const Style    = 1 << 3;
+    }
+}
+
+/// Fingerprints of the sections of a component.
+///
+/// A section is fingerprinted along with the offsets it starts at, as a
+/// section moving shifts the spans of everything reported in it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fingerprints {
+    module: Fingerprint,
+    instance: Fingerprint,
+    template: Fingerprint,
+    style: Fingerprint,
+}
+
+/// The hash of the parts of a section, and the parts themselves: comparing
+/// the hashes first is quick, but two sections are only the same once their
+/// source text is.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Fingerprint {
+    hash: u64,
+    /// The source text of the parts, and their start offsets.
+    parts: Vec<(u32, String)>,
+}
+
+impl Fingerprint {
+    fn new<'s, I: IntoIterator<Item = (u32, &'s str)>>(parts: I) -> Self {
+        let mut hasher = FxHasher::default();
+        let parts = parts
+            .into_iter()
+            .map(|(start, text)| {
+                start.hash(&mut hasher);
+                text.hash(&mut hasher);
+                (start, text.to_string())
+            })
+            .collect();
+        Self { hash: hasher.finish(), parts }
+    }
+}
+
+impl Fingerprints {
+    pub fn new(source_text: &str, root: &Root) -> Self {
+        let section = |span: Option<Span>| {
+            Fingerprint::new(span.map(|span| (span.start, span.source_text(source_text))))
+        };
+        let module = root.module.as_ref().map(|script| script.span);
+        let instance = root.instance.as_ref().map(|script| script.span);
+        let style = root.css.as_ref().map(|style| style.span);
+
+        // The template is whatever surrounds the other sections
+        let mut spans = [module, instance, style].into_iter().flatten().collect::<Vec<_>>();
+        spans.sort_unstable_by_key(|span| span.start);
+        let mut template = vec![];
+        #[allow(clippy::cast_possible_truncation)]
+        let source_end = source_text.len() as u32;
+        let mut start = 0;
+        for span in spans.into_iter().chain([Span::new(source_end, source_end)]) {
+            let end = span.start.max(start);
+            // Nothing can be reported in an empty part, e.g. after the style
+            if end > start {
+                template.push((start, Span::new(start, end).source_text(source_text)));
+            }
+            start = span.end.max(end);
+        }
+
+        Self {
+            module: section(module),
+            instance: section(instance),
+            template: Fingerprint::new(template),
+            style: section(style),
+        }
+    }
+
+    /// The sections that differ between `self` and `other`.
+    pub fn changed(&self, other: &Self) -> Sections {
+        let mut changed = Sections::empty();
+        changed.set(Sections::Module, self.module != other.module);
+        changed.set(Sections::Instance, self.instance != other.instance);
+        changed.set(Sections::Template, self.template != other.template);
+        changed.set(Sections::Style, self.style != other.style);
+        changed
+    }
+}
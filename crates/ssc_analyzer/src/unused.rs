@@ -0,0 +1,173 @@
+//! Unused components and exports
+//!
+//! Finds the components of a [`Project`] that no other file imports, and the
+//! exports of its modules and module scripts that no other file imports, so
+//! they can be deleted. Deleting them may leave more of them unused.
+
+use std::fmt::Write as _;
+
+use oxc_span::Span;
+
+use crate::project::{FileKind, ImportedName, Project};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnusedKind {
+    /// A component no other file imports.
+    Component,
+    /// An export no other file imports.
+    Export,
+}
+
+/// A component or an export found by [`Project::unused`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unused {
+    pub kind: UnusedKind,
+    pub filename: String,
+    /// The exported name, `None` for components.
+    pub name: Option<String>,
+    /// The span of the export, `None` for components.
+    pub span: Option<Span>,
+}
+
+impl<'r> Project<'r> {
+    /// The unused components and exports of the project, by filename, then
+    /// offset. The components `is_entry` returns `true` for, e.g. `App.svelte`
+    /// or routes, are used by definition.
+    ///
+    /// Files that don't parse may not have all their imports, so what they
+    /// import can be reported as unused.
+    pub fn unused<F: Fn(&str) -> bool>(&self, is_entry: F) -> Vec<Unused> {
+        let mut unused = vec![];
+        for (filename, file) in self.files() {
+            let imports = self
+                .importers(filename)
+                .filter(|(importer, _)| *importer != filename)
+                .map(|(_, import)| import)
+                .collect::<Vec<_>>();
+            if file.kind == FileKind::Component && imports.is_empty() && !is_entry(filename) {
+                unused.push(Unused {
+                    kind: UnusedKind::Component,
+                    filename: filename.to_string(),
+                    name: None,
+                    span: None,
+                });
+            }
+            for export in &file.exports {
+                let is_used =
+                    imports.iter().flat_map(|import| &import.names).any(|name| match name {
+                        ImportedName::Namespace => true,
+                        // The default import of a component is the component
+                        ImportedName::Default => {
+                            file.kind == FileKind::Module && export.name == "default"
+                        }
+                        ImportedName::Named(name) => *name == export.name,
+                    });
+                if !is_used {
+                    unused.push(Unused {
+                        kind: UnusedKind::Export,
+                        filename: filename.to_string(),
+                        name: Some(export.name.clone()),
+                        span: Some(export.span),
+                    });
+                }
+            }
+        }
+        unused.sort_by(|a, b| {
+            (&a.filename, a.span.map(|span| span.start))
+                .cmp(&(&b.filename, b.span.map(|span| span.start)))
+        });
+        unused
+    }
+}
+
+/// `unused` as a JSON array, with one object per component or export, e.g.
+/// `{"file":"lib/format.svelte.js","kind":"export","name":"pad","start":7,"end":10}`.
+///
+/// `name`, `start` and `end` are `null` for components.
+pub fn unused_json(unused: &[Unused]) -> String {
+    let entries = unused
+        .iter()
+        .map(|unused| {
+            let kind = match unused.kind {
+                UnusedKind::Component => "component",
+                UnusedKind::Export => "export",
+            };
+            let number = |value: Option<u32>| value.map_or("null".into(), |n| n.to_string());
+            format!(
+                r#"{{"file":{},"kind":"{kind}","name":{},"start":{},"end":{}}}"#,
+                json_string(&unused.filename),
+                unused.name.as_deref().map_or("null".into(), json_string),
+                number(unused.span.map(|span| span.start)),
+                number(unused.span.map(|span| span.end)),
+            )
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", entries.join(","))
+}
+
+/// `value` as a JSON string literal.
+fn json_string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn resolve(specifier: &str, _: &str) -> Option<String> {
+        specifier.strip_prefix("./").map(ToString::to_string)
+    }
+
+    #[test]
+    fn unused() {
+        let mut project = Project::new(&resolve);
+        project.update(
+            "App.svelte",
+            "<script>import Button, { sizes } from './Button.svelte';\nimport * as format from './format.svelte.js';</script><Button />",
+        );
+        project.update(
+            "Button.svelte",
+            "<script module>export const sizes = [];\nexport const colors = [];</script><button />",
+        );
+        project.update("Dead.svelte", "<script>import { pad } from './pad.svelte.js';</script>");
+        project.update("format.svelte.js", "export const date = 1;");
+        project.update("pad.svelte.js", "export const pad = 1;\nexport default pad;");
+
+        let unused = project.unused(|filename| filename == "App.svelte");
+        let names = unused
+            .iter()
+            .map(|unused| (unused.kind, unused.filename.as_str(), unused.name.as_deref()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                (UnusedKind::Export, "Button.svelte", Some("colors")),
+                (UnusedKind::Component, "Dead.svelte", None),
+                (UnusedKind::Export, "pad.svelte.js", Some("default")),
+            ]
+        );
+        assert_eq!(
+            unused_json(&unused[1..2]),
+            r#"[{"file":"Dead.svelte","kind":"component","name":null,"start":null,"end":null}]"#
+        );
+        let json = unused_json(&unused[..1]);
+        assert!(json
+            .starts_with(r#"[{"file":"Button.svelte","kind":"export","name":"colors","start":"#));
+    }
+}
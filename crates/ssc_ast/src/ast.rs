@@ -1,6 +1,8 @@
 // Silence erroneous warnings from Rust Analyser for `#[derive(Tsify)]`
 #![allow(non_snake_case)]
 
+use std::sync::atomic::{AtomicUsize, Ordering};
+
 use bitflags::bitflags;
 use oxc_allocator::Vec;
 use oxc_ast::ast::{
@@ -10,12 +12,10 @@ use oxc_ast::ast::{
 };
 use oxc_index::define_index_type;
 use oxc_span::{Atom, Span};
-use oxc_syntax::reference::ReferenceId;
 use rustc_hash::FxHashMap;
 #[cfg(feature = "serialize")]
 use serde::Serialize;
 use ssc_css_ast::ast::StyleSheet;
-use std::cell::Cell;
 #[cfg(feature = "serialize")]
 use tsify::Tsify;
 
@@ -67,6 +67,7 @@ pub struct Fragment<'a> {
 #[cfg_attr(feature = "serialize", serde(untagged))]
 pub enum FragmentNode<'a> {
     Text(Text<'a>),
+    Comment(Comment<'a>),
     Tag(Tag<'a>),
     Element(Element<'a>),
     Block(Block<'a>),
@@ -82,6 +83,16 @@ pub struct Text<'a> {
     pub raw: Atom<'a>,
 }
 
+/// `<!-- data -->`
+#[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Tsify))]
+#[cfg_attr(feature = "serialize", serde(tag = "type"))]
+pub struct Comment<'a> {
+    #[cfg_attr(feature = "serialize", serde(flatten))]
+    pub span: Span,
+    pub data: Atom<'a>,
+}
+
 #[derive(Debug)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Tsify))]
 #[cfg_attr(feature = "serialize", serde(untagged))]
@@ -101,7 +112,7 @@ pub struct ExpressionTag<'a> {
     pub span: Span,
     pub expression: Expression<'a>,
     #[cfg_attr(feature = "serialize", serde(skip))]
-    pub flags: Cell<ExpressionTagFlags>,
+    pub node_id: NodeId,
 }
 
 #[derive(Debug)]
@@ -157,6 +168,7 @@ pub enum Element<'a> {
     SlotElement(SlotElement<'a>),
     RegularElement(RegularElement<'a>),
     SvelteBody(SvelteBody<'a>),
+    SvelteBoundary(SvelteBoundary<'a>),
     SvelteComponent(SvelteComponent<'a>),
     SvelteDocument(SvelteDocument<'a>),
     SvelteElement(SvelteElement<'a>),
@@ -167,6 +179,65 @@ pub enum Element<'a> {
     SvelteWindow(SvelteWindow<'a>),
 }
 
+impl<'a> Element<'a> {
+    pub fn attributes(&self) -> &[ElementAttribute<'a>] {
+        match self {
+            Element::Component(element) => &element.attributes,
+            Element::TitleElement(element) => &element.attributes,
+            Element::SlotElement(element) => &element.attributes,
+            Element::RegularElement(element) => &element.attributes,
+            Element::SvelteBody(element) => &element.attributes,
+            Element::SvelteBoundary(element) => &element.attributes,
+            Element::SvelteComponent(element) => &element.attributes,
+            Element::SvelteDocument(element) => &element.attributes,
+            Element::SvelteElement(element) => &element.attributes,
+            Element::SvelteFragment(element) => &element.attributes,
+            Element::SvelteHead(element) => &element.attributes,
+            Element::SvelteOptionsRaw(element) => &element.attributes,
+            Element::SvelteSelf(element) => &element.attributes,
+            Element::SvelteWindow(element) => &element.attributes,
+        }
+    }
+
+    pub fn attributes_mut(&mut self) -> &mut Vec<'a, ElementAttribute<'a>> {
+        match self {
+            Element::Component(element) => &mut element.attributes,
+            Element::TitleElement(element) => &mut element.attributes,
+            Element::SlotElement(element) => &mut element.attributes,
+            Element::RegularElement(element) => &mut element.attributes,
+            Element::SvelteBody(element) => &mut element.attributes,
+            Element::SvelteBoundary(element) => &mut element.attributes,
+            Element::SvelteComponent(element) => &mut element.attributes,
+            Element::SvelteDocument(element) => &mut element.attributes,
+            Element::SvelteElement(element) => &mut element.attributes,
+            Element::SvelteFragment(element) => &mut element.attributes,
+            Element::SvelteHead(element) => &mut element.attributes,
+            Element::SvelteOptionsRaw(element) => &mut element.attributes,
+            Element::SvelteSelf(element) => &mut element.attributes,
+            Element::SvelteWindow(element) => &mut element.attributes,
+        }
+    }
+
+    pub fn fragment(&self) -> &Fragment<'a> {
+        match self {
+            Element::Component(element) => &element.fragment,
+            Element::TitleElement(element) => &element.fragment,
+            Element::SlotElement(element) => &element.fragment,
+            Element::RegularElement(element) => &element.fragment,
+            Element::SvelteBody(element) => &element.fragment,
+            Element::SvelteBoundary(element) => &element.fragment,
+            Element::SvelteComponent(element) => &element.fragment,
+            Element::SvelteDocument(element) => &element.fragment,
+            Element::SvelteElement(element) => &element.fragment,
+            Element::SvelteFragment(element) => &element.fragment,
+            Element::SvelteHead(element) => &element.fragment,
+            Element::SvelteOptionsRaw(element) => &element.fragment,
+            Element::SvelteSelf(element) => &element.fragment,
+            Element::SvelteWindow(element) => &element.fragment,
+        }
+    }
+}
+
 #[derive(Debug)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Tsify))]
 #[cfg_attr(feature = "serialize", serde(untagged))]
@@ -267,7 +338,7 @@ pub struct RegularElement<'a> {
     pub attributes: Vec<'a, ElementAttribute<'a>>,
     pub fragment: Fragment<'a>,
     #[cfg_attr(feature = "serialize", serde(skip))]
-    pub flags: Cell<RegularElementFlags>,
+    pub node_id: NodeId,
 }
 
 #[derive(Debug)]
@@ -280,6 +351,18 @@ pub struct SvelteBody<'a> {
     pub fragment: Fragment<'a>,
 }
 
+/// `<svelte:boundary>`, which renders its `failed` snippet instead of its
+/// content when rendering or updating the content throws.
+#[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Tsify))]
+#[cfg_attr(feature = "serialize", serde(tag = "type"))]
+pub struct SvelteBoundary<'a> {
+    #[cfg_attr(feature = "serialize", serde(flatten))]
+    pub span: Span,
+    pub attributes: Vec<'a, ElementAttribute<'a>>,
+    pub fragment: Fragment<'a>,
+}
+
 #[derive(Debug)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Tsify))]
 #[cfg_attr(feature = "serialize", serde(tag = "type"))]
@@ -311,7 +394,7 @@ pub struct SvelteElement<'a> {
     pub fragment: Fragment<'a>,
     pub expression: Expression<'a>,
     #[cfg_attr(feature = "serialize", serde(skip))]
-    pub flags: Cell<SvelteElementFlags>,
+    pub node_id: NodeId,
 }
 
 #[derive(Debug)]
@@ -375,6 +458,22 @@ pub enum Block<'a> {
     SnippetBlock(SnippetBlock<'a>),
 }
 
+impl<'a> Block<'a> {
+    /// The branches of the block, in source order.
+    pub fn fragments(&self) -> impl DoubleEndedIterator<Item = &Fragment<'a>> + '_ {
+        let fragments = match self {
+            Block::EachBlock(block) => [Some(&block.body), block.fallback.as_ref(), None],
+            Block::IfBlock(block) => [Some(&block.consequent), block.alternate.as_ref(), None],
+            Block::AwaitBlock(block) => {
+                [block.pending.as_ref(), block.then.as_ref(), block.catch.as_ref()]
+            }
+            Block::KeyBlock(block) => [Some(&block.fragment), None, None],
+            Block::SnippetBlock(block) => [Some(&block.body), None, None],
+        };
+        fragments.into_iter().flatten()
+    }
+}
+
 #[derive(Debug)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Tsify))]
 #[cfg_attr(feature = "serialize", serde(tag = "type"))]
@@ -446,6 +545,9 @@ pub struct Style<'a> {
     pub span: Span,
     pub attributes: Vec<'a, Attribute<'a>>,
     pub stylesheet: StyleSheet<'a>,
+    /// The content as written, when its `lang` isn't CSS and no preprocessor
+    /// turned it into CSS. `stylesheet` is then empty.
+    pub raw: Option<Atom<'a>>,
 }
 
 #[derive(Debug)]
@@ -534,6 +636,19 @@ pub struct Script<'a> {
     #[cfg_attr(feature = "serialize", serde(rename = "content"))]
     pub program: Program<'a>,
     pub attributes: Vec<'a, Attribute<'a>>,
+    /// The content as written, when its `lang` is neither JavaScript nor
+    /// TypeScript and no preprocessor compiled it. `program` is then empty.
+    pub raw: Option<Atom<'a>>,
+}
+
+impl<'a> Script<'a> {
+    /// Replace the program, e.g. by the output of a TypeScript transform,
+    /// returning the previous one.
+    ///
+    /// Analyses of the script must be rerun afterwards.
+    pub fn replace_program(&mut self, program: Program<'a>) -> Program<'a> {
+        std::mem::replace(&mut self.program, program)
+    }
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -544,6 +659,23 @@ pub enum ScriptContext {
     Module,
 }
 
+/// Spans of the parts of an attribute or a directive, e.g. `on`, `click`,
+/// `|once`, `=` and `{f}` in `on:click|once={f}`.
+///
+/// Empty for attributes created by transforms.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AttributeParts {
+    /// The directive type before the colon, `None` for attributes.
+    pub prefix: Option<Span>,
+    pub name: Span,
+    /// The modifiers, with their leading `|`.
+    pub modifiers: Option<Span>,
+    pub equals: Option<Span>,
+    /// The value, with its quotes or braces. For the `{name}` shorthand, the
+    /// whole attribute.
+    pub value: Option<Span>,
+}
+
 #[derive(Debug)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Tsify))]
 #[cfg_attr(feature = "serialize", serde(tag = "type"))]
@@ -552,6 +684,8 @@ pub struct Attribute<'a> {
     pub span: Span,
     pub name: Atom<'a>,
     pub value: Option<AttributeValue<'a>>,
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub parts: AttributeParts,
 }
 
 #[derive(Debug)]
@@ -579,7 +713,7 @@ pub struct SpreadAttribute<'a> {
     pub span: Span,
     pub expression: Expression<'a>,
     #[cfg_attr(feature = "serialize", serde(skip))]
-    pub flags: Cell<ExpressionTagFlags>,
+    pub node_id: NodeId,
 }
 
 #[derive(Debug)]
@@ -596,6 +730,34 @@ pub enum DirectiveAttribute<'a> {
     UseDirective(UseDirective<'a>),
 }
 
+impl<'a> DirectiveAttribute<'a> {
+    pub fn parts(&self) -> &AttributeParts {
+        match self {
+            DirectiveAttribute::AnimateDirective(directive) => &directive.parts,
+            DirectiveAttribute::BindDirective(directive) => &directive.parts,
+            DirectiveAttribute::ClassDirective(directive) => &directive.parts,
+            DirectiveAttribute::LetDirective(directive) => &directive.parts,
+            DirectiveAttribute::OnDirective(directive) => &directive.parts,
+            DirectiveAttribute::StyleDirective(directive) => &directive.parts,
+            DirectiveAttribute::TransitionDirective(directive) => &directive.parts,
+            DirectiveAttribute::UseDirective(directive) => &directive.parts,
+        }
+    }
+
+    pub fn parts_mut(&mut self) -> &mut AttributeParts {
+        match self {
+            DirectiveAttribute::AnimateDirective(directive) => &mut directive.parts,
+            DirectiveAttribute::BindDirective(directive) => &mut directive.parts,
+            DirectiveAttribute::ClassDirective(directive) => &mut directive.parts,
+            DirectiveAttribute::LetDirective(directive) => &mut directive.parts,
+            DirectiveAttribute::OnDirective(directive) => &mut directive.parts,
+            DirectiveAttribute::StyleDirective(directive) => &mut directive.parts,
+            DirectiveAttribute::TransitionDirective(directive) => &mut directive.parts,
+            DirectiveAttribute::UseDirective(directive) => &mut directive.parts,
+        }
+    }
+}
+
 #[derive(Debug)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Tsify))]
 #[cfg_attr(feature = "serialize", serde(tag = "type"))]
@@ -604,6 +766,8 @@ pub struct AnimateDirective<'a> {
     pub span: Span,
     pub name: Atom<'a>,
     pub expression: Option<Expression<'a>>,
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub parts: AttributeParts,
 }
 
 #[derive(Debug)]
@@ -615,8 +779,9 @@ pub struct BindDirective<'a> {
     pub name: Atom<'a>,
     pub expression: BindDirectiveExpression<'a>,
     #[cfg_attr(feature = "serialize", serde(skip))]
-    pub binding_group_name: Cell<Option<ReferenceId>>,
-    pub parent_block: Cell<Option<BlockId>>,
+    pub node_id: NodeId,
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub parts: AttributeParts,
 }
 
 #[derive(Debug)]
@@ -635,6 +800,8 @@ pub struct ClassDirective<'a> {
     pub span: Span,
     pub name: Atom<'a>,
     pub expression: Expression<'a>,
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub parts: AttributeParts,
 }
 
 #[derive(Debug)]
@@ -645,6 +812,8 @@ pub struct LetDirective<'a> {
     pub span: Span,
     pub name: Atom<'a>,
     pub expression: Option<LetDirectiveExpression<'a>>,
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub parts: AttributeParts,
 }
 
 #[derive(Debug)]
@@ -666,6 +835,8 @@ pub struct OnDirective<'a> {
     pub expression: Option<Expression<'a>>,
     // TODO: use concrete type instead of Atom
     pub modifiers: Vec<'a, Atom<'a>>,
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub parts: AttributeParts,
 }
 
 #[derive(Debug)]
@@ -678,7 +849,9 @@ pub struct StyleDirective<'a> {
     pub value: Option<AttributeValue<'a>>,
     pub modifiers: Vec<'a, StyleDirectiveModifier>,
     #[cfg_attr(feature = "serialize", serde(skip))]
-    pub dynamic: Cell<bool>,
+    pub node_id: NodeId,
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub parts: AttributeParts,
 }
 
 #[derive(Debug)]
@@ -699,6 +872,8 @@ pub struct TransitionDirective<'a> {
     pub modifiers: Vec<'a, TransitionDirectiveModifier>,
     pub intro: bool,
     pub outro: bool,
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub parts: AttributeParts,
 }
 
 #[derive(Debug)]
@@ -717,6 +892,8 @@ pub struct UseDirective<'a> {
     pub span: Span,
     pub name: Atom<'a>,
     pub expression: Option<Expression<'a>>,
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub parts: AttributeParts,
 }
 
 define_index_type! {
@@ -727,6 +904,22 @@ define_index_type! {
     pub struct SlotElementId = usize;
 }
 
+define_index_type! {
+    /// The identity of a node the analysis keeps metadata about, see
+    /// [`NodeId::next`].
+    pub struct NodeId = usize;
+}
+
+impl NodeId {
+    /// A new id, different from all the ids returned before in the process,
+    /// so that the nodes a transform builds never take the id of a parsed
+    /// node.
+    pub fn next() -> Self {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        Self::from_usize(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
 bitflags! {
     #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
     pub struct ExpressionTagFlags: u8 {
@@ -1,4 +1,4 @@
-use std::{cell::Cell, mem};
+use std::mem;
 
 use oxc_allocator::{Allocator, Box, String, Vec};
 use oxc_ast::ast::{
@@ -90,8 +90,9 @@ impl<'a> AstBuilder<'a> {
         context: ScriptContext,
         program: Program<'a>,
         attributes: Vec<'a, Attribute<'a>>,
+        raw: Option<Atom<'a>>,
     ) -> Script<'a> {
-        Script { span, context, program, attributes }
+        Script { span, context, program, attributes, raw }
     }
 
     #[inline]
@@ -100,8 +101,9 @@ impl<'a> AstBuilder<'a> {
         span: Span,
         stylesheet: StyleSheet<'a>,
         attributes: Vec<'a, Attribute<'a>>,
+        raw: Option<Atom<'a>>,
     ) -> Style<'a> {
-        Style { span, attributes, stylesheet }
+        Style { span, attributes, stylesheet, raw }
     }
 
     #[inline]
@@ -148,7 +150,7 @@ impl<'a> AstBuilder<'a> {
             name,
             attributes,
             fragment,
-            flags: Cell::new(RegularElementFlags::empty()),
+            node_id: NodeId::next(),
         })
     }
 
@@ -162,6 +164,16 @@ impl<'a> AstBuilder<'a> {
         Element::SvelteBody(SvelteBody { span, attributes, fragment })
     }
 
+    #[inline]
+    pub fn svelte_boundary(
+        &self,
+        span: Span,
+        attributes: Vec<'a, ElementAttribute<'a>>,
+        fragment: Fragment<'a>,
+    ) -> Element<'a> {
+        Element::SvelteBoundary(SvelteBoundary { span, attributes, fragment })
+    }
+
     #[inline]
     pub fn svelte_component(
         &self,
@@ -196,7 +208,7 @@ impl<'a> AstBuilder<'a> {
             attributes,
             fragment,
             expression,
-            flags: Cell::new(SvelteElementFlags::empty()),
+            node_id: NodeId::next(),
         })
     }
 
@@ -230,6 +242,22 @@ impl<'a> AstBuilder<'a> {
         Element::SvelteOptionsRaw(SvelteOptionsRaw { span, attributes, fragment })
     }
 
+    /// The options of a `<svelte:options>` element at `span`, none of them
+    /// set, to fill in and put in [`Root::options`].
+    #[inline]
+    pub fn options(&self, span: Span) -> SvelteOptions<'a> {
+        SvelteOptions {
+            span,
+            runes: None,
+            immutable: None,
+            accessors: None,
+            preserve_whitespace: None,
+            namespace: None,
+            custom_element: None,
+            attributes: self.new_vec(),
+        }
+    }
+
     #[inline]
     pub fn svelte_self(
         &self,
@@ -255,6 +283,11 @@ impl<'a> AstBuilder<'a> {
         Text { span, data: raw.clone(), raw }
     }
 
+    #[inline]
+    pub fn comment(&self, span: Span, data: Atom<'a>) -> Comment<'a> {
+        Comment { span, data }
+    }
+
     #[inline]
     pub fn attribute(
         &self,
@@ -262,7 +295,7 @@ impl<'a> AstBuilder<'a> {
         name: Atom<'a>,
         value: Option<AttributeValue<'a>>,
     ) -> Attribute<'a> {
-        Attribute { span, name, value }
+        Attribute { span, name, value, parts: AttributeParts::default() }
     }
 
     #[inline]
@@ -294,7 +327,7 @@ impl<'a> AstBuilder<'a> {
 
     #[inline]
     pub fn spread_attribute(&self, span: Span, expression: Expression<'a>) -> SpreadAttribute<'a> {
-        SpreadAttribute { span, expression, flags: Cell::new(ExpressionTagFlags::empty()) }
+        SpreadAttribute { span, expression, node_id: NodeId::next() }
     }
 
     #[inline]
@@ -304,7 +337,12 @@ impl<'a> AstBuilder<'a> {
         name: Atom<'a>,
         expression: Option<Expression<'a>>,
     ) -> DirectiveAttribute<'a> {
-        DirectiveAttribute::AnimateDirective(AnimateDirective { span, name, expression })
+        DirectiveAttribute::AnimateDirective(AnimateDirective {
+            span,
+            name,
+            expression,
+            parts: AttributeParts::default(),
+        })
     }
 
     #[inline]
@@ -318,8 +356,8 @@ impl<'a> AstBuilder<'a> {
             span,
             name,
             expression,
-            binding_group_name: Cell::new(None),
-            parent_block: Cell::new(None),
+            node_id: NodeId::next(),
+            parts: AttributeParts::default(),
         })
     }
 
@@ -330,7 +368,12 @@ impl<'a> AstBuilder<'a> {
         name: Atom<'a>,
         expression: Expression<'a>,
     ) -> DirectiveAttribute<'a> {
-        DirectiveAttribute::ClassDirective(ClassDirective { span, name, expression })
+        DirectiveAttribute::ClassDirective(ClassDirective {
+            span,
+            name,
+            expression,
+            parts: AttributeParts::default(),
+        })
     }
 
     #[inline]
@@ -340,7 +383,12 @@ impl<'a> AstBuilder<'a> {
         name: Atom<'a>,
         expression: Option<LetDirectiveExpression<'a>>,
     ) -> DirectiveAttribute<'a> {
-        DirectiveAttribute::LetDirective(LetDirective { span, name, expression })
+        DirectiveAttribute::LetDirective(LetDirective {
+            span,
+            name,
+            expression,
+            parts: AttributeParts::default(),
+        })
     }
 
     #[inline]
@@ -351,7 +399,13 @@ impl<'a> AstBuilder<'a> {
         expression: Option<Expression<'a>>,
         modifiers: Vec<'a, Atom<'a>>,
     ) -> DirectiveAttribute<'a> {
-        DirectiveAttribute::OnDirective(OnDirective { span, name, expression, modifiers })
+        DirectiveAttribute::OnDirective(OnDirective {
+            span,
+            name,
+            expression,
+            modifiers,
+            parts: AttributeParts::default(),
+        })
     }
 
     #[inline]
@@ -367,7 +421,8 @@ impl<'a> AstBuilder<'a> {
             name,
             value,
             modifiers,
-            dynamic: Cell::new(false),
+            node_id: NodeId::next(),
+            parts: AttributeParts::default(),
         })
     }
 
@@ -388,6 +443,7 @@ impl<'a> AstBuilder<'a> {
             modifiers,
             intro,
             outro,
+            parts: AttributeParts::default(),
         })
     }
 
@@ -398,12 +454,17 @@ impl<'a> AstBuilder<'a> {
         name: Atom<'a>,
         expression: Option<Expression<'a>>,
     ) -> DirectiveAttribute<'a> {
-        DirectiveAttribute::UseDirective(UseDirective { span, name, expression })
+        DirectiveAttribute::UseDirective(UseDirective {
+            span,
+            name,
+            expression,
+            parts: AttributeParts::default(),
+        })
     }
 
     #[inline]
     pub fn expression_tag(&self, span: Span, expression: Expression<'a>) -> ExpressionTag<'a> {
-        ExpressionTag { span, expression, flags: Cell::new(ExpressionTagFlags::empty()) }
+        ExpressionTag { span, expression, node_id: NodeId::next() }
     }
 
     #[inline]
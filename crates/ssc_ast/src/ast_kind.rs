@@ -3,6 +3,12 @@ use oxc_span::{GetSpan, Span};
 #[allow(clippy::wildcard_imports)]
 use crate::ast::*;
 
+/// A node with its own [`AstKind`] variant.
+pub trait AstNode<'a> {
+    /// The node wrapped by `kind`, if it is of this type.
+    fn cast(kind: AstKind<'a>) -> Option<&'a Self>;
+}
+
 macro_rules! ast_kinds {
     { $($ident:ident($type:ty),)* } => (
         #[derive(Debug, Clone, Copy)]
@@ -13,37 +19,62 @@ macro_rules! ast_kinds {
         /// Untyped AST Node Kind
         #[derive(Debug, Clone, Copy)]
         pub enum AstKind<'a> {
-            $($ident($type),)*
+            $($ident(&'a $type),)*
         }
+
+        $(
+            impl<'a> AstNode<'a> for $type {
+                fn cast(kind: AstKind<'a>) -> Option<&'a Self> {
+                    match kind {
+                        AstKind::$ident(node) => Some(node),
+                        _ => None,
+                    }
+                }
+            }
+        )*
     )
 }
 
 ast_kinds! {
-    Root(&'a Root<'a>),
-    Text(&'a Text<'a>),
-    ExpressionTag(&'a ExpressionTag<'a>),
-    HtmlTag(&'a HtmlTag<'a>),
-    ConstTag(&'a ConstTag<'a>),
-    DebugTag(&'a DebugTag<'a>),
-    RenderTag(&'a RenderTag<'a>),
-    Component(&'a Component<'a>),
-    TitleElement(&'a TitleElement<'a>),
-    SlotElement(&'a SlotElement<'a>),
-    RegularElement(&'a RegularElement<'a>),
-    SvelteBody(&'a SvelteBody<'a>),
-    SvelteComponent(&'a SvelteComponent<'a>),
-    SvelteDocument(&'a SvelteDocument<'a>),
-    SvelteElement(&'a SvelteElement<'a>),
-    SvelteFragment(&'a SvelteFragment<'a>),
-    SvelteHead(&'a SvelteHead<'a>),
-    SvelteOptionsRaw(&'a SvelteOptionsRaw<'a>),
-    SvelteSelf(&'a SvelteSelf<'a>),
-    SvelteWindow(&'a SvelteWindow<'a>),
-    EachBlock(&'a EachBlock<'a>),
-    IfBlock(&'a IfBlock<'a>),
-    AwaitBlock(&'a AwaitBlock<'a>),
-    KeyBlock(&'a KeyBlock<'a>),
-    SnippetBlock(&'a SnippetBlock<'a>),
+    Root(Root<'a>),
+    Text(Text<'a>),
+    Comment(Comment<'a>),
+    ExpressionTag(ExpressionTag<'a>),
+    HtmlTag(HtmlTag<'a>),
+    ConstTag(ConstTag<'a>),
+    DebugTag(DebugTag<'a>),
+    RenderTag(RenderTag<'a>),
+    Component(Component<'a>),
+    TitleElement(TitleElement<'a>),
+    SlotElement(SlotElement<'a>),
+    RegularElement(RegularElement<'a>),
+    SvelteBody(SvelteBody<'a>),
+    SvelteBoundary(SvelteBoundary<'a>),
+    SvelteComponent(SvelteComponent<'a>),
+    SvelteDocument(SvelteDocument<'a>),
+    SvelteElement(SvelteElement<'a>),
+    SvelteFragment(SvelteFragment<'a>),
+    SvelteHead(SvelteHead<'a>),
+    SvelteOptionsRaw(SvelteOptionsRaw<'a>),
+    SvelteSelf(SvelteSelf<'a>),
+    SvelteWindow(SvelteWindow<'a>),
+    EachBlock(EachBlock<'a>),
+    IfBlock(IfBlock<'a>),
+    AwaitBlock(AwaitBlock<'a>),
+    KeyBlock(KeyBlock<'a>),
+    SnippetBlock(SnippetBlock<'a>),
+    Attribute(Attribute<'a>),
+    SpreadAttribute(SpreadAttribute<'a>),
+    AnimateDirective(AnimateDirective<'a>),
+    BindDirective(BindDirective<'a>),
+    ClassDirective(ClassDirective<'a>),
+    LetDirective(LetDirective<'a>),
+    OnDirective(OnDirective<'a>),
+    StyleDirective(StyleDirective<'a>),
+    TransitionDirective(TransitionDirective<'a>),
+    UseDirective(UseDirective<'a>),
+    Script(Script<'a>),
+    Style(Style<'a>),
 }
 
 #[allow(unsafe_code)]
@@ -63,6 +94,7 @@ impl<'a> GetSpan for AstKind<'a> {
         match self {
             Self::Root(x) => x.span,
             Self::Text(x) => x.span,
+            Self::Comment(x) => x.span,
             Self::ExpressionTag(x) => x.span,
             Self::HtmlTag(x) => x.span,
             Self::ConstTag(x) => x.span,
@@ -73,6 +105,7 @@ impl<'a> GetSpan for AstKind<'a> {
             Self::SlotElement(x) => x.span,
             Self::RegularElement(x) => x.span,
             Self::SvelteBody(x) => x.span,
+            Self::SvelteBoundary(x) => x.span,
             Self::SvelteComponent(x) => x.span,
             Self::SvelteDocument(x) => x.span,
             Self::SvelteElement(x) => x.span,
@@ -86,6 +119,18 @@ impl<'a> GetSpan for AstKind<'a> {
             Self::AwaitBlock(x) => x.span,
             Self::KeyBlock(x) => x.span,
             Self::SnippetBlock(x) => x.span,
+            Self::Attribute(x) => x.span,
+            Self::SpreadAttribute(x) => x.span,
+            Self::AnimateDirective(x) => x.span,
+            Self::BindDirective(x) => x.span,
+            Self::ClassDirective(x) => x.span,
+            Self::LetDirective(x) => x.span,
+            Self::OnDirective(x) => x.span,
+            Self::StyleDirective(x) => x.span,
+            Self::TransitionDirective(x) => x.span,
+            Self::UseDirective(x) => x.span,
+            Self::Script(x) => x.span,
+            Self::Style(x) => x.span,
         }
     }
 }
@@ -101,6 +146,7 @@ impl<'a> AstKind<'a> {
         match self {
             Self::Root(_) => "Root".into(),
             Self::Text(_) => "Text".into(),
+            Self::Comment(_) => "Comment".into(),
             Self::ExpressionTag(_) => "ExpressionTag".into(),
             Self::HtmlTag(_) => "HtmlTag".into(),
             Self::ConstTag(_) => "ConstTag".into(),
@@ -111,6 +157,7 @@ impl<'a> AstKind<'a> {
             Self::SlotElement(_) => "SlotElement".into(),
             Self::RegularElement(_) => "RegularElement".into(),
             Self::SvelteBody(_) => "SvelteBody".into(),
+            Self::SvelteBoundary(_) => "SvelteBoundary".into(),
             Self::SvelteComponent(_) => "SvelteComponent".into(),
             Self::SvelteDocument(_) => "SvelteDocument".into(),
             Self::SvelteElement(_) => "SvelteElement".into(),
@@ -124,6 +171,18 @@ impl<'a> AstKind<'a> {
             Self::AwaitBlock(_) => "AwaitBlock".into(),
             Self::KeyBlock(_) => "KeyBlock".into(),
             Self::SnippetBlock(_) => "SnippetBlock".into(),
+            Self::Attribute(_) => "Attribute".into(),
+            Self::SpreadAttribute(_) => "SpreadAttribute".into(),
+            Self::AnimateDirective(_) => "AnimateDirective".into(),
+            Self::BindDirective(_) => "BindDirective".into(),
+            Self::ClassDirective(_) => "ClassDirective".into(),
+            Self::LetDirective(_) => "LetDirective".into(),
+            Self::OnDirective(_) => "OnDirective".into(),
+            Self::StyleDirective(_) => "StyleDirective".into(),
+            Self::TransitionDirective(_) => "TransitionDirective".into(),
+            Self::UseDirective(_) => "UseDirective".into(),
+            Self::Script(_) => "Script".into(),
+            Self::Style(_) => "Style".into(),
         }
     }
 }
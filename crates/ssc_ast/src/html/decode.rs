@@ -0,0 +1,125 @@
+//! HTML character reference decoding
+//!
+//! Follows the [tokenizer of the HTML spec](https://html.spec.whatwg.org/multipage/parsing.html#character-reference-state),
+//! so text and attribute values mean what they mean in a browser.
+
+use std::borrow::Cow;
+
+use super::entities::{LONGEST_NAME, NAMED_REFERENCES};
+
+/// Decode the character references of the text content of an element, e.g.
+/// `a &lt; b` is `a < b`.
+pub fn decode_text(value: &str) -> Cow<'_, str> {
+    decode(value, false)
+}
+
+/// Decode the character references of an attribute value.
+///
+/// Differs from [`decode_text`] for legacy named references without a `;`,
+/// which aren't decoded when followed by `=` or an alphanumeric, so the query
+/// of `href="?a=1&copy=2"` is left as is.
+pub fn decode_attribute(value: &str) -> Cow<'_, str> {
+    decode(value, true)
+}
+
+fn decode(value: &str, in_attribute: bool) -> Cow<'_, str> {
+    let Some(first) = value.find('&') else {
+        return Cow::Borrowed(value);
+    };
+    let mut decoded = String::with_capacity(value.len());
+    decoded.push_str(&value[..first]);
+    let mut rest = &value[first..];
+    while let Some(index) = rest.find('&') {
+        decoded.push_str(&rest[..index]);
+        rest = &rest[index + 1..];
+        let consumed = if let Some(number) = rest.strip_prefix('#') {
+            decode_numeric(number, &mut decoded).map(|len| len + 1)
+        } else {
+            decode_named(rest, in_attribute, &mut decoded)
+        };
+        match consumed {
+            Some(len) => rest = &rest[len..],
+            None => decoded.push('&'),
+        }
+    }
+    decoded.push_str(rest);
+    Cow::Owned(decoded)
+}
+
+/// Decode the numeric reference `value` starts with, after the `&#`, and
+/// return its length.
+fn decode_numeric(value: &str, out: &mut String) -> Option<usize> {
+    let (radix, prefix) = match value.as_bytes().first() {
+        Some(b'x' | b'X') => (16, 1),
+        _ => (10, 0),
+    };
+    let digits = value[prefix..]
+        .bytes()
+        .take_while(|byte| byte.is_ascii_digit() || radix == 16 && byte.is_ascii_hexdigit())
+        .count();
+    if digits == 0 {
+        return None;
+    }
+    let code = value[prefix..prefix + digits]
+        .chars()
+        .try_fold(0u32, |code, ch| code.checked_mul(radix)?.checked_add(ch.to_digit(radix)?))
+        .unwrap_or(u32::MAX);
+    out.push(numeric_reference_char(code));
+    let len = prefix + digits;
+    Some(if value[len..].starts_with(';') { len + 1 } else { len })
+}
+
+/// The character of the code point of a numeric reference, with the
+/// replacements of the spec for the ones that can't appear in a document.
+fn numeric_reference_char(code: u32) -> char {
+    // C1 controls are read as Windows-1252, as they were before Unicode
+    const WINDOWS_1252: [u32; 32] = [
+        0x20AC, 0x81, 0x201A, 0x0192, 0x201E, 0x2026, 0x2020, 0x2021, 0x02C6, 0x2030, 0x0160,
+        0x2039, 0x0152, 0x8D, 0x017D, 0x8F, 0x90, 0x2018, 0x2019, 0x201C, 0x201D, 0x2022, 0x2013,
+        0x2014, 0x02DC, 0x2122, 0x0161, 0x203A, 0x0153, 0x9D, 0x017E, 0x0178,
+    ];
+    let code = match code {
+        0 => return char::REPLACEMENT_CHARACTER,
+        0x80..=0x9F => WINDOWS_1252[(code - 0x80) as usize],
+        _ => code,
+    };
+    char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+/// Decode the longest named reference `value` starts with, after the `&`,
+/// and return its length.
+fn decode_named(value: &str, in_attribute: bool, out: &mut String) -> Option<usize> {
+    let alphanumeric = value.bytes().take_while(u8::is_ascii_alphanumeric).count();
+    let longest =
+        if value[alphanumeric..].starts_with(';') { alphanumeric + 1 } else { alphanumeric };
+    let (name, replacement) = (1..=longest.min(LONGEST_NAME)).rev().find_map(|len| {
+        let name = &value[..len];
+        let index = NAMED_REFERENCES.binary_search_by(|(key, _)| key.cmp(&name)).ok()?;
+        Some((name, NAMED_REFERENCES[index].1))
+    })?;
+    if in_attribute
+        && !name.ends_with(';')
+        && value[name.len()..].starts_with(|ch: char| ch == '=' || ch.is_ascii_alphanumeric())
+    {
+        return None;
+    }
+    out.push_str(replacement);
+    Some(name.len())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decoding() {
+        assert!(matches!(decode_text("plain text"), Cow::Borrowed(_)));
+        assert_eq!(decode_text("a &lt; b &amp;&amp c &gt"), "a < b && c >");
+        assert_eq!(decode_text("&#60;&#x3E;&#X3e &#128512;"), "<>> 😀");
+        assert_eq!(decode_text("&#0; &#x110000; &#xD800; &#150;"), "\u{FFFD} \u{FFFD} \u{FFFD} –");
+        assert_eq!(decode_text("& &# &#x; &unknown; &amp"), "& &# &#x; &unknown; &");
+        assert_eq!(decode_text("&notin; &notit; &NotNestedLessLess;"), "∉ ¬it; ⪡\u{338}");
+        assert_eq!(decode_attribute("?a=1&copy=2&amp=3&copy;"), "?a=1&copy=2&amp=3©");
+        assert_eq!(decode_attribute("&quot;x&quot"), "\"x\"");
+    }
+}
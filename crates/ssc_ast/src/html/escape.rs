@@ -0,0 +1,218 @@
+//! HTML escaping
+//!
+//! Matches the escaping of the Svelte runtime, so server and client rendered
+//! markup are byte for byte identical.
+
+use std::borrow::Cow;
+
+fn escape(value: &str, is_special: fn(u8) -> bool) -> Cow<'_, str> {
+    let Some(first) = value.bytes().position(is_special) else {
+        return Cow::Borrowed(value);
+    };
+    let mut escaped = String::with_capacity(value.len() + 8);
+    escaped.push_str(&value[..first]);
+    for ch in value[first..].chars() {
+        #[allow(clippy::cast_possible_truncation)]
+        match ch {
+            '&' | '<' | '"' | '\'' if is_special(ch as u8) => escaped.push_str(match ch {
+                '&' => "&amp;",
+                '<' => "&lt;",
+                '"' => "&quot;",
+                _ => "&#39;",
+            }),
+            _ => escaped.push(ch),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+/// Escape `value` for use as the text content of an element.
+///
+/// `>` is not escaped, it can't start markup on its own.
+pub fn escape_text(value: &str) -> Cow<'_, str> {
+    escape(value, |byte| matches!(byte, b'&' | b'<'))
+}
+
+/// Escape `value` for use inside of a double quoted attribute value.
+pub fn escape_attribute(value: &str) -> Cow<'_, str> {
+    escape(value, |byte| matches!(byte, b'&' | b'"' | b'<'))
+}
+
+/// Escape `value` for use inside of a single quoted attribute value.
+pub fn escape_single_quoted_attribute(value: &str) -> Cow<'_, str> {
+    escape(value, |byte| matches!(byte, b'&' | b'\'' | b'<'))
+}
+
+/// Whether `value` can be written as an unquoted attribute value.
+pub fn is_unquoted_attribute_safe(value: &str) -> bool {
+    !value.is_empty()
+        && !value.bytes().any(|byte| {
+            byte.is_ascii_whitespace()
+                || matches!(byte, b'"' | b'\'' | b'=' | b'<' | b'>' | b'`' | b'&')
+        })
+}
+
+/// Write the ` name="value"` form of an attribute to `out`.
+///
+/// A `None` value writes a boolean attribute. Values are only left unquoted
+/// when `unquoted` is set and [`is_unquoted_attribute_safe`].
+pub fn write_attribute(out: &mut String, name: &str, value: Option<&str>, unquoted: bool) {
+    out.push(' ');
+    out.push_str(name);
+    let Some(value) = value else { return };
+    out.push('=');
+    if unquoted && is_unquoted_attribute_safe(value) {
+        out.push_str(value);
+    } else {
+        out.push('"');
+        out.push_str(&escape_attribute(value));
+        out.push('"');
+    }
+}
+
+/// Builds the markup passed to `$.template()`, and the JS template literal
+/// holding it.
+///
+/// Text and attribute values are HTML escaped as they are pushed, the
+/// literal escaping is applied once by [`TemplateString::to_literal`].
+#[derive(Debug, Default, Clone)]
+pub struct TemplateString {
+    html: String,
+}
+
+impl TemplateString {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The markup built so far.
+    pub fn as_str(&self) -> &str {
+        &self.html
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.html.is_empty()
+    }
+
+    /// Push markup as is, it must already be valid HTML.
+    pub fn push_raw(&mut self, html: &str) {
+        self.html.push_str(html);
+    }
+
+    pub fn push_text(&mut self, text: &str) {
+        self.html.push_str(&escape_text(text));
+    }
+
+    /// Push the content of the [raw text](super::is_raw_text) element `name`.
+    ///
+    /// It isn't escaped, only `</` is written `<\/` where it would close the
+    /// element early, which keeps the meaning of JS and JSON.
+    pub fn push_raw_text(&mut self, name: &str, text: &str) {
+        let mut rest = text;
+        while let Some(index) = rest.find("</") {
+            let after = &rest[index + 2..];
+            let closes = after.get(..name.len()).is_some_and(|tag| tag.eq_ignore_ascii_case(name));
+            self.html.push_str(&rest[..index]);
+            self.html.push_str(if closes { "<\\/" } else { "</" });
+            rest = after;
+        }
+        self.html.push_str(rest);
+    }
+
+    /// Push `<name`, attributes are pushed next and the tag is closed with
+    /// [`TemplateString::close_start_tag`].
+    pub fn open_start_tag(&mut self, name: &str) {
+        self.html.push('<');
+        self.html.push_str(name);
+    }
+
+    pub fn push_attribute(&mut self, name: &str, value: Option<&str>) {
+        write_attribute(&mut self.html, name, value, false);
+    }
+
+    pub fn close_start_tag(&mut self) {
+        self.html.push('>');
+    }
+
+    /// Push `</name>`, nothing is pushed for void elements.
+    pub fn push_end_tag(&mut self, name: &str) {
+        if !super::is_void(name) {
+            self.html.push_str("</");
+            self.html.push_str(name);
+            self.html.push('>');
+        }
+    }
+
+    /// Push a comment, used as an anchor by the client runtime.
+    pub fn push_comment(&mut self, data: &str) {
+        self.html.push_str("<!--");
+        // `-->` would end the comment early
+        self.html.push_str(&data.replace("-->", "--&gt;"));
+        self.html.push_str("-->");
+    }
+
+    /// The markup as JS template literal source, backticks included.
+    pub fn to_literal(&self) -> String {
+        let mut literal = String::with_capacity(self.html.len() + 2);
+        literal.push('`');
+        let mut chars = self.html.chars().peekable();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '\\' | '`' => {
+                    literal.push('\\');
+                    literal.push(ch);
+                }
+                '$' if chars.peek() == Some(&'{') => literal.push_str("\\$"),
+                _ => literal.push(ch),
+            }
+        }
+        literal.push('`');
+        literal
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn escaping() {
+        assert!(matches!(escape_text("plain > text"), Cow::Borrowed(_)));
+        assert_eq!(escape_text("a < b && \"c\""), "a &lt; b &amp;&amp; \"c\"");
+        assert_eq!(escape_attribute("é \"<a>\" &"), "é &quot;&lt;a>&quot; &amp;");
+        assert_eq!(escape_single_quoted_attribute("it's \"a\""), "it&#39;s \"a\"");
+
+        let mut out = String::new();
+        write_attribute(&mut out, "class", Some("a"), true);
+        write_attribute(&mut out, "title", Some("a b"), true);
+        write_attribute(&mut out, "alt", Some(""), true);
+        write_attribute(&mut out, "hidden", None, true);
+        assert_eq!(out, r#" class=a title="a b" alt="" hidden"#);
+    }
+
+    #[test]
+    fn template_string() {
+        let mut template = TemplateString::new();
+        template.open_start_tag("p");
+        template.push_attribute("title", Some("`${x}` \\"));
+        template.close_start_tag();
+        template.push_text("<b>${y}</b>");
+        template.push_end_tag("p");
+        template.open_start_tag("br");
+        template.close_start_tag();
+        template.push_end_tag("br");
+        template.push_comment("-->");
+        assert_eq!(
+            template.as_str(),
+            r#"<p title="`${x}` \">&lt;b>${y}&lt;/b></p><br><!----&gt;-->"#
+        );
+        assert_eq!(
+            template.to_literal(),
+            r#"`<p title="\`\${x}\` \\">&lt;b>\${y}&lt;/b></p><br><!----&gt;-->`"#
+        );
+
+        let mut template = TemplateString::new();
+        template.push_raw_text("script", r#"{"a": "</p></SCRIPT>", "b": 1 < 2}"#);
+        assert_eq!(template.as_str(), r#"{"a": "</p><\/SCRIPT>", "b": 1 < 2}"#);
+    }
+}
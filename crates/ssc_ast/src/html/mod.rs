@@ -0,0 +1,81 @@
+//! HTML element semantics shared by the parser and the printers
+
+mod decode;
+mod entities;
+mod escape;
+
+pub use self::{
+    decode::{decode_attribute, decode_text},
+    escape::{
+        escape_attribute, escape_single_quoted_attribute, escape_text, is_unquoted_attribute_safe,
+        write_attribute, TemplateString,
+    },
+};
+
+/// Elements that can't have children, and so have no closing tag.
+///
+/// Includes the obsolete `command` and `keygen`, as the Svelte compiler does.
+pub const VOID_ELEMENTS: [&str; 16] = [
+    "area", "base", "br", "col", "command", "embed", "hr", "img", "input", "keygen", "link",
+    "meta", "param", "source", "track", "wbr",
+];
+
+/// Whether `name` is a [void element](VOID_ELEMENTS).
+///
+/// Element names are case-sensitive in Svelte, `<Input>` is a component.
+pub fn is_void(name: &str) -> bool {
+    VOID_ELEMENTS.contains(&name)
+}
+
+/// Attributes whose presence is their value, e.g. `disabled`, which are left
+/// out rather than set to `"false"`, as the Svelte runtime does.
+pub const BOOLEAN_ATTRIBUTES: [&str; 26] = [
+    "allowfullscreen",
+    "async",
+    "autofocus",
+    "autoplay",
+    "checked",
+    "controls",
+    "default",
+    "defer",
+    "disabled",
+    "formnovalidate",
+    "hidden",
+    "indeterminate",
+    "inert",
+    "ismap",
+    "loop",
+    "multiple",
+    "muted",
+    "nomodule",
+    "novalidate",
+    "open",
+    "playsinline",
+    "readonly",
+    "required",
+    "reversed",
+    "seamless",
+    "selected",
+];
+
+/// Whether `name` is a [boolean attribute](BOOLEAN_ATTRIBUTES).
+pub fn is_boolean_attribute(name: &str) -> bool {
+    BOOLEAN_ATTRIBUTES.contains(&name)
+}
+
+/// Whether `name` starts a foreign (SVG or MathML) subtree, in which
+/// self-closing tags are meaningful.
+pub fn is_foreign_root(name: &str) -> bool {
+    matches!(name, "svg" | "math")
+}
+
+/// Whether the content of `name` is raw text, which ends at its closing tag
+/// and can't contain markup, tags or blocks, e.g. `<script>` in the template.
+pub fn is_raw_text(name: &str) -> bool {
+    matches!(name, "script" | "style")
+}
+
+/// Whether the whitespace of the children of `name` is rendered as is.
+pub fn is_preformatted(name: &str) -> bool {
+    matches!(name, "pre" | "textarea")
+}
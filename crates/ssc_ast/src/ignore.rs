@@ -0,0 +1,242 @@
+//! `svelte-ignore` comments
+//!
+//! `<!-- svelte-ignore a11y_autofocus a11y_missing_attribute -->` silences
+//! the warnings of these codes on the node following the comment and the
+//! nodes nested in it. Consecutive comments add up, and whitespace between
+//! them and the node doesn't break the association.
+//!
+//! The parser, the semantic analysis, the linter and the compiler all drop
+//! the warnings silenced this way with [`SvelteIgnores::is_diagnostic_ignored`].
+
+use std::{fmt, ops::ControlFlow};
+
+use oxc_diagnostics::{Error, OxcDiagnostic, Severity};
+use oxc_span::{GetSpan, Span};
+
+use crate::{ast::*, visit::walk::walk_fragment, Visit};
+
+/// A warning code listed by a `svelte-ignore` comment, e.g. `a11y_autofocus`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IgnoreCode(String);
+
+impl IgnoreCode {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for IgnoreCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl PartialEq<str> for IgnoreCode {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for IgnoreCode {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+/// The codes listed by a comment, `None` if it isn't a `svelte-ignore` one.
+///
+/// Codes are separated by whitespace or commas. Svelte 4 codes are written
+/// with dashes, `a11y-autofocus` also ignores `a11y_autofocus`.
+pub fn parse_ignore_codes(data: &str) -> Option<Vec<IgnoreCode>> {
+    let rest = data.trim_start().strip_prefix("svelte-ignore")?;
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let mut codes = vec![];
+    for code in rest.split(|ch: char| ch.is_whitespace() || ch == ',') {
+        if code.is_empty() {
+            continue;
+        }
+        codes.push(IgnoreCode(code.to_string()));
+        if code.contains('-') {
+            codes.push(IgnoreCode(code.replace('-', "_")));
+        }
+    }
+    Some(codes)
+}
+
+/// The `svelte-ignore` comments of a template, by the span of the node they
+/// apply to. Built by [`Root::svelte_ignores`].
+#[derive(Debug, Default)]
+pub struct SvelteIgnores {
+    /// Sorted by start, nodes nested in one another don't start together.
+    nodes: Vec<(Span, Vec<IgnoreCode>)>,
+}
+
+impl SvelteIgnores {
+    /// The codes ignored by the comments right before the node at `span`,
+    /// not by the ones of the nodes enclosing it.
+    pub fn ignores(&self, span: Span) -> &[IgnoreCode] {
+        match self.nodes.binary_search_by_key(&span.start, |(node, _)| node.start) {
+            Ok(index) if self.nodes[index].0 == span => &self.nodes[index].1,
+            _ => &[],
+        }
+    }
+
+    /// Whether the warnings of `code` at `span` are ignored, by the comments
+    /// before the node at `span` or before a node enclosing it.
+    pub fn is_ignored(&self, span: Span, code: &str) -> bool {
+        self.nodes.iter().take_while(|(node, _)| node.start <= span.start).any(|(node, codes)| {
+            span.end <= node.end && codes.iter().any(|ignored| ignored == code)
+        })
+    }
+
+    /// Whether `diagnostic` is a warning whose code is ignored, see
+    /// [`Self::is_ignored`], at one of its labels.
+    ///
+    /// The code is the one set by `OxcDiagnostic::with_error_code`.
+    pub fn is_diagnostic_ignored(&self, diagnostic: &OxcDiagnostic) -> bool {
+        if self.is_empty() || diagnostic.severity != Severity::Warning {
+            return false;
+        }
+        let (Some(code), Some(labels)) = (diagnostic.code.number.as_deref(), &diagnostic.labels)
+        else {
+            return false;
+        };
+        labels.iter().any(|label| {
+            #[allow(clippy::cast_possible_truncation)]
+            let span = Span::new(label.offset() as u32, (label.offset() + label.len()) as u32);
+            self.is_ignored(span, code)
+        })
+    }
+
+    /// Like [`Self::is_diagnostic_ignored`], for a diagnostic turned into an
+    /// [`Error`].
+    pub fn is_error_ignored(&self, error: &Error) -> bool {
+        error
+            .downcast_ref::<OxcDiagnostic>()
+            .is_some_and(|diagnostic| self.is_diagnostic_ignored(diagnostic))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+#[derive(Default)]
+struct Collector {
+    nodes: Vec<(Span, Vec<IgnoreCode>)>,
+}
+
+impl<'a> Visit<'a> for Collector {
+    fn visit_fragment(&mut self, fragment: &Fragment<'a>) -> ControlFlow<()> {
+        let mut pending = vec![];
+        for node in &fragment.nodes {
+            match node {
+                FragmentNode::Comment(comment) => {
+                    if let Some(codes) = parse_ignore_codes(&comment.data) {
+                        pending.extend(codes);
+                    }
+                }
+                FragmentNode::Text(text) if text.data.trim().is_empty() => {}
+                _ if pending.is_empty() => {}
+                _ => self.nodes.push((node.span(), std::mem::take(&mut pending))),
+            }
+        }
+        walk_fragment(self, fragment)
+    }
+}
+
+impl<'a> Root<'a> {
+    /// Collect the `svelte-ignore` comments of the template.
+    ///
+    /// ```ignore
+    /// let ignores = root.svelte_ignores();
+    /// if !ignores.is_ignored(element.span, "a11y_autofocus") {
+    ///     // warn
+    /// }
+    /// ```
+    pub fn svelte_ignores(&self) -> SvelteIgnores {
+        let mut collector = Collector::default();
+        let _ = collector.visit_fragment(&self.fragment);
+        collector.nodes.sort_by_key(|(span, _)| span.start);
+        SvelteIgnores { nodes: collector.nodes }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_span::{Atom, Span};
+
+    use super::*;
+    use crate::AstBuilder;
+
+    #[test]
+    fn parse_codes() {
+        let codes = parse_ignore_codes(" svelte-ignore a11y-autofocus, a11y_missing_content ");
+        let codes = codes.unwrap();
+        assert_eq!(codes, ["a11y-autofocus", "a11y_autofocus", "a11y_missing_content"]);
+        assert!(parse_ignore_codes(" svelte-ignored a11y_autofocus ").is_none());
+        assert!(parse_ignore_codes(" a note ").is_none());
+    }
+
+    #[test]
+    fn svelte_ignores() {
+        let allocator = Allocator::default();
+        let ast = AstBuilder::new(&allocator);
+        let fragment = |nodes: std::vec::Vec<_>| ast.fragment(ast.new_vec_from_iter(nodes), false);
+        let comment = |start, data: &'static str| {
+            FragmentNode::Comment(ast.comment(Span::new(start, start + 1), Atom::from(data)))
+        };
+        let text = |start, data: &'static str| {
+            FragmentNode::Text(ast.text(Span::new(start, start + 1), Atom::from(data)))
+        };
+        let element = |span: Span, nodes| {
+            FragmentNode::Element(ast.regular_element(
+                span,
+                Atom::from("div"),
+                ast.new_vec(),
+                fragment(nodes),
+            ))
+        };
+
+        // <!-- svelte-ignore a --> <!-- svelte-ignore b --> <div><p /></div> <span />
+        let outer = Span::new(4, 20);
+        let inner = Span::new(10, 14);
+        let sibling = Span::new(22, 24);
+        let root = ast.root(
+            Span::default(),
+            fragment(vec![
+                comment(0, " svelte-ignore a "),
+                text(1, " "),
+                comment(2, " svelte-ignore b "),
+                element(outer, vec![element(inner, vec![])]),
+                text(21, " "),
+                element(sibling, vec![]),
+            ]),
+            None,
+            None,
+            None,
+            false,
+        );
+
+        let ignores = root.svelte_ignores();
+        assert_eq!(ignores.ignores(outer), ["a", "b"]);
+        assert!(ignores.ignores(inner).is_empty());
+        assert!(ignores.ignores(sibling).is_empty());
+        assert!(ignores.is_ignored(inner, "b"));
+        assert!(!ignores.is_ignored(inner, "c"));
+        assert!(!ignores.is_ignored(sibling, "a"));
+
+        let warning = |span: Span, code: &'static str| {
+            OxcDiagnostic::warn("").with_error_code("svelte", code).with_label(span)
+        };
+        assert!(ignores.is_diagnostic_ignored(&warning(inner, "a")));
+        assert!(!ignores.is_diagnostic_ignored(&warning(sibling, "a")));
+        assert!(!ignores.is_diagnostic_ignored(&OxcDiagnostic::warn("").with_label(inner)));
+        let error = OxcDiagnostic::error("").with_error_code("svelte", "a").with_label(inner);
+        assert!(!ignores.is_diagnostic_ignored(&error));
+        assert!(ignores.is_error_ignored(&warning(inner, "b").into()));
+    }
+}
@@ -0,0 +1,132 @@
+use std::slice;
+
+use bitflags::bitflags;
+
+use crate::ast::*;
+
+bitflags! {
+    /// Which nodes [`Descendants`] descends into.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Descend: u8 {
+        /// The children of elements.
+        const Elements = 1 << 0;
+        /// The branches of blocks.
+        const Blocks   = 1 << 1;
+    }
+}
+
+/// Pre-order iterator over fragment nodes.
+///
+/// Created with [`Fragment::iter_nodes`] or [`Root::descendants`].
+pub struct Descendants<'b, 'a> {
+    descend: Descend,
+    stack: Vec<slice::Iter<'b, FragmentNode<'a>>>,
+}
+
+impl<'b, 'a> Iterator for Descendants<'b, 'a> {
+    type Item = &'b FragmentNode<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = loop {
+            let nodes = self.stack.last_mut()?;
+            match nodes.next() {
+                Some(node) => break node,
+                None => {
+                    self.stack.pop();
+                }
+            }
+        };
+        match node {
+            FragmentNode::Element(element) if self.descend.contains(Descend::Elements) => {
+                self.stack.push(element.fragment().nodes.iter());
+            }
+            FragmentNode::Block(block) if self.descend.contains(Descend::Blocks) => {
+                // Pushed last to first, so that the first branch is visited first
+                self.stack.extend(block.fragments().rev().map(|fragment| fragment.nodes.iter()));
+            }
+            _ => {}
+        }
+        Some(node)
+    }
+}
+
+impl<'a> Fragment<'a> {
+    /// Iterate over the nodes of the fragment, and the nodes nested in them as
+    /// selected by `descend`, in pre-order.
+    ///
+    /// `Descend::empty()` only yields the nodes of the fragment itself.
+    pub fn iter_nodes(&self, descend: Descend) -> Descendants<'_, 'a> {
+        Descendants { descend, stack: vec![self.nodes.iter()] }
+    }
+}
+
+impl<'a> Root<'a> {
+    /// Iterate over every node of the template, in pre-order.
+    pub fn descendants(&self) -> Descendants<'_, 'a> {
+        self.fragment.iter_nodes(Descend::all())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_span::{Atom, Span};
+
+    use super::*;
+    use crate::AstBuilder;
+
+    #[test]
+    fn pre_order() {
+        let allocator = Allocator::default();
+        let ast = AstBuilder::new(&allocator);
+        let js = oxc_ast::AstBuilder::new(&allocator);
+        let text =
+            |raw: &'static str| FragmentNode::Text(ast.text(Span::default(), Atom::from(raw)));
+        let fragment = |nodes: std::vec::Vec<_>| ast.fragment(ast.new_vec_from_iter(nodes), false);
+
+        // <div>a<p>b</p></div>{#if true}c{:else}d{/if}e
+        let p = ast.regular_element(
+            Span::default(),
+            Atom::from("p"),
+            ast.new_vec(),
+            fragment(vec![text("b")]),
+        );
+        let div = ast.regular_element(
+            Span::default(),
+            Atom::from("div"),
+            ast.new_vec(),
+            fragment(vec![text("a"), FragmentNode::Element(p)]),
+        );
+        let condition = js.literal_boolean_expression(js.boolean_literal(Span::default(), true));
+        let if_block = ast.if_block(
+            Span::default(),
+            false,
+            condition,
+            fragment(vec![text("c")]),
+            Some(fragment(vec![text("d")])),
+        );
+        let root = fragment(vec![
+            FragmentNode::Element(div),
+            FragmentNode::Block(Block::IfBlock(if_block)),
+            text("e"),
+        ]);
+
+        let names = |descend| {
+            root.iter_nodes(descend)
+                .map(|node| match node {
+                    FragmentNode::Text(text) => text.raw.as_str(),
+                    FragmentNode::Element(element) => match element {
+                        Element::RegularElement(element) => element.name.as_str(),
+                        _ => unreachable!(),
+                    },
+                    FragmentNode::Block(_) => "if",
+                    FragmentNode::Comment(_) | FragmentNode::Tag(_) => unreachable!(),
+                })
+                .collect::<std::vec::Vec<_>>()
+        };
+        assert_eq!(names(Descend::empty()), ["div", "if", "e"]);
+        assert_eq!(names(Descend::Elements), ["div", "a", "p", "b", "if", "e"]);
+        assert_eq!(names(Descend::Blocks), ["div", "if", "c", "d", "e"]);
+        assert_eq!(names(Descend::all()), ["div", "a", "p", "b", "if", "c", "d", "e"]);
+    }
+}
@@ -3,20 +3,32 @@
 //! # SSC AST
 //!
 //! ## Cargo Features
-//! * `"serialize"` enables support for serde serialization
+//! * `"serialize"` enables support for serde serialization, and
+//!   position-free JSON for [snapshots](snapshot)
 
 pub mod ast;
 mod ast_builder;
 mod ast_kind;
+pub mod html;
+mod ignore;
+mod iter;
+mod query;
+mod selector;
+#[cfg(feature = "serialize")]
+pub mod snapshot;
 mod span;
 mod trivia;
 pub mod visit;
 
 pub use crate::{
     ast_builder::AstBuilder,
-    ast_kind::{AstKind, AstType},
+    ast_kind::{AstKind, AstNode, AstType},
+    ignore::{parse_ignore_codes, IgnoreCode, SvelteIgnores},
+    iter::{Descend, Descendants},
+    query::QueryMatch,
+    selector::Selector,
     trivia::{Comment, Trivias, TriviasMap},
-    visit::{Visit, VisitMut},
+    visit::{Visit, VisitControl, VisitMut},
 };
 
 #[test]
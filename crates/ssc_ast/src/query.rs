@@ -0,0 +1,151 @@
+use crate::{
+    ast::*,
+    ast_kind::{AstKind, AstNode},
+    iter::Descend,
+    Visit, VisitControl,
+};
+
+/// A node found by [`Root::find_all`] or [`Fragment::find_all`].
+#[derive(Debug)]
+pub struct QueryMatch<'a, T> {
+    pub node: &'a T,
+    /// The nodes enclosing `node`, outermost first.
+    pub ancestors: Vec<AstKind<'a>>,
+}
+
+impl<'a, T> QueryMatch<'a, T> {
+    /// The node directly enclosing `node`.
+    pub fn parent(&self) -> Option<AstKind<'a>> {
+        self.ancestors.last().copied()
+    }
+
+    /// The closest enclosing node of type `U`.
+    pub fn closest<U: AstNode<'a>>(&self) -> Option<&'a U> {
+        self.ancestors.iter().rev().find_map(|kind| U::cast(*kind))
+    }
+}
+
+struct FindAll<'a, T> {
+    ancestors: Vec<AstKind<'a>>,
+    matches: Vec<QueryMatch<'a, T>>,
+}
+
+impl<'a, T: AstNode<'a>> FindAll<'a, T> {
+    fn new() -> Self {
+        Self { ancestors: vec![], matches: vec![] }
+    }
+}
+
+impl<'a, T: AstNode<'a>> Visit<'a> for FindAll<'a, T> {
+    fn enter_node(&mut self, kind: AstKind<'a>) -> VisitControl {
+        if let Some(node) = T::cast(kind) {
+            self.matches.push(QueryMatch { node, ancestors: self.ancestors.clone() });
+        }
+        self.ancestors.push(kind);
+        VisitControl::Continue
+    }
+
+    fn leave_node(&mut self, _kind: AstKind<'a>) {
+        self.ancestors.pop();
+    }
+}
+
+impl<'a> Root<'a> {
+    /// Every node of type `T` in the template, in pre-order.
+    ///
+    /// ```ignore
+    /// for found in root.find_all::<RegularElement>() {
+    ///     let in_each = found.closest::<EachBlock>().is_some();
+    /// }
+    /// ```
+    pub fn find_all<'b, T: AstNode<'b>>(&'b self) -> Vec<QueryMatch<'b, T>> {
+        let mut finder = FindAll::new();
+        let _ = finder.visit_root(self);
+        finder.matches
+    }
+}
+
+impl<'a> Fragment<'a> {
+    /// Every node of type `T` in the fragment, in pre-order.
+    ///
+    /// Ancestors are relative to the fragment.
+    pub fn find_all<'b, T: AstNode<'b>>(&'b self) -> Vec<QueryMatch<'b, T>> {
+        let mut finder = FindAll::new();
+        let _ = finder.visit_fragment(self);
+        finder.matches
+    }
+
+    /// The first non-`None` result of `f` over the nodes of the fragment and
+    /// everything nested in them, in pre-order.
+    pub fn find_map<'b, B, F>(&'b self, f: F) -> Option<B>
+    where
+        F: FnMut(&'b FragmentNode<'a>) -> Option<B>,
+    {
+        self.iter_nodes(Descend::all()).find_map(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_span::{Atom, Span};
+
+    use super::*;
+    use crate::AstBuilder;
+
+    #[test]
+    fn find_all() {
+        let allocator = Allocator::default();
+        let ast = AstBuilder::new(&allocator);
+        let js = oxc_ast::AstBuilder::new(&allocator);
+        let fragment = |nodes: std::vec::Vec<_>| ast.fragment(ast.new_vec_from_iter(nodes), false);
+        let element = |name: &'static str, nodes| {
+            FragmentNode::Element(ast.regular_element(
+                Span::default(),
+                Atom::from(name),
+                ast.new_vec(),
+                fragment(nodes),
+            ))
+        };
+
+        // <div><p></p></div>{#if true}<span></span>{/if}
+        let condition = js.literal_boolean_expression(js.boolean_literal(Span::default(), true));
+        let if_block = ast.if_block(
+            Span::default(),
+            false,
+            condition,
+            fragment(vec![element("span", vec![])]),
+            None,
+        );
+        let root = ast.root(
+            Span::default(),
+            fragment(vec![
+                element("div", vec![element("p", vec![])]),
+                FragmentNode::Block(Block::IfBlock(if_block)),
+            ]),
+            None,
+            None,
+            None,
+            false,
+        );
+
+        let found = root.find_all::<RegularElement>();
+        let names = found.iter().map(|found| found.node.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["div", "p", "span"]);
+        assert!(matches!(found[0].parent(), Some(AstKind::Root(_))));
+        assert_eq!(found[1].closest::<RegularElement>().unwrap().name.as_str(), "div");
+        assert!(found[2].closest::<IfBlock>().is_some());
+        assert!(found[1].closest::<IfBlock>().is_none());
+
+        assert_eq!(root.fragment.find_all::<IfBlock>().len(), 1);
+        let first_nested = root.fragment.find_map(|node| match node {
+            FragmentNode::Element(Element::RegularElement(element))
+                if element.name.as_str() != "div" =>
+            {
+                Some(element.name.as_str())
+            }
+            _ => None,
+        });
+        assert_eq!(first_nested, Some("p"));
+    }
+}
@@ -0,0 +1,688 @@
+//! Selectors over the template AST
+//!
+//! An [esquery](https://github.com/estools/esquery)-like selector language,
+//! e.g. `RegularElement[name=button] > ExpressionTag` or
+//! `EachBlock:not([key])`.
+//!
+//! * Types are the names of [`AstKind`] variants, `*` matches any node.
+//! * `A B` matches a `B` nested in an `A`, `A > B` a `B` directly in an `A`.
+//! * `[prop]` matches nodes which have `prop`, `[prop=value]` and
+//!   `[prop!=value]` compare it to a bare or quoted string.
+//! * `:not(...)`, `:is(...)` and `:has(...)` take selector lists,
+//!   `:element`, `:block` and `:tag` match every node of a category.
+//!
+//! Properties are `name` for elements and snippets, `data` for text and
+//! comments, `key`, `index` and `fallback` for each blocks, `elseif` and
+//! `alternate` for if blocks, `pending`, `then` and `catch` for await
+//! blocks, and `attributes.<name>` for the attributes of elements.
+
+use std::{ops::ControlFlow, str::FromStr};
+
+use crate::{ast::*, ast_kind::AstKind, Visit, VisitControl};
+
+/// A compiled selector.
+///
+/// ```ignore
+/// let selector = Selector::parse("EachBlock:not([key])")?;
+/// for node in root.select(&selector) {
+///     report(node.span());
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector {
+    alternatives: Vec<ComplexSelector>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ComplexSelector {
+    compounds: Vec<CompoundSelector>,
+    /// `combinators[i]` joins `compounds[i]` and `compounds[i + 1]`.
+    combinators: Vec<Combinator>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Combinator {
+    Descendant,
+    Child,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CompoundSelector {
+    /// `None` for `*`.
+    node_type: Option<String>,
+    filters: Vec<Filter>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Filter {
+    Property { path: String, comparison: Option<(bool, String)> },
+    Not(Selector),
+    Is(Selector),
+    Has(Selector),
+    Element,
+    Block,
+    Tag,
+}
+
+/// The value of a node property.
+enum Property<'a> {
+    Value(&'a str),
+    /// Set, but not to a static string, e.g. a keyed each block's `key`.
+    Present,
+}
+
+const NODE_TYPES: &[&str] = &[
+    "Root",
+    "Text",
+    "Comment",
+    "ExpressionTag",
+    "HtmlTag",
+    "ConstTag",
+    "DebugTag",
+    "RenderTag",
+    "Component",
+    "TitleElement",
+    "SlotElement",
+    "RegularElement",
+    "SvelteBody",
+    "SvelteBoundary",
+    "SvelteComponent",
+    "SvelteDocument",
+    "SvelteElement",
+    "SvelteFragment",
+    "SvelteHead",
+    "SvelteOptionsRaw",
+    "SvelteSelf",
+    "SvelteWindow",
+    "EachBlock",
+    "IfBlock",
+    "AwaitBlock",
+    "KeyBlock",
+    "SnippetBlock",
+    "Attribute",
+    "SpreadAttribute",
+    "AnimateDirective",
+    "BindDirective",
+    "ClassDirective",
+    "LetDirective",
+    "OnDirective",
+    "StyleDirective",
+    "TransitionDirective",
+    "UseDirective",
+    "Script",
+    "Style",
+];
+
+impl Selector {
+    /// Compile `source`.
+    ///
+    /// # Errors
+    ///
+    /// A message describing the first syntax error, or the unknown type or
+    /// pseudo-class.
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut parser = SelectorParser { source, pos: 0 };
+        let selector = parser.parse_selector_list()?;
+        parser.skip_whitespace();
+        match parser.peek() {
+            None => Ok(selector),
+            Some(ch) => Err(parser.unexpected(ch)),
+        }
+    }
+
+    /// Whether `node`, enclosed by `ancestors` (outermost first), matches.
+    ///
+    /// Meant to be called from [`Visit::enter_node`], for visitors keeping
+    /// track of the ancestors themselves.
+    pub fn matches<'a>(&self, node: AstKind<'a>, ancestors: &[AstKind<'a>]) -> bool {
+        self.alternatives
+            .iter()
+            .any(|complex| complex.matches_at(complex.compounds.len() - 1, node, ancestors))
+    }
+}
+
+impl FromStr for Selector {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl ComplexSelector {
+    /// Whether `compounds[..=last]` match, with `compounds[last]` matching
+    /// `node`.
+    fn matches_at<'a>(&self, last: usize, node: AstKind<'a>, ancestors: &[AstKind<'a>]) -> bool {
+        if !self.compounds[last].matches(node, ancestors) {
+            return false;
+        }
+        if last == 0 {
+            return true;
+        }
+        match &self.combinators[last - 1] {
+            Combinator::Child => ancestors
+                .split_last()
+                .is_some_and(|(parent, rest)| self.matches_at(last - 1, *parent, rest)),
+            Combinator::Descendant => (0..ancestors.len())
+                .rev()
+                .any(|i| self.matches_at(last - 1, ancestors[i], &ancestors[..i])),
+        }
+    }
+}
+
+impl CompoundSelector {
+    fn matches<'a>(&self, node: AstKind<'a>, ancestors: &[AstKind<'a>]) -> bool {
+        if self.node_type.as_ref().is_some_and(|node_type| node.debug_name() != node_type.as_str())
+        {
+            return false;
+        }
+        self.filters.iter().all(|filter| match filter {
+            Filter::Property { path, comparison } => match (property(node, path), comparison) {
+                (None, None) => false,
+                (Some(_), None) => true,
+                (value, Some((equals, expected))) => {
+                    let is_equal =
+                        matches!(value, Some(Property::Value(value)) if value == expected);
+                    is_equal == *equals
+                }
+            },
+            Filter::Not(selector) => !selector.matches(node, ancestors),
+            Filter::Is(selector) => selector.matches(node, ancestors),
+            Filter::Has(selector) => {
+                let mut has = Has { selector, ancestors: vec![] };
+                // `Has` stops at the first match
+                visit_kind(&mut has, node).is_break()
+            }
+            Filter::Element => is_element(node),
+            Filter::Block => matches!(
+                node,
+                AstKind::EachBlock(_)
+                    | AstKind::IfBlock(_)
+                    | AstKind::AwaitBlock(_)
+                    | AstKind::KeyBlock(_)
+                    | AstKind::SnippetBlock(_)
+            ),
+            Filter::Tag => matches!(
+                node,
+                AstKind::ExpressionTag(_)
+                    | AstKind::HtmlTag(_)
+                    | AstKind::ConstTag(_)
+                    | AstKind::DebugTag(_)
+                    | AstKind::RenderTag(_)
+            ),
+        })
+    }
+}
+
+fn is_element(node: AstKind) -> bool {
+    element_attributes(node).is_some()
+}
+
+/// The value of the property at `path` of `node`, `None` when it is unset.
+fn property<'a>(node: AstKind<'a>, path: &str) -> Option<Property<'a>> {
+    if let Some(name) = path.strip_prefix("attributes.") {
+        return element_attributes(node)?
+            .iter()
+            .filter_map(ElementAttribute::as_attribute)
+            .find(|attribute| attribute.name.as_str() == name)
+            .map(|attribute| match &attribute.value {
+                Some(value) => match value.sequence.as_slice() {
+                    [AttributeSequenceValue::Text(text)] => Property::Value(text.data.as_str()),
+                    _ => Property::Present,
+                },
+                None => Property::Present,
+            });
+    }
+    let flag = |set: bool| set.then_some(Property::Present);
+    match (node, path) {
+        (AstKind::Text(text), "data") => Some(Property::Value(text.data.as_str())),
+        (AstKind::Comment(comment), "data") => Some(Property::Value(comment.data.as_str())),
+        (AstKind::EachBlock(block), "key") => flag(block.key.is_some()),
+        (AstKind::EachBlock(block), "index") => {
+            block.index.as_ref().map(|index| Property::Value(index.name.as_str()))
+        }
+        (AstKind::EachBlock(block), "fallback") => flag(block.fallback.is_some()),
+        (AstKind::IfBlock(block), "elseif") => flag(block.elseif),
+        (AstKind::IfBlock(block), "alternate") => flag(block.alternate.is_some()),
+        (AstKind::AwaitBlock(block), "pending") => flag(block.pending.is_some()),
+        (AstKind::AwaitBlock(block), "then") => flag(block.then.is_some()),
+        (AstKind::AwaitBlock(block), "catch") => flag(block.catch.is_some()),
+        (AstKind::SnippetBlock(block), "name") => {
+            Some(Property::Value(block.expression.name.as_str()))
+        }
+        (_, "name") => element_name(node).map(Property::Value),
+        _ => None,
+    }
+}
+
+fn element_name(node: AstKind) -> Option<&str> {
+    Some(match node {
+        AstKind::RegularElement(element) => element.name.as_str(),
+        AstKind::Component(component) => component.name.as_str(),
+        AstKind::TitleElement(_) => "title",
+        AstKind::SlotElement(_) => "slot",
+        AstKind::SvelteBody(_) => "svelte:body",
+        AstKind::SvelteBoundary(_) => "svelte:boundary",
+        AstKind::SvelteComponent(_) => "svelte:component",
+        AstKind::SvelteDocument(_) => "svelte:document",
+        AstKind::SvelteElement(_) => "svelte:element",
+        AstKind::SvelteFragment(_) => "svelte:fragment",
+        AstKind::SvelteHead(_) => "svelte:head",
+        AstKind::SvelteOptionsRaw(_) => "svelte:options",
+        AstKind::SvelteSelf(_) => "svelte:self",
+        AstKind::SvelteWindow(_) => "svelte:window",
+        _ => return None,
+    })
+}
+
+fn element_attributes(node: AstKind) -> Option<&[ElementAttribute]> {
+    Some(match node {
+        AstKind::RegularElement(element) => &element.attributes,
+        AstKind::Component(element) => &element.attributes,
+        AstKind::TitleElement(element) => &element.attributes,
+        AstKind::SlotElement(element) => &element.attributes,
+        AstKind::SvelteBody(element) => &element.attributes,
+        AstKind::SvelteBoundary(element) => &element.attributes,
+        AstKind::SvelteComponent(element) => &element.attributes,
+        AstKind::SvelteDocument(element) => &element.attributes,
+        AstKind::SvelteElement(element) => &element.attributes,
+        AstKind::SvelteFragment(element) => &element.attributes,
+        AstKind::SvelteHead(element) => &element.attributes,
+        AstKind::SvelteOptionsRaw(element) => &element.attributes,
+        AstKind::SvelteSelf(element) => &element.attributes,
+        AstKind::SvelteWindow(element) => &element.attributes,
+        _ => return None,
+    })
+}
+
+/// Visit `node` and everything nested in it.
+fn visit_kind<'a, V: Visit<'a>>(visitor: &mut V, node: AstKind<'a>) -> ControlFlow<()> {
+    match node {
+        AstKind::Root(root) => visitor.visit_root(root),
+        AstKind::Text(text) => visitor.visit_text(text),
+        AstKind::Comment(comment) => visitor.visit_comment(comment),
+        AstKind::ExpressionTag(tag) => visitor.visit_expression_tag(tag),
+        AstKind::HtmlTag(tag) => visitor.visit_html_tag(tag),
+        AstKind::ConstTag(tag) => visitor.visit_const_tag(tag),
+        AstKind::DebugTag(tag) => visitor.visit_debug_tag(tag),
+        AstKind::RenderTag(tag) => visitor.visit_render_tag(tag),
+        AstKind::Component(element) => visitor.visit_component(element),
+        AstKind::TitleElement(element) => visitor.visit_title_element(element),
+        AstKind::SlotElement(element) => visitor.visit_slot_element(element),
+        AstKind::RegularElement(element) => visitor.visit_regular_element(element),
+        AstKind::SvelteBody(element) => visitor.visit_svelte_body(element),
+        AstKind::SvelteBoundary(element) => visitor.visit_svelte_boundary(element),
+        AstKind::SvelteComponent(element) => visitor.visit_svelte_component(element),
+        AstKind::SvelteDocument(element) => visitor.visit_svelte_document(element),
+        AstKind::SvelteElement(element) => visitor.visit_svelte_element(element),
+        AstKind::SvelteFragment(element) => visitor.visit_svelte_fragment(element),
+        AstKind::SvelteHead(element) => visitor.visit_svelte_head(element),
+        AstKind::SvelteOptionsRaw(element) => visitor.visit_svelte_options_raw(element),
+        AstKind::SvelteSelf(element) => visitor.visit_svelte_self(element),
+        AstKind::SvelteWindow(element) => visitor.visit_svelte_window(element),
+        AstKind::EachBlock(block) => visitor.visit_each_block(block),
+        AstKind::IfBlock(block) => visitor.visit_if_block(block),
+        AstKind::AwaitBlock(block) => visitor.visit_await_block(block),
+        AstKind::KeyBlock(block) => visitor.visit_key_block(block),
+        AstKind::SnippetBlock(block) => visitor.visit_snippet_block(block),
+        AstKind::Attribute(attribute) => visitor.visit_attribute(attribute),
+        AstKind::SpreadAttribute(attribute) => visitor.visit_spread_attribute(attribute),
+        AstKind::AnimateDirective(directive) => visitor.visit_animate_directive(directive),
+        AstKind::BindDirective(directive) => visitor.visit_bind_directive(directive),
+        AstKind::ClassDirective(directive) => visitor.visit_class_directive(directive),
+        AstKind::LetDirective(directive) => visitor.visit_let_directive(directive),
+        AstKind::OnDirective(directive) => visitor.visit_on_directive(directive),
+        AstKind::StyleDirective(directive) => visitor.visit_style_directive(directive),
+        AstKind::TransitionDirective(directive) => visitor.visit_transition_directive(directive),
+        AstKind::UseDirective(directive) => visitor.visit_use_directive(directive),
+        AstKind::Script(script) => visitor.visit_script(script),
+        AstKind::Style(style) => visitor.visit_style(style),
+    }
+}
+
+/// Looks for a node matching `selector` nested in the visited one. Ancestors
+/// are relative to it.
+struct Has<'s, 'a> {
+    selector: &'s Selector,
+    ancestors: Vec<AstKind<'a>>,
+}
+
+impl<'s, 'a> Visit<'a> for Has<'s, 'a> {
+    fn enter_node(&mut self, kind: AstKind<'a>) -> VisitControl {
+        let found = !self.ancestors.is_empty() && self.selector.matches(kind, &self.ancestors);
+        self.ancestors.push(kind);
+        if found {
+            VisitControl::Stop
+        } else {
+            VisitControl::Continue
+        }
+    }
+
+    fn leave_node(&mut self, _kind: AstKind<'a>) {
+        self.ancestors.pop();
+    }
+}
+
+struct Select<'s, 'a> {
+    selector: &'s Selector,
+    ancestors: Vec<AstKind<'a>>,
+    matches: Vec<AstKind<'a>>,
+}
+
+impl<'s, 'a> Select<'s, 'a> {
+    fn new(selector: &'s Selector) -> Self {
+        Self { selector, ancestors: vec![], matches: vec![] }
+    }
+}
+
+impl<'s, 'a> Visit<'a> for Select<'s, 'a> {
+    fn enter_node(&mut self, kind: AstKind<'a>) -> VisitControl {
+        if self.selector.matches(kind, &self.ancestors) {
+            self.matches.push(kind);
+        }
+        self.ancestors.push(kind);
+        VisitControl::Continue
+    }
+
+    fn leave_node(&mut self, _kind: AstKind<'a>) {
+        self.ancestors.pop();
+    }
+}
+
+impl<'a> Root<'a> {
+    /// Every node of the template matching `selector`, in pre-order.
+    pub fn select<'b>(&'b self, selector: &Selector) -> Vec<AstKind<'b>> {
+        let mut select = Select::new(selector);
+        let _ = select.visit_root(self);
+        select.matches
+    }
+}
+
+impl<'a> Fragment<'a> {
+    /// Every node of the fragment matching `selector`, in pre-order.
+    ///
+    /// Ancestors are relative to the fragment.
+    pub fn select<'b>(&'b self, selector: &Selector) -> Vec<AstKind<'b>> {
+        let mut select = Select::new(selector);
+        let _ = select.visit_fragment(self);
+        select.matches
+    }
+}
+
+struct SelectorParser<'s> {
+    source: &'s str,
+    pos: usize,
+}
+
+impl<'s> SelectorParser<'s> {
+    fn peek(&self) -> Option<char> {
+        self.source[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        self.pos += ch.len_utf8();
+        Some(ch)
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        let eaten = self.peek() == Some(expected);
+        if eaten {
+            self.pos += expected.len_utf8();
+        }
+        eaten
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        if self.eat(expected) {
+            return Ok(());
+        }
+        match self.peek() {
+            Some(ch) => Err(self.unexpected(ch)),
+            None => Err(format!("Expected `{expected}` at the end of the selector")),
+        }
+    }
+
+    /// Skip whitespace, returning whether there was any.
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.pos;
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+        self.pos > start
+    }
+
+    fn unexpected(&self, ch: char) -> String {
+        format!("Unexpected `{ch}` at offset {}", self.pos)
+    }
+
+    fn parse_identifier(&mut self) -> Result<&'s str, String> {
+        let start = self.pos;
+        while self.peek().is_some_and(|ch| ch.is_alphanumeric() || matches!(ch, '_' | '-')) {
+            self.bump();
+        }
+        if self.pos > start {
+            return Ok(&self.source[start..self.pos]);
+        }
+        match self.peek() {
+            Some(ch) => Err(self.unexpected(ch)),
+            None => Err("Unexpected end of the selector".to_string()),
+        }
+    }
+
+    fn parse_selector_list(&mut self) -> Result<Selector, String> {
+        let mut alternatives = vec![];
+        loop {
+            self.skip_whitespace();
+            alternatives.push(self.parse_complex_selector()?);
+            self.skip_whitespace();
+            if !self.eat(',') {
+                return Ok(Selector { alternatives });
+            }
+        }
+    }
+
+    fn parse_complex_selector(&mut self) -> Result<ComplexSelector, String> {
+        let mut compounds = vec![self.parse_compound_selector()?];
+        let mut combinators = vec![];
+        loop {
+            let whitespace = self.skip_whitespace();
+            let combinator = match self.peek() {
+                Some('>') => {
+                    self.bump();
+                    self.skip_whitespace();
+                    Combinator::Child
+                }
+                Some(',' | ')') | None => break,
+                Some(_) if whitespace => Combinator::Descendant,
+                Some(ch) => return Err(self.unexpected(ch)),
+            };
+            combinators.push(combinator);
+            compounds.push(self.parse_compound_selector()?);
+        }
+        Ok(ComplexSelector { compounds, combinators })
+    }
+
+    fn parse_compound_selector(&mut self) -> Result<CompoundSelector, String> {
+        let node_type = match self.peek() {
+            Some('*') => {
+                self.bump();
+                None
+            }
+            Some('[' | ':') => None,
+            _ => {
+                let name = self.parse_identifier()?;
+                if !NODE_TYPES.contains(&name) {
+                    return Err(format!("Unknown node type `{name}`"));
+                }
+                Some(name.to_string())
+            }
+        };
+        let mut filters = vec![];
+        loop {
+            match self.peek() {
+                Some('[') => filters.push(self.parse_property_filter()?),
+                Some(':') => filters.push(self.parse_pseudo_class()?),
+                _ => break,
+            }
+        }
+        Ok(CompoundSelector { node_type, filters })
+    }
+
+    fn parse_property_filter(&mut self) -> Result<Filter, String> {
+        self.expect('[')?;
+        self.skip_whitespace();
+        let start = self.pos;
+        self.parse_identifier()?;
+        while self.eat('.') {
+            self.parse_identifier()?;
+        }
+        let path = self.source[start..self.pos].to_string();
+        self.skip_whitespace();
+        let equals = if self.eat('=') {
+            true
+        } else if self.eat('!') {
+            self.expect('=')?;
+            false
+        } else {
+            self.expect(']')?;
+            return Ok(Filter::Property { path, comparison: None });
+        };
+        self.skip_whitespace();
+        let value = self.parse_value()?;
+        self.skip_whitespace();
+        self.expect(']')?;
+        Ok(Filter::Property { path, comparison: Some((equals, value)) })
+    }
+
+    fn parse_value(&mut self) -> Result<String, String> {
+        if let Some(quote @ ('"' | '\'')) = self.peek() {
+            self.bump();
+            let start = self.pos;
+            while self.peek().is_some_and(|ch| ch != quote) {
+                self.bump();
+            }
+            let value = self.source[start..self.pos].to_string();
+            self.expect(quote)?;
+            return Ok(value);
+        }
+        let start = self.pos;
+        while self.peek().is_some_and(|ch| ch != ']' && !ch.is_whitespace()) {
+            self.bump();
+        }
+        if self.pos == start {
+            return Err(format!("Expected a value at offset {start}"));
+        }
+        Ok(self.source[start..self.pos].to_string())
+    }
+
+    fn parse_pseudo_class(&mut self) -> Result<Filter, String> {
+        self.expect(':')?;
+        let name = self.parse_identifier()?;
+        let filter = match name {
+            "element" => Filter::Element,
+            "block" => Filter::Block,
+            "tag" => Filter::Tag,
+            "not" | "is" | "has" => {
+                self.expect('(')?;
+                let selector = self.parse_selector_list()?;
+                self.expect(')')?;
+                match name {
+                    "not" => Filter::Not(selector),
+                    "is" => Filter::Is(selector),
+                    _ => Filter::Has(selector),
+                }
+            }
+            _ => return Err(format!("Unknown pseudo-class `:{name}`")),
+        };
+        Ok(filter)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_span::{Atom, Span};
+
+    use super::*;
+    use crate::AstBuilder;
+
+    #[test]
+    fn select() {
+        let allocator = Allocator::default();
+        let ast = AstBuilder::new(&allocator);
+        let js = oxc_ast::AstBuilder::new(&allocator);
+        let fragment = |nodes: std::vec::Vec<_>| ast.fragment(ast.new_vec_from_iter(nodes), false);
+        let expression =
+            || js.literal_boolean_expression(js.boolean_literal(Span::default(), true));
+        let tag = || {
+            FragmentNode::Tag(Tag::ExpressionTag(ast.expression_tag(Span::default(), expression())))
+        };
+        let attribute = |name: &'static str, value: &'static str| {
+            let text = ast.text(Span::default(), Atom::from(value));
+            let sequence = ast.new_vec_single(AttributeSequenceValue::Text(text));
+            ElementAttribute::Attribute(ast.attribute(
+                Span::default(),
+                Atom::from(name),
+                Some(ast.attribute_value(Span::default(), sequence)),
+            ))
+        };
+        let element = |name: &'static str, attributes: std::vec::Vec<_>, nodes| {
+            FragmentNode::Element(ast.regular_element(
+                Span::default(),
+                Atom::from(name),
+                ast.new_vec_from_iter(attributes),
+                fragment(nodes),
+            ))
+        };
+
+        // <button type="submit">{true}</button><div><span>{true}</span></div>{#if true}<p></p>{/if}
+        let if_block = ast.if_block(
+            Span::default(),
+            false,
+            expression(),
+            fragment(vec![element("p", vec![], vec![])]),
+            None,
+        );
+        let root = ast.root(
+            Span::default(),
+            fragment(vec![
+                element("button", vec![attribute("type", "submit")], vec![tag()]),
+                element("div", vec![], vec![element("span", vec![], vec![tag()])]),
+                FragmentNode::Block(Block::IfBlock(if_block)),
+            ]),
+            None,
+            None,
+            None,
+            false,
+        );
+
+        let select = |source: &str| {
+            let selector = Selector::parse(source).unwrap();
+            root.select(&selector)
+                .into_iter()
+                .map(|node| {
+                    element_name(node)
+                        .map_or_else(|| node.debug_name().into_owned(), str::to_string)
+                })
+                .collect::<std::vec::Vec<_>>()
+        };
+        assert_eq!(select("RegularElement[name=button] > ExpressionTag"), ["ExpressionTag"]);
+        assert_eq!(select("RegularElement[name=div] > ExpressionTag"), [] as [&str; 0]);
+        assert_eq!(select("RegularElement[name='div'] ExpressionTag"), ["ExpressionTag"]);
+        assert_eq!(select("RegularElement:has(ExpressionTag)"), ["button", "div", "span"]);
+        assert_eq!(select("IfBlock :element, [attributes.type=submit]"), ["button", "p"]);
+        assert_eq!(select(":element:not([name=span], [name=p])"), ["button", "div"]);
+        assert_eq!(select("Root > :block:not([elseif])"), ["IfBlock"]);
+        assert_eq!(select("*[name!=button]:is(:element)"), ["div", "span", "p"]);
+
+        for (source, error) in [
+            ("Element", "Unknown node type `Element`"),
+            ("RegularElement:first", "Unknown pseudo-class `:first`"),
+            ("RegularElement[name", "Expected `]` at the end of the selector"),
+            ("IfBlock ~ Text", "Unexpected `~` at offset 8"),
+        ] {
+            assert_eq!(Selector::parse(source), Err(error.to_string()), "{source}");
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//! Position-free serialization
+//!
+//! Serializes ASTs to JSON without their positions, so that snapshots and
+//! comparisons of ASTs across versions aren't invalidated by edits that only
+//! move nodes, like whitespace changes.
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// Keys of the position-derived data removed by [`strip_positions`]. The
+/// spans of the nodes of the template, the scripts and the styles are all
+/// flattened into `start` and `end`.
+const POSITION_KEYS: [&str; 4] = ["start", "end", "loc", "range"];
+
+/// `value` as JSON, without its positions, see [`strip_positions`].
+///
+/// # Errors
+///
+/// If `value` fails to serialize.
+pub fn to_value<T: Serialize + ?Sized>(value: &T) -> serde_json::Result<Value> {
+    let mut value = serde_json::to_value(value)?;
+    strip_positions(&mut value);
+    Ok(value)
+}
+
+/// `value` as pretty-printed JSON, without its positions, e.g. for snapshot
+/// tests.
+///
+/// # Errors
+///
+/// If `value` fails to serialize.
+pub fn to_string_pretty<T: Serialize + ?Sized>(value: &T) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(&to_value(value)?)
+}
+
+/// Remove the spans and locations from the objects of `value`, recursively.
+/// Keys named like them whose value isn't a position, e.g. a string, are
+/// kept.
+pub fn strip_positions(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            object.retain(|key, value| {
+                !(POSITION_KEYS.contains(&key.as_str())
+                    && (value.is_number() || value.is_object() || value.is_array()))
+            });
+            object.values_mut().for_each(strip_positions);
+        }
+        Value::Array(values) => values.iter_mut().for_each(strip_positions),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_span::{Atom, Span};
+    use serde_json::json;
+
+    use super::{strip_positions, to_value};
+    use crate::ast::Text;
+
+    #[test]
+    fn strip() {
+        let text = |start, end| Text {
+            span: Span::new(start, end),
+            data: Atom::from("hi"),
+            raw: Atom::from("hi"),
+        };
+        assert_eq!(
+            to_value(&text(3, 5)).unwrap(),
+            json!({ "type": "Text", "data": "hi", "raw": "hi" })
+        );
+        assert_eq!(to_value(&text(3, 5)).unwrap(), to_value(&text(10, 12)).unwrap());
+
+        let mut value = json!([{
+            "type": "Literal",
+            "loc": { "start": { "line": 1, "column": 0 } },
+            "range": [0, 1],
+            "end": "-",
+            "value": { "start": 0, "raw": "0" },
+        }]);
+        strip_positions(&mut value);
+        assert_eq!(value, json!([{ "type": "Literal", "end": "-", "value": { "raw": "0" } }]));
+    }
+}
@@ -6,6 +6,7 @@ impl<'a> GetSpan for FragmentNode<'a> {
     fn span(&self) -> Span {
         match self {
             FragmentNode::Text(text) => text.span,
+            FragmentNode::Comment(comment) => comment.span,
             FragmentNode::Tag(tag) => tag.span(),
             FragmentNode::Element(element) => element.span(),
             FragmentNode::Block(block) => block.span(),
@@ -33,6 +34,7 @@ impl<'a> GetSpan for Element<'a> {
             Element::SlotElement(slot) => slot.span,
             Element::RegularElement(regular) => regular.span,
             Element::SvelteBody(svelte_body) => svelte_body.span,
+            Element::SvelteBoundary(svelte_boundary) => svelte_boundary.span,
             Element::SvelteComponent(svelte_component) => svelte_component.span,
             Element::SvelteDocument(svelte_document) => svelte_document.span,
             Element::SvelteElement(svelte_element) => svelte_element.span,
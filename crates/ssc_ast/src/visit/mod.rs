@@ -5,3 +5,181 @@ mod visit_mut;
 
 pub use visit::*;
 pub use visit_mut::*;
+
+/// What a visitor's `enter_node` asks of the walk.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum VisitControl {
+    /// Walk the children of the node.
+    #[default]
+    Continue,
+    /// Don't walk the children of the node, its `leave_node` is still called.
+    Skip,
+    /// Like [`VisitControl::Skip`], and no other node is walked: the walk
+    /// returns [`ControlFlow::Break`] up to where it started.
+    ///
+    /// [`ControlFlow::Break`]: std::ops::ControlFlow::Break
+    Stop,
+}
+
+impl VisitControl {
+    pub fn is_continue(self) -> bool {
+        self == Self::Continue
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_ast::ast::Expression;
+    use oxc_span::{Atom, Span};
+
+    use super::*;
+    use crate::{ast::*, AstBuilder, AstKind};
+
+    #[derive(Default)]
+    struct Collector {
+        kinds: std::vec::Vec<String>,
+        left: usize,
+        expressions: usize,
+        /// What `enter_node` returns for the nodes of the given kinds, instead
+        /// of `Continue`.
+        controls: std::vec::Vec<(&'static str, VisitControl)>,
+    }
+
+    impl<'a> Visit<'a> for Collector {
+        fn enter_node(&mut self, kind: AstKind<'a>) -> VisitControl {
+            let name = kind.debug_name().into_owned();
+            let control = self.controls.iter().find(|(kind, _)| *kind == name);
+            self.kinds.push(name);
+            control.map_or(VisitControl::Continue, |(_, control)| *control)
+        }
+
+        fn leave_node(&mut self, _kind: AstKind<'a>) {
+            self.left += 1;
+        }
+
+        fn visit_expression(&mut self, _expression: &Expression<'a>) {
+            self.expressions += 1;
+        }
+    }
+
+    /// `<div class:a={true} on:click {...true}>{true}</div>`
+    fn root(allocator: &Allocator) -> Root<'_> {
+        let ast = AstBuilder::new(allocator);
+        let js = oxc_ast::AstBuilder::new(allocator);
+        let expression =
+            || js.literal_boolean_expression(js.boolean_literal(Span::default(), true));
+
+        let attributes = ast.new_vec_from_iter([
+            ElementAttribute::DirectiveAttribute(ast.class_directive(
+                Span::default(),
+                Atom::from("a"),
+                expression(),
+            )),
+            ElementAttribute::DirectiveAttribute(ast.on_directive(
+                Span::default(),
+                Atom::from("click"),
+                None,
+                ast.new_vec(),
+            )),
+            ElementAttribute::SpreadAttribute(ast.spread_attribute(Span::default(), expression())),
+        ]);
+        let tag = Tag::ExpressionTag(ast.expression_tag(Span::default(), expression()));
+        let element = ast.regular_element(
+            Span::default(),
+            Atom::from("div"),
+            attributes,
+            ast.fragment(ast.new_vec_single(FragmentNode::Tag(tag)), false),
+        );
+        ast.root(
+            Span::default(),
+            ast.fragment(ast.new_vec_single(FragmentNode::Element(element)), false),
+            None,
+            None,
+            None,
+            false,
+        )
+    }
+
+    #[test]
+    fn visit_attributes() {
+        let allocator = Allocator::default();
+        let root = root(&allocator);
+        let mut collector = Collector::default();
+        assert!(collector.visit_root(&root).is_continue());
+        assert_eq!(
+            collector.kinds,
+            [
+                "Root",
+                "RegularElement",
+                "ClassDirective",
+                "OnDirective",
+                "SpreadAttribute",
+                "ExpressionTag"
+            ]
+        );
+        assert_eq!(collector.left, collector.kinds.len());
+        assert_eq!(collector.expressions, 3);
+    }
+
+    #[test]
+    fn skip() {
+        let allocator = Allocator::default();
+        let root = root(&allocator);
+        let mut collector = Collector {
+            controls: vec![
+                ("ClassDirective", VisitControl::Skip),
+                ("OnDirective", VisitControl::Skip),
+            ],
+            ..Collector::default()
+        };
+        assert!(collector.visit_root(&root).is_continue());
+        assert_eq!(
+            collector.kinds,
+            [
+                "Root",
+                "RegularElement",
+                "ClassDirective",
+                "OnDirective",
+                "SpreadAttribute",
+                "ExpressionTag"
+            ]
+        );
+        assert_eq!(collector.left, collector.kinds.len());
+        // The expression of the class directive is skipped
+        assert_eq!(collector.expressions, 2);
+
+        let mut collector = Collector {
+            controls: vec![("RegularElement", VisitControl::Skip)],
+            ..Collector::default()
+        };
+        assert!(collector.visit_root(&root).is_continue());
+        assert_eq!(collector.kinds, ["Root", "RegularElement"]);
+        assert_eq!(collector.left, 2);
+        assert_eq!(collector.expressions, 0);
+    }
+
+    #[test]
+    fn stop() {
+        let allocator = Allocator::default();
+        let root = root(&allocator);
+        let mut collector = Collector {
+            controls: vec![("ClassDirective", VisitControl::Stop)],
+            ..Collector::default()
+        };
+        assert!(collector.visit_root(&root).is_break());
+        assert_eq!(collector.kinds, ["Root", "RegularElement", "ClassDirective"]);
+        // The stopped node and its ancestors are still left
+        assert_eq!(collector.left, 3);
+        assert_eq!(collector.expressions, 0);
+
+        // Stopping in a fragment skips the rest of the walk
+        let mut collector = Collector {
+            controls: vec![("ExpressionTag", VisitControl::Stop)],
+            ..Collector::default()
+        };
+        assert!(collector.visit_fragment(&root.fragment).is_break());
+        assert_eq!(collector.kinds.last().map(String::as_str), Some("ExpressionTag"));
+        assert_eq!(collector.expressions, 2);
+    }
+}
@@ -4,13 +4,30 @@
 //! * [visitor pattern](https://rust-unofficial.github.io/patterns/patterns/behavioural/visitor.html)
 //! * [rustc visitor](https://github.com/rust-lang/rust/blob/master/compiler/rustc_ast/src/visit.rs)
 
+use std::ops::ControlFlow;
+
+use oxc_ast::ast::{
+    ArrayExpression, BindingPattern, CallExpression, Expression, IdentifierReference,
+    MemberExpression, ObjectExpression, Program, VariableDeclaration,
+};
+use ssc_css_ast::ast::StyleSheet;
 use walk::*;
 
+use super::VisitControl;
 use crate::{ast::*, ast_kind::AstKind};
 
 /// Syntax tree traversal
+///
+/// The `visit_*` methods of the nodes of the template return
+/// [`ControlFlow::Break`] once the walk was stopped, overrides return the
+/// result of walking the node so that it is passed up to where the walk
+/// started.
 pub trait Visit<'a>: Sized {
-    fn enter_node(&mut self, _kind: AstKind<'a>) {}
+    /// Called before walking the children of a node, which can be skipped, or
+    /// the rest of the walk stopped.
+    fn enter_node(&mut self, _kind: AstKind<'a>) -> VisitControl {
+        VisitControl::Continue
+    }
     fn leave_node(&mut self, _kind: AstKind<'a>) {}
 
     fn alloc<T>(&self, t: &T) -> &'a T {
@@ -23,172 +40,327 @@ pub trait Visit<'a>: Sized {
         }
     }
 
-    fn visit_root(&mut self, root: &Root<'a>) {
-        walk_root(self, root);
+    fn visit_root(&mut self, root: &Root<'a>) -> ControlFlow<()> {
+        walk_root(self, root)
     }
 
     /* ----------  Fragment ---------- */
 
-    fn visit_fragment(&mut self, fragment: &Fragment<'a>) {
-        walk_fragment(self, fragment);
+    fn visit_fragment(&mut self, fragment: &Fragment<'a>) -> ControlFlow<()> {
+        walk_fragment(self, fragment)
+    }
+
+    fn visit_fragment_node(&mut self, node: &FragmentNode<'a>) -> ControlFlow<()> {
+        walk_fragment_node(self, node)
     }
 
-    fn visit_fragment_node(&mut self, node: &FragmentNode<'a>) {
-        walk_fragment_node(self, node);
+    fn visit_text(&mut self, text: &Text<'a>) -> ControlFlow<()> {
+        walk_text(self, text)
     }
 
-    fn visit_text(&mut self, text: &Text<'a>) {
-        walk_text(self, text);
+    fn visit_comment(&mut self, comment: &Comment<'a>) -> ControlFlow<()> {
+        walk_comment(self, comment)
     }
 
     /* ----------  Tag ---------- */
 
-    fn visit_tag(&mut self, tag: &Tag<'a>) {
-        walk_tag(self, tag);
+    fn visit_tag(&mut self, tag: &Tag<'a>) -> ControlFlow<()> {
+        walk_tag(self, tag)
     }
 
-    fn visit_expression_tag(&mut self, expression_tag: &ExpressionTag<'a>) {
-        walk_expression_tag(self, expression_tag);
+    fn visit_expression_tag(&mut self, expression_tag: &ExpressionTag<'a>) -> ControlFlow<()> {
+        walk_expression_tag(self, expression_tag)
     }
 
-    fn visit_html_tag(&mut self, html_tag: &HtmlTag<'a>) {
-        walk_html_tag(self, html_tag);
+    fn visit_html_tag(&mut self, html_tag: &HtmlTag<'a>) -> ControlFlow<()> {
+        walk_html_tag(self, html_tag)
     }
 
-    fn visit_const_tag(&mut self, const_tag: &ConstTag<'a>) {
-        walk_const_tag(self, const_tag);
+    fn visit_const_tag(&mut self, const_tag: &ConstTag<'a>) -> ControlFlow<()> {
+        walk_const_tag(self, const_tag)
     }
 
-    fn visit_debug_tag(&mut self, debug_tag: &DebugTag<'a>) {
-        walk_debug_tag(self, debug_tag);
+    fn visit_debug_tag(&mut self, debug_tag: &DebugTag<'a>) -> ControlFlow<()> {
+        walk_debug_tag(self, debug_tag)
     }
 
-    fn visit_render_tag(&mut self, render_tag: &RenderTag<'a>) {
-        walk_render_tag(self, render_tag);
+    fn visit_render_tag(&mut self, render_tag: &RenderTag<'a>) -> ControlFlow<()> {
+        walk_render_tag(self, render_tag)
     }
 
     /* ----------  Element ---------- */
 
-    fn visit_element(&mut self, element: &Element<'a>) {
-        walk_element(self, element);
+    fn visit_element(&mut self, element: &Element<'a>) -> ControlFlow<()> {
+        walk_element(self, element)
     }
 
-    fn visit_component(&mut self, component: &Component<'a>) {
-        walk_component(self, component);
+    fn visit_component(&mut self, component: &Component<'a>) -> ControlFlow<()> {
+        walk_component(self, component)
     }
 
-    fn visit_title_element(&mut self, title_element: &TitleElement<'a>) {
-        walk_title_element(self, title_element);
+    fn visit_title_element(&mut self, title_element: &TitleElement<'a>) -> ControlFlow<()> {
+        walk_title_element(self, title_element)
     }
 
-    fn visit_slot_element(&mut self, slot_element: &SlotElement<'a>) {
-        walk_slot_element(self, slot_element);
+    fn visit_slot_element(&mut self, slot_element: &SlotElement<'a>) -> ControlFlow<()> {
+        walk_slot_element(self, slot_element)
+    }
+
+    fn visit_regular_element(&mut self, regular_element: &RegularElement<'a>) -> ControlFlow<()> {
+        walk_regular_element(self, regular_element)
+    }
+
+    fn visit_svelte_body(&mut self, svelte_body: &SvelteBody<'a>) -> ControlFlow<()> {
+        walk_svelte_body(self, svelte_body)
+    }
+
+    fn visit_svelte_boundary(&mut self, svelte_boundary: &SvelteBoundary<'a>) -> ControlFlow<()> {
+        walk_svelte_boundary(self, svelte_boundary)
+    }
+
+    fn visit_svelte_component(
+        &mut self,
+        svelte_component: &SvelteComponent<'a>,
+    ) -> ControlFlow<()> {
+        walk_svelte_component(self, svelte_component)
     }
 
-    fn visit_regular_element(&mut self, regular_element: &RegularElement<'a>) {
-        walk_regular_element(self, regular_element);
+    fn visit_svelte_document(&mut self, svelte_document: &SvelteDocument<'a>) -> ControlFlow<()> {
+        walk_svelte_document(self, svelte_document)
     }
 
-    fn visit_svelte_body(&mut self, svelte_body: &SvelteBody<'a>) {
-        walk_svelte_body(self, svelte_body);
+    fn visit_svelte_element(&mut self, svelte_element: &SvelteElement<'a>) -> ControlFlow<()> {
+        walk_svelte_element(self, svelte_element)
     }
 
-    fn visit_svelte_component(&mut self, svelte_component: &SvelteComponent<'a>) {
-        walk_svelte_component(self, svelte_component);
+    fn visit_svelte_fragment(&mut self, svelte_fragment: &SvelteFragment<'a>) -> ControlFlow<()> {
+        walk_svelte_fragment(self, svelte_fragment)
+    }
+
+    fn visit_svelte_head(&mut self, svelte_head: &SvelteHead<'a>) -> ControlFlow<()> {
+        walk_svelte_head(self, svelte_head)
+    }
+
+    fn visit_svelte_options_raw(
+        &mut self,
+        svelte_options_raw: &SvelteOptionsRaw<'a>,
+    ) -> ControlFlow<()> {
+        walk_svelte_options_raw(self, svelte_options_raw)
+    }
+
+    fn visit_svelte_self(&mut self, svelte_self: &SvelteSelf<'a>) -> ControlFlow<()> {
+        walk_svelte_self(self, svelte_self)
+    }
+
+    fn visit_svelte_window(&mut self, svelte_window: &SvelteWindow<'a>) -> ControlFlow<()> {
+        walk_svelte_window(self, svelte_window)
+    }
+
+    /* ----------  Attribute ---------- */
+
+    fn visit_element_attributes(&mut self, attributes: &[ElementAttribute<'a>]) -> ControlFlow<()> {
+        walk_element_attributes(self, attributes)
     }
 
-    fn visit_svelte_document(&mut self, svelte_document: &SvelteDocument<'a>) {
-        walk_svelte_document(self, svelte_document);
+    fn visit_element_attribute(&mut self, attribute: &ElementAttribute<'a>) -> ControlFlow<()> {
+        walk_element_attribute(self, attribute)
     }
 
-    fn visit_svelte_element(&mut self, svelte_element: &SvelteElement<'a>) {
-        walk_svelte_element(self, svelte_element);
+    fn visit_attribute(&mut self, attribute: &Attribute<'a>) -> ControlFlow<()> {
+        walk_attribute(self, attribute)
     }
 
-    fn visit_svelte_fragment(&mut self, svelte_fragment: &SvelteFragment<'a>) {
-        walk_svelte_fragment(self, svelte_fragment);
+    fn visit_attribute_value(&mut self, value: &AttributeValue<'a>) -> ControlFlow<()> {
+        walk_attribute_value(self, value)
     }
 
-    fn visit_svelte_head(&mut self, svelte_head: &SvelteHead<'a>) {
-        walk_svelte_head(self, svelte_head);
+    fn visit_spread_attribute(
+        &mut self,
+        spread_attribute: &SpreadAttribute<'a>,
+    ) -> ControlFlow<()> {
+        walk_spread_attribute(self, spread_attribute)
     }
 
-    fn visit_svelte_options_raw(&mut self, svelte_options_raw: &SvelteOptionsRaw<'a>) {
-        walk_svelte_options_raw(self, svelte_options_raw);
+    fn visit_directive_attribute(&mut self, directive: &DirectiveAttribute<'a>) -> ControlFlow<()> {
+        walk_directive_attribute(self, directive)
     }
 
-    fn visit_svelte_self(&mut self, svelte_self: &SvelteSelf<'a>) {
-        walk_svelte_self(self, svelte_self);
+    fn visit_animate_directive(&mut self, directive: &AnimateDirective<'a>) -> ControlFlow<()> {
+        walk_animate_directive(self, directive)
     }
 
-    fn visit_svelte_window(&mut self, svelte_window: &SvelteWindow<'a>) {
-        walk_svelte_window(self, svelte_window);
+    fn visit_bind_directive(&mut self, directive: &BindDirective<'a>) -> ControlFlow<()> {
+        walk_bind_directive(self, directive)
+    }
+
+    fn visit_class_directive(&mut self, directive: &ClassDirective<'a>) -> ControlFlow<()> {
+        walk_class_directive(self, directive)
+    }
+
+    fn visit_let_directive(&mut self, directive: &LetDirective<'a>) -> ControlFlow<()> {
+        walk_let_directive(self, directive)
+    }
+
+    fn visit_on_directive(&mut self, directive: &OnDirective<'a>) -> ControlFlow<()> {
+        walk_on_directive(self, directive)
+    }
+
+    fn visit_style_directive(&mut self, directive: &StyleDirective<'a>) -> ControlFlow<()> {
+        walk_style_directive(self, directive)
+    }
+
+    fn visit_transition_directive(
+        &mut self,
+        directive: &TransitionDirective<'a>,
+    ) -> ControlFlow<()> {
+        walk_transition_directive(self, directive)
+    }
+
+    fn visit_use_directive(&mut self, directive: &UseDirective<'a>) -> ControlFlow<()> {
+        walk_use_directive(self, directive)
     }
 
     /* ----------  Block ---------- */
 
-    fn visit_block(&mut self, block: &Block<'a>) {
-        walk_block(self, block);
+    fn visit_block(&mut self, block: &Block<'a>) -> ControlFlow<()> {
+        walk_block(self, block)
     }
 
-    fn visit_each_block(&mut self, each_block: &EachBlock<'a>) {
-        walk_each_block(self, each_block);
+    fn visit_each_block(&mut self, each_block: &EachBlock<'a>) -> ControlFlow<()> {
+        walk_each_block(self, each_block)
     }
 
-    fn visit_if_block(&mut self, if_block: &IfBlock<'a>) {
-        walk_if_block(self, if_block);
+    fn visit_if_block(&mut self, if_block: &IfBlock<'a>) -> ControlFlow<()> {
+        walk_if_block(self, if_block)
     }
 
-    fn visit_await_block(&mut self, await_block: &AwaitBlock<'a>) {
-        walk_await_block(self, await_block);
+    fn visit_await_block(&mut self, await_block: &AwaitBlock<'a>) -> ControlFlow<()> {
+        walk_await_block(self, await_block)
     }
 
-    fn visit_key_block(&mut self, key_block: &KeyBlock<'a>) {
-        walk_key_block(self, key_block);
+    fn visit_key_block(&mut self, key_block: &KeyBlock<'a>) -> ControlFlow<()> {
+        walk_key_block(self, key_block)
     }
 
-    fn visit_snippet_block(&mut self, snippet_block: &SnippetBlock<'a>) {
-        walk_snippet_block(self, snippet_block);
+    fn visit_snippet_block(&mut self, snippet_block: &SnippetBlock<'a>) -> ControlFlow<()> {
+        walk_snippet_block(self, snippet_block)
     }
+
+    /* ----------  Script and Style ---------- */
+
+    fn visit_script(&mut self, script: &Script<'a>) -> ControlFlow<()> {
+        walk_script(self, script)
+    }
+
+    fn visit_style(&mut self, style: &Style<'a>) -> ControlFlow<()> {
+        walk_style(self, style)
+    }
+
+    /* ----------  JavaScript and CSS ---------- */
+
+    // The scripts, styles and expressions embedded in the template aren't
+    // walked, override these to reach them, e.g. by delegating to an
+    // `oxc_ast::Visit` or an `ssc_css_ast::Visit`.
+
+    fn visit_program(&mut self, _program: &Program<'a>) {}
+
+    fn visit_expression(&mut self, _expression: &Expression<'a>) {}
+
+    fn visit_binding_pattern(&mut self, _pattern: &BindingPattern<'a>) {}
+
+    fn visit_identifier_reference(&mut self, _ident: &IdentifierReference<'a>) {}
+
+    fn visit_variable_declaration(&mut self, _declaration: &VariableDeclaration<'a>) {}
+
+    fn visit_call_expression(&mut self, _expr: &CallExpression<'a>) {}
+
+    fn visit_member_expression(&mut self, _expr: &MemberExpression<'a>) {}
+
+    fn visit_array_expression(&mut self, _expr: &ArrayExpression<'a>) {}
+
+    fn visit_object_expression(&mut self, _expr: &ObjectExpression<'a>) {}
+
+    fn visit_stylesheet(&mut self, _stylesheet: &StyleSheet<'a>) {}
 }
 
 pub mod walk {
     use super::*;
 
-    pub fn walk_root<'a, V: Visit<'a>>(visitor: &mut V, root: &Root<'a>) {
-        let kind = AstKind::Root(visitor.alloc(root));
-        visitor.enter_node(kind);
-
-        visitor.visit_fragment(&root.fragment);
+    /// Enter the node of `kind`, walk its children with `walk_children` unless
+    /// `enter_node` skips them, and leave it. Breaks if the walk was stopped,
+    /// by the node or one of its descendants, which are all left first.
+    fn walk_node<'a, V: Visit<'a>, F: FnOnce(&mut V) -> ControlFlow<()>>(
+        visitor: &mut V,
+        kind: AstKind<'a>,
+        walk_children: F,
+    ) -> ControlFlow<()> {
+        let flow = match visitor.enter_node(kind) {
+            VisitControl::Continue => walk_children(visitor),
+            VisitControl::Skip => ControlFlow::Continue(()),
+            VisitControl::Stop => ControlFlow::Break(()),
+        };
         visitor.leave_node(kind);
+        flow
+    }
+
+    pub fn walk_root<'a, V: Visit<'a>>(visitor: &mut V, root: &Root<'a>) -> ControlFlow<()> {
+        let kind = AstKind::Root(visitor.alloc(root));
+        walk_node(visitor, kind, |visitor| {
+            if let Some(module) = root.module.as_ref() {
+                visitor.visit_script(module)?;
+            }
+            if let Some(instance) = root.instance.as_ref() {
+                visitor.visit_script(instance)?;
+            }
+            visitor.visit_fragment(&root.fragment)?;
+            if let Some(css) = root.css.as_ref() {
+                visitor.visit_style(css)?;
+            }
+            ControlFlow::Continue(())
+        })
     }
 
     /* ----------  Fragment ---------- */
 
-    pub fn walk_fragment<'a, V: Visit<'a>>(visitor: &mut V, fragment: &Fragment<'a>) {
+    pub fn walk_fragment<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        fragment: &Fragment<'a>,
+    ) -> ControlFlow<()> {
         for node in &fragment.nodes {
-            visitor.visit_fragment_node(node);
+            visitor.visit_fragment_node(node)?;
         }
+        ControlFlow::Continue(())
     }
 
-    pub fn walk_fragment_node<'a, V: Visit<'a>>(visitor: &mut V, node: &FragmentNode<'a>) {
+    pub fn walk_fragment_node<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        node: &FragmentNode<'a>,
+    ) -> ControlFlow<()> {
         match node {
             FragmentNode::Text(text) => visitor.visit_text(text),
+            FragmentNode::Comment(comment) => visitor.visit_comment(comment),
             FragmentNode::Tag(tag) => visitor.visit_tag(tag),
             FragmentNode::Element(element) => visitor.visit_element(element),
             FragmentNode::Block(block) => visitor.visit_block(block),
         }
     }
 
-    pub fn walk_text<'a, V: Visit<'a>>(visitor: &mut V, text: &Text<'a>) {
+    pub fn walk_text<'a, V: Visit<'a>>(visitor: &mut V, text: &Text<'a>) -> ControlFlow<()> {
         let kind = AstKind::Text(visitor.alloc(text));
-        visitor.enter_node(kind);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |_| ControlFlow::Continue(()))
+    }
+
+    pub fn walk_comment<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        comment: &Comment<'a>,
+    ) -> ControlFlow<()> {
+        let kind = AstKind::Comment(visitor.alloc(comment));
+        walk_node(visitor, kind, |_| ControlFlow::Continue(()))
     }
 
     /* ----------  Tag ---------- */
 
-    pub fn walk_tag<'a, V: Visit<'a>>(visitor: &mut V, tag: &Tag<'a>) {
+    pub fn walk_tag<'a, V: Visit<'a>>(visitor: &mut V, tag: &Tag<'a>) -> ControlFlow<()> {
         match tag {
             Tag::ExpressionTag(expression_tag) => visitor.visit_expression_tag(expression_tag),
             Tag::HtmlTag(html_tag) => visitor.visit_html_tag(html_tag),
@@ -201,178 +373,479 @@ pub mod walk {
     pub fn walk_expression_tag<'a, V: Visit<'a>>(
         visitor: &mut V,
         expression_tag: &ExpressionTag<'a>,
-    ) {
+    ) -> ControlFlow<()> {
         let kind = AstKind::ExpressionTag(visitor.alloc(expression_tag));
-        visitor.enter_node(kind);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_expression(&expression_tag.expression);
+            ControlFlow::Continue(())
+        })
     }
 
-    pub fn walk_html_tag<'a, V: Visit<'a>>(visitor: &mut V, html_tag: &HtmlTag<'a>) {
+    pub fn walk_html_tag<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        html_tag: &HtmlTag<'a>,
+    ) -> ControlFlow<()> {
         let kind = AstKind::HtmlTag(visitor.alloc(html_tag));
-        visitor.enter_node(kind);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_expression(&html_tag.expression);
+            ControlFlow::Continue(())
+        })
     }
 
-    pub fn walk_const_tag<'a, V: Visit<'a>>(visitor: &mut V, const_tag: &ConstTag<'a>) {
+    pub fn walk_const_tag<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        const_tag: &ConstTag<'a>,
+    ) -> ControlFlow<()> {
         let kind = AstKind::ConstTag(visitor.alloc(const_tag));
-        visitor.enter_node(kind);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_variable_declaration(&const_tag.declaration);
+            ControlFlow::Continue(())
+        })
     }
 
-    pub fn walk_debug_tag<'a, V: Visit<'a>>(visitor: &mut V, debug_tag: &DebugTag<'a>) {
+    pub fn walk_debug_tag<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        debug_tag: &DebugTag<'a>,
+    ) -> ControlFlow<()> {
         let kind = AstKind::DebugTag(visitor.alloc(debug_tag));
-        visitor.enter_node(kind);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            for ident in &debug_tag.identifiers {
+                visitor.visit_identifier_reference(ident);
+            }
+            ControlFlow::Continue(())
+        })
     }
 
-    pub fn walk_render_tag<'a, V: Visit<'a>>(visitor: &mut V, render_tag: &RenderTag<'a>) {
+    pub fn walk_render_tag<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        render_tag: &RenderTag<'a>,
+    ) -> ControlFlow<()> {
         let kind = AstKind::RenderTag(visitor.alloc(render_tag));
-        visitor.enter_node(kind);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            match &render_tag.expression {
+                RenderTagExpression::Call(expr) | RenderTagExpression::Chain(expr) => {
+                    visitor.visit_call_expression(expr);
+                }
+            }
+            ControlFlow::Continue(())
+        })
     }
 
     /* ----------  Element ---------- */
 
-    pub fn walk_element<'a, V: Visit<'a>>(visitor: &mut V, element: &Element<'a>) {
+    pub fn walk_element<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        element: &Element<'a>,
+    ) -> ControlFlow<()> {
         match element {
             Element::Component(component) => visitor.visit_component(component),
             Element::TitleElement(title_element) => visitor.visit_title_element(title_element),
             Element::SlotElement(slot_element) => visitor.visit_slot_element(slot_element),
             Element::RegularElement(regular_element) => {
-                visitor.visit_regular_element(regular_element);
+                visitor.visit_regular_element(regular_element)
             }
             Element::SvelteBody(svelte_body) => visitor.visit_svelte_body(svelte_body),
+            Element::SvelteBoundary(svelte_boundary) => {
+                visitor.visit_svelte_boundary(svelte_boundary)
+            }
             Element::SvelteComponent(svelte_component) => {
-                visitor.visit_svelte_component(svelte_component);
+                visitor.visit_svelte_component(svelte_component)
             }
             Element::SvelteDocument(svelte_document) => {
-                visitor.visit_svelte_document(svelte_document);
+                visitor.visit_svelte_document(svelte_document)
             }
             Element::SvelteElement(svelte_element) => visitor.visit_svelte_element(svelte_element),
             Element::SvelteFragment(svelte_fragment) => {
-                visitor.visit_svelte_fragment(svelte_fragment);
+                visitor.visit_svelte_fragment(svelte_fragment)
             }
             Element::SvelteHead(svelte_head) => visitor.visit_svelte_head(svelte_head),
             Element::SvelteOptionsRaw(svelte_options_raw) => {
-                visitor.visit_svelte_options_raw(svelte_options_raw);
+                visitor.visit_svelte_options_raw(svelte_options_raw)
             }
             Element::SvelteSelf(svelte_self) => visitor.visit_svelte_self(svelte_self),
             Element::SvelteWindow(svelte_window) => visitor.visit_svelte_window(svelte_window),
         }
     }
 
-    pub fn walk_component<'a, V: Visit<'a>>(visitor: &mut V, component: &Component<'a>) {
+    pub fn walk_component<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        component: &Component<'a>,
+    ) -> ControlFlow<()> {
         let kind = AstKind::Component(visitor.alloc(component));
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&component.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_element_attributes(&component.attributes)?;
+            visitor.visit_fragment(&component.fragment)?;
+            ControlFlow::Continue(())
+        })
     }
 
-    pub fn walk_title_element<'a, V: Visit<'a>>(visitor: &mut V, title_element: &TitleElement<'a>) {
+    pub fn walk_title_element<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        title_element: &TitleElement<'a>,
+    ) -> ControlFlow<()> {
         let kind = AstKind::TitleElement(visitor.alloc(title_element));
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&title_element.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_element_attributes(&title_element.attributes)?;
+            visitor.visit_fragment(&title_element.fragment)?;
+            ControlFlow::Continue(())
+        })
     }
 
-    pub fn walk_slot_element<'a, V: Visit<'a>>(visitor: &mut V, slot_element: &SlotElement<'a>) {
+    pub fn walk_slot_element<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        slot_element: &SlotElement<'a>,
+    ) -> ControlFlow<()> {
         let kind = AstKind::SlotElement(visitor.alloc(slot_element));
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&slot_element.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_element_attributes(&slot_element.attributes)?;
+            visitor.visit_fragment(&slot_element.fragment)?;
+            ControlFlow::Continue(())
+        })
     }
 
     pub fn walk_regular_element<'a, V: Visit<'a>>(
         visitor: &mut V,
         regular_element: &RegularElement<'a>,
-    ) {
+    ) -> ControlFlow<()> {
         let kind = AstKind::RegularElement(visitor.alloc(regular_element));
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&regular_element.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_element_attributes(&regular_element.attributes)?;
+            visitor.visit_fragment(&regular_element.fragment)?;
+            ControlFlow::Continue(())
+        })
     }
 
-    pub fn walk_svelte_body<'a, V: Visit<'a>>(visitor: &mut V, svelte_body: &SvelteBody<'a>) {
+    pub fn walk_svelte_body<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        svelte_body: &SvelteBody<'a>,
+    ) -> ControlFlow<()> {
         let kind = AstKind::SvelteBody(visitor.alloc(svelte_body));
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&svelte_body.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_element_attributes(&svelte_body.attributes)?;
+            visitor.visit_fragment(&svelte_body.fragment)?;
+            ControlFlow::Continue(())
+        })
+    }
+
+    pub fn walk_svelte_boundary<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        svelte_boundary: &SvelteBoundary<'a>,
+    ) -> ControlFlow<()> {
+        let kind = AstKind::SvelteBoundary(visitor.alloc(svelte_boundary));
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_element_attributes(&svelte_boundary.attributes)?;
+            visitor.visit_fragment(&svelte_boundary.fragment)?;
+            ControlFlow::Continue(())
+        })
     }
 
     pub fn walk_svelte_component<'a, V: Visit<'a>>(
         visitor: &mut V,
         svelte_component: &SvelteComponent<'a>,
-    ) {
+    ) -> ControlFlow<()> {
         let kind = AstKind::SvelteComponent(visitor.alloc(svelte_component));
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&svelte_component.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_expression(&svelte_component.expression);
+            visitor.visit_element_attributes(&svelte_component.attributes)?;
+            visitor.visit_fragment(&svelte_component.fragment)?;
+            ControlFlow::Continue(())
+        })
     }
 
     pub fn walk_svelte_document<'a, V: Visit<'a>>(
         visitor: &mut V,
         svelte_document: &SvelteDocument<'a>,
-    ) {
+    ) -> ControlFlow<()> {
         let kind = AstKind::SvelteDocument(visitor.alloc(svelte_document));
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&svelte_document.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_element_attributes(&svelte_document.attributes)?;
+            visitor.visit_fragment(&svelte_document.fragment)?;
+            ControlFlow::Continue(())
+        })
     }
 
     pub fn walk_svelte_element<'a, V: Visit<'a>>(
         visitor: &mut V,
         svelte_element: &SvelteElement<'a>,
-    ) {
+    ) -> ControlFlow<()> {
         let kind = AstKind::SvelteElement(visitor.alloc(svelte_element));
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&svelte_element.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_expression(&svelte_element.expression);
+            visitor.visit_element_attributes(&svelte_element.attributes)?;
+            visitor.visit_fragment(&svelte_element.fragment)?;
+            ControlFlow::Continue(())
+        })
     }
 
     pub fn walk_svelte_fragment<'a, V: Visit<'a>>(
         visitor: &mut V,
         svelte_fragment: &SvelteFragment<'a>,
-    ) {
+    ) -> ControlFlow<()> {
         let kind = AstKind::SvelteFragment(visitor.alloc(svelte_fragment));
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&svelte_fragment.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_element_attributes(&svelte_fragment.attributes)?;
+            visitor.visit_fragment(&svelte_fragment.fragment)?;
+            ControlFlow::Continue(())
+        })
     }
 
-    pub fn walk_svelte_head<'a, V: Visit<'a>>(visitor: &mut V, svelte_head: &SvelteHead<'a>) {
+    pub fn walk_svelte_head<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        svelte_head: &SvelteHead<'a>,
+    ) -> ControlFlow<()> {
         let kind = AstKind::SvelteHead(visitor.alloc(svelte_head));
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&svelte_head.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_element_attributes(&svelte_head.attributes)?;
+            visitor.visit_fragment(&svelte_head.fragment)?;
+            ControlFlow::Continue(())
+        })
     }
 
     pub fn walk_svelte_options_raw<'a, V: Visit<'a>>(
         visitor: &mut V,
         svelte_options_raw: &SvelteOptionsRaw<'a>,
-    ) {
+    ) -> ControlFlow<()> {
         let kind = AstKind::SvelteOptionsRaw(visitor.alloc(svelte_options_raw));
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&svelte_options_raw.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_element_attributes(&svelte_options_raw.attributes)?;
+            visitor.visit_fragment(&svelte_options_raw.fragment)?;
+            ControlFlow::Continue(())
+        })
     }
 
-    pub fn walk_svelte_self<'a, V: Visit<'a>>(visitor: &mut V, svelte_self: &SvelteSelf<'a>) {
+    pub fn walk_svelte_self<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        svelte_self: &SvelteSelf<'a>,
+    ) -> ControlFlow<()> {
         let kind = AstKind::SvelteSelf(visitor.alloc(svelte_self));
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&svelte_self.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_element_attributes(&svelte_self.attributes)?;
+            visitor.visit_fragment(&svelte_self.fragment)?;
+            ControlFlow::Continue(())
+        })
     }
 
-    pub fn walk_svelte_window<'a, V: Visit<'a>>(visitor: &mut V, svelte_window: &SvelteWindow<'a>) {
+    pub fn walk_svelte_window<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        svelte_window: &SvelteWindow<'a>,
+    ) -> ControlFlow<()> {
         let kind = AstKind::SvelteWindow(visitor.alloc(svelte_window));
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&svelte_window.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_element_attributes(&svelte_window.attributes)?;
+            visitor.visit_fragment(&svelte_window.fragment)?;
+            ControlFlow::Continue(())
+        })
+    }
+
+    /* ----------  Attribute ---------- */
+
+    pub fn walk_element_attributes<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        attributes: &[ElementAttribute<'a>],
+    ) -> ControlFlow<()> {
+        for attribute in attributes {
+            visitor.visit_element_attribute(attribute)?;
+        }
+        ControlFlow::Continue(())
+    }
+
+    pub fn walk_element_attribute<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        attribute: &ElementAttribute<'a>,
+    ) -> ControlFlow<()> {
+        match attribute {
+            ElementAttribute::Attribute(attribute) => visitor.visit_attribute(attribute),
+            ElementAttribute::SpreadAttribute(spread_attribute) => {
+                visitor.visit_spread_attribute(spread_attribute)
+            }
+            ElementAttribute::DirectiveAttribute(directive) => {
+                visitor.visit_directive_attribute(directive)
+            }
+        }
+    }
+
+    pub fn walk_attribute<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        attribute: &Attribute<'a>,
+    ) -> ControlFlow<()> {
+        let kind = AstKind::Attribute(visitor.alloc(attribute));
+        walk_node(visitor, kind, |visitor| {
+            if let Some(value) = &attribute.value {
+                visitor.visit_attribute_value(value)?;
+            }
+            ControlFlow::Continue(())
+        })
+    }
+
+    pub fn walk_attribute_value<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        value: &AttributeValue<'a>,
+    ) -> ControlFlow<()> {
+        for part in &value.sequence {
+            match part {
+                AttributeSequenceValue::Text(text) => visitor.visit_text(text)?,
+                AttributeSequenceValue::ExpressionTag(expression_tag) => {
+                    visitor.visit_expression_tag(expression_tag)?;
+                }
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    pub fn walk_spread_attribute<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        spread_attribute: &SpreadAttribute<'a>,
+    ) -> ControlFlow<()> {
+        let kind = AstKind::SpreadAttribute(visitor.alloc(spread_attribute));
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_expression(&spread_attribute.expression);
+            ControlFlow::Continue(())
+        })
+    }
+
+    pub fn walk_directive_attribute<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        directive: &DirectiveAttribute<'a>,
+    ) -> ControlFlow<()> {
+        match directive {
+            DirectiveAttribute::AnimateDirective(directive) => {
+                visitor.visit_animate_directive(directive)
+            }
+            DirectiveAttribute::BindDirective(directive) => visitor.visit_bind_directive(directive),
+            DirectiveAttribute::ClassDirective(directive) => {
+                visitor.visit_class_directive(directive)
+            }
+            DirectiveAttribute::LetDirective(directive) => visitor.visit_let_directive(directive),
+            DirectiveAttribute::OnDirective(directive) => visitor.visit_on_directive(directive),
+            DirectiveAttribute::StyleDirective(directive) => {
+                visitor.visit_style_directive(directive)
+            }
+            DirectiveAttribute::TransitionDirective(directive) => {
+                visitor.visit_transition_directive(directive)
+            }
+            DirectiveAttribute::UseDirective(directive) => visitor.visit_use_directive(directive),
+        }
+    }
+
+    pub fn walk_animate_directive<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        directive: &AnimateDirective<'a>,
+    ) -> ControlFlow<()> {
+        let kind = AstKind::AnimateDirective(visitor.alloc(directive));
+        walk_node(visitor, kind, |visitor| {
+            if let Some(expression) = &directive.expression {
+                visitor.visit_expression(expression);
+            }
+            ControlFlow::Continue(())
+        })
+    }
+
+    pub fn walk_bind_directive<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        directive: &BindDirective<'a>,
+    ) -> ControlFlow<()> {
+        let kind = AstKind::BindDirective(visitor.alloc(directive));
+        walk_node(visitor, kind, |visitor| {
+            match &directive.expression {
+                BindDirectiveExpression::Identifier(ident) => {
+                    visitor.visit_identifier_reference(ident);
+                }
+                BindDirectiveExpression::MemberExpression(expr) => {
+                    visitor.visit_member_expression(expr);
+                }
+            }
+            ControlFlow::Continue(())
+        })
+    }
+
+    pub fn walk_class_directive<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        directive: &ClassDirective<'a>,
+    ) -> ControlFlow<()> {
+        let kind = AstKind::ClassDirective(visitor.alloc(directive));
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_expression(&directive.expression);
+            ControlFlow::Continue(())
+        })
+    }
+
+    pub fn walk_let_directive<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        directive: &LetDirective<'a>,
+    ) -> ControlFlow<()> {
+        let kind = AstKind::LetDirective(visitor.alloc(directive));
+        walk_node(visitor, kind, |visitor| {
+            match &directive.expression {
+                Some(LetDirectiveExpression::Identifier(ident)) => {
+                    visitor.visit_identifier_reference(ident);
+                }
+                Some(LetDirectiveExpression::ArrayExpression(expr)) => {
+                    visitor.visit_array_expression(expr);
+                }
+                Some(LetDirectiveExpression::ObjectExpression(expr)) => {
+                    visitor.visit_object_expression(expr);
+                }
+                None => {}
+            }
+            ControlFlow::Continue(())
+        })
+    }
+
+    pub fn walk_on_directive<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        directive: &OnDirective<'a>,
+    ) -> ControlFlow<()> {
+        let kind = AstKind::OnDirective(visitor.alloc(directive));
+        walk_node(visitor, kind, |visitor| {
+            if let Some(expression) = &directive.expression {
+                visitor.visit_expression(expression);
+            }
+            ControlFlow::Continue(())
+        })
+    }
+
+    pub fn walk_style_directive<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        directive: &StyleDirective<'a>,
+    ) -> ControlFlow<()> {
+        let kind = AstKind::StyleDirective(visitor.alloc(directive));
+        walk_node(visitor, kind, |visitor| {
+            if let Some(value) = &directive.value {
+                visitor.visit_attribute_value(value)?;
+            }
+            ControlFlow::Continue(())
+        })
+    }
+
+    pub fn walk_transition_directive<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        directive: &TransitionDirective<'a>,
+    ) -> ControlFlow<()> {
+        let kind = AstKind::TransitionDirective(visitor.alloc(directive));
+        walk_node(visitor, kind, |visitor| {
+            if let Some(expression) = &directive.expression {
+                visitor.visit_expression(expression);
+            }
+            ControlFlow::Continue(())
+        })
+    }
+
+    pub fn walk_use_directive<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        directive: &UseDirective<'a>,
+    ) -> ControlFlow<()> {
+        let kind = AstKind::UseDirective(visitor.alloc(directive));
+        walk_node(visitor, kind, |visitor| {
+            if let Some(expression) = &directive.expression {
+                visitor.visit_expression(expression);
+            }
+            ControlFlow::Continue(())
+        })
     }
 
     /* ----------  Block ---------- */
 
-    pub fn walk_block<'a, V: Visit<'a>>(visitor: &mut V, block: &Block<'a>) {
+    pub fn walk_block<'a, V: Visit<'a>>(visitor: &mut V, block: &Block<'a>) -> ControlFlow<()> {
         match block {
             Block::EachBlock(each_block) => visitor.visit_each_block(each_block),
             Block::IfBlock(if_block) => visitor.visit_if_block(if_block),
@@ -382,52 +855,107 @@ pub mod walk {
         }
     }
 
-    pub fn walk_each_block<'a, V: Visit<'a>>(visitor: &mut V, each_block: &EachBlock<'a>) {
+    pub fn walk_each_block<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        each_block: &EachBlock<'a>,
+    ) -> ControlFlow<()> {
         let kind = AstKind::EachBlock(visitor.alloc(each_block));
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&each_block.body);
-        if let Some(fallback) = each_block.fallback.as_ref() {
-            visitor.visit_fragment(fallback);
-        }
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_expression(&each_block.expression);
+            visitor.visit_binding_pattern(&each_block.context);
+            if let Some(expression) = &each_block.key {
+                visitor.visit_expression(expression);
+            }
+            visitor.visit_fragment(&each_block.body)?;
+            if let Some(fallback) = each_block.fallback.as_ref() {
+                visitor.visit_fragment(fallback)?;
+            }
+            ControlFlow::Continue(())
+        })
     }
 
-    pub fn walk_if_block<'a, V: Visit<'a>>(visitor: &mut V, if_block: &IfBlock<'a>) {
+    pub fn walk_if_block<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        if_block: &IfBlock<'a>,
+    ) -> ControlFlow<()> {
         let kind = AstKind::IfBlock(visitor.alloc(if_block));
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&if_block.consequent);
-        if let Some(alternate) = if_block.alternate.as_ref() {
-            visitor.visit_fragment(alternate);
-        }
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_expression(&if_block.test);
+            visitor.visit_fragment(&if_block.consequent)?;
+            if let Some(alternate) = if_block.alternate.as_ref() {
+                visitor.visit_fragment(alternate)?;
+            }
+            ControlFlow::Continue(())
+        })
     }
 
-    pub fn walk_await_block<'a, V: Visit<'a>>(visitor: &mut V, await_block: &AwaitBlock<'a>) {
+    pub fn walk_await_block<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        await_block: &AwaitBlock<'a>,
+    ) -> ControlFlow<()> {
         let kind = AstKind::AwaitBlock(visitor.alloc(await_block));
-        visitor.enter_node(kind);
-        if let Some(pending) = await_block.pending.as_ref() {
-            visitor.visit_fragment(pending);
-        }
-        if let Some(then) = await_block.then.as_ref() {
-            visitor.visit_fragment(then);
-        }
-        if let Some(catch) = await_block.catch.as_ref() {
-            visitor.visit_fragment(catch);
-        }
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_expression(&await_block.expression);
+            if let Some(pending) = await_block.pending.as_ref() {
+                visitor.visit_fragment(pending)?;
+            }
+            if let Some(pattern) = &await_block.value {
+                visitor.visit_binding_pattern(pattern);
+            }
+            if let Some(then) = await_block.then.as_ref() {
+                visitor.visit_fragment(then)?;
+            }
+            if let Some(pattern) = &await_block.error {
+                visitor.visit_binding_pattern(pattern);
+            }
+            if let Some(catch) = await_block.catch.as_ref() {
+                visitor.visit_fragment(catch)?;
+            }
+            ControlFlow::Continue(())
+        })
     }
 
-    pub fn walk_key_block<'a, V: Visit<'a>>(visitor: &mut V, key_block: &KeyBlock<'a>) {
+    pub fn walk_key_block<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        key_block: &KeyBlock<'a>,
+    ) -> ControlFlow<()> {
         let kind = AstKind::KeyBlock(visitor.alloc(key_block));
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&key_block.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_expression(&key_block.expression);
+            visitor.visit_fragment(&key_block.fragment)?;
+            ControlFlow::Continue(())
+        })
     }
 
-    pub fn walk_snippet_block<'a, V: Visit<'a>>(visitor: &mut V, snippet_block: &SnippetBlock<'a>) {
+    pub fn walk_snippet_block<'a, V: Visit<'a>>(
+        visitor: &mut V,
+        snippet_block: &SnippetBlock<'a>,
+    ) -> ControlFlow<()> {
         let kind = AstKind::SnippetBlock(visitor.alloc(snippet_block));
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&snippet_block.body);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            for pattern in &snippet_block.parameters {
+                visitor.visit_binding_pattern(pattern);
+            }
+            visitor.visit_fragment(&snippet_block.body)?;
+            ControlFlow::Continue(())
+        })
+    }
+
+    /* ----------  Script and Style ---------- */
+
+    pub fn walk_script<'a, V: Visit<'a>>(visitor: &mut V, script: &Script<'a>) -> ControlFlow<()> {
+        let kind = AstKind::Script(visitor.alloc(script));
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_program(&script.program);
+            ControlFlow::Continue(())
+        })
+    }
+
+    pub fn walk_style<'a, V: Visit<'a>>(visitor: &mut V, style: &Style<'a>) -> ControlFlow<()> {
+        let kind = AstKind::Style(visitor.alloc(style));
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_stylesheet(&style.stylesheet);
+            ControlFlow::Continue(())
+        })
     }
 }
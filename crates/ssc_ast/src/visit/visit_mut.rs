@@ -1,183 +1,380 @@
 //! Visit Mut Pattern
 
+use std::ops::ControlFlow;
+
+use oxc_ast::ast::{
+    ArrayExpression, BindingPattern, CallExpression, Expression, IdentifierReference,
+    MemberExpression, ObjectExpression, Program, VariableDeclaration,
+};
+use ssc_css_ast::ast::StyleSheet;
+
 use self::walk_mut::*;
+use super::VisitControl;
 use crate::{ast::*, AstType};
 
 /// Syntax tree traversal to mutate an exclusive borrow of a syntax tree in
 /// place a syntax tree in place.
+///
+/// The `visit_*` methods of the nodes of the template return
+/// [`ControlFlow::Break`] once the walk was stopped, overrides return the
+/// result of walking the node so that it is passed up to where the walk
+/// started.
 pub trait VisitMut<'a>: Sized {
-    fn enter_node(&mut self, _kind: AstType) {}
+    /// Called before walking the children of a node, which can be skipped, or
+    /// the rest of the walk stopped.
+    fn enter_node(&mut self, _kind: AstType) -> VisitControl {
+        VisitControl::Continue
+    }
     fn leave_node(&mut self, _kind: AstType) {}
 
-    fn visit_root(&mut self, root: &mut Root<'a>) {
-        walk_root_mut(self, root);
+    fn visit_root(&mut self, root: &mut Root<'a>) -> ControlFlow<()> {
+        walk_root_mut(self, root)
     }
 
     /* ----------  Fragment ---------- */
 
-    fn visit_fragment(&mut self, fragment: &mut Fragment<'a>) {
-        walk_fragment_mut(self, fragment);
+    fn visit_fragment(&mut self, fragment: &mut Fragment<'a>) -> ControlFlow<()> {
+        walk_fragment_mut(self, fragment)
     }
 
-    fn visit_fragment_node(&mut self, node: &mut FragmentNode<'a>) {
-        walk_fragment_node_mut(self, node);
+    fn visit_fragment_node(&mut self, node: &mut FragmentNode<'a>) -> ControlFlow<()> {
+        walk_fragment_node_mut(self, node)
     }
 
-    fn visit_text(&mut self, text: &mut Text<'a>) {
-        walk_text_mut(self, text);
+    fn visit_text(&mut self, text: &mut Text<'a>) -> ControlFlow<()> {
+        walk_text_mut(self, text)
+    }
+
+    fn visit_comment(&mut self, comment: &mut Comment<'a>) -> ControlFlow<()> {
+        walk_comment_mut(self, comment)
     }
 
     /* ----------  Tag ---------- */
 
-    fn visit_tag(&mut self, tag: &mut Tag<'a>) {
-        walk_tag_mut(self, tag);
+    fn visit_tag(&mut self, tag: &mut Tag<'a>) -> ControlFlow<()> {
+        walk_tag_mut(self, tag)
     }
 
-    fn visit_expression_tag(&mut self, expression_tag: &mut ExpressionTag<'a>) {
-        walk_expression_tag_mut(self, expression_tag);
+    fn visit_expression_tag(&mut self, expression_tag: &mut ExpressionTag<'a>) -> ControlFlow<()> {
+        walk_expression_tag_mut(self, expression_tag)
     }
 
-    fn visit_html_tag(&mut self, html_tag: &mut HtmlTag<'a>) {
-        walk_html_tag_mut(self, html_tag);
+    fn visit_html_tag(&mut self, html_tag: &mut HtmlTag<'a>) -> ControlFlow<()> {
+        walk_html_tag_mut(self, html_tag)
     }
 
-    fn visit_const_tag(&mut self, const_tag: &mut ConstTag<'a>) {
-        walk_const_tag_mut(self, const_tag);
+    fn visit_const_tag(&mut self, const_tag: &mut ConstTag<'a>) -> ControlFlow<()> {
+        walk_const_tag_mut(self, const_tag)
     }
 
-    fn visit_debug_tag(&mut self, debug_tag: &mut DebugTag<'a>) {
-        walk_debug_tag_mut(self, debug_tag);
+    fn visit_debug_tag(&mut self, debug_tag: &mut DebugTag<'a>) -> ControlFlow<()> {
+        walk_debug_tag_mut(self, debug_tag)
     }
 
-    fn visit_render_tag(&mut self, render_tag: &mut RenderTag<'a>) {
-        walk_render_tag_mut(self, render_tag);
+    fn visit_render_tag(&mut self, render_tag: &mut RenderTag<'a>) -> ControlFlow<()> {
+        walk_render_tag_mut(self, render_tag)
     }
 
     /* ----------  Element ---------- */
 
-    fn visit_element(&mut self, element: &mut Element<'a>) {
-        walk_element_mut(self, element);
+    fn visit_element(&mut self, element: &mut Element<'a>) -> ControlFlow<()> {
+        walk_element_mut(self, element)
     }
 
-    fn visit_component(&mut self, component: &mut Component<'a>) {
-        walk_component_mut(self, component);
+    fn visit_component(&mut self, component: &mut Component<'a>) -> ControlFlow<()> {
+        walk_component_mut(self, component)
     }
 
-    fn visit_title_element(&mut self, title_element: &mut TitleElement<'a>) {
-        walk_title_element_mut(self, title_element);
+    fn visit_title_element(&mut self, title_element: &mut TitleElement<'a>) -> ControlFlow<()> {
+        walk_title_element_mut(self, title_element)
     }
 
-    fn visit_slot_element(&mut self, slot_element: &mut SlotElement<'a>) {
-        walk_slot_element_mut(self, slot_element);
+    fn visit_slot_element(&mut self, slot_element: &mut SlotElement<'a>) -> ControlFlow<()> {
+        walk_slot_element_mut(self, slot_element)
     }
 
-    fn visit_regular_element(&mut self, regular_element: &mut RegularElement<'a>) {
-        walk_regular_element_mut(self, regular_element);
+    fn visit_regular_element(
+        &mut self,
+        regular_element: &mut RegularElement<'a>,
+    ) -> ControlFlow<()> {
+        walk_regular_element_mut(self, regular_element)
     }
 
-    fn visit_svelte_body(&mut self, svelte_body: &mut SvelteBody<'a>) {
-        walk_svelte_body_mut(self, svelte_body);
+    fn visit_svelte_body(&mut self, svelte_body: &mut SvelteBody<'a>) -> ControlFlow<()> {
+        walk_svelte_body_mut(self, svelte_body)
     }
 
-    fn visit_svelte_component(&mut self, svelte_component: &mut SvelteComponent<'a>) {
-        walk_svelte_component_mut(self, svelte_component);
+    fn visit_svelte_boundary(
+        &mut self,
+        svelte_boundary: &mut SvelteBoundary<'a>,
+    ) -> ControlFlow<()> {
+        walk_svelte_boundary_mut(self, svelte_boundary)
     }
 
-    fn visit_svelte_document(&mut self, svelte_document: &mut SvelteDocument<'a>) {
-        walk_svelte_document_mut(self, svelte_document);
+    fn visit_svelte_component(
+        &mut self,
+        svelte_component: &mut SvelteComponent<'a>,
+    ) -> ControlFlow<()> {
+        walk_svelte_component_mut(self, svelte_component)
     }
 
-    fn visit_svelte_element(&mut self, svelte_element: &mut SvelteElement<'a>) {
-        walk_svelte_element_mut(self, svelte_element);
+    fn visit_svelte_document(
+        &mut self,
+        svelte_document: &mut SvelteDocument<'a>,
+    ) -> ControlFlow<()> {
+        walk_svelte_document_mut(self, svelte_document)
+    }
+
+    fn visit_svelte_element(&mut self, svelte_element: &mut SvelteElement<'a>) -> ControlFlow<()> {
+        walk_svelte_element_mut(self, svelte_element)
+    }
+
+    fn visit_svelte_fragment(
+        &mut self,
+        svelte_fragment: &mut SvelteFragment<'a>,
+    ) -> ControlFlow<()> {
+        walk_svelte_fragment_mut(self, svelte_fragment)
+    }
+
+    fn visit_svelte_head(&mut self, svelte_head: &mut SvelteHead<'a>) -> ControlFlow<()> {
+        walk_svelte_head_mut(self, svelte_head)
+    }
+
+    fn visit_svelte_options_raw(
+        &mut self,
+        svelte_options_raw: &mut SvelteOptionsRaw<'a>,
+    ) -> ControlFlow<()> {
+        walk_svelte_options_raw_mut(self, svelte_options_raw)
+    }
+
+    fn visit_svelte_self(&mut self, svelte_self: &mut SvelteSelf<'a>) -> ControlFlow<()> {
+        walk_svelte_self_mut(self, svelte_self)
+    }
+
+    fn visit_svelte_window(&mut self, svelte_window: &mut SvelteWindow<'a>) -> ControlFlow<()> {
+        walk_svelte_window_mut(self, svelte_window)
+    }
+
+    /* ----------  Attribute ---------- */
+
+    fn visit_element_attributes(
+        &mut self,
+        attributes: &mut [ElementAttribute<'a>],
+    ) -> ControlFlow<()> {
+        walk_element_attributes_mut(self, attributes)
     }
 
-    fn visit_svelte_fragment(&mut self, svelte_fragment: &mut SvelteFragment<'a>) {
-        walk_svelte_fragment_mut(self, svelte_fragment);
+    fn visit_element_attribute(&mut self, attribute: &mut ElementAttribute<'a>) -> ControlFlow<()> {
+        walk_element_attribute_mut(self, attribute)
     }
 
-    fn visit_svelte_head(&mut self, svelte_head: &mut SvelteHead<'a>) {
-        walk_svelte_head_mut(self, svelte_head);
+    fn visit_attribute(&mut self, attribute: &mut Attribute<'a>) -> ControlFlow<()> {
+        walk_attribute_mut(self, attribute)
     }
 
-    fn visit_svelte_options_raw(&mut self, svelte_options_raw: &mut SvelteOptionsRaw<'a>) {
-        walk_svelte_options_raw_mut(self, svelte_options_raw);
+    fn visit_attribute_value(&mut self, value: &mut AttributeValue<'a>) -> ControlFlow<()> {
+        walk_attribute_value_mut(self, value)
     }
 
-    fn visit_svelte_self(&mut self, svelte_self: &mut SvelteSelf<'a>) {
-        walk_svelte_self_mut(self, svelte_self);
+    fn visit_spread_attribute(
+        &mut self,
+        spread_attribute: &mut SpreadAttribute<'a>,
+    ) -> ControlFlow<()> {
+        walk_spread_attribute_mut(self, spread_attribute)
     }
 
-    fn visit_svelte_window(&mut self, svelte_window: &mut SvelteWindow<'a>) {
-        walk_svelte_window_mut(self, svelte_window);
+    fn visit_directive_attribute(
+        &mut self,
+        directive: &mut DirectiveAttribute<'a>,
+    ) -> ControlFlow<()> {
+        walk_directive_attribute_mut(self, directive)
+    }
+
+    fn visit_animate_directive(&mut self, directive: &mut AnimateDirective<'a>) -> ControlFlow<()> {
+        walk_animate_directive_mut(self, directive)
+    }
+
+    fn visit_bind_directive(&mut self, directive: &mut BindDirective<'a>) -> ControlFlow<()> {
+        walk_bind_directive_mut(self, directive)
+    }
+
+    fn visit_class_directive(&mut self, directive: &mut ClassDirective<'a>) -> ControlFlow<()> {
+        walk_class_directive_mut(self, directive)
+    }
+
+    fn visit_let_directive(&mut self, directive: &mut LetDirective<'a>) -> ControlFlow<()> {
+        walk_let_directive_mut(self, directive)
+    }
+
+    fn visit_on_directive(&mut self, directive: &mut OnDirective<'a>) -> ControlFlow<()> {
+        walk_on_directive_mut(self, directive)
+    }
+
+    fn visit_style_directive(&mut self, directive: &mut StyleDirective<'a>) -> ControlFlow<()> {
+        walk_style_directive_mut(self, directive)
+    }
+
+    fn visit_transition_directive(
+        &mut self,
+        directive: &mut TransitionDirective<'a>,
+    ) -> ControlFlow<()> {
+        walk_transition_directive_mut(self, directive)
+    }
+
+    fn visit_use_directive(&mut self, directive: &mut UseDirective<'a>) -> ControlFlow<()> {
+        walk_use_directive_mut(self, directive)
     }
 
     /* ----------  Block ---------- */
 
-    fn visit_block(&mut self, block: &mut Block<'a>) {
-        walk_block_mut(self, block);
+    fn visit_block(&mut self, block: &mut Block<'a>) -> ControlFlow<()> {
+        walk_block_mut(self, block)
+    }
+
+    fn visit_each_block(&mut self, each_block: &mut EachBlock<'a>) -> ControlFlow<()> {
+        walk_each_block_mut(self, each_block)
     }
 
-    fn visit_each_block(&mut self, each_block: &mut EachBlock<'a>) {
-        walk_each_block_mut(self, each_block);
+    fn visit_if_block(&mut self, if_block: &mut IfBlock<'a>) -> ControlFlow<()> {
+        walk_if_block_mut(self, if_block)
     }
 
-    fn visit_if_block(&mut self, if_block: &mut IfBlock<'a>) {
-        walk_if_block_mut(self, if_block);
+    fn visit_await_block(&mut self, await_block: &mut AwaitBlock<'a>) -> ControlFlow<()> {
+        walk_await_block_mut(self, await_block)
     }
 
-    fn visit_await_block(&mut self, await_block: &mut AwaitBlock<'a>) {
-        walk_await_block_mut(self, await_block);
+    fn visit_key_block(&mut self, key_block: &mut KeyBlock<'a>) -> ControlFlow<()> {
+        walk_key_block_mut(self, key_block)
     }
 
-    fn visit_key_block(&mut self, key_block: &mut KeyBlock<'a>) {
-        walk_key_block_mut(self, key_block);
+    fn visit_snippet_block(&mut self, snippet_block: &mut SnippetBlock<'a>) -> ControlFlow<()> {
+        walk_snippet_block_mut(self, snippet_block)
     }
 
-    fn visit_snippet_block(&mut self, snippet_block: &mut SnippetBlock<'a>) {
-        walk_snippet_block_mut(self, snippet_block);
+    /* ----------  Script and Style ---------- */
+
+    fn visit_script(&mut self, script: &mut Script<'a>) -> ControlFlow<()> {
+        walk_script_mut(self, script)
+    }
+
+    fn visit_style(&mut self, style: &mut Style<'a>) -> ControlFlow<()> {
+        walk_style_mut(self, style)
     }
+
+    /* ----------  JavaScript and CSS ---------- */
+
+    // The scripts, styles and expressions embedded in the template aren't
+    // walked, override these to reach them, e.g. by delegating to an
+    // `oxc_ast::Visit` or an `ssc_css_ast::Visit`.
+
+    fn visit_program(&mut self, _program: &mut Program<'a>) {}
+
+    fn visit_expression(&mut self, _expression: &mut Expression<'a>) {}
+
+    fn visit_binding_pattern(&mut self, _pattern: &mut BindingPattern<'a>) {}
+
+    fn visit_identifier_reference(&mut self, _ident: &mut IdentifierReference<'a>) {}
+
+    fn visit_variable_declaration(&mut self, _declaration: &mut VariableDeclaration<'a>) {}
+
+    fn visit_call_expression(&mut self, _expr: &mut CallExpression<'a>) {}
+
+    fn visit_member_expression(&mut self, _expr: &mut MemberExpression<'a>) {}
+
+    fn visit_array_expression(&mut self, _expr: &mut ArrayExpression<'a>) {}
+
+    fn visit_object_expression(&mut self, _expr: &mut ObjectExpression<'a>) {}
+
+    fn visit_stylesheet(&mut self, _stylesheet: &mut StyleSheet<'a>) {}
 }
 
 pub mod walk_mut {
     use super::*;
 
-    pub fn walk_root_mut<'a, V: VisitMut<'a>>(visitor: &mut V, root: &mut Root<'a>) {
-        let kind = AstType::Root;
-        visitor.enter_node(kind);
-
-        visitor.visit_fragment(&mut root.fragment);
+    /// Enter the node of `kind`, walk its children with `walk_children` unless
+    /// `enter_node` skips them, and leave it. Breaks if the walk was stopped,
+    /// by the node or one of its descendants, which are all left first.
+    fn walk_node<'a, V: VisitMut<'a>, F: FnOnce(&mut V) -> ControlFlow<()>>(
+        visitor: &mut V,
+        kind: AstType,
+        walk_children: F,
+    ) -> ControlFlow<()> {
+        let flow = match visitor.enter_node(kind) {
+            VisitControl::Continue => walk_children(visitor),
+            VisitControl::Skip => ControlFlow::Continue(()),
+            VisitControl::Stop => ControlFlow::Break(()),
+        };
         visitor.leave_node(kind);
+        flow
+    }
+
+    pub fn walk_root_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        root: &mut Root<'a>,
+    ) -> ControlFlow<()> {
+        let kind = AstType::Root;
+        walk_node(visitor, kind, |visitor| {
+            if let Some(module) = root.module.as_mut() {
+                visitor.visit_script(module)?;
+            }
+            if let Some(instance) = root.instance.as_mut() {
+                visitor.visit_script(instance)?;
+            }
+            visitor.visit_fragment(&mut root.fragment)?;
+            if let Some(css) = root.css.as_mut() {
+                visitor.visit_style(css)?;
+            }
+            ControlFlow::Continue(())
+        })
     }
 
     /* ----------  Fragment ---------- */
 
-    pub fn walk_fragment_mut<'a, V: VisitMut<'a>>(visitor: &mut V, fragment: &mut Fragment<'a>) {
-        for node in fragment.nodes.iter_mut() {
-            visitor.visit_fragment_node(node);
+    pub fn walk_fragment_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        fragment: &mut Fragment<'a>,
+    ) -> ControlFlow<()> {
+        for node in &mut fragment.nodes {
+            visitor.visit_fragment_node(node)?;
         }
+        ControlFlow::Continue(())
     }
 
     pub fn walk_fragment_node_mut<'a, V: VisitMut<'a>>(
         visitor: &mut V,
         node: &mut FragmentNode<'a>,
-    ) {
+    ) -> ControlFlow<()> {
         match node {
             FragmentNode::Text(text) => visitor.visit_text(text),
+            FragmentNode::Comment(comment) => visitor.visit_comment(comment),
             FragmentNode::Tag(tag) => visitor.visit_tag(tag),
             FragmentNode::Element(element) => visitor.visit_element(element),
             FragmentNode::Block(block) => visitor.visit_block(block),
         }
     }
 
-    pub fn walk_text_mut<'a, V: VisitMut<'a>>(visitor: &mut V, _text: &mut Text<'a>) {
+    pub fn walk_text_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        _text: &mut Text<'a>,
+    ) -> ControlFlow<()> {
         let kind = AstType::Text;
-        visitor.enter_node(kind);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |_| ControlFlow::Continue(()))
+    }
+
+    pub fn walk_comment_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        _comment: &mut Comment<'a>,
+    ) -> ControlFlow<()> {
+        let kind = AstType::Comment;
+        walk_node(visitor, kind, |_| ControlFlow::Continue(()))
     }
 
     /* ----------  Tag ---------- */
 
-    pub fn walk_tag_mut<'a, V: VisitMut<'a>>(visitor: &mut V, tag: &mut Tag<'a>) {
+    pub fn walk_tag_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        tag: &mut Tag<'a>,
+    ) -> ControlFlow<()> {
         match tag {
             Tag::ExpressionTag(expression_tag) => visitor.visit_expression_tag(expression_tag),
             Tag::HtmlTag(html_tag) => visitor.visit_html_tag(html_tag),
@@ -189,200 +386,483 @@ pub mod walk_mut {
 
     pub fn walk_expression_tag_mut<'a, V: VisitMut<'a>>(
         visitor: &mut V,
-        _expression_tag: &mut ExpressionTag<'a>,
-    ) {
+        expression_tag: &mut ExpressionTag<'a>,
+    ) -> ControlFlow<()> {
         let kind = AstType::ExpressionTag;
-        visitor.enter_node(kind);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_expression(&mut expression_tag.expression);
+            ControlFlow::Continue(())
+        })
     }
 
-    pub fn walk_html_tag_mut<'a, V: VisitMut<'a>>(visitor: &mut V, _html_tag: &mut HtmlTag<'a>) {
+    pub fn walk_html_tag_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        html_tag: &mut HtmlTag<'a>,
+    ) -> ControlFlow<()> {
         let kind = AstType::HtmlTag;
-        visitor.enter_node(kind);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_expression(&mut html_tag.expression);
+            ControlFlow::Continue(())
+        })
     }
 
-    pub fn walk_const_tag_mut<'a, V: VisitMut<'a>>(visitor: &mut V, _const_tag: &mut ConstTag<'a>) {
+    pub fn walk_const_tag_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        const_tag: &mut ConstTag<'a>,
+    ) -> ControlFlow<()> {
         let kind = AstType::ConstTag;
-        visitor.enter_node(kind);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_variable_declaration(&mut const_tag.declaration);
+            ControlFlow::Continue(())
+        })
     }
 
-    pub fn walk_debug_tag_mut<'a, V: VisitMut<'a>>(visitor: &mut V, _debug_tag: &mut DebugTag<'a>) {
+    pub fn walk_debug_tag_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        debug_tag: &mut DebugTag<'a>,
+    ) -> ControlFlow<()> {
         let kind = AstType::DebugTag;
-        visitor.enter_node(kind);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            for ident in &mut debug_tag.identifiers {
+                visitor.visit_identifier_reference(ident);
+            }
+            ControlFlow::Continue(())
+        })
     }
 
     pub fn walk_render_tag_mut<'a, V: VisitMut<'a>>(
         visitor: &mut V,
-        _render_tag: &mut RenderTag<'a>,
-    ) {
+        render_tag: &mut RenderTag<'a>,
+    ) -> ControlFlow<()> {
         let kind = AstType::RenderTag;
-        visitor.enter_node(kind);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            match &mut render_tag.expression {
+                RenderTagExpression::Call(expr) | RenderTagExpression::Chain(expr) => {
+                    visitor.visit_call_expression(expr);
+                }
+            }
+            ControlFlow::Continue(())
+        })
     }
 
     /* ----------  Element ---------- */
 
-    pub fn walk_element_mut<'a, V: VisitMut<'a>>(visitor: &mut V, element: &mut Element<'a>) {
+    pub fn walk_element_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        element: &mut Element<'a>,
+    ) -> ControlFlow<()> {
         match element {
             Element::Component(component) => visitor.visit_component(component),
             Element::TitleElement(title_element) => visitor.visit_title_element(title_element),
             Element::SlotElement(slot_element) => visitor.visit_slot_element(slot_element),
             Element::RegularElement(regular_element) => {
-                visitor.visit_regular_element(regular_element);
+                visitor.visit_regular_element(regular_element)
             }
             Element::SvelteBody(svelte_body) => visitor.visit_svelte_body(svelte_body),
+            Element::SvelteBoundary(svelte_boundary) => {
+                visitor.visit_svelte_boundary(svelte_boundary)
+            }
             Element::SvelteComponent(svelte_component) => {
-                visitor.visit_svelte_component(svelte_component);
+                visitor.visit_svelte_component(svelte_component)
             }
             Element::SvelteDocument(svelte_document) => {
-                visitor.visit_svelte_document(svelte_document);
+                visitor.visit_svelte_document(svelte_document)
             }
             Element::SvelteElement(svelte_element) => visitor.visit_svelte_element(svelte_element),
             Element::SvelteFragment(svelte_fragment) => {
-                visitor.visit_svelte_fragment(svelte_fragment);
+                visitor.visit_svelte_fragment(svelte_fragment)
             }
             Element::SvelteHead(svelte_head) => visitor.visit_svelte_head(svelte_head),
             Element::SvelteOptionsRaw(svelte_options_raw) => {
-                visitor.visit_svelte_options_raw(svelte_options_raw);
+                visitor.visit_svelte_options_raw(svelte_options_raw)
             }
             Element::SvelteSelf(svelte_self) => visitor.visit_svelte_self(svelte_self),
             Element::SvelteWindow(svelte_window) => visitor.visit_svelte_window(svelte_window),
         }
     }
 
-    pub fn walk_component_mut<'a, V: VisitMut<'a>>(visitor: &mut V, component: &mut Component<'a>) {
+    pub fn walk_component_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        component: &mut Component<'a>,
+    ) -> ControlFlow<()> {
         let kind = AstType::Component;
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&mut component.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_element_attributes(&mut component.attributes)?;
+            visitor.visit_fragment(&mut component.fragment)?;
+            ControlFlow::Continue(())
+        })
     }
 
     pub fn walk_title_element_mut<'a, V: VisitMut<'a>>(
         visitor: &mut V,
         title_element: &mut TitleElement<'a>,
-    ) {
+    ) -> ControlFlow<()> {
         let kind = AstType::TitleElement;
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&mut title_element.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_element_attributes(&mut title_element.attributes)?;
+            visitor.visit_fragment(&mut title_element.fragment)?;
+            ControlFlow::Continue(())
+        })
     }
 
     pub fn walk_slot_element_mut<'a, V: VisitMut<'a>>(
         visitor: &mut V,
         slot_element: &mut SlotElement<'a>,
-    ) {
+    ) -> ControlFlow<()> {
         let kind = AstType::SlotElement;
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&mut slot_element.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_element_attributes(&mut slot_element.attributes)?;
+            visitor.visit_fragment(&mut slot_element.fragment)?;
+            ControlFlow::Continue(())
+        })
     }
 
     pub fn walk_regular_element_mut<'a, V: VisitMut<'a>>(
         visitor: &mut V,
         regular_element: &mut RegularElement<'a>,
-    ) {
+    ) -> ControlFlow<()> {
         let kind = AstType::RegularElement;
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&mut regular_element.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_element_attributes(&mut regular_element.attributes)?;
+            visitor.visit_fragment(&mut regular_element.fragment)?;
+            ControlFlow::Continue(())
+        })
     }
 
     pub fn walk_svelte_body_mut<'a, V: VisitMut<'a>>(
         visitor: &mut V,
         svelte_body: &mut SvelteBody<'a>,
-    ) {
+    ) -> ControlFlow<()> {
         let kind = AstType::SvelteBody;
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&mut svelte_body.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_element_attributes(&mut svelte_body.attributes)?;
+            visitor.visit_fragment(&mut svelte_body.fragment)?;
+            ControlFlow::Continue(())
+        })
+    }
+
+    pub fn walk_svelte_boundary_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        svelte_boundary: &mut SvelteBoundary<'a>,
+    ) -> ControlFlow<()> {
+        let kind = AstType::SvelteBoundary;
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_element_attributes(&mut svelte_boundary.attributes)?;
+            visitor.visit_fragment(&mut svelte_boundary.fragment)?;
+            ControlFlow::Continue(())
+        })
     }
 
     pub fn walk_svelte_component_mut<'a, V: VisitMut<'a>>(
         visitor: &mut V,
         svelte_component: &mut SvelteComponent<'a>,
-    ) {
+    ) -> ControlFlow<()> {
         let kind = AstType::SvelteComponent;
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&mut svelte_component.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_expression(&mut svelte_component.expression);
+            visitor.visit_element_attributes(&mut svelte_component.attributes)?;
+            visitor.visit_fragment(&mut svelte_component.fragment)?;
+            ControlFlow::Continue(())
+        })
     }
 
     pub fn walk_svelte_document_mut<'a, V: VisitMut<'a>>(
         visitor: &mut V,
         svelte_document: &mut SvelteDocument<'a>,
-    ) {
+    ) -> ControlFlow<()> {
         let kind = AstType::SvelteDocument;
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&mut svelte_document.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_element_attributes(&mut svelte_document.attributes)?;
+            visitor.visit_fragment(&mut svelte_document.fragment)?;
+            ControlFlow::Continue(())
+        })
     }
 
     pub fn walk_svelte_element_mut<'a, V: VisitMut<'a>>(
         visitor: &mut V,
         svelte_element: &mut SvelteElement<'a>,
-    ) {
+    ) -> ControlFlow<()> {
         let kind = AstType::SvelteElement;
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&mut svelte_element.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_expression(&mut svelte_element.expression);
+            visitor.visit_element_attributes(&mut svelte_element.attributes)?;
+            visitor.visit_fragment(&mut svelte_element.fragment)?;
+            ControlFlow::Continue(())
+        })
     }
 
     pub fn walk_svelte_fragment_mut<'a, V: VisitMut<'a>>(
         visitor: &mut V,
         svelte_fragment: &mut SvelteFragment<'a>,
-    ) {
+    ) -> ControlFlow<()> {
         let kind = AstType::SvelteFragment;
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&mut svelte_fragment.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_element_attributes(&mut svelte_fragment.attributes)?;
+            visitor.visit_fragment(&mut svelte_fragment.fragment)?;
+            ControlFlow::Continue(())
+        })
     }
 
     pub fn walk_svelte_head_mut<'a, V: VisitMut<'a>>(
         visitor: &mut V,
         svelte_head: &mut SvelteHead<'a>,
-    ) {
+    ) -> ControlFlow<()> {
         let kind = AstType::SvelteHead;
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&mut svelte_head.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_element_attributes(&mut svelte_head.attributes)?;
+            visitor.visit_fragment(&mut svelte_head.fragment)?;
+            ControlFlow::Continue(())
+        })
     }
 
     pub fn walk_svelte_options_raw_mut<'a, V: VisitMut<'a>>(
         visitor: &mut V,
         svelte_options_raw: &mut SvelteOptionsRaw<'a>,
-    ) {
+    ) -> ControlFlow<()> {
         let kind = AstType::SvelteOptionsRaw;
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&mut svelte_options_raw.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_element_attributes(&mut svelte_options_raw.attributes)?;
+            visitor.visit_fragment(&mut svelte_options_raw.fragment)?;
+            ControlFlow::Continue(())
+        })
     }
 
     pub fn walk_svelte_self_mut<'a, V: VisitMut<'a>>(
         visitor: &mut V,
         svelte_self: &mut SvelteSelf<'a>,
-    ) {
+    ) -> ControlFlow<()> {
         let kind = AstType::SvelteSelf;
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&mut svelte_self.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_element_attributes(&mut svelte_self.attributes)?;
+            visitor.visit_fragment(&mut svelte_self.fragment)?;
+            ControlFlow::Continue(())
+        })
     }
 
     pub fn walk_svelte_window_mut<'a, V: VisitMut<'a>>(
         visitor: &mut V,
         svelte_window: &mut SvelteWindow<'a>,
-    ) {
+    ) -> ControlFlow<()> {
         let kind = AstType::SvelteWindow;
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&mut svelte_window.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_element_attributes(&mut svelte_window.attributes)?;
+            visitor.visit_fragment(&mut svelte_window.fragment)?;
+            ControlFlow::Continue(())
+        })
+    }
+
+    /* ----------  Attribute ---------- */
+
+    pub fn walk_element_attributes_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        attributes: &mut [ElementAttribute<'a>],
+    ) -> ControlFlow<()> {
+        for attribute in attributes {
+            visitor.visit_element_attribute(attribute)?;
+        }
+        ControlFlow::Continue(())
+    }
+
+    pub fn walk_element_attribute_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        attribute: &mut ElementAttribute<'a>,
+    ) -> ControlFlow<()> {
+        match attribute {
+            ElementAttribute::Attribute(attribute) => visitor.visit_attribute(attribute),
+            ElementAttribute::SpreadAttribute(spread_attribute) => {
+                visitor.visit_spread_attribute(spread_attribute)
+            }
+            ElementAttribute::DirectiveAttribute(directive) => {
+                visitor.visit_directive_attribute(directive)
+            }
+        }
+    }
+
+    pub fn walk_attribute_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        attribute: &mut Attribute<'a>,
+    ) -> ControlFlow<()> {
+        let kind = AstType::Attribute;
+        walk_node(visitor, kind, |visitor| {
+            if let Some(value) = &mut attribute.value {
+                visitor.visit_attribute_value(value)?;
+            }
+            ControlFlow::Continue(())
+        })
+    }
+
+    pub fn walk_attribute_value_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        value: &mut AttributeValue<'a>,
+    ) -> ControlFlow<()> {
+        for part in &mut value.sequence {
+            match part {
+                AttributeSequenceValue::Text(text) => visitor.visit_text(text)?,
+                AttributeSequenceValue::ExpressionTag(expression_tag) => {
+                    visitor.visit_expression_tag(expression_tag)?;
+                }
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    pub fn walk_spread_attribute_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        spread_attribute: &mut SpreadAttribute<'a>,
+    ) -> ControlFlow<()> {
+        let kind = AstType::SpreadAttribute;
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_expression(&mut spread_attribute.expression);
+            ControlFlow::Continue(())
+        })
+    }
+
+    pub fn walk_directive_attribute_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        directive: &mut DirectiveAttribute<'a>,
+    ) -> ControlFlow<()> {
+        match directive {
+            DirectiveAttribute::AnimateDirective(directive) => {
+                visitor.visit_animate_directive(directive)
+            }
+            DirectiveAttribute::BindDirective(directive) => visitor.visit_bind_directive(directive),
+            DirectiveAttribute::ClassDirective(directive) => {
+                visitor.visit_class_directive(directive)
+            }
+            DirectiveAttribute::LetDirective(directive) => visitor.visit_let_directive(directive),
+            DirectiveAttribute::OnDirective(directive) => visitor.visit_on_directive(directive),
+            DirectiveAttribute::StyleDirective(directive) => {
+                visitor.visit_style_directive(directive)
+            }
+            DirectiveAttribute::TransitionDirective(directive) => {
+                visitor.visit_transition_directive(directive)
+            }
+            DirectiveAttribute::UseDirective(directive) => visitor.visit_use_directive(directive),
+        }
+    }
+
+    pub fn walk_animate_directive_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        directive: &mut AnimateDirective<'a>,
+    ) -> ControlFlow<()> {
+        let kind = AstType::AnimateDirective;
+        walk_node(visitor, kind, |visitor| {
+            if let Some(expression) = &mut directive.expression {
+                visitor.visit_expression(expression);
+            }
+            ControlFlow::Continue(())
+        })
+    }
+
+    pub fn walk_bind_directive_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        directive: &mut BindDirective<'a>,
+    ) -> ControlFlow<()> {
+        let kind = AstType::BindDirective;
+        walk_node(visitor, kind, |visitor| {
+            match &mut directive.expression {
+                BindDirectiveExpression::Identifier(ident) => {
+                    visitor.visit_identifier_reference(ident);
+                }
+                BindDirectiveExpression::MemberExpression(expr) => {
+                    visitor.visit_member_expression(expr);
+                }
+            }
+            ControlFlow::Continue(())
+        })
+    }
+
+    pub fn walk_class_directive_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        directive: &mut ClassDirective<'a>,
+    ) -> ControlFlow<()> {
+        let kind = AstType::ClassDirective;
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_expression(&mut directive.expression);
+            ControlFlow::Continue(())
+        })
+    }
+
+    pub fn walk_let_directive_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        directive: &mut LetDirective<'a>,
+    ) -> ControlFlow<()> {
+        let kind = AstType::LetDirective;
+        walk_node(visitor, kind, |visitor| {
+            match &mut directive.expression {
+                Some(LetDirectiveExpression::Identifier(ident)) => {
+                    visitor.visit_identifier_reference(ident);
+                }
+                Some(LetDirectiveExpression::ArrayExpression(expr)) => {
+                    visitor.visit_array_expression(expr);
+                }
+                Some(LetDirectiveExpression::ObjectExpression(expr)) => {
+                    visitor.visit_object_expression(expr);
+                }
+                None => {}
+            }
+            ControlFlow::Continue(())
+        })
+    }
+
+    pub fn walk_on_directive_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        directive: &mut OnDirective<'a>,
+    ) -> ControlFlow<()> {
+        let kind = AstType::OnDirective;
+        walk_node(visitor, kind, |visitor| {
+            if let Some(expression) = &mut directive.expression {
+                visitor.visit_expression(expression);
+            }
+            ControlFlow::Continue(())
+        })
+    }
+
+    pub fn walk_style_directive_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        directive: &mut StyleDirective<'a>,
+    ) -> ControlFlow<()> {
+        let kind = AstType::StyleDirective;
+        walk_node(visitor, kind, |visitor| {
+            if let Some(value) = &mut directive.value {
+                visitor.visit_attribute_value(value)?;
+            }
+            ControlFlow::Continue(())
+        })
+    }
+
+    pub fn walk_transition_directive_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        directive: &mut TransitionDirective<'a>,
+    ) -> ControlFlow<()> {
+        let kind = AstType::TransitionDirective;
+        walk_node(visitor, kind, |visitor| {
+            if let Some(expression) = &mut directive.expression {
+                visitor.visit_expression(expression);
+            }
+            ControlFlow::Continue(())
+        })
+    }
+
+    pub fn walk_use_directive_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        directive: &mut UseDirective<'a>,
+    ) -> ControlFlow<()> {
+        let kind = AstType::UseDirective;
+        walk_node(visitor, kind, |visitor| {
+            if let Some(expression) = &mut directive.expression {
+                visitor.visit_expression(expression);
+            }
+            ControlFlow::Continue(())
+        })
     }
 
     /* ----------  Block ---------- */
 
-    pub fn walk_block_mut<'a, V: VisitMut<'a>>(visitor: &mut V, block: &mut Block<'a>) {
+    pub fn walk_block_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        block: &mut Block<'a>,
+    ) -> ControlFlow<()> {
         match block {
             Block::EachBlock(each_block) => visitor.visit_each_block(each_block),
             Block::IfBlock(if_block) => visitor.visit_if_block(if_block),
@@ -395,58 +875,110 @@ pub mod walk_mut {
     pub fn walk_each_block_mut<'a, V: VisitMut<'a>>(
         visitor: &mut V,
         each_block: &mut EachBlock<'a>,
-    ) {
+    ) -> ControlFlow<()> {
         let kind = AstType::EachBlock;
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&mut each_block.body);
-        if let Some(fallback) = each_block.fallback.as_mut() {
-            visitor.visit_fragment(fallback);
-        }
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_expression(&mut each_block.expression);
+            visitor.visit_binding_pattern(&mut each_block.context);
+            if let Some(expression) = &mut each_block.key {
+                visitor.visit_expression(expression);
+            }
+            visitor.visit_fragment(&mut each_block.body)?;
+            if let Some(fallback) = each_block.fallback.as_mut() {
+                visitor.visit_fragment(fallback)?;
+            }
+            ControlFlow::Continue(())
+        })
     }
 
-    pub fn walk_if_block_mut<'a, V: VisitMut<'a>>(visitor: &mut V, if_block: &mut IfBlock<'a>) {
+    pub fn walk_if_block_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        if_block: &mut IfBlock<'a>,
+    ) -> ControlFlow<()> {
         let kind = AstType::IfBlock;
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&mut if_block.consequent);
-        if let Some(alternate) = if_block.alternate.as_mut() {
-            visitor.visit_fragment(alternate);
-        }
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_expression(&mut if_block.test);
+            visitor.visit_fragment(&mut if_block.consequent)?;
+            if let Some(alternate) = if_block.alternate.as_mut() {
+                visitor.visit_fragment(alternate)?;
+            }
+            ControlFlow::Continue(())
+        })
     }
 
     pub fn walk_await_block_mut<'a, V: VisitMut<'a>>(
         visitor: &mut V,
         await_block: &mut AwaitBlock<'a>,
-    ) {
+    ) -> ControlFlow<()> {
         let kind = AstType::AwaitBlock;
-        visitor.enter_node(kind);
-        if let Some(pending) = await_block.pending.as_mut() {
-            visitor.visit_fragment(pending);
-        }
-        if let Some(then) = await_block.then.as_mut() {
-            visitor.visit_fragment(then);
-        }
-        if let Some(catch) = await_block.catch.as_mut() {
-            visitor.visit_fragment(catch);
-        }
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_expression(&mut await_block.expression);
+            if let Some(pending) = await_block.pending.as_mut() {
+                visitor.visit_fragment(pending)?;
+            }
+            if let Some(pattern) = &mut await_block.value {
+                visitor.visit_binding_pattern(pattern);
+            }
+            if let Some(then) = await_block.then.as_mut() {
+                visitor.visit_fragment(then)?;
+            }
+            if let Some(pattern) = &mut await_block.error {
+                visitor.visit_binding_pattern(pattern);
+            }
+            if let Some(catch) = await_block.catch.as_mut() {
+                visitor.visit_fragment(catch)?;
+            }
+            ControlFlow::Continue(())
+        })
     }
 
-    pub fn walk_key_block_mut<'a, V: VisitMut<'a>>(visitor: &mut V, key_block: &mut KeyBlock<'a>) {
+    pub fn walk_key_block_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        key_block: &mut KeyBlock<'a>,
+    ) -> ControlFlow<()> {
         let kind = AstType::KeyBlock;
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&mut key_block.fragment);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_expression(&mut key_block.expression);
+            visitor.visit_fragment(&mut key_block.fragment)?;
+            ControlFlow::Continue(())
+        })
     }
 
     pub fn walk_snippet_block_mut<'a, V: VisitMut<'a>>(
         visitor: &mut V,
         snippet_block: &mut SnippetBlock<'a>,
-    ) {
+    ) -> ControlFlow<()> {
         let kind = AstType::SnippetBlock;
-        visitor.enter_node(kind);
-        visitor.visit_fragment(&mut snippet_block.body);
-        visitor.leave_node(kind);
+        walk_node(visitor, kind, |visitor| {
+            for pattern in &mut snippet_block.parameters {
+                visitor.visit_binding_pattern(pattern);
+            }
+            visitor.visit_fragment(&mut snippet_block.body)?;
+            ControlFlow::Continue(())
+        })
+    }
+
+    /* ----------  Script and Style ---------- */
+
+    pub fn walk_script_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        script: &mut Script<'a>,
+    ) -> ControlFlow<()> {
+        let kind = AstType::Script;
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_program(&mut script.program);
+            ControlFlow::Continue(())
+        })
+    }
+
+    pub fn walk_style_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        style: &mut Style<'a>,
+    ) -> ControlFlow<()> {
+        let kind = AstType::Style;
+        walk_node(visitor, kind, |visitor| {
+            visitor.visit_stylesheet(&mut style.stylesheet);
+            ControlFlow::Continue(())
+        })
     }
 }
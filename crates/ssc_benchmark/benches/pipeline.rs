@@ -0,0 +1,96 @@
+use std::time::{Duration, Instant};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use oxc_allocator::Allocator;
+use ssc_benchmark::{fixtures, Fixture};
+use ssc_codegen::{Codegen, CodegenOptions};
+use ssc_parser::{Parser, ParserReturn};
+use ssc_semantic::SemanticBuilder;
+use ssc_transformer::Transformer;
+
+fn parse<'a>(allocator: &'a Allocator, source_text: &'a str) -> ParserReturn<'a> {
+    let ret = Parser::new(allocator, source_text).parse();
+    assert!(ret.errors.is_empty());
+    ret
+}
+
+/// Time `phase` alone, on a freshly parsed component for every iteration, as
+/// it mutates the AST.
+fn time_after_parse<F: for<'a> FnMut(&'a Allocator, &mut ParserReturn<'a>)>(
+    source_text: &str,
+    iters: u64,
+    mut phase: F,
+) -> Duration {
+    let mut elapsed = Duration::ZERO;
+    for _ in 0..iters {
+        let allocator = Allocator::default();
+        let mut ret = parse(&allocator, source_text);
+        let start = Instant::now();
+        phase(&allocator, &mut ret);
+        elapsed += start.elapsed();
+    }
+    elapsed
+}
+
+/// The template and script semantics, and the stylesheet analysis.
+fn analyze(ret: &mut ParserReturn<'_>, source_text: &str) {
+    black_box(SemanticBuilder::new(source_text).build(&ret.root));
+    if let Some(style) = &mut ret.root.css {
+        black_box(ssc_css_analyzer::Analyzer::new().build(&mut style.stylesheet));
+    }
+}
+
+fn transform<'a>(allocator: &'a Allocator, ret: &mut ParserReturn<'a>) {
+    Transformer::new(allocator).build(&mut ret.root);
+    if let Some(style) = &mut ret.root.css {
+        let analysis = ssc_css_analyzer::Analyzer::new().build(&mut style.stylesheet).analysis;
+        let hash = allocator.alloc_str(&analysis.hash);
+        ssc_css_transformer::Transformer::new(allocator, hash)
+            .with_keyframes(analysis.keyframes)
+            .build(&mut style.stylesheet);
+    }
+}
+
+fn bench_phases(c: &mut Criterion, fixture: &Fixture) {
+    let source_text = fixture.source_text.as_str();
+    let mut group = c.benchmark_group(fixture.name);
+    group.throughput(Throughput::Bytes(source_text.len() as u64));
+
+    group.bench_function("parse", |b| {
+        b.iter_with_large_drop(|| {
+            let allocator = Allocator::default();
+            black_box(parse(&allocator, source_text).errors.len());
+            allocator
+        });
+    });
+
+    group.bench_function("analyze", |b| {
+        b.iter_custom(|iters| {
+            time_after_parse(source_text, iters, |_, ret| analyze(ret, source_text))
+        });
+    });
+
+    group.bench_function("transform", |b| {
+        b.iter_custom(|iters| time_after_parse(source_text, iters, transform));
+    });
+
+    let allocator = Allocator::default();
+    let ret = parse(&allocator, source_text);
+    group.bench_function("codegen", |b| {
+        b.iter(|| {
+            Codegen::<false>::new("", source_text, CodegenOptions::default())
+                .build(black_box(&ret.root))
+        });
+    });
+
+    group.finish();
+}
+
+fn bench_pipeline(c: &mut Criterion) {
+    for fixture in fixtures() {
+        bench_phases(c, &fixture);
+    }
+}
+
+criterion_group!(pipeline, bench_pipeline);
+criterion_main!(pipeline);
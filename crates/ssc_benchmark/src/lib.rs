@@ -0,0 +1,147 @@
+//! Benchmark fixtures
+//!
+//! Representative components for the benchmarks of the pipeline phases, each
+//! stressing a different part of it. They're generated, so their size can be
+//! tuned without checking in large files.
+//!
+//! Run `cargo bench -p ssc_benchmark`.
+
+use std::fmt::Write;
+
+/// A component to benchmark.
+pub struct Fixture {
+    pub name: &'static str,
+    pub source_text: String,
+}
+
+/// All the fixtures, from the smallest.
+pub fn fixtures() -> Vec<Fixture> {
+    vec![
+        Fixture { name: "small", source_text: small() },
+        Fixture { name: "huge_template", source_text: huge_template(2000) },
+        Fixture { name: "huge_script", source_text: huge_script(2000) },
+        Fixture { name: "css_heavy", source_text: css_heavy(2000) },
+    ]
+}
+
+/// A counter, the size of most components.
+fn small() -> String {
+    r#"<script>
+	let count = $state(0);
+	let doubled = $derived(count * 2);
+</script>
+
+<button onclick={() => count++}>
+	Clicked {count} {count === 1 ? 'time' : 'times'}
+</button>
+<p>{doubled}</p>
+
+<style>
+	button {
+		padding: 0.5em 1em;
+	}
+</style>
+"#
+    .to_string()
+}
+
+/// A template of `rows` rows of elements, blocks, directives and expressions.
+fn huge_template(rows: usize) -> String {
+    let mut source = String::from(
+        "<script>\n\tlet { items, selected = $bindable() } = $props();\n\tlet filter = $state('');\n</script>\n\n",
+    );
+    for row in 0..rows {
+        let _ = write!(
+            source,
+            r#"<section class="row-{row}" class:active={{selected === {row}}}>
+	<h2 title="Row {row}">{{items[{row}]?.title ?? 'Untitled'}}</h2>
+	{{#if items[{row}]}}
+		<input bind:value={{filter}} placeholder="Filter {row}" />
+		{{#each items[{row}].children as child, index (child.id)}}
+			<a href="/items/{{child.id}}" onclick={{() => (selected = {row})}}>{{index}}: {{child.label}}</a>
+		{{:else}}
+			<p>No children</p>
+		{{/each}}
+	{{:else}}
+		<p>Missing row {row}</p>
+	{{/if}}
+</section>
+"#
+        );
+    }
+    source
+}
+
+/// An instance script of `functions` functions, with a small template.
+fn huge_script(functions: usize) -> String {
+    let mut source = String::from("<script>\n\tlet total = $state(0);\n");
+    for index in 0..functions {
+        let _ = write!(
+            source,
+            r"
+	const values{index} = [{index}, {index} + 1, {index} * 2].map((value) => value ** 2);
+	function update{index}(delta = 1) {{
+		for (const value of values{index}) {{
+			if (value % 2 === 0) {{
+				total += value * delta;
+			}} else {{
+				total -= Math.floor(value / 2);
+			}}
+		}}
+		return {{ index: {index}, total, label: `update {index}: ${{total}}` }};
+	}}
+"
+        );
+    }
+    source.push_str("</script>\n\n<p>{total}</p>\n");
+    source
+}
+
+/// A small template with a stylesheet of `rules` rules, with complex
+/// selectors, at-rules and keyframes.
+fn css_heavy(rules: usize) -> String {
+    let mut source = String::from(
+        "<div class=\"card\"><h1>Title</h1><ul><li><a href=\"/\">Link</a></li></ul></div>\n\n<style>\n",
+    );
+    for index in 0..rules {
+        let _ = write!(
+            source,
+            r"	.card > h1:not(.muted-{index}) + ul li:nth-child(2n + {index}) a:hover {{
+		color: rgb({red} 128 255 / 50%);
+		margin: calc(1rem + {index}px) auto;
+		transition: opacity 0.2s ease-in-out;
+	}}
+	@media (min-width: {width}px) {{
+		:global(.theme-{index}) .card {{
+			animation: fade-{index} 1s infinite;
+		}}
+	}}
+	@keyframes fade-{index} {{
+		from {{ opacity: 0; }}
+		to {{ opacity: 1; }}
+	}}
+",
+            red = index % 256,
+            width = 320 + index,
+        );
+    }
+    source.push_str("</style>\n");
+    source
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use ssc_parser::Parser;
+
+    use super::fixtures;
+
+    #[test]
+    fn fixtures_parse() {
+        for fixture in fixtures() {
+            let allocator = Allocator::default();
+            let ret = Parser::new(&allocator, &fixture.source_text).parse();
+            assert!(ret.errors.is_empty(), "{}: {:?}", fixture.name, ret.errors);
+        }
+    }
+}
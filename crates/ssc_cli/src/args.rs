@@ -0,0 +1,93 @@
+//! Command line arguments
+//!
+//! Options are `--name value` or `--name=value`, flags are `--name`, and
+//! everything else is positional.
+
+pub struct Args {
+    args: Vec<String>,
+}
+
+impl Args {
+    pub fn new<I: IntoIterator<Item = String>>(args: I) -> Self {
+        Self { args: args.into_iter().collect() }
+    }
+
+    /// Take the first argument, the command, unless it's an option.
+    pub fn command(&mut self) -> Option<String> {
+        if self.args.first()?.starts_with("--") {
+            return None;
+        }
+        Some(self.args.remove(0))
+    }
+
+    /// Take every `--name` flag, returning whether there was one.
+    pub fn flag(&mut self, name: &str) -> bool {
+        let len = self.args.len();
+        self.args.retain(|arg| arg.strip_prefix("--") != Some(name));
+        self.args.len() != len
+    }
+
+    /// Take the value of the `--name` option, the last one if it's repeated.
+    ///
+    /// # Errors
+    ///
+    /// If the option is last, without a value.
+    pub fn option(&mut self, name: &str) -> Result<Option<String>, String> {
+        let mut value = None;
+        let mut i = 0;
+        while i < self.args.len() {
+            let Some(arg) = self.args[i].strip_prefix("--") else {
+                i += 1;
+                continue;
+            };
+            if let Some(inline) = arg.strip_prefix(name).and_then(|arg| arg.strip_prefix('=')) {
+                value = Some(inline.to_string());
+                self.args.remove(i);
+            } else if arg == name {
+                if i + 1 == self.args.len() {
+                    return Err(format!("Missing value of `--{name}`"));
+                }
+                value = Some(self.args.remove(i + 1));
+                self.args.remove(i);
+            } else {
+                i += 1;
+            }
+        }
+        Ok(value)
+    }
+
+    /// The positional arguments left, once every option was taken.
+    ///
+    /// # Errors
+    ///
+    /// If an unknown option is left.
+    pub fn finish(self) -> Result<Vec<String>, String> {
+        match self.args.iter().find(|arg| arg.starts_with("--")) {
+            Some(arg) => Err(format!("Unknown option `{arg}`")),
+            None => Ok(self.args),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Args;
+
+    #[test]
+    fn args() {
+        let args = ["compile", "a.svelte", "--emit", "css", "--json", "--out-dir=out", "b.svelte"];
+        let mut args = Args::new(args.map(String::from));
+        assert_eq!(args.command().as_deref(), Some("compile"));
+        assert_eq!(args.option("emit"), Ok(Some("css".into())));
+        assert_eq!(args.option("out-dir"), Ok(Some("out".into())));
+        assert_eq!(args.option("config"), Ok(None));
+        assert!(args.flag("json"));
+        assert!(!args.flag("json"));
+        assert_eq!(args.finish(), Ok(vec!["a.svelte".into(), "b.svelte".into()]));
+
+        let mut args = Args::new(["--emit"].map(String::from));
+        assert!(args.option("emit").is_err());
+        let args = Args::new(["--unknown"].map(String::from));
+        assert!(args.finish().is_err());
+    }
+}
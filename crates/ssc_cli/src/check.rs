@@ -0,0 +1,81 @@
+//! `ssc check`
+
+use std::{fs, process::ExitCode};
+
+use oxc_allocator::Allocator;
+use oxc_diagnostics::{Error, Severity};
+use ssc::{
+    compiler::{CompileOptions, Compiler, Emit},
+    linter::{Linter, Message},
+    semantic::SemanticBuilder,
+};
+
+use crate::{
+    args::Args,
+    lint::read_config,
+    report::{self, FileReport, Format},
+};
+
+/// Analyze and lint every file, without writing any artifact, and print the
+/// diagnostics of the compiler, the semantic analysis and the lint rules.
+///
+/// # Errors
+///
+/// If the arguments or the lint configuration are invalid, or a file can't be
+/// read.
+pub fn run(mut args: Args) -> Result<ExitCode, String> {
+    let config = args.option("config")?;
+    let format = args.option("format")?.map(|format| format.parse::<Format>()).transpose()?;
+    let files = args.finish()?;
+    if files.is_empty() {
+        return Err("No files to check".into());
+    }
+    let linter = Linter::new(read_config(config.as_deref())?);
+
+    let mut reports = vec![];
+    for filename in files {
+        let source_text = fs::read_to_string(&filename)
+            .map_err(|error| format!("Failed to read '{filename}': {error}"))?;
+        let messages = check(&linter, &filename, &source_text);
+        reports.push(FileReport { filename, source_text, messages });
+    }
+    let failed = report::print(format.unwrap_or(Format::Text), reports);
+    Ok(if failed { ExitCode::FAILURE } else { ExitCode::SUCCESS })
+}
+
+/// The diagnostics of the compiler, which stops at syntax errors and includes
+/// the ones of the semantic analysis, then of the lint rules, in source order.
+///
+/// Warnings the lint rules report again, at the same place, are dropped for
+/// the lint messages, which carry the fixes.
+fn check(linter: &Linter, filename: &str, source_text: &str) -> Vec<Message> {
+    let allocator = Allocator::default();
+    let options = CompileOptions {
+        filename: filename.to_string(),
+        emit: Emit::Ast | Emit::Css,
+        ..CompileOptions::default()
+    };
+    let ret = Compiler::new(&allocator, source_text, options).compile();
+    let mut messages = ret.errors.into_iter().map(Message::from).collect::<Vec<_>>();
+    let Some(root) = ret.ast else { return messages };
+    // Its diagnostics were reported by the compiler
+    let semantic = SemanticBuilder::new(source_text).build(&root).semantic;
+    let lint_messages = linter.run(&root, &semantic);
+    let linted = lint_messages
+        .iter()
+        .filter_map(|message| primary_label(&message.error))
+        .collect::<Vec<_>>();
+    messages.retain(|message| {
+        message.error.severity() != Some(Severity::Warning)
+            || !primary_label(&message.error).is_some_and(|label| linted.contains(&label))
+    });
+    messages.extend(lint_messages);
+    messages.sort_by_key(|message| primary_label(&message.error));
+    messages
+}
+
+/// The offset and length of the first label of `error`.
+fn primary_label(error: &Error) -> Option<(usize, usize)> {
+    let label = error.labels()?.next()?;
+    Some((label.offset(), label.len()))
+}
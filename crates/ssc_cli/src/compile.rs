@@ -0,0 +1,143 @@
+//! `ssc compile`
+
+use std::{
+    fs,
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Duration,
+};
+
+use oxc_allocator::Allocator;
+use oxc_diagnostics::Severity;
+use serde_json::json;
+use ssc::{
+    compiler::{CompileOptions, CompileReturn, CompileStats, Compiler, Emit},
+    diagnostics::render_diagnostics,
+};
+
+use crate::args::Args;
+
+/// Compile every file, writing the artifacts of the components that compile.
+///
+/// # Errors
+///
+/// If the arguments are invalid, or a file can't be read or written.
+pub fn run(mut args: Args) -> Result<ExitCode, String> {
+    let emit = args.option("emit")?.map(|emit| emit.parse::<Emit>()).transpose()?;
+    let out_dir = args.option("out-dir")?.map(PathBuf::from);
+    let timings = args.flag("timings");
+    let stats = args.flag("stats");
+    let json = args.flag("json");
+    let files = args.finish()?;
+    if files.is_empty() {
+        return Err("No files to compile".into());
+    }
+    if let Some(out_dir) = &out_dir {
+        fs::create_dir_all(out_dir)
+            .map_err(|error| format!("Failed to create '{}': {error}", out_dir.display()))?;
+    }
+
+    let color = io::stderr().is_terminal();
+    let mut failed = false;
+    let mut total = CompileStats::default();
+    for filename in files {
+        let path = Path::new(&filename);
+        let source_text = fs::read_to_string(path)
+            .map_err(|error| format!("Failed to read '{filename}': {error}"))?;
+        let allocator = Allocator::default();
+        let options = CompileOptions {
+            filename: filename.clone(),
+            emit: emit.unwrap_or_default(),
+            ..CompileOptions::default()
+        };
+        let mut ret = Compiler::new(&allocator, &source_text, options).compile();
+        let dir = out_dir.as_deref().or_else(|| path.parent()).unwrap_or(Path::new(""));
+        write_artifacts(dir, path, &ret)?;
+        if timings {
+            print_stats(Some(&filename), &ret.stats, json);
+        }
+        total += ret.stats;
+
+        let errors = std::mem::take(&mut ret.errors);
+        failed |= errors
+            .iter()
+            .any(|error| !matches!(error.severity(), Some(Severity::Warning | Severity::Advice)));
+        if !errors.is_empty() {
+            eprint!("{}", render_diagnostics(errors, &filename, &source_text, color));
+        }
+    }
+    if stats {
+        print_stats(None, &total, json);
+    }
+    Ok(if failed { ExitCode::FAILURE } else { ExitCode::SUCCESS })
+}
+
+/// Write the artifacts of `ret` into `dir`, named after the component `path`:
+/// `App.svelte` emits `App.css`, `App.css.map`, `App.js`,
+/// `App.hydration.json`, `App.server.js` and `App.ast.json`.
+fn write_artifacts(dir: &Path, path: &Path, ret: &CompileReturn) -> Result<(), String> {
+    let name = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("component");
+    let write = |filename: &str, contents: &str| {
+        let path = dir.join(filename);
+        fs::write(&path, contents)
+            .map_err(|error| format!("Failed to write '{}': {error}", path.display()))
+    };
+    if let (Some(css), Some(css_filename)) = (&ret.css, &ret.css_filename) {
+        write(css_filename, &css.source_text)?;
+        if let Some(map) = css.source_map.as_ref().and_then(|map| map.to_json_string().ok()) {
+            write(&format!("{css_filename}.map"), &map)?;
+        }
+    }
+    if let Some(js) = &ret.js {
+        write(&format!("{name}.js"), &js.source_text)?;
+    }
+    if let Some(hydration) = &ret.hydration {
+        write(&format!("{name}.hydration.json"), &hydration.to_json())?;
+    }
+    if let Some(server) = &ret.server {
+        write(&format!("{name}.server.js"), &server.source_text)?;
+    }
+    if let Some(ast) = &ret.ast {
+        let json = serde_json::to_string(ast).map_err(|error| error.to_string())?;
+        write(&format!("{name}.ast.json"), &json)?;
+    }
+    Ok(())
+}
+
+/// Print the stats of the compilation of `filename`, or of every file, to
+/// stdout, as a line of JSON when `json` is set.
+fn print_stats(filename: Option<&str>, stats: &CompileStats, json: bool) {
+    let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+    if json {
+        let mut value = json!({
+            "files": stats.files,
+            "parseMs": ms(stats.parse),
+            "analyzeMs": ms(stats.analyze),
+            "transformMs": ms(stats.transform),
+            "codegenMs": ms(stats.codegen),
+            "totalMs": ms(stats.total()),
+            "errors": stats.errors,
+            "warnings": stats.warnings,
+            "sourceSize": stats.source_size,
+            "outputSize": stats.output_size,
+        });
+        if let Some(filename) = filename {
+            value["file"] = json!(filename);
+        }
+        println!("{value}");
+        return;
+    }
+    println!("{}", filename.map_or_else(|| format!("{} files", stats.files), str::to_string));
+    for (phase, duration) in [
+        ("parse", stats.parse),
+        ("analyze", stats.analyze),
+        ("transform", stats.transform),
+        ("codegen", stats.codegen),
+        ("total", stats.total()),
+    ] {
+        println!("  {phase:<10} {:>10.3}ms", ms(duration));
+    }
+    println!("  {} errors, {} warnings", stats.errors, stats.warnings);
+    println!("  {} bytes in, {} bytes out", stats.source_size, stats.output_size);
+}
@@ -0,0 +1,54 @@
+//! `ssc format`
+
+use std::{
+    fs,
+    io::{self, IsTerminal},
+    process::ExitCode,
+};
+
+use ssc::{
+    codegen::CodegenOptions,
+    diagnostics::render_diagnostics,
+    format::{format, unified_diff},
+};
+
+use crate::args::Args;
+
+/// Format every file in place or, with `--check`, print the diff formatting
+/// would apply to each and fail if there's any.
+///
+/// # Errors
+///
+/// If the arguments are invalid, or a file can't be read or written.
+pub fn run(mut args: Args) -> Result<ExitCode, String> {
+    let check = args.flag("check");
+    let files = args.finish()?;
+    if files.is_empty() {
+        return Err("No files to format".into());
+    }
+
+    let color = io::stderr().is_terminal();
+    let mut failed = false;
+    for filename in files {
+        let source_text = fs::read_to_string(&filename)
+            .map_err(|error| format!("Failed to read '{filename}': {error}"))?;
+        let formatted = match format(&source_text, CodegenOptions::default()) {
+            Ok(formatted) => formatted,
+            Err(errors) => {
+                failed = true;
+                eprint!("{}", render_diagnostics(errors, &filename, &source_text, color));
+                continue;
+            }
+        };
+        if check {
+            if let Some(diff) = unified_diff(&filename, &source_text, &formatted) {
+                print!("{diff}");
+                failed = true;
+            }
+        } else if formatted != source_text {
+            fs::write(&filename, formatted)
+                .map_err(|error| format!("Failed to write '{filename}': {error}"))?;
+        }
+    }
+    Ok(if failed { ExitCode::FAILURE } else { ExitCode::SUCCESS })
+}
@@ -0,0 +1,90 @@
+//! `ssc lint`
+
+use std::{fs, path::Path, process::ExitCode};
+
+use oxc_allocator::Allocator;
+use oxc_diagnostics::{Error, Severity};
+use ssc::{
+    linter::{Fixer, LintConfig, Linter, Message},
+    parser::Parser,
+    semantic::SemanticBuilder,
+};
+
+use crate::{
+    args::Args,
+    report::{self, FileReport, Format},
+};
+
+/// The configuration used when `--config` isn't given, if it exists.
+const DEFAULT_CONFIG: &str = ".ssclintrc.json";
+
+/// Lint every file and print the problems found. With `--fix`, the fixes are
+/// applied first and only the problems left are printed.
+///
+/// # Errors
+///
+/// If the arguments or the configuration are invalid, or a file can't be
+/// read or written.
+pub fn run(mut args: Args) -> Result<ExitCode, String> {
+    let fix = args.flag("fix");
+    let config = args.option("config")?;
+    let format = args.option("format")?.map(|format| format.parse::<Format>()).transpose()?;
+    let files = args.finish()?;
+    if files.is_empty() {
+        return Err("No files to lint".into());
+    }
+    let linter = Linter::new(read_config(config.as_deref())?);
+
+    let mut reports = vec![];
+    for filename in files {
+        let mut source_text = fs::read_to_string(&filename)
+            .map_err(|error| format!("Failed to read '{filename}': {error}"))?;
+        let mut messages = lint(&linter, &source_text);
+        if fix {
+            let ret = Fixer::new(&source_text, messages).fix();
+            messages = ret.messages;
+            if ret.fixed {
+                let fixed_code = ret.fixed_code.into_owned();
+                fs::write(&filename, &fixed_code)
+                    .map_err(|error| format!("Failed to write '{filename}': {error}"))?;
+                // Spans of the messages left are offsets in the old source
+                messages = lint(&linter, &fixed_code);
+                source_text = fixed_code;
+            }
+        }
+        reports.push(FileReport { filename, source_text, messages });
+    }
+    let failed = report::print(format.unwrap_or(Format::Text), reports);
+    Ok(if failed { ExitCode::FAILURE } else { ExitCode::SUCCESS })
+}
+
+/// The configuration in `path`, or in [`DEFAULT_CONFIG`] if there's one, the
+/// default configuration otherwise.
+pub fn read_config(path: Option<&str>) -> Result<LintConfig, String> {
+    let path = match path {
+        Some(path) => path,
+        None if Path::new(DEFAULT_CONFIG).is_file() => DEFAULT_CONFIG,
+        None => return Ok(LintConfig::default()),
+    };
+    let json =
+        fs::read_to_string(path).map_err(|error| format!("Failed to read '{path}': {error}"))?;
+    LintConfig::from_json(&json).map_err(|error| format!("Invalid configuration '{path}': {error}"))
+}
+
+/// The messages of the rules, after the syntax errors, which the rules don't
+/// run for if the parser gave up.
+fn lint(linter: &Linter, source_text: &str) -> Vec<Message> {
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text).parse();
+    let mut messages = ret
+        .errors
+        .into_iter()
+        .filter(|error| error.severity == Severity::Error)
+        .map(|error| Message::from(Error::from(error)))
+        .collect::<Vec<_>>();
+    if !ret.panicked {
+        let semantic = SemanticBuilder::new(source_text).build(&ret.root).semantic;
+        messages.extend(linter.run(&ret.root, &semantic));
+    }
+    messages
+}
@@ -0,0 +1,67 @@
+//! The `ssc` command line interface
+//!
+//! ```text
+//! ssc check [--config <file>] [--format <format>] <files>...
+//! ssc compile [--emit <kinds>] [--out-dir <dir>] [--timings] [--stats] [--json] <files>...
+//! ssc format [--check] <files>...
+//! ssc lint [--fix] [--config <file>] [--format <format>] <files>...
+//! ```
+#![allow(clippy::print_stdout, clippy::print_stderr)]
+
+mod args;
+mod check;
+mod compile;
+mod format;
+mod lint;
+mod report;
+
+use std::process::ExitCode;
+
+use crate::args::Args;
+
+const USAGE: &str = "\
+Usage: ssc <command> [options] <files>...
+
+Commands:
+  check    Report the diagnostics of the compiler and the lint rules, without emitting anything
+  compile  Compile components, writing the emitted artifacts next to them
+  format   Format components in place
+  lint     Report the problems found by the lint rules
+
+Options of `check`:
+  --config <file>   JSON file configuring the lint rules [default: .ssclintrc.json if it exists]
+  --format <format> Output format: text, json, sarif [default: text]
+
+Options of `compile`:
+  --emit <kinds>    Comma separated artifacts to emit: ast, css, hydration, js, map [default: css,map]
+  --out-dir <dir>   Directory to write the artifacts to, instead of next to the components
+  --timings         Print the time spent in each phase for every file
+  --stats           Print the times, diagnostic counts and sizes of all files
+  --json            Print timings and stats as lines of JSON
+
+Options of `format`:
+  --check           Print the diff formatting would apply instead, failing if there's any
+
+Options of `lint`:
+  --fix             Apply the fixes of the problems found, reporting the others
+  --config <file>   JSON file configuring the rules [default: .ssclintrc.json if it exists]
+  --format <format> Output format: text, json, sarif [default: text]";
+
+fn main() -> ExitCode {
+    let mut args = Args::new(std::env::args().skip(1));
+    let result = match args.command().as_deref() {
+        Some("check") => check::run(args),
+        Some("compile") => compile::run(args),
+        Some("format") => format::run(args),
+        Some("lint") => lint::run(args),
+        Some("help") | None => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Some(command) => Err(format!("Unknown command `{command}`\n\n{USAGE}")),
+    };
+    result.unwrap_or_else(|message| {
+        eprintln!("{message}");
+        ExitCode::from(2)
+    })
+}
@@ -0,0 +1,78 @@
+//! Output formats of diagnostics
+
+use std::{
+    io::{self, IsTerminal},
+    str::FromStr,
+};
+
+use oxc_diagnostics::Severity;
+use ssc::{
+    compiler::{diagnostics_json, diagnostics_sarif, FileDiagnostics},
+    diagnostics::render_diagnostics,
+    linter::Message,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Rendered with the source, on stderr.
+    Text,
+    Json,
+    Sarif,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "sarif" => Ok(Self::Sarif),
+            _ => Err(format!("Unknown format `{s}`")),
+        }
+    }
+}
+
+/// The messages reported for a file.
+pub struct FileReport {
+    pub filename: String,
+    pub source_text: String,
+    pub messages: Vec<Message>,
+}
+
+/// Print the messages of every file in `format`, JSON and SARIF as a single
+/// document on stdout, returning whether any is an error.
+pub fn print(format: Format, reports: Vec<FileReport>) -> bool {
+    let failed = reports.iter().flat_map(|report| &report.messages).any(|message| {
+        !matches!(message.error.severity(), Some(Severity::Warning | Severity::Advice))
+    });
+    match format {
+        Format::Text => {
+            let color = io::stderr().is_terminal();
+            for report in reports.into_iter().filter(|report| !report.messages.is_empty()) {
+                let errors = report.messages.into_iter().map(|message| message.error).collect();
+                eprint!(
+                    "{}",
+                    render_diagnostics(errors, &report.filename, &report.source_text, color)
+                );
+            }
+        }
+        Format::Json | Format::Sarif => {
+            let files = reports
+                .iter()
+                .map(|report| FileDiagnostics {
+                    filename: &report.filename,
+                    source_text: &report.source_text,
+                    messages: &report.messages,
+                })
+                .collect::<Vec<_>>();
+            let output = if format == Format::Json {
+                diagnostics_json(&files)
+            } else {
+                diagnostics_sarif(&files)
+            };
+            println!("{output}");
+        }
+    }
+    failed
+}
@@ -0,0 +1,173 @@
+#[allow(clippy::wildcard_imports)]
+use ssc_ast::ast::*;
+
+/// Kind of an element attribute, used to group attributes when printing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AttributeKind {
+    Attribute,
+    Animate,
+    Bind,
+    Class,
+    Let,
+    On,
+    Style,
+    Transition,
+    Use,
+}
+
+impl AttributeKind {
+    /// Returns `None` for spread attributes, as those are never moved.
+    fn of(attribute: &ElementAttribute<'_>) -> Option<Self> {
+        Some(match attribute {
+            ElementAttribute::Attribute(_) => Self::Attribute,
+            ElementAttribute::SpreadAttribute(_) => return None,
+            ElementAttribute::DirectiveAttribute(directive) => match directive {
+                DirectiveAttribute::AnimateDirective(_) => Self::Animate,
+                DirectiveAttribute::BindDirective(_) => Self::Bind,
+                DirectiveAttribute::ClassDirective(_) => Self::Class,
+                DirectiveAttribute::LetDirective(_) => Self::Let,
+                DirectiveAttribute::OnDirective(_) => Self::On,
+                DirectiveAttribute::StyleDirective(_) => Self::Style,
+                DirectiveAttribute::TransitionDirective(_) => Self::Transition,
+                DirectiveAttribute::UseDirective(_) => Self::Use,
+            },
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct AttributeLayoutOptions {
+    /// Sort attributes by name instead of keeping the source order.
+    pub sort: bool,
+
+    /// Group attributes by kind, in this order, e.g. `[Attribute, Bind]`
+    /// prints plain attributes before bindings.
+    /// Kinds which are not listed are printed after the listed ones.
+    /// An empty list disables grouping.
+    ///
+    /// Directives keep their source order between them whatever the options,
+    /// as it's the order they're applied in, e.g. an `on:input` handler runs
+    /// before the `bind:value` update it precedes.
+    pub group_order: Vec<AttributeKind>,
+
+    /// Print each attribute on its own line when an element has more
+    /// attributes than this. `None` always prints them on one line.
+    pub one_per_line_threshold: Option<usize>,
+}
+
+impl AttributeLayoutOptions {
+    /// Returns the attributes in the order they should be printed.
+    ///
+    /// Spread attributes are never moved, and no attribute is moved across
+    /// one, as that would change which value wins. Directives only move
+    /// around plain attributes.
+    pub(crate) fn arrange<'a, 'b>(
+        &self,
+        attributes: &'b [ElementAttribute<'a>],
+    ) -> Vec<&'b ElementAttribute<'a>> {
+        let mut attributes = attributes.iter().collect::<Vec<_>>();
+        if !self.sort && self.group_order.is_empty() {
+            return attributes;
+        }
+        for segment in attributes.split_mut(|attribute| AttributeKind::of(attribute).is_none()) {
+            let directives = segment
+                .iter()
+                .copied()
+                .filter(|attribute| is_directive(attribute))
+                .collect::<Vec<_>>();
+            segment.sort_by_cached_key(|attribute| {
+                let rank = if self.group_order.is_empty() {
+                    0
+                } else {
+                    AttributeKind::of(attribute)
+                        .and_then(|kind| self.group_order.iter().position(|k| *k == kind))
+                        .unwrap_or(self.group_order.len())
+                };
+                let name = if self.sort { attribute_name(attribute) } else { "" };
+                (rank, name)
+            });
+            // The slots of the directives are filled back in source order
+            let slots = segment.iter_mut().filter(|attribute| is_directive(attribute));
+            for (slot, directive) in slots.zip(directives) {
+                *slot = directive;
+            }
+        }
+        attributes
+    }
+
+    pub(crate) fn is_multiline(&self, num_attributes: usize) -> bool {
+        self.one_per_line_threshold.is_some_and(|threshold| num_attributes > threshold)
+    }
+}
+
+fn is_directive(attribute: &ElementAttribute<'_>) -> bool {
+    matches!(attribute, ElementAttribute::DirectiveAttribute(_))
+}
+
+fn attribute_name<'b>(attribute: &'b ElementAttribute<'_>) -> &'b str {
+    match attribute {
+        ElementAttribute::Attribute(attribute) => attribute.name.as_str(),
+        ElementAttribute::SpreadAttribute(_) => "",
+        ElementAttribute::DirectiveAttribute(directive) => match directive {
+            DirectiveAttribute::AnimateDirective(directive) => directive.name.as_str(),
+            DirectiveAttribute::BindDirective(directive) => directive.name.as_str(),
+            DirectiveAttribute::ClassDirective(directive) => directive.name.as_str(),
+            DirectiveAttribute::LetDirective(directive) => directive.name.as_str(),
+            DirectiveAttribute::OnDirective(directive) => directive.name.as_str(),
+            DirectiveAttribute::StyleDirective(directive) => directive.name.as_str(),
+            DirectiveAttribute::TransitionDirective(directive) => directive.name.as_str(),
+            DirectiveAttribute::UseDirective(directive) => directive.name.as_str(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use ssc_parser::Parser;
+
+    use super::*;
+    use crate::{Codegen, CodegenOptions};
+
+    fn print(source: &str, attribute_layout: AttributeLayoutOptions) -> String {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty());
+        let options = CodegenOptions { attribute_layout, ..CodegenOptions::default() };
+        Codegen::<false>::new("", source, options).build(&ret.root).source_text
+    }
+
+    #[test]
+    fn attribute_layout() {
+        let source = r#"<div on:input={f} b="1" bind:value={v} {...rest} c a on:blur={g}>x</div>"#;
+
+        assert_eq!(print(source, AttributeLayoutOptions::default()), source);
+
+        let options = AttributeLayoutOptions { sort: true, ..AttributeLayoutOptions::default() };
+        assert_eq!(
+            print(source, options),
+            r#"<div b="1" on:input={f} bind:value={v} {...rest} a on:blur={g} c>x</div>"#
+        );
+
+        let options = AttributeLayoutOptions {
+            group_order: vec![AttributeKind::Attribute, AttributeKind::Bind],
+            ..AttributeLayoutOptions::default()
+        };
+        assert_eq!(
+            print(source, options.clone()),
+            r#"<div b="1" on:input={f} bind:value={v} {...rest} c a on:blur={g}>x</div>"#
+        );
+        // Only the plain attributes move around the directives
+        assert_eq!(
+            print(r#"<input on:input={f} bind:value={v} a use:b class:c={e} d>"#, options),
+            r#"<input a d on:input={f} bind:value={v} use:b class:c={e} />"#
+        );
+
+        let options = AttributeLayoutOptions {
+            one_per_line_threshold: Some(1),
+            ..AttributeLayoutOptions::default()
+        };
+        assert_eq!(print(r#"<p a="1">x</p>"#, options.clone()), r#"<p a="1">x</p>"#);
+        assert_eq!(print(r#"<p a="1" b>x</p>"#, options), "<p\n\ta=\"1\"\n\tb\n>x</p>");
+    }
+}
@@ -0,0 +1,220 @@
+//! Reflowing of the template, see [`CodegenOptions::format`]
+//!
+//! Svelte collapses the whitespace between nodes to a single space, and
+//! drops the whitespace at the start and end of fragments, so line breaks
+//! can replace the former and be added in place of the latter without
+//! changing the rendered output.
+
+#[allow(clippy::wildcard_imports)]
+use ssc_ast::ast::*;
+
+use crate::{Codegen, CodegenOptions, Gen};
+
+/// Elements laid out in the line by default, printed like words.
+const INLINE_ELEMENTS: &[&str] = &[
+    "a", "abbr", "b", "bdi", "bdo", "br", "button", "cite", "code", "data", "dfn", "em", "i",
+    "img", "input", "kbd", "label", "mark", "q", "s", "samp", "select", "small", "span", "strong",
+    "sub", "sup", "time", "u", "var", "wbr",
+];
+
+impl<const MINIFY: bool> Codegen<MINIFY> {
+    /// The width of `node` printed on one line, `None` if it takes several.
+    pub(crate) fn width_of<T: Gen<MINIFY>>(&self, node: &T) -> Option<usize> {
+        let options =
+            CodegenOptions { enable_source_map: false, format: false, ..self.options.clone() };
+        let mut codegen = Self::new("", "", options);
+        node.gen(&mut codegen);
+        (!codegen.code.contains(&b'\n')).then(|| codegen.column())
+    }
+}
+
+/// Print the children of an element or a block, on their own lines unless
+/// they are text, tags and inline elements fitting on the current line.
+pub(crate) fn print_children<const MINIFY: bool>(
+    fragment: &Fragment<'_>,
+    p: &mut Codegen<{ MINIFY }>,
+) {
+    if !p.is_formatting() {
+        fragment.gen(p);
+        return;
+    }
+    if fits_inline(fragment, p) {
+        print_nodes(&fragment.nodes, p);
+        return;
+    }
+    p.indent();
+    p.print_soft_newline();
+    p.print_indent();
+    print_nodes(&fragment.nodes, p);
+    p.dedent();
+    p.print_soft_newline();
+    p.print_indent();
+}
+
+fn fits_inline<const MINIFY: bool>(fragment: &Fragment<'_>, p: &Codegen<{ MINIFY }>) -> bool {
+    let mut width = p.column();
+    for node in &fragment.nodes {
+        width += match node {
+            FragmentNode::Text(text) => {
+                let words = text.data.split(is_whitespace).filter(|word| !word.is_empty());
+                words.map(|word| word.chars().count() + 1).sum()
+            }
+            FragmentNode::Comment(_) if !p.options.preserve_comments => 0,
+            FragmentNode::Comment(comment) => match p.width_of(comment) {
+                Some(width) => width,
+                None => return false,
+            },
+            FragmentNode::Tag(tag) => match p.width_of(tag) {
+                Some(width) => width,
+                None => return false,
+            },
+            FragmentNode::Element(element) if is_inline(element) => match p.width_of(element) {
+                Some(width) => width,
+                None => return false,
+            },
+            FragmentNode::Element(_) | FragmentNode::Block(_) => return false,
+        };
+    }
+    width <= p.options.line_width
+}
+
+/// Print `nodes` from the current position. The whitespace between them
+/// becomes a line break next to elements laid out as blocks, Svelte blocks,
+/// and where the line would get too long, or a space otherwise.
+pub(crate) fn print_nodes<const MINIFY: bool>(
+    nodes: &[FragmentNode<'_>],
+    p: &mut Codegen<{ MINIFY }>,
+) {
+    let mut flow = Flow::default();
+    for node in nodes {
+        match node {
+            FragmentNode::Text(text) => {
+                let mut rest = text.data.as_str();
+                loop {
+                    let word = rest.trim_start_matches(is_whitespace);
+                    if word.len() < rest.len() {
+                        flow.gap(&rest[..rest.len() - word.len()]);
+                    }
+                    if word.is_empty() {
+                        break;
+                    }
+                    let len = word.find(is_whitespace).unwrap_or(word.len());
+                    flow.start(p, word[..len].chars().count(), false);
+                    p.print_str(&word.as_bytes()[..len]);
+                    rest = &word[len..];
+                }
+            }
+            FragmentNode::Comment(_) if !p.options.preserve_comments => {}
+            FragmentNode::Comment(comment) => {
+                let width = p.width_of(comment).unwrap_or(0);
+                flow.start(p, width, false);
+                comment.gen(p);
+            }
+            FragmentNode::Tag(tag) => {
+                let width = p.width_of(tag).unwrap_or(0);
+                flow.start(p, width, false);
+                tag.gen(p);
+            }
+            FragmentNode::Element(element) if is_inline(element) => {
+                let width = p.width_of(element).unwrap_or(0);
+                flow.start(p, width, false);
+                element.gen(p);
+            }
+            FragmentNode::Element(element) => {
+                flow.start(p, 0, true);
+                element.gen(p);
+            }
+            FragmentNode::Block(block) => {
+                flow.start(p, 0, true);
+                block.gen(p);
+            }
+        }
+    }
+}
+
+/// The whitespace pending between the nodes printed by [`print_nodes`].
+#[derive(Default)]
+struct Flow {
+    started: bool,
+    /// Whether there's whitespace since the last node, and whether it has
+    /// an empty line.
+    gap: Option<bool>,
+    after_block: bool,
+}
+
+impl Flow {
+    fn gap(&mut self, whitespace: &str) {
+        let empty_line = whitespace.matches('\n').count() > 1;
+        self.gap = Some(self.gap.unwrap_or(false) || empty_line);
+    }
+
+    /// Print the whitespace before a node `width` wide, dropped at the start
+    /// of the nodes.
+    fn start<const MINIFY: bool>(
+        &mut self,
+        p: &mut Codegen<{ MINIFY }>,
+        width: usize,
+        block: bool,
+    ) {
+        if let Some(empty_line) = self.gap.take().filter(|_| self.started) {
+            let block = block || self.after_block;
+            if block || p.column() + 1 + width > p.options.line_width {
+                // Empty lines are kept between blocks, not in text
+                if empty_line && block {
+                    p.print_soft_newline();
+                }
+                p.print_soft_newline();
+                p.print_indent();
+            } else {
+                p.print_hard_space();
+            }
+        }
+        self.started = true;
+        self.after_block = block;
+    }
+}
+
+fn is_inline(element: &Element<'_>) -> bool {
+    matches!(element, Element::RegularElement(element) if INLINE_ELEMENTS.contains(&element.name.as_str()))
+}
+
+/// Whitespace as HTML collapses it.
+fn is_whitespace(ch: char) -> bool {
+    matches!(ch, ' ' | '\t' | '\n' | '\r' | '\x0c')
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use ssc_parser::Parser;
+
+    use crate::{Codegen, CodegenOptions};
+
+    fn format(source: &str, options: CodegenOptions) -> String {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let options = CodegenOptions { format: true, ..options };
+        Codegen::<false>::new("", source, options).build(&ret.root).source_text
+    }
+
+    #[test]
+    fn format_template() {
+        let source = "<div class=\"a\"><p>Hello <b>{name}</b>!</p>\n\n\n<ul>{#each items as item}<li>{item}</li>{/each}</ul></div>\n<pre>  a\n   <b>b</b></pre>";
+        assert_eq!(
+            format(source, CodegenOptions::default()),
+            "<div class=\"a\">\n\t<p>Hello <b>{name}</b>!</p>\n\n\t<ul>\n\t\t{#each items as item}\n\t\t\t<li>{item}</li>\n\t\t{/each}\n\t</ul>\n</div>\n<pre>  a\n   <b>b</b></pre>\n"
+        );
+
+        let options = CodegenOptions {
+            use_tabs: false,
+            indent_width: 2,
+            line_width: 20,
+            ..CodegenOptions::default()
+        };
+        assert_eq!(
+            format("<p>one two three four five six</p>\n<Card a=\"1\" bind:value={v} on:click={f} />", options),
+            "<p>\n  one two three four\n  five six\n</p>\n<Card\n  a=\"1\"\n  bind:value={v}\n  on:click={f}\n />\n"
+        );
+    }
+}
@@ -1,10 +1,15 @@
 use oxc_allocator::Box;
 use oxc_codegen::{Context, Gen as OxcGen, GenExpr};
+use oxc_span::{GetSpan, Span};
 use oxc_syntax::precedence::Precedence;
 #[allow(clippy::wildcard_imports)]
-use ssc_ast::ast::*;
+use ssc_ast::{ast::*, html};
 
-use super::Codegen;
+use super::{
+    format::{print_children, print_nodes},
+    js_mappings::JsNode,
+    Codegen,
+};
 
 pub trait Gen<const MINIFY: bool> {
     fn gen(&self, _p: &mut Codegen<{ MINIFY }>) {}
@@ -21,23 +26,29 @@ where
 
 impl<'a, const MINIFY: bool> Gen<MINIFY> for Root<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
-        if let Some(instance) = self.instance.as_ref() {
-            instance.gen(p);
-            p.print_soft_newline();
-        }
         if let Some(module) = self.module.as_ref() {
             module.gen(p);
             p.print_soft_newline();
         }
+        if let Some(instance) = self.instance.as_ref() {
+            instance.gen(p);
+            p.print_soft_newline();
+        }
         if let Some(css) = self.css.as_ref() {
             css.gen(p);
             p.print_soft_newline();
         }
-        self.fragment.gen(p);
+        if p.is_formatting() {
+            print_nodes(&self.fragment.nodes, p);
+            p.print_soft_newline();
+        } else {
+            self.fragment.gen(p);
+        }
     }
 }
 
 impl<'a, const MINIFY: bool> Gen<MINIFY> for Script<'a> {
+    #[allow(clippy::cast_possible_truncation)]
     fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
         p.add_source_mapping(self.span.start);
         p.print_str(b"<script");
@@ -46,6 +57,34 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for Script<'a> {
             attr.gen(p);
         }
         p.print(b'>');
+        if let Some(raw) = &self.raw {
+            p.print_str(raw.as_bytes());
+            p.print_str(b"</script>");
+            return;
+        }
+        if let Some((formatter, source_text)) = p.script_formatter.take() {
+            // The contents start after the `>` following the attributes
+            let attributes_end = self
+                .attributes
+                .last()
+                .map_or(self.span.start + "<script".len() as u32, |attribute| attribute.span.end);
+            let start = source_text[attributes_end as usize..]
+                .find('>')
+                .map_or(attributes_end, |index| attributes_end + index as u32 + 1);
+            let contents = &source_text[start as usize..self.program.span.end as usize];
+            let formatted = formatter.format_script(contents, self.program.source_type);
+            if let Some(formatted) = &formatted {
+                p.print_soft_newline();
+                p.indent();
+                p.print_formatted_script(formatted, contents, start);
+                p.dedent();
+            }
+            p.script_formatter = Some((formatter, source_text));
+            if formatted.is_some() {
+                p.print_str(b"</script>");
+                return;
+            }
+        }
         let source = oxc_codegen::Codegen::<MINIFY>::new().build(&self.program).source_text;
         if !source.is_empty() {
             p.print_soft_newline();
@@ -66,7 +105,13 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for Style<'a> {
             attr.gen(p);
         }
         p.print(b'>');
-        let options = ssc_css_codegen::CodegenOptions { enable_source_map: false };
+        if let Some(raw) = &self.raw {
+            p.print_str(raw.as_bytes());
+            p.print_str(b"</style>");
+            return;
+        }
+        let options =
+            ssc_css_codegen::CodegenOptions { enable_source_map: false, ..p.options.css.clone() };
         let source = ssc_css_codegen::Codegen::<MINIFY>::new("", "", options)
             .build(&self.stylesheet)
             .source_text;
@@ -116,13 +161,22 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for AttributeValue<'a> {
         if let Some(tag) = tag {
             tag.gen(p);
         } else {
-            p.print(b'"');
+            // The other quote if the preferred one is in the text
+            let quote = p.options.quote_style.quote();
+            let contains_quote = self.sequence.iter().any(|value| match value {
+                AttributeSequenceValue::Text(text) => text.data.as_bytes().contains(&quote),
+                AttributeSequenceValue::ExpressionTag(_) => false,
+            });
+            let quote = if contains_quote { p.options.quote_style.other().quote() } else { quote };
+            p.print(quote);
             for el in &self.sequence {
                 match el {
                     AttributeSequenceValue::Text(text) => {
+                        p.add_source_mapping(text.span.start);
                         p.print_str(text.data.as_bytes());
                     }
                     AttributeSequenceValue::ExpressionTag(tag) => {
+                        p.add_source_mapping(tag.span.start);
                         p.print(b'{');
                         let mut codegen = oxc_codegen::Codegen::<true>::new();
                         tag.expression.gen_expr(
@@ -131,12 +185,12 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for AttributeValue<'a> {
                             Context::default(),
                         );
                         let source = codegen.into_source_text();
-                        p.print_str(source.as_bytes());
+                        p.print_js(&source, &tag.expression, tag.expression.span());
                         p.print(b'}');
                     }
                 }
             }
-            p.print(b'"');
+            p.print(quote);
         }
     }
 }
@@ -145,7 +199,7 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for SpreadAttribute<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
         p.add_source_mapping(self.span.start);
         p.print_str(b"{...");
-        print_oxc_gen_expr(&self.expression, p);
+        print_oxc_gen_expr(&self.expression, self.expression.span(), p);
         p.print(b'}');
     }
 }
@@ -172,7 +226,7 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for AnimateDirective<'a> {
         p.print_str(self.name.as_bytes());
         if let Some(expression) = self.expression.as_ref() {
             p.print_str(b"={");
-            print_oxc_gen_expr(expression, p);
+            print_oxc_gen_expr(expression, expression.span(), p);
             p.print(b'}');
         }
     }
@@ -185,8 +239,10 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for BindDirective<'a> {
         p.print_str(self.name.as_bytes());
         p.print_str(b"={");
         match &self.expression {
-            BindDirectiveExpression::Identifier(ident) => print_oxc_gen(ident, p),
-            BindDirectiveExpression::MemberExpression(expr) => print_oxc_gen_expr(expr, p),
+            BindDirectiveExpression::Identifier(ident) => print_oxc_gen(ident, ident.span, p),
+            BindDirectiveExpression::MemberExpression(expr) => {
+                print_oxc_gen_expr(expr, expr.span(), p)
+            }
         };
         p.print(b'}');
     }
@@ -198,7 +254,7 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for ClassDirective<'a> {
         p.print_str(b"class:");
         p.print_str(self.name.as_bytes());
         p.print_str(b"={");
-        print_oxc_gen_expr(&self.expression, p);
+        print_oxc_gen_expr(&self.expression, self.expression.span(), p);
         p.print(b'}');
     }
 }
@@ -211,9 +267,11 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for LetDirective<'a> {
         if let Some(expression) = self.expression.as_ref() {
             p.print_str(b"={");
             match expression {
-                LetDirectiveExpression::Identifier(ident) => print_oxc_gen(ident, p),
-                LetDirectiveExpression::ArrayExpression(expr) => print_oxc_gen(expr, p),
-                LetDirectiveExpression::ObjectExpression(expr) => print_oxc_gen_expr(expr, p),
+                LetDirectiveExpression::Identifier(ident) => print_oxc_gen(ident, ident.span, p),
+                LetDirectiveExpression::ArrayExpression(expr) => print_oxc_gen(expr, expr.span, p),
+                LetDirectiveExpression::ObjectExpression(expr) => {
+                    print_oxc_gen_expr(expr, expr.span, p)
+                }
             };
             p.print(b'}');
         }
@@ -231,7 +289,7 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for OnDirective<'a> {
         }
         if let Some(expression) = self.expression.as_ref() {
             p.print_str(b"={");
-            print_oxc_gen_expr(expression, p);
+            print_oxc_gen_expr(expression, expression.span(), p);
             p.print(b'}');
         }
     }
@@ -277,7 +335,7 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for TransitionDirective<'a> {
         }
         if let Some(expression) = self.expression.as_ref() {
             p.print_str(b"={");
-            print_oxc_gen_expr(expression, p);
+            print_oxc_gen_expr(expression, expression.span(), p);
             p.print(b'}');
         }
     }
@@ -290,7 +348,7 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for UseDirective<'a> {
         p.print_str(self.name.as_bytes());
         if let Some(expression) = self.expression.as_ref() {
             p.print_str(b"={");
-            print_oxc_gen_expr(expression, p);
+            print_oxc_gen_expr(expression, expression.span(), p);
             p.print(b'}');
         }
     }
@@ -301,6 +359,7 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for Fragment<'a> {
         for node in &self.nodes {
             match node {
                 FragmentNode::Text(text) => text.gen(p),
+                FragmentNode::Comment(comment) => comment.gen(p),
                 FragmentNode::Tag(tag) => tag.gen(p),
                 FragmentNode::Element(element) => element.gen(p),
                 FragmentNode::Block(block) => block.gen(p),
@@ -315,6 +374,17 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for Text<'a> {
     }
 }
 
+impl<'a, const MINIFY: bool> Gen<MINIFY> for Comment<'a> {
+    fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
+        if p.options.preserve_comments {
+            p.add_source_mapping(self.span.start);
+            p.print_str(b"<!--");
+            p.print_str(self.data.as_bytes());
+            p.print_str(b"-->");
+        }
+    }
+}
+
 impl<'a, const MINIFY: bool> Gen<MINIFY> for Tag<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
         match self {
@@ -331,7 +401,7 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for ExpressionTag<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
         p.add_source_mapping(self.span.start);
         p.print(b'{');
-        print_oxc_gen_expr(&self.expression, p);
+        print_oxc_gen_expr(&self.expression, self.expression.span(), p);
         p.print(b'}');
     }
 }
@@ -340,7 +410,7 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for HtmlTag<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
         p.add_source_mapping(self.span.start);
         p.print_str(b"{@html ");
-        print_oxc_gen_expr(&self.expression, p);
+        print_oxc_gen_expr(&self.expression, self.expression.span(), p);
         p.print(b'}');
     }
 }
@@ -349,7 +419,7 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for ConstTag<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
         p.add_source_mapping(self.span.start);
         p.print_str(b"{@");
-        print_oxc_gen(&self.declaration, p);
+        print_oxc_gen(&self.declaration, self.declaration.span, p);
         p.print(b'}');
     }
 }
@@ -360,7 +430,7 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for DebugTag<'a> {
         p.print_str(b"{@debug ");
         let num_identifiers = self.identifiers.len();
         for (i, identifier) in self.identifiers.iter().enumerate() {
-            print_oxc_gen(identifier, p);
+            print_oxc_gen(identifier, identifier.span, p);
             if (i + 1) != num_identifiers {
                 p.print(b',');
                 p.print_soft_space();
@@ -376,7 +446,7 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for RenderTag<'a> {
         p.print_str(b"{@render ");
         match &self.expression {
             RenderTagExpression::Call(expr) | RenderTagExpression::Chain(expr) => {
-                print_oxc_gen_expr(expr, p);
+                print_oxc_gen_expr(expr, expr.span, p);
             }
         };
         p.print(b'}');
@@ -391,6 +461,7 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for Element<'a> {
             Self::SlotElement(element) => element.gen(p),
             Self::RegularElement(element) => element.gen(p),
             Self::SvelteBody(element) => element.gen(p),
+            Self::SvelteBoundary(element) => element.gen(p),
             Self::SvelteComponent(element) => element.gen(p),
             Self::SvelteDocument(element) => element.gen(p),
             Self::SvelteElement(element) => element.gen(p),
@@ -408,16 +479,22 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for Component<'a> {
         p.add_source_mapping(self.span.start);
         p.print(b'<');
         p.print_str(self.name.as_bytes());
-        for attribute in &self.attributes {
-            p.print_hard_space();
-            attribute.gen(p);
-        }
-        if self.fragment.nodes.is_empty() {
+        print_attributes(&self.attributes, p);
+        if self.fragment.nodes.is_empty() && p.options.self_closing.components {
             p.print_soft_space();
             p.print_str(b"/>");
         } else {
             p.print(b'>');
-            self.fragment.gen(p);
+            if html::is_raw_text(&self.name) {
+                // Comments in raw text are part of it
+                for node in &self.fragment.nodes {
+                    if let FragmentNode::Text(text) = node {
+                        p.print_str(text.data.as_bytes());
+                    }
+                }
+            } else {
+                print_children(&self.fragment, p);
+            }
             p.print_str(b"</");
             p.print_str(self.name.as_bytes());
             p.print(b'>');
@@ -429,16 +506,13 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for TitleElement<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
         p.add_source_mapping(self.span.start);
         p.print_str(b"<title");
-        for attribute in &self.attributes {
-            p.print_hard_space();
-            attribute.gen(p);
-        }
-        if self.fragment.nodes.is_empty() {
+        print_attributes(&self.attributes, p);
+        if self.fragment.nodes.is_empty() && p.options.self_closing.components {
             p.print_soft_space();
             p.print_str(b"/>");
         } else {
             p.print(b'>');
-            self.fragment.gen(p);
+            print_children(&self.fragment, p);
             p.print_str(b"</title>");
         }
     }
@@ -448,16 +522,13 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for SlotElement<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
         p.add_source_mapping(self.span.start);
         p.print_str(b"<slot");
-        for attribute in &self.attributes {
-            p.print_hard_space();
-            attribute.gen(p);
-        }
-        if self.fragment.nodes.is_empty() {
+        print_attributes(&self.attributes, p);
+        if self.fragment.nodes.is_empty() && p.options.self_closing.components {
             p.print_soft_space();
             p.print_str(b"/>");
         } else {
             p.print(b'>');
-            self.fragment.gen(p);
+            print_children(&self.fragment, p);
             p.print_str(b"</slot>");
         }
     }
@@ -468,16 +539,30 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for RegularElement<'a> {
         p.add_source_mapping(self.span.start);
         p.print(b'<');
         p.print_str(self.name.as_bytes());
-        for attribute in &self.attributes {
-            p.print_hard_space();
-            attribute.gen(p);
-        }
-        if self.fragment.nodes.is_empty() {
-            p.print_soft_space();
-            p.print_str(b"/>");
+        print_attributes(&self.attributes, p);
+        // Only void elements are self-closed, `<div />` is ambiguous in HTML
+        if html::is_void(&self.name) {
+            if p.options.self_closing.void_elements {
+                p.print_soft_space();
+                p.print_str(b"/>");
+            } else {
+                p.print(b'>');
+            }
         } else {
             p.print(b'>');
-            self.fragment.gen(p);
+            if html::is_raw_text(&self.name) {
+                // Comments in raw text are part of it
+                for node in &self.fragment.nodes {
+                    if let FragmentNode::Text(text) = node {
+                        p.print_str(text.data.as_bytes());
+                    }
+                }
+            } else {
+                let preformatted = p.preformatted;
+                p.preformatted |= html::is_preformatted(&self.name);
+                print_children(&self.fragment, p);
+                p.preformatted = preformatted;
+            }
             p.print_str(b"</");
             p.print_str(self.name.as_bytes());
             p.print(b'>');
@@ -489,37 +574,47 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for SvelteBody<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
         p.add_source_mapping(self.span.start);
         p.print_str(b"<svelte:body");
-        for attribute in &self.attributes {
-            p.print_hard_space();
-            attribute.gen(p);
-        }
-        if self.fragment.nodes.is_empty() {
+        print_attributes(&self.attributes, p);
+        if self.fragment.nodes.is_empty() && p.options.self_closing.components {
             p.print_soft_space();
             p.print_str(b"/>");
         } else {
             p.print(b'>');
-            self.fragment.gen(p);
+            print_children(&self.fragment, p);
             p.print_str(b"</svelte:body>");
         }
     }
 }
 
+impl<'a, const MINIFY: bool> Gen<MINIFY> for SvelteBoundary<'a> {
+    fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
+        p.add_source_mapping(self.span.start);
+        p.print_str(b"<svelte:boundary");
+        print_attributes(&self.attributes, p);
+        if self.fragment.nodes.is_empty() && p.options.self_closing.components {
+            p.print_soft_space();
+            p.print_str(b"/>");
+        } else {
+            p.print(b'>');
+            print_children(&self.fragment, p);
+            p.print_str(b"</svelte:boundary>");
+        }
+    }
+}
+
 impl<'a, const MINIFY: bool> Gen<MINIFY> for SvelteComponent<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
         p.add_source_mapping(self.span.start);
         p.print_str(b"<svelte:component this={");
-        print_oxc_gen_expr(&self.expression, p);
+        print_oxc_gen_expr(&self.expression, self.expression.span(), p);
         p.print(b'}');
-        for attribute in &self.attributes {
-            p.print_hard_space();
-            attribute.gen(p);
-        }
-        if self.fragment.nodes.is_empty() {
+        print_attributes(&self.attributes, p);
+        if self.fragment.nodes.is_empty() && p.options.self_closing.components {
             p.print_soft_space();
             p.print_str(b"/>");
         } else {
             p.print(b'>');
-            self.fragment.gen(p);
+            print_children(&self.fragment, p);
             p.print_str(b"</svelte:component>");
         }
     }
@@ -528,17 +623,14 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for SvelteComponent<'a> {
 impl<'a, const MINIFY: bool> Gen<MINIFY> for SvelteDocument<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
         p.add_source_mapping(self.span.start);
-        p.print_str(b"<svele:document");
-        for attribute in &self.attributes {
-            p.print_hard_space();
-            attribute.gen(p);
-        }
-        if self.fragment.nodes.is_empty() {
+        p.print_str(b"<svelte:document");
+        print_attributes(&self.attributes, p);
+        if self.fragment.nodes.is_empty() && p.options.self_closing.components {
             p.print_soft_space();
             p.print_str(b"/>");
         } else {
             p.print(b'>');
-            self.fragment.gen(p);
+            print_children(&self.fragment, p);
             p.print_str(b"</svelte:document>");
         }
     }
@@ -548,18 +640,15 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for SvelteElement<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
         p.add_source_mapping(self.span.start);
         p.print_str(b"<svelte:element this={");
-        print_oxc_gen_expr(&self.expression, p);
+        print_oxc_gen_expr(&self.expression, self.expression.span(), p);
         p.print(b'}');
-        for attribute in &self.attributes {
-            p.print_hard_space();
-            attribute.gen(p);
-        }
-        if self.fragment.nodes.is_empty() {
+        print_attributes(&self.attributes, p);
+        if self.fragment.nodes.is_empty() && p.options.self_closing.components {
             p.print_soft_space();
             p.print_str(b"/>");
         } else {
             p.print(b'>');
-            self.fragment.gen(p);
+            print_children(&self.fragment, p);
             p.print_str(b"</svelte:element>");
         }
     }
@@ -569,16 +658,13 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for SvelteFragment<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
         p.add_source_mapping(self.span.start);
         p.print_str(b"<svelte:fragment");
-        for attribute in &self.attributes {
-            p.print_hard_space();
-            attribute.gen(p);
-        }
-        if self.fragment.nodes.is_empty() {
+        print_attributes(&self.attributes, p);
+        if self.fragment.nodes.is_empty() && p.options.self_closing.components {
             p.print_soft_space();
             p.print_str(b"/>");
         } else {
             p.print(b'>');
-            self.fragment.gen(p);
+            print_children(&self.fragment, p);
             p.print_str(b"</svelte:fragment>");
         }
     }
@@ -588,16 +674,13 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for SvelteHead<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
         p.add_source_mapping(self.span.start);
         p.print_str(b"<svelte:head");
-        for attribute in &self.attributes {
-            p.print_hard_space();
-            attribute.gen(p);
-        }
-        if self.fragment.nodes.is_empty() {
+        print_attributes(&self.attributes, p);
+        if self.fragment.nodes.is_empty() && p.options.self_closing.components {
             p.print_soft_space();
             p.print_str(b"/>");
         } else {
             p.print(b'>');
-            self.fragment.gen(p);
+            print_children(&self.fragment, p);
             p.print_str(b"</svelte:head>");
         }
     }
@@ -607,16 +690,13 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for SvelteOptionsRaw<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
         p.add_source_mapping(self.span.start);
         p.print_str(b"<svelte:options");
-        for attribute in &self.attributes {
-            p.print_hard_space();
-            attribute.gen(p);
-        }
-        if self.fragment.nodes.is_empty() {
+        print_attributes(&self.attributes, p);
+        if self.fragment.nodes.is_empty() && p.options.self_closing.components {
             p.print_soft_space();
             p.print_str(b"/>");
         } else {
             p.print(b'>');
-            self.fragment.gen(p);
+            print_children(&self.fragment, p);
             p.print_str(b"</svelte:options>");
         }
     }
@@ -626,16 +706,13 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for SvelteSelf<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
         p.add_source_mapping(self.span.start);
         p.print_str(b"<svelte:self");
-        for attribute in &self.attributes {
-            p.print_hard_space();
-            attribute.gen(p);
-        }
-        if self.fragment.nodes.is_empty() {
+        print_attributes(&self.attributes, p);
+        if self.fragment.nodes.is_empty() && p.options.self_closing.components {
             p.print_soft_space();
             p.print_str(b"/>");
         } else {
             p.print(b'>');
-            self.fragment.gen(p);
+            print_children(&self.fragment, p);
             p.print_str(b"</svelte:self>");
         }
     }
@@ -645,16 +722,13 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for SvelteWindow<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
         p.add_source_mapping(self.span.start);
         p.print_str(b"<svelte:window");
-        for attribute in &self.attributes {
-            p.print_hard_space();
-            attribute.gen(p);
-        }
-        if self.fragment.nodes.is_empty() {
+        print_attributes(&self.attributes, p);
+        if self.fragment.nodes.is_empty() && p.options.self_closing.components {
             p.print_soft_space();
             p.print_str(b"/>");
         } else {
             p.print(b'>');
-            self.fragment.gen(p);
+            print_children(&self.fragment, p);
             p.print_str(b"</svelte:window>");
         }
     }
@@ -676,25 +750,25 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for EachBlock<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
         p.add_source_mapping(self.span.start);
         p.print_str(b"{#each ");
-        print_oxc_gen_expr(&self.expression, p);
+        print_oxc_gen_expr(&self.expression, self.expression.span(), p);
         p.print_str(b" as ");
-        print_oxc_gen(&self.context, p);
+        print_oxc_gen(&self.context, self.context.span(), p);
         if let Some(index) = self.index.as_ref() {
             p.print(b',');
             p.print_soft_space();
-            print_oxc_gen(index, p);
+            print_oxc_gen(index, index.span, p);
         }
         if let Some(key) = self.key.as_ref() {
             p.print_soft_space();
             p.print(b'(');
-            print_oxc_gen_expr(key, p);
+            print_oxc_gen_expr(key, key.span(), p);
             p.print(b')');
         }
         p.print(b'}');
-        self.body.gen(p);
+        print_children(&self.body, p);
         if let Some(fallback) = self.fallback.as_ref() {
             p.print_str(b"{:else}");
-            fallback.gen(p);
+            print_children(fallback, p);
         }
         p.print_str(b"{/each}");
     }
@@ -712,27 +786,27 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for AwaitBlock<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
         p.add_source_mapping(self.span.start);
         p.print_str(b"{#await ");
-        print_oxc_gen_expr(&self.expression, p);
+        print_oxc_gen_expr(&self.expression, self.expression.span(), p);
         if let Some(pending) = self.pending.as_ref() {
             p.print(b'}');
-            pending.gen(p);
+            print_children(pending, p);
             if let Some(then) = self.then.as_ref() {
                 p.print_str(b"{:then");
                 if let Some(value) = self.value.as_ref() {
                     p.print_hard_space();
-                    print_oxc_gen(value, p);
+                    print_oxc_gen(value, value.span(), p);
                 }
                 p.print(b'}');
-                then.gen(p);
+                print_children(then, p);
             }
             if let Some(catch) = self.catch.as_ref() {
                 p.print_str(b"{:catch");
                 if let Some(error) = self.error.as_ref() {
                     p.print_hard_space();
-                    print_oxc_gen(error, p);
+                    print_oxc_gen(error, error.span(), p);
                 }
                 p.print(b'}');
-                catch.gen(p);
+                print_children(catch, p);
             }
             p.print_str(b"{/await}");
             return;
@@ -741,18 +815,18 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for AwaitBlock<'a> {
             p.print_str(b" then");
             if let Some(value) = self.value.as_ref() {
                 p.print_hard_space();
-                print_oxc_gen(value, p);
+                print_oxc_gen(value, value.span(), p);
             }
             p.print(b'}');
-            then.gen(p);
+            print_children(then, p);
             if let Some(catch) = self.catch.as_ref() {
                 p.print_str(b"{:catch");
                 if let Some(error) = self.error.as_ref() {
                     p.print_hard_space();
-                    print_oxc_gen(error, p);
+                    print_oxc_gen(error, error.span(), p);
                 }
                 p.print(b'}');
-                catch.gen(p);
+                print_children(catch, p);
             }
             p.print_str(b"{/await}");
             return;
@@ -761,10 +835,10 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for AwaitBlock<'a> {
             p.print_str(b" catch");
             if let Some(value) = self.value.as_ref() {
                 p.print_hard_space();
-                print_oxc_gen(value, p);
+                print_oxc_gen(value, value.span(), p);
             }
             p.print(b'}');
-            catch.gen(p);
+            print_children(catch, p);
             p.print_str(b"{/await}");
             return;
         }
@@ -776,9 +850,9 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for KeyBlock<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
         p.add_source_mapping(self.span.start);
         p.print_str(b"{#key ");
-        print_oxc_gen_expr(&self.expression, p);
+        print_oxc_gen_expr(&self.expression, self.expression.span(), p);
         p.print(b'}');
-        self.fragment.gen(p);
+        print_children(&self.fragment, p);
         p.print_str(b"{/key}");
     }
 }
@@ -787,26 +861,26 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for SnippetBlock<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
         p.add_source_mapping(self.span.start);
         p.print_str(b"{#snippet ");
-        print_oxc_gen(&self.expression, p);
+        print_oxc_gen(&self.expression, self.expression.span, p);
         p.print(b'(');
         let num_parameter = self.parameters.len();
         for (i, parameter) in self.parameters.iter().enumerate() {
-            print_oxc_gen(parameter, p);
+            print_oxc_gen(parameter, parameter.span(), p);
             if (i + 1) != num_parameter {
-                p.print_soft_space();
                 p.print(b',');
+                p.print_soft_space();
             }
         }
         p.print_str(b")}");
-        self.body.gen(p);
+        print_children(&self.body, p);
         p.print_str(b"{/snippet}");
     }
 }
 
 fn print_if_block<const MINIFY: bool>(block: &IfBlock<'_>, p: &mut Codegen<{ MINIFY }>) {
-    print_oxc_gen_expr(&block.test, p);
+    print_oxc_gen_expr(&block.test, block.test.span(), p);
     p.print(b'}');
-    block.consequent.gen(p);
+    print_children(&block.consequent, p);
     if let Some(alternate) = block.alternate.as_ref() {
         if alternate.nodes.len() == 1 {
             let first = &alternate.nodes[0];
@@ -820,21 +894,73 @@ fn print_if_block<const MINIFY: bool>(block: &IfBlock<'_>, p: &mut Codegen<{ MIN
             }
         }
         p.print_str(b"{:else}");
-        alternate.gen(p);
+        print_children(alternate, p);
     }
     p.print_str(b"{/if}");
 }
 
-fn print_oxc_gen_expr<const MINIFY: bool, T: GenExpr<MINIFY>>(x: &T, p: &mut Codegen<{ MINIFY }>) {
+fn print_attributes<const MINIFY: bool>(
+    attributes: &[ElementAttribute<'_>],
+    p: &mut Codegen<{ MINIFY }>,
+) {
+    let attributes = p.options.attribute_layout.arrange(attributes);
+    let multiline = !MINIFY
+        && (p.options.attribute_layout.is_multiline(attributes.len())
+            || p.is_formatting() && !fits_on_line(&attributes, p));
+    if multiline {
+        p.indent();
+    }
+    for attribute in attributes {
+        if multiline {
+            p.print_soft_newline();
+            p.print_indent();
+        } else {
+            p.print_hard_space();
+        }
+        attribute.gen(p);
+    }
+    if multiline {
+        p.dedent();
+        p.print_soft_newline();
+        p.print_indent();
+    }
+}
+
+/// Whether `attributes` fit on the current line, with the end of the tag.
+fn fits_on_line<const MINIFY: bool>(
+    attributes: &[&ElementAttribute<'_>],
+    p: &Codegen<{ MINIFY }>,
+) -> bool {
+    let mut width = p.column() + " />".len();
+    for attribute in attributes {
+        match p.width_of(*attribute) {
+            Some(attribute_width) => width += attribute_width + 1,
+            None => return false,
+        }
+    }
+    width <= p.options.line_width
+}
+
+/// Print `x`, the JavaScript at `span` of the source.
+fn print_oxc_gen_expr<'a, const MINIFY: bool, T: GenExpr<MINIFY> + JsNode<'a>>(
+    x: &T,
+    span: Span,
+    p: &mut Codegen<{ MINIFY }>,
+) {
     let mut codegen = oxc_codegen::Codegen::<MINIFY>::new();
     x.gen_expr(&mut codegen, Precedence::lowest(), Context::default());
     let source = codegen.into_source_text();
-    p.print_str(source.as_bytes());
+    p.print_js(&source, x, span);
 }
 
-fn print_oxc_gen<const MINIFY: bool, T: OxcGen<MINIFY>>(x: &T, p: &mut Codegen<{ MINIFY }>) {
+/// Print `x`, the JavaScript at `span` of the source.
+fn print_oxc_gen<'a, const MINIFY: bool, T: OxcGen<MINIFY> + JsNode<'a>>(
+    x: &T,
+    span: Span,
+    p: &mut Codegen<{ MINIFY }>,
+) {
     let mut codegen = oxc_codegen::Codegen::<MINIFY>::new();
     x.gen(&mut codegen, Context::default());
     let source = codegen.into_source_text();
-    p.print_str(source.as_bytes());
+    p.print_js(&source, x, span);
 }
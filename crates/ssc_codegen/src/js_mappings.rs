@@ -0,0 +1,184 @@
+//! Source mappings of the JavaScript printed by `oxc_codegen`
+//!
+//! `oxc_codegen` prints the expressions of the template to its own buffer,
+//! without mappings. Their mappings are emitted from the spans of the printed
+//! nodes instead: the identifiers, string literals and text of template
+//! literals are collected in printing order, and each is mapped to where it's
+//! printed next.
+
+use oxc_allocator::Box;
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, Visit};
+use oxc_span::Atom;
+
+/// A node printed by `oxc_codegen` in the template.
+pub trait JsNode<'a> {
+    fn collect(&self, collector: &mut Collector<'a>);
+}
+
+impl<'a, T: JsNode<'a>> JsNode<'a> for Box<'a, T> {
+    fn collect(&self, collector: &mut Collector<'a>) {
+        (**self).collect(collector);
+    }
+}
+
+macro_rules! impl_js_node {
+    ($($ty:ident => $visit:ident,)*) => {
+        $(
+            impl<'a> JsNode<'a> for $ty<'a> {
+                fn collect(&self, collector: &mut Collector<'a>) {
+                    collector.$visit(self);
+                }
+            }
+        )*
+    };
+}
+
+impl_js_node! {
+    Expression => visit_expression,
+    IdentifierReference => visit_identifier_reference,
+    IdentifierName => visit_identifier_name,
+    MemberExpression => visit_member_expression,
+    CallExpression => visit_call_expression,
+    ArrayExpression => visit_array_expression,
+    ObjectExpression => visit_object_expression,
+    BindingPattern => visit_binding_pattern,
+    VariableDeclaration => visit_variable_declaration,
+}
+
+enum Mapped<'a> {
+    /// An identifier or keyword, printed as `name`.
+    Name(Atom<'a>),
+    /// A string literal, whose quotes and escapes may be printed differently.
+    String,
+    /// The text of a template literal after its backtick or an expression,
+    /// printed as written.
+    Quasi(Atom<'a>),
+}
+
+/// The nodes with a mapping, by their start in the source.
+#[derive(Default)]
+pub struct Collector<'a> {
+    nodes: Vec<(u32, Mapped<'a>)>,
+}
+
+impl<'a> Collector<'a> {
+    fn push(&mut self, start: u32, node: Mapped<'a>) {
+        // Nodes printed once but visited twice, e.g. the key and value of a
+        // shorthand property, and nodes printed out of source order, would
+        // move the search past the nodes printed after them
+        if !matches!(self.nodes.last(), Some((last, _)) if *last >= start) {
+            self.nodes.push((start, node));
+        }
+    }
+}
+
+impl<'a> Visit<'a> for Collector<'a> {
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        self.push(ident.span.start, Mapped::Name(ident.name.clone()));
+    }
+
+    fn visit_binding_identifier(&mut self, ident: &BindingIdentifier<'a>) {
+        self.push(ident.span.start, Mapped::Name(ident.name.clone()));
+    }
+
+    fn visit_identifier_name(&mut self, ident: &IdentifierName<'a>) {
+        self.push(ident.span.start, Mapped::Name(ident.name.clone()));
+    }
+
+    fn visit_label_identifier(&mut self, ident: &LabelIdentifier<'a>) {
+        self.push(ident.span.start, Mapped::Name(ident.name.clone()));
+    }
+
+    fn visit_private_identifier(&mut self, ident: &PrivateIdentifier<'a>) {
+        // The name after the `#`
+        self.push(ident.span.start + 1, Mapped::Name(ident.name.clone()));
+    }
+
+    fn visit_this_expression(&mut self, expr: &ThisExpression) {
+        self.push(expr.span.start, Mapped::Name(Atom::from("this")));
+    }
+
+    fn visit_string_literal(&mut self, lit: &StringLiteral<'a>) {
+        self.push(lit.span.start, Mapped::String);
+    }
+
+    fn visit_template_literal(&mut self, lit: &TemplateLiteral<'a>) {
+        // The text and the expressions are printed in turn, the names in the
+        // text must be skipped before looking for the next expression
+        for (index, quasi) in lit.quasis.iter().enumerate() {
+            if !quasi.value.raw.is_empty() {
+                self.push(quasi.span.start, Mapped::Quasi(quasi.value.raw.clone()));
+            }
+            if let Some(expression) = lit.expressions.get(index) {
+                self.visit_expression(expression);
+            }
+        }
+    }
+}
+
+/// The mappings of `code`, printed from `node`: the offsets in `code` of
+/// the identifiers, string literals and template text of `node`, with their
+/// start in the source.
+pub fn mappings<'a>(code: &str, node: &impl JsNode<'a>) -> Vec<(usize, u32)> {
+    let mut collector = Collector::default();
+    node.collect(&mut collector);
+    let mut mappings = vec![];
+    let mut position = 0;
+    for (start, node) in collector.nodes {
+        // Nodes the codegen didn't print, e.g. types, are skipped
+        let found = match node {
+            Mapped::Name(name) => {
+                find_name(code, position, name.as_str()).map(|offset| (offset, offset + name.len()))
+            }
+            Mapped::String => find_string(code, position),
+            Mapped::Quasi(raw) => find_quasi(code, position, raw.as_str()),
+        };
+        if let Some((offset, end)) = found {
+            mappings.push((offset, start));
+            position = end;
+        }
+    }
+    mappings
+}
+
+fn is_name_char(ch: char) -> bool {
+    ch == '_' || ch == '$' || ch.is_alphanumeric()
+}
+
+/// The offset of the next `name` in `code` from `position`, which isn't
+/// part of a longer name.
+fn find_name(code: &str, position: usize, name: &str) -> Option<usize> {
+    code[position..].match_indices(name).map(|(offset, _)| position + offset).find(|&offset| {
+        !code[..offset].ends_with(is_name_char)
+            && !code[offset + name.len()..].starts_with(is_name_char)
+    })
+}
+
+/// The offset and end of the next text `raw` of a template literal in `code`
+/// from `position`, which follows a backtick or the brace closing an
+/// expression.
+fn find_quasi(code: &str, position: usize, raw: &str) -> Option<(usize, usize)> {
+    ['`', '}']
+        .into_iter()
+        .filter_map(|open| code[position..].find(&format!("{open}{raw}")))
+        .min()
+        .map(|offset| (position + offset + 1, position + offset + 1 + raw.len()))
+}
+
+/// The offset and end of the next string literal in `code` from `position`.
+fn find_string(code: &str, position: usize) -> Option<(usize, usize)> {
+    let start = position + code[position..].find(['"', '\''])?;
+    let quote = code.as_bytes()[start];
+    let mut escaped = false;
+    for (offset, &ch) in code.as_bytes().iter().enumerate().skip(start + 1) {
+        if escaped {
+            escaped = false;
+        } else if ch == b'\\' {
+            escaped = true;
+        } else if ch == quote {
+            return Some((start, offset + 1));
+        }
+    }
+    None
+}
@@ -4,26 +4,134 @@
 //!
 //! * whitespace removal
 //! * sourcemaps
+//! * HTML comments
+//! * external `<script>` formatters
+//! * formatting, see [`CodegenOptions::format`]
 //!
 //! Code adapted from
 //! * [oxc](https://github.com/oxc-project/oxc/blob/main/crates/oxc_codegen/src/lib.rs)
 
+mod attribute_layout;
+mod format;
 mod gen;
+mod js_mappings;
+mod script_formatter;
 mod sourcemap_builder;
 
+use js_mappings::JsNode;
+use oxc_span::Span;
 use sourcemap_builder::SourcemapBuilder;
 #[allow(clippy::wildcard_imports)]
 use ssc_ast::ast::*;
 
-pub use crate::gen::Gen;
+pub use crate::{
+    attribute_layout::{AttributeKind, AttributeLayoutOptions},
+    gen::Gen,
+    script_formatter::{FormattedScript, ScriptFormatter},
+};
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone)]
 pub struct CodegenOptions {
     /// Pass in the filename to enable source map support.
     pub enable_source_map: bool,
 
     /// Enable TypeScript code generation.
     pub enable_typescript: bool,
+
+    /// Controls the order and wrapping of element attributes.
+    pub attribute_layout: AttributeLayoutOptions,
+
+    /// Keep the comment nodes of the template instead of stripping them.
+    pub preserve_comments: bool,
+
+    /// Options of the `<style>` contents. Their source map isn't generated.
+    pub css: ssc_css_codegen::CodegenOptions,
+
+    /// The quotes around attribute values. The other quote is used for
+    /// values containing the preferred one.
+    pub quote_style: QuoteStyle,
+
+    /// Which elements without content are printed self-closing.
+    pub self_closing: SelfClosingOptions,
+
+    /// Reflow the template like a formatter: elements and blocks with
+    /// child elements or blocks, and contents too long for the line, are
+    /// printed one child per line, long attribute lists are wrapped, and
+    /// text is wrapped at [`Self::line_width`].
+    ///
+    /// Only whitespace that doesn't change the rendered output is added or
+    /// removed: line breaks replace whitespace between nodes, and the
+    /// whitespace at the start and end of fragments is dropped. The children
+    /// of `<pre>` and `<textarea>` are printed as is.
+    pub format: bool,
+
+    /// Number of spaces per indentation level, unless [`Self::use_tabs`].
+    pub indent_width: u8,
+
+    /// Indent with tabs rather than spaces.
+    pub use_tabs: bool,
+
+    /// The line width [`Self::format`] tries to fit lines in.
+    pub line_width: usize,
+}
+
+impl Default for CodegenOptions {
+    fn default() -> Self {
+        Self {
+            enable_source_map: false,
+            enable_typescript: false,
+            attribute_layout: AttributeLayoutOptions::default(),
+            preserve_comments: false,
+            css: ssc_css_codegen::CodegenOptions::default(),
+            quote_style: QuoteStyle::default(),
+            self_closing: SelfClosingOptions::default(),
+            format: false,
+            indent_width: 2,
+            use_tabs: true,
+            line_width: 80,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteStyle {
+    #[default]
+    Double,
+    Single,
+}
+
+impl QuoteStyle {
+    fn quote(self) -> u8 {
+        match self {
+            Self::Double => b'"',
+            Self::Single => b'\'',
+        }
+    }
+
+    fn other(self) -> Self {
+        match self {
+            Self::Double => Self::Single,
+            Self::Single => Self::Double,
+        }
+    }
+}
+
+/// Which elements without content are printed self-closing. Regular
+/// elements never are, as `<div />` is ambiguous in HTML.
+#[derive(Debug, Clone, Copy)]
+pub struct SelfClosingOptions {
+    /// `<br />` instead of `<br>`.
+    pub void_elements: bool,
+
+    /// `<Card />`, `<slot />` and `<svelte:window />` instead of
+    /// `<Card></Card>`, `<slot></slot>` and `<svelte:window></svelte:window>`.
+    pub components: bool,
+}
+
+impl Default for SelfClosingOptions {
+    fn default() -> Self {
+        Self { void_elements: true, components: true }
+    }
 }
 
 pub struct CodegenReturn {
@@ -32,7 +140,6 @@ pub struct CodegenReturn {
 }
 
 pub struct Codegen<const MINIFY: bool> {
-    #[allow(unused)]
     options: CodegenOptions,
 
     /// Output Code
@@ -42,6 +149,14 @@ pub struct Codegen<const MINIFY: bool> {
     indentation: u8,
 
     sourcemap_builder: Option<SourcemapBuilder>,
+
+    /// Formatter of the `<script>` contents, with the source text they're
+    /// sliced from.
+    script_formatter: Option<(Box<dyn ScriptFormatter>, String)>,
+
+    /// Are we inside a `<pre>` or `<textarea>`, whose whitespace is printed
+    /// as is?
+    preformatted: bool,
 }
 
 impl<const MINIFY: bool> Codegen<MINIFY> {
@@ -63,9 +178,24 @@ impl<const MINIFY: bool> Codegen<MINIFY> {
             code: Vec::with_capacity(capacity),
             indentation: 0,
             sourcemap_builder,
+            script_formatter: None,
+            preformatted: false,
         }
     }
 
+    /// Print the contents of `<script>` elements with `formatter`, falling
+    /// back to the codegen when it returns `None`. `source_text` is the
+    /// source of the AST.
+    #[must_use]
+    pub fn with_script_formatter<F: ScriptFormatter + 'static>(
+        mut self,
+        formatter: F,
+        source_text: &str,
+    ) -> Self {
+        self.script_formatter = Some((Box::new(formatter), source_text.to_string()));
+        self
+    }
+
     pub fn build(mut self, root: &Root<'_>) -> CodegenReturn {
         root.gen(&mut self);
         let source_text = self.into_source_text();
@@ -91,16 +221,69 @@ impl<const MINIFY: bool> Codegen<MINIFY> {
         self.code.extend_from_slice(s);
     }
 
+    /// Print the lines of `s` indented, their own tab indentation replaced
+    /// by the configured one.
     pub fn print_str_with_indention(&mut self, s: &[u8]) {
         let lines = s.split(|&ch| ch == b'\n');
 
         for line in lines {
             self.print_indent();
-            self.print_str(line);
+            let tabs = line.iter().take_while(|&&ch| ch == b'\t').count();
+            for _ in 0..tabs {
+                self.print_indent_unit();
+            }
+            self.print_str(&line[tabs..]);
             self.print_soft_newline();
         }
     }
 
+    /// Print `script`, formatted from the `original` text at
+    /// `original_start`, with the mappings of its source map.
+    fn print_formatted_script(
+        &mut self,
+        script: &FormattedScript,
+        original: &str,
+        original_start: u32,
+    ) {
+        let mut mappings = script.mappings(original).into_iter().peekable();
+        let mut line_start = 0;
+        for line in script.source_text.trim_end().split('\n') {
+            self.print_indent();
+            let mut printed = 0;
+            while let Some((generated, original)) =
+                mappings.next_if(|(generated, _)| *generated <= line_start + line.len())
+            {
+                let generated = generated.saturating_sub(line_start).max(printed);
+                self.print_str(&line.as_bytes()[printed..generated]);
+                printed = generated;
+                #[allow(clippy::cast_possible_truncation)]
+                self.add_source_mapping(original_start + original as u32);
+            }
+            self.print_str(&line.as_bytes()[printed..]);
+            // The lines of a formatted script aren't joined, as they may
+            // rely on automatic semicolon insertion or end with a comment
+            self.print(b'\n');
+            line_start += line.len() + 1;
+        }
+    }
+
+    /// Print `code`, printed by `oxc_codegen` from `node` at `span` of the
+    /// source, with the mappings of its identifiers and string literals.
+    fn print_js<'a>(&mut self, code: &str, node: &impl JsNode<'a>, span: Span) {
+        if self.sourcemap_builder.is_none() {
+            self.print_str(code.as_bytes());
+            return;
+        }
+        self.add_source_mapping(span.start);
+        let mut printed = 0;
+        for (offset, start) in js_mappings::mappings(code, node) {
+            self.print_str(&code.as_bytes()[printed..offset]);
+            printed = offset;
+            self.add_source_mapping(start);
+        }
+        self.print_str(&code.as_bytes()[printed..]);
+    }
+
     fn print_soft_space(&mut self) {
         if !MINIFY {
             self.print(b' ');
@@ -132,14 +315,172 @@ impl<const MINIFY: bool> Codegen<MINIFY> {
     fn print_indent(&mut self) {
         if !MINIFY {
             for _ in 0..self.indentation {
-                self.print(b'\t');
+                self.print_indent_unit();
+            }
+        }
+    }
+
+    fn print_indent_unit(&mut self) {
+        if self.options.use_tabs {
+            self.print(b'\t');
+        } else {
+            for _ in 0..self.options.indent_width {
+                self.print(b' ');
             }
         }
     }
 
+    /// Whether the template is reflowed, see [`CodegenOptions::format`].
+    fn is_formatting(&self) -> bool {
+        !MINIFY && self.options.format && !self.preformatted
+    }
+
+    /// The width of the current line, indentation included.
+    fn column(&self) -> usize {
+        let line_start = self.code.iter().rposition(|&ch| ch == b'\n').map_or(0, |index| index + 1);
+        let line = &self.code[line_start..];
+        // The continuation bytes of UTF-8 characters aren't counted
+        let chars = line.iter().filter(|&&ch| ch & 0xC0 != 0x80).count();
+        let tabs = line.iter().filter(|&&ch| ch == b'\t').count();
+        chars - tabs + tabs * usize::from(self.options.indent_width)
+    }
+
     fn add_source_mapping(&mut self, position: u32) {
         if let Some(sourcemap_builder) = self.sourcemap_builder.as_mut() {
             sourcemap_builder.add_source_mapping(&self.code, position, None);
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use ssc_parser::Parser;
+
+    use oxc_sourcemap::{SourceMap, Token};
+    use oxc_span::SourceType;
+
+    use crate::{Codegen, CodegenOptions, FormattedScript, QuoteStyle, SelfClosingOptions};
+
+    fn print(source: &str, preserve_comments: bool) -> String {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty());
+        let options = CodegenOptions { preserve_comments, ..CodegenOptions::default() };
+        Codegen::<false>::new("", source, options).build(&ret.root).source_text
+    }
+
+    #[test]
+    fn comments() {
+        let source = "<!-- hydrate -->\n<p>a <!-- b --> c</p><div><!-- x --></div><!-- end -->";
+        assert_eq!(print(source, true), source);
+        assert_eq!(print(source, false), "\n<p>a  c</p><div></div>");
+    }
+
+    #[test]
+    fn script_order() {
+        let source =
+            "<script>let b;</script><script context=\"module\">export const a = 1;</script>";
+        let output = print(source, false);
+        assert!(output.find("export const a").unwrap() < output.find("let b").unwrap(), "{output}");
+    }
+
+    #[test]
+    fn quote_style_and_self_closing() {
+        let source = r#"<input value="a" title="it's {b}"><Card label='say "hi"' /><svelte:window /><p class="c"></p>"#;
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty());
+        let options = CodegenOptions {
+            quote_style: QuoteStyle::Single,
+            self_closing: SelfClosingOptions { void_elements: false, components: false },
+            ..CodegenOptions::default()
+        };
+        assert_eq!(
+            Codegen::<false>::new("", source, options).build(&ret.root).source_text,
+            r#"<input value='a' title="it's {b}"><Card label='say "hi"'></Card><svelte:window></svelte:window><p class='c'></p>"#
+        );
+        assert_eq!(
+            Codegen::<false>::new("", source, CodegenOptions::default())
+                .build(&ret.root)
+                .source_text,
+            r#"<input value="a" title="it's {b}" /><Card label='say "hi"' /><svelte:window /><p class="c"></p>"#
+        );
+    }
+
+    #[test]
+    fn raw_text_elements() {
+        let source = r#"<svelte:head><script type="application/ld+json">{"@type": "<!-- {a} -->"}</script></svelte:head>{#if a}<script>if (a < b) {}</script>{/if}"#;
+        assert_eq!(print(source, false), source);
+        assert_eq!(print(source, true), source);
+    }
+
+    #[test]
+    fn script_formatter() {
+        let source = "<script>\n  let  a=1\n</script>";
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source).parse();
+        // `a` of `let a = 1;` from `a` of `  let  a=1`
+        let format = |_: &str, _: SourceType| {
+            let tokens = vec![Token::new(0, 4, 1, 7, Some(0), None)];
+            let source_map = SourceMap::new(None, vec![], None, vec![], None, tokens, None);
+            let mut script = FormattedScript::new("let a = 1;\n".to_string());
+            script.source_map = Some(source_map);
+            Some(script)
+        };
+        let options = CodegenOptions { enable_source_map: true, ..CodegenOptions::default() };
+        let ret = Codegen::<false>::new("a.svelte", source, options)
+            .with_script_formatter(format, source)
+            .build(&ret.root);
+        assert_eq!(ret.source_text, "<script>\n\tlet a = 1;\n</script>\n");
+        let source_map = ret.source_map.unwrap();
+        let token = source_map.get_tokens().last().unwrap();
+        assert_eq!(
+            (token.get_dst_line(), token.get_dst_col(), token.get_src_line(), token.get_src_col()),
+            (1, 5, 1, 7)
+        );
+    }
+
+    #[test]
+    fn expression_mappings() {
+        let source = "<p class=\"a {b}\">\n\t{value  +  count}\n\t{f(\"x\",  x)}\n\t{`x ${x}`}</p>";
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source).parse();
+        let options = CodegenOptions { enable_source_map: true, ..CodegenOptions::default() };
+        let ret = Codegen::<false>::new("a.svelte", source, options).build(&ret.root);
+        assert_eq!(
+            ret.source_text,
+            "<p class=\"a {b}\">\n\t{value + count}\n\t{f(\"x\", x)}\n\t{`x ${x}`}</p>"
+        );
+        let tokens = ret
+            .source_map
+            .unwrap()
+            .get_tokens()
+            .map(|token| {
+                (
+                    token.get_dst_line(),
+                    token.get_dst_col(),
+                    token.get_src_line(),
+                    token.get_src_col(),
+                )
+            })
+            .collect::<Vec<_>>();
+        // `a` of the attribute value, `{b}`, `b`, `value`, `count`, `"x"`, and
+        // `x` rather than the `x` of the string, and the text and the `x` of
+        // the template literal
+        for token in [
+            (0, 10, 0, 10),
+            (0, 12, 0, 12),
+            (0, 13, 0, 13),
+            (1, 2, 1, 2),
+            (1, 10, 1, 12),
+            (2, 4, 2, 4),
+            (2, 9, 2, 10),
+            (3, 3, 3, 3),
+            (3, 7, 3, 7),
+        ] {
+            assert!(tokens.contains(&token), "{token:?} not in {tokens:?}");
+        }
+        assert!(!tokens.contains(&(3, 3, 3, 7)), "{tokens:?}");
+    }
+}
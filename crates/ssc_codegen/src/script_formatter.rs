@@ -0,0 +1,92 @@
+//! Delegation of `<script>` contents to an external formatter, e.g. Prettier
+//! or Biome, see [`Codegen::with_script_formatter`].
+//!
+//! [`Codegen::with_script_formatter`]: crate::Codegen::with_script_formatter
+
+use oxc_span::SourceType;
+
+/// Formats the contents of `<script>` elements instead of the codegen.
+///
+/// Implemented for closures with the signature of [`format_script`].
+///
+/// [`format_script`]: ScriptFormatter::format_script
+pub trait ScriptFormatter {
+    /// The formatted `source_text`, the contents of a `<script>` as written,
+    /// or `None` to print it with the codegen.
+    fn format_script(&self, source_text: &str, source_type: SourceType) -> Option<FormattedScript>;
+}
+
+impl<F> ScriptFormatter for F
+where
+    F: Fn(&str, SourceType) -> Option<FormattedScript>,
+{
+    fn format_script(&self, source_text: &str, source_type: SourceType) -> Option<FormattedScript> {
+        self(source_text, source_type)
+    }
+}
+
+/// The output of a [`ScriptFormatter`].
+pub struct FormattedScript {
+    pub source_text: String,
+    /// Maps `source_text` to the contents it was formatted from, to be
+    /// included in the source map of the component.
+    pub source_map: Option<oxc_sourcemap::SourceMap>,
+}
+
+impl FormattedScript {
+    pub fn new(source_text: String) -> Self {
+        Self { source_text, source_map: None }
+    }
+
+    /// The offsets in `source_text` of the tokens of the source map, with the
+    /// offsets in `original` they map to, by offset in `source_text`.
+    pub(crate) fn mappings(&self, original: &str) -> Vec<(usize, usize)> {
+        let Some(source_map) = &self.source_map else {
+            return vec![];
+        };
+        let mut mappings = source_map
+            .get_tokens()
+            .filter_map(|token| {
+                let generated =
+                    offset(&self.source_text, token.get_dst_line(), token.get_dst_col())?;
+                let original = offset(original, token.get_src_line(), token.get_src_col())?;
+                Some((generated, original))
+            })
+            .collect::<Vec<_>>();
+        mappings.sort_unstable();
+        mappings
+    }
+}
+
+/// The offset in `text` of the zero based `line` and UTF-16 `column`.
+fn offset(text: &str, line: u32, column: u32) -> Option<usize> {
+    let mut line_start = 0;
+    for _ in 0..line {
+        line_start += text[line_start..].find('\n')? + 1;
+    }
+    let mut utf16_column = 0;
+    for (index, c) in text[line_start..].char_indices() {
+        if utf16_column >= column as usize || c == '\n' {
+            return (utf16_column == column as usize).then_some(line_start + index);
+        }
+        utf16_column += c.len_utf16();
+    }
+    (utf16_column == column as usize).then_some(text.len())
+}
+
+#[cfg(test)]
+mod test {
+    use super::offset;
+
+    #[test]
+    fn offsets() {
+        let text = "a\nbé😀c\n";
+        assert_eq!(offset(text, 0, 1), Some(1));
+        assert_eq!(offset(text, 1, 1), Some(3));
+        assert_eq!(offset(text, 1, 2), Some(5));
+        assert_eq!(offset(text, 1, 4), Some(9));
+        assert_eq!(offset(text, 1, 3), None);
+        assert_eq!(offset(text, 2, 0), Some(11));
+        assert_eq!(offset(text, 3, 0), None);
+    }
+}
@@ -0,0 +1,88 @@
+//! Keyframes referenced by `animation` and `animation-name` declarations
+
+/// Keywords of the `animation` shorthand and CSS-wide keywords, which can't
+/// be keyframes names.
+const KEYWORDS: &[&str] = &[
+    "none",
+    "auto",
+    "initial",
+    "inherit",
+    "unset",
+    "revert",
+    "revert-layer",
+    "ease",
+    "ease-in",
+    "ease-out",
+    "ease-in-out",
+    "linear",
+    "step-start",
+    "step-end",
+    "infinite",
+    "normal",
+    "reverse",
+    "alternate",
+    "alternate-reverse",
+    "forwards",
+    "backwards",
+    "both",
+    "running",
+    "paused",
+];
+
+/// Whether `property` sets the keyframes of an animation.
+pub fn is_animation_property(property: &str) -> bool {
+    matches!(super::remove_css_prefix(property), "animation" | "animation-name")
+}
+
+/// The keyframes names set by an `animation` or `animation-name` declaration,
+/// one per animation at most.
+pub fn animation_names(value: &str) -> Vec<&str> {
+    split_top_level(value, |ch| ch == ',')
+        .into_iter()
+        .filter_map(|animation| {
+            split_top_level(animation, char::is_whitespace).into_iter().find_map(|token| {
+                let name = unquote(token).unwrap_or(token);
+                (is_identifier(name) || name != token).then_some(name)
+            })
+        })
+        .filter(|name| !KEYWORDS.contains(&name.to_ascii_lowercase().as_str()))
+        .collect()
+}
+
+/// Split `value` where `is_separator` matches outside of parentheses,
+/// dropping empty parts.
+fn split_top_level(value: &str, is_separator: fn(char) -> bool) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (index, ch) in value.char_indices() {
+        match ch {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ if depth == 0 && is_separator(ch) => {
+                parts.push(&value[start..index]);
+                start = index + ch.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&value[start..]);
+    parts.into_iter().map(str::trim).filter(|part| !part.is_empty()).collect()
+}
+
+fn unquote(token: &str) -> Option<&str> {
+    let quote = token.chars().next().filter(|ch| matches!(ch, '"' | '\''))?;
+    token[1..].strip_suffix(quote)
+}
+
+/// Whether `token` is a CSS identifier, rather than a number, time or
+/// function.
+fn is_identifier(token: &str) -> bool {
+    let mut chars = token.chars();
+    let first = match chars.next() {
+        Some('-') => chars.next(),
+        first => first,
+    };
+    first.is_some_and(|ch| ch.is_alphabetic() || ch == '_' || ch == '-' || !ch.is_ascii())
+        && token.chars().all(|ch| ch.is_alphanumeric() || matches!(ch, '-' | '_') || !ch.is_ascii())
+}
@@ -0,0 +1,52 @@
+//! Containers declared by `container` and `container-name` declarations, and
+//! referenced by `@container` queries
+
+/// Keywords that can't be container names.
+const KEYWORDS: &[&str] = &[
+    "none",
+    "and",
+    "not",
+    "or",
+    "initial",
+    "inherit",
+    "unset",
+    "revert",
+    "revert-layer",
+    "default",
+];
+
+/// Whether `property` names containers.
+pub fn is_container_property(property: &str) -> bool {
+    matches!(property, "container" | "container-name")
+}
+
+/// The container names set by a `container` or `container-name`
+/// declaration, `a b` in `container: a b / inline-size`.
+pub fn container_names<'a>(property: &str, value: &'a str) -> Vec<&'a str> {
+    let names = match property {
+        "container" => value.split('/').next().unwrap_or_default(),
+        _ => value,
+    };
+    names.split_whitespace().filter(|name| is_container_name(name)).collect()
+}
+
+/// The name of the container queried by the prelude of a `@container` rule,
+/// `sidebar` in `@container sidebar (min-width: 400px)`.
+pub fn queried_container(prelude: &str) -> Option<&str> {
+    let prelude = prelude.trim_start();
+    let len = prelude.find(|ch: char| ch.is_whitespace() || ch == '(').unwrap_or(prelude.len());
+    let (name, rest) = prelude.split_at(len);
+    // `style(...)` and `scroll-state(...)` are queries, not names
+    (is_container_name(name) && !rest.starts_with('(')).then_some(name)
+}
+
+fn is_container_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    let first = match chars.next() {
+        Some('-') => chars.next(),
+        first => first,
+    };
+    first.is_some_and(|ch| ch.is_alphabetic() || ch == '_' || ch == '-' || !ch.is_ascii())
+        && name.chars().all(|ch| ch.is_alphanumeric() || matches!(ch, '-' | '_') || !ch.is_ascii())
+        && !KEYWORDS.contains(&name.to_ascii_lowercase().as_str())
+}
@@ -49,3 +49,21 @@ pub fn invalid_global_block_declaration(span: Span) -> OxcDiagnostic {
     OxcDiagnostic::error("A :global {...} block can only contain rules, not declarations")
         .with_label(span)
 }
+
+pub fn unknown_keyframes(span: Span, name: &str) -> OxcDiagnostic {
+    OxcDiagnostic::warn(format!("No `@keyframes {name}` is declared in this component"))
+        .with_error_code("svelte", "css_unknown_keyframes")
+        .with_label(span)
+        .with_help(format!(
+            "If `{name}` is declared in a global stylesheet, reference it as `-global-{name}`"
+        ))
+}
+
+pub fn unknown_container(span: Span, name: &str) -> OxcDiagnostic {
+    OxcDiagnostic::warn(format!("No container named `{name}` is declared in this component"))
+        .with_error_code("svelte", "css_unknown_container")
+        .with_label(span)
+        .with_help(format!(
+            "Declare it with `container-name: {name}`, or ignore this warning if a parent component or a global stylesheet declares it"
+        ))
+}
@@ -1,5 +1,7 @@
 #![allow(clippy::cast_possible_truncation, clippy::cast_lossless, clippy::cast_sign_loss)]
 
+/// The hash of `input` the Svelte compiler uses, e.g. for the class scoping a
+/// component's styles.
 pub fn hash(input: &str) -> String {
     // Remove carriage return characters
     let input = input.replace('\r', "");
@@ -1,13 +1,15 @@
+mod animation;
+mod container;
 mod diagnostics;
 mod hash;
 pub mod node;
 
 use std::mem;
 
-use hash::hash;
+pub use hash::hash;
 use node::{AstNode, AstNodes};
 use oxc_diagnostics::{Error, OxcDiagnostic};
-use oxc_span::{Atom, GetSpan};
+use oxc_span::{Atom, GetSpan, Span};
 #[allow(clippy::wildcard_imports)]
 use ssc_css_ast::ast::*;
 use ssc_css_ast::{
@@ -18,6 +20,15 @@ use ssc_css_ast::{
 pub struct Analyzer<'a> {
     errors: Vec<OxcDiagnostic>,
     keyframes: Vec<Atom<'a>>,
+    /// Keyframes declared with the `-global-` prefix, without it.
+    global_keyframes: Vec<&'a str>,
+    /// Keyframes names referenced by animations, and the declarations
+    /// referencing them.
+    animations: Vec<(Span, &'a str)>,
+    /// Names of the containers declared by `container` and `container-name`.
+    containers: Vec<&'a str>,
+    /// Container names queried by `@container` rules, and the rules.
+    container_queries: Vec<(Span, &'a str)>,
     current_node_id: AstNodeId,
     nodes: AstNodes<'a>,
     block_stack: Vec<AstNodeId>,
@@ -41,6 +52,10 @@ impl<'a> Default for Analyzer<'a> {
         Self {
             errors: vec![],
             keyframes: vec![],
+            global_keyframes: vec![],
+            animations: vec![],
+            containers: vec![],
+            container_queries: vec![],
             current_node_id: AstNodeId::new(0),
             nodes: AstNodes::default(),
             block_stack: vec![],
@@ -86,6 +101,8 @@ impl<'a> Analyzer<'a> {
 
     pub fn build(mut self, stylesheet: &mut StyleSheet<'a>) -> AnalyzerReturn<'a> {
         self.visit_stylesheet(stylesheet);
+        self.check_animations();
+        self.check_container_queries();
         let errors = self.take_errors();
         AnalyzerReturn {
             analysis: Analysis {
@@ -110,12 +127,33 @@ impl<'a> Visit<'a> for Analyzer<'a> {
     }
 
     fn visit_at_rule(&mut self, rule: &AtRule<'a>) {
-        if is_keyframe_node(&rule.name) && !rule.prelude.starts_with("-global-") {
-            self.keyframes.push(rule.prelude.clone());
+        if is_keyframe_node(&rule.name) {
+            if let Some(name) = rule.prelude.as_str().strip_prefix("-global-") {
+                self.global_keyframes.push(name);
+            } else {
+                self.keyframes.push(rule.prelude.clone());
+            }
+        }
+        if rule.name == "container" {
+            if let Some(name) = container::queried_container(rule.prelude.as_str()) {
+                self.container_queries.push((rule.span, name));
+            }
         }
         walk_at_rule(self, rule);
     }
 
+    fn visit_declaration(&mut self, decl: &Declaration<'a>) {
+        if animation::is_animation_property(&decl.property) {
+            let value = decl.value.as_str();
+            self.animations.extend(
+                animation::animation_names(value).into_iter().map(|name| (decl.span, name)),
+            );
+        }
+        if container::is_container_property(&decl.property) {
+            self.containers.extend(container::container_names(&decl.property, decl.value.as_str()));
+        }
+    }
+
     fn visit_nesting_selector(&mut self, selector: &NestingSelector) {
         if self.block_stack.is_empty() {
             self.error(diagnostics::invalid_nesting_selector_placement(selector.span));
@@ -284,6 +322,28 @@ impl<'a> Visit<'a> for Analyzer<'a> {
 }
 
 impl<'a> Analyzer<'a> {
+    /// Warn on animations referencing keyframes the component doesn't declare.
+    fn check_animations(&mut self) {
+        for (span, name) in mem::take(&mut self.animations) {
+            let name = name.strip_prefix("-global-").unwrap_or(name);
+            let declared = self.keyframes.iter().any(|keyframes| keyframes.as_str() == name)
+                || self.global_keyframes.contains(&name);
+            if !declared {
+                self.error(diagnostics::unknown_keyframes(span, name));
+            }
+        }
+    }
+
+    /// Warn on `@container` queries of containers the component doesn't
+    /// declare.
+    fn check_container_queries(&mut self) {
+        for (span, name) in mem::take(&mut self.container_queries) {
+            if !self.containers.contains(&name) {
+                self.error(diagnostics::unknown_container(span, name));
+            }
+        }
+    }
+
     fn enter_kind(&mut self, kind: AstKind<'a>) {
         #[allow(clippy::single_match)]
         match kind {
@@ -330,13 +390,9 @@ fn is_global_like(selector: &RelativeSelector<'_>) -> bool {
             _ => false,
         }
     } else {
-        selector.selectors.iter().position(|selector| {
-            if let SimpleSelector::PseudoClassSelector(selector) = selector {
-                selector.name.as_str() == "root"
-            } else {
-                false
-            }
-        }) != Some(0)
+        selector.selectors.iter().any(|selector| {
+            matches!(selector, SimpleSelector::PseudoClassSelector(selector) if selector.name.as_str() == "root")
+        })
     }
 }
 
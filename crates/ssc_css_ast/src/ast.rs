@@ -147,7 +147,6 @@ pub struct AttributeSelector<'a> {
 
 #[derive(Debug)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Tsify))]
-#[cfg_attr(feature = "serialize", serde(untagged))]
 pub enum AttributeMatcher {
     #[cfg_attr(feature = "serialize", serde(rename = "~="))]
     /// `~=`
@@ -241,7 +240,6 @@ pub struct Combinator {
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Tsify))]
-#[cfg_attr(feature = "serialize", serde(untagged))]
 pub enum CombinatorKind {
     #[cfg_attr(feature = "serialize", serde(rename = "+"))]
     NextSibling,
@@ -303,6 +301,224 @@ pub struct Declaration<'a> {
     pub span: Span,
     pub property: Atom<'a>,
     pub value: Atom<'a>,
+    /// `value` as component values, without whitespace and comments.
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub components: Vec<'a, ComponentValue<'a>>,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Tsify))]
+#[cfg_attr(feature = "serialize", serde(tag = "type"))]
+pub enum ComponentValue<'a> {
+    Ident(Ident<'a>),
+    Number(Number<'a>),
+    Percentage(Percentage<'a>),
+    Dimension(Dimension<'a>),
+    Hash(Hash<'a>),
+    Operator(Operator),
+    Url(Url<'a>),
+    UnicodeRange(UnicodeRange),
+    Function(Function<'a>),
+    String(StringValue<'a>),
+    Token(Token<'a>),
+}
+
+/// `solid`, `-webkit-box` or `--gap`
+#[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Tsify))]
+#[cfg_attr(feature = "serialize", serde(tag = "type"))]
+pub struct Ident<'a> {
+    #[cfg_attr(feature = "serialize", serde(flatten))]
+    pub span: Span,
+    /// The identifier, as written.
+    pub name: Atom<'a>,
+}
+
+/// `1`, `-0.5` or `1e3`
+#[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Tsify))]
+#[cfg_attr(feature = "serialize", serde(tag = "type"))]
+pub struct Number<'a> {
+    #[cfg_attr(feature = "serialize", serde(flatten))]
+    pub span: Span,
+    pub value: f64,
+    /// The number, as written.
+    pub raw: Atom<'a>,
+}
+
+/// `50%`
+#[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Tsify))]
+#[cfg_attr(feature = "serialize", serde(tag = "type"))]
+pub struct Percentage<'a> {
+    #[cfg_attr(feature = "serialize", serde(flatten))]
+    pub span: Span,
+    pub value: f64,
+    /// The percentage, as written.
+    pub raw: Atom<'a>,
+}
+
+/// `1px` or `.5em`
+#[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Tsify))]
+#[cfg_attr(feature = "serialize", serde(tag = "type"))]
+pub struct Dimension<'a> {
+    #[cfg_attr(feature = "serialize", serde(flatten))]
+    pub span: Span,
+    pub value: f64,
+    pub unit: Atom<'a>,
+    /// The dimension, as written.
+    pub raw: Atom<'a>,
+}
+
+/// `#fff` or `#main`
+#[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Tsify))]
+#[cfg_attr(feature = "serialize", serde(tag = "type"))]
+pub struct Hash<'a> {
+    #[cfg_attr(feature = "serialize", serde(flatten))]
+    pub span: Span,
+    /// The name after the `#`.
+    pub value: Atom<'a>,
+}
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Tsify))]
+#[cfg_attr(feature = "serialize", serde(tag = "type"))]
+pub struct Operator {
+    #[cfg_attr(feature = "serialize", serde(flatten))]
+    pub span: Span,
+    pub kind: OperatorKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Tsify))]
+pub enum OperatorKind {
+    /// `,`
+    Comma,
+    /// `/`
+    Slash,
+    /// `*`
+    Asterisk,
+    /// `+`
+    Plus,
+    /// `-`
+    Minus,
+}
+
+impl OperatorKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Comma => ",",
+            Self::Slash => "/",
+            Self::Asterisk => "*",
+            Self::Plus => "+",
+            Self::Minus => "-",
+        }
+    }
+}
+
+/// `url(a.png)` or `url("a.png")`
+#[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Tsify))]
+#[cfg_attr(feature = "serialize", serde(tag = "type"))]
+pub struct Url<'a> {
+    #[cfg_attr(feature = "serialize", serde(flatten))]
+    pub span: Span,
+    /// The URL, unescaped.
+    pub value: Atom<'a>,
+    /// The quote of the URL, `None` if it's unquoted.
+    pub quote: Option<char>,
+}
+
+/// `U+0025-00FF` or `U+4??`, the code points from `start` to `end`
+/// inclusive.
+#[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Tsify))]
+#[cfg_attr(feature = "serialize", serde(tag = "type"))]
+pub struct UnicodeRange {
+    #[cfg_attr(feature = "serialize", serde(flatten))]
+    pub span: Span,
+    pub start: u32,
+    pub end: u32,
+}
+
+/// `name(arguments)`, e.g. `rgb(0 0 0 / 50%)`. Parentheses without a name
+/// have an empty one.
+#[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Tsify))]
+#[cfg_attr(feature = "serialize", serde(tag = "type"))]
+pub struct Function<'a> {
+    #[cfg_attr(feature = "serialize", serde(flatten))]
+    pub span: Span,
+    pub name: Atom<'a>,
+    pub arguments: Vec<'a, ComponentValue<'a>>,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Tsify))]
+#[cfg_attr(feature = "serialize", serde(tag = "type"))]
+pub struct StringValue<'a> {
+    #[cfg_attr(feature = "serialize", serde(flatten))]
+    pub span: Span,
+    /// The string, unescaped.
+    pub value: Atom<'a>,
+    pub quote: char,
+}
+
+/// Any other token, as written, e.g. `!important`.
+#[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Tsify))]
+#[cfg_attr(feature = "serialize", serde(tag = "type"))]
+pub struct Token<'a> {
+    #[cfg_attr(feature = "serialize", serde(flatten))]
+    pub span: Span,
+    pub value: Atom<'a>,
+}
+
+impl<'a> Declaration<'a> {
+    /// The component values of the value, including those in function
+    /// arguments, in source order.
+    pub fn all_components(&self) -> impl Iterator<Item = &ComponentValue<'a>> {
+        let mut stack = self.components.iter().rev().collect::<std::vec::Vec<_>>();
+        std::iter::from_fn(move || {
+            let component = stack.pop()?;
+            if let ComponentValue::Function(function) = component {
+                stack.extend(function.arguments.iter().rev());
+            }
+            Some(component)
+        })
+    }
+
+    /// The `url()`s of the value, including those in function arguments,
+    /// e.g. of `image-set()`.
+    pub fn urls(&self) -> impl Iterator<Item = &Url<'a>> {
+        self.all_components().filter_map(|component| match component {
+            ComponentValue::Url(url) => Some(url),
+            _ => None,
+        })
+    }
+
+    /// Whether the declaration sets a custom property, e.g. `--gap: 1px`.
+    pub fn is_custom_property(&self) -> bool {
+        self.property.starts_with("--")
+    }
+
+    /// The custom properties the value reads with `var()`, e.g. `--gap` for
+    /// `calc(var(--gap) * 2)`.
+    pub fn custom_property_references(&self) -> impl Iterator<Item = &Ident<'a>> {
+        self.all_components().filter_map(|component| match component {
+            ComponentValue::Function(function) if function.name.eq_ignore_ascii_case("var") => {
+                match function.arguments.first() {
+                    Some(ComponentValue::Ident(ident)) if ident.name.starts_with("--") => {
+                        Some(ident)
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+    }
 }
 
 define_index_type! {
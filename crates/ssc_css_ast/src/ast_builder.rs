@@ -104,8 +104,80 @@ impl<'a> AstBuilder<'a> {
     }
 
     #[inline]
-    pub fn declaration(&self, span: Span, property: Atom<'a>, value: Atom<'a>) -> Declaration<'a> {
-        Declaration { span, property, value }
+    pub fn declaration(
+        &self,
+        span: Span,
+        property: Atom<'a>,
+        value: Atom<'a>,
+        components: Vec<'a, ComponentValue<'a>>,
+    ) -> Declaration<'a> {
+        Declaration { span, property, value, components }
+    }
+
+    #[inline]
+    pub fn ident(&self, span: Span, name: Atom<'a>) -> ComponentValue<'a> {
+        ComponentValue::Ident(Ident { span, name })
+    }
+
+    #[inline]
+    pub fn number(&self, span: Span, value: f64, raw: Atom<'a>) -> ComponentValue<'a> {
+        ComponentValue::Number(Number { span, value, raw })
+    }
+
+    #[inline]
+    pub fn percentage(&self, span: Span, value: f64, raw: Atom<'a>) -> ComponentValue<'a> {
+        ComponentValue::Percentage(Percentage { span, value, raw })
+    }
+
+    #[inline]
+    pub fn dimension(
+        &self,
+        span: Span,
+        value: f64,
+        unit: Atom<'a>,
+        raw: Atom<'a>,
+    ) -> ComponentValue<'a> {
+        ComponentValue::Dimension(Dimension { span, value, unit, raw })
+    }
+
+    #[inline]
+    pub fn hash(&self, span: Span, value: Atom<'a>) -> ComponentValue<'a> {
+        ComponentValue::Hash(Hash { span, value })
+    }
+
+    #[inline]
+    pub fn operator(&self, span: Span, kind: OperatorKind) -> ComponentValue<'a> {
+        ComponentValue::Operator(Operator { span, kind })
+    }
+
+    #[inline]
+    pub fn url(&self, span: Span, value: Atom<'a>, quote: Option<char>) -> ComponentValue<'a> {
+        ComponentValue::Url(Url { span, value, quote })
+    }
+
+    #[inline]
+    pub fn unicode_range(&self, span: Span, start: u32, end: u32) -> ComponentValue<'a> {
+        ComponentValue::UnicodeRange(UnicodeRange { span, start, end })
+    }
+
+    #[inline]
+    pub fn function(
+        &self,
+        span: Span,
+        name: Atom<'a>,
+        arguments: Vec<'a, ComponentValue<'a>>,
+    ) -> ComponentValue<'a> {
+        ComponentValue::Function(Function { span, name, arguments })
+    }
+
+    #[inline]
+    pub fn string_value(&self, span: Span, value: Atom<'a>, quote: char) -> ComponentValue<'a> {
+        ComponentValue::String(StringValue { span, value, quote })
+    }
+
+    #[inline]
+    pub fn token(&self, span: Span, value: Atom<'a>) -> ComponentValue<'a> {
+        ComponentValue::Token(Token { span, value })
     }
 
     #[inline]
@@ -36,3 +36,21 @@ impl<'a> GetSpan for Rule<'a> {
         }
     }
 }
+
+impl<'a> GetSpan for ComponentValue<'a> {
+    fn span(&self) -> Span {
+        match self {
+            Self::Ident(ident) => ident.span,
+            Self::Number(number) => number.span,
+            Self::Percentage(percentage) => percentage.span,
+            Self::Dimension(dimension) => dimension.span,
+            Self::Hash(hash) => hash.span,
+            Self::Operator(operator) => operator.span,
+            Self::Url(url) => url.span,
+            Self::UnicodeRange(range) => range.span,
+            Self::Function(function) => function.span,
+            Self::String(string) => string.span,
+            Self::Token(token) => token.span,
+        }
+    }
+}
@@ -60,6 +60,10 @@ pub trait Visit<'a>: Sized {
         walk_declaration(self, decl);
     }
 
+    fn visit_component_value(&mut self, component: &ComponentValue<'a>) {
+        walk_component_value(self, component);
+    }
+
     /* ----------  Selector ---------- */
 
     fn visit_selector_list(&mut self, selector_list: &SelectorList<'a>) {
@@ -183,9 +187,20 @@ pub mod walk {
     pub fn walk_declaration<'a, V: Visit<'a>>(visitor: &mut V, decl: &Declaration<'a>) {
         let kind = AstKind::Declaration(visitor.alloc(decl));
         visitor.enter_node(kind);
+        for component in &decl.components {
+            visitor.visit_component_value(component);
+        }
         visitor.leave_node(kind);
     }
 
+    pub fn walk_component_value<'a, V: Visit<'a>>(visitor: &mut V, component: &ComponentValue<'a>) {
+        if let ComponentValue::Function(function) = component {
+            for argument in &function.arguments {
+                visitor.visit_component_value(argument);
+            }
+        }
+    }
+
     /* ----------  Selector ---------- */
 
     pub fn walk_selector_list<'a, V: Visit<'a>>(visitor: &mut V, selector_list: &SelectorList<'a>) {
@@ -47,6 +47,10 @@ pub trait VisitMut<'a>: Sized {
         walk_declaration_mut(self, decl);
     }
 
+    fn visit_component_value(&mut self, component: &mut ComponentValue<'a>) {
+        walk_component_value_mut(self, component);
+    }
+
     /* ----------  Selector ---------- */
 
     fn visit_selector_list(&mut self, selector_list: &mut SelectorList<'a>) {
@@ -170,12 +174,26 @@ pub mod walk_mut {
         }
     }
 
-    pub fn walk_declaration_mut<'a, V: VisitMut<'a>>(visitor: &mut V, _decl: &mut Declaration<'a>) {
+    pub fn walk_declaration_mut<'a, V: VisitMut<'a>>(visitor: &mut V, decl: &mut Declaration<'a>) {
         let kind = AstType::Declaration;
         visitor.enter_node(kind);
+        for component in decl.components.iter_mut() {
+            visitor.visit_component_value(component);
+        }
         visitor.leave_node(kind);
     }
 
+    pub fn walk_component_value_mut<'a, V: VisitMut<'a>>(
+        visitor: &mut V,
+        component: &mut ComponentValue<'a>,
+    ) {
+        if let ComponentValue::Function(function) = component {
+            for argument in function.arguments.iter_mut() {
+                visitor.visit_component_value(argument);
+            }
+        }
+    }
+
     /* ----------  Selector ---------- */
 
     pub fn walk_selector_list_mut<'a, V: VisitMut<'a>>(
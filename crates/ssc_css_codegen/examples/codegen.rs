@@ -27,7 +27,7 @@ fn main() -> std::io::Result<()> {
     println!("Original:");
     println!("{source_text}");
 
-    let options = CodegenOptions { enable_source_map: false };
+    let options = CodegenOptions::default();
     let printed =
         Codegen::<false>::new("", &source_text, options.clone()).build(&ret.stylesheet).source_text;
     println!("Printed:");
@@ -25,7 +25,7 @@ fn main() -> std::io::Result<()> {
         return Ok(());
     }
 
-    let codegen_options = CodegenOptions { enable_source_map: true };
+    let codegen_options = CodegenOptions { enable_source_map: true, ..CodegenOptions::default() };
 
     let CodegenReturn { source_text, source_map } =
         Codegen::<false>::new(path.to_string_lossy().as_ref(), &source_text, codegen_options)
@@ -1,8 +1,9 @@
 use oxc_allocator::Box;
+use oxc_span::GetSpan;
 #[allow(clippy::wildcard_imports)]
 use ssc_css_ast::ast::*;
 
-use super::Codegen;
+use super::{value, Codegen, QuoteStyle};
 
 pub trait Gen<const MINIFY: bool> {
     fn gen(&self, _p: &mut Codegen<{ MINIFY }>) {}
@@ -96,11 +97,116 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for Declaration<'a> {
         p.print_str(self.property.as_bytes());
         p.print(b':');
         p.print_soft_space();
-        p.print_str(self.value.as_bytes());
+        if p.options.normalizes_values() && !self.components.is_empty() {
+            gen_component_values(&self.components, p);
+        } else {
+            p.print_str(self.value.as_bytes());
+        }
         p.print_semicolon();
     }
 }
 
+/// Print `components` with a space between those separated in the source,
+/// except around `,`, `/` and `*` when minifying.
+fn gen_component_values<const MINIFY: bool>(
+    components: &[ComponentValue<'_>],
+    p: &mut Codegen<{ MINIFY }>,
+) {
+    let is_delimiter = |component: &ComponentValue| {
+        matches!(
+            component,
+            ComponentValue::Operator(Operator {
+                kind: OperatorKind::Comma | OperatorKind::Slash | OperatorKind::Asterisk,
+                ..
+            })
+        )
+    };
+    let mut prev: Option<&ComponentValue> = None;
+    for component in components {
+        if let Some(prev) = prev {
+            let is_separated = prev.span().end != component.span().start;
+            if is_separated && !(MINIFY && (is_delimiter(prev) || is_delimiter(component))) {
+                p.print_hard_space();
+            }
+        }
+        component.gen(p);
+        prev = Some(component);
+    }
+}
+
+impl<'a, const MINIFY: bool> Gen<MINIFY> for ComponentValue<'a> {
+    fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
+        match self {
+            Self::Ident(ident) => p.print_str(ident.name.as_bytes()),
+            Self::Number(Number { raw, .. })
+            | Self::Percentage(Percentage { raw, .. })
+            | Self::Dimension(Dimension { raw, .. }) => {
+                let normalized =
+                    p.options.normalize_numbers.then(|| value::normalize_number(raw, MINIFY));
+                p.print_str(normalized.flatten().as_deref().unwrap_or(raw).as_bytes());
+            }
+            Self::Hash(hash) => {
+                p.print(b'#');
+                let is_color = matches!(hash.value.len(), 3 | 4 | 6 | 8)
+                    && hash.value.bytes().all(|byte| byte.is_ascii_hexdigit());
+                if is_color && p.options.lowercase_hex_colors {
+                    p.print_str(hash.value.to_ascii_lowercase().as_bytes());
+                } else {
+                    p.print_str(hash.value.as_bytes());
+                }
+            }
+            Self::Operator(operator) => p.print_str(operator.kind.as_str().as_bytes()),
+            Self::Url(url) => url.gen(p),
+            Self::UnicodeRange(range) => range.gen(p),
+            Self::Function(function) => function.gen(p),
+            Self::String(string) => {
+                let quote = p.options.quote_style.map_or(string.quote, QuoteStyle::as_char);
+                p.print_str(value::quote_string(&string.value, quote).as_bytes());
+            }
+            Self::Token(token) => token.gen(p),
+        }
+    }
+}
+
+impl<'a, const MINIFY: bool> Gen<MINIFY> for Url<'a> {
+    fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
+        p.print_str(b"url(");
+        if let Some(quote) = self.quote {
+            let quote = p.options.quote_style.map_or(quote, QuoteStyle::as_char);
+            p.print_str(value::quote_string(&self.value, quote).as_bytes());
+        } else {
+            p.print_str(value::escape_url(&self.value).as_bytes());
+        }
+        p.print(b')');
+    }
+}
+
+impl<const MINIFY: bool> Gen<MINIFY> for UnicodeRange {
+    fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
+        let range = if self.start == self.end {
+            format!("U+{:X}", self.start)
+        } else {
+            format!("U+{:X}-{:X}", self.start, self.end)
+        };
+        p.print_str(range.as_bytes());
+    }
+}
+
+impl<'a, const MINIFY: bool> Gen<MINIFY> for Function<'a> {
+    fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
+        p.print_str(self.name.as_bytes());
+        p.print(b'(');
+        gen_component_values(&self.arguments, p);
+        p.print(b')');
+    }
+}
+
+impl<'a, const MINIFY: bool> Gen<MINIFY> for Token<'a> {
+    fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
+        p.print_str(self.value.as_bytes());
+    }
+}
+
 impl<'a, const MINIFY: bool> Gen<MINIFY> for SelectorList<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>) {
         let len = self.children.len();
@@ -4,12 +4,14 @@
 //!
 //! * whitespace removal
 //! * sourcemaps
+//! * value normalization
 //!
 //! Code adapted from
 //! * [oxc](https://github.com/oxc-project/oxc/blob/main/crates/oxc_codegen/src/lib.rs)
 
 mod gen;
 mod sourcemap_builder;
+mod value;
 
 use sourcemap_builder::SourcemapBuilder;
 #[allow(clippy::wildcard_imports)]
@@ -21,6 +23,41 @@ pub use crate::gen::Gen;
 pub struct CodegenOptions {
     /// Pass in the filename to enable source map support.
     pub enable_source_map: bool,
+
+    /// Print the hex colors of declaration values in lowercase, e.g. `#FFF`
+    /// as `#fff`.
+    pub lowercase_hex_colors: bool,
+
+    /// Print the numbers of declaration values without redundant zeros, e.g.
+    /// `00.50px` as `0.5px`, or `.5px` when minifying.
+    pub normalize_numbers: bool,
+
+    /// Quote the strings and `url()`s of declaration values with, instead of
+    /// their own quotes. Unquoted `url()`s stay unquoted.
+    pub quote_style: Option<QuoteStyle>,
+}
+
+impl CodegenOptions {
+    /// Whether declaration values are printed from their component values,
+    /// rather than as written. Comments within them are dropped then.
+    fn normalizes_values(&self) -> bool {
+        self.lowercase_hex_colors || self.normalize_numbers || self.quote_style.is_some()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteStyle {
+    Double,
+    Single,
+}
+
+impl QuoteStyle {
+    fn as_char(self) -> char {
+        match self {
+            Self::Double => '"',
+            Self::Single => '\'',
+        }
+    }
 }
 
 pub struct CodegenReturn {
@@ -29,7 +66,6 @@ pub struct CodegenReturn {
 }
 
 pub struct Codegen<const MINIFY: bool> {
-    #[allow(unused)]
     options: CodegenOptions,
 
     /// Output Code
@@ -0,0 +1,154 @@
+//! Normalization of declaration values, see [`CodegenOptions`].
+//!
+//! [`CodegenOptions`]: crate::CodegenOptions
+
+use std::borrow::Cow;
+
+/// `token` without redundant zeros if it's a number, a percentage or a
+/// dimension, e.g. `00.50px` as `0.5px`. The zero before the decimal point of
+/// numbers below one is kept, or removed when `minify`.
+pub fn normalize_number(token: &str, minify: bool) -> Option<String> {
+    let (sign, rest) = match token.as_bytes().first()? {
+        b'+' | b'-' => token.split_at(1),
+        _ => ("", token),
+    };
+    let integer_len = rest.bytes().take_while(u8::is_ascii_digit).count();
+    let (integer, rest) = rest.split_at(integer_len);
+    let (fraction, unit) = match rest.strip_prefix('.') {
+        Some(rest) => {
+            let fraction_len = rest.bytes().take_while(u8::is_ascii_digit).count();
+            if fraction_len == 0 {
+                return None;
+            }
+            rest.split_at(fraction_len)
+        }
+        None => ("", rest),
+    };
+    if integer.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    // Units start with a letter, and exponents, e.g. `1e3`, aren't normalized
+    let mut unit_chars = unit.chars();
+    match (unit_chars.next(), unit_chars.next()) {
+        (None | Some('%'), _) => {}
+        (Some('e' | 'E'), Some(c)) if c.is_ascii_digit() || c == '+' || c == '-' => return None,
+        (Some(c), _) if c.is_alphabetic() || c == '_' || c == '\\' || !c.is_ascii() => {}
+        _ => return None,
+    }
+
+    let integer = integer.trim_start_matches('0');
+    let fraction = fraction.trim_end_matches('0');
+    let mut number = String::with_capacity(token.len());
+    number.push_str(sign);
+    match (integer, fraction) {
+        ("", "") => number.push('0'),
+        (integer, "") => number.push_str(integer),
+        (integer, fraction) => {
+            if integer.is_empty() && !minify {
+                number.push('0');
+            }
+            number.push_str(integer);
+            number.push('.');
+            number.push_str(fraction);
+        }
+    }
+    number.push_str(unit);
+    Some(number)
+}
+
+/// `value` as a CSS string quoted with `quote`.
+pub fn quote_string(value: &str, quote: char) -> String {
+    let mut string = String::with_capacity(value.len() + 2);
+    string.push(quote);
+    for c in value.chars() {
+        match c {
+            '\\' => string.push_str("\\\\"),
+            '\n' => string.push_str("\\a "),
+            c if c == quote => {
+                string.push('\\');
+                string.push(c);
+            }
+            c => string.push(c),
+        }
+    }
+    string.push(quote);
+    string
+}
+
+/// `value` escaped for an unquoted `url()`.
+pub fn escape_url(value: &str) -> Cow<'_, str> {
+    let needs_escape = |c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '(' | ')' | '\\');
+    if !value.contains(needs_escape) {
+        return Cow::Borrowed(value);
+    }
+    let mut url = String::with_capacity(value.len() + 4);
+    for c in value.chars() {
+        if c.is_whitespace() {
+            url.push_str(&format!("\\{:x} ", c as u32));
+        } else {
+            if needs_escape(c) {
+                url.push('\\');
+            }
+            url.push(c);
+        }
+    }
+    Cow::Owned(url)
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use ssc_css_parser::Parser;
+
+    use super::*;
+    use crate::{Codegen, CodegenOptions, QuoteStyle};
+
+    fn print<const MINIFY: bool>(source: &str, options: CodegenOptions) -> String {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        Codegen::<MINIFY>::new("", source, options).build(&ret.stylesheet).source_text
+    }
+
+    #[test]
+    fn declarations() {
+        let source = "p { color: #FFF; margin: 00.50px .5em 1.0px; background: url( 'a b.png' ) , url(c.png); content: \"it's\"; font: 12px/1.5 serif; }";
+        let options = CodegenOptions {
+            lowercase_hex_colors: true,
+            normalize_numbers: true,
+            quote_style: Some(QuoteStyle::Single),
+            ..CodegenOptions::default()
+        };
+        assert_eq!(
+            print::<false>(source, options.clone()),
+            "p {\n\tcolor: #fff;\n\tmargin: 0.5px 0.5em 1px;\n\tbackground: url('a b.png') , url(c.png);\n\tcontent: 'it\\'s';\n\tfont: 12px/1.5 serif;\n}"
+        );
+        assert_eq!(
+            print::<true>(source, options),
+            "p{color:#fff;margin:.5px .5em 1px;background:url('a b.png'),url(c.png);content:'it\\'s';font:12px/1.5 serif;}"
+        );
+        // Values are printed as written by default
+        assert!(print::<false>(source, CodegenOptions::default())
+            .contains("margin: 00.50px .5em 1.0px;"));
+    }
+
+    #[test]
+    fn normalize() {
+        let number = normalize_number;
+        assert_eq!(number("00.50px", false).as_deref(), Some("0.5px"));
+        assert_eq!(number(".5em", false).as_deref(), Some("0.5em"));
+        assert_eq!(number("0.5em", true).as_deref(), Some(".5em"));
+        assert_eq!(number("-1.0", false).as_deref(), Some("-1"));
+        assert_eq!(number("100%", false).as_deref(), Some("100%"));
+        assert_eq!(number("0.000", true).as_deref(), Some("0"));
+        // Container query units are kept
+        assert_eq!(number("0.50cqi", true).as_deref(), Some(".5cqi"));
+        assert_eq!(number("0cqmin", true).as_deref(), Some("0cqmin"));
+        assert_eq!(number("1e3", false), None);
+        assert_eq!(number("h1", false), None);
+        assert_eq!(number("1.2.3", false), None);
+
+        assert_eq!(quote_string("it's \"a\"", '"'), r#""it's \"a\"""#);
+        assert_eq!(escape_url("a b(c).png"), r"a\20 b\(c\).png");
+    }
+}
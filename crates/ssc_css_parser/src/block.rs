@@ -1,7 +1,8 @@
 use oxc_diagnostics::Result;
+use oxc_span::{Atom, Span};
 use ssc_css_ast::ast::*;
 
-use crate::{Kind, ParserImpl};
+use crate::{diagnostics, Kind, ParserImpl};
 
 impl<'a> ParserImpl<'a> {
     pub(crate) fn parse_block(&mut self) -> Result<Block<'a>> {
@@ -42,14 +43,59 @@ impl<'a> ParserImpl<'a> {
     fn parse_declaration(&mut self) -> Result<Declaration<'a>> {
         let span = self.start_span();
 
+        // The `*property` hack, `_property` is lexed as an identifier
+        let is_star_hack = self.eat(Kind::Star);
         let property = self.parse_identifier()?;
+        let property_span = Span::new(span.start, self.prev_token_end);
+        let property = if is_star_hack {
+            Atom::from(&self.source_text[property_span.start as usize..property_span.end as usize])
+        } else {
+            property
+        };
+        if is_star_hack || property.starts_with('_') {
+            self.error(diagnostics::property_hack(&property, property_span));
+        }
         self.expect(Kind::Colon)?;
-        let value = self.parse_value()?;
+        let value_span = self.parse_value_span()?;
+        let value =
+            Atom::from(&self.source_text[value_span.start as usize..value_span.end as usize]);
+        self.check_value_hacks(&value, value_span.start);
+        let components = self.parse_component_values(value_span);
 
         if !self.at(Kind::RCurly) {
             self.expect(Kind::Semicolon)?;
         }
 
-        Ok(self.ast.declaration(self.end_span(span), property, value))
+        Ok(self.ast.declaration(self.end_span(span), property, value, components))
+    }
+
+    /// Warn about the Internet Explorer hacks in the declaration value
+    /// `value`, which starts at `start`.
+    fn check_value_hacks(&mut self, value: &str, start: u32) {
+        let span = |offset: usize, len: usize| {
+            #[allow(clippy::cast_possible_truncation)]
+            let start = start + offset as u32;
+            #[allow(clippy::cast_possible_truncation)]
+            Span::new(start, start + len as u32)
+        };
+        let lowercase = value.to_ascii_lowercase();
+        for hack in ["progid:", "expression(", "!ie"] {
+            if let Some(offset) = lowercase.find(hack) {
+                let len = if hack == "expression(" { hack.len() - 1 } else { hack.len() };
+                self.error(diagnostics::value_hack(
+                    &value[offset..offset + len],
+                    span(offset, len),
+                ));
+            }
+        }
+        // `\9`, `\0` or `\0/` at the end of the value, before `!important`
+        let end = lowercase.strip_suffix("!important").map_or(value.len(), str::len);
+        let value = value[..end].trim_end();
+        if let Some(offset) = value.trim_end_matches('/').rfind('\\') {
+            let suffix = &value[offset..];
+            if matches!(suffix, "\\9" | "\\0" | "\\0/") {
+                self.error(diagnostics::value_hack(suffix, span(offset, suffix.len())));
+            }
+        }
     }
 }
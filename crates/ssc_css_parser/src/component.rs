@@ -0,0 +1,464 @@
+//! Component values of declarations
+//!
+//! The value of a declaration is skipped by the parser, and split into
+//! component values from its source text afterwards. Component values never
+//! fail to parse: anything malformed is kept as a token.
+
+#![allow(clippy::cast_possible_truncation)]
+
+use oxc_allocator::{Allocator, String, Vec};
+use oxc_span::{Atom, Span};
+use ssc_css_ast::{
+    ast::{ComponentValue, OperatorKind},
+    AstBuilder,
+};
+
+use crate::ParserImpl;
+
+impl<'a> ParserImpl<'a> {
+    /// Parse the value at `span` into component values.
+    pub(crate) fn parse_component_values(&self, span: Span) -> Vec<'a, ComponentValue<'a>> {
+        let mut parser = ComponentParser {
+            ast: &self.ast,
+            source_text: self.source_text,
+            offset: 0,
+            pos: span.start as usize,
+            end: span.end as usize,
+        };
+        parser.parse_list(false)
+    }
+}
+
+/// Parse the declaration value `value` into component values, e.g. for a
+/// declaration built without the parser. Their spans start at `start`, the
+/// offset of `value` in the source text.
+pub fn parse_component_values<'a>(
+    allocator: &'a Allocator,
+    value: &'a str,
+    start: u32,
+) -> Vec<'a, ComponentValue<'a>> {
+    let ast = AstBuilder::new(allocator);
+    let mut parser =
+        ComponentParser { ast: &ast, source_text: value, offset: start, pos: 0, end: value.len() };
+    parser.parse_list(false)
+}
+
+struct ComponentParser<'p, 'a> {
+    ast: &'p AstBuilder<'a>,
+    source_text: &'a str,
+    /// Added to the positions in `source_text` for the spans.
+    offset: u32,
+    pos: usize,
+    end: usize,
+}
+
+impl<'p, 'a> ComponentParser<'p, 'a> {
+    fn peek(&self) -> Option<char> {
+        self.source_text[self.pos..self.end].chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let matched = self.peek() == Some(c);
+        if matched {
+            self.pos += c.len_utf8();
+        }
+        matched
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn span(&self, start: usize) -> Span {
+        Span::new(self.offset + start as u32, self.offset + self.pos as u32)
+    }
+
+    fn atom(&self, start: usize) -> Atom<'a> {
+        Atom::from(&self.source_text[start..self.pos])
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    fn skip_whitespace_and_comments(&mut self) {
+        loop {
+            self.skip_whitespace();
+            if !self.source_text[self.pos..self.end].starts_with("/*") {
+                return;
+            }
+            let comment = &self.source_text[self.pos + 2..self.end];
+            self.pos = comment.find("*/").map_or(self.end, |end| self.pos + 2 + end + 2);
+        }
+    }
+
+    /// Parse component values up to the end, or up to and including the `)`
+    /// closing a function when `in_function`.
+    fn parse_list(&mut self, in_function: bool) -> Vec<'a, ComponentValue<'a>> {
+        let mut components = self.ast.new_vec();
+        loop {
+            self.skip_whitespace_and_comments();
+            let start = self.pos;
+            match self.peek() {
+                None => break,
+                Some(')') if in_function => {
+                    self.bump();
+                    break;
+                }
+                // Unbalanced
+                Some(')') => {
+                    self.bump();
+                    components.push(self.ast.token(self.span(start), self.atom(start)));
+                }
+                Some(_) => components.push(self.parse_component()),
+            }
+        }
+        components
+    }
+
+    fn parse_component(&mut self) -> ComponentValue<'a> {
+        let start = self.pos;
+        match self.peek() {
+            Some(quote @ ('"' | '\'')) => {
+                let value = self.parse_string(quote);
+                return self.ast.string_value(self.span(start), value, quote);
+            }
+            Some('(') => {
+                self.bump();
+                let arguments = self.parse_list(true);
+                return self.ast.function(self.span(start), Atom::from(""), arguments);
+            }
+            Some(c @ (',' | '/' | '*' | '+' | '-'))
+                if !self.starts_number() && !self.starts_ident() =>
+            {
+                self.bump();
+                let kind = match c {
+                    ',' => OperatorKind::Comma,
+                    '/' => OperatorKind::Slash,
+                    '*' => OperatorKind::Asterisk,
+                    '+' => OperatorKind::Plus,
+                    _ => OperatorKind::Minus,
+                };
+                return self.ast.operator(self.span(start), kind);
+            }
+            _ => {}
+        }
+        if let Some(range) = self.parse_unicode_range() {
+            return range;
+        }
+        if self.starts_number() {
+            if let Some(numeric) = self.parse_numeric() {
+                return numeric;
+            }
+        } else if self.eat('#') {
+            let name_start = self.pos;
+            self.skip_name();
+            if self.pos > name_start && self.at_boundary() {
+                return self.ast.hash(self.span(start), self.atom(name_start));
+            }
+        } else if self.starts_ident() {
+            self.skip_name();
+            let name = self.atom(start);
+            if self.eat('(') {
+                if name.eq_ignore_ascii_case("url") {
+                    if let Some(url) = self.parse_url(start) {
+                        return url;
+                    }
+                }
+                let arguments = self.parse_list(true);
+                return self.ast.function(self.span(start), name, arguments);
+            }
+            if self.at_boundary() {
+                return self.ast.ident(self.span(start), name);
+            }
+        }
+        // Anything else, up to a delimiter
+        self.pos = start;
+        self.skip_word();
+        self.ast.token(self.span(start), self.atom(start))
+    }
+
+    /// A number, a percentage or a dimension, `None` if it isn't followed by
+    /// a delimiter, e.g. `1.2.3`.
+    fn parse_numeric(&mut self) -> Option<ComponentValue<'a>> {
+        let start = self.pos;
+        if !self.eat('+') {
+            self.eat('-');
+        }
+        self.skip_digits();
+        let before_fraction = self.pos;
+        if self.eat('.') {
+            if self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                self.skip_digits();
+            } else {
+                self.pos = before_fraction;
+            }
+        }
+        // `1e3` or `1e-3`, but not `1em`
+        let rest = &self.source_text[self.pos..self.end];
+        let exponent = rest.strip_prefix(['e', 'E']).and_then(|rest| {
+            let sign_len = usize::from(rest.starts_with(['+', '-']));
+            rest[sign_len..].starts_with(|c: char| c.is_ascii_digit()).then_some(1 + sign_len)
+        });
+        if let Some(len) = exponent {
+            self.pos += len;
+            self.skip_digits();
+        }
+        let number = &self.source_text[start..self.pos];
+        let value = number.parse::<f64>().ok()?;
+
+        let component = if self.eat('%') {
+            self.ast.percentage(self.span(start), value, self.atom(start))
+        } else if self.starts_ident() {
+            let unit_start = self.pos;
+            self.skip_name();
+            let unit = self.atom(unit_start);
+            self.ast.dimension(self.span(start), value, unit, self.atom(start))
+        } else {
+            self.ast.number(self.span(start), value, self.atom(start))
+        };
+        self.at_boundary().then_some(component)
+    }
+
+    fn skip_digits(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.bump();
+        }
+    }
+
+    fn starts_number(&self) -> bool {
+        let mut chars = self.source_text[self.pos..self.end].chars();
+        let mut c = chars.next();
+        if matches!(c, Some('+' | '-')) {
+            c = chars.next();
+        }
+        match c {
+            Some('0'..='9') => true,
+            Some('.') => chars.next().is_some_and(|c| c.is_ascii_digit()),
+            _ => false,
+        }
+    }
+
+    fn starts_ident(&self) -> bool {
+        let mut chars = self.source_text[self.pos..self.end].chars();
+        let mut c = chars.next();
+        if c == Some('-') {
+            c = chars.next();
+            if c == Some('-') {
+                return true;
+            }
+        }
+        c.is_some_and(|c| is_name_start(c) || c == '\\')
+    }
+
+    /// Skip the name characters, and escapes, of an identifier or a unit.
+    fn skip_name(&mut self) {
+        while let Some(c) = self.peek() {
+            if c == '\\' {
+                self.bump();
+                self.bump();
+            } else if is_name_start(c) || c.is_ascii_digit() || c == '-' {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Whether the current component ends here.
+    fn at_boundary(&self) -> bool {
+        self.peek().map_or(true, |c| {
+            c.is_whitespace() || matches!(c, '"' | '\'' | '(' | ')' | ',' | '/' | '*' | '!')
+        })
+    }
+
+    /// Skip a run of characters up to a delimiter.
+    fn skip_word(&mut self) {
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() || matches!(c, '"' | '\'' | '(' | ')' | ',' | '/' | '*') {
+                break;
+            }
+            self.bump();
+            if c == '\\' {
+                self.bump();
+            }
+        }
+    }
+
+    /// `U+0025-00FF`, `U+4??` or `U+26`.
+    fn parse_unicode_range(&mut self) -> Option<ComponentValue<'a>> {
+        let start = self.pos;
+        let rest = &self.source_text[self.pos..self.end];
+        if !rest.starts_with(['u', 'U']) || !rest[1..].starts_with('+') {
+            return None;
+        }
+        self.pos += 2;
+        let range = self.unicode_range_bounds();
+        let is_boundary = self
+            .peek()
+            .map_or(true, |c| c.is_whitespace() || matches!(c, ',' | ')' | ';' | '/' | '!'));
+        match range {
+            Some((first, last)) if is_boundary => {
+                Some(self.ast.unicode_range(self.span(start), first, last))
+            }
+            _ => {
+                self.pos = start;
+                None
+            }
+        }
+    }
+
+    fn unicode_range_bounds(&mut self) -> Option<(u32, u32)> {
+        let digits = self.hex_digits(6);
+        let wildcards = self.source_text[self.pos..self.end]
+            .chars()
+            .take(6 - digits.len())
+            .take_while(|&c| c == '?')
+            .count();
+        self.pos += wildcards;
+        if digits.is_empty() && wildcards == 0 {
+            return None;
+        }
+        let first = u32::from_str_radix(&format!("{digits}{}", "0".repeat(wildcards)), 16).ok()?;
+        if wildcards > 0 {
+            let last =
+                u32::from_str_radix(&format!("{digits}{}", "F".repeat(wildcards)), 16).ok()?;
+            return Some((first, last));
+        }
+        let before_end = self.pos;
+        if self.eat('-') {
+            let end = self.hex_digits(6);
+            if !end.is_empty() {
+                return Some((first, u32::from_str_radix(end, 16).ok()?));
+            }
+            self.pos = before_end;
+        }
+        Some((first, first))
+    }
+
+    fn hex_digits(&mut self, max: usize) -> &'a str {
+        let start = self.pos;
+        let len = self.source_text[self.pos..self.end]
+            .chars()
+            .take(max)
+            .take_while(char::is_ascii_hexdigit)
+            .count();
+        self.pos += len;
+        &self.source_text[start..self.pos]
+    }
+
+    /// Parse the rest of a `url(`, which starts at `start`. `None` if it isn't
+    /// a URL, e.g. `url("a.png" format("png"))`.
+    fn parse_url(&mut self, start: usize) -> Option<ComponentValue<'a>> {
+        let after_name = self.pos;
+        self.skip_whitespace();
+        let url = match self.peek() {
+            Some(quote @ ('"' | '\'')) => {
+                let value = self.parse_string(quote);
+                self.skip_whitespace();
+                (self.eat(')') || self.peek().is_none()).then_some((value, Some(quote)))
+            }
+            _ => self.parse_unquoted_url().map(|value| (value, None)),
+        };
+        let Some((value, quote)) = url else {
+            self.pos = after_name;
+            return None;
+        };
+        Some(self.ast.url(self.span(start), value, quote))
+    }
+
+    fn parse_unquoted_url(&mut self) -> Option<Atom<'a>> {
+        let start = self.pos;
+        let mut unescaped: Option<String<'a>> = None;
+        let mut end = self.pos;
+        loop {
+            let Some(c) = self.bump() else { break };
+            match c {
+                ')' => break,
+                c if c.is_whitespace() => {
+                    self.skip_whitespace();
+                    if !self.eat(')') && self.peek().is_some() {
+                        return None;
+                    }
+                    break;
+                }
+                '"' | '\'' | '(' => return None,
+                '\\' => {
+                    let value = unescaped.get_or_insert_with(|| {
+                        String::from_str_in(&self.source_text[start..end], self.ast.allocator)
+                    });
+                    value.push(self.escape()?);
+                }
+                c => {
+                    if let Some(value) = &mut unescaped {
+                        value.push(c);
+                    }
+                }
+            }
+            end = self.pos;
+        }
+        Some(match unescaped {
+            Some(value) => Atom::from(value.into_bump_str()),
+            None => Atom::from(&self.source_text[start..end]),
+        })
+    }
+
+    /// Parse a string, unescaped. Unterminated strings end with the value.
+    fn parse_string(&mut self, quote: char) -> Atom<'a> {
+        self.bump();
+        let start = self.pos;
+        let mut unescaped: Option<String<'a>> = None;
+        let mut end = self.pos;
+        while let Some(c) = self.bump() {
+            if c == quote {
+                break;
+            }
+            if c == '\\' {
+                let value = unescaped.get_or_insert_with(|| {
+                    String::from_str_in(&self.source_text[start..end], self.ast.allocator)
+                });
+                // An escaped newline continues the string
+                if let Some(c) = self.escape() {
+                    value.push(c);
+                } else {
+                    self.bump();
+                }
+            } else if let Some(value) = &mut unescaped {
+                value.push(c);
+            }
+            end = self.pos;
+        }
+        match unescaped {
+            Some(value) => Atom::from(value.into_bump_str()),
+            None => Atom::from(&self.source_text[start..end]),
+        }
+    }
+
+    /// The character escaped by the `\` before the current position. `None`
+    /// if the `\` is followed by a newline or ends the value.
+    fn escape(&mut self) -> Option<char> {
+        let digits = self.hex_digits(6);
+        if digits.is_empty() {
+            return match self.peek() {
+                None | Some('\n' | '\r' | '\u{c}') => None,
+                Some(_) => self.bump(),
+            };
+        }
+        if self.eat('\r') {
+            self.eat('\n');
+        } else if self.peek().is_some_and(|c| matches!(c, ' ' | '\t' | '\n' | '\u{c}')) {
+            self.bump();
+        }
+        let value = u32::from_str_radix(digits, 16).ok()?;
+        let c = char::from_u32(value).filter(|&c| c != '\0');
+        Some(c.unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+}
+
+fn is_name_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_' || !c.is_ascii()
+}
@@ -51,3 +51,19 @@ pub fn invalid_css_selector(span: Span) -> OxcDiagnostic {
 pub fn unexpected_end(span: Span) -> OxcDiagnostic {
     OxcDiagnostic::error("Unexpected end of file").with_label(span)
 }
+
+#[cold]
+pub fn property_hack(x0: &str, span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::warn(format!("`{x0}` is a legacy Internet Explorer property hack"))
+        .with_error_code("svelte", "css_property_hack")
+        .with_help("Other browsers ignore this declaration")
+        .with_label(span)
+}
+
+#[cold]
+pub fn value_hack(x0: &str, span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::warn(format!("`{x0}` is legacy Internet Explorer syntax"))
+        .with_error_code("svelte", "css_value_hack")
+        .with_help("Other browsers ignore this declaration")
+        .with_label(span)
+}
@@ -48,17 +48,19 @@ impl<'a> Lexer<'a> {
     /// Identifier `UnicodeEscapeSequence`
     ///   \u `Hex4Digits`
     ///   \u{ `CodePoint` }
+    ///
+    /// Other escapes are CSS ones, see [`Lexer::identifier_css_escape`].
     pub(super) fn identifier_unicode_escape_sequence(
         &mut self,
         str: &mut String<'a>,
         check_identifier_start: bool,
     ) {
         let start = self.offset();
-        if self.next_char() != Some('u') {
-            let range = Span::new(start, self.offset());
-            self.error(diagnostics::unicode_escape_sequence(range));
+        if self.peek() != Some('u') {
+            self.identifier_css_escape(str, start);
             return;
         }
+        self.consume_char();
 
         let value = match self.peek() {
             Some('{') => self.unicode_code_point(),
@@ -101,6 +103,37 @@ impl<'a> Lexer<'a> {
         str.push(ch);
     }
 
+    /// CSS escape, `\` followed by one to six hex digits and an optional
+    /// whitespace, e.g. the `\9` of the IE hack `width: 100px\9`, or by any
+    /// other character but a newline, which stands for itself.
+    ///
+    /// Any code point can be escaped, so the result isn't checked to be an
+    /// identifier part.
+    fn identifier_css_escape(&mut self, str: &mut String<'a>, start: u32) {
+        if let Some(mut value) = self.hex_digit() {
+            for _ in 1..6 {
+                let Some(digit) = self.hex_digit() else { break };
+                value = (value << 4) | digit;
+            }
+            if matches!(self.peek(), Some(' ' | TAB | LF | FF)) {
+                self.consume_char();
+            } else if self.peek() == Some(CR) {
+                self.consume_char();
+                self.next_eq(LF);
+            }
+            let ch = char::try_from(value).ok().filter(|&ch| ch != '\0');
+            str.push(ch.unwrap_or(char::REPLACEMENT_CHARACTER));
+            return;
+        }
+        match self.peek() {
+            None | Some(LF | CR | FF) => {
+                let range = Span::new(start, self.offset());
+                self.error(diagnostics::invalid_escape_sequence(range));
+            }
+            Some(_) => str.push(self.consume_char()),
+        }
+    }
+
     /// String `UnicodeEscapeSequence`
     ///   \u `Hex4Digits`
     ///   \u `Hex4Digits` \u `Hex4Digits`
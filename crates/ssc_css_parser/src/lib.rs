@@ -27,6 +27,7 @@
 mod cursor;
 
 mod block;
+mod component;
 mod rule;
 mod selector;
 mod value;
@@ -45,6 +46,7 @@ use oxc_diagnostics::{OxcDiagnostic, Result};
 use oxc_span::{Atom, Span};
 use ssc_css_ast::{ast::StyleSheet, AstBuilder, Trivias};
 
+pub use crate::component::parse_component_values;
 pub use crate::lexer::Kind; // re-export for codegen
 use crate::lexer::{Lexer, Token};
 
@@ -290,6 +292,9 @@ impl<'a> ParserImpl<'a> {
 
 #[cfg(test)]
 mod test {
+    use oxc_diagnostics::Severity;
+    use ssc_css_ast::ast::{BlockChild, ComponentValue, Declaration, Operator, OperatorKind, Rule};
+
     use super::*;
 
     #[test]
@@ -317,6 +322,170 @@ mod test {
         }
     }
 
+    #[test]
+    fn legacy_hacks() {
+        let allocator = Allocator::default();
+        let source = r"p {
+            -webkit-box-shadow: 0 0 1px red;
+            *zoom: 1;
+            _height: 1px;
+            width: 100px\9;
+            color: red !ie;
+            filter: progid:DXImageTransform.Microsoft.Alpha(Opacity=50);
+            height: expression(document.body.clientHeight);
+        }
+        * html p { color: red }";
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(!ret.panicked);
+        assert!(
+            ret.errors.iter().all(|error| error.severity == Severity::Warning),
+            "{:?}",
+            ret.errors
+        );
+        assert_eq!(ret.errors.len(), 6, "{:?}", ret.errors);
+
+        let Some(Rule::StyleRule(rule)) = ret.stylesheet.children.first() else { unreachable!() };
+        let declarations = rule
+            .block
+            .children
+            .iter()
+            .filter_map(|child| match child {
+                BlockChild::Declaration(declaration) => {
+                    Some((declaration.property.as_str(), declaration.value.as_str()))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            declarations,
+            [
+                ("-webkit-box-shadow", "0 0 1px red"),
+                ("*zoom", "1"),
+                ("_height", "1px"),
+                ("width", "100px\\9"),
+                ("color", "red !ie"),
+                ("filter", "progid:DXImageTransform.Microsoft.Alpha(Opacity=50)"),
+                ("height", "expression(document.body.clientHeight)"),
+            ]
+        );
+    }
+
+    #[test]
+    fn component_values() {
+        let allocator = Allocator::default();
+        let source = r#"@font-face {
+            src: url(a\ b.woff2) format("woff2"), url( 'c.woff' ) format('woff');
+            unicode-range: U+0025-00FF, u+4??;
+            background: image-set(url("d.png") 1x, url(e.png) 2x), /* url(f.png) */ url();
+            content: "\"\26 x";
+        }"#;
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+
+        let Some(Rule::AtRule(rule)) = ret.stylesheet.children.first() else { unreachable!() };
+        let declarations = rule
+            .block
+            .iter()
+            .flat_map(|block| &block.children)
+            .filter_map(|child| match child {
+                BlockChild::Declaration(declaration) => Some(declaration),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let urls = |declaration: &Declaration| {
+            declaration.urls().map(|url| (url.value.to_string(), url.quote)).collect::<Vec<_>>()
+        };
+
+        let src = declarations[0];
+        assert_eq!(
+            urls(src),
+            [("a b.woff2".to_string(), None), ("c.woff".to_string(), Some('\''))]
+        );
+        let ComponentValue::Function(format) = &src.components[1] else { unreachable!() };
+        assert_eq!(format.name, "format");
+        assert!(
+            matches!(format.arguments.as_slice(), [ComponentValue::String(string)] if string.value == "woff2")
+        );
+        assert!(matches!(
+            &src.components[2],
+            ComponentValue::Operator(Operator { kind: OperatorKind::Comma, .. })
+        ));
+        let ComponentValue::Url(url) = &src.components[3] else { unreachable!() };
+        assert_eq!(&source[url.span.start as usize..url.span.end as usize], "url( 'c.woff' )");
+
+        let ranges = declarations[1]
+            .components
+            .iter()
+            .filter_map(|component| match component {
+                ComponentValue::UnicodeRange(range) => Some((range.start, range.end)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, [(0x25, 0xff), (0x400, 0x4ff)]);
+
+        assert_eq!(
+            urls(declarations[2]),
+            [("d.png".to_string(), Some('"')), ("e.png".to_string(), None), (String::new(), None)]
+        );
+
+        let [ComponentValue::String(content)] = declarations[3].components.as_slice() else {
+            unreachable!()
+        };
+        assert_eq!(content.value, "\"&x");
+    }
+
+    #[test]
+    fn component_value_tokens() {
+        let allocator = Allocator::default();
+        let source =
+            "p { margin: calc(var(--gap) * -2) -.5em 10% #FFF -webkit-box 1e3 1px\\9 !important; }";
+        let ret = Parser::new(&allocator, source).parse();
+        let Some(Rule::StyleRule(rule)) = ret.stylesheet.children.first() else { unreachable!() };
+        let Some(BlockChild::Declaration(declaration)) = rule.block.children.first() else {
+            unreachable!()
+        };
+        let tokens = declaration
+            .all_components()
+            .map(|component| match component {
+                ComponentValue::Ident(ident) => format!("ident {}", ident.name),
+                ComponentValue::Number(number) => format!("number {}", number.value),
+                ComponentValue::Percentage(percentage) => {
+                    format!("percentage {}", percentage.value)
+                }
+                ComponentValue::Dimension(dimension) => {
+                    format!("dimension {} {}", dimension.value, dimension.unit)
+                }
+                ComponentValue::Hash(hash) => format!("hash {}", hash.value),
+                ComponentValue::Operator(operator) => {
+                    format!("operator {}", operator.kind.as_str())
+                }
+                ComponentValue::Function(function) => format!("function {}", function.name),
+                ComponentValue::Token(token) => format!("token {}", token.value),
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tokens,
+            [
+                "function calc",
+                "function var",
+                "ident --gap",
+                "operator *",
+                "number -2",
+                "dimension -0.5 em",
+                "percentage 10",
+                "hash FFF",
+                "ident -webkit-box",
+                "number 1000",
+                "dimension 1 px\\9",
+                "token !important",
+            ]
+        );
+        let references = declaration.custom_property_references().collect::<Vec<_>>();
+        assert_eq!(references.len(), 1);
+        assert_eq!(references[0].name, "--gap");
+    }
+
     // Source with length MAX_LEN + 1 fails to parse.
     // Skip this test on 32-bit systems as impossible to allocate a string
     // longer than `isize::MAX`.
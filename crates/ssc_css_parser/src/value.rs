@@ -7,6 +7,12 @@ use crate::{diagnostics, Kind, ParserImpl};
 
 impl<'a> ParserImpl<'a> {
     pub(crate) fn parse_value(&mut self) -> Result<Atom<'a>> {
+        let span = self.parse_value_span()?;
+        Ok(Atom::from(&self.source_text[(span.start as usize)..(span.end as usize)]))
+    }
+
+    /// Skip a value, and return its span without the surrounding whitespace.
+    pub(crate) fn parse_value_span(&mut self) -> Result<Span> {
         let mut in_url = false;
         let start = self.prev_token_end;
 
@@ -35,7 +41,7 @@ impl<'a> ParserImpl<'a> {
                     let offset = (starting_source.len() - start_trimmed.len()) as u32;
                     (start + offset).min(end)
                 };
-                return Ok(Atom::from(&self.source_text[(start as usize)..(end as usize)]));
+                return Ok(Span::new(start, end));
             } else {
                 self.eat(self.cur_kind());
             }
@@ -3,8 +3,16 @@
 //! CSS Transformer
 
 use oxc_allocator::{Allocator, Vec};
+use std::cell::Cell;
+
 use oxc_span::{Atom, SPAN};
-use ssc_css_ast::{ast::*, visit::walk_mut::walk_complex_selector_mut, VisitMut};
+use ssc_css_ast::{
+    ast::*,
+    visit::walk_mut::{
+        walk_at_rule_mut, walk_block_mut, walk_complex_selector_mut, walk_style_rule_mut,
+    },
+    VisitMut,
+};
 
 fn clone<T>(x: &T) -> T {
     #[allow(unsafe_code)]
@@ -14,14 +22,67 @@ fn clone<T>(x: &T) -> T {
     }
 }
 
+/// How selectors are scoped to the component.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scoping {
+    /// Append the hash class, `p.svelte-xyz`, which raises the specificity of
+    /// every selector by one class.
+    #[default]
+    Class,
+    /// Append it in a `:where()`, `p:where(.svelte-xyz)`, which keeps the
+    /// specificity of the selectors as written.
+    Where,
+}
+
 pub struct Transformer<'a> {
     allocator: &'a Allocator,
     hash: &'a str,
+    /// Keyframes scoped to the component, see [`Transformer::with_keyframes`].
+    keyframes: std::vec::Vec<Atom<'a>>,
+    /// See [`Transformer::with_remove_unused`].
+    remove_unused: bool,
+    scoping: Scoping,
 }
 
 impl<'a> Transformer<'a> {
     pub fn new(allocator: &'a Allocator, hash: &'a str) -> Self {
-        Self { allocator, hash }
+        Self { allocator, hash, keyframes: vec![], remove_unused: false, scoping: Scoping::Class }
+    }
+
+    /// Scope the given keyframes, as collected by the analyzer, to the
+    /// component: `@keyframes fade` and the animations referencing it become
+    /// `@keyframes {hash}-fade`.
+    #[must_use]
+    pub fn with_keyframes(mut self, keyframes: std::vec::Vec<Atom<'a>>) -> Self {
+        self.keyframes = keyframes;
+        self
+    }
+
+    /// Drop the style rules none of whose selectors is marked
+    /// [`used`](ComplexSelector::used), and the unused selectors of the
+    /// others. The flags must have been set against the template, the
+    /// analyzer only marks the `:global` selectors.
+    #[must_use]
+    pub fn with_remove_unused(mut self, remove_unused: bool) -> Self {
+        self.remove_unused = remove_unused;
+        self
+    }
+
+    #[must_use]
+    pub fn with_scoping(mut self, scoping: Scoping) -> Self {
+        self.scoping = scoping;
+        self
+    }
+
+    fn is_unused(&self, rule: &StyleRule<'a>) -> bool {
+        self.remove_unused
+            && !rule.flags.get().has_global_block()
+            && rule.prelude.children.iter().all(|selector| !selector.used.get())
+    }
+
+    /// Whether `rule` only contained unused style rules, which were removed.
+    fn is_emptied(&self, rule: &AtRule<'a>, len: usize) -> bool {
+        self.remove_unused && len > 0 && rule.block.as_ref().is_some_and(|b| b.children.is_empty())
     }
 
     pub fn build(mut self, stylesheet: &mut StyleSheet<'a>) {
@@ -37,6 +98,10 @@ impl<'a> VisitMut<'a> for Transformer<'a> {
 
         for (i, rule) in rules.iter_mut().enumerate() {
             if let Rule::StyleRule(rule) = rule {
+                if self.is_unused(rule) {
+                    deletions.push(i - deletions.len());
+                    continue;
+                }
                 if rule.flags.get().has_global_block() {
                     let mut children = Vec::from_iter_in(
                         rule.block.children.drain(..).filter_map(BlockChild::rule),
@@ -53,6 +118,14 @@ impl<'a> VisitMut<'a> for Transformer<'a> {
                     continue;
                 }
             }
+            if let Rule::AtRule(rule) = rule {
+                let len = rule.block.as_ref().map_or(0, |block| block.children.len());
+                self.visit_at_rule(rule);
+                if self.is_emptied(rule, len) {
+                    deletions.push(i - deletions.len());
+                }
+                continue;
+            }
             self.visit_rule(rule);
         }
 
@@ -71,6 +144,66 @@ impl<'a> VisitMut<'a> for Transformer<'a> {
         }
     }
 
+    fn visit_block(&mut self, block: &mut Block<'a>) {
+        if !self.remove_unused {
+            walk_block_mut(self, block);
+            return;
+        }
+        block.children.retain(|child| match child {
+            BlockChild::StyleRule(rule) => !self.is_unused(rule),
+            _ => true,
+        });
+        let mut deletions = vec![];
+        for (i, child) in block.children.iter_mut().enumerate() {
+            if let BlockChild::AtRule(rule) = child {
+                let len = rule.block.as_ref().map_or(0, |block| block.children.len());
+                self.visit_at_rule(rule);
+                if self.is_emptied(rule, len) {
+                    deletions.push(i - deletions.len());
+                }
+            } else {
+                self.visit_block_child(child);
+            }
+        }
+        for i in deletions {
+            block.children.remove(i);
+        }
+    }
+
+    fn visit_style_rule(&mut self, rule: &mut StyleRule<'a>) {
+        if self.remove_unused && !rule.flags.get().has_global_block() {
+            rule.prelude.children.retain(|selector| selector.used.get());
+        }
+        walk_style_rule_mut(self, rule);
+    }
+
+    fn visit_at_rule(&mut self, rule: &mut AtRule<'a>) {
+        if remove_css_prefix(&rule.name) == "keyframes" {
+            if let Some(name) = rule.prelude.as_str().strip_prefix("-global-") {
+                rule.prelude = Atom::from(name);
+            } else if self.keyframes.contains(&rule.prelude) {
+                let name = format!("{}-{}", self.hash, rule.prelude);
+                rule.prelude = Atom::from(&*self.allocator.alloc_str(&name));
+            }
+        }
+        walk_at_rule_mut(self, rule);
+    }
+
+    fn visit_declaration(&mut self, decl: &mut Declaration<'a>) {
+        if !matches!(remove_css_prefix(&decl.property), "animation" | "animation-name") {
+            return;
+        }
+        if let Some(value) = scope_animation_names(&decl.value, &self.keyframes, self.hash) {
+            decl.value = Atom::from(&*self.allocator.alloc_str(&value));
+            for component in decl.components.iter_mut() {
+                let ComponentValue::Ident(ident) = component else { continue };
+                if let Some(name) = scope_animation_names(&ident.name, &self.keyframes, self.hash) {
+                    ident.name = Atom::from(&*self.allocator.alloc_str(&name));
+                }
+            }
+        }
+    }
+
     fn visit_complex_selector(&mut self, selector: &mut ComplexSelector<'a>) {
         walk_complex_selector_mut(self, selector);
         let mut replaces = vec![];
@@ -89,6 +222,7 @@ impl<'a> VisitMut<'a> for Transformer<'a> {
                                 clone(relative_selector),
                                 j,
                                 self.hash,
+                                self.scoping,
                             );
                             if !transformed.is_empty() {
                                 replaces.push((i, transformed.remove(0)));
@@ -125,11 +259,88 @@ impl<'a> VisitMut<'a> for Transformer<'a> {
         if has_global_selector {
             return;
         }
-        selector.selectors.push(SimpleSelector::ClassSelector(ClassSelector {
-            span: SPAN,
-            name: Atom::from(self.hash),
-        }));
+        selector.selectors.push(scope_selector(self.allocator, self.hash, self.scoping));
+    }
+}
+
+fn remove_css_prefix(name: &str) -> &str {
+    ["-webkit-", "-moz-", "-o-", "-ms-"]
+        .iter()
+        .find_map(|prefix| name.strip_prefix(prefix))
+        .unwrap_or(name)
+}
+
+// example:
+//   input:  `fade 1s, 2s slide, -global-spin` (`fade` is scoped)
+//   output: `{hash}-fade 1s, 2s slide, spin`
+fn scope_animation_names(value: &str, keyframes: &[Atom], hash: &str) -> Option<String> {
+    let mut output = String::with_capacity(value.len());
+    let mut depth = 0usize;
+    let mut changed = false;
+    let mut rest = value;
+    while let Some(ch) = rest.chars().next() {
+        let len = rest
+            .find(|ch: char| !(ch.is_alphanumeric() || matches!(ch, '-' | '_') || !ch.is_ascii()))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            match ch {
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            output.push(ch);
+            rest = &rest[ch.len_utf8()..];
+            continue;
+        }
+        // Identifiers within functions, e.g. `var(--fade)`, aren't names
+        let is_function = rest[len..].starts_with('(');
+        let mut word = &rest[..len];
+        if depth == 0 && !is_function {
+            if let Some(name) = word.strip_prefix("-global-") {
+                word = name;
+                changed = true;
+            } else if keyframes.iter().any(|name| name.as_str() == word) {
+                output.push_str(hash);
+                output.push('-');
+                changed = true;
+            }
+        }
+        output.push_str(word);
+        rest = &rest[len..];
     }
+    changed.then_some(output)
+}
+
+/// The selector appended to compound selectors to scope them.
+fn scope_selector<'a>(
+    allocator: &'a Allocator,
+    hash: &'a str,
+    scoping: Scoping,
+) -> SimpleSelector<'a> {
+    let class = SimpleSelector::ClassSelector(ClassSelector { span: SPAN, name: Atom::from(hash) });
+    if scoping == Scoping::Class {
+        return class;
+    }
+    let relative_selector = RelativeSelector {
+        span: SPAN,
+        combinator: None,
+        selectors: Vec::from_iter_in([class], allocator),
+        flags: Cell::new(RelativeSelectorFlags::empty()),
+    };
+    let complex_selector = ComplexSelector {
+        span: SPAN,
+        children: Vec::from_iter_in([relative_selector], allocator),
+        rule: Cell::default(),
+        used: Cell::new(true),
+    };
+    SimpleSelector::PseudoClassSelector(PseudoClassSelector {
+        span: SPAN,
+        name: Atom::from("where"),
+        args: Some(SelectorList {
+            span: SPAN,
+            children: Vec::from_iter_in([complex_selector], allocator),
+        }),
+    })
 }
 
 // example:
@@ -144,6 +355,7 @@ fn transform_global_selector<'a>(
     mut relative_selector: RelativeSelector<'a>,
     global_selector_index: usize,
     hash: &'a str,
+    scoping: Scoping,
 ) -> Vec<'a, RelativeSelector<'a>> {
     let mut selectors_after_global = Vec::from_iter_in(
         relative_selector.selectors.drain((global_selector_index + 1)..),
@@ -152,16 +364,10 @@ fn transform_global_selector<'a>(
     let mut selectors_before_global =
         Vec::from_iter_in(relative_selector.selectors.drain(0..global_selector_index), allocator);
     if !selectors_after_global.is_empty() {
-        selectors_after_global.push(SimpleSelector::ClassSelector(ClassSelector {
-            span: SPAN,
-            name: Atom::from(hash),
-        }));
+        selectors_after_global.push(scope_selector(allocator, hash, scoping));
     }
     if !selectors_before_global.is_empty() {
-        selectors_before_global.push(SimpleSelector::ClassSelector(ClassSelector {
-            span: SPAN,
-            name: Atom::from(hash),
-        }));
+        selectors_before_global.push(scope_selector(allocator, hash, scoping));
     }
     if inner_selectors.is_empty() {
         let mut vec = Vec::new_in(allocator);
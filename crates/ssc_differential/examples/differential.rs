@@ -0,0 +1,42 @@
+#![allow(clippy::print_stdout)]
+
+use std::{env, fs, path::Path};
+
+use ssc_differential::{Component, Report, SvelteCompiler};
+
+// Instruction:
+// run `npm install` in `crates/ssc_differential`,
+// run `cargo run -p ssc_differential --example differential -- <corpus> [--json]`
+
+fn collect(dir: &Path, components: &mut Vec<Component>) -> Result<(), String> {
+    let entries = fs::read_dir(dir).map_err(|_| format!("Missing '{}'", dir.display()))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect(&path, components)?;
+        } else if path.extension().is_some_and(|extension| extension == "svelte") {
+            let source_text = fs::read_to_string(&path)
+                .map_err(|_| format!("Failed to read '{}'", path.display()))?;
+            components.push(Component { filename: path.display().to_string(), source_text });
+        }
+    }
+    Ok(())
+}
+
+fn main() -> Result<(), String> {
+    let corpus = env::args().nth(1).unwrap_or_else(|| "corpus".to_string());
+    let json = env::args().any(|arg| arg == "--json");
+
+    let mut components = vec![];
+    collect(Path::new(&corpus), &mut components)?;
+    components.sort_by(|a, b| a.filename.cmp(&b.filename));
+
+    let report = Report::run(&components, &SvelteCompiler::default())?;
+    if json {
+        println!("{}", report.to_json());
+    } else {
+        println!("{report}");
+    }
+
+    Ok(())
+}
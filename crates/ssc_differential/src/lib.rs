@@ -0,0 +1,554 @@
+//! Differential testing against `svelte/compiler`
+//!
+//! Compiles a corpus of components with both ssc and the `svelte/compiler`
+//! version pinned in `package.json`, run through Node, and reports where they
+//! diverge:
+//! * which components fail to compile
+//! * warning codes and their start offsets
+//! * the node types of the AST, ignoring positions and fields only one of
+//!   them has
+//! * the emitted CSS, with the scoping hashes and whitespace normalized
+//!
+//! Run `npm install` in this crate's directory, then
+//! `cargo run -p ssc_differential --example differential -- <corpus>`.
+
+use std::{
+    fmt,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+use oxc_allocator::Allocator;
+use oxc_diagnostics::Severity;
+use serde_json::{json, Map, Value};
+use ssc::{
+    compiler::{warning_code, CompileOptions, Compiler, Emit},
+    parser::Parser,
+};
+
+/// The version of `svelte` in `package.json`, checked before comparing.
+pub const SVELTE_VERSION: &str = "5.0.0";
+
+const SCRIPT: &str = include_str!("svelte.mjs");
+
+/// Object keys left out of AST comparisons, as they are position-derived or
+/// internal to one of the compilers.
+const IGNORED_KEYS: [&str; 6] = ["start", "end", "loc", "range", "metadata", "comments"];
+
+/// A component of the corpus.
+#[derive(Debug, Clone)]
+pub struct Component {
+    pub filename: String,
+    pub source_text: String,
+}
+
+/// A diagnostic, by code and start offset.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Diagnostic {
+    pub code: String,
+    /// Offset of the start of the diagnostic, in UTF-16 code units, as
+    /// reported by `svelte/compiler`.
+    pub start: Option<usize>,
+}
+
+/// What a compiler produced for a component.
+#[derive(Debug, Clone, Default)]
+pub struct Output {
+    /// The AST, in the JSON shape of `svelte/compiler`'s modern AST.
+    pub ast: Option<Value>,
+    pub warnings: Vec<Diagnostic>,
+    pub css: Option<String>,
+    /// The first error, when the component doesn't compile. Error codes of
+    /// ssc are always `error`, as it doesn't have any.
+    pub error: Option<Diagnostic>,
+}
+
+impl Output {
+    /// Compile `component` with ssc.
+    pub fn ssc(component: &Component) -> Self {
+        let source_text = component.source_text.as_str();
+        let allocator = Allocator::default();
+        let ast = Parser::new(&allocator, source_text).parse();
+        let ast = ast.errors.is_empty().then(|| serde_json::to_value(&ast.root).ok()).flatten();
+
+        // Parsed again, as emitting the CSS scopes the styles of the AST
+        let allocator = Allocator::default();
+        let options = CompileOptions {
+            filename: component.filename.clone(),
+            emit: Emit::Css,
+            ..CompileOptions::default()
+        };
+        let ret = Compiler::new(&allocator, source_text, options).compile();
+        let (warnings, errors): (Vec<_>, Vec<_>) =
+            ret.errors.iter().partition(|error| error.severity() == Some(Severity::Warning));
+        let diagnostic = |code: &str, error: &oxc_diagnostics::Error| Diagnostic {
+            code: code.to_string(),
+            start: error
+                .labels()
+                .and_then(|mut labels| labels.next())
+                .map(|label| utf16_offset(source_text, label.offset())),
+        };
+        let mut warnings = warnings
+            .into_iter()
+            .map(|warning| diagnostic(warning_code(warning).unwrap_or("unknown"), warning))
+            .collect::<Vec<_>>();
+        warnings.sort();
+        Self {
+            ast,
+            warnings,
+            css: ret.css.map(|css| css.source_text),
+            error: errors.first().map(|error| diagnostic("error", *error)),
+        }
+    }
+
+    fn from_json(value: &Value) -> Self {
+        let diagnostic = |value: &Value| Diagnostic {
+            code: value["code"].as_str().unwrap_or("unknown").to_string(),
+            start: value["start"].as_u64().and_then(|start| usize::try_from(start).ok()),
+        };
+        let mut warnings = value["warnings"]
+            .as_array()
+            .map(|warnings| warnings.iter().map(diagnostic).collect::<Vec<_>>())
+            .unwrap_or_default();
+        warnings.sort();
+        Self {
+            ast: Some(value["ast"].clone()).filter(|ast| !ast.is_null()),
+            warnings,
+            css: value["css"].as_str().map(ToString::to_string),
+            error: Some(&value["error"]).filter(|error| !error.is_null()).map(diagnostic),
+        }
+    }
+}
+
+fn utf16_offset(source_text: &str, offset: usize) -> usize {
+    source_text.get(..offset).unwrap_or(source_text).encode_utf16().count()
+}
+
+/// Runs the pinned `svelte/compiler` through Node.
+#[derive(Debug, Clone)]
+pub struct SvelteCompiler {
+    node: PathBuf,
+    /// Directory of the `node_modules` with `svelte` installed.
+    root: PathBuf,
+}
+
+impl Default for SvelteCompiler {
+    /// `node` from the `PATH`, with `svelte` installed in this crate's
+    /// directory.
+    fn default() -> Self {
+        Self::new(PathBuf::from(env!("CARGO_MANIFEST_DIR")))
+    }
+}
+
+impl SvelteCompiler {
+    pub fn new(root: PathBuf) -> Self {
+        Self { node: PathBuf::from("node"), root }
+    }
+
+    /// Run `node` instead of the one on the `PATH`.
+    #[must_use]
+    pub fn with_node(mut self, node: PathBuf) -> Self {
+        self.node = node;
+        self
+    }
+
+    /// Compile `components`, in a single Node process.
+    ///
+    /// # Errors
+    ///
+    /// When Node can't be run, fails, or `svelte` isn't [`SVELTE_VERSION`].
+    pub fn compile(&self, components: &[Component]) -> Result<Vec<Output>, String> {
+        let input = components
+            .iter()
+            .map(|component| json!({ "filename": component.filename, "source": component.source_text }))
+            .collect::<Vec<_>>();
+        let mut child = Command::new(&self.node)
+            .args(["--input-type=module", "--eval", SCRIPT])
+            .current_dir(&self.root)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|error| format!("Failed to run `{}`: {error}", self.node.display()))?;
+        let mut stdin = child.stdin.take().ok_or("Failed to open the stdin of Node")?;
+        stdin
+            .write_all(Value::Array(input).to_string().as_bytes())
+            .map_err(|error| format!("{error}"))?;
+        drop(stdin);
+        let output = child.wait_with_output().map_err(|error| format!("{error}"))?;
+        if !output.status.success() {
+            return Err(format!(
+                "svelte/compiler failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        let output: Value =
+            serde_json::from_slice(&output.stdout).map_err(|error| format!("{error}"))?;
+        let version = output["version"].as_str().unwrap_or_default();
+        if version != SVELTE_VERSION {
+            return Err(format!(
+                "Expected svelte {SVELTE_VERSION}, found `{version}`, run `npm install` in `{}`",
+                self.root.display()
+            ));
+        }
+        let results = output["results"].as_array().ok_or("Missing results")?;
+        Ok(results.iter().map(Output::from_json).collect())
+    }
+}
+
+/// A difference between the outputs of ssc and `svelte/compiler`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Divergence {
+    /// Only one of them fails to compile the component.
+    Error { ssc: Option<Diagnostic>, svelte: Option<Diagnostic> },
+    /// A warning only reported by ssc.
+    ExtraWarning(Diagnostic),
+    /// A warning only reported by `svelte/compiler`.
+    MissingWarning(Diagnostic),
+    /// The first node of the AST whose type differs, at a path like
+    /// `fragment.nodes.0`. A missing node has no type.
+    Ast { path: String, ssc: Option<String>, svelte: Option<String> },
+    /// The normalized CSS, see [`normalize_css`].
+    Css { ssc: Option<String>, svelte: Option<String> },
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let or_none = |value: Option<&str>| value.unwrap_or("none").to_string();
+        let diagnostic = |diagnostic: Option<&Diagnostic>| {
+            diagnostic.map_or("none".to_string(), |diagnostic| match diagnostic.start {
+                Some(start) => format!("{} at {start}", diagnostic.code),
+                None => diagnostic.code.clone(),
+            })
+        };
+        match self {
+            Self::Error { ssc, svelte } => write!(
+                f,
+                "error: ssc {}, svelte {}",
+                diagnostic(ssc.as_ref()),
+                diagnostic(svelte.as_ref())
+            ),
+            Self::ExtraWarning(warning) => write!(f, "extra warning {}", diagnostic(Some(warning))),
+            Self::MissingWarning(warning) => {
+                write!(f, "missing warning {}", diagnostic(Some(warning)))
+            }
+            Self::Ast { path, ssc, svelte } => write!(
+                f,
+                "AST at `{path}`: ssc {}, svelte {}",
+                or_none(ssc.as_deref()),
+                or_none(svelte.as_deref())
+            ),
+            Self::Css { ssc, svelte } => write!(
+                f,
+                "CSS:\n  ssc    {}\n  svelte {}",
+                or_none(ssc.as_deref()),
+                or_none(svelte.as_deref())
+            ),
+        }
+    }
+}
+
+/// The divergences of `ssc` from `svelte`, for the same component. When
+/// either fails to compile, only the AST is compared besides the errors.
+pub fn compare(ssc: &Output, svelte: &Output) -> Vec<Divergence> {
+    let mut divergences = vec![];
+    if ssc.error.is_some() != svelte.error.is_some() {
+        divergences
+            .push(Divergence::Error { ssc: ssc.error.clone(), svelte: svelte.error.clone() });
+    }
+    if let (Some(ssc_ast), Some(svelte_ast)) = (&ssc.ast, &svelte.ast) {
+        if let Some((path, ssc, svelte)) = first_ast_divergence(ssc_ast, svelte_ast) {
+            divergences.push(Divergence::Ast { path, ssc, svelte });
+        }
+    }
+    if ssc.error.is_some() || svelte.error.is_some() {
+        return divergences;
+    }
+
+    let mut svelte_warnings = svelte.warnings.iter().collect::<Vec<_>>();
+    for warning in &ssc.warnings {
+        match svelte_warnings.iter().position(|other| *other == warning) {
+            Some(index) => {
+                svelte_warnings.remove(index);
+            }
+            None => divergences.push(Divergence::ExtraWarning(warning.clone())),
+        }
+    }
+    divergences.extend(
+        svelte_warnings.into_iter().map(|warning| Divergence::MissingWarning(warning.clone())),
+    );
+
+    let ssc_css = ssc.css.as_deref().map(normalize_css).filter(|css| !css.is_empty());
+    let svelte_css = svelte.css.as_deref().map(normalize_css).filter(|css| !css.is_empty());
+    if ssc_css != svelte_css {
+        divergences.push(Divergence::Css { ssc: ssc_css, svelte: svelte_css });
+    }
+    divergences
+}
+
+/// The node types of `value` with their paths, in document order.
+fn node_types(value: &Value, path: &str, types: &mut Vec<(String, String)>) {
+    let child_path = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{path}.{key}")
+        }
+    };
+    match value {
+        Value::Object(object) => {
+            if let Some(ty) = object.get("type").and_then(Value::as_str) {
+                types.push((path.to_string(), ty.to_string()));
+            }
+            for key in child_keys(value, value) {
+                node_types(&value[key], &child_path(key), types);
+            }
+        }
+        Value::Array(values) => {
+            for (index, value) in values.iter().enumerate() {
+                node_types(value, &child_path(&index.to_string()), types);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Keys of `a` that `b` also has, sorted, without the [`IGNORED_KEYS`].
+fn child_keys<'v>(a: &'v Value, b: &Value) -> Vec<&'v str> {
+    let (Value::Object(a), Value::Object(b)) = (a, b) else {
+        return vec![];
+    };
+    let mut keys = a
+        .keys()
+        .map(String::as_str)
+        .filter(|key| b.contains_key(*key) && !IGNORED_KEYS.contains(key))
+        .collect::<Vec<_>>();
+    keys.sort_unstable();
+    keys
+}
+
+/// The first path where the node types of `ssc` and `svelte` differ, with
+/// their types there. Fields only one of the ASTs has are skipped.
+fn first_ast_divergence(
+    ssc: &Value,
+    svelte: &Value,
+) -> Option<(String, Option<String>, Option<String>)> {
+    match (ssc, svelte) {
+        (Value::Object(a), Value::Object(b)) => {
+            let ty = |object: &Map<String, Value>| {
+                object.get("type").and_then(Value::as_str).map(ToString::to_string)
+            };
+            if ty(a) != ty(b) {
+                return Some((String::new(), ty(a), ty(b)));
+            }
+            for key in child_keys(ssc, svelte) {
+                if let Some((path, a, b)) = first_ast_divergence(&ssc[key], &svelte[key]) {
+                    let path =
+                        if path.is_empty() { key.to_string() } else { format!("{key}.{path}") };
+                    return Some((path, a, b));
+                }
+            }
+            None
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            for (index, (a, b)) in a.iter().zip(b).enumerate() {
+                if let Some((path, a, b)) = first_ast_divergence(a, b) {
+                    let path =
+                        if path.is_empty() { index.to_string() } else { format!("{index}.{path}") };
+                    return Some((path, a, b));
+                }
+            }
+            // The first node only one of them has
+            let (longer, is_ssc) = if a.len() > b.len() { (a, true) } else { (b, false) };
+            longer.iter().enumerate().skip(a.len().min(b.len())).find_map(|(index, value)| {
+                let mut types = vec![];
+                node_types(value, "", &mut types);
+                let (path, ty) = types.into_iter().next()?;
+                let path =
+                    if path.is_empty() { index.to_string() } else { format!("{index}.{path}") };
+                Some(if is_ssc { (path, Some(ty), None) } else { (path, None, Some(ty)) })
+            })
+        }
+        _ => None,
+    }
+}
+
+/// `css` with the scoping hashes replaced by `svelte-hash`, comments removed
+/// and whitespace collapsed, so that only meaningful differences remain.
+pub fn normalize_css(css: &str) -> String {
+    let mut normalized = String::with_capacity(css.len());
+    let mut rest = css;
+    let mut pending_space = false;
+    while let Some(c) = rest.chars().next() {
+        if rest.starts_with("/*") {
+            rest = rest[2..].find("*/").map_or("", |end| &rest[end + 4..]);
+            pending_space = true;
+            continue;
+        }
+        if rest.starts_with("svelte-") {
+            let hash_len = rest["svelte-".len()..]
+                .find(|c: char| !c.is_ascii_alphanumeric())
+                .unwrap_or(rest.len() - "svelte-".len());
+            if hash_len > 0 {
+                normalized.push_str("svelte-hash");
+                rest = &rest["svelte-".len() + hash_len..];
+                continue;
+            }
+        }
+        rest = &rest[c.len_utf8()..];
+        if c.is_whitespace() {
+            pending_space = true;
+            continue;
+        }
+        let is_punctuation = |c: char| matches!(c, '{' | '}' | ';' | ':' | ',' | '>');
+        if pending_space
+            && !normalized.is_empty()
+            && !is_punctuation(c)
+            && !normalized.ends_with(is_punctuation)
+        {
+            normalized.push(' ');
+        }
+        pending_space = false;
+        // A trailing `;` before `}` is optional
+        if c == '}' && normalized.ends_with(';') {
+            normalized.pop();
+        }
+        normalized.push(c);
+    }
+    normalized
+}
+
+/// The divergences of a component.
+#[derive(Debug, Clone)]
+pub struct FileReport {
+    pub filename: String,
+    pub divergences: Vec<Divergence>,
+}
+
+/// The divergences of a corpus, see [`Report::run`].
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    pub files: Vec<FileReport>,
+}
+
+impl Report {
+    /// Compile `components` with ssc and `svelte`, and compare them.
+    ///
+    /// # Errors
+    ///
+    /// See [`SvelteCompiler::compile`].
+    pub fn run(components: &[Component], svelte: &SvelteCompiler) -> Result<Self, String> {
+        let outputs = svelte.compile(components)?;
+        let files = components
+            .iter()
+            .zip(&outputs)
+            .map(|(component, svelte)| FileReport {
+                filename: component.filename.clone(),
+                divergences: compare(&Output::ssc(component), svelte),
+            })
+            .collect();
+        Ok(Self { files })
+    }
+
+    /// Number of components with at least one divergence.
+    pub fn divergent_files(&self) -> usize {
+        self.files.iter().filter(|file| !file.divergences.is_empty()).count()
+    }
+
+    /// The report as a JSON array of `{"file","divergences"}` objects, for
+    /// the divergent components, with the divergences as strings.
+    pub fn to_json(&self) -> String {
+        let files = self
+            .files
+            .iter()
+            .filter(|file| !file.divergences.is_empty())
+            .map(|file| {
+                let divergences =
+                    file.divergences.iter().map(ToString::to_string).collect::<Vec<_>>();
+                json!({ "file": file.filename, "divergences": divergences })
+            })
+            .collect::<Vec<_>>();
+        Value::Array(files).to_string()
+    }
+}
+
+impl fmt::Display for Report {
+    /// The divergences by component, followed by a summary line.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for file in self.files.iter().filter(|file| !file.divergences.is_empty()) {
+            writeln!(f, "{}", file.filename)?;
+            for divergence in &file.divergences {
+                writeln!(f, "  {divergence}")?;
+            }
+        }
+        write!(
+            f,
+            "{} of {} components diverge from svelte {SVELTE_VERSION}",
+            self.divergent_files(),
+            self.files.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn component(source_text: &str) -> Component {
+        Component { filename: "App.svelte".to_string(), source_text: source_text.to_string() }
+    }
+
+    #[test]
+    fn css_normalization() {
+        assert_eq!(
+            normalize_css("p.svelte-1x2y3z {\n  color: red;\n}\n/* a */\ndiv > p.svelte-abc { margin: 0 auto }"),
+            "p.svelte-hash{color:red}div>p.svelte-hash{margin:0 auto}"
+        );
+    }
+
+    #[test]
+    fn ast_divergence() {
+        let ssc = json!({
+            "type": "Root", "start": 0, "ts": false,
+            "fragment": { "type": "Fragment", "nodes": [{ "type": "Text" }, { "type": "Comment" }] }
+        });
+        let svelte = json!({
+            "type": "Root", "start": 1,
+            "fragment": { "type": "Fragment", "nodes": [{ "type": "Text" }] }
+        });
+        assert_eq!(
+            first_ast_divergence(&ssc, &svelte),
+            Some(("fragment.nodes.1".to_string(), Some("Comment".to_string()), None))
+        );
+        assert_eq!(first_ast_divergence(&svelte, &svelte), None);
+    }
+
+    #[test]
+    fn divergences() {
+        let ssc = Output::ssc(&component("<p />\n<style>p { color: red; }</style>"));
+        assert_eq!(ssc.warnings.len(), 1);
+        assert_eq!(ssc.warnings[0].code, "element_invalid_self_closing_tag");
+        let svelte = Output::from_json(&json!({
+            "ast": null,
+            "warnings": [{ "code": "a11y_missing_content", "start": 0 }],
+            "css": "p.svelte-xyz { color: red; }",
+            "error": null,
+        }));
+        assert_eq!(
+            compare(&ssc, &svelte),
+            vec![
+                Divergence::ExtraWarning(ssc.warnings[0].clone()),
+                Divergence::MissingWarning(svelte.warnings[0].clone()),
+            ]
+        );
+
+        let svelte = Output::from_json(&json!({
+            "ast": null,
+            "warnings": [],
+            "css": null,
+            "error": { "code": "css_expected_identifier", "start": 3 },
+        }));
+        assert_eq!(compare(&Output::ssc(&component("<p></p>")), &svelte).len(), 1);
+    }
+}
@@ -0,0 +1,31 @@
+// Runs the pinned svelte/compiler on the components read from stdin, a JSON
+// array of `{ filename, source }`, and writes `{ version, results }` to
+// stdout, with one result per component.
+import { compile, parse, VERSION } from 'svelte/compiler';
+
+function location(diagnostic) {
+  return { code: diagnostic.code, start: diagnostic.start?.character ?? null };
+}
+
+function run({ filename, source }) {
+  let ast = null;
+  try {
+    ast = parse(source, { filename, modern: true });
+  } catch (error) {
+    return { ast, warnings: [], css: null, error: location(error) };
+  }
+  try {
+    const { warnings, css } = compile(source, { filename, css: 'external' });
+    return { ast, warnings: warnings.map(location), css: css?.code ?? null, error: null };
+  } catch (error) {
+    return { ast, warnings: [], css: null, error: location(error) };
+  }
+}
+
+let input = '';
+process.stdin.setEncoding('utf8');
+for await (const chunk of process.stdin) {
+  input += chunk;
+}
+const results = JSON.parse(input).map(run);
+process.stdout.write(JSON.stringify({ version: VERSION, results }));
@@ -0,0 +1,64 @@
+use oxc_span::{Atom, Span};
+use ssc_semantic::{BindingKind, Semantic};
+
+#[derive(Debug)]
+pub struct Definition<'a> {
+    pub name: Atom<'a>,
+    /// Span of the identifier declaring the binding, in a script or in the
+    /// template block declaring it.
+    pub span: Span,
+    pub kind: BindingKind,
+}
+
+/// Declaration of the binding used or declared at `offset`.
+///
+/// Returns `None` for globals and for offsets outside of any identifier.
+pub fn goto_definition<'a>(semantic: &Semantic<'a>, offset: u32) -> Option<Definition<'a>> {
+    let symbol_id = semantic.symbol_at(offset)?;
+    let symbols = semantic.symbols();
+    Some(Definition {
+        name: symbols.get_name(symbol_id).clone(),
+        span: symbols.get_span(symbol_id),
+        kind: symbols.get_kind(symbol_id),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use ssc_parser::Parser;
+    use ssc_semantic::SemanticBuilder;
+
+    use super::*;
+
+    #[test]
+    fn definitions() {
+        let source = r"<script>
+    import List from './List.svelte';
+    let value = $state('');
+</script>
+{#snippet row(item)}<li>{item}</li>{/snippet}
+<List><input bind:value={value}></List>
+{#each [1, 2] as item}{@render row(item)}{/each}
+{missing}";
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty());
+        let semantic = SemanticBuilder::new(source).build(&ret.root).semantic;
+
+        let definition_at = |needle: &str, nth: usize| {
+            let offset = source.match_indices(needle).nth(nth).unwrap().0;
+            goto_definition(&semantic, u32::try_from(offset).unwrap())
+                .map(|definition| (definition.span.start as usize, definition.kind))
+        };
+        let position = |needle: &str| source.find(needle).unwrap();
+
+        assert_eq!(definition_at("List>", 0), Some((position("List from"), BindingKind::Normal)));
+        assert_eq!(definition_at("value}", 0), Some((position("value ="), BindingKind::State)));
+        assert_eq!(definition_at("row(item)", 1), Some((position("row("), BindingKind::Snippet)));
+        // The snippet parameter and the each context are different bindings
+        assert_eq!(definition_at("item}", 0), Some((position("item)"), BindingKind::Snippet)));
+        assert_eq!(definition_at("item)}", 1), Some((position("item}{@"), BindingKind::Each)));
+        assert_eq!(definition_at("missing", 0), None);
+    }
+}
@@ -0,0 +1,275 @@
+use std::ops::ControlFlow;
+
+use oxc_ast::ast::Statement;
+use oxc_span::{Atom, Span};
+use oxc_syntax::symbol::SymbolFlags;
+#[allow(clippy::wildcard_imports)]
+use ssc_ast::ast::*;
+use ssc_ast::{visit::walk::walk_element, Visit};
+use ssc_semantic::{BindingKind, Semantic};
+
+#[derive(Debug)]
+pub enum Hover<'a> {
+    Binding(BindingHover<'a>),
+    Directive(DirectiveHover<'a>),
+    Component(ComponentHover<'a>),
+}
+
+#[derive(Debug)]
+pub struct BindingHover<'a> {
+    pub name: Atom<'a>,
+    /// Span of the identifier declaring the binding.
+    pub declaration: Span,
+    pub flags: SymbolFlags,
+    pub kind: BindingKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirectiveKind {
+    Animate,
+    Bind,
+    Class,
+    Let,
+    On,
+    Style,
+    /// `transition:`
+    Transition,
+    /// `in:`
+    In,
+    /// `out:`
+    Out,
+    Use,
+}
+
+#[derive(Debug)]
+pub struct DirectiveHover<'a> {
+    pub span: Span,
+    pub kind: DirectiveKind,
+    pub name: Atom<'a>,
+    pub modifiers: Vec<&'a str>,
+}
+
+#[derive(Debug)]
+pub struct ComponentHover<'a> {
+    /// Span of the tag name.
+    pub span: Span,
+    pub name: Atom<'a>,
+    /// The module the component is imported from, if it is imported.
+    pub source: Option<Atom<'a>>,
+    /// Names of the props passed to the component.
+    pub props: Vec<Atom<'a>>,
+}
+
+/// Hover information for the node at `offset`.
+///
+/// Directive names and component tags take precedence, anything else is looked
+/// up as a binding.
+pub fn hover<'a>(root: &Root<'a>, semantic: &Semantic<'a>, offset: u32) -> Option<Hover<'a>> {
+    let mut finder = TemplateHover { root, semantic, offset, hover: None };
+    let _ = finder.visit_fragment(&root.fragment);
+    finder.hover.or_else(|| binding_hover(semantic, offset).map(Hover::Binding))
+}
+
+fn binding_hover<'a>(semantic: &Semantic<'a>, offset: u32) -> Option<BindingHover<'a>> {
+    let symbol_id = semantic.symbol_at(offset)?;
+    let symbols = semantic.symbols();
+    Some(BindingHover {
+        name: symbols.get_name(symbol_id).clone(),
+        declaration: symbols.get_span(symbol_id),
+        flags: symbols.get_flag(symbol_id),
+        kind: symbols.get_kind(symbol_id),
+    })
+}
+
+struct TemplateHover<'r, 'a> {
+    root: &'r Root<'a>,
+    semantic: &'r Semantic<'a>,
+    offset: u32,
+    hover: Option<Hover<'a>>,
+}
+
+impl<'r, 'a> TemplateHover<'r, 'a> {
+    fn contains(&self, span: Span) -> bool {
+        span.start <= self.offset && self.offset < span.end
+    }
+
+    fn directive_hover(&self, directive: &DirectiveAttribute<'a>) -> Option<DirectiveHover<'a>> {
+        let (span, kind, name, modifiers) = match directive {
+            DirectiveAttribute::AnimateDirective(directive) => {
+                (directive.span, DirectiveKind::Animate, &directive.name, vec![])
+            }
+            DirectiveAttribute::BindDirective(directive) => {
+                (directive.span, DirectiveKind::Bind, &directive.name, vec![])
+            }
+            DirectiveAttribute::ClassDirective(directive) => {
+                (directive.span, DirectiveKind::Class, &directive.name, vec![])
+            }
+            DirectiveAttribute::LetDirective(directive) => {
+                (directive.span, DirectiveKind::Let, &directive.name, vec![])
+            }
+            DirectiveAttribute::OnDirective(directive) => (
+                directive.span,
+                DirectiveKind::On,
+                &directive.name,
+                directive.modifiers.iter().map(Atom::as_str).collect(),
+            ),
+            DirectiveAttribute::StyleDirective(directive) => (
+                directive.span,
+                DirectiveKind::Style,
+                &directive.name,
+                directive
+                    .modifiers
+                    .iter()
+                    .map(|modifier| match modifier {
+                        StyleDirectiveModifier::Important => "important",
+                    })
+                    .collect(),
+            ),
+            DirectiveAttribute::TransitionDirective(directive) => {
+                let kind = match (directive.intro, directive.outro) {
+                    (true, false) => DirectiveKind::In,
+                    (false, true) => DirectiveKind::Out,
+                    _ => DirectiveKind::Transition,
+                };
+                let modifiers = directive
+                    .modifiers
+                    .iter()
+                    .map(|modifier| match modifier {
+                        TransitionDirectiveModifier::Local => "local",
+                        TransitionDirectiveModifier::Global => "global",
+                    })
+                    .collect();
+                (directive.span, kind, &directive.name, modifiers)
+            }
+            DirectiveAttribute::UseDirective(directive) => {
+                (directive.span, DirectiveKind::Use, &directive.name, vec![])
+            }
+        };
+
+        // Only the `kind:name|modifiers` part, the value is hovered as bindings
+        let source = &self.semantic.source_text()[span.start as usize..span.end as usize];
+        #[allow(clippy::cast_possible_truncation)]
+        let header_end = span.start + source.find('=').unwrap_or(source.len()) as u32;
+        self.contains(Span::new(span.start, header_end)).then(|| DirectiveHover {
+            span,
+            kind,
+            name: name.clone(),
+            modifiers,
+        })
+    }
+
+    fn component_hover(&self, component: &Component<'a>) -> Option<ComponentHover<'a>> {
+        #[allow(clippy::cast_possible_truncation)]
+        let span = Span::new(component.span.start + 1, {
+            component.span.start + 1 + component.name.len() as u32
+        });
+        if !self.contains(span) {
+            return None;
+        }
+        let props = component
+            .attributes
+            .iter()
+            .filter_map(|attribute| match attribute {
+                ElementAttribute::Attribute(attribute) => Some(attribute.name.clone()),
+                ElementAttribute::DirectiveAttribute(DirectiveAttribute::BindDirective(
+                    directive,
+                )) => Some(directive.name.clone()),
+                _ => None,
+            })
+            .collect();
+        Some(ComponentHover {
+            span,
+            name: component.name.clone(),
+            source: self.import_source(span.start),
+            props,
+        })
+    }
+
+    /// Source of the import declaring the binding referenced at `offset`.
+    fn import_source(&self, offset: u32) -> Option<Atom<'a>> {
+        let symbol_id = self.semantic.symbol_at(offset)?;
+        let declaration = self.semantic.symbols().get_span(symbol_id);
+        [&self.root.module, &self.root.instance]
+            .into_iter()
+            .flatten()
+            .flat_map(|script| script.program.body.iter())
+            .find_map(|statement| match statement {
+                Statement::ImportDeclaration(decl)
+                    if decl.span.start <= declaration.start && declaration.end <= decl.span.end =>
+                {
+                    Some(decl.source.value.clone())
+                }
+                _ => None,
+            })
+    }
+}
+
+impl<'r, 'a> Visit<'a> for TemplateHover<'r, 'a> {
+    fn visit_element(&mut self, element: &Element<'a>) -> ControlFlow<()> {
+        if let Element::Component(component) = element {
+            if let Some(hover) = self.component_hover(component) {
+                self.hover = Some(Hover::Component(hover));
+                return ControlFlow::Break(());
+            }
+        }
+        let hover = element
+            .attributes()
+            .iter()
+            .filter_map(ElementAttribute::as_directive_attribute)
+            .find_map(|directive| self.directive_hover(directive));
+        if let Some(hover) = hover {
+            self.hover = Some(Hover::Directive(hover));
+            return ControlFlow::Break(());
+        }
+        walk_element(self, element)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use ssc_parser::Parser;
+    use ssc_semantic::SemanticBuilder;
+
+    use super::*;
+
+    fn hover_at(source: &str, needle: &str, f: impl FnOnce(Hover)) {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty());
+        let semantic = SemanticBuilder::new(source).build(&ret.root).semantic;
+        let offset = u32::try_from(source.find(needle).unwrap()).unwrap();
+        f(hover(&ret.root, &semantic, offset).unwrap());
+    }
+
+    #[test]
+    fn hover_info() {
+        let source = r#"<script>
+    import Card from './Card.svelte';
+    let count = $state(0);
+</script>
+<Card title="hi" bind:open={count}></Card>
+<button on:click|once|preventDefault={() => count++}>{count}</button>"#;
+
+        hover_at(source, "Card title", |hover| {
+            let Hover::Component(component) = hover else { panic!("{hover:?}") };
+            assert_eq!(component.source.unwrap().as_str(), "./Card.svelte");
+            assert_eq!(component.props, ["title", "open"]);
+        });
+
+        hover_at(source, "click", |hover| {
+            let Hover::Directive(directive) = hover else { panic!("{hover:?}") };
+            assert_eq!(directive.kind, DirectiveKind::On);
+            assert_eq!(directive.modifiers, ["once", "preventDefault"]);
+        });
+
+        hover_at(source, "count}</button>", |hover| {
+            let Hover::Binding(binding) = hover else { panic!("{hover:?}") };
+            assert_eq!(binding.kind, BindingKind::State);
+            assert_eq!(
+                &source[binding.declaration.start as usize..],
+                &source[source.find("count =").unwrap()..]
+            );
+        });
+    }
+}
@@ -0,0 +1,16 @@
+//! IDE features for Svelte components, built on the semantic model from
+//! `ssc_semantic`.
+
+mod goto_definition;
+mod hover;
+mod quick_fix;
+mod rename;
+
+pub use crate::{
+    goto_definition::{goto_definition, Definition},
+    hover::{hover, BindingHover, ComponentHover, DirectiveHover, DirectiveKind, Hover},
+    quick_fix::{migrate_event_directive, migrate_slot_element, quick_fixes, QuickFix},
+    rename::{
+        prepare_rename, rename, rename_locations, RenameLocation, RenameLocationKind, TextEdit,
+    },
+};
@@ -0,0 +1,292 @@
+use std::ops::ControlFlow;
+
+use oxc_ast::ast::{BindingPatternKind, Expression, Statement};
+use oxc_span::{GetSpan, Span};
+#[allow(clippy::wildcard_imports)]
+use ssc_ast::ast::*;
+use ssc_ast::{visit::walk, Visit};
+use ssc_semantic::{BindingKind, Semantic};
+
+use crate::TextEdit;
+
+/// Edits fixing a diagnostic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuickFix {
+    pub title: String,
+    pub edits: Vec<TextEdit>,
+}
+
+/// Fixes for the deprecation warnings of runes mode overlapping `span`:
+/// `on:` directives become event attributes and `<slot>`s `{@render}` tags.
+///
+/// Occurrences that can't be migrated without changing the behavior, e.g.
+/// directives with modifiers, get no fix.
+pub fn quick_fixes(root: &Root<'_>, semantic: &Semantic<'_>, span: Span) -> Vec<QuickFix> {
+    if !semantic.is_runes() {
+        return vec![];
+    }
+    let mut finder = QuickFixFinder { root, semantic, span, fixes: vec![] };
+    let _ = finder.visit_fragment(&root.fragment);
+    finder.fixes
+}
+
+/// Rewrite the `on:` directive `directive` of an element with `attributes`
+/// as an event attribute, e.g. `on:click={f}` as `onclick={f}`.
+pub fn migrate_event_directive(
+    directive: &OnDirective<'_>,
+    attributes: &[ElementAttribute<'_>],
+) -> Option<Vec<TextEdit>> {
+    // Forwarded events and modifiers have no attribute equivalent
+    if directive.expression.is_none() || !directive.modifiers.is_empty() {
+        return None;
+    }
+    // An element can only have one attribute per event
+    let attribute_name = format!("on{}", directive.name);
+    let conflicts = attributes.iter().any(|attribute| match attribute {
+        ElementAttribute::Attribute(attribute) => attribute.name == attribute_name.as_str(),
+        ElementAttribute::DirectiveAttribute(DirectiveAttribute::OnDirective(other)) => {
+            other.span != directive.span && other.name == directive.name
+        }
+        _ => false,
+    });
+    if conflicts {
+        return None;
+    }
+    let span = Span::new(directive.span.start, directive.parts.name.end);
+    Some(vec![TextEdit { span, new_text: attribute_name }])
+}
+
+/// Rewrite `slot` as a `{@render}` tag of the snippet prop named after it,
+/// `children` for the default slot, declaring the prop if needed.
+pub fn migrate_slot_element(
+    root: &Root<'_>,
+    semantic: &Semantic<'_>,
+    slot: &SlotElement<'_>,
+) -> Option<Vec<TextEdit>> {
+    let source_text = semantic.source_text();
+    let mut name = "children";
+    for attribute in &slot.attributes {
+        // Slot props would become snippet arguments, which the parent has to
+        // be migrated for
+        let ElementAttribute::Attribute(attribute) = attribute else { return None };
+        if attribute.name != "name" {
+            return None;
+        }
+        let sequence = attribute.value.as_ref().map(|value| value.sequence.as_slice());
+        let Some([AttributeSequenceValue::Text(text)]) = sequence else { return None };
+        name = text.data.as_str();
+    }
+    if !is_identifier(name) {
+        return None;
+    }
+
+    let fallback = match (slot.fragment.nodes.first(), slot.fragment.nodes.last()) {
+        (Some(first), Some(last)) => {
+            Some(&source_text[first.span().start as usize..last.span().end as usize])
+        }
+        _ => None,
+    };
+    let new_text = if let Some(fallback) = fallback.filter(|fallback| !fallback.trim().is_empty()) {
+        format!("{{#if {name}}}{{@render {name}()}}{{:else}}{fallback}{{/if}}")
+    } else {
+        format!("{{@render {name}?.()}}")
+    };
+    let mut edits = vec![TextEdit { span: slot.span, new_text }];
+    edits.extend(declare_prop(root, semantic, name)?);
+    Some(edits)
+}
+
+/// The edits declaring the prop `name` in the `$props()` destructure of the
+/// component, `None` if a binding of another kind is already named so.
+fn declare_prop(root: &Root<'_>, semantic: &Semantic<'_>, name: &str) -> Option<Vec<TextEdit>> {
+    let scopes = semantic.scopes();
+    let symbols = semantic.symbols();
+    let existing = symbols.iter().find(|&symbol_id| {
+        let scope_id = symbols.get_scope_id(symbol_id);
+        symbols.get_name(symbol_id).as_str() == name
+            && scopes.get_parent_id(scope_id) == Some(scopes.root_scope_id())
+    });
+    if let Some(symbol_id) = existing {
+        return matches!(
+            symbols.get_kind(symbol_id),
+            BindingKind::Prop | BindingKind::BindableProp
+        )
+        .then(Vec::new);
+    }
+
+    let Some(instance) = &root.instance else {
+        return Some(vec![TextEdit {
+            span: Span::new(0, 0),
+            new_text: format!("<script>\n\tlet {{ {name} }} = $props();\n</script>\n\n"),
+        }]);
+    };
+    // The `$props()` declaration, with its pattern if it's destructured
+    let props = instance.program.body.iter().find_map(|statement| {
+        let Statement::VariableDeclaration(declaration) = statement else { return None };
+        declaration.declarations.iter().find_map(|declarator| {
+            let Some(Expression::CallExpression(call)) = &declarator.init else { return None };
+            let Expression::Identifier(callee) = &call.callee else { return None };
+            (callee.name == "$props").then_some(match &declarator.id.kind {
+                BindingPatternKind::ObjectPattern(pattern) => Some(pattern),
+                _ => None,
+            })
+        })
+    });
+    let edit = match props {
+        Some(Some(pattern)) => match (&pattern.rest, pattern.properties.last()) {
+            (Some(rest), _) => TextEdit {
+                span: Span::new(rest.span.start, rest.span.start),
+                new_text: format!("{name}, "),
+            },
+            (None, Some(last)) => TextEdit {
+                span: Span::new(last.span.end, last.span.end),
+                new_text: format!(", {name}"),
+            },
+            (None, None) => {
+                let span = Span::new(pattern.span.start + 1, pattern.span.end - 1);
+                TextEdit { span, new_text: format!(" {name} ") }
+            }
+        },
+        // `let props = $props()` can't declare it, and `$props()` can only be
+        // called once
+        Some(None) => return None,
+        None => {
+            // After the opening tag
+            let tag = &semantic.source_text()[instance.span.start as usize..];
+            #[allow(clippy::cast_possible_truncation)]
+            let start = instance.span.start + tag.find('>')? as u32 + 1;
+            TextEdit {
+                span: Span::new(start, start),
+                new_text: format!("\n\tlet {{ {name} }} = $props();"),
+            }
+        }
+    };
+    Some(vec![edit])
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+struct QuickFixFinder<'r, 'a> {
+    root: &'r Root<'a>,
+    semantic: &'r Semantic<'a>,
+    span: Span,
+    fixes: Vec<QuickFix>,
+}
+
+impl<'r, 'a> QuickFixFinder<'r, 'a> {
+    fn overlaps(&self, span: Span) -> bool {
+        span.start <= self.span.end && self.span.start <= span.end
+    }
+}
+
+impl<'r, 'a> Visit<'a> for QuickFixFinder<'r, 'a> {
+    fn visit_element(&mut self, element: &Element<'a>) -> ControlFlow<()> {
+        match element {
+            Element::SlotElement(slot) if self.overlaps(slot.span) => {
+                if let Some(edits) = migrate_slot_element(self.root, self.semantic, slot) {
+                    let title = "Replace `<slot>` with a `{@render}` tag".to_string();
+                    self.fixes.push(QuickFix { title, edits });
+                }
+            }
+            Element::RegularElement(_)
+            | Element::SvelteElement(_)
+            | Element::SvelteBody(_)
+            | Element::SvelteDocument(_)
+            | Element::SvelteWindow(_) => {
+                let attributes = element.attributes();
+                for attribute in attributes {
+                    let Some(DirectiveAttribute::OnDirective(directive)) =
+                        attribute.as_directive_attribute()
+                    else {
+                        continue;
+                    };
+                    if !self.overlaps(directive.span) {
+                        continue;
+                    }
+                    if let Some(edits) = migrate_event_directive(directive, attributes) {
+                        let name = &directive.name;
+                        let title = format!("Replace `on:{name}` with `on{name}`");
+                        self.fixes.push(QuickFix { title, edits });
+                    }
+                }
+            }
+            _ => {}
+        }
+        walk::walk_element(self, element)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use ssc_parser::Parser;
+    use ssc_semantic::SemanticBuilder;
+
+    use super::*;
+
+    fn fix(source: &str, needle: &str) -> Option<String> {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let semantic = SemanticBuilder::new(source).build(&ret.root).semantic;
+        let start = u32::try_from(source.find(needle).unwrap()).unwrap();
+        let mut fixes = quick_fixes(&ret.root, &semantic, Span::new(start, start));
+        assert!(fixes.len() <= 1);
+        let mut edits = fixes.pop()?.edits;
+        let mut output = source.to_string();
+        edits.sort_by_key(|edit| std::cmp::Reverse(edit.span.start));
+        for edit in edits {
+            output.replace_range(edit.span.start as usize..edit.span.end as usize, &edit.new_text);
+        }
+        Some(output)
+    }
+
+    #[test]
+    fn event_directives() {
+        let runes = "<svelte:options runes />";
+        assert_eq!(
+            fix(&format!("{runes}<button on:click={{f}}></button>"), "on:").unwrap(),
+            format!("{runes}<button onclick={{f}}></button>")
+        );
+        assert_eq!(fix(&format!("{runes}<button on:click|once={{f}}></button>"), "on:"), None);
+        assert_eq!(fix(&format!("{runes}<button on:click></button>"), "on:"), None);
+        assert_eq!(
+            fix(&format!("{runes}<button on:click={{f}} on:click={{g}}></button>"), "on:"),
+            None
+        );
+        // Legacy mode has nothing to migrate
+        assert_eq!(fix("<button on:click={f}></button>", "on:"), None);
+    }
+
+    #[test]
+    fn slot_elements() {
+        assert_eq!(
+            fix("<script>\n\tlet { a } = $props();\n</script>\n<slot />", "<slot").unwrap(),
+            "<script>\n\tlet { a, children } = $props();\n</script>\n{@render children?.()}"
+        );
+        assert_eq!(
+            fix(
+                "<script>\n\tlet { ...rest } = $props();\n</script>\n<slot name=\"footer\">none</slot>",
+                "<slot"
+            )
+            .unwrap(),
+            "<script>\n\tlet { footer, ...rest } = $props();\n</script>\n{#if footer}{@render footer()}{:else}none{/if}"
+        );
+        assert_eq!(
+            fix("<script>\n\tlet a = $state(0);\n</script>\n<slot />", "<slot").unwrap(),
+            "<script>\n\tlet { children } = $props();\n\tlet a = $state(0);\n</script>\n{@render children?.()}"
+        );
+        assert_eq!(
+            fix("<svelte:options runes /><slot />", "<slot").unwrap(),
+            "<script>\n\tlet { children } = $props();\n</script>\n\n<svelte:options runes />{@render children?.()}"
+        );
+        assert_eq!(
+            fix("<script>\n\tlet { children } = $props();\n</script>\n<slot item={1} />", "<slot"),
+            None
+        );
+    }
+}
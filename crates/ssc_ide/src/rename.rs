@@ -0,0 +1,365 @@
+use std::ops::ControlFlow;
+
+use oxc_ast::{
+    ast::{
+        AssignmentTargetPropertyIdentifier, BindingPatternKind, BindingProperty, Expression,
+        ObjectProperty,
+    },
+    visit::walk as js_walk,
+    Visit as JsVisit,
+};
+use oxc_span::Span;
+use rustc_hash::FxHashMap;
+#[allow(clippy::wildcard_imports)]
+use ssc_ast::ast::*;
+use ssc_ast::{visit::walk, Visit};
+use ssc_semantic::{Reference, Semantic};
+
+/// How an occurrence of a binding is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenameLocationKind {
+    /// The name on its own, which can be replaced as is.
+    Plain,
+    /// The name doubles as a property key, e.g. `{ a }` or `let { a } = b`.
+    ShorthandProperty,
+    /// The name doubles as the attribute name, e.g. `<input {value}>`.
+    /// The span of the whole attribute is attached.
+    ShorthandAttribute(Span),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenameLocation {
+    pub span: Span,
+    pub kind: RenameLocationKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub span: Span,
+    pub new_text: String,
+}
+
+/// Span of the identifier at `offset` if it can be renamed, i.e. it refers
+/// to a binding declared in the component.
+pub fn prepare_rename(semantic: &Semantic<'_>, offset: u32) -> Option<Span> {
+    let symbols = semantic.symbols();
+    let symbol_id = semantic.symbol_at(offset)?;
+    let declaration = symbols.get_span(symbol_id);
+    if declaration.start <= offset && offset < declaration.end {
+        return Some(declaration);
+    }
+    semantic.reference_at(offset).map(|reference_id| symbols.get_reference(reference_id).span())
+}
+
+/// Every occurrence of the binding at `offset`: its declaration followed by
+/// its references in the scripts and the template, in source order.
+pub fn rename_locations(
+    root: &Root<'_>,
+    semantic: &Semantic<'_>,
+    offset: u32,
+) -> Option<Vec<RenameLocation>> {
+    let symbol_id = semantic.symbol_at(offset)?;
+    let symbols = semantic.symbols();
+
+    let mut shorthands = ShorthandCollector::default();
+    for script in [&root.module, &root.instance].into_iter().flatten() {
+        shorthands.visit_program(&script.program);
+    }
+    let mut template = TemplateShorthands { source_text: semantic.source_text(), shorthands };
+    let _ = template.visit_fragment(&root.fragment);
+    let shorthands = template.shorthands.spans;
+
+    let mut spans = vec![symbols.get_span(symbol_id)];
+    spans.extend(symbols.get_resolved_references(symbol_id).map(Reference::span));
+    spans.sort_unstable_by_key(|span| span.start);
+    spans.dedup();
+    Some(
+        spans
+            .into_iter()
+            .map(|span| RenameLocation {
+                span,
+                kind: shorthands.get(&span).copied().unwrap_or(RenameLocationKind::Plain),
+            })
+            .collect(),
+    )
+}
+
+/// Edits renaming the binding at `offset` to `new_name`, expanding shorthands
+/// so they keep their meaning.
+pub fn rename(
+    root: &Root<'_>,
+    semantic: &Semantic<'_>,
+    offset: u32,
+    new_name: &str,
+) -> Option<Vec<TextEdit>> {
+    let source_text = semantic.source_text();
+    let locations = rename_locations(root, semantic, offset)?;
+    Some(
+        locations
+            .into_iter()
+            .map(|location| {
+                let old_name =
+                    &source_text[location.span.start as usize..location.span.end as usize];
+                match location.kind {
+                    RenameLocationKind::Plain => {
+                        TextEdit { span: location.span, new_text: new_name.to_string() }
+                    }
+                    RenameLocationKind::ShorthandProperty => TextEdit {
+                        span: location.span,
+                        new_text: format!("{old_name}: {new_name}"),
+                    },
+                    RenameLocationKind::ShorthandAttribute(span) => {
+                        TextEdit { span, new_text: format!("{old_name}={{{new_name}}}") }
+                    }
+                }
+            })
+            .collect(),
+    )
+}
+
+#[derive(Default)]
+struct ShorthandCollector {
+    spans: FxHashMap<Span, RenameLocationKind>,
+}
+
+impl<'a> JsVisit<'a> for ShorthandCollector {
+    fn visit_object_property(&mut self, prop: &ObjectProperty<'a>) {
+        if prop.shorthand {
+            if let Expression::Identifier(ident) = &prop.value {
+                self.spans.insert(ident.span, RenameLocationKind::ShorthandProperty);
+            }
+        }
+        js_walk::walk_object_property(self, prop);
+    }
+
+    fn visit_binding_property(&mut self, prop: &BindingProperty<'a>) {
+        if prop.shorthand {
+            let ident = match &prop.value.kind {
+                BindingPatternKind::BindingIdentifier(ident) => Some(ident),
+                BindingPatternKind::AssignmentPattern(pattern) => match &pattern.left.kind {
+                    BindingPatternKind::BindingIdentifier(ident) => Some(ident),
+                    _ => None,
+                },
+                _ => None,
+            };
+            if let Some(ident) = ident {
+                self.spans.insert(ident.span, RenameLocationKind::ShorthandProperty);
+            }
+        }
+        js_walk::walk_binding_property(self, prop);
+    }
+
+    fn visit_assignment_target_property_identifier(
+        &mut self,
+        ident: &AssignmentTargetPropertyIdentifier<'a>,
+    ) {
+        self.spans.insert(ident.binding.span, RenameLocationKind::ShorthandProperty);
+        js_walk::walk_assignment_target_property_identifier(self, ident);
+    }
+}
+
+/// Finds shorthands in the template, including in its expressions.
+struct TemplateShorthands<'s> {
+    source_text: &'s str,
+    shorthands: ShorthandCollector,
+}
+
+impl<'s> TemplateShorthands<'s> {
+    fn visit_attribute_value(&mut self, value: &AttributeValue<'_>) {
+        for part in &value.sequence {
+            if let AttributeSequenceValue::ExpressionTag(tag) = part {
+                self.shorthands.visit_expression(&tag.expression);
+            }
+        }
+    }
+
+    fn visit_attribute(&mut self, attribute: &ElementAttribute<'_>) {
+        match attribute {
+            ElementAttribute::Attribute(attribute) => {
+                let Some(value) = &attribute.value else { return };
+                let is_shorthand = self.source_text
+                    [attribute.span.start as usize..attribute.span.end as usize]
+                    .starts_with('{');
+                if let [AttributeSequenceValue::ExpressionTag(tag)] = value.sequence.as_slice() {
+                    if let (true, Expression::Identifier(ident)) = (is_shorthand, &tag.expression) {
+                        self.shorthands.spans.insert(
+                            ident.span,
+                            RenameLocationKind::ShorthandAttribute(attribute.span),
+                        );
+                    }
+                }
+                self.visit_attribute_value(value);
+            }
+            ElementAttribute::SpreadAttribute(attribute) => {
+                self.shorthands.visit_expression(&attribute.expression);
+            }
+            ElementAttribute::DirectiveAttribute(directive) => match directive {
+                DirectiveAttribute::AnimateDirective(AnimateDirective { expression, .. })
+                | DirectiveAttribute::OnDirective(OnDirective { expression, .. })
+                | DirectiveAttribute::TransitionDirective(TransitionDirective {
+                    expression, ..
+                })
+                | DirectiveAttribute::UseDirective(UseDirective { expression, .. }) => {
+                    if let Some(expression) = expression {
+                        self.shorthands.visit_expression(expression);
+                    }
+                }
+                DirectiveAttribute::BindDirective(directive) => {
+                    if let BindDirectiveExpression::MemberExpression(member) = &directive.expression
+                    {
+                        self.shorthands.visit_member_expression(member);
+                    }
+                }
+                DirectiveAttribute::ClassDirective(directive) => {
+                    self.shorthands.visit_expression(&directive.expression);
+                }
+                DirectiveAttribute::StyleDirective(directive) => {
+                    if let Some(value) = &directive.value {
+                        self.visit_attribute_value(value);
+                    }
+                }
+                DirectiveAttribute::LetDirective(_) => {}
+            },
+        }
+    }
+}
+
+impl<'s, 'a> Visit<'a> for TemplateShorthands<'s> {
+    fn visit_expression_tag(&mut self, tag: &ExpressionTag<'a>) -> ControlFlow<()> {
+        self.shorthands.visit_expression(&tag.expression);
+        ControlFlow::Continue(())
+    }
+
+    fn visit_html_tag(&mut self, tag: &HtmlTag<'a>) -> ControlFlow<()> {
+        self.shorthands.visit_expression(&tag.expression);
+        ControlFlow::Continue(())
+    }
+
+    fn visit_const_tag(&mut self, tag: &ConstTag<'a>) -> ControlFlow<()> {
+        self.shorthands.visit_variable_declaration(&tag.declaration);
+        ControlFlow::Continue(())
+    }
+
+    fn visit_render_tag(&mut self, tag: &RenderTag<'a>) -> ControlFlow<()> {
+        match &tag.expression {
+            RenderTagExpression::Call(call) | RenderTagExpression::Chain(call) => {
+                self.shorthands.visit_call_expression(call);
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn visit_element(&mut self, element: &Element<'a>) -> ControlFlow<()> {
+        let (attributes, expression) = match element {
+            Element::Component(element) => (&element.attributes, None),
+            Element::TitleElement(element) => (&element.attributes, None),
+            Element::SlotElement(element) => (&element.attributes, None),
+            Element::RegularElement(element) => (&element.attributes, None),
+            Element::SvelteBody(element) => (&element.attributes, None),
+            Element::SvelteBoundary(element) => (&element.attributes, None),
+            Element::SvelteComponent(element) => (&element.attributes, Some(&element.expression)),
+            Element::SvelteDocument(element) => (&element.attributes, None),
+            Element::SvelteElement(element) => (&element.attributes, Some(&element.expression)),
+            Element::SvelteFragment(element) => (&element.attributes, None),
+            Element::SvelteHead(element) => (&element.attributes, None),
+            Element::SvelteOptionsRaw(element) => (&element.attributes, None),
+            Element::SvelteSelf(element) => (&element.attributes, None),
+            Element::SvelteWindow(element) => (&element.attributes, None),
+        };
+        if let Some(expression) = expression {
+            self.shorthands.visit_expression(expression);
+        }
+        for attribute in attributes {
+            self.visit_attribute(attribute);
+        }
+        walk::walk_element(self, element)
+    }
+
+    fn visit_each_block(&mut self, block: &EachBlock<'a>) -> ControlFlow<()> {
+        self.shorthands.visit_expression(&block.expression);
+        self.shorthands.visit_binding_pattern(&block.context);
+        if let Some(key) = &block.key {
+            self.shorthands.visit_expression(key);
+        }
+        walk::walk_each_block(self, block)
+    }
+
+    fn visit_if_block(&mut self, block: &IfBlock<'a>) -> ControlFlow<()> {
+        self.shorthands.visit_expression(&block.test);
+        walk::walk_if_block(self, block)
+    }
+
+    fn visit_await_block(&mut self, block: &AwaitBlock<'a>) -> ControlFlow<()> {
+        self.shorthands.visit_expression(&block.expression);
+        for pattern in [&block.value, &block.error].into_iter().flatten() {
+            self.shorthands.visit_binding_pattern(pattern);
+        }
+        walk::walk_await_block(self, block)
+    }
+
+    fn visit_key_block(&mut self, block: &KeyBlock<'a>) -> ControlFlow<()> {
+        self.shorthands.visit_expression(&block.expression);
+        walk::walk_key_block(self, block)
+    }
+
+    fn visit_snippet_block(&mut self, block: &SnippetBlock<'a>) -> ControlFlow<()> {
+        for parameter in &block.parameters {
+            self.shorthands.visit_binding_pattern(parameter);
+        }
+        walk::walk_snippet_block(self, block)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use ssc_parser::Parser;
+    use ssc_semantic::SemanticBuilder;
+
+    use super::*;
+
+    fn apply(source: &str, mut edits: Vec<TextEdit>) -> String {
+        let mut output = source.to_string();
+        edits.sort_by_key(|edit| std::cmp::Reverse(edit.span.start));
+        for edit in edits {
+            output.replace_range(edit.span.start as usize..edit.span.end as usize, &edit.new_text);
+        }
+        output
+    }
+
+    #[test]
+    fn rename_binding() {
+        let source = r"<script>
+    let value = $state('');
+    const data = { value };
+    let { value: other } = data;
+    $effect(() => { ({ value } = data); });
+</script>
+<input {value} bind:value={value}>
+<p title={value}>{value.length}</p>";
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty());
+        let semantic = SemanticBuilder::new(source).build(&ret.root).semantic;
+
+        let offset = u32::try_from(source.find("value =").unwrap()).unwrap();
+        assert_eq!(prepare_rename(&semantic, offset), Some(Span::new(offset, offset + 5)));
+        assert_eq!(rename_locations(&ret.root, &semantic, offset).unwrap().len(), 7);
+
+        let edits = rename(&ret.root, &semantic, offset + 2, "text").unwrap();
+        assert_eq!(
+            apply(source, edits),
+            r"<script>
+    let text = $state('');
+    const data = { value: text };
+    let { value: other } = data;
+    $effect(() => { ({ value: text } = data); });
+</script>
+<input value={text} bind:value={text}>
+<p title={text}>{text.length}</p>"
+        );
+
+        let offset = u32::try_from(source.find("$state").unwrap()).unwrap();
+        assert_eq!(prepare_rename(&semantic, offset), None);
+    }
+}
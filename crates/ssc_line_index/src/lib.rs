@@ -0,0 +1,225 @@
+//! # Line index
+//!
+//! Converts between the byte offsets of a source and zero based lines and
+//! columns, in UTF-8, UTF-16 or UTF-32 code units. The index is built once
+//! per source, then shared by diagnostics rendering, editor APIs and source
+//! maps, instead of each of them scanning the source for line starts.
+//!
+//! Lines end at `\n`, a `\r` before it isn't part of the line.
+
+use std::ops::Range;
+
+use rustc_hash::FxHashMap;
+
+/// The unit of columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// Bytes.
+    Utf8,
+    /// UTF-16 code units, like source maps, editors and JavaScript strings.
+    Utf16,
+    /// Characters.
+    Utf32,
+}
+
+/// Zero based line and column of an offset.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LineCol {
+    pub line: u32,
+    pub column: u32,
+}
+
+/// A non-ASCII character, whose length depends on the [`Encoding`].
+#[derive(Debug, Clone, Copy)]
+struct WideChar {
+    /// Offset from the start of its line.
+    start: u32,
+    /// Length in bytes.
+    len: u8,
+}
+
+impl WideChar {
+    fn len(self, encoding: Encoding) -> u32 {
+        match encoding {
+            Encoding::Utf8 => u32::from(self.len),
+            Encoding::Utf16 if self.len == 4 => 2,
+            Encoding::Utf16 | Encoding::Utf32 => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LineIndex {
+    /// Offset of the first byte of every line.
+    line_starts: Vec<u32>,
+    /// Offset of the end of every line, before its line terminator.
+    line_ends: Vec<u32>,
+    /// The non-ASCII characters of the lines that have any, in order.
+    wide_chars: FxHashMap<u32, Vec<WideChar>>,
+    len: u32,
+}
+
+impl LineIndex {
+    /// Index the lines of `text`, which must be shorter than 4 GiB, like the
+    /// sources spans point into.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn new(text: &str) -> Self {
+        let bytes = text.as_bytes();
+        let mut index = Self {
+            line_starts: vec![0],
+            line_ends: vec![],
+            wide_chars: FxHashMap::default(),
+            len: text.len() as u32,
+        };
+        let mut line_start = 0;
+        let mut line_wide_chars = vec![];
+        for (offset, c) in text.char_indices() {
+            let offset = offset as u32;
+            if c == '\n' {
+                let end = if bytes.get(offset.wrapping_sub(1) as usize) == Some(&b'\r') {
+                    offset - 1
+                } else {
+                    offset
+                };
+                index.end_line(end.max(line_start), &mut line_wide_chars);
+                line_start = offset + 1;
+                index.line_starts.push(line_start);
+            } else if !c.is_ascii() {
+                let len = c.len_utf8() as u8;
+                line_wide_chars.push(WideChar { start: offset - line_start, len });
+            }
+        }
+        index.end_line(index.len, &mut line_wide_chars);
+        index
+    }
+
+    fn end_line(&mut self, end: u32, wide_chars: &mut Vec<WideChar>) {
+        #[allow(clippy::cast_possible_truncation)]
+        let line = self.line_ends.len() as u32;
+        self.line_ends.push(end);
+        if !wide_chars.is_empty() {
+            self.wide_chars.insert(line, std::mem::take(wide_chars));
+        }
+    }
+
+    /// Length of the indexed text, in bytes.
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// The offsets of the zero based `line`, without its line terminator.
+    pub fn line_range(&self, line: u32) -> Option<Range<u32>> {
+        let start = *self.line_starts.get(line as usize)?;
+        Some(start..self.line_ends[line as usize])
+    }
+
+    /// Line and column of `offset`. Offsets past the end are clamped to it,
+    /// and offsets inside of a character to its start.
+    pub fn line_col(&self, offset: u32, encoding: Encoding) -> LineCol {
+        let offset = offset.min(self.len);
+        let line = self.line_starts.partition_point(|start| *start <= offset) - 1;
+        #[allow(clippy::cast_possible_truncation)]
+        let line = line as u32;
+        let byte_column = offset - self.line_starts[line as usize];
+        let mut column = byte_column;
+        for c in self.wide_chars.get(&line).into_iter().flatten() {
+            if c.start >= byte_column {
+                break;
+            }
+            if c.start + u32::from(c.len) > byte_column {
+                column -= byte_column - c.start;
+                break;
+            }
+            column -= u32::from(c.len) - c.len(encoding);
+        }
+        LineCol { line, column }
+    }
+
+    /// The offset of `line_col`, `None` if the line doesn't exist, or the
+    /// column is past its end or inside of a character.
+    pub fn offset(&self, line_col: LineCol, encoding: Encoding) -> Option<u32> {
+        match self.resolve(line_col, encoding)? {
+            (offset, true) => Some(offset),
+            (_, false) => None,
+        }
+    }
+
+    /// The offset of `line_col`, clamped to its line, or to the end of the
+    /// text when the line doesn't exist. Columns inside of a character are
+    /// moved to its end.
+    pub fn clamped_offset(&self, line_col: LineCol, encoding: Encoding) -> u32 {
+        self.resolve(line_col, encoding).map_or(self.len, |(offset, _)| offset)
+    }
+
+    /// The offset of `line_col`, with whether it's exact, see
+    /// [`LineIndex::clamped_offset`].
+    fn resolve(&self, line_col: LineCol, encoding: Encoding) -> Option<(u32, bool)> {
+        let range = self.line_range(line_col.line)?;
+        let mut remaining = line_col.column;
+        let mut offset = range.start;
+        for c in self.wide_chars.get(&line_col.line).into_iter().flatten() {
+            let ascii = range.start + c.start - offset;
+            if remaining <= ascii {
+                return Some((offset + remaining, true));
+            }
+            remaining -= ascii;
+            offset = range.start + c.start + u32::from(c.len);
+            if remaining < c.len(encoding) {
+                return Some((offset, false));
+            }
+            remaining -= c.len(encoding);
+        }
+        let end = offset.saturating_add(remaining);
+        Some(if end > range.end { (range.end, false) } else { (end, true) })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn line_index() {
+        let text = "<p>\r\n  é😀x\n</p>";
+        let index = LineIndex::new(text);
+        assert_eq!(index.line_count(), 3);
+        assert_eq!(index.line_range(0), Some(0..3));
+        assert_eq!(index.line_range(1), Some(5..14));
+        assert_eq!(index.line_range(3), None);
+
+        let x = u32::try_from(text.find('x').unwrap()).unwrap();
+        for (encoding, column) in [(Encoding::Utf8, 8), (Encoding::Utf16, 5), (Encoding::Utf32, 4)]
+        {
+            let line_col = LineCol { line: 1, column };
+            assert_eq!(index.line_col(x, encoding), line_col);
+            assert_eq!(index.offset(line_col, encoding), Some(x));
+        }
+        assert_eq!(index.line_col(100, Encoding::Utf16), LineCol { line: 2, column: 4 });
+        // Inside of `😀`
+        assert_eq!(index.line_col(x - 1, Encoding::Utf16), LineCol { line: 1, column: 3 });
+        assert_eq!(index.offset(LineCol { line: 1, column: 4 }, Encoding::Utf16), None);
+        assert_eq!(index.clamped_offset(LineCol { line: 1, column: 4 }, Encoding::Utf16), x);
+
+        // Past the end of the line, before `\r`
+        assert_eq!(index.offset(LineCol { line: 0, column: 4 }, Encoding::Utf16), None);
+        assert_eq!(index.clamped_offset(LineCol { line: 0, column: 4 }, Encoding::Utf8), 3);
+        assert_eq!(
+            index.clamped_offset(LineCol { line: 3, column: 0 }, Encoding::Utf8),
+            index.len()
+        );
+        assert_eq!(index.offset(LineCol { line: 2, column: 4 }, Encoding::Utf8), Some(index.len()));
+
+        let index = LineIndex::new("");
+        assert!(index.is_empty());
+        assert_eq!(index.line_count(), 1);
+        assert_eq!(index.line_col(0, Encoding::Utf8), LineCol::default());
+    }
+}
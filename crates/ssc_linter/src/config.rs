@@ -0,0 +1,50 @@
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+
+use crate::rules::{self, Rule};
+
+/// What to do with the problems a rule finds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleSeverity {
+    /// Don't run the rule.
+    Off,
+    /// Report its problems as warnings.
+    Warn,
+    /// Report its problems as errors.
+    Error,
+}
+
+/// The configuration of the [`Linter`], usually read from a JSON file, e.g.
+/// `{"rules": {"no_at_html_tags": "off", "no_target_blank": "error"}}`.
+///
+/// Rules missing from `rules` run with their default severity.
+///
+/// [`Linter`]: crate::Linter
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LintConfig {
+    #[serde(default)]
+    pub rules: FxHashMap<String, RuleSeverity>,
+}
+
+impl LintConfig {
+    /// Parse a configuration file.
+    ///
+    /// # Errors
+    ///
+    /// If `json` isn't a configuration, or configures a rule that doesn't
+    /// exist.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let config: Self = serde_json::from_str(json).map_err(|error| error.to_string())?;
+        if let Some(name) = config.rules.keys().find(|name| rules::find(name).is_none()) {
+            return Err(format!("Unknown rule `{name}`"));
+        }
+        Ok(config)
+    }
+
+    /// The severity of `rule`, the configured one or its default.
+    pub fn severity(&self, rule: &dyn Rule) -> RuleSeverity {
+        self.rules.get(rule.name()).copied().unwrap_or_else(|| rule.default_severity())
+    }
+}
@@ -0,0 +1,42 @@
+use oxc_diagnostics::{OxcDiagnostic, Severity};
+use ssc_ast::ast::Root;
+use ssc_semantic::Semantic;
+
+use crate::{Fix, Message};
+
+/// What a rule runs on, and collects the messages it reports.
+pub struct LintContext<'s, 'a> {
+    root: &'s Root<'a>,
+    semantic: &'s Semantic<'a>,
+    severity: Severity,
+    messages: Vec<Message>,
+}
+
+impl<'s, 'a> LintContext<'s, 'a> {
+    pub(crate) fn new(root: &'s Root<'a>, semantic: &'s Semantic<'a>, severity: Severity) -> Self {
+        Self { root, semantic, severity, messages: vec![] }
+    }
+
+    pub fn root(&self) -> &'s Root<'a> {
+        self.root
+    }
+
+    pub fn semantic(&self) -> &'s Semantic<'a> {
+        self.semantic
+    }
+
+    pub fn source_text(&self) -> &'a str {
+        self.semantic.source_text()
+    }
+
+    /// Report `diagnostic` with the severity configured for the rule, along
+    /// with the fix of the problem if there's one.
+    pub fn report(&mut self, diagnostic: OxcDiagnostic, fix: Option<Fix>) {
+        let error = diagnostic.with_severity(self.severity).into();
+        self.messages.push(Message::new(error, fix));
+    }
+
+    pub(crate) fn into_messages(self) -> Vec<Message> {
+        self.messages
+    }
+}
@@ -0,0 +1,110 @@
+use std::borrow::Cow;
+
+use oxc_diagnostics::Error;
+use oxc_span::Span;
+
+/// A replacement of the source text fixing the problem reported by a
+/// [`Message`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    pub span: Span,
+    pub content: String,
+}
+
+impl Fix {
+    /// Replace the text of `span` with `content`.
+    pub fn replace<S: Into<String>>(span: Span, content: S) -> Self {
+        Self { span, content: content.into() }
+    }
+
+    /// Insert `content` at `offset`.
+    pub fn insert<S: Into<String>>(offset: u32, content: S) -> Self {
+        Self::replace(Span::new(offset, offset), content)
+    }
+}
+
+/// A diagnostic, with the fix that can be applied without a human deciding
+/// how, if there's one.
+#[derive(Debug)]
+pub struct Message {
+    pub error: Error,
+    pub fix: Option<Fix>,
+}
+
+impl Message {
+    pub fn new(error: Error, fix: Option<Fix>) -> Self {
+        Self { error, fix }
+    }
+}
+
+impl From<Error> for Message {
+    fn from(error: Error) -> Self {
+        Self::new(error, None)
+    }
+}
+
+#[derive(Debug)]
+pub struct FixResult<'a> {
+    /// Whether any fix was applied.
+    pub fixed: bool,
+    pub fixed_code: Cow<'a, str>,
+    /// The messages whose fix wasn't applied, or that have none.
+    pub messages: Vec<Message>,
+}
+
+/// Applies the fixes of messages to the source text they were reported on.
+pub struct Fixer<'a> {
+    source_text: &'a str,
+    messages: Vec<Message>,
+}
+
+impl<'a> Fixer<'a> {
+    pub fn new(source_text: &'a str, messages: Vec<Message>) -> Self {
+        Self { source_text, messages }
+    }
+
+    /// Apply the fixes in source order. A fix overlapping one applied before
+    /// it is skipped, its message is kept for the next run.
+    pub fn fix(self) -> FixResult<'a> {
+        let mut fixes = self
+            .messages
+            .iter()
+            .enumerate()
+            .filter_map(|(index, message)| Some((index, message.fix.as_ref()?)))
+            .filter(|(_, fix)| {
+                fix.span.start <= fix.span.end && fix.span.end as usize <= self.source_text.len()
+            })
+            .collect::<Vec<_>>();
+        if fixes.is_empty() {
+            return FixResult {
+                fixed: false,
+                fixed_code: Cow::Borrowed(self.source_text),
+                messages: self.messages,
+            };
+        }
+        fixes.sort_by_key(|(_, fix)| (fix.span.start, fix.span.end));
+
+        let mut fixed_code = String::with_capacity(self.source_text.len());
+        let mut applied = vec![false; self.messages.len()];
+        let mut last_end = 0;
+        for (index, fix) in fixes {
+            let start = fix.span.start as usize;
+            if start < last_end {
+                continue;
+            }
+            fixed_code.push_str(&self.source_text[last_end..start]);
+            fixed_code.push_str(&fix.content);
+            last_end = fix.span.end as usize;
+            applied[index] = true;
+        }
+        fixed_code.push_str(&self.source_text[last_end..]);
+
+        let messages = self
+            .messages
+            .into_iter()
+            .zip(applied)
+            .filter_map(|(message, applied)| (!applied).then_some(message))
+            .collect();
+        FixResult { fixed: true, fixed_code: Cow::Owned(fixed_code), messages }
+    }
+}
@@ -0,0 +1,144 @@
+//! # SSC Linter
+//!
+//! Lint rules for Svelte components, run on the AST and the semantic model
+//! from `ssc_semantic`. Rules are selected, and their severity set, by a
+//! [`LintConfig`]. The problems that can be fixed without a human deciding
+//! how come with a [`Fix`], which the [`Fixer`] applies to the source text.
+
+mod config;
+mod context;
+mod fixer;
+pub mod rules;
+
+use oxc_diagnostics::Severity;
+use ssc_ast::ast::Root;
+use ssc_semantic::Semantic;
+
+use crate::rules::RULES;
+pub use crate::{
+    config::{LintConfig, RuleSeverity},
+    context::LintContext,
+    fixer::{Fix, FixResult, Fixer, Message},
+};
+
+pub struct Linter {
+    config: LintConfig,
+}
+
+impl Linter {
+    pub fn new(config: LintConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run the enabled rules on a component, returning their messages in
+    /// source order.
+    pub fn run<'a>(&self, root: &Root<'a>, semantic: &Semantic<'a>) -> Vec<Message> {
+        let ignores = root.svelte_ignores();
+        let mut messages = vec![];
+        for rule in RULES {
+            let severity = match self.config.severity(*rule) {
+                RuleSeverity::Off => continue,
+                RuleSeverity::Warn => Severity::Warning,
+                RuleSeverity::Error => Severity::Error,
+            };
+            let mut ctx = LintContext::new(root, semantic, severity);
+            rule.run(&mut ctx);
+            let reported = ctx.into_messages().into_iter();
+            messages.extend(reported.filter(|message| !ignores.is_error_ignored(&message.error)));
+        }
+        messages.sort_by_key(|message| {
+            message.error.labels().and_then(|mut labels| labels.next()).map(|label| label.offset())
+        });
+        messages
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_diagnostics::{OxcDiagnostic, Severity};
+    use ssc_parser::Parser;
+    use ssc_semantic::SemanticBuilder;
+
+    use super::*;
+
+    fn lint(source_text: &str, config: LintConfig) -> Vec<Message> {
+        let allocator = Allocator::default();
+        let root = Parser::new(&allocator, source_text).parse().root;
+        let semantic = SemanticBuilder::new(source_text).build(&root).semantic;
+        Linter::new(config).run(&root, &semantic)
+    }
+
+    fn codes(messages: &[Message]) -> Vec<String> {
+        messages
+            .iter()
+            .map(|message| {
+                let diagnostic = message.error.downcast_ref::<OxcDiagnostic>().unwrap();
+                diagnostic.code.number.as_deref().unwrap().to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rules() {
+        let source_text = r#"<div /><a href="/" target="_blank">a</a><a target="_blank" rel="help">b</a>
+<iframe srcdoc={html} title="t"></iframe>{@html html}<svg><path /></svg><input />"#;
+        let messages = lint(source_text, LintConfig::default());
+        assert_eq!(
+            codes(&messages),
+            [
+                "self_closing_tags",
+                "no_target_blank",
+                "no_target_blank",
+                "no_unsandboxed_srcdoc",
+                "no_at_html_tags"
+            ]
+        );
+        assert!(messages.iter().all(|message| message.error.severity() == Some(Severity::Warning)));
+        assert!(messages[2].fix.is_none() && messages[4].fix.is_none());
+
+        let ret = Fixer::new(source_text, messages).fix();
+        assert!(ret.fixed);
+        assert_eq!(
+            ret.fixed_code,
+            r#"<div></div><a href="/" target="_blank" rel="noopener noreferrer">a</a><a target="_blank" rel="help">b</a>
+<iframe sandbox srcdoc={html} title="t"></iframe>{@html html}<svg><path /></svg><input />"#
+        );
+        assert_eq!(codes(&ret.messages), ["no_target_blank", "no_at_html_tags"]);
+    }
+
+    #[test]
+    fn overlapping_fixes() {
+        let source_text = "abc";
+        let messages = [(0, 2, "x"), (1, 3, "y"), (3, 3, "z")]
+            .map(|(start, end, content)| {
+                let error = oxc_diagnostics::OxcDiagnostic::warn(content).into();
+                Message::new(error, Some(Fix::replace(oxc_span::Span::new(start, end), content)))
+            })
+            .into();
+        let ret = Fixer::new(source_text, messages).fix();
+        assert_eq!(ret.fixed_code, "xcz");
+        assert_eq!(ret.messages.len(), 1);
+        assert_eq!(ret.messages[0].error.to_string(), "y");
+    }
+
+    #[test]
+    fn config() {
+        let config = LintConfig::from_json(
+            r#"{"rules": {"no_at_html_tags": "error", "self_closing_tags": "off"}}"#,
+        )
+        .unwrap();
+        let messages = lint("<div />{@html a}", config);
+        assert_eq!(codes(&messages), ["no_at_html_tags"]);
+        assert_eq!(messages[0].error.severity(), Some(Severity::Error));
+        let source_text = "<!-- svelte-ignore no_at_html_tags -->{@html a}";
+        assert!(lint(source_text, LintConfig::default()).is_empty());
+
+        assert_eq!(
+            LintConfig::from_json(r#"{"rules": {"no_html": "off"}}"#).unwrap_err(),
+            "Unknown rule `no_html`"
+        );
+        assert!(LintConfig::from_json(r#"{"rules": {"no_at_html_tags": "deny"}}"#).is_err());
+        assert!(LintConfig::from_json(r#"{"plugins": []}"#).is_err());
+    }
+}
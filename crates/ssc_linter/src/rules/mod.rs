@@ -0,0 +1,64 @@
+//! The lint rules
+//!
+//! Each rule is named after the code of its messages, set with
+//! `OxcDiagnostic::with_error_code("svelte", <name>)`, and the configuration
+//! refers to it by that name.
+
+mod no_at_html_tags;
+mod no_target_blank;
+mod no_unsandboxed_srcdoc;
+mod self_closing_tags;
+
+use ssc_ast::ast::{Attribute, AttributeSequenceValue, ElementAttribute};
+
+pub use self::{
+    no_at_html_tags::NoAtHtmlTags, no_target_blank::NoTargetBlank,
+    no_unsandboxed_srcdoc::NoUnsandboxedSrcdoc, self_closing_tags::SelfClosingTags,
+};
+use crate::{LintContext, RuleSeverity};
+
+pub trait Rule: Sync {
+    /// The name of the rule, and the code of its messages.
+    fn name(&self) -> &'static str;
+
+    /// The severity of the rule when the configuration doesn't set one.
+    fn default_severity(&self) -> RuleSeverity {
+        RuleSeverity::Warn
+    }
+
+    /// Report the problems found in the component of `ctx`.
+    fn run(&self, ctx: &mut LintContext<'_, '_>);
+}
+
+/// Every rule, by name.
+pub static RULES: &[&dyn Rule] =
+    &[&NoAtHtmlTags, &NoTargetBlank, &NoUnsandboxedSrcdoc, &SelfClosingTags];
+
+/// The rule named `name`.
+pub fn find(name: &str) -> Option<&'static dyn Rule> {
+    RULES.iter().copied().find(|rule| rule.name() == name)
+}
+
+fn find_attribute<'b, 'a>(
+    attributes: &'b [ElementAttribute<'a>],
+    name: &str,
+) -> Option<&'b Attribute<'a>> {
+    attributes.iter().find_map(|attribute| match attribute {
+        ElementAttribute::Attribute(attribute) if attribute.name == name => Some(attribute),
+        _ => None,
+    })
+}
+
+/// The value of `attribute` if it's text, `None` if it's an expression or
+/// has no value.
+fn static_value<'b>(attribute: &'b Attribute) -> Option<&'b str> {
+    match attribute.value.as_ref()?.sequence.as_slice() {
+        [AttributeSequenceValue::Text(text)] => Some(text.data.as_str()),
+        _ => None,
+    }
+}
+
+/// Whether a spread may set any of the `attributes`.
+fn has_spread(attributes: &[ElementAttribute]) -> bool {
+    attributes.iter().any(|attribute| matches!(attribute, ElementAttribute::SpreadAttribute(_)))
+}
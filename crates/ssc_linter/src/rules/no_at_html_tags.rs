@@ -0,0 +1,30 @@
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_span::Span;
+use ssc_ast::ast::HtmlTag;
+
+use super::Rule;
+use crate::LintContext;
+
+fn no_at_html_tags(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::warn("`{@html}` inserts its markup as is, running the scripts injected into it")
+        .with_error_code("svelte", "no_at_html_tags")
+        .with_label(span)
+        .with_help("Render the content as text, or sanitize it first")
+}
+
+/// `{@html}` tags, a cross-site scripting risk unless their content is
+/// trusted.
+pub struct NoAtHtmlTags;
+
+impl Rule for NoAtHtmlTags {
+    fn name(&self) -> &'static str {
+        "no_at_html_tags"
+    }
+
+    fn run(&self, ctx: &mut LintContext<'_, '_>) {
+        let root = ctx.root();
+        for found in root.find_all::<HtmlTag>() {
+            ctx.report(no_at_html_tags(found.node.span), None);
+        }
+    }
+}
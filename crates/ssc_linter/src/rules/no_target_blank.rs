@@ -0,0 +1,60 @@
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_span::Span;
+use ssc_ast::ast::RegularElement;
+
+use super::{find_attribute, has_spread, static_value, Rule};
+use crate::{Fix, LintContext};
+
+fn no_target_blank(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::warn(
+        "Using `target=\"_blank\"` without `rel=\"noopener noreferrer\"` lets the opened page access `window.opener`",
+    )
+    .with_error_code("svelte", "no_target_blank")
+    .with_label(span)
+    .with_help("Add `noopener` to the `rel` attribute")
+}
+
+/// Links opening a new tab without `rel="noopener"`, fixed by adding the
+/// attribute if the link has no `rel` to merge with.
+pub struct NoTargetBlank;
+
+impl Rule for NoTargetBlank {
+    fn name(&self) -> &'static str {
+        "no_target_blank"
+    }
+
+    fn run(&self, ctx: &mut LintContext<'_, '_>) {
+        let root = ctx.root();
+        for found in root.find_all::<RegularElement>() {
+            let element = found.node;
+            let attributes = &element.attributes;
+            if !matches!(element.name.as_str(), "a" | "area") || has_spread(attributes) {
+                continue;
+            }
+            let Some(target) = find_attribute(attributes, "target") else { continue };
+            if !static_value(target).is_some_and(|value| value.eq_ignore_ascii_case("_blank")) {
+                continue;
+            }
+            let Some(rel) = find_attribute(attributes, "rel") else {
+                let fix = Fix::insert(target.span.end, " rel=\"noopener noreferrer\"");
+                ctx.report(no_target_blank(target.span), Some(fix));
+                continue;
+            };
+            let value = match &rel.value {
+                None => "",
+                Some(_) => match static_value(rel) {
+                    Some(value) => value,
+                    // A dynamic `rel` may set `noopener`
+                    None => continue,
+                },
+            };
+            let is_safe = value.split_ascii_whitespace().any(|keyword| {
+                keyword.eq_ignore_ascii_case("noopener")
+                    || keyword.eq_ignore_ascii_case("noreferrer")
+            });
+            if !is_safe {
+                ctx.report(no_target_blank(target.span), None);
+            }
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_span::Span;
+use ssc_ast::ast::RegularElement;
+
+use super::{find_attribute, has_spread, static_value, Rule};
+use crate::{Fix, LintContext};
+
+fn no_unsandboxed_srcdoc(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::warn(
+        "A dynamic `srcdoc` renders its markup with the privileges of the page unless the `<iframe>` is sandboxed",
+    )
+    .with_error_code("svelte", "no_unsandboxed_srcdoc")
+    .with_label(span)
+    .with_help("Add the `sandbox` attribute, allowing only what the content needs")
+}
+
+/// `<iframe>`s with a dynamic `srcdoc` and no `sandbox`, fixed by adding an
+/// empty `sandbox`, the most restrictive one.
+pub struct NoUnsandboxedSrcdoc;
+
+impl Rule for NoUnsandboxedSrcdoc {
+    fn name(&self) -> &'static str {
+        "no_unsandboxed_srcdoc"
+    }
+
+    fn run(&self, ctx: &mut LintContext<'_, '_>) {
+        let root = ctx.root();
+        for found in root.find_all::<RegularElement>() {
+            let element = found.node;
+            let attributes = &element.attributes;
+            if element.name != "iframe" || has_spread(attributes) {
+                continue;
+            }
+            let Some(srcdoc) = find_attribute(attributes, "srcdoc") else { continue };
+            if srcdoc.value.is_some()
+                && static_value(srcdoc).is_none()
+                && find_attribute(attributes, "sandbox").is_none()
+            {
+                let fix = Fix::insert(srcdoc.span.start, "sandbox ");
+                ctx.report(no_unsandboxed_srcdoc(srcdoc.span), Some(fix));
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_span::Span;
+use ssc_ast::{
+    ast::{RegularElement, RegularElementFlags},
+    html,
+};
+
+use super::Rule;
+use crate::{Fix, LintContext};
+
+fn self_closing_tags(span: Span, name: &str) -> OxcDiagnostic {
+    OxcDiagnostic::warn(format!(
+        "Self-closing HTML tags for non-void elements are ambiguous, use `<{name} ...></{name}>` rather than `<{name} ... />`"
+    ))
+    .with_error_code("svelte", "self_closing_tags")
+    .with_label(span)
+}
+
+/// Non-void HTML elements written as `<div />`, which browsers read as an
+/// opening tag, fixed by closing them.
+pub struct SelfClosingTags;
+
+impl Rule for SelfClosingTags {
+    fn name(&self) -> &'static str {
+        "self_closing_tags"
+    }
+
+    fn run(&self, ctx: &mut LintContext<'_, '_>) {
+        let root = ctx.root();
+        let source_text = ctx.source_text();
+        for found in root.find_all::<RegularElement>() {
+            let element = found.node;
+            let span = element.span;
+            if html::is_void(&element.name)
+                || !element.fragment.nodes.is_empty()
+                || !span.source_text(source_text).ends_with("/>")
+            {
+                continue;
+            }
+            // Elements of `<svg>` and `<math>` can be self-closing
+            let flags = ctx.semantic().metadata().get_element_flags(element.node_id);
+            if flags.intersects(RegularElementFlags::Svg | RegularElementFlags::Mathml) {
+                continue;
+            }
+            let slash = span.end as usize - "/>".len();
+            #[allow(clippy::cast_possible_truncation)]
+            let start = source_text[..slash].trim_end().len() as u32;
+            let fix = Fix::replace(Span::new(start, span.end), format!("></{}>", element.name));
+            ctx.report(self_closing_tags(span, &element.name), Some(fix));
+        }
+    }
+}
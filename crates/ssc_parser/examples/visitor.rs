@@ -1,6 +1,6 @@
 #![allow(clippy::print_stdout)]
 
-use std::{env, path::Path};
+use std::{env, ops::ControlFlow, path::Path};
 
 use oxc_allocator::Allocator;
 use ssc_ast::{
@@ -29,7 +29,7 @@ fn main() -> std::io::Result<()> {
     let root = ret.root;
 
     let mut ast_pass = CountASTNodes::default();
-    ast_pass.visit_root(&root);
+    let _ = ast_pass.visit_root(&root);
     println!("{ast_pass:?}");
 
     Ok(())
@@ -43,18 +43,18 @@ struct CountASTNodes {
 }
 
 impl<'a> Visit<'a> for CountASTNodes {
-    fn visit_element(&mut self, element: &Element<'a>) {
+    fn visit_element(&mut self, element: &Element<'a>) -> ControlFlow<()> {
         self.elements += 1;
-        walk::walk_element(self, element);
+        walk::walk_element(self, element)
     }
 
-    fn visit_tag(&mut self, tag: &Tag<'a>) {
+    fn visit_tag(&mut self, tag: &Tag<'a>) -> ControlFlow<()> {
         self.tags += 1;
-        walk::walk_tag(self, tag);
+        walk::walk_tag(self, tag)
     }
 
-    fn visit_block(&mut self, block: &Block<'a>) {
+    fn visit_block(&mut self, block: &Block<'a>) -> ControlFlow<()> {
         self.blocks += 1;
-        walk::walk_block(self, block);
+        walk::walk_block(self, block)
     }
 }
@@ -173,6 +173,9 @@ impl<'a> ParserImpl<'a> {
                 }
                 let parameter = self.parse_js_binding_pattern()?;
                 parameters.push(parameter);
+                if !self.at(Kind::RParen) {
+                    self.expect(Kind::Comma)?;
+                }
             }
             self.expect(Kind::RParen)?;
             self.expect(Kind::RCurly)?;
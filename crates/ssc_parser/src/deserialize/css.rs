@@ -0,0 +1,197 @@
+//! Deserialization of the stylesheet
+//!
+//! The JSON of `ssc_css_ast` and of the Svelte compiler are the same but for
+//! the `content` of the Svelte compiler's stylesheet. The component values of
+//! the declarations aren't serialized, they are split again from the `value`.
+
+#![allow(clippy::cast_possible_truncation)]
+
+use oxc_allocator::Allocator;
+use oxc_diagnostics::Result;
+use oxc_span::Atom;
+use serde_json::Value;
+use ssc_css_ast::{ast::*, AstBuilder};
+
+use super::Node;
+
+pub(super) struct CssDeserializer<'a> {
+    allocator: &'a Allocator,
+    css: AstBuilder<'a>,
+    source_text: &'a str,
+}
+
+impl<'a> CssDeserializer<'a> {
+    pub(super) fn new(allocator: &'a Allocator, source_text: &'a str) -> Self {
+        Self { allocator, css: AstBuilder::new(allocator), source_text }
+    }
+
+    fn node<'v>(&self, value: &'v Value) -> Result<Node<'v>> {
+        Node::new(value, self.source_text)
+    }
+
+    fn atom(&self, value: &str) -> Atom<'a> {
+        self.css.new_atom(value)
+    }
+
+    fn optional_atom(&self, node: Node, field: &str) -> Result<Option<Atom<'a>>> {
+        match node.optional(field) {
+            Some(value) => Ok(Some(self.atom(value.as_str().ok_or_else(|| node.invalid(field))?))),
+            None => Ok(None),
+        }
+    }
+
+    pub(super) fn stylesheet(&self, value: &Value) -> Result<StyleSheet<'a>> {
+        let node = self.node(value)?;
+        if node.kind != "StyleSheet" {
+            return Err(node.unexpected());
+        }
+        // The stylesheet of the Svelte compiler spans the whole `<style>`, its
+        // `content` is the CSS
+        let (span, source) = match node.optional("content") {
+            Some(content) => {
+                let styles = content.get("styles").and_then(Value::as_str);
+                let span = node.span_of("content")?.unwrap_or_default();
+                (span, styles.ok_or_else(|| node.invalid("content"))?)
+            }
+            None => (node.span, node.span.source_text(self.source_text)),
+        };
+        let mut children = self.css.new_vec();
+        for value in node.array("children")? {
+            let child = self.node(value)?;
+            children.push(self.block_child(value)?.rule().ok_or_else(|| child.unexpected())?);
+        }
+        Ok(self.css.stylesheet(span, children, self.atom(source)))
+    }
+
+    fn block_child(&self, value: &Value) -> Result<BlockChild<'a>> {
+        let node = self.node(value)?;
+        let span = node.span;
+        Ok(match node.kind {
+            "Rule" | "StyleRule" => {
+                let prelude = self.selector_list(node.field("prelude")?)?;
+                let block = self.block(node.field("block")?)?;
+                BlockChild::StyleRule(self.css.style_rule(span, prelude, block))
+            }
+            "Atrule" | "AtRule" => {
+                let block = node.optional("block").map(|block| self.block(block)).transpose()?;
+                BlockChild::AtRule(self.css.at_rule(
+                    span,
+                    self.atom(node.str("name")?),
+                    self.atom(node.str("prelude")?),
+                    block,
+                ))
+            }
+            "Declaration" => {
+                let value = self.css.new_str(node.str("value")?);
+                // Where the value starts after the `:`, for the spans of its
+                // components
+                let text = span.source_text(self.source_text);
+                let start = text.find(':').map_or(span.start, |colon| {
+                    let value = &text[colon + 1..];
+                    span.start + (text.len() - value.trim_start().len()) as u32
+                });
+                let components =
+                    ssc_css_parser::parse_component_values(self.allocator, value, start);
+                BlockChild::Declaration(self.css.declaration(
+                    span,
+                    self.atom(node.str("property")?),
+                    Atom::from(value),
+                    components,
+                ))
+            }
+            _ => return Err(node.unexpected()),
+        })
+    }
+
+    fn block(&self, value: &Value) -> Result<Block<'a>> {
+        let node = self.node(value)?;
+        let mut children = self.css.new_vec();
+        for value in node.array("children")? {
+            children.push(self.block_child(value)?);
+        }
+        Ok(self.css.block(node.span, children))
+    }
+
+    fn selector_list(&self, value: &Value) -> Result<SelectorList<'a>> {
+        let node = self.node(value)?;
+        if node.kind != "SelectorList" {
+            return Err(node.unexpected());
+        }
+        let mut children = self.css.new_vec();
+        for value in node.array("children")? {
+            let complex = self.node(value)?;
+            let mut relatives = self.css.new_vec();
+            for value in complex.array("children")? {
+                relatives.push(self.relative_selector(value)?);
+            }
+            let selector = self.css.complex_selector(complex.span, relatives);
+            selector.used.set(complex.flag("used")?);
+            children.push(selector);
+        }
+        Ok(self.css.selector_list(node.span, children))
+    }
+
+    fn relative_selector(&self, value: &Value) -> Result<RelativeSelector<'a>> {
+        let node = self.node(value)?;
+        let combinator = match node.optional("combinator") {
+            Some(combinator) => {
+                let combinator = self.node(combinator)?;
+                let kind = match combinator.str("name")? {
+                    "+" => CombinatorKind::NextSibling,
+                    "~" => CombinatorKind::LaterSibling,
+                    ">" => CombinatorKind::Child,
+                    "||" => CombinatorKind::Column,
+                    " " => CombinatorKind::Descendant,
+                    _ => return Err(combinator.invalid("name")),
+                };
+                Some(self.css.combinator(combinator.span, kind))
+            }
+            None => None,
+        };
+        let mut selectors = self.css.new_vec();
+        for value in node.array("selectors")? {
+            selectors.push(self.simple_selector(value)?);
+        }
+        Ok(self.css.relative_selector(node.span, combinator, selectors))
+    }
+
+    fn simple_selector(&self, value: &Value) -> Result<SimpleSelector<'a>> {
+        let node = self.node(value)?;
+        let span = node.span;
+        let name = || node.str("name").map(|name| self.atom(name));
+        Ok(match node.kind {
+            "TypeSelector" => self.css.type_selector(span, name()?),
+            "IdSelector" => self.css.id_selector(span, name()?),
+            "ClassSelector" => self.css.class_selector(span, name()?),
+            "PseudoElementSelector" => self.css.pseudo_element_selector(span, name()?),
+            "PseudoClassSelector" => {
+                let args =
+                    node.optional("args").map(|args| self.selector_list(args)).transpose()?;
+                self.css.pseudo_class_selector(span, name()?, args)
+            }
+            "AttributeSelector" => {
+                let matcher = match node.optional("matcher").map(Value::as_str) {
+                    None => None,
+                    Some(Some("~=")) => Some(AttributeMatcher::Substring),
+                    Some(Some("^=")) => Some(AttributeMatcher::Prefix),
+                    Some(Some("$=")) => Some(AttributeMatcher::Suffix),
+                    Some(Some("*=")) => Some(AttributeMatcher::Includes),
+                    Some(Some("|=")) => Some(AttributeMatcher::DashMatch),
+                    Some(Some("=")) => Some(AttributeMatcher::Equal),
+                    Some(_) => return Err(node.invalid("matcher")),
+                };
+                self.css.attribute_selector(
+                    span,
+                    name()?,
+                    matcher,
+                    self.optional_atom(node, "value")?,
+                    self.optional_atom(node, "flags")?,
+                )
+            }
+            "Percentage" => self.css.percentage_selector(span, self.atom(node.str("value")?)),
+            "Nth" => self.css.nth_selector(span, self.atom(node.str("value")?)),
+            "NestingSelector" => self.css.nesting_selector(span),
+            _ => return Err(node.unexpected()),
+        })
+    }
+}
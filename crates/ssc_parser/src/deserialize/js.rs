@@ -0,0 +1,1575 @@
+//! Deserialization of the JavaScript and TypeScript nodes
+//!
+//! Reads the JSON of `oxc_ast`'s serialization and the ESTree JSON of the
+//! Svelte compiler, which differ in the names of a few nodes and in how
+//! parameters, rest elements, literals and directives are laid out. Only the
+//! TypeScript a component needs is supported: type annotations, aliases,
+//! interfaces and `as`, `satisfies` and `!` expressions.
+
+#![allow(clippy::cast_possible_truncation)]
+
+use oxc_allocator::{Allocator, Box, Vec};
+use oxc_ast::{ast::*, AstBuilder};
+use oxc_diagnostics::Result;
+use oxc_span::{Atom, SourceType, Span};
+use oxc_syntax::{
+    number::{BigintBase, NumberBase},
+    operator::{
+        AssignmentOperator, BinaryOperator, LogicalOperator, UnaryOperator, UpdateOperator,
+    },
+};
+use serde_json::Value;
+
+use super::Node;
+
+pub(super) struct JsDeserializer<'a> {
+    js: AstBuilder<'a>,
+    source_text: &'a str,
+    source_type: SourceType,
+}
+
+impl<'a> JsDeserializer<'a> {
+    pub(super) fn new(allocator: &'a Allocator, source_text: &'a str, ts: bool) -> Self {
+        Self {
+            js: AstBuilder::new(allocator),
+            source_text,
+            source_type: SourceType::default().with_typescript(ts),
+        }
+    }
+
+    fn node<'v>(&self, value: &'v Value) -> Result<Node<'v>> {
+        Node::new(value, self.source_text)
+    }
+
+    fn atom(&self, value: &str) -> Atom<'a> {
+        Atom::from(self.js.new_str(value))
+    }
+
+    fn list<T, F>(&self, node: Node, field: &str, deserialize: F) -> Result<Vec<'a, T>>
+    where
+        F: Fn(&Self, &Value) -> Result<T>,
+    {
+        let values = node.array(field)?;
+        let mut list = self.js.new_vec_with_capacity(values.len());
+        for value in values {
+            list.push(deserialize(self, value)?);
+        }
+        Ok(list)
+    }
+
+    fn optional<T, F>(&self, node: Node, field: &str, deserialize: F) -> Result<Option<T>>
+    where
+        F: FnOnce(&Self, &Value) -> Result<T>,
+    {
+        node.optional(field).map(|value| deserialize(self, value)).transpose()
+    }
+
+    pub(super) fn program(&self, value: &Value) -> Result<Program<'a>> {
+        let node = self.node(value)?;
+        if node.kind != "Program" {
+            return Err(node.unexpected());
+        }
+        let hashbang = match node.optional("hashbang") {
+            Some(hashbang) => {
+                let hashbang = self.node(hashbang)?;
+                Some(self.js.hashbang(hashbang.span, self.atom(hashbang.str("value")?)))
+            }
+            None => None,
+        };
+        let (directives, body) = self.directives_and_statements(node, "body")?;
+        Ok(self.js.program(node.span, self.source_type, directives, hashbang, body))
+    }
+
+    /// The directives and statements of a program or a function body. ESTree
+    /// has no `directives`, its directives are the leading expression
+    /// statements with a `directive`.
+    fn directives_and_statements(
+        &self,
+        node: Node,
+        field: &str,
+    ) -> Result<(Vec<'a, Directive<'a>>, Vec<'a, Statement<'a>>)> {
+        let mut directives = self.js.new_vec();
+        if node.optional("directives").is_some() {
+            directives = self.list(node, "directives", Self::directive)?;
+        }
+        let mut statements = self.js.new_vec();
+        for value in node.array(field)? {
+            let statement = self.node(value)?;
+            if statements.is_empty() && statement.optional("directive").is_some() {
+                directives.push(self.directive(value)?);
+            } else {
+                statements.push(self.statement(value)?);
+            }
+        }
+        Ok((directives, statements))
+    }
+
+    fn directive(&self, value: &Value) -> Result<Directive<'a>> {
+        let node = self.node(value)?;
+        let expression = self.string_literal(node.field("expression")?)?;
+        Ok(self.js.directive(node.span, expression, self.atom(node.str("directive")?)))
+    }
+
+    /* ----------  Identifiers  ---------- */
+
+    fn name(&self, node: Node) -> Result<Atom<'a>> {
+        if node.kind != "Identifier" {
+            return Err(node.unexpected());
+        }
+        Ok(self.atom(node.str("name")?))
+    }
+
+    pub(super) fn identifier_reference(&self, value: &Value) -> Result<IdentifierReference<'a>> {
+        let node = self.node(value)?;
+        Ok(IdentifierReference::new(node.span, self.name(node)?))
+    }
+
+    fn binding_identifier(&self, value: &Value) -> Result<BindingIdentifier<'a>> {
+        let node = self.node(value)?;
+        Ok(BindingIdentifier::new(node.span, self.name(node)?))
+    }
+
+    pub(super) fn identifier_name(&self, value: &Value) -> Result<IdentifierName<'a>> {
+        let node = self.node(value)?;
+        Ok(IdentifierName::new(node.span, self.name(node)?))
+    }
+
+    fn label_identifier(&self, value: &Value) -> Result<LabelIdentifier<'a>> {
+        let node = self.node(value)?;
+        Ok(LabelIdentifier { span: node.span, name: self.name(node)? })
+    }
+
+    fn private_identifier(&self, value: &Value) -> Result<PrivateIdentifier<'a>> {
+        let node = self.node(value)?;
+        if node.kind != "PrivateIdentifier" {
+            return Err(node.unexpected());
+        }
+        Ok(PrivateIdentifier { span: node.span, name: self.atom(node.str("name")?) })
+    }
+
+    fn property_key(&self, value: &Value, computed: bool) -> Result<PropertyKey<'a>> {
+        let node = self.node(value)?;
+        Ok(match node.kind {
+            "Identifier" if !computed => {
+                PropertyKey::StaticIdentifier(self.js.alloc(self.identifier_name(value)?))
+            }
+            "PrivateIdentifier" => {
+                PropertyKey::PrivateIdentifier(self.js.alloc(self.private_identifier(value)?))
+            }
+            _ => PropertyKey::from(self.expression(value)?),
+        })
+    }
+
+    /* ----------  Literals  ---------- */
+
+    pub(super) fn string_literal(&self, value: &Value) -> Result<StringLiteral<'a>> {
+        let node = self.node(value)?;
+        if !matches!(node.kind, "StringLiteral" | "Literal") {
+            return Err(node.unexpected());
+        }
+        Ok(StringLiteral::new(node.span, self.atom(node.str("value")?)))
+    }
+
+    fn literal(&self, node: Node) -> Result<Expression<'a>> {
+        let span = node.span;
+        let kind = match node.kind {
+            // ESTree tells the literals apart by their value
+            "Literal" if node.optional("regex").is_some() => "RegExpLiteral",
+            "Literal" if node.optional("bigint").is_some() => "BigIntLiteral",
+            "Literal" => match node.optional("value") {
+                None => "NullLiteral",
+                Some(Value::Bool(_)) => "BooleanLiteral",
+                Some(Value::Number(_)) => "NumericLiteral",
+                Some(_) => "StringLiteral",
+            },
+            kind => kind,
+        };
+        Ok(match kind {
+            "NullLiteral" => self.js.literal_null_expression(NullLiteral { span }),
+            "BooleanLiteral" => {
+                let value = node.field("value")?.as_bool().ok_or_else(|| node.invalid("value"))?;
+                self.js.literal_boolean_expression(BooleanLiteral { span, value })
+            }
+            "StringLiteral" => self
+                .js
+                .literal_string_expression(StringLiteral::new(span, self.atom(node.str("value")?))),
+            "NumericLiteral" => {
+                let value = node.field("value")?.as_f64().ok_or_else(|| node.invalid("value"))?;
+                let raw = match node.optional("raw") {
+                    Some(raw) => raw.as_str().ok_or_else(|| node.invalid("raw"))?,
+                    None => span.source_text(self.source_text),
+                };
+                let raw = self.js.new_str(raw);
+                self.js.literal_number_expression(NumericLiteral::new(
+                    span,
+                    value,
+                    raw,
+                    number_base(raw, value),
+                ))
+            }
+            "BigIntLiteral" => {
+                let raw = node.str("raw")?;
+                let base = match raw.get(..2).map(str::to_ascii_lowercase).as_deref() {
+                    Some("0x") => BigintBase::Hex,
+                    Some("0o") => BigintBase::Octal,
+                    Some("0b") => BigintBase::Binary,
+                    _ => BigintBase::Decimal,
+                };
+                self.js.literal_bigint_expression(self.js.bigint_literal(
+                    span,
+                    self.atom(raw),
+                    base,
+                ))
+            }
+            "RegExpLiteral" => {
+                let regex = node.field("regex")?;
+                let part = |field: &str| {
+                    regex.get(field).and_then(Value::as_str).ok_or_else(|| node.invalid("regex"))
+                };
+                let mut flags = RegExpFlags::empty();
+                for flag in part("flags")?.chars() {
+                    flags |= RegExpFlags::try_from(flag).map_err(|_| node.invalid("regex"))?;
+                }
+                let pattern = self.js.new_str(part("pattern")?);
+                self.js.literal_regexp_expression(self.js.reg_exp_literal(span, pattern, flags))
+            }
+            _ => return Err(node.unexpected()),
+        })
+    }
+
+    fn template_literal(&self, value: &Value) -> Result<TemplateLiteral<'a>> {
+        let node = self.node(value)?;
+        if node.kind != "TemplateLiteral" {
+            return Err(node.unexpected());
+        }
+        let quasis = self.list(node, "quasis", |this, value| {
+            let element = this.node(value)?;
+            let text = element.field("value")?;
+            let raw =
+                text.get("raw").and_then(Value::as_str).ok_or_else(|| element.invalid("value"))?;
+            let cooked = text.get("cooked").and_then(Value::as_str).map(|cooked| this.atom(cooked));
+            Ok(TemplateElement {
+                span: element.span,
+                tail: element.flag("tail")?,
+                value: TemplateElementValue { raw: this.atom(raw), cooked },
+            })
+        })?;
+        let expressions = self.list(node, "expressions", Self::expression)?;
+        Ok(TemplateLiteral { span: node.span, quasis, expressions })
+    }
+
+    /* ----------  Expressions  ---------- */
+
+    pub(super) fn expression(&self, value: &Value) -> Result<Expression<'a>> {
+        let node = self.node(value)?;
+        let span = node.span;
+        Ok(match node.kind {
+            "Identifier" => {
+                self.js.identifier_reference_expression(self.identifier_reference(value)?)
+            }
+            "Literal" | "BooleanLiteral" | "NullLiteral" | "NumericLiteral" | "BigIntLiteral"
+            | "RegExpLiteral" | "StringLiteral" => self.literal(node)?,
+            "TemplateLiteral" => self.js.template_literal_expression(self.template_literal(value)?),
+            "TaggedTemplateExpression" => {
+                let tag = self.expression(node.field("tag")?)?;
+                let quasi = self.template_literal(node.field("quasi")?)?;
+                let type_parameters = self.type_arguments_of(node)?;
+                self.js.tagged_template_expression(span, tag, quasi, type_parameters)
+            }
+            "ThisExpression" => self.js.this_expression(span),
+            "Super" => self.js.super_(span),
+            "ArrayExpression" => {
+                let mut elements = self.js.new_vec();
+                for element in node.array("elements")? {
+                    if element.is_null() {
+                        elements.push(ArrayExpressionElement::Elision(Elision {
+                            span: Span::default(),
+                        }));
+                        continue;
+                    }
+                    let element_node = self.node(element)?;
+                    elements.push(match element_node.kind {
+                        "Elision" => {
+                            ArrayExpressionElement::Elision(Elision { span: element_node.span })
+                        }
+                        "SpreadElement" => {
+                            ArrayExpressionElement::SpreadElement(self.spread_element(element)?)
+                        }
+                        _ => ArrayExpressionElement::from(self.expression(element)?),
+                    });
+                }
+                let trailing_comma = node.span_of("trailingComma")?;
+                self.js.array_expression(span, elements, trailing_comma)
+            }
+            "ObjectExpression" => {
+                let properties = self.list(node, "properties", Self::object_property_kind)?;
+                let trailing_comma = node.span_of("trailingComma")?;
+                self.js.object_expression(span, properties, trailing_comma)
+            }
+            "FunctionExpression" => self.js.function_expression(self.function(value)?),
+            "ArrowFunctionExpression" => {
+                let params =
+                    self.formal_parameters(node, FormalParameterKind::ArrowFormalParameters)?;
+                let body_value = node.field("body")?;
+                let body_node = self.node(body_value)?;
+                let body = if matches!(body_node.kind, "FunctionBody" | "BlockStatement") {
+                    self.function_body(body_value)?
+                } else {
+                    // The expression body of ESTree
+                    let statement =
+                        self.js.expression_statement(body_node.span, self.expression(body_value)?);
+                    self.js.function_body(
+                        body_node.span,
+                        self.js.new_vec(),
+                        self.js.new_vec_single(statement),
+                    )
+                };
+                let type_parameters =
+                    self.optional(node, "typeParameters", Self::type_parameters)?;
+                let return_type = self.optional(node, "returnType", Self::type_annotation)?;
+                self.js.arrow_function_expression(
+                    span,
+                    node.flag("expression")?,
+                    node.flag("async")?,
+                    params,
+                    body,
+                    type_parameters,
+                    return_type,
+                )
+            }
+            "ClassExpression" => self.js.class_expression(self.class(value)?),
+            "StaticMemberExpression"
+            | "ComputedMemberExpression"
+            | "PrivateFieldExpression"
+            | "MemberExpression" => self.member_expression(node)?,
+            "CallExpression" => {
+                let callee = self.expression(node.field("callee")?)?;
+                let arguments = self.list(node, "arguments", Self::argument)?;
+                let type_parameters = self.type_arguments_of(node)?;
+                self.js.call_expression(
+                    span,
+                    callee,
+                    arguments,
+                    node.flag("optional")?,
+                    type_parameters,
+                )
+            }
+            "NewExpression" => {
+                let callee = self.expression(node.field("callee")?)?;
+                let arguments = self.list(node, "arguments", Self::argument)?;
+                let type_parameters = self.type_arguments_of(node)?;
+                self.js.new_expression(span, callee, arguments, type_parameters)
+            }
+            "ChainExpression" => {
+                let value = node.field("expression")?;
+                let element = match self.expression(value)? {
+                    Expression::CallExpression(call) => ChainElement::CallExpression(call),
+                    expression => MemberExpression::try_from(expression)
+                        .map(ChainElement::from)
+                        .map_err(|_| node.invalid("expression"))?,
+                };
+                self.js.chain_expression(span, element)
+            }
+            "MetaProperty" => {
+                let meta = self.identifier_name(node.field("meta")?)?;
+                let property = self.identifier_name(node.field("property")?)?;
+                self.js.meta_property(span, meta, property)
+            }
+            "ImportExpression" => {
+                let source = self.expression(node.field("source")?)?;
+                let arguments = if node.optional("arguments").is_some() {
+                    self.list(node, "arguments", Self::expression)?
+                } else {
+                    // ESTree has the options alone
+                    let options = self.optional(node, "options", Self::expression)?;
+                    self.js.new_vec_from_iter(options)
+                };
+                self.js.import_expression(span, source, arguments)
+            }
+            "UnaryExpression" => {
+                let operator = unary_operator(node.str("operator")?)
+                    .ok_or_else(|| node.invalid("operator"))?;
+                self.js.unary_expression(span, operator, self.expression(node.field("argument")?)?)
+            }
+            "UpdateExpression" => {
+                let operator = match node.str("operator")? {
+                    "++" => UpdateOperator::Increment,
+                    "--" => UpdateOperator::Decrement,
+                    _ => return Err(node.invalid("operator")),
+                };
+                let argument = self.simple_assignment_target(node.field("argument")?)?;
+                self.js.update_expression(span, operator, node.flag("prefix")?, argument)
+            }
+            "BinaryExpression" | "PrivateInExpression" => {
+                let operator = binary_operator(node.str("operator")?)
+                    .ok_or_else(|| node.invalid("operator"))?;
+                let left_value = node.field("left")?;
+                let right = self.expression(node.field("right")?)?;
+                if self.node(left_value)?.kind == "PrivateIdentifier" {
+                    Expression::PrivateInExpression(self.js.alloc(PrivateInExpression {
+                        span,
+                        left: self.private_identifier(left_value)?,
+                        operator,
+                        right,
+                    }))
+                } else {
+                    self.js.binary_expression(span, self.expression(left_value)?, operator, right)
+                }
+            }
+            "LogicalExpression" => {
+                let operator = match node.str("operator")? {
+                    "||" => LogicalOperator::Or,
+                    "&&" => LogicalOperator::And,
+                    "??" => LogicalOperator::Coalesce,
+                    _ => return Err(node.invalid("operator")),
+                };
+                let left = self.expression(node.field("left")?)?;
+                let right = self.expression(node.field("right")?)?;
+                self.js.logical_expression(span, left, operator, right)
+            }
+            "ConditionalExpression" => {
+                let test = self.expression(node.field("test")?)?;
+                let consequent = self.expression(node.field("consequent")?)?;
+                let alternate = self.expression(node.field("alternate")?)?;
+                self.js.conditional_expression(span, test, consequent, alternate)
+            }
+            "AssignmentExpression" => {
+                let operator = assignment_operator(node.str("operator")?)
+                    .ok_or_else(|| node.invalid("operator"))?;
+                let left = self.assignment_target(node.field("left")?)?;
+                let right = self.expression(node.field("right")?)?;
+                self.js.assignment_expression(span, operator, left, right)
+            }
+            "SequenceExpression" => {
+                self.js.sequence_expression(span, self.list(node, "expressions", Self::expression)?)
+            }
+            "ParenthesizedExpression" => {
+                self.js.parenthesized_expression(span, self.expression(node.field("expression")?)?)
+            }
+            "AwaitExpression" => {
+                self.js.await_expression(span, self.expression(node.field("argument")?)?)
+            }
+            "YieldExpression" => {
+                let argument = self.optional(node, "argument", Self::expression)?;
+                self.js.yield_expression(span, node.flag("delegate")?, argument)
+            }
+            "TSAsExpression" => {
+                let expression = self.expression(node.field("expression")?)?;
+                let type_annotation = self.ts_type(node.field("typeAnnotation")?)?;
+                self.js.ts_as_expression(span, expression, type_annotation)
+            }
+            "TSSatisfiesExpression" => {
+                let expression = self.expression(node.field("expression")?)?;
+                let type_annotation = self.ts_type(node.field("typeAnnotation")?)?;
+                self.js.ts_satisfies_expression(span, expression, type_annotation)
+            }
+            "TSNonNullExpression" => {
+                self.js.ts_non_null_expression(span, self.expression(node.field("expression")?)?)
+            }
+            _ => return Err(node.unexpected()),
+        })
+    }
+
+    fn member_expression(&self, node: Node) -> Result<Expression<'a>> {
+        let span = node.span;
+        let object = self.expression(node.field("object")?)?;
+        let optional = node.flag("optional")?;
+        let kind = match node.kind {
+            // ESTree has a single node for the three
+            "MemberExpression" if node.flag("computed")? => "ComputedMemberExpression",
+            "MemberExpression" => match self.node(node.field("property")?)?.kind {
+                "PrivateIdentifier" => "PrivateFieldExpression",
+                _ => "StaticMemberExpression",
+            },
+            kind => kind,
+        };
+        Ok(match kind {
+            "StaticMemberExpression" => {
+                let property = self.identifier_name(node.field("property")?)?;
+                self.js.static_member_expression(span, object, property, optional)
+            }
+            "ComputedMemberExpression" => {
+                let field = if node.kind == "MemberExpression" { "property" } else { "expression" };
+                let expression = self.expression(node.field(field)?)?;
+                self.js.computed_member_expression(span, object, expression, optional)
+            }
+            _ => {
+                let field = if node.kind == "MemberExpression" { "property" } else { "field" };
+                let field = self.private_identifier(node.field(field)?)?;
+                self.js.private_field_expression(span, object, field, optional)
+            }
+        })
+    }
+
+    fn spread_element(&self, value: &Value) -> Result<Box<'a, SpreadElement<'a>>> {
+        let node = self.node(value)?;
+        if node.kind != "SpreadElement" {
+            return Err(node.unexpected());
+        }
+        Ok(self.js.spread_element(node.span, self.expression(node.field("argument")?)?))
+    }
+
+    fn argument(&self, value: &Value) -> Result<Argument<'a>> {
+        Ok(match self.node(value)?.kind {
+            "SpreadElement" => Argument::SpreadElement(self.spread_element(value)?),
+            _ => Argument::from(self.expression(value)?),
+        })
+    }
+
+    fn object_property_kind(&self, value: &Value) -> Result<ObjectPropertyKind<'a>> {
+        let node = self.node(value)?;
+        match node.kind {
+            "SpreadElement" => Ok(ObjectPropertyKind::SpreadProperty(self.spread_element(value)?)),
+            "ObjectProperty" | "Property" => {
+                let kind = match node.str("kind")? {
+                    "init" => PropertyKind::Init,
+                    "get" => PropertyKind::Get,
+                    "set" => PropertyKind::Set,
+                    _ => return Err(node.invalid("kind")),
+                };
+                let computed = node.flag("computed")?;
+                let key = self.property_key(node.field("key")?, computed)?;
+                let value = self.expression(node.field("value")?)?;
+                let init = self.optional(node, "init", Self::expression)?;
+                Ok(ObjectPropertyKind::ObjectProperty(self.js.object_property(
+                    node.span,
+                    kind,
+                    key,
+                    value,
+                    init,
+                    node.flag("method")?,
+                    node.flag("shorthand")?,
+                    computed,
+                )))
+            }
+            _ => Err(node.unexpected()),
+        }
+    }
+
+    /* ----------  Patterns and assignment targets  ---------- */
+
+    pub(super) fn binding_pattern(&self, value: &Value) -> Result<BindingPattern<'a>> {
+        let node = self.node(value)?;
+        let kind = match node.kind {
+            "Identifier" => self.js.binding_pattern_identifier(self.binding_identifier(value)?),
+            "ObjectPattern" => {
+                let mut properties = self.js.new_vec();
+                let mut rest = self.optional(node, "rest", Self::rest_element)?;
+                for value in node.array("properties")? {
+                    let property = self.node(value)?;
+                    match property.kind {
+                        "RestElement" => rest = Some(self.rest_element(value)?),
+                        "BindingProperty" | "Property" => {
+                            let computed = property.flag("computed")?;
+                            let key = self.property_key(property.field("key")?, computed)?;
+                            let value = self.binding_pattern(property.field("value")?)?;
+                            properties.push(self.js.binding_property(
+                                property.span,
+                                key,
+                                value,
+                                property.flag("shorthand")?,
+                                computed,
+                            ));
+                        }
+                        _ => return Err(property.unexpected()),
+                    }
+                }
+                self.js.object_pattern(node.span, properties, rest)
+            }
+            "ArrayPattern" => {
+                let mut elements = self.js.new_vec();
+                let mut rest = self.optional(node, "rest", Self::rest_element)?;
+                for value in node.array("elements")? {
+                    if value.is_null() {
+                        elements.push(None);
+                    } else if self.node(value)?.kind == "RestElement" {
+                        rest = Some(self.rest_element(value)?);
+                    } else {
+                        elements.push(Some(self.binding_pattern(value)?));
+                    }
+                }
+                self.js.array_pattern(node.span, elements, rest)
+            }
+            "AssignmentPattern" => {
+                let left = self.binding_pattern(node.field("left")?)?;
+                let right = self.expression(node.field("right")?)?;
+                return Ok(self.js.assignment_pattern(node.span, left, right));
+            }
+            _ => return Err(node.unexpected()),
+        };
+        let type_annotation = self.optional(node, "typeAnnotation", Self::type_annotation)?;
+        Ok(self.js.binding_pattern(kind, type_annotation, node.flag("optional")?))
+    }
+
+    fn rest_element(&self, value: &Value) -> Result<Box<'a, BindingRestElement<'a>>> {
+        let node = self.node(value)?;
+        if node.kind != "RestElement" {
+            return Err(node.unexpected());
+        }
+        let mut argument = self.binding_pattern(node.field("argument")?)?;
+        // The type annotation of a rest parameter may be on the rest element
+        if argument.type_annotation.is_none() {
+            argument.type_annotation =
+                self.optional(node, "typeAnnotation", Self::type_annotation)?;
+        }
+        Ok(self.js.rest_element(node.span, argument))
+    }
+
+    fn assignment_target(&self, value: &Value) -> Result<AssignmentTarget<'a>> {
+        let node = self.node(value)?;
+        let span = node.span;
+        Ok(match node.kind {
+            "ArrayAssignmentTarget" | "ArrayPattern" => {
+                let mut elements = self.js.new_vec();
+                let mut rest = self.optional(node, "rest", Self::assignment_target_rest)?;
+                for value in node.array("elements")? {
+                    if value.is_null() {
+                        elements.push(None);
+                    } else if self.node(value)?.kind == "RestElement" {
+                        rest = Some(self.assignment_target_rest(value)?);
+                    } else {
+                        elements.push(Some(self.assignment_target_maybe_default(value)?));
+                    }
+                }
+                let trailing_comma = node.span_of("trailingComma")?;
+                AssignmentTarget::ArrayAssignmentTarget(self.js.alloc(ArrayAssignmentTarget {
+                    span,
+                    elements,
+                    rest,
+                    trailing_comma,
+                }))
+            }
+            "ObjectAssignmentTarget" | "ObjectPattern" => {
+                let mut properties = self.js.new_vec();
+                let mut rest = self.optional(node, "rest", Self::assignment_target_rest)?;
+                for value in node.array("properties")? {
+                    if self.node(value)?.kind == "RestElement" {
+                        rest = Some(self.assignment_target_rest(value)?);
+                    } else {
+                        properties.push(self.assignment_target_property(value)?);
+                    }
+                }
+                AssignmentTarget::ObjectAssignmentTarget(self.js.alloc(ObjectAssignmentTarget {
+                    span,
+                    properties,
+                    rest,
+                }))
+            }
+            _ => AssignmentTarget::from(self.simple_assignment_target(value)?),
+        })
+    }
+
+    fn simple_assignment_target(&self, value: &Value) -> Result<SimpleAssignmentTarget<'a>> {
+        let node = self.node(value)?;
+        match self.expression(value)? {
+            Expression::Identifier(ident) => {
+                Ok(SimpleAssignmentTarget::AssignmentTargetIdentifier(ident))
+            }
+            Expression::TSAsExpression(expression) => {
+                Ok(SimpleAssignmentTarget::TSAsExpression(expression))
+            }
+            Expression::TSSatisfiesExpression(expression) => {
+                Ok(SimpleAssignmentTarget::TSSatisfiesExpression(expression))
+            }
+            Expression::TSNonNullExpression(expression) => {
+                Ok(SimpleAssignmentTarget::TSNonNullExpression(expression))
+            }
+            expression => MemberExpression::try_from(expression)
+                .map(SimpleAssignmentTarget::from)
+                .map_err(|_| node.unexpected()),
+        }
+    }
+
+    fn assignment_target_maybe_default(
+        &self,
+        value: &Value,
+    ) -> Result<AssignmentTargetMaybeDefault<'a>> {
+        let node = self.node(value)?;
+        let (binding, init) = match node.kind {
+            "AssignmentTargetWithDefault" => ("binding", "init"),
+            "AssignmentPattern" => ("left", "right"),
+            _ => return Ok(AssignmentTargetMaybeDefault::from(self.assignment_target(value)?)),
+        };
+        Ok(AssignmentTargetMaybeDefault::AssignmentTargetWithDefault(self.js.alloc(
+            AssignmentTargetWithDefault {
+                span: node.span,
+                binding: self.assignment_target(node.field(binding)?)?,
+                init: self.expression(node.field(init)?)?,
+            },
+        )))
+    }
+
+    fn assignment_target_rest(&self, value: &Value) -> Result<AssignmentTargetRest<'a>> {
+        let node = self.node(value)?;
+        if node.kind != "RestElement" {
+            return Err(node.unexpected());
+        }
+        let field = if node.optional("target").is_some() { "target" } else { "argument" };
+        Ok(AssignmentTargetRest {
+            span: node.span,
+            target: self.assignment_target(node.field(field)?)?,
+        })
+    }
+
+    fn assignment_target_property(&self, value: &Value) -> Result<AssignmentTargetProperty<'a>> {
+        let node = self.node(value)?;
+        let span = node.span;
+        let (binding, init) = match node.kind {
+            "AssignmentTargetPropertyIdentifier" => {
+                let binding = self.identifier_reference(node.field("binding")?)?;
+                (binding, self.optional(node, "init", Self::expression)?)
+            }
+            // ESTree, `{ a }` or `{ a = 1 }`
+            "Property" if node.flag("shorthand")? => {
+                let value = self.node(node.field("value")?)?;
+                if value.kind == "AssignmentPattern" {
+                    let binding = self.identifier_reference(value.field("left")?)?;
+                    (binding, Some(self.expression(value.field("right")?)?))
+                } else {
+                    (self.identifier_reference(node.field("key")?)?, None)
+                }
+            }
+            "AssignmentTargetPropertyProperty" | "Property" => {
+                let (name, binding) = match node.kind {
+                    "Property" => ("key", "value"),
+                    _ => ("name", "binding"),
+                };
+                let name = self.property_key(node.field(name)?, node.flag("computed")?)?;
+                let binding = self.assignment_target_maybe_default(node.field(binding)?)?;
+                return Ok(AssignmentTargetProperty::AssignmentTargetPropertyProperty(
+                    self.js.alloc(AssignmentTargetPropertyProperty { span, name, binding }),
+                ));
+            }
+            _ => return Err(node.unexpected()),
+        };
+        Ok(AssignmentTargetProperty::AssignmentTargetPropertyIdentifier(
+            self.js.alloc(AssignmentTargetPropertyIdentifier { span, binding, init }),
+        ))
+    }
+
+    /* ----------  Functions and classes  ---------- */
+
+    fn function(&self, value: &Value) -> Result<Box<'a, Function<'a>>> {
+        let node = self.node(value)?;
+        let r#type = match node.kind {
+            "FunctionDeclaration" => FunctionType::FunctionDeclaration,
+            "FunctionExpression" => FunctionType::FunctionExpression,
+            "TSDeclareFunction" => FunctionType::TSDeclareFunction,
+            _ => return Err(node.unexpected()),
+        };
+        let id = self.optional(node, "id", Self::binding_identifier)?;
+        let this_param = self.optional(node, "thisParam", Self::this_parameter)?;
+        let params = self.formal_parameters(node, FormalParameterKind::FormalParameter)?;
+        let body = self.optional(node, "body", Self::function_body)?;
+        let type_parameters = self.optional(node, "typeParameters", Self::type_parameters)?;
+        let return_type = self.optional(node, "returnType", Self::type_annotation)?;
+        Ok(self.js.function(
+            r#type,
+            node.span,
+            id,
+            node.flag("generator")?,
+            node.flag("async")?,
+            node.flag("declare")?,
+            this_param,
+            params,
+            body,
+            type_parameters,
+            return_type,
+        ))
+    }
+
+    fn this_parameter(&self, value: &Value) -> Result<TSThisParameter<'a>> {
+        let node = self.node(value)?;
+        let this = match node.optional("this") {
+            Some(this) => self.identifier_name(this)?,
+            None => IdentifierName::new(
+                Span::new(node.span.start, node.span.start + 4),
+                Atom::from("this"),
+            ),
+        };
+        let type_annotation = self.optional(node, "typeAnnotation", Self::type_annotation)?;
+        Ok(self.js.ts_this_parameter(node.span, this, type_annotation))
+    }
+
+    /// The parameters of `function`, `{ kind, items }` in `oxc_ast` and an
+    /// array of patterns in ESTree, with the rest element among them.
+    fn formal_parameters(
+        &self,
+        function: Node,
+        default_kind: FormalParameterKind,
+    ) -> Result<Box<'a, FormalParameters<'a>>> {
+        let params = function.field("params")?;
+        let (span, kind, values) = match params {
+            Value::Array(values) => {
+                let mut span = Span::default();
+                if let (Some(first), Some(last)) = (values.first(), values.last()) {
+                    span = Span::new(self.node(first)?.span.start, self.node(last)?.span.end);
+                }
+                (span, default_kind, values.as_slice())
+            }
+            _ => {
+                let node = self.node(params)?;
+                let kind = match node.optional("kind").and_then(Value::as_str) {
+                    Some("FormalParameter") => FormalParameterKind::FormalParameter,
+                    Some("UniqueFormalParameters") => FormalParameterKind::UniqueFormalParameters,
+                    Some("ArrowFormalParameters") => FormalParameterKind::ArrowFormalParameters,
+                    Some("Signature") => FormalParameterKind::Signature,
+                    _ => default_kind,
+                };
+                (node.span, kind, node.array("items")?)
+            }
+        };
+        let mut items = self.js.new_vec_with_capacity(values.len());
+        let mut rest = None;
+        for value in values {
+            let item = self.node(value)?;
+            match item.kind {
+                "RestElement" => rest = Some(self.rest_element(value)?),
+                "FormalParameter" => {
+                    let pattern = self.binding_pattern(item.field("pattern")?)?;
+                    let accessibility = self.accessibility(item)?;
+                    let decorators = self.decorators(item)?;
+                    items.push(self.js.formal_parameter(
+                        item.span,
+                        pattern,
+                        accessibility,
+                        item.flag("readonly")?,
+                        item.flag("override")?,
+                        decorators,
+                    ));
+                }
+                _ => {
+                    let pattern = self.binding_pattern(value)?;
+                    items.push(self.js.plain_formal_parameter(item.span, pattern));
+                }
+            }
+        }
+        Ok(self.js.formal_parameters(span, kind, items, rest))
+    }
+
+    fn function_body(&self, value: &Value) -> Result<Box<'a, FunctionBody<'a>>> {
+        let node = self.node(value)?;
+        let field = match node.kind {
+            "FunctionBody" => "statements",
+            "BlockStatement" => "body",
+            _ => return Err(node.unexpected()),
+        };
+        let (directives, statements) = self.directives_and_statements(node, field)?;
+        Ok(self.js.function_body(node.span, directives, statements))
+    }
+
+    fn class(&self, value: &Value) -> Result<Box<'a, Class<'a>>> {
+        let node = self.node(value)?;
+        let r#type = match node.kind {
+            "ClassDeclaration" => ClassType::ClassDeclaration,
+            "ClassExpression" => ClassType::ClassExpression,
+            _ => return Err(node.unexpected()),
+        };
+        let id = self.optional(node, "id", Self::binding_identifier)?;
+        let super_class = self.optional(node, "superClass", Self::expression)?;
+        let body = self.node(node.field("body")?)?;
+        let body = self.js.class_body(body.span, self.list(body, "body", Self::class_element)?);
+        let type_parameters = self.optional(node, "typeParameters", Self::type_parameters)?;
+        let super_type_parameters =
+            self.optional(node, "superTypeParameters", Self::type_arguments)?;
+        let implements = match node.optional("implements") {
+            Some(implements) if implements.as_array().is_some_and(|array| !array.is_empty()) => {
+                Some(self.list(node, "implements", |this, value| {
+                    let implement = this.node(value)?;
+                    let name = this.ts_type_name(implement.field("expression")?)?;
+                    let type_parameters = this.type_arguments_of(implement)?;
+                    Ok(this.js.ts_type_implement(implement.span, name, type_parameters))
+                })?)
+            }
+            _ => None,
+        };
+        Ok(self.js.class(
+            r#type,
+            node.span,
+            id,
+            super_class,
+            body,
+            type_parameters,
+            super_type_parameters,
+            implements,
+            self.decorators(node)?,
+            node.flag("abstract")?,
+            node.flag("declare")?,
+        ))
+    }
+
+    fn class_element(&self, value: &Value) -> Result<ClassElement<'a>> {
+        let node = self.node(value)?;
+        let span = node.span;
+        Ok(match node.kind {
+            "MethodDefinition" | "TSAbstractMethodDefinition" => {
+                let r#type = if node.kind == "MethodDefinition" {
+                    MethodDefinitionType::MethodDefinition
+                } else {
+                    MethodDefinitionType::TSAbstractMethodDefinition
+                };
+                let kind = match node.str("kind")? {
+                    "constructor" => MethodDefinitionKind::Constructor,
+                    "method" => MethodDefinitionKind::Method,
+                    "get" => MethodDefinitionKind::Get,
+                    "set" => MethodDefinitionKind::Set,
+                    _ => return Err(node.invalid("kind")),
+                };
+                let computed = node.flag("computed")?;
+                ClassElement::MethodDefinition(self.js.alloc(MethodDefinition {
+                    r#type,
+                    span,
+                    key: self.property_key(node.field("key")?, computed)?,
+                    value: self.function(node.field("value")?)?,
+                    kind,
+                    computed,
+                    r#static: node.flag("static")?,
+                    r#override: node.flag("override")?,
+                    accessibility: self.accessibility(node)?,
+                    optional: node.flag("optional")?,
+                    decorators: self.decorators(node)?,
+                }))
+            }
+            "PropertyDefinition" | "TSAbstractPropertyDefinition" => {
+                let r#type = if node.kind == "PropertyDefinition" {
+                    PropertyDefinitionType::PropertyDefinition
+                } else {
+                    PropertyDefinitionType::TSAbstractPropertyDefinition
+                };
+                let computed = node.flag("computed")?;
+                ClassElement::PropertyDefinition(self.js.alloc(PropertyDefinition {
+                    r#type,
+                    span,
+                    key: self.property_key(node.field("key")?, computed)?,
+                    value: self.optional(node, "value", Self::expression)?,
+                    computed,
+                    r#static: node.flag("static")?,
+                    declare: node.flag("declare")?,
+                    r#override: node.flag("override")?,
+                    readonly: node.flag("readonly")?,
+                    type_annotation: self.optional(
+                        node,
+                        "typeAnnotation",
+                        Self::type_annotation,
+                    )?,
+                    accessibility: self.accessibility(node)?,
+                    optional: node.flag("optional")?,
+                    definite: node.flag("definite")?,
+                    decorators: self.decorators(node)?,
+                }))
+            }
+            "StaticBlock" => self.js.static_block(span, self.list(node, "body", Self::statement)?),
+            _ => return Err(node.unexpected()),
+        })
+    }
+
+    fn accessibility(&self, node: Node) -> Result<Option<TSAccessibility>> {
+        Ok(match node.optional("accessibility").map(Value::as_str) {
+            None => None,
+            Some(Some("public")) => Some(TSAccessibility::Public),
+            Some(Some("protected")) => Some(TSAccessibility::Protected),
+            Some(Some("private")) => Some(TSAccessibility::Private),
+            Some(_) => return Err(node.invalid("accessibility")),
+        })
+    }
+
+    fn decorators(&self, node: Node) -> Result<Vec<'a, Decorator<'a>>> {
+        if node.optional("decorators").is_none() {
+            return Ok(self.js.new_vec());
+        }
+        self.list(node, "decorators", |this, value| {
+            let decorator = this.node(value)?;
+            Ok(this.js.decorator(decorator.span, this.expression(decorator.field("expression")?)?))
+        })
+    }
+
+    /* ----------  Statements  ---------- */
+
+    pub(super) fn statement(&self, value: &Value) -> Result<Statement<'a>> {
+        let node = self.node(value)?;
+        let span = node.span;
+        Ok(match node.kind {
+            "ExpressionStatement" => {
+                self.js.expression_statement(span, self.expression(node.field("expression")?)?)
+            }
+            "BlockStatement" => self.js.block_statement(self.block(value)?),
+            "EmptyStatement" => self.js.empty_statement(span),
+            "DebuggerStatement" => self.js.debugger_statement(span),
+            "IfStatement" => {
+                let test = self.expression(node.field("test")?)?;
+                let consequent = self.statement(node.field("consequent")?)?;
+                let alternate = self.optional(node, "alternate", Self::statement)?;
+                self.js.if_statement(span, test, consequent, alternate)
+            }
+            "ReturnStatement" => {
+                self.js.return_statement(span, self.optional(node, "argument", Self::expression)?)
+            }
+            "ThrowStatement" => {
+                self.js.throw_statement(span, self.expression(node.field("argument")?)?)
+            }
+            "WhileStatement" => {
+                let test = self.expression(node.field("test")?)?;
+                self.js.while_statement(span, test, self.statement(node.field("body")?)?)
+            }
+            "DoWhileStatement" => {
+                let body = self.statement(node.field("body")?)?;
+                self.js.do_while_statement(span, body, self.expression(node.field("test")?)?)
+            }
+            "ForStatement" => {
+                let init = self.optional(node, "init", |this, value| {
+                    Ok(match this.node(value)?.kind {
+                        "VariableDeclaration" => {
+                            ForStatementInit::VariableDeclaration(this.variable_declaration(value)?)
+                        }
+                        _ => ForStatementInit::from(this.expression(value)?),
+                    })
+                })?;
+                let test = self.optional(node, "test", Self::expression)?;
+                let update = self.optional(node, "update", Self::expression)?;
+                let body = self.statement(node.field("body")?)?;
+                self.js.for_statement(span, init, test, update, body)
+            }
+            "ForInStatement" => {
+                let left = self.for_statement_left(node.field("left")?)?;
+                let right = self.expression(node.field("right")?)?;
+                let body = self.statement(node.field("body")?)?;
+                self.js.for_in_statement(span, left, right, body)
+            }
+            "ForOfStatement" => {
+                let left = self.for_statement_left(node.field("left")?)?;
+                let right = self.expression(node.field("right")?)?;
+                let body = self.statement(node.field("body")?)?;
+                self.js.for_of_statement(span, node.flag("await")?, left, right, body)
+            }
+            "BreakStatement" => {
+                self.js.break_statement(span, self.optional(node, "label", Self::label_identifier)?)
+            }
+            "ContinueStatement" => self
+                .js
+                .continue_statement(span, self.optional(node, "label", Self::label_identifier)?),
+            "LabeledStatement" => {
+                let label = self.label_identifier(node.field("label")?)?;
+                self.js.labeled_statement(span, label, self.statement(node.field("body")?)?)
+            }
+            "SwitchStatement" => {
+                let discriminant = self.expression(node.field("discriminant")?)?;
+                let cases = self.list(node, "cases", |this, value| {
+                    let case = this.node(value)?;
+                    let test = this.optional(case, "test", Self::expression)?;
+                    let consequent = this.list(case, "consequent", Self::statement)?;
+                    Ok(this.js.switch_case(case.span, test, consequent))
+                })?;
+                self.js.switch_statement(span, discriminant, cases)
+            }
+            "TryStatement" => {
+                let block = self.block(node.field("block")?)?;
+                let handler = self.optional(node, "handler", |this, value| {
+                    let clause = this.node(value)?;
+                    let param = this.optional(clause, "param", |this, value| {
+                        let param = this.node(value)?;
+                        // The pattern alone in ESTree
+                        let pattern = match param.kind {
+                            "CatchParameter" => this.binding_pattern(param.field("pattern")?)?,
+                            _ => this.binding_pattern(value)?,
+                        };
+                        Ok(this.js.catch_parameter(param.span, pattern))
+                    })?;
+                    let body = this.block(clause.field("body")?)?;
+                    Ok(this.js.catch_clause(clause.span, param, body))
+                })?;
+                let finalizer = self.optional(node, "finalizer", Self::block)?;
+                self.js.try_statement(span, block, handler, finalizer)
+            }
+            "ImportDeclaration"
+            | "ExportNamedDeclaration"
+            | "ExportDefaultDeclaration"
+            | "ExportAllDeclaration" => self.js.module_declaration(self.module_declaration(node)?),
+            _ => Statement::from(self.declaration(value)?),
+        })
+    }
+
+    fn block(&self, value: &Value) -> Result<Box<'a, BlockStatement<'a>>> {
+        let node = self.node(value)?;
+        if node.kind != "BlockStatement" {
+            return Err(node.unexpected());
+        }
+        Ok(self.js.block(node.span, self.list(node, "body", Self::statement)?))
+    }
+
+    fn for_statement_left(&self, value: &Value) -> Result<ForStatementLeft<'a>> {
+        Ok(match self.node(value)?.kind {
+            "VariableDeclaration" => {
+                ForStatementLeft::VariableDeclaration(self.variable_declaration(value)?)
+            }
+            _ => ForStatementLeft::from(self.assignment_target(value)?),
+        })
+    }
+
+    fn declaration(&self, value: &Value) -> Result<Declaration<'a>> {
+        let node = self.node(value)?;
+        let span = node.span;
+        Ok(match node.kind {
+            "VariableDeclaration" => {
+                Declaration::VariableDeclaration(self.variable_declaration(value)?)
+            }
+            "FunctionDeclaration" | "TSDeclareFunction" => {
+                Declaration::FunctionDeclaration(self.function(value)?)
+            }
+            "ClassDeclaration" => Declaration::ClassDeclaration(self.class(value)?),
+            "TSTypeAliasDeclaration" => {
+                let id = self.binding_identifier(node.field("id")?)?;
+                let type_annotation = self.ts_type(node.field("typeAnnotation")?)?;
+                let type_parameters =
+                    self.optional(node, "typeParameters", Self::type_parameters)?;
+                self.js.ts_type_alias_declaration(
+                    span,
+                    id,
+                    type_annotation,
+                    type_parameters,
+                    node.flag("declare")?,
+                )
+            }
+            "TSInterfaceDeclaration" => {
+                let id = self.binding_identifier(node.field("id")?)?;
+                let body = self.node(node.field("body")?)?;
+                let body = self
+                    .js
+                    .ts_interface_body(body.span, self.list(body, "body", Self::ts_signature)?);
+                let type_parameters =
+                    self.optional(node, "typeParameters", Self::type_parameters)?;
+                let extends = match node.optional("extends") {
+                    Some(extends) if extends.as_array().is_some_and(|array| !array.is_empty()) => {
+                        let extends = self.list(node, "extends", |this, value| {
+                            let heritage = this.node(value)?;
+                            let expression = this.expression(heritage.field("expression")?)?;
+                            Ok((expression, this.type_arguments_of(heritage)?, heritage.span))
+                        })?;
+                        Some(self.js.ts_interface_heritages(extends))
+                    }
+                    _ => None,
+                };
+                self.js.ts_interface_declaration(
+                    span,
+                    id,
+                    body,
+                    type_parameters,
+                    extends,
+                    node.flag("declare")?,
+                )
+            }
+            _ => return Err(node.unexpected()),
+        })
+    }
+
+    pub(super) fn variable_declaration(
+        &self,
+        value: &Value,
+    ) -> Result<Box<'a, VariableDeclaration<'a>>> {
+        let node = self.node(value)?;
+        if node.kind != "VariableDeclaration" {
+            return Err(node.unexpected());
+        }
+        let kind = match node.str("kind")? {
+            "var" => VariableDeclarationKind::Var,
+            "let" => VariableDeclarationKind::Let,
+            "const" => VariableDeclarationKind::Const,
+            _ => return Err(node.invalid("kind")),
+        };
+        let declarations = self.list(node, "declarations", |this, value| {
+            let declarator = this.node(value)?;
+            let id = this.binding_pattern(declarator.field("id")?)?;
+            let init = this.optional(declarator, "init", Self::expression)?;
+            Ok(this.js.variable_declarator(
+                declarator.span,
+                kind,
+                id,
+                init,
+                declarator.flag("definite")?,
+            ))
+        })?;
+        Ok(self.js.variable_declaration(node.span, kind, declarations, node.flag("declare")?))
+    }
+
+    /* ----------  Modules  ---------- */
+
+    fn module_declaration(&self, node: Node) -> Result<ModuleDeclaration<'a>> {
+        let span = node.span;
+        // Import attributes aren't supported
+        if node.optional("withClause").is_some()
+            || node
+                .optional("attributes")
+                .and_then(Value::as_array)
+                .is_some_and(|attributes| !attributes.is_empty())
+        {
+            return Err(node.invalid("withClause"));
+        }
+        Ok(match node.kind {
+            "ImportDeclaration" => {
+                // An empty list in ESTree for `import "a"`
+                let specifiers = match node.optional("specifiers") {
+                    Some(specifiers)
+                        if specifiers.as_array().is_some_and(|list| !list.is_empty()) =>
+                    {
+                        Some(self.list(node, "specifiers", Self::import_specifier)?)
+                    }
+                    _ => None,
+                };
+                let source = self.string_literal(node.field("source")?)?;
+                let import_kind = self.import_or_export_kind(node, "importKind")?;
+                ModuleDeclaration::ImportDeclaration(self.js.import_declaration(
+                    span,
+                    specifiers,
+                    source,
+                    None,
+                    import_kind,
+                ))
+            }
+            "ExportNamedDeclaration" => {
+                let declaration = self.optional(node, "declaration", Self::declaration)?;
+                let source = self.optional(node, "source", Self::string_literal)?;
+                // The local names are references when they aren't re-exported
+                let reference = source.is_none();
+                let specifiers = self.list(node, "specifiers", |this, value| {
+                    let specifier = this.node(value)?;
+                    if specifier.kind != "ExportSpecifier" {
+                        return Err(specifier.unexpected());
+                    }
+                    Ok(ExportSpecifier {
+                        span: specifier.span,
+                        local: this.module_export_name(specifier.field("local")?, reference)?,
+                        exported: this.module_export_name(specifier.field("exported")?, false)?,
+                        export_kind: this.import_or_export_kind(specifier, "exportKind")?,
+                    })
+                })?;
+                let export_kind = self.import_or_export_kind(node, "exportKind")?;
+                ModuleDeclaration::ExportNamedDeclaration(self.js.export_named_declaration(
+                    span,
+                    declaration,
+                    specifiers,
+                    source,
+                    export_kind,
+                    None,
+                ))
+            }
+            "ExportDefaultDeclaration" => {
+                let value = node.field("declaration")?;
+                let declaration = match self.node(value)?.kind {
+                    "FunctionDeclaration" => {
+                        ExportDefaultDeclarationKind::FunctionDeclaration(self.function(value)?)
+                    }
+                    "ClassDeclaration" => {
+                        ExportDefaultDeclarationKind::ClassDeclaration(self.class(value)?)
+                    }
+                    _ => ExportDefaultDeclarationKind::from(self.expression(value)?),
+                };
+                let exported = match node.optional("exported") {
+                    Some(exported) => self.module_export_name(exported, false)?,
+                    // Not in ESTree, it's the `default` keyword
+                    None => {
+                        let start = span
+                            .source_text(self.source_text)
+                            .find("default")
+                            .map_or(span.start, |offset| span.start + offset as u32);
+                        ModuleExportName::IdentifierName(IdentifierName::new(
+                            Span::new(start, start + 7),
+                            Atom::from("default"),
+                        ))
+                    }
+                };
+                ModuleDeclaration::ExportDefaultDeclaration(self.js.export_default_declaration(
+                    span,
+                    declaration,
+                    exported,
+                ))
+            }
+            "ExportAllDeclaration" => {
+                let exported = match node.optional("exported") {
+                    Some(exported) => Some(self.module_export_name(exported, false)?),
+                    None => None,
+                };
+                let source = self.string_literal(node.field("source")?)?;
+                let export_kind = self.import_or_export_kind(node, "exportKind")?;
+                ModuleDeclaration::ExportAllDeclaration(self.js.export_all_declaration(
+                    span,
+                    exported,
+                    source,
+                    None,
+                    export_kind,
+                ))
+            }
+            _ => return Err(node.unexpected()),
+        })
+    }
+
+    fn import_specifier(&self, value: &Value) -> Result<ImportDeclarationSpecifier<'a>> {
+        let node = self.node(value)?;
+        let span = node.span;
+        let local = self.binding_identifier(node.field("local")?)?;
+        Ok(match node.kind {
+            "ImportSpecifier" => {
+                ImportDeclarationSpecifier::ImportSpecifier(self.js.alloc(ImportSpecifier {
+                    span,
+                    imported: self.module_export_name(node.field("imported")?, false)?,
+                    local,
+                    import_kind: self.import_or_export_kind(node, "importKind")?,
+                }))
+            }
+            "ImportDefaultSpecifier" => ImportDeclarationSpecifier::ImportDefaultSpecifier(
+                self.js.alloc(ImportDefaultSpecifier { span, local }),
+            ),
+            "ImportNamespaceSpecifier" => ImportDeclarationSpecifier::ImportNamespaceSpecifier(
+                self.js.alloc(ImportNamespaceSpecifier { span, local }),
+            ),
+            _ => return Err(node.unexpected()),
+        })
+    }
+
+    fn module_export_name(&self, value: &Value, reference: bool) -> Result<ModuleExportName<'a>> {
+        Ok(match self.node(value)?.kind {
+            "Identifier" if reference => {
+                ModuleExportName::IdentifierReference(self.identifier_reference(value)?)
+            }
+            "Identifier" => ModuleExportName::IdentifierName(self.identifier_name(value)?),
+            _ => ModuleExportName::StringLiteral(self.string_literal(value)?),
+        })
+    }
+
+    fn import_or_export_kind(&self, node: Node, field: &str) -> Result<ImportOrExportKind> {
+        Ok(match node.optional(field).map(Value::as_str) {
+            None | Some(Some("value")) => ImportOrExportKind::Value,
+            Some(Some("type")) => ImportOrExportKind::Type,
+            Some(_) => return Err(node.invalid(field)),
+        })
+    }
+
+    /* ----------  TypeScript  ---------- */
+
+    fn type_annotation(&self, value: &Value) -> Result<Box<'a, TSTypeAnnotation<'a>>> {
+        let node = self.node(value)?;
+        if node.kind != "TSTypeAnnotation" {
+            return Err(node.unexpected());
+        }
+        Ok(self.js.ts_type_annotation(node.span, self.ts_type(node.field("typeAnnotation")?)?))
+    }
+
+    /// The type arguments of `node`, `typeArguments` in recent ESTree.
+    fn type_arguments_of(
+        &self,
+        node: Node,
+    ) -> Result<Option<Box<'a, TSTypeParameterInstantiation<'a>>>> {
+        let field = if node.optional("typeArguments").is_some() {
+            "typeArguments"
+        } else {
+            "typeParameters"
+        };
+        self.optional(node, field, Self::type_arguments)
+    }
+
+    fn type_arguments(&self, value: &Value) -> Result<Box<'a, TSTypeParameterInstantiation<'a>>> {
+        let node = self.node(value)?;
+        Ok(self.js.ts_type_arguments(node.span, self.list(node, "params", Self::ts_type)?))
+    }
+
+    fn type_parameters(&self, value: &Value) -> Result<Box<'a, TSTypeParameterDeclaration<'a>>> {
+        let node = self.node(value)?;
+        let params = self.list(node, "params", |this, value| {
+            let param = this.node(value)?;
+            let name = match param.field("name")? {
+                // A string in acorn-typescript
+                Value::String(name) => {
+                    let start = param.span.start;
+                    BindingIdentifier::new(
+                        Span::new(start, start + name.len() as u32),
+                        this.atom(name),
+                    )
+                }
+                name => this.binding_identifier(name)?,
+            };
+            Ok(this.js.ts_type_parameter(
+                param.span,
+                name,
+                this.optional(param, "constraint", Self::ts_type)?,
+                this.optional(param, "default", Self::ts_type)?,
+                param.flag("in")?,
+                param.flag("out")?,
+                param.flag("const")?,
+            ))
+        })?;
+        Ok(self.js.ts_type_parameters(node.span, params))
+    }
+
+    fn ts_type_name(&self, value: &Value) -> Result<TSTypeName<'a>> {
+        let node = self.node(value)?;
+        Ok(match node.kind {
+            "Identifier" => {
+                TSTypeName::IdentifierReference(self.js.alloc(self.identifier_reference(value)?))
+            }
+            "TSQualifiedName" => {
+                let left = self.ts_type_name(node.field("left")?)?;
+                let right = self.identifier_name(node.field("right")?)?;
+                TSTypeName::QualifiedName(self.js.alloc(TSQualifiedName {
+                    span: node.span,
+                    left,
+                    right,
+                }))
+            }
+            _ => return Err(node.unexpected()),
+        })
+    }
+
+    fn ts_type(&self, value: &Value) -> Result<TSType<'a>> {
+        let node = self.node(value)?;
+        let span = node.span;
+        Ok(match node.kind {
+            "TSAnyKeyword" => self.js.ts_any_keyword(span),
+            "TSBigIntKeyword" => self.js.ts_bigint_keyword(span),
+            "TSBooleanKeyword" => self.js.ts_boolean_keyword(span),
+            "TSNeverKeyword" => self.js.ts_never_keyword(span),
+            "TSNullKeyword" => self.js.ts_null_keyword(span),
+            "TSNumberKeyword" => self.js.ts_number_keyword(span),
+            "TSObjectKeyword" => self.js.ts_object_keyword(span),
+            "TSStringKeyword" => self.js.ts_string_keyword(span),
+            "TSSymbolKeyword" => self.js.ts_symbol_keyword(span),
+            "TSUndefinedKeyword" => self.js.ts_undefined_keyword(span),
+            "TSUnknownKeyword" => self.js.ts_unknown_keyword(span),
+            "TSVoidKeyword" => self.js.ts_void_keyword(span),
+            "TSTypeReference" => {
+                let name = self.ts_type_name(node.field("typeName")?)?;
+                self.js.ts_type_reference(span, name, self.type_arguments_of(node)?)
+            }
+            "TSArrayType" => self.js.ts_array_type(span, self.ts_type(node.field("elementType")?)?),
+            "TSUnionType" => self.js.ts_union_type(span, self.list(node, "types", Self::ts_type)?),
+            "TSIntersectionType" => {
+                self.js.ts_intersection_type(span, self.list(node, "types", Self::ts_type)?)
+            }
+            "TSParenthesizedType" => {
+                self.js.ts_parenthesized_type(span, self.ts_type(node.field("typeAnnotation")?)?)
+            }
+            "TSLiteralType" => {
+                let literal = match self.expression(node.field("literal")?)? {
+                    Expression::BooleanLiteral(literal) => TSLiteral::BooleanLiteral(literal),
+                    Expression::NullLiteral(literal) => TSLiteral::NullLiteral(literal),
+                    Expression::NumericLiteral(literal) => TSLiteral::NumericLiteral(literal),
+                    Expression::BigIntLiteral(literal) => TSLiteral::BigIntLiteral(literal),
+                    Expression::RegExpLiteral(literal) => TSLiteral::RegExpLiteral(literal),
+                    Expression::StringLiteral(literal) => TSLiteral::StringLiteral(literal),
+                    Expression::TemplateLiteral(literal) => TSLiteral::TemplateLiteral(literal),
+                    Expression::UnaryExpression(literal) => TSLiteral::UnaryExpression(literal),
+                    _ => return Err(node.invalid("literal")),
+                };
+                self.js.ts_literal_type(span, literal)
+            }
+            "TSTypeLiteral" => {
+                self.js.ts_type_literal(span, self.list(node, "members", Self::ts_signature)?)
+            }
+            "TSFunctionType" => {
+                let this_param = self.optional(node, "thisParam", Self::this_parameter)?;
+                let params = self.formal_parameters(node, FormalParameterKind::Signature)?;
+                let return_type = self.type_annotation(node.field("returnType")?)?;
+                let type_parameters =
+                    self.optional(node, "typeParameters", Self::type_parameters)?;
+                self.js.ts_function_type(span, this_param, params, return_type, type_parameters)
+            }
+            _ => return Err(node.unexpected()),
+        })
+    }
+
+    fn ts_signature(&self, value: &Value) -> Result<TSSignature<'a>> {
+        let node = self.node(value)?;
+        if node.kind != "TSPropertySignature" {
+            return Err(node.unexpected());
+        }
+        let computed = node.flag("computed")?;
+        let key = self.property_key(node.field("key")?, computed)?;
+        let type_annotation = self.optional(node, "typeAnnotation", Self::type_annotation)?;
+        Ok(self.js.ts_property_signature(
+            node.span,
+            computed,
+            node.flag("optional")?,
+            node.flag("readonly")?,
+            key,
+            type_annotation,
+        ))
+    }
+}
+
+/// The base of the number literal `raw`, as the lexer tells it.
+fn number_base(raw: &str, value: f64) -> NumberBase {
+    match raw.get(..2).map(str::to_ascii_lowercase).as_deref() {
+        Some("0x") => NumberBase::Hex,
+        Some("0o") => NumberBase::Octal,
+        Some("0b") => NumberBase::Binary,
+        _ if raw.contains(['e', 'E']) => {
+            if value.fract() == 0.0 {
+                NumberBase::Decimal
+            } else {
+                NumberBase::Float
+            }
+        }
+        _ if raw.contains('.') => NumberBase::Float,
+        _ => NumberBase::Decimal,
+    }
+}
+
+fn unary_operator(operator: &str) -> Option<UnaryOperator> {
+    Some(match operator {
+        "-" => UnaryOperator::UnaryNegation,
+        "+" => UnaryOperator::UnaryPlus,
+        "!" => UnaryOperator::LogicalNot,
+        "~" => UnaryOperator::BitwiseNot,
+        "typeof" => UnaryOperator::Typeof,
+        "void" => UnaryOperator::Void,
+        "delete" => UnaryOperator::Delete,
+        _ => return None,
+    })
+}
+
+fn binary_operator(operator: &str) -> Option<BinaryOperator> {
+    Some(match operator {
+        "==" => BinaryOperator::Equality,
+        "!=" => BinaryOperator::Inequality,
+        "===" => BinaryOperator::StrictEquality,
+        "!==" => BinaryOperator::StrictInequality,
+        "<" => BinaryOperator::LessThan,
+        "<=" => BinaryOperator::LessEqualThan,
+        ">" => BinaryOperator::GreaterThan,
+        ">=" => BinaryOperator::GreaterEqualThan,
+        "<<" => BinaryOperator::ShiftLeft,
+        ">>" => BinaryOperator::ShiftRight,
+        ">>>" => BinaryOperator::ShiftRightZeroFill,
+        "+" => BinaryOperator::Addition,
+        "-" => BinaryOperator::Subtraction,
+        "*" => BinaryOperator::Multiplication,
+        "/" => BinaryOperator::Division,
+        "%" => BinaryOperator::Remainder,
+        "|" => BinaryOperator::BitwiseOR,
+        "^" => BinaryOperator::BitwiseXOR,
+        "&" => BinaryOperator::BitwiseAnd,
+        "in" => BinaryOperator::In,
+        "instanceof" => BinaryOperator::Instanceof,
+        "**" => BinaryOperator::Exponential,
+        _ => return None,
+    })
+}
+
+fn assignment_operator(operator: &str) -> Option<AssignmentOperator> {
+    Some(match operator {
+        "=" => AssignmentOperator::Assign,
+        "+=" => AssignmentOperator::Addition,
+        "-=" => AssignmentOperator::Subtraction,
+        "*=" => AssignmentOperator::Multiplication,
+        "/=" => AssignmentOperator::Division,
+        "%=" => AssignmentOperator::Remainder,
+        "<<=" => AssignmentOperator::ShiftLeft,
+        ">>=" => AssignmentOperator::ShiftRight,
+        ">>>=" => AssignmentOperator::ShiftRightZeroFill,
+        "|=" => AssignmentOperator::BitwiseOR,
+        "^=" => AssignmentOperator::BitwiseXOR,
+        "&=" => AssignmentOperator::BitwiseAnd,
+        "&&=" => AssignmentOperator::LogicalAnd,
+        "||=" => AssignmentOperator::LogicalOr,
+        "??=" => AssignmentOperator::LogicalNullish,
+        "**=" => AssignmentOperator::Exponential,
+        _ => return None,
+    })
+}
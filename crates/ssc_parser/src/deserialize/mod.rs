@@ -0,0 +1,806 @@
+//! Deserialization of the AST from JSON
+//!
+//! Rebuilds a [`Root`] from the JSON of `ssc_ast`'s serialization, or from
+//! the JSON of the Svelte compiler's `parse`. Every node, including those of
+//! the embedded JavaScript and CSS, is built from the fields of the JSON, so
+//! an edited document gives an edited AST. The source text is only read for
+//! what isn't serialized: the parts of attributes and the source of the
+//! stylesheet.
+
+#![allow(clippy::cast_possible_truncation)]
+
+mod css;
+mod js;
+
+use oxc_ast::ast::{BindingPattern, Expression, IdentifierName, VariableDeclarationKind};
+use oxc_diagnostics::{OxcDiagnostic, Result};
+use oxc_span::{Atom, GetSpan, Span};
+use serde_json::{Map, Value};
+use ssc_ast::ast::*;
+
+use self::{css::CssDeserializer, js::JsDeserializer};
+use crate::{
+    diagnostics,
+    element::{attribute_parts, bind_directive_expression, let_directive_expression},
+    tag::render_tag_expression,
+    ParserImpl, ParserReturn, SourceText,
+};
+
+/// A node of the JSON, an object with a `type`, and a span if it has `start`
+/// and `end` offsets.
+#[derive(Clone, Copy)]
+struct Node<'v> {
+    object: &'v Map<String, Value>,
+    kind: &'v str,
+    span: Span,
+}
+
+impl<'v> Node<'v> {
+    /// The node `value`, with its span checked to be in `source_text`.
+    fn new(value: &'v Value, source_text: &str) -> Result<Self> {
+        let object = value
+            .as_object()
+            .ok_or_else(|| diagnostics::ast_json_expected_node(Span::default()))?;
+        let kind = object
+            .get("type")
+            .and_then(Value::as_str)
+            .ok_or_else(|| diagnostics::ast_json_expected_node(Span::default()))?;
+        let offset = |field: &str| {
+            let offset = object.get(field)?.as_u64().and_then(|offset| u32::try_from(offset).ok());
+            Some(offset.filter(|offset| source_text.is_char_boundary(*offset as usize)))
+        };
+        let span = match (offset("start"), offset("end")) {
+            (None, None) => Span::default(),
+            (Some(Some(start)), Some(Some(end))) if start <= end => Span::new(start, end),
+            _ => return Err(diagnostics::ast_json_invalid_field(Span::default(), kind, "end")),
+        };
+        Ok(Self { object, kind, span })
+    }
+
+    fn invalid(&self, field: &str) -> OxcDiagnostic {
+        diagnostics::ast_json_invalid_field(self.span, self.kind, field)
+    }
+
+    fn unexpected(&self) -> OxcDiagnostic {
+        diagnostics::ast_json_unexpected_node(self.span, self.kind)
+    }
+
+    /// The value of `field`, `None` if it's missing or `null`.
+    fn optional(&self, field: &str) -> Option<&'v Value> {
+        self.object.get(field).filter(|value| !value.is_null())
+    }
+
+    fn field(&self, field: &str) -> Result<&'v Value> {
+        self.optional(field).ok_or_else(|| self.invalid(field))
+    }
+
+    fn str(&self, field: &str) -> Result<&'v str> {
+        self.field(field)?.as_str().ok_or_else(|| self.invalid(field))
+    }
+
+    fn array(&self, field: &str) -> Result<&'v [Value]> {
+        self.field(field)?.as_array().map(Vec::as_slice).ok_or_else(|| self.invalid(field))
+    }
+
+    /// The value of the boolean `field`, `false` if it's missing.
+    fn flag(&self, field: &str) -> Result<bool> {
+        self.optional(field)
+            .map_or(Ok(false), |value| value.as_bool().ok_or_else(|| self.invalid(field)))
+    }
+
+    /// The span of the object `field` with `start` and `end` offsets, e.g.
+    /// the `trailingComma` of an array.
+    fn span_of(&self, field: &str) -> Result<Option<Span>> {
+        let Some(value) = self.optional(field) else { return Ok(None) };
+        let offset =
+            |field: &str| value.get(field)?.as_u64().and_then(|offset| u32::try_from(offset).ok());
+        match (offset("start"), offset("end")) {
+            (Some(start), Some(end)) if start <= end => Ok(Some(Span::new(start, end))),
+            _ => Err(self.invalid(field)),
+        }
+    }
+
+    /// The strings of the array `field`, e.g. the modifiers of a directive.
+    fn strings(&self, field: &str) -> Result<impl Iterator<Item = &'v str> + '_> {
+        let values = self.array(field)?;
+        if values.iter().any(|value| !value.is_string()) {
+            return Err(self.invalid(field));
+        }
+        Ok(values.iter().filter_map(Value::as_str))
+    }
+}
+
+impl<'a> ParserImpl<'a> {
+    /// Entry point of [`Parser::deserialize`](crate::Parser::deserialize)
+    pub(crate) fn deserialize(mut self, json: &Value) -> ParserReturn<'a> {
+        let (root, panicked) = match self.deserialize_root(json) {
+            Ok(root) => (root, false),
+            Err(error) => {
+                self.error(error);
+                (self.empty_root(), true)
+            }
+        };
+        let errors = self.lexer.errors.into_iter().chain(self.errors).collect();
+        let trivias = self.lexer.trivia_builder.build();
+        let source_text = SourceText::new(self.source_text);
+        ParserReturn { root, errors, trivias, panicked, source_text }
+    }
+
+    fn deserialize_root(&mut self, json: &Value) -> Result<Root<'a>> {
+        let node = self.node(json)?;
+        if node.kind != "Root" {
+            return Err(node.unexpected());
+        }
+        // Decides how the scripts are built, so it's read first. The Svelte
+        // compiler has it in `metadata`
+        let ts = node.optional("ts").or_else(|| node.optional("metadata")?.get("ts"));
+        if let Some(ts) = ts {
+            self.ts = ts.as_bool().ok_or_else(|| node.invalid("ts"))?;
+        }
+        let mut fragment = self.deserialize_fragment(node.field("fragment")?)?;
+        let css = node.optional("css").map(|css| self.deserialize_style(css)).transpose()?;
+        let instance =
+            node.optional("instance").map(|script| self.deserialize_script(script)).transpose()?;
+        let module =
+            node.optional("module").map(|script| self.deserialize_script(script)).transpose()?;
+
+        // The Svelte compiler leaves `<svelte:options>` out of the fragment
+        let has_options_element = fragment
+            .nodes
+            .iter()
+            .any(|node| matches!(node, FragmentNode::Element(Element::SvelteOptionsRaw(_))));
+        if !has_options_element {
+            if let Some(element) = self.deserialize_options_element(node)? {
+                let start = element.span().start;
+                let index = fragment
+                    .nodes
+                    .iter()
+                    .position(|node| node.span().start > start)
+                    .unwrap_or(fragment.nodes.len());
+                fragment.nodes.insert(index, FragmentNode::Element(element));
+            }
+        }
+        let options = self.parse_svelte_options(&fragment);
+        let mut root = self.ast.root(node.span, fragment, css, instance, module, self.ts);
+        root.options = options;
+        Ok(root)
+    }
+
+    /// The `<svelte:options>` element of the `options` of the Svelte
+    /// compiler's JSON, which spans the element and keeps its attributes.
+    fn deserialize_options_element(&self, root: Node) -> Result<Option<Element<'a>>> {
+        let Some(options) = root.optional("options") else { return Ok(None) };
+        let Some(attributes) = options.get("attributes").and_then(Value::as_array) else {
+            return Ok(None);
+        };
+        let span = root.span_of("options")?.unwrap_or_default();
+        let mut element_attributes = self.ast.new_vec_with_capacity(attributes.len());
+        for attribute in attributes {
+            element_attributes.push(self.deserialize_element_attribute(attribute)?);
+        }
+        let fragment = self.ast.fragment(self.ast.new_vec(), false);
+        Ok(Some(self.ast.svelte_options(span, element_attributes, fragment)))
+    }
+
+    fn node<'v>(&self, value: &'v Value) -> Result<Node<'v>> {
+        Node::new(value, self.source_text)
+    }
+
+    fn js(&self) -> JsDeserializer<'a> {
+        JsDeserializer::new(self.allocator, self.source_text, self.ts)
+    }
+
+    fn deserialize_fragment(&mut self, value: &Value) -> Result<Fragment<'a>> {
+        let node = self.node(value)?;
+        let mut nodes = self.ast.new_vec();
+        for value in node.array("nodes")? {
+            let child = self.node(value)?;
+            nodes.push(match child.kind {
+                "Text" => FragmentNode::Text(self.deserialize_text(child)?),
+                "Comment" => {
+                    // Also kept as trivias, as the parser does
+                    self.lexer.trivia_builder.add_comment(child.span.start, child.span.end);
+                    FragmentNode::Comment(self.deserialize_comment(child)?)
+                }
+                "ExpressionTag" | "HtmlTag" | "ConstTag" | "DebugTag" | "RenderTag" => {
+                    FragmentNode::Tag(self.deserialize_tag(child)?)
+                }
+                "EachBlock" | "IfBlock" | "AwaitBlock" | "KeyBlock" | "SnippetBlock" => {
+                    FragmentNode::Block(self.deserialize_block(child)?)
+                }
+                _ => FragmentNode::Element(self.deserialize_element(child)?),
+            });
+        }
+        Ok(self.ast.fragment(nodes, node.flag("transparent")?))
+    }
+
+    fn deserialize_optional_fragment(
+        &mut self,
+        node: Node,
+        field: &str,
+    ) -> Result<Option<Fragment<'a>>> {
+        node.optional(field).map(|fragment| self.deserialize_fragment(fragment)).transpose()
+    }
+
+    fn deserialize_comment(&self, node: Node) -> Result<Comment<'a>> {
+        Ok(self.ast.comment(node.span, self.ast.new_atom(node.str("data")?)))
+    }
+
+    fn deserialize_text(&self, node: Node) -> Result<Text<'a>> {
+        let raw = self.ast.new_atom(node.str("raw")?);
+        let data = match node.optional("data") {
+            Some(data) => self.ast.new_atom(data.as_str().ok_or_else(|| node.invalid("data"))?),
+            None => raw.clone(),
+        };
+        Ok(Text { span: node.span, data, raw })
+    }
+
+    fn deserialize_tag(&self, node: Node) -> Result<Tag<'a>> {
+        Ok(match node.kind {
+            "ExpressionTag" => Tag::ExpressionTag(
+                self.ast
+                    .expression_tag(node.span, self.deserialize_expression(node, "expression")?),
+            ),
+            "HtmlTag" => Tag::HtmlTag(
+                self.ast.html_tag(node.span, self.deserialize_expression(node, "expression")?),
+            ),
+            "ConstTag" => {
+                let declaration = self.js().variable_declaration(node.field("declaration")?)?;
+                if declaration.kind != VariableDeclarationKind::Const
+                    || declaration.declarations.len() != 1
+                {
+                    return Err(node.invalid("declaration"));
+                }
+                Tag::ConstTag(self.ast.const_tag(node.span, declaration.unbox()))
+            }
+            "DebugTag" => {
+                let js = self.js();
+                let mut identifiers = self.ast.new_vec();
+                for identifier in node.array("identifiers")? {
+                    identifiers.push(js.identifier_reference(identifier)?);
+                }
+                Tag::DebugTag(self.ast.debug_tag(node.span, identifiers))
+            }
+            "RenderTag" => {
+                let value = node.field("expression")?;
+                let expression = match self.js().expression(value)? {
+                    // The chain expression of an optional call isn't serialized
+                    Expression::CallExpression(call) if in_optional_chain(value) => {
+                        RenderTagExpression::Chain(call.unbox())
+                    }
+                    expression => render_tag_expression(expression, node.span)?,
+                };
+                Tag::RenderTag(self.ast.render_tag(node.span, expression))
+            }
+            _ => return Err(node.unexpected()),
+        })
+    }
+
+    fn deserialize_block(&mut self, node: Node) -> Result<Block<'a>> {
+        Ok(match node.kind {
+            "EachBlock" => {
+                let expression = self.deserialize_expression(node, "expression")?;
+                let context = self.deserialize_binding_pattern(node.field("context")?)?;
+                let body = self.deserialize_fragment(node.field("body")?)?;
+                let fallback = self.deserialize_optional_fragment(node, "fallback")?;
+                let index = match node.optional("index") {
+                    // A string in the Svelte compiler's JSON, after the context
+                    Some(Value::String(name)) => {
+                        let context_end = self.node(node.field("context")?)?.span.end;
+                        let span = self
+                            .source_text
+                            .get((context_end as usize)..(node.span.end as usize))
+                            .and_then(|text| text.find(name.as_str()))
+                            .map_or(Span::default(), |offset| {
+                                let start = context_end + offset as u32;
+                                Span::new(start, start + name.len() as u32)
+                            });
+                        Some(IdentifierName::new(span, self.ast.new_atom(name)))
+                    }
+                    Some(index) => Some(self.js().identifier_name(index)?),
+                    None => None,
+                };
+                let key = self.deserialize_optional_expression(node, "key")?;
+                Block::EachBlock(
+                    self.ast.each_block(node.span, expression, context, body, fallback, index, key),
+                )
+            }
+            "IfBlock" => {
+                let test = self.deserialize_expression(node, "test")?;
+                let consequent = self.deserialize_fragment(node.field("consequent")?)?;
+                let alternate = self.deserialize_optional_fragment(node, "alternate")?;
+                Block::IfBlock(self.ast.if_block(
+                    node.span,
+                    node.flag("elseif")?,
+                    test,
+                    consequent,
+                    alternate,
+                ))
+            }
+            "AwaitBlock" => {
+                let expression = self.deserialize_expression(node, "expression")?;
+                let value = node
+                    .optional("value")
+                    .map(|value| self.deserialize_binding_pattern(value))
+                    .transpose()?;
+                let error = node
+                    .optional("error")
+                    .map(|error| self.deserialize_binding_pattern(error))
+                    .transpose()?;
+                let pending = self.deserialize_optional_fragment(node, "pending")?;
+                let then = self.deserialize_optional_fragment(node, "then")?;
+                let catch = self.deserialize_optional_fragment(node, "catch")?;
+                Block::AwaitBlock(
+                    self.ast.await_block(node.span, expression, value, error, pending, then, catch),
+                )
+            }
+            "KeyBlock" => {
+                let expression = self.deserialize_expression(node, "expression")?;
+                let fragment = self.deserialize_fragment(node.field("fragment")?)?;
+                Block::KeyBlock(self.ast.key_block(node.span, expression, fragment))
+            }
+            "SnippetBlock" => {
+                let expression = self.js().identifier_name(node.field("expression")?)?;
+                let mut parameters = self.ast.new_vec();
+                for parameter in node.array("parameters")? {
+                    parameters.push(self.deserialize_binding_pattern(parameter)?);
+                }
+                let body = self.deserialize_fragment(node.field("body")?)?;
+                Block::SnippetBlock(self.ast.snippet_block(node.span, expression, parameters, body))
+            }
+            _ => return Err(node.unexpected()),
+        })
+    }
+
+    fn deserialize_element(&mut self, node: Node) -> Result<Element<'a>> {
+        let mut attributes = self.ast.new_vec();
+        for attribute in node.array("attributes")? {
+            attributes.push(self.deserialize_element_attribute(attribute)?);
+        }
+        let fragment = self.deserialize_fragment(node.field("fragment")?)?;
+        let span = node.span;
+        Ok(match node.kind {
+            "Component" => {
+                self.ast.component(span, self.ast.new_atom(node.str("name")?), attributes, fragment)
+            }
+            "RegularElement" => self.ast.regular_element(
+                span,
+                self.ast.new_atom(node.str("name")?),
+                attributes,
+                fragment,
+            ),
+            "TitleElement" => self.ast.title_element(span, attributes, fragment),
+            "SlotElement" => self.ast.slot_element(span, attributes, fragment),
+            "SvelteBody" => self.ast.svelte_body(span, attributes, fragment),
+            "SvelteBoundary" => self.ast.svelte_boundary(span, attributes, fragment),
+            "SvelteComponent" => {
+                let expression = self.deserialize_expression(node, "expression")?;
+                self.ast.svelte_component(span, attributes, fragment, expression)
+            }
+            "SvelteDocument" => self.ast.svelte_document(span, attributes, fragment),
+            "SvelteElement" => {
+                // `tag` in the Svelte compiler's JSON
+                let field = if node.optional("tag").is_some() { "tag" } else { "expression" };
+                let expression = self.deserialize_expression(node, field)?;
+                self.ast.svelte_element(span, attributes, fragment, expression)
+            }
+            "SvelteFragment" => self.ast.svelte_fragment(span, attributes, fragment),
+            "SvelteHead" => self.ast.svelte_head(span, attributes, fragment),
+            "SvelteOptionsRaw" => self.ast.svelte_options(span, attributes, fragment),
+            "SvelteSelf" => self.ast.svelte_self(span, attributes, fragment),
+            "SvelteWindow" => self.ast.svelte_window(span, attributes, fragment),
+            _ => return Err(node.unexpected()),
+        })
+    }
+
+    fn deserialize_element_attribute(&self, value: &Value) -> Result<ElementAttribute<'a>> {
+        let node = self.node(value)?;
+        let name = || node.str("name").map(|name| self.ast.new_atom(name));
+        let mut directive = match node.kind {
+            "Attribute" => {
+                return self.deserialize_attribute(node).map(ElementAttribute::Attribute)
+            }
+            "SpreadAttribute" => {
+                let expression = self.deserialize_expression(node, "expression")?;
+                return Ok(ElementAttribute::SpreadAttribute(
+                    self.ast.spread_attribute(node.span, expression),
+                ));
+            }
+            "AnimateDirective" => {
+                let expression = self.deserialize_optional_expression(node, "expression")?;
+                self.ast.animate_directive(node.span, name()?, expression)
+            }
+            "BindDirective" => {
+                let expression = self.deserialize_expression(node, "expression")?;
+                let expression = bind_directive_expression(Some(expression), node.span)?;
+                self.ast.bind_directive(node.span, name()?, expression)
+            }
+            "ClassDirective" => {
+                let expression = self.deserialize_expression(node, "expression")?;
+                self.ast.class_directive(node.span, name()?, expression)
+            }
+            "LetDirective" => {
+                let expression = self
+                    .deserialize_optional_expression(node, "expression")?
+                    .map(|expression| let_directive_expression(expression, node.span))
+                    .transpose()?;
+                self.ast.let_directive(node.span, name()?, expression)
+            }
+            "OnDirective" => {
+                let expression = self.deserialize_optional_expression(node, "expression")?;
+                let modifiers = self.ast.new_vec_from_iter(
+                    node.strings("modifiers")?.map(|modifier| self.ast.new_atom(modifier)),
+                );
+                self.ast.on_directive(node.span, name()?, expression, modifiers)
+            }
+            "StyleDirective" => {
+                let parts = self.attribute_parts(node.span);
+                let value =
+                    self.deserialize_attribute_value(node.optional("value"), parts.value)?;
+                let mut modifiers = self.ast.new_vec();
+                for modifier in node.strings("modifiers")? {
+                    modifiers.push(match modifier {
+                        "important" => StyleDirectiveModifier::Important,
+                        _ => {
+                            return Err(diagnostics::invalid_modifier(
+                                node.span,
+                                modifier,
+                                &["important"],
+                            ))
+                        }
+                    });
+                }
+                self.ast.style_directive(node.span, name()?, value, modifiers)
+            }
+            "TransitionDirective" => {
+                let expression = self.deserialize_optional_expression(node, "expression")?;
+                let mut modifiers = self.ast.new_vec();
+                for modifier in node.strings("modifiers")? {
+                    modifiers.push(match modifier {
+                        "local" => TransitionDirectiveModifier::Local,
+                        "global" => TransitionDirectiveModifier::Global,
+                        _ => {
+                            return Err(diagnostics::invalid_modifier(
+                                node.span,
+                                modifier,
+                                &["local", "global"],
+                            ))
+                        }
+                    });
+                }
+                self.ast.transition_directive(
+                    node.span,
+                    name()?,
+                    expression,
+                    modifiers,
+                    node.flag("intro")?,
+                    node.flag("outro")?,
+                )
+            }
+            "UseDirective" => {
+                let expression = self.deserialize_optional_expression(node, "expression")?;
+                self.ast.use_directive(node.span, name()?, expression)
+            }
+            _ => return Err(node.unexpected()),
+        };
+        *directive.parts_mut() = self.attribute_parts(node.span);
+        Ok(ElementAttribute::DirectiveAttribute(directive))
+    }
+
+    fn deserialize_attribute(&self, node: Node) -> Result<Attribute<'a>> {
+        if node.kind != "Attribute" {
+            return Err(node.unexpected());
+        }
+        let parts = self.attribute_parts(node.span);
+        let value = self.deserialize_attribute_value(node.optional("value"), parts.value)?;
+        let mut attribute =
+            self.ast.attribute(node.span, self.ast.new_atom(node.str("name")?), value);
+        attribute.parts = parts;
+        Ok(attribute)
+    }
+
+    /// The value of an attribute or a `style:` directive, from an
+    /// `AttributeValue` node, or from the `true`, array or `ExpressionTag`
+    /// value of the JSON of the Svelte compiler, spanning `span`.
+    fn deserialize_attribute_value(
+        &self,
+        value: Option<&Value>,
+        span: Option<Span>,
+    ) -> Result<Option<AttributeValue<'a>>> {
+        let (values, span) = match value {
+            None | Some(Value::Bool(true)) => return Ok(None),
+            Some(Value::Array(values)) => (values.as_slice(), span),
+            Some(value) => {
+                let node = self.node(value)?;
+                if node.kind == "AttributeValue" {
+                    (node.array("sequence")?, Some(node.span))
+                } else {
+                    (std::slice::from_ref(value), span.or(Some(node.span)))
+                }
+            }
+        };
+        let mut sequence = self.ast.new_vec_with_capacity(values.len());
+        for value in values {
+            let node = self.node(value)?;
+            sequence.push(match node.kind {
+                "Text" => AttributeSequenceValue::Text(self.deserialize_text(node)?),
+                "ExpressionTag" => {
+                    AttributeSequenceValue::ExpressionTag(self.ast.expression_tag(
+                        node.span,
+                        self.deserialize_expression(node, "expression")?,
+                    ))
+                }
+                _ => return Err(node.unexpected()),
+            });
+        }
+        Ok(Some(self.ast.attribute_value(span.unwrap_or_default(), sequence)))
+    }
+
+    /// The parts of the attribute or directive at `span`, read from the
+    /// source text as they aren't serialized.
+    fn attribute_parts(&self, span: Span) -> AttributeParts {
+        let text = span.source_text(self.source_text);
+        // The `{name}` shorthand
+        if let Some(name) = text.strip_prefix('{') {
+            let name = name.trim_end_matches('}');
+            let start = span.start + 1 + (name.len() - name.trim_start().len()) as u32;
+            let name = Span::new(start, start + name.trim().len() as u32);
+            return AttributeParts { name, value: Some(span), ..Default::default() };
+        }
+        let name_len = text.find(|ch: char| ch == '=' || ch.is_whitespace()).unwrap_or(text.len());
+        let rest = &text[name_len..];
+        let (equals, value) = match rest.trim_start().strip_prefix('=') {
+            Some(value) => {
+                let equals = span.end - value.len() as u32 - 1;
+                let value = Span::new(span.end - value.trim_start().len() as u32, span.end);
+                (Some(Span::new(equals, equals + 1)), Some(value))
+            }
+            None => (None, None),
+        };
+        attribute_parts(span.start, &text[..name_len], equals, value)
+    }
+
+    fn deserialize_script(&self, value: &Value) -> Result<Script<'a>> {
+        let node = self.node(value)?;
+        let attributes = self.deserialize_raw_text_attributes(node)?;
+        let raw = self.deserialize_raw(node)?;
+        let program = self.js().program(node.field("content")?)?;
+        let context = match node.str("context")? {
+            "default" => ScriptContext::Default,
+            "module" => ScriptContext::Module,
+            _ => return Err(node.invalid("context")),
+        };
+        Ok(self.ast.script(node.span, context, program, attributes, raw))
+    }
+
+    /// The `<style>` from a `Style` node, or from the `StyleSheet` of the
+    /// Svelte compiler, which has the attributes of the `<style>`.
+    fn deserialize_style(&self, value: &Value) -> Result<Style<'a>> {
+        let node = self.node(value)?;
+        let css = CssDeserializer::new(self.allocator, self.source_text);
+        let attributes = self.deserialize_raw_text_attributes(node)?;
+        if node.kind == "StyleSheet" {
+            return Ok(self.ast.style(node.span, css.stylesheet(value)?, attributes, None));
+        }
+        let raw = self.deserialize_raw(node)?;
+        let stylesheet = css.stylesheet(node.field("stylesheet")?)?;
+        Ok(self.ast.style(node.span, stylesheet, attributes, raw))
+    }
+
+    /// The attributes of a `<script>` or `<style>`.
+    fn deserialize_raw_text_attributes(
+        &self,
+        node: Node,
+    ) -> Result<oxc_allocator::Vec<'a, Attribute<'a>>> {
+        let mut attributes = self.ast.new_vec();
+        for attribute in node.array("attributes")? {
+            let attribute = self.node(attribute)?;
+            attributes.push(self.deserialize_attribute(attribute)?);
+        }
+        Ok(attributes)
+    }
+
+    /// The unparsed content of a `<script>` or `<style>`.
+    fn deserialize_raw(&self, node: Node) -> Result<Option<Atom<'a>>> {
+        match node.optional("raw") {
+            Some(raw) => {
+                Ok(Some(self.ast.new_atom(raw.as_str().ok_or_else(|| node.invalid("raw"))?)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn deserialize_expression(&self, node: Node, field: &str) -> Result<Expression<'a>> {
+        self.js().expression(node.field(field)?)
+    }
+
+    fn deserialize_optional_expression(
+        &self,
+        node: Node,
+        field: &str,
+    ) -> Result<Option<Expression<'a>>> {
+        match node.optional(field) {
+            Some(_) => self.deserialize_expression(node, field).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn deserialize_binding_pattern(&self, value: &Value) -> Result<BindingPattern<'a>> {
+        self.js().binding_pattern(value)
+    }
+}
+
+/// Whether the call or member expression `value` is part of an optional
+/// chain, e.g. `a?.b()`.
+fn in_optional_chain(value: &Value) -> bool {
+    value.get("optional").and_then(Value::as_bool) == Some(true)
+        || ["callee", "object"]
+            .into_iter()
+            .any(|field| value.get(field).is_some_and(in_optional_chain))
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_ast::ast::{BindingPatternKind, Statement};
+    use oxc_span::Span;
+    use serde_json::{json, Value};
+    use ssc_ast::ast::*;
+    use ssc_css_ast::ast as css;
+
+    use crate::Parser;
+
+    #[test]
+    fn round_trip() {
+        let source = r#"<script context="module">export const a = 1;</script>
+<script lang="ts">let { items, Card }: { items: string[] } = $props(); let value = $state("");</script>
+<svelte:options runes />
+<!-- comment -->
+{#each items as { id, label }, index (id)}
+    {@const double = index * 2}
+    <p class="item {label}" {id} on:click|once={() => value = label} style:color="red">{label}: {double}</p>
+{:else}
+    {@html "<b>empty</b>"}
+{/each}
+{#if value}<input bind:value={value} use:action transition:fade|local />{:else if items}{@debug value}{/if}
+{#await Promise.resolve(1) then result}{result}{:catch error}{error}{/await}
+{#key value}<Card {...$$props} let:item={{ name }} />{/key}
+{#snippet row(item)}<svelte:element this="div">{item}</svelte:element>{/snippet}
+{@render row?.("a")}
+<svelte:component this={Card} />
+<style>p { color: red; }</style>"#;
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let json = serde_json::to_value(&ret.root).unwrap();
+
+        let ret = Parser::new(&allocator, source).deserialize(&json);
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        assert!(!ret.panicked);
+        assert_eq!(serde_json::to_value(&ret.root).unwrap(), json);
+        assert_eq!(ret.root.options.unwrap().runes, Some(true));
+    }
+
+    #[test]
+    fn edited_json() {
+        let source = r#"<script lang="ts">let count: number = $state(0);</script>
+<button on:click={() => count++}>clicks: {count}</button>
+<style>button { color: red; }</style>"#;
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let json = serde_json::to_value(&ret.root).unwrap().to_string();
+        let json = json
+            .replace(r#""name":"count""#, r#""name":"total""#)
+            .replace("clicks: ", "total: ")
+            .replace(r#""value":"red""#, r#""value":"blue""#);
+        let json = serde_json::from_str::<Value>(&json).unwrap();
+
+        let ret = Parser::new(&allocator, source).deserialize(&json);
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        assert_eq!(serde_json::to_value(&ret.root).unwrap(), json);
+
+        let program = &ret.root.instance.as_ref().unwrap().program;
+        let Statement::VariableDeclaration(declaration) = &program.body[0] else { panic!() };
+        let BindingPatternKind::BindingIdentifier(id) = &declaration.declarations[0].id.kind else {
+            panic!()
+        };
+        assert_eq!(id.name.as_str(), "total");
+        let button = ret.root.fragment.nodes.iter().find_map(|node| match node {
+            FragmentNode::Element(Element::RegularElement(element)) => Some(element),
+            _ => None,
+        });
+        let FragmentNode::Text(text) = &button.unwrap().fragment.nodes[0] else { panic!() };
+        assert_eq!(text.data.as_str(), "total: ");
+        let stylesheet = &ret.root.css.as_ref().unwrap().stylesheet;
+        let css::Rule::StyleRule(rule) = &stylesheet.children[0] else { panic!() };
+        let css::BlockChild::Declaration(declaration) = &rule.block.children[0] else { panic!() };
+        let css::ComponentValue::Ident(ident) = &declaration.components[0] else { panic!() };
+        assert_eq!(ident.name.as_str(), "blue");
+    }
+
+    #[test]
+    fn svelte_json() {
+        let source = "<svelte:options runes />\n{#each items as item, i}<p>{i}</p>{/each}\n<style>p { color: red; }</style>";
+        let json = json!({
+            "type": "Root", "start": 0, "end": 99,
+            "options": {
+                "start": 0, "end": 24, "runes": true,
+                "attributes": [{ "type": "Attribute", "start": 16, "end": 21, "name": "runes", "value": true }]
+            },
+            "fragment": { "type": "Fragment", "nodes": [
+                { "type": "Text", "start": 24, "end": 25, "raw": "\n", "data": "\n" },
+                {
+                    "type": "EachBlock", "start": 25, "end": 66,
+                    "expression": { "type": "Identifier", "start": 32, "end": 37, "name": "items" },
+                    "context": { "type": "Identifier", "start": 41, "end": 45, "name": "item" },
+                    "index": "i",
+                    "body": { "type": "Fragment", "nodes": [{
+                        "type": "RegularElement", "start": 49, "end": 59, "name": "p", "attributes": [],
+                        "fragment": { "type": "Fragment", "nodes": [{
+                            "type": "ExpressionTag", "start": 52, "end": 55,
+                            "expression": { "type": "Identifier", "start": 53, "end": 54, "name": "i" }
+                        }] }
+                    }] }
+                },
+                { "type": "Text", "start": 66, "end": 67, "raw": "\n", "data": "\n" }
+            ] },
+            "css": {
+                "type": "StyleSheet", "start": 67, "end": 99, "attributes": [],
+                "children": [{
+                    "type": "Rule", "start": 74, "end": 91,
+                    "prelude": { "type": "SelectorList", "start": 74, "end": 75, "children": [{
+                        "type": "ComplexSelector", "start": 74, "end": 75, "children": [{
+                            "type": "RelativeSelector", "start": 74, "end": 75, "combinator": null,
+                            "selectors": [{ "type": "TypeSelector", "start": 74, "end": 75, "name": "p" }]
+                        }]
+                    }] },
+                    "block": { "type": "Block", "start": 76, "end": 91, "children": [
+                        { "type": "Declaration", "start": 78, "end": 88, "property": "color", "value": "red" }
+                    ] }
+                }],
+                "content": { "start": 74, "end": 91, "styles": "p { color: red; }", "comment": null }
+            },
+            "instance": null,
+            "module": null,
+            "metadata": { "ts": false }
+        });
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source).deserialize(&json);
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let root = ret.root;
+        assert_eq!(root.options.unwrap().runes, Some(true));
+        assert!(matches!(
+            root.fragment.nodes[0],
+            FragmentNode::Element(Element::SvelteOptionsRaw(_))
+        ));
+        let FragmentNode::Block(Block::EachBlock(block)) = &root.fragment.nodes[2] else {
+            panic!()
+        };
+        let index = block.index.as_ref().unwrap();
+        assert_eq!((index.name.as_str(), index.span), ("i", Span::new(47, 48)));
+        let style = root.css.unwrap();
+        assert_eq!(style.stylesheet.source.as_str(), "p { color: red; }");
+        let css::Rule::StyleRule(rule) = &style.stylesheet.children[0] else { panic!() };
+        let css::BlockChild::Declaration(declaration) = &rule.block.children[0] else { panic!() };
+        let css::ComponentValue::Ident(ident) = &declaration.components[0] else { panic!() };
+        assert_eq!(ident.span, Span::new(85, 88));
+    }
+
+    #[test]
+    fn invalid_json() {
+        let allocator = Allocator::default();
+        for json in [
+            json!(null),
+            json!({ "type": "Fragment", "nodes": [] }),
+            json!({ "type": "Root", "start": 0, "end": 9, "fragment": { "type": "Fragment", "nodes": [] } }),
+            json!({ "type": "Root", "fragment": { "type": "Fragment", "nodes": [{ "type": "Paragraph" }] } }),
+            json!({ "type": "Root", "fragment": { "type": "Fragment", "nodes": [{ "type": "Text" }] } }),
+        ] {
+            let ret = Parser::new(&allocator, "<p>").deserialize(&json);
+            assert!(ret.panicked, "{json}");
+            assert_eq!(ret.errors.len(), 1, "{json}");
+            assert!(ret.root.fragment.nodes.is_empty());
+        }
+    }
+}
@@ -62,6 +62,27 @@ pub fn invalid_modifier(span: Span, name: &str, valid: &[&str]) -> OxcDiagnostic
     .with_label(span)
 }
 
+#[cold]
+pub fn duplicate_modifier(span: Span, name: &str) -> OxcDiagnostic {
+    OxcDiagnostic::warn(format!("The `{name}` modifier is repeated"))
+        .with_error_code("svelte", "duplicate_modifier")
+        .with_label(span)
+}
+
+#[cold]
+pub fn style_directive_invalid_name(span: Span, name: &str) -> OxcDiagnostic {
+    OxcDiagnostic::error(format!("`{name}` is not a valid CSS property name")).with_label(span)
+}
+
+#[cold]
+pub fn style_directive_invalid_shorthand(span: Span, name: &str) -> OxcDiagnostic {
+    OxcDiagnostic::error(format!(
+        "`style:{name}` can't be shorthand, `{name}` is not a valid identifier"
+    ))
+    .with_label(span)
+    .with_help(format!("Add a value, `style:{name}={{value}}`"))
+}
+
 #[cold]
 pub fn duplicate_script(span0: Span, span1: Span) -> OxcDiagnostic {
     OxcDiagnostic::error("A component can have a single top-level `<script>` element and/or a single top-level `<script context=\"module\">` element").with_labels([
@@ -131,3 +152,53 @@ pub fn svelte_component_invalid_this(span: Span) -> OxcDiagnostic {
 pub fn svelte_element_missing_this(span: Span) -> OxcDiagnostic {
     OxcDiagnostic::error("`<svelte:element>` must have a 'this' attribute").with_label(span)
 }
+
+#[cold]
+pub fn void_element_invalid_content(span: Span, name: &str) -> OxcDiagnostic {
+    OxcDiagnostic::error(format!(
+        "`<{name}>` is a void element, it cannot have children or a closing tag"
+    ))
+    .with_label(span)
+}
+
+#[cold]
+pub fn element_invalid_self_closing_tag(span: Span, slash: Span, name: &str) -> OxcDiagnostic {
+    OxcDiagnostic::warn(format!(
+        "Self-closing HTML tags for non-void elements are ambiguous — use `<{name} ...></{name}>` rather than `<{name} ... />`"
+    ))
+    .with_error_code("svelte", "element_invalid_self_closing_tag")
+    .with_labels([span.into(), slash.label(format!("Replace with `></{name}>`"))])
+    .with_help("Only void elements, components, `<svelte:*>` elements and the elements of `<svg>` and `<math>` can be self-closing")
+}
+
+#[cold]
+pub fn svelte_options_invalid_attribute_value(span: Span, list: &str) -> OxcDiagnostic {
+    OxcDiagnostic::error(format!("Value must be {list}, if specified")).with_label(span)
+}
+
+#[cold]
+pub fn unsupported_lang(span: Span, element: &str, lang: &str) -> OxcDiagnostic {
+    OxcDiagnostic::warn(format!(
+        "The content of `<{element}>` wasn't parsed, `{lang}` isn't supported"
+    ))
+    .with_error_code("svelte", "unsupported_lang")
+    .with_help(format!("Preprocess the `<{element}>` before compiling the component"))
+    .with_label(span)
+}
+
+#[cold]
+pub fn ast_json_expected_node(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error("Invalid AST JSON: expected a node, an object with a `type`")
+        .with_label(span)
+}
+
+#[cold]
+pub fn ast_json_invalid_field(span: Span, kind: &str, field: &str) -> OxcDiagnostic {
+    OxcDiagnostic::error(format!("Invalid AST JSON: missing or invalid `{field}` in `{kind}` node"))
+        .with_label(span)
+}
+
+#[cold]
+pub fn ast_json_unexpected_node(span: Span, kind: &str) -> OxcDiagnostic {
+    OxcDiagnostic::error(format!("Invalid AST JSON: unexpected `{kind}` node")).with_label(span)
+}
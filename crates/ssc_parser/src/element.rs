@@ -1,10 +1,13 @@
 #![allow(clippy::cast_possible_truncation)]
 
+use std::borrow::Cow;
+
 use oxc_allocator::Vec;
 use oxc_ast::ast::{Expression, MemberExpression, StringLiteral};
 use oxc_diagnostics::Result;
 use oxc_span::{Atom, GetSpan, SourceType, Span};
-use ssc_ast::{ast::*, AstBuilder};
+use oxc_syntax::identifier::is_identifier_name;
+use ssc_ast::{ast::*, html, AstBuilder};
 
 use crate::{diagnostics, Kind, ParserImpl};
 
@@ -43,10 +46,12 @@ impl<'a> ParserImpl<'a> {
 
         while !self.at(Kind::Eof) {
             if self.prev_token_end != self.cur_token().start {
-                let text = self.parse_text();
-                nodes.push(FragmentNode::Text(text));
+                self.parse_text(&mut nodes);
             } else if self.at(Kind::LAngle) {
-                if self.peek_at(Kind::Script) {
+                if self.template {
+                    let element = self.parse_element()?;
+                    nodes.push(FragmentNode::Element(element));
+                } else if self.peek_at(Kind::Script) {
                     let cur_script = self.parse_script()?;
 
                     if cur_script.context == ScriptContext::Default {
@@ -86,8 +91,7 @@ impl<'a> ParserImpl<'a> {
                     nodes.push(FragmentNode::Tag(tag));
                 }
             } else {
-                let text = self.parse_text();
-                nodes.push(FragmentNode::Text(text));
+                self.parse_text(&mut nodes);
             }
         }
 
@@ -95,6 +99,54 @@ impl<'a> ParserImpl<'a> {
         Ok((fragment, style, script, module))
     }
 
+    /// Read the options set by the top-level `<svelte:options>` element, which
+    /// is kept in the fragment along with its attributes.
+    pub(crate) fn parse_svelte_options(
+        &mut self,
+        fragment: &Fragment<'a>,
+    ) -> Option<SvelteOptions<'a>> {
+        let element = fragment.nodes.iter().find_map(|node| match node {
+            FragmentNode::Element(Element::SvelteOptionsRaw(element)) => Some(element),
+            _ => None,
+        })?;
+        let mut options = self.ast.options(element.span);
+        for attribute in element.attributes.iter().filter_map(ElementAttribute::as_attribute) {
+            let option = match attribute.name.as_str() {
+                "runes" => &mut options.runes,
+                "immutable" => &mut options.immutable,
+                "accessors" => &mut options.accessors,
+                "preserveWhitespace" => &mut options.preserve_whitespace,
+                "namespace" => {
+                    options.namespace = match static_attribute_value(attribute).as_deref() {
+                        Some("html" | "http://www.w3.org/1999/xhtml") => Some(Namespace::Html),
+                        Some("svg" | "http://www.w3.org/2000/svg") => Some(Namespace::Svg),
+                        Some("mathml" | "http://www.w3.org/1998/Math/MathML") => {
+                            Some(Namespace::MathMl)
+                        }
+                        Some("foreign") => Some(Namespace::Foreign),
+                        _ => {
+                            self.error(diagnostics::svelte_options_invalid_attribute_value(
+                                attribute.span,
+                                r#""html", "mathml", "svg" or "foreign""#,
+                            ));
+                            None
+                        }
+                    };
+                    continue;
+                }
+                _ => continue,
+            };
+            *option = boolean_attribute_value(attribute);
+            if option.is_none() {
+                self.error(diagnostics::svelte_options_invalid_attribute_value(
+                    attribute.span,
+                    "`true` or `false`",
+                ));
+            }
+        }
+        Some(options)
+    }
+
     pub(crate) fn parse_script(&mut self) -> Result<Script<'a>> {
         let span = self.start_span();
         self.expect(Kind::LAngle)?;
@@ -102,35 +154,20 @@ impl<'a> ParserImpl<'a> {
         let attributes = self.parse_static_attributes()?;
         self.expect(Kind::RAngle)?;
         let source_start = self.prev_token_end;
-        self.parse_text();
-        let source_end = loop {
-            if self.at(Kind::Eof) {
-                let end = self.cur_token().start;
-                return Err(diagnostics::unexpected_end(Span::new(end, end)));
-            } else if self.eat(Kind::LCurly) {
-                self.parse_text();
-            // we are at `<` if the next token isn't `/` continue
-            } else if !self.peek_at(Kind::Slash) {
-                self.bump_any();
-                self.bump_any();
-                self.parse_text();
-            // we are at `</` if the next token isn't `script` continue
-            } else if !self.nth_at(2, Kind::Script) {
-                self.bump_any();
-                self.bump_any();
-                self.bump_any();
-                self.parse_text();
-            // at `</script`
-            } else {
-                break self.cur_token().start;
-            }
-        };
+        let source_end = self.parse_raw_text_end(Kind::Script)?;
+        let raw = self.unsupported_content(
+            &attributes,
+            "script",
+            &["js", "javascript", "ts", "typescript", "module"],
+            Span::new(source_start, source_end),
+        );
+        // Unsupported content is skipped by parsing from its end
         let ret = oxc_parser::Parser::new(
             self.allocator,
             &self.source_text[..(source_end as usize)],
             SourceType::default().with_typescript(self.ts),
         )
-        .parse_from_position(source_start);
+        .parse_from_position(if raw.is_some() { source_end } else { source_start });
         for error in ret.errors {
             self.error(error);
         }
@@ -139,7 +176,19 @@ impl<'a> ParserImpl<'a> {
         self.expect(Kind::Script)?;
         self.expect(Kind::RAngle)?;
 
-        Ok(self.ast.script(self.end_span(span), ScriptContext::Default, ret.program, attributes))
+        // `<script context="module">`, or `<script module>` in Svelte 5
+        let is_module = attributes.iter().any(|attribute| match attribute.name.as_str() {
+            "context" => static_attribute_value(attribute).is_some_and(|value| value == "module"),
+            "module" => attribute.value.is_none(),
+            _ => false,
+        });
+        Ok(self.ast.script(
+            self.end_span(span),
+            if is_module { ScriptContext::Module } else { ScriptContext::Default },
+            ret.program,
+            attributes,
+            raw,
+        ))
     }
 
     fn parse_style(&mut self) -> Result<Style<'a>> {
@@ -149,41 +198,81 @@ impl<'a> ParserImpl<'a> {
         let attributes = self.parse_static_attributes()?;
         self.expect(Kind::RAngle)?;
         let source_start = self.prev_token_end;
-        self.parse_text();
-        let source_end = loop {
+        let source_end = self.parse_raw_text_end(Kind::Style)?;
+        let raw = self.unsupported_content(
+            &attributes,
+            "style",
+            &["css"],
+            Span::new(source_start, source_end),
+        );
+        let ret =
+            ssc_css_parser::Parser::new(self.allocator, &self.source_text[..(source_end as usize)])
+                .parse_from_position(if raw.is_some() { source_end } else { source_start });
+        for error in ret.errors {
+            self.error(error);
+        }
+        self.expect(Kind::LAngle)?;
+        self.expect(Kind::Slash)?;
+        self.expect(Kind::Style)?;
+        self.expect(Kind::RAngle)?;
+
+        Ok(self.ast.style(self.end_span(span), ret.stylesheet, attributes, raw))
+    }
+
+    /// Skip raw text up to the closing tag of `element`, and return its start.
+    fn parse_raw_text_end(&mut self, element: Kind) -> Result<u32> {
+        self.skip_text();
+        loop {
             if self.at(Kind::Eof) {
                 let end = self.cur_token().start;
                 return Err(diagnostics::unexpected_end(Span::new(end, end)));
             } else if self.eat(Kind::LCurly) {
-                self.parse_text();
+                self.skip_text();
             // we are at `<` if the next token isn't `/` continue
             } else if !self.peek_at(Kind::Slash) {
                 self.bump_any();
                 self.bump_any();
-                self.parse_text();
-            // we are at `</` if the next token isn't `style` continue
-            } else if !self.nth_at(2, Kind::Style) {
+                self.skip_text();
+            // we are at `</` if the next token isn't the end tag continue
+            } else if !self.nth_at(2, element) {
                 self.bump_any();
                 self.bump_any();
                 self.bump_any();
-                self.parse_text();
-            // at `</style`
+                self.skip_text();
+            // at the end tag
             } else {
-                break self.cur_token().start;
+                return Ok(self.cur_token().start);
             }
-        };
-        let ret =
-            ssc_css_parser::Parser::new(self.allocator, &self.source_text[..(source_end as usize)])
-                .parse_from_position(source_start);
-        for error in ret.errors {
-            self.error(error);
         }
-        self.expect(Kind::LAngle)?;
-        self.expect(Kind::Slash)?;
-        self.expect(Kind::Style)?;
-        self.expect(Kind::RAngle)?;
+    }
 
-        Ok(self.ast.style(self.end_span(span), ret.stylesheet, attributes))
+    /// The content of a `<script>` or `<style>` whose `lang`, or `type`, isn't
+    /// one of `supported`, with a warning, as parsing it would only report
+    /// bogus errors.
+    fn unsupported_content(
+        &mut self,
+        attributes: &[Attribute<'a>],
+        element: &str,
+        supported: &[&str],
+        content: Span,
+    ) -> Option<Atom<'a>> {
+        let (attribute, lang) = attributes.iter().find_map(|attribute| {
+            let lang = match attribute.name.as_str() {
+                "lang" => static_attribute_value(attribute)?,
+                // e.g. `text/scss`, `text/javascript` or `module`
+                "type" => match static_attribute_value(attribute)? {
+                    Cow::Borrowed(value) => Cow::Borrowed(value.rsplit('/').next()?),
+                    Cow::Owned(value) => Cow::Owned(value.rsplit('/').next()?.to_string()),
+                },
+                _ => return None,
+            };
+            Some((attribute, lang))
+        })?;
+        if supported.iter().any(|supported| lang.eq_ignore_ascii_case(supported)) {
+            return None;
+        }
+        self.error(diagnostics::unsupported_lang(attribute.span, element, &lang));
+        Some(Atom::from(content.source_text(self.source_text)))
     }
 
     pub(crate) fn parse_element(&mut self) -> Result<Element<'a>> {
@@ -191,10 +280,34 @@ impl<'a> ParserImpl<'a> {
         self.expect(Kind::LAngle)?;
         let name = self.parse_identifier()?;
         let attributes = self.parse_attributes()?;
+        let slash = self.start_span();
+        let self_closing = self.eat(Kind::Slash);
         self.expect(Kind::RAngle)?;
+
+        let is_void = html::is_void(&name);
+        if self_closing || is_void {
+            let span = self.end_span(span);
+            if is_void && !self_closing {
+                self.check_void_element_closing_tag(&name)?;
+            } else if !is_void && !self.in_foreign && is_regular_element_name(&name) {
+                // `/>` is replaced by `></name>`
+                let slash = Span::new(slash.start, span.end);
+                self.error(diagnostics::element_invalid_self_closing_tag(span, slash, &name));
+            }
+            let fragment = self.ast.fragment(self.ast.new_vec(), false);
+            return create_element(&self.ast, span, name, attributes, fragment);
+        }
+
+        if html::is_raw_text(&name) {
+            return self.parse_raw_text_element(span, name, attributes);
+        }
+
+        let in_foreign = self.in_foreign;
+        self.in_foreign |= html::is_foreign_root(&name);
         // this will guarantee that we are at either EOF or a closing tag
-        let children = self.parse_fragment_nodes()?;
-        let fragment = self.ast.fragment(children, false);
+        let children = self.parse_fragment_nodes();
+        self.in_foreign = in_foreign;
+        let fragment = self.ast.fragment(children?, false);
         if self.at(Kind::Eof) {
             let end = self.cur_token().start;
             return Err(diagnostics::unexpected_end(Span::new(end, end)));
@@ -212,6 +325,76 @@ impl<'a> ParserImpl<'a> {
         }
     }
 
+    /// The rest of a `<script>` or `<style>` of the template, e.g. a JSON-LD
+    /// block, whose content is kept as a single text node, unlike the ones of
+    /// the component.
+    fn parse_raw_text_element(
+        &mut self,
+        span: Span,
+        name: Atom<'a>,
+        attributes: Vec<'a, ElementAttribute<'a>>,
+    ) -> Result<Element<'a>> {
+        let element = if name.as_str() == "script" { Kind::Script } else { Kind::Style };
+        let text_start = self.prev_token_end;
+        let text_end = self.parse_raw_text_end(element)?;
+        let mut nodes = self.ast.new_vec();
+        if text_start < text_end {
+            let text = &self.source_text[(text_start as usize)..(text_end as usize)];
+            nodes.push(FragmentNode::Text(
+                self.ast.text(Span::new(text_start, text_end), Atom::from(text)),
+            ));
+        }
+        self.expect(Kind::LAngle)?;
+        self.expect(Kind::Slash)?;
+        self.expect(element)?;
+        self.expect(Kind::RAngle)?;
+        let fragment = self.ast.fragment(nodes, false);
+        create_element(&self.ast, self.end_span(span), name, attributes, fragment)
+    }
+
+    /// Validate a `style:` directive.
+    fn finish_style_directive(
+        &mut self,
+        span: Span,
+        name: &str,
+        name_span: Span,
+        value: Option<AttributeValue<'a>>,
+        mut modifiers: Vec<'a, StyleDirectiveModifier>,
+    ) -> Result<DirectiveAttribute<'a>> {
+        if !is_css_property_name(name) {
+            return Err(diagnostics::style_directive_invalid_name(name_span, name));
+        }
+        // `style:color` is short for `style:color={color}`
+        if value.is_none() && !is_identifier_name(name) {
+            return Err(diagnostics::style_directive_invalid_shorthand(name_span, name));
+        }
+        if modifiers.len() > 1 {
+            // `important` is the only modifier, so any other one is a repeat
+            self.error(diagnostics::duplicate_modifier(span, "important"));
+            modifiers.truncate(1);
+        }
+        Ok(self.ast.style_directive(span, self.ast.new_atom(name), value, modifiers))
+    }
+
+    /// Void elements are complete after their start tag, `</input>` is an
+    /// error.
+    fn check_void_element_closing_tag(&mut self, name: &str) -> Result<()> {
+        if !(self.at(Kind::LAngle) && self.peek_at(Kind::Slash)) {
+            return Ok(());
+        }
+        let checkpoint = self.checkpoint();
+        let span = self.start_span();
+        self.bump_any();
+        self.bump_any();
+        let end_name = self.parse_identifier()?;
+        if end_name.as_str() == name {
+            self.eat(Kind::RAngle);
+            return Err(diagnostics::void_element_invalid_content(self.end_span(span), name));
+        }
+        self.rewind(checkpoint);
+        Ok(())
+    }
+
     fn parse_static_attributes(&mut self) -> Result<Vec<'a, Attribute<'a>>> {
         let mut attributes = self.ast.new_vec();
 
@@ -230,6 +413,7 @@ impl<'a> ParserImpl<'a> {
     fn parse_static_attribute(&mut self) -> Result<Attribute<'a>> {
         let span = self.start_span();
         let name = self.parse_identifier()?;
+        let equals = self.at(Kind::Eq).then(|| self.cur_token().span());
         let value = if self.eat(Kind::Eq) {
             self.expect_without_advance(Kind::Str)?;
             let span = self.cur_token().span();
@@ -245,7 +429,11 @@ impl<'a> ParserImpl<'a> {
             None
         };
 
-        Ok(self.ast.attribute(self.end_span(span), name, value))
+        let parts =
+            attribute_parts(span.start, &name, equals, value.as_ref().map(|value| value.span));
+        let mut attribute = self.ast.attribute(self.end_span(span), name, value);
+        attribute.parts = parts;
+        Ok(attribute)
     }
 
     fn parse_attributes(&mut self) -> Result<Vec<'a, ElementAttribute<'a>>> {
@@ -276,7 +464,8 @@ impl<'a> ParserImpl<'a> {
                 let ident = self.parse_js_identifier()?;
                 self.expect(Kind::RCurly)?;
                 let span = self.end_span(span);
-                Ok(ElementAttribute::Attribute(self.ast.attribute(
+                let name_span = ident.span;
+                let mut attribute = self.ast.attribute(
                     span,
                     ident.name.clone(),
                     Some(self.ast.attribute_value(
@@ -286,162 +475,154 @@ impl<'a> ParserImpl<'a> {
                             Expression::Identifier(self.ast.alloc(ident)),
                         )),
                     )),
-                )))
+                );
+                attribute.parts =
+                    AttributeParts { name: name_span, value: Some(span), ..Default::default() };
+                Ok(ElementAttribute::Attribute(attribute))
             }
         } else {
             let name = self.parse_identifier()?;
+            let equals = self.at(Kind::Eq).then(|| self.cur_token().span());
             let value = if self.eat(Kind::Eq) { Some(self.parse_attribute_value()?) } else { None };
-            let value_span = value.as_ref().map_or(self.end_span(span), |value| value.span);
-
-            if let Some(colon_index) = name.as_str().chars().position(|ch| ch == ':') {
-                let directive_type = &name[..colon_index];
-                let rest = &name[(colon_index + 1).min(name.len() - 1)..];
-                let mut modifiers = rest.split('|');
-                let Some(directive_name) = modifiers.next() else {
-                    return Err(diagnostics::missing_directive_name(self.end_span(span)));
-                };
-                let modifiers: std::vec::Vec<_> = modifiers.collect();
+            let parts =
+                attribute_parts(span.start, &name, equals, value.as_ref().map(|value| value.span));
+            let mut attribute = self.finish_attribute(span, name, value)?;
+            match &mut attribute {
+                ElementAttribute::Attribute(attribute) => attribute.parts = parts,
+                ElementAttribute::DirectiveAttribute(directive) => *directive.parts_mut() = parts,
+                ElementAttribute::SpreadAttribute(_) => {}
+            }
+            Ok(attribute)
+        }
+    }
 
-                if directive_type == "style" {
-                    let modifiers = parse_modifiers! {
-                        modifiers (span.start + 2 + (directive_type.len() as u32) + (directive_name.len() as u32)) in (self.allocator) {
-                            "important" => StyleDirectiveModifier::Important
-                        }
-                    };
-                    return Ok(ElementAttribute::DirectiveAttribute(self.ast.style_directive(
+    /// Build the attribute or directive named `name`, started at `span`.
+    fn finish_attribute(
+        &mut self,
+        span: Span,
+        name: Atom<'a>,
+        value: Option<AttributeValue<'a>>,
+    ) -> Result<ElementAttribute<'a>> {
+        let value_span = value.as_ref().map_or(self.end_span(span), |value| value.span);
+
+        if let Some(colon_index) = name.as_str().chars().position(|ch| ch == ':') {
+            let directive_type = &name[..colon_index];
+            let rest = &name[(colon_index + 1).min(name.len() - 1)..];
+            let mut modifiers = rest.split('|');
+            let Some(directive_name) = modifiers.next() else {
+                return Err(diagnostics::missing_directive_name(self.end_span(span)));
+            };
+            let modifiers: std::vec::Vec<_> = modifiers.collect();
+
+            if directive_type == "style" {
+                let modifiers = parse_modifiers! {
+                    modifiers (span.start + 2 + (directive_type.len() as u32) + (directive_name.len() as u32)) in (self.allocator) {
+                        "important" => StyleDirectiveModifier::Important
+                    }
+                };
+                let name_start = span.start + 1 + directive_type.len() as u32;
+                let name_span = Span::new(name_start, name_start + directive_name.len() as u32);
+                return self
+                    .finish_style_directive(
                         self.end_span(span),
-                        self.ast.new_atom(directive_name),
+                        directive_name,
+                        name_span,
                         value,
                         modifiers,
-                    )));
-                }
+                    )
+                    .map(ElementAttribute::DirectiveAttribute);
+            }
 
-                let expression = if let Some(mut value) = value {
-                    let first = value.sequence.remove(0);
-                    let expression = if let AttributeSequenceValue::ExpressionTag(tag) = first {
-                        if value.sequence.is_empty() {
-                            tag.expression
-                        } else {
-                            return Err(diagnostics::invalid_directive_value(value.span));
-                        }
+            let expression = if let Some(mut value) = value {
+                let first = value.sequence.remove(0);
+                let expression = if let AttributeSequenceValue::ExpressionTag(tag) = first {
+                    if value.sequence.is_empty() {
+                        tag.expression
                     } else {
                         return Err(diagnostics::invalid_directive_value(value.span));
-                    };
-                    Some(expression)
+                    }
                 } else {
-                    None
+                    return Err(diagnostics::invalid_directive_value(value.span));
                 };
+                Some(expression)
+            } else {
+                None
+            };
 
-                if directive_type == "animate" {
-                    Ok(ElementAttribute::DirectiveAttribute(self.ast.animate_directive(
-                        self.end_span(span),
-                        self.ast.new_atom(directive_name),
-                        expression,
-                    )))
-                } else if directive_type == "bind" {
-                    let expression = match expression {
-                        Some(Expression::Identifier(ident)) => {
-                            BindDirectiveExpression::Identifier(ident.unbox())
-                        }
-                        Some(Expression::ComputedMemberExpression(expr)) => {
-                            BindDirectiveExpression::MemberExpression(
-                                MemberExpression::ComputedMemberExpression(expr),
-                            )
-                        }
-                        Some(Expression::StaticMemberExpression(expr)) => {
-                            BindDirectiveExpression::MemberExpression(
-                                MemberExpression::StaticMemberExpression(expr),
-                            )
-                        }
-                        Some(Expression::PrivateFieldExpression(expr)) => {
-                            BindDirectiveExpression::MemberExpression(
-                                MemberExpression::PrivateFieldExpression(expr),
-                            )
-                        }
-                        _ => return Err(diagnostics::invalid_bind_directive_value(value_span)),
-                    };
-                    Ok(ElementAttribute::DirectiveAttribute(self.ast.bind_directive(
-                        self.end_span(span),
-                        self.ast.new_atom(directive_name),
-                        expression,
-                    )))
-                } else if directive_type == "class" {
-                    let Some(expression) = expression else {
-                        return Err(diagnostics::missing_class_directive_value(value_span));
-                    };
-                    Ok(ElementAttribute::DirectiveAttribute(self.ast.class_directive(
-                        self.end_span(span),
-                        self.ast.new_atom(directive_name),
-                        expression,
-                    )))
-                } else if directive_type == "let" {
-                    let expression = expression.map(|expression| match expression {
-                        Expression::Identifier(ident) => {
-                            Ok(LetDirectiveExpression::Identifier(ident.unbox()))
-                        }
-                        Expression::ArrayExpression(expr) => {
-                            Ok(LetDirectiveExpression::ArrayExpression(expr.unbox()))
-                        }
-                        Expression::ObjectExpression(expr) => {
-                            Ok(LetDirectiveExpression::ObjectExpression(expr.unbox()))
-                        }
-                        _ => Err(diagnostics::invalid_let_directive_value(value_span)),
-                    });
-                    let expression =
-                        if let Some(expression) = expression { Some(expression?) } else { None };
-                    Ok(ElementAttribute::DirectiveAttribute(self.ast.let_directive(
-                        self.end_span(span),
-                        self.ast.new_atom(directive_name),
-                        expression,
-                    )))
-                } else if directive_type == "on" {
-                    let on_directive_modifiers = self.ast.new_vec_from_iter(
-                        modifiers.into_iter().map(|modifier| self.ast.new_atom(modifier)),
-                    );
-                    Ok(ElementAttribute::DirectiveAttribute(self.ast.on_directive(
-                        self.end_span(span),
-                        self.ast.new_atom(directive_name),
-                        expression,
-                        on_directive_modifiers,
-                    )))
-                } else if directive_type == "in"
-                    || directive_type == "out"
-                    || directive_type == "transition"
-                {
-                    let modifiers = parse_modifiers! {
-                        modifiers (span.start + 2 + (directive_type.len() as u32) + (directive_name.len() as u32)) in (self.allocator) {
-                            "local" => TransitionDirectiveModifier::Local,
-                            "global" => TransitionDirectiveModifier::Global,
-                        }
-                    };
+            if directive_type == "animate" {
+                Ok(ElementAttribute::DirectiveAttribute(self.ast.animate_directive(
+                    self.end_span(span),
+                    self.ast.new_atom(directive_name),
+                    expression,
+                )))
+            } else if directive_type == "bind" {
+                let expression = bind_directive_expression(expression, value_span)?;
+                Ok(ElementAttribute::DirectiveAttribute(self.ast.bind_directive(
+                    self.end_span(span),
+                    self.ast.new_atom(directive_name),
+                    expression,
+                )))
+            } else if directive_type == "class" {
+                let Some(expression) = expression else {
+                    return Err(diagnostics::missing_class_directive_value(value_span));
+                };
+                Ok(ElementAttribute::DirectiveAttribute(self.ast.class_directive(
+                    self.end_span(span),
+                    self.ast.new_atom(directive_name),
+                    expression,
+                )))
+            } else if directive_type == "let" {
+                let expression = expression
+                    .map(|expression| let_directive_expression(expression, value_span))
+                    .transpose()?;
+                Ok(ElementAttribute::DirectiveAttribute(self.ast.let_directive(
+                    self.end_span(span),
+                    self.ast.new_atom(directive_name),
+                    expression,
+                )))
+            } else if directive_type == "on" {
+                let on_directive_modifiers = self.ast.new_vec_from_iter(
+                    modifiers.into_iter().map(|modifier| self.ast.new_atom(modifier)),
+                );
+                Ok(ElementAttribute::DirectiveAttribute(self.ast.on_directive(
+                    self.end_span(span),
+                    self.ast.new_atom(directive_name),
+                    expression,
+                    on_directive_modifiers,
+                )))
+            } else if directive_type == "in"
+                || directive_type == "out"
+                || directive_type == "transition"
+            {
+                let modifiers = parse_modifiers! {
+                    modifiers (span.start + 2 + (directive_type.len() as u32) + (directive_name.len() as u32)) in (self.allocator) {
+                        "local" => TransitionDirectiveModifier::Local,
+                        "global" => TransitionDirectiveModifier::Global,
+                    }
+                };
 
-                    Ok(ElementAttribute::DirectiveAttribute(self.ast.transition_directive(
-                        self.end_span(span),
-                        self.ast.new_atom(directive_name),
-                        expression,
-                        modifiers,
-                        directive_type == "in" || directive_name == "transition",
-                        directive_type == "out" || directive_name == "transition",
-                    )))
-                } else if directive_type == "use" {
-                    Ok(ElementAttribute::DirectiveAttribute(self.ast.use_directive(
-                        self.end_span(span),
-                        self.ast.new_atom(directive_name),
-                        expression,
-                    )))
-                } else {
-                    return Err(diagnostics::unknown_directive_type(
-                        self.end_span(span),
-                        directive_type,
-                    ));
-                }
-            } else {
-                Ok(ElementAttribute::Attribute(self.ast.attribute(
+                Ok(ElementAttribute::DirectiveAttribute(self.ast.transition_directive(
                     self.end_span(span),
-                    name,
-                    value,
+                    self.ast.new_atom(directive_name),
+                    expression,
+                    modifiers,
+                    directive_type == "in" || directive_name == "transition",
+                    directive_type == "out" || directive_name == "transition",
                 )))
+            } else if directive_type == "use" {
+                Ok(ElementAttribute::DirectiveAttribute(self.ast.use_directive(
+                    self.end_span(span),
+                    self.ast.new_atom(directive_name),
+                    expression,
+                )))
+            } else {
+                return Err(diagnostics::unknown_directive_type(
+                    self.end_span(span),
+                    directive_type,
+                ));
             }
+        } else {
+            Ok(ElementAttribute::Attribute(self.ast.attribute(self.end_span(span), name, value)))
         }
     }
 
@@ -523,6 +704,69 @@ impl<'a> ParserImpl<'a> {
     }
 }
 
+/// The expression of a `bind:` directive with its value at `span`, an
+/// identifier or a member expression.
+pub(crate) fn bind_directive_expression(
+    expression: Option<Expression<'_>>,
+    span: Span,
+) -> Result<BindDirectiveExpression<'_>> {
+    Ok(match expression {
+        Some(Expression::Identifier(ident)) => BindDirectiveExpression::Identifier(ident.unbox()),
+        Some(Expression::ComputedMemberExpression(expr)) => {
+            BindDirectiveExpression::MemberExpression(MemberExpression::ComputedMemberExpression(
+                expr,
+            ))
+        }
+        Some(Expression::StaticMemberExpression(expr)) => {
+            BindDirectiveExpression::MemberExpression(MemberExpression::StaticMemberExpression(
+                expr,
+            ))
+        }
+        Some(Expression::PrivateFieldExpression(expr)) => {
+            BindDirectiveExpression::MemberExpression(MemberExpression::PrivateFieldExpression(
+                expr,
+            ))
+        }
+        _ => return Err(diagnostics::invalid_bind_directive_value(span)),
+    })
+}
+
+/// The expression of a `let:` directive with its value at `span`, an
+/// identifier or a destructuring pattern.
+pub(crate) fn let_directive_expression(
+    expression: Expression<'_>,
+    span: Span,
+) -> Result<LetDirectiveExpression<'_>> {
+    match expression {
+        Expression::Identifier(ident) => Ok(LetDirectiveExpression::Identifier(ident.unbox())),
+        Expression::ArrayExpression(expr) => {
+            Ok(LetDirectiveExpression::ArrayExpression(expr.unbox()))
+        }
+        Expression::ObjectExpression(expr) => {
+            Ok(LetDirectiveExpression::ObjectExpression(expr.unbox()))
+        }
+        _ => Err(diagnostics::invalid_let_directive_value(span)),
+    }
+}
+
+/// Whether `name` is a CSS property name, either a custom property (`--x`) or
+/// an optionally vendor prefixed ident.
+fn is_css_property_name(name: &str) -> bool {
+    if let Some(custom) = name.strip_prefix("--") {
+        return !custom.is_empty();
+    }
+    let mut chars = name.strip_prefix('-').unwrap_or(name).chars();
+    chars.next().is_some_and(|ch| ch.is_ascii_alphabetic() || ch == '_')
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_'))
+}
+
+/// Whether `name` is parsed into a [`RegularElement`], see [`create_element`].
+fn is_regular_element_name(name: &str) -> bool {
+    !matches!(name, "slot" | "title")
+        && !name.starts_with("svelte:")
+        && !name.chars().next().is_some_and(|ch| ch.is_ascii_uppercase())
+}
+
 fn create_element<'a>(
     ast: &AstBuilder<'a>,
     span: Span,
@@ -564,6 +808,7 @@ fn create_element<'a>(
             };
             ast.svelte_component(span, attributes, fragment, expression)
         }
+        "svelte:boundary" => ast.svelte_boundary(span, attributes, fragment),
         "svelte:document" => ast.svelte_document(span, attributes, fragment),
         "svelte:element" => {
             let this_attribute_index = attributes.iter().position(|attribute| {
@@ -610,3 +855,51 @@ fn create_element<'a>(
         }
     })
 }
+
+/// The value of `name`, `name={true}` or `name={false}`.
+fn boolean_attribute_value(attribute: &Attribute<'_>) -> Option<bool> {
+    let Some(value) = &attribute.value else { return Some(true) };
+    match value.sequence.as_slice() {
+        [AttributeSequenceValue::ExpressionTag(tag)] => match &tag.expression {
+            Expression::BooleanLiteral(literal) => Some(literal.value),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The value of `name="value"` or `name={"value"}`.
+fn static_attribute_value<'a>(attribute: &Attribute<'a>) -> Option<Cow<'a, str>> {
+    match attribute.value.as_ref()?.sequence.as_slice() {
+        [AttributeSequenceValue::Text(text)] => Some(html::decode_attribute(text.data.as_str())),
+        [AttributeSequenceValue::ExpressionTag(tag)] => match &tag.expression {
+            Expression::StringLiteral(literal) => Some(Cow::Borrowed(literal.value.as_str())),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The spans of the parts of the attribute or directive named `name`, which
+/// starts at `start`.
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn attribute_parts(
+    start: u32,
+    name: &str,
+    equals: Option<Span>,
+    value: Option<Span>,
+) -> AttributeParts {
+    let end = start + name.len() as u32;
+    let Some(colon) = name.find(':') else {
+        return AttributeParts { name: Span::new(start, end), equals, value, ..Default::default() };
+    };
+    let name_start = start + colon as u32 + 1;
+    let name_end = name[colon + 1..].find('|').map_or(end, |bar| name_start + bar as u32);
+    AttributeParts {
+        prefix: Some(Span::new(start, start + colon as u32)),
+        name: Span::new(name_start, name_end),
+        modifiers: (name_end < end).then(|| Span::new(name_end, end)),
+        equals,
+        value,
+    }
+}
@@ -9,8 +9,7 @@ impl<'a> ParserImpl<'a> {
 
         while !self.at(Kind::Eof) {
             if self.prev_token_end != self.cur_token().start {
-                let text = self.parse_text();
-                nodes.push(FragmentNode::Text(text));
+                self.parse_text(&mut nodes);
             } else if self.at(Kind::LAngle) {
                 if self.peek_at(Kind::Slash) {
                     break;
@@ -30,8 +29,7 @@ impl<'a> ParserImpl<'a> {
                     nodes.push(FragmentNode::Tag(tag));
                 }
             } else {
-                let text = self.parse_text();
-                nodes.push(FragmentNode::Text(text));
+                self.parse_text(&mut nodes);
             }
         }
 
@@ -0,0 +1,108 @@
+//! Parse results sent across threads
+
+use std::{cell::Cell, marker::PhantomData, sync::Arc};
+
+use oxc_allocator::Allocator;
+use oxc_diagnostics::OxcDiagnostic;
+use ssc_ast::{ast::Root, Trivias};
+
+use crate::{Parser, ParserReturn};
+
+/// A parsed component owning its arena and source text, which can be sent to
+/// another thread, e.g. parsed on one worker and analyzed on another.
+///
+/// It has a single owner: the AST is only handed out by shared reference,
+/// but the scripts' nodes from `oxc_ast` keep their scope, symbol and
+/// reference ids in `Cell`s, as the stylesheet's rules and selectors do with
+/// their flags, so it isn't `Sync`. A thread analyzing it must own it, and
+/// send it on when it's done.
+pub struct FrozenParse {
+    /// Borrows `allocator` and `source_text` for as long as `self` lives, so
+    /// is declared, and dropped, first. The `'static` lifetime never leaves
+    /// `self`, accessors shorten it to the borrow of `self`.
+    ret: ParserReturn<'static>,
+    source_text: Arc<str>,
+    /// Boxed so the AST's references to it stay valid when `self` moves.
+    _allocator: Box<Allocator>,
+    /// Keeps `self` from being `Sync` even if the AST stops having `Cell`s,
+    /// as `Send` is implemented by hand.
+    _single_owner: PhantomData<Cell<()>>,
+}
+
+#[allow(unsafe_code)]
+// SAFETY:
+// The arena moves along with every node allocated in it, and nothing outside of
+// it points into the arena.
+unsafe impl Send for FrozenParse {}
+
+impl FrozenParse {
+    /// Parse `source_text`.
+    pub fn new<S: Into<Arc<str>>>(source_text: S) -> Self {
+        Self::new_with(source_text, |_, _| ()).0
+    }
+
+    /// Parse `source_text`, and run `analyze` on the result before freezing
+    /// it, e.g. to annotate it on the thread parsing it.
+    ///
+    /// The results of `analyze` can't borrow the AST, they're returned next to
+    /// it.
+    pub fn new_with<S, T, F>(source_text: S, analyze: F) -> (Self, T)
+    where
+        S: Into<Arc<str>>,
+        F: for<'a> FnOnce(&'a str, &ParserReturn<'a>) -> T,
+    {
+        let allocator = Box::<Allocator>::default();
+        let source_text: Arc<str> = source_text.into();
+        let ret = Parser::new(&allocator, &source_text).parse();
+        let analysis = analyze(&source_text, &ret);
+        #[allow(unsafe_code)]
+        // SAFETY:
+        // The heap allocations of `allocator` and `source_text` don't move when
+        // they're moved into `Self`, and outlive `ret`, which is dropped first.
+        let ret = unsafe { std::mem::transmute::<ParserReturn<'_>, ParserReturn<'static>>(ret) };
+        (Self { ret, source_text, _allocator: allocator, _single_owner: PhantomData }, analysis)
+    }
+
+    pub fn source_text(&self) -> &str {
+        &self.source_text
+    }
+
+    pub fn root(&self) -> &Root<'_> {
+        &self.ret.root
+    }
+
+    pub fn errors(&self) -> &[OxcDiagnostic] {
+        &self.ret.errors
+    }
+
+    pub fn trivias(&self) -> &Trivias {
+        &self.ret.trivias
+    }
+
+    /// Whether the parser panicked, see [`ParserReturn::panicked`].
+    pub fn panicked(&self) -> bool {
+        self.ret.panicked
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn sent_across_threads() {
+        let (parse, nodes) =
+            FrozenParse::new_with("<p>{a}</p><p>{b}</p>", |_, ret| ret.root.fragment.nodes.len());
+        assert_eq!(nodes, 2);
+        let worker = thread::spawn(move || {
+            let nodes = parse.root().fragment.nodes.len();
+            (parse, nodes)
+        });
+        let (parse, nodes) = worker.join().unwrap();
+        assert_eq!(nodes, 2);
+        assert!(parse.errors().is_empty());
+        assert_eq!(parse.source_text(), "<p>{a}</p><p>{b}</p>");
+    }
+}
@@ -295,6 +295,11 @@ ascii_byte_handler!(MIN(lexer) {
 // .
 ascii_byte_handler!(PRD(lexer) {
     lexer.consume_char();
+    if lexer.peek() == Some('.') && lexer.peek2() == Some('.') {
+        lexer.consume_char();
+        lexer.consume_char();
+        return Kind::Dot3;
+    }
     Kind::Dot
 });
 
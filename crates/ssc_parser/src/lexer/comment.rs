@@ -97,13 +97,11 @@ impl<'a> Lexer<'a> {
             },
             handle_eof: {
                 self.error(diagnostics::unterminated_multi_line_comment(self.unterminated_range()));
-                self.last_comment_end = self.offset();
                 return Kind::Eof;
             },
         };
 
         self.trivia_builder.add_comment(self.token.start, self.offset());
-        self.last_comment_end = self.offset();
         Kind::Skip
     }
 
@@ -127,12 +125,10 @@ impl<'a> Lexer<'a> {
             // `SourcePosition`
             self.source.set_position(unsafe { pos.add(index + 3) });
             self.trivia_builder.add_comment(self.token.start, self.offset());
-            self.last_comment_end = self.offset();
             Kind::Skip
         } else {
             self.source.advance_to_end();
             self.error(diagnostics::unterminated_multi_line_comment(self.unterminated_range()));
-            self.last_comment_end = self.offset();
             Kind::Eof
         }
     }
@@ -67,9 +67,6 @@ pub struct Lexer<'a> {
     /// `memchr` Finder for end of multi-line comments. Created lazily when
     /// first used.
     comment_end_finder: Option<memchr::memmem::Finder<'static>>,
-
-    /// End position of the last comment
-    pub last_comment_end: u32,
 }
 
 #[allow(clippy::unused_self)]
@@ -98,7 +95,6 @@ impl<'a> Lexer<'a> {
             trivia_builder: TriviaBuilder::default(),
             escaped_strings: FxHashMap::default(),
             comment_end_finder: None,
-            last_comment_end: 0,
         }
     }
 
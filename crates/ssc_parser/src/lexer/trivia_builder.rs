@@ -28,6 +28,15 @@ impl TriviaBuilder {
         self.comments.push((start + 4, Comment::new(end - 3)));
     }
 
+    /// The spans of the comments in `span`, `<!--` and `-->` included.
+    pub fn comments_in(&self, span: Span) -> impl Iterator<Item = Span> + '_ {
+        let first = self.comments.partition_point(|(start, _)| *start < span.start + 4);
+        self.comments[first..]
+            .iter()
+            .map(|(start, comment)| Span::new(start - 4, comment.end + 3))
+            .take_while(move |comment| comment.end <= span.end)
+    }
+
     pub fn add_irregular_whitespace(&mut self, start: u32, end: u32) {
         self.irregular_whitespaces.push(Span::new(start, end));
     }
@@ -34,7 +34,11 @@ mod js;
 mod tag;
 mod text;
 
+#[cfg(feature = "deserialize")]
+mod deserialize;
 mod diagnostics;
+mod frozen;
+mod source_text;
 
 mod lexer;
 
@@ -43,8 +47,11 @@ use oxc_diagnostics::{OxcDiagnostic, Result};
 use oxc_span::Span;
 use ssc_ast::{ast::Root, AstBuilder, Trivias};
 
+pub use crate::frozen::FrozenParse;
 pub use crate::lexer::Kind; // re-export for codegen
 use crate::lexer::{Lexer, Token};
+pub use crate::source_text::SourceText;
+pub use ssc_line_index::{Encoding, LineCol, LineIndex};
 
 /// Maximum length of source which can be parsed (in bytes).
 /// ~4 GiB on 64-bit systems, ~2 GiB on 32-bit systems.
@@ -72,6 +79,8 @@ pub struct ParserReturn<'a> {
     pub errors: Vec<OxcDiagnostic>,
     pub trivias: Trivias,
     pub panicked: bool,
+    /// The parsed source, to locate spans of the AST and errors in.
+    pub source_text: SourceText<'a>,
 }
 
 /// Recursive Descent Parser
@@ -80,12 +89,22 @@ pub struct ParserReturn<'a> {
 pub struct Parser<'a> {
     allocator: &'a Allocator,
     source_text: &'a str,
+    template: bool,
 }
 
 impl<'a> Parser<'a> {
     /// Create a new parser
     pub fn new(allocator: &'a Allocator, source_text: &'a str) -> Self {
-        Self { allocator, source_text }
+        Self { allocator, source_text, template: false }
+    }
+
+    /// Parse the source as markup alone rather than as a component, e.g. the
+    /// constant markup of an `{@html}` tag: top-level `<script>` and `<style>`
+    /// are elements of the fragment, and `<svelte:options>` sets no options.
+    #[must_use]
+    pub fn with_template(mut self, template: bool) -> Self {
+        self.template = template;
+        self
     }
 }
 
@@ -100,9 +119,10 @@ mod parser_parse {
     ///
     /// `ParserImpl::new`, `Lexer::new` and `lexer::Source::new` all require a
     /// `UniquePromise` to be provided to them. `UniquePromise::new` is not
-    /// visible outside this module, so only `Parser::parse` can create one,
-    /// and it only calls `ParserImpl::new` once. This enforces the
-    /// invariant throughout the entire parser.
+    /// visible outside this module, so only `Parser::parse` and
+    /// `Parser::deserialize` can create one, and they only call
+    /// `ParserImpl::new` once. This enforces the invariant throughout the
+    /// entire parser.
     ///
     /// `UniquePromise` is a zero-sized type and has no runtime cost. It's
     /// purely for the type-checker.
@@ -137,9 +157,28 @@ mod parser_parse {
         /// Recoverable errors are stored inside `errors`.
         pub fn parse(self) -> ParserReturn<'a> {
             let unique = UniquePromise::new();
-            let parser = ParserImpl::new(self.allocator, self.source_text, unique);
+            let mut parser = ParserImpl::new(self.allocator, self.source_text, unique);
+            parser.template = self.template;
             parser.parse()
         }
+
+        /// Rebuild the AST of the source text from its JSON, as serialized by
+        /// `ssc_ast` with the `serialize` feature, e.g. the `root` returned
+        /// by the napi and wasm parsers, or as returned by the Svelte
+        /// compiler's `parse` in modern mode.
+        ///
+        /// Every node is built from the fields of the JSON, so it may have
+        /// been edited. The source text is only read for the parts of
+        /// attributes and the source of the stylesheet, which aren't
+        /// serialized, so the spans of `json` must still be in it.
+        ///
+        /// Returns an empty `Root` if `json` isn't a valid AST.
+        #[cfg(feature = "deserialize")]
+        pub fn deserialize(self, json: &serde_json::Value) -> ParserReturn<'a> {
+            let unique = UniquePromise::new();
+            let parser = ParserImpl::new(self.allocator, self.source_text, unique);
+            parser.deserialize(json)
+        }
     }
 }
 use parser_parse::UniquePromise;
@@ -169,6 +208,12 @@ struct ParserImpl<'a> {
 
     /// Is typescript enabled?
     ts: bool,
+
+    /// Are we inside of an `<svg>` or `<math>` element?
+    in_foreign: bool,
+
+    /// Is the source markup alone? See [`Parser::with_template`].
+    template: bool,
 }
 
 impl<'a> ParserImpl<'a> {
@@ -186,8 +231,9 @@ impl<'a> ParserImpl<'a> {
             token: Token::default(),
             prev_token_end: 0,
             ast: AstBuilder::new(allocator),
-            // make it working
-            ts: true,
+            ts: is_typescript(source_text),
+            in_foreign: false,
+            template: false,
         }
     }
 
@@ -211,20 +257,32 @@ impl<'a> ParserImpl<'a> {
             Ok(root) => (root, false),
             Err(error) => {
                 self.error(self.overlong_error().unwrap_or(error));
-                let root = self.ast.root(
-                    Span::default(),
-                    self.ast.fragment(self.ast.new_vec(), false),
-                    None,
-                    None,
-                    None,
-                    self.ts,
-                );
-                (root, true)
+                (self.empty_root(), true)
             }
         };
-        let errors = self.lexer.errors.into_iter().chain(self.errors).collect();
+        let ignores = root.svelte_ignores();
+        let errors = self
+            .lexer
+            .errors
+            .into_iter()
+            .chain(self.errors)
+            .filter(|error| !ignores.is_diagnostic_ignored(error))
+            .collect();
         let trivias = self.lexer.trivia_builder.build();
-        ParserReturn { root, errors, trivias, panicked }
+        let source_text = SourceText::new(self.source_text);
+        ParserReturn { root, errors, trivias, panicked, source_text }
+    }
+
+    /// The root returned on unrecoverable error.
+    fn empty_root(&self) -> Root<'a> {
+        self.ast.root(
+            Span::default(),
+            self.ast.fragment(self.ast.new_vec(), false),
+            None,
+            None,
+            None,
+            self.ts,
+        )
     }
 
     #[allow(clippy::cast_possible_truncation)]
@@ -235,7 +293,10 @@ impl<'a> ParserImpl<'a> {
 
         let (fragment, css, instance, module) = self.parse_root_elements()?;
 
-        Ok(self.ast.root(self.end_span(span), fragment, css, instance, module, self.ts))
+        let options = if self.template { None } else { self.parse_svelte_options(&fragment) };
+        let mut root = self.ast.root(self.end_span(span), fragment, css, instance, module, self.ts);
+        root.options = options;
+        Ok(root)
     }
 
     /// Check if source length exceeds MAX_LEN, if the file cannot be parsed.
@@ -269,8 +330,39 @@ impl<'a> ParserImpl<'a> {
     }
 }
 
+/// Whether a `<script>` of the component is `lang="ts"`, which enables
+/// TypeScript syntax in the whole component, template expressions included.
+///
+/// Decided before parsing, as the script can follow the template.
+fn is_typescript(source_text: &str) -> bool {
+    let mut rest = source_text;
+    while let Some(index) = rest.find('<') {
+        rest = &rest[index..];
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.split_once("-->").map_or("", |(_, after)| after);
+            continue;
+        }
+        rest = &rest[1..];
+        let Some(tag) = rest.strip_prefix("script") else { continue };
+        let tag = tag.split_once('>').map_or(tag, |(tag, _)| tag);
+        let lang = tag.split_ascii_whitespace().find_map(|attribute| {
+            let value = attribute.strip_prefix("lang=")?;
+            Some(value.trim_matches(|ch| ch == '"' || ch == '\''))
+        });
+        if matches!(lang, Some("ts" | "typescript")) {
+            return true;
+        }
+    }
+    false
+}
+
 #[cfg(test)]
 mod test {
+    use oxc_diagnostics::Severity;
+    use ssc_ast::ast::{
+        DirectiveAttribute, Element, ElementAttribute, FragmentNode, Namespace, ScriptContext,
+    };
+
     use super::*;
 
     #[test]
@@ -293,6 +385,235 @@ mod test {
         }
     }
 
+    #[test]
+    fn comment_nodes() {
+        let allocator = Allocator::default();
+        let source = "<p>a <!-- svelte-ignore a11y_missing_content --><!--b-->\n</p>";
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty());
+        let [FragmentNode::Element(Element::RegularElement(p))] =
+            ret.root.fragment.nodes.as_slice()
+        else {
+            panic!("{:?}", ret.root.fragment.nodes);
+        };
+        let [FragmentNode::Text(a), FragmentNode::Comment(ignore), FragmentNode::Comment(b), FragmentNode::Text(newline)] =
+            p.fragment.nodes.as_slice()
+        else {
+            panic!("{:?}", p.fragment.nodes);
+        };
+        assert_eq!(a.data, "a ");
+        assert_eq!(ignore.data, " svelte-ignore a11y_missing_content ");
+        assert_eq!(ignore.span.source_text(source), "<!-- svelte-ignore a11y_missing_content -->");
+        assert_eq!(b.data, "b");
+        assert_eq!(newline.data, "\n");
+    }
+
+    #[test]
+    fn void_and_self_closing_elements() {
+        let allocator = Allocator::default();
+        let source = "<p><input value=\"a\"><br/>text</p><Card /><svg><path /></svg>";
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty());
+        let [FragmentNode::Element(Element::RegularElement(p)), FragmentNode::Element(Element::Component(_)), FragmentNode::Element(Element::RegularElement(_))] =
+            ret.root.fragment.nodes.as_slice()
+        else {
+            panic!("{:?}", ret.root.fragment.nodes);
+        };
+        assert_eq!(p.fragment.nodes.len(), 3);
+
+        let ret = Parser::new(&allocator, "<div />").parse();
+        assert_eq!(ret.errors.len(), 1);
+        assert_eq!(ret.errors[0].severity, Severity::Warning);
+        let labels = ret.errors[0].labels.as_ref().unwrap();
+        assert_eq!((labels[1].offset(), labels[1].len()), (5, 2));
+        assert_eq!(labels[1].label(), Some("Replace with `></div>`"));
+        let ret = Parser::new(&allocator, "<svelte:head /><svg><g /></svg><Card />").parse();
+        assert!(ret.errors.is_empty());
+        let source = "<!-- svelte-ignore element_invalid_self_closing_tag --><div />";
+        assert!(Parser::new(&allocator, source).parse().errors.is_empty());
+        assert!(Parser::new(&allocator, "<input></input>").parse().panicked);
+    }
+
+    #[test]
+    fn script_context() {
+        let allocator = Allocator::default();
+        for source in [
+            "<script context=\"module\">export const a = 1;</script><script>let b;</script>",
+            "<script>let b;</script><script module>export const a = 1;</script>",
+        ] {
+            let ret = Parser::new(&allocator, source).parse();
+            assert!(ret.errors.is_empty(), "{source}");
+            assert_eq!(ret.root.module.unwrap().context, ScriptContext::Module);
+            assert_eq!(ret.root.instance.unwrap().context, ScriptContext::Default);
+        }
+    }
+
+    #[test]
+    fn template() {
+        let allocator = Allocator::default();
+        let source = "<script>a()</script><style>p {}</style><svelte:options runes /><p></p>";
+        let ret = Parser::new(&allocator, source).with_template(true).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        assert!(ret.root.instance.is_none() && ret.root.css.is_none());
+        assert!(ret.root.options.is_none());
+        assert_eq!(ret.root.fragment.nodes.len(), 4);
+    }
+
+    #[test]
+    fn svelte_options() {
+        let allocator = Allocator::default();
+        let source = r#"<svelte:options runes accessors={false} namespace="svg" /><p></p>"#;
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let options = ret.root.options.unwrap();
+        assert_eq!(options.runes, Some(true));
+        assert_eq!(options.accessors, Some(false));
+        assert_eq!(options.immutable, None);
+        assert!(matches!(options.namespace, Some(Namespace::Svg)));
+        assert_eq!(ret.root.fragment.nodes.len(), 2);
+
+        assert!(Parser::new(&allocator, "<p></p>").parse().root.options.is_none());
+        let ret = Parser::new(&allocator, r#"<svelte:options runes="yes" />"#).parse();
+        assert_eq!(ret.errors.len(), 1);
+        assert_eq!(ret.root.options.unwrap().runes, None);
+    }
+
+    #[test]
+    fn style_directives() {
+        let allocator = Allocator::default();
+        let source = r#"<div style:color style:--gap="1px" style:font-size="{size}px" style:-webkit-mask={"none"} style:width|important|important={w}></div>"#;
+        let ret = Parser::new(&allocator, source).parse();
+        assert_eq!(ret.errors.len(), 1);
+        assert_eq!(ret.errors[0].severity, Severity::Warning);
+        let [FragmentNode::Element(Element::RegularElement(div))] =
+            ret.root.fragment.nodes.as_slice()
+        else {
+            panic!("{:?}", ret.root.fragment.nodes);
+        };
+        let styles = div
+            .attributes
+            .iter()
+            .map(|attribute| match attribute {
+                ElementAttribute::DirectiveAttribute(DirectiveAttribute::StyleDirective(style)) => {
+                    (style.name.as_str(), style.modifiers.len())
+                }
+                _ => panic!("{attribute:?}"),
+            })
+            .collect::<std::vec::Vec<_>>();
+        assert_eq!(
+            styles,
+            [("color", 0), ("--gap", 0), ("font-size", 0), ("-webkit-mask", 0), ("width", 1)]
+        );
+
+        for source in
+            ["<div style:font-size></div>", "<div style:a.b={x}></div>", "<div style:--></div>"]
+        {
+            let ret = Parser::new(&allocator, source).parse();
+            assert!(ret.panicked, "{source}");
+        }
+    }
+
+    #[test]
+    fn snippet_parameters() {
+        use ssc_ast::ast::Block;
+
+        let allocator = Allocator::default();
+        let source = "{#snippet row(a, { b }, c)}{a}{b}{c}{/snippet}";
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let [FragmentNode::Block(Block::SnippetBlock(snippet))] =
+            ret.root.fragment.nodes.as_slice()
+        else {
+            panic!("{:?}", ret.root.fragment.nodes);
+        };
+        assert_eq!(snippet.parameters.len(), 3);
+    }
+
+    #[test]
+    fn attribute_parts() {
+        let allocator = Allocator::default();
+        let source =
+            r#"<button on:click|once|preventDefault={f} class = "a" {id} disabled></button>"#;
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let [FragmentNode::Element(Element::RegularElement(button))] =
+            ret.root.fragment.nodes.as_slice()
+        else {
+            panic!("{:?}", ret.root.fragment.nodes);
+        };
+        let text = |span: Option<Span>| span.map(|span| span.source_text(source));
+        let parts = button
+            .attributes
+            .iter()
+            .map(|attribute| match attribute {
+                ElementAttribute::Attribute(attribute) => attribute.parts,
+                ElementAttribute::DirectiveAttribute(directive) => *directive.parts(),
+                ElementAttribute::SpreadAttribute(_) => panic!("{attribute:?}"),
+            })
+            .map(|parts| {
+                (
+                    text(parts.prefix),
+                    parts.name.source_text(source),
+                    text(parts.modifiers),
+                    text(parts.equals),
+                    text(parts.value),
+                )
+            })
+            .collect::<std::vec::Vec<_>>();
+        assert_eq!(
+            parts,
+            [
+                (Some("on"), "click", Some("|once|preventDefault"), Some("="), Some("{f}")),
+                (None, "class", None, Some("="), Some(r#""a""#)),
+                (None, "id", None, None, Some("{id}")),
+                (None, "disabled", None, None, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn unsupported_lang() {
+        let allocator = Allocator::default();
+        let source = "<script lang=\"coffee\">x = -> 1</script><style lang=\"scss\">$c: red; p { color: $c }</style>";
+        let ret = Parser::new(&allocator, source).parse();
+        assert_eq!(ret.errors.len(), 2, "{:?}", ret.errors);
+        assert!(ret.errors.iter().all(|error| error.severity == Severity::Warning));
+        let script = ret.root.instance.unwrap();
+        assert_eq!(script.raw.as_deref(), Some("x = -> 1"));
+        assert!(script.program.body.is_empty());
+        let style = ret.root.css.unwrap();
+        assert_eq!(style.raw.as_deref(), Some("$c: red; p { color: $c }"));
+        assert!(style.stylesheet.children.is_empty());
+
+        let source =
+            "<script lang=\"ts\">let a: number;</script><style type=\"text/css\">p {}</style>";
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        assert!(ret.root.instance.unwrap().raw.is_none());
+        assert!(ret.root.css.unwrap().raw.is_none());
+    }
+
+    #[test]
+    fn expression_tags_and_spreads() {
+        use oxc_span::GetSpan;
+        use ssc_ast::ast::{Element, ElementAttribute, FragmentNode, Tag};
+
+        let allocator = Allocator::default();
+        let source = "<div {...props}>{a + b}</div>";
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let FragmentNode::Element(Element::RegularElement(div)) = &ret.root.fragment.nodes[0]
+        else {
+            panic!("{:?}", ret.root.fragment.nodes);
+        };
+        assert!(matches!(div.attributes[0], ElementAttribute::SpreadAttribute(_)));
+        let FragmentNode::Tag(Tag::ExpressionTag(tag)) = &div.fragment.nodes[0] else {
+            panic!("{:?}", div.fragment.nodes);
+        };
+        // The first token of the expression used to be skipped
+        assert_eq!(tag.expression.span().source_text(source), "a + b");
+    }
+
     // Source with length MAX_LEN + 1 fails to parse.
     // Skip this test on 32-bit systems as impossible to allocate a string
     // longer than `isize::MAX`.
@@ -0,0 +1,124 @@
+use std::cell::OnceCell;
+
+use oxc_span::{GetSpan, Span};
+use ssc_line_index::{Encoding, LineCol, LineIndex};
+
+/// The source a [`crate::ParserReturn`] was parsed from, with a line index.
+///
+/// ```ignore
+/// let ret = Parser::new(&allocator, source_text).parse();
+/// for error in &ret.errors { /* ret.source_text.line_col(..) */ }
+/// ```
+///
+/// The line index is only built on the first query of lines and columns.
+#[derive(Debug, Clone)]
+pub struct SourceText<'a> {
+    text: &'a str,
+    index: OnceCell<LineIndex>,
+}
+
+impl<'a> SourceText<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self { text, index: OnceCell::new() }
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.line_index().line_count()
+    }
+
+    /// The line index of the source, to share with other consumers of
+    /// positions, e.g. source maps.
+    pub fn line_index(&self) -> &LineIndex {
+        self.index.get_or_init(|| LineIndex::new(self.text))
+    }
+
+    /// The text covered by the span of `node`.
+    ///
+    /// See [`SourceText::slice`] for out of range spans.
+    pub fn text_of<T: GetSpan>(&self, node: &T) -> &'a str {
+        self.slice(node.span())
+    }
+
+    /// The text covered by `span`.
+    ///
+    /// The span is clamped to the source and shrunk to the nearest char
+    /// boundaries, so this never panics.
+    pub fn slice(&self, span: Span) -> &'a str {
+        let end = self.floor_char_boundary(span.end as usize);
+        let start = self.ceil_char_boundary(span.start as usize).min(end);
+        &self.text[start..end]
+    }
+
+    /// The text covered by `span`, if it lies on char boundaries inside the
+    /// source.
+    pub fn get(&self, span: Span) -> Option<&'a str> {
+        self.text.get(span.start as usize..span.end as usize)
+    }
+
+    /// Text of the zero based `line`, without its line terminator.
+    pub fn line(&self, line: u32) -> Option<&'a str> {
+        let range = self.line_index().line_range(line)?;
+        Some(&self.text[range.start as usize..range.end as usize])
+    }
+
+    /// Line and UTF-16 column of `offset`, clamped to the source.
+    pub fn line_col(&self, offset: u32) -> LineCol {
+        self.line_index().line_col(offset, Encoding::Utf16)
+    }
+
+    /// The offset of the line and UTF-16 column `line_col`, clamped to its
+    /// line.
+    pub fn offset(&self, line_col: LineCol) -> u32 {
+        self.line_index().clamped_offset(line_col, Encoding::Utf16)
+    }
+
+    fn floor_char_boundary(&self, mut index: usize) -> usize {
+        index = index.min(self.text.len());
+        while !self.text.is_char_boundary(index) {
+            index -= 1;
+        }
+        index
+    }
+
+    fn ceil_char_boundary(&self, mut index: usize) -> usize {
+        index = index.min(self.text.len());
+        while !self.text.is_char_boundary(index) {
+            index += 1;
+        }
+        index
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn source_text() {
+        let source = SourceText::new("<p>\r\n  é😀x\n</p>");
+        assert_eq!(source.line_count(), 3);
+        assert_eq!(source.line(0), Some("<p>"));
+        assert_eq!(source.line(1), Some("  é😀x"));
+        assert_eq!(source.line(3), None);
+
+        let x = u32::try_from(source.as_str().find('x').unwrap()).unwrap();
+        assert_eq!(source.line_col(x), LineCol { line: 1, column: 5 });
+        assert_eq!(source.offset(LineCol { line: 1, column: 5 }), x);
+        assert_eq!(source.line_col(100), LineCol { line: 2, column: 4 });
+
+        // Inside of `é`
+        assert_eq!(source.slice(Span::new(8, x + 1)), "😀x");
+        assert_eq!(source.get(Span::new(8, x + 1)), None);
+        assert_eq!(source.slice(Span::new(0, 3)), "<p>");
+        assert_eq!(source.slice(Span::new(50, 60)), "");
+
+        let allocator = oxc_allocator::Allocator::default();
+        let ret = crate::Parser::new(&allocator, "<p>\n{a}</p>").parse();
+        assert_eq!(ret.source_text.line(1), Some("{a}</p>"));
+        assert_eq!(ret.source_text.text_of(&ret.root.fragment.nodes[0]), "<p>\n{a}</p>");
+    }
+}
@@ -1,5 +1,6 @@
 use oxc_ast::ast::{ChainElement, Expression, VariableDeclaration, VariableDeclarationKind};
 use oxc_diagnostics::Result;
+use oxc_span::Span;
 use ssc_ast::ast::*;
 
 use crate::{diagnostics, Kind, ParserImpl};
@@ -41,20 +42,7 @@ impl<'a> ParserImpl<'a> {
                 let expression = self.parse_js_expression()?;
                 self.expect(Kind::RCurly)?;
                 let span = self.end_span(span);
-                let expression = match expression {
-                    Expression::ChainExpression(expr) => {
-                        if let ChainElement::CallExpression(expr) = expr.unbox().expression {
-                            RenderTagExpression::Chain(expr.unbox())
-                        } else {
-                            return Err(diagnostics::invalid_render_tag_expression(span));
-                        }
-                    }
-                    Expression::CallExpression(expr) => RenderTagExpression::Call(expr.unbox()),
-                    _ => {
-                        return Err(diagnostics::invalid_render_tag_expression(span));
-                    }
-                };
-                Tag::RenderTag(self.ast.render_tag(span, expression))
+                Tag::RenderTag(self.ast.render_tag(span, render_tag_expression(expression, span)?))
             } else {
                 return Err(self.unexpected());
             };
@@ -67,3 +55,22 @@ impl<'a> ParserImpl<'a> {
         }
     }
 }
+
+/// The expression of the `{@render ...}` tag at `span`, a call or an optional
+/// call.
+pub(crate) fn render_tag_expression(
+    expression: Expression<'_>,
+    span: Span,
+) -> Result<RenderTagExpression<'_>> {
+    match expression {
+        Expression::ChainExpression(expr) => {
+            if let ChainElement::CallExpression(expr) = expr.unbox().expression {
+                Ok(RenderTagExpression::Chain(expr.unbox()))
+            } else {
+                Err(diagnostics::invalid_render_tag_expression(span))
+            }
+        }
+        Expression::CallExpression(expr) => Ok(RenderTagExpression::Call(expr.unbox())),
+        _ => Err(diagnostics::invalid_render_tag_expression(span)),
+    }
+}
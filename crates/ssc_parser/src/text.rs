@@ -1,27 +1,44 @@
+use oxc_allocator::Vec;
 use oxc_span::{Atom, Span};
 use ssc_ast::ast::*;
 
 use crate::{Kind, ParserImpl};
 
 impl<'a> ParserImpl<'a> {
-    pub(crate) fn parse_text(&mut self) -> Text<'a> {
-        let start = if self.source_text[(self.prev_token_end as usize)..]
-            .trim_start()
-            .starts_with("<!--")
-        {
-            self.lexer.last_comment_end
-        } else {
-            self.prev_token_end
-        };
+    /// Parse the text up to the next tag, block or element into `nodes`, as
+    /// the text and comment nodes it consists of.
+    pub(crate) fn parse_text(&mut self, nodes: &mut Vec<'a, FragmentNode<'a>>) {
+        let span = self.skip_text();
+        let mut start = span.start;
+        for comment in self.lexer.trivia_builder.comments_in(span) {
+            if start < comment.start {
+                nodes.push(FragmentNode::Text(self.text(Span::new(start, comment.start))));
+            }
+            let data = &self.source_text[(comment.start + 4) as usize..(comment.end - 3) as usize];
+            nodes.push(FragmentNode::Comment(self.ast.comment(comment, Atom::from(data))));
+            start = comment.end;
+        }
+        if start < span.end {
+            nodes.push(FragmentNode::Text(self.text(Span::new(start, span.end))));
+        }
+    }
 
+    /// Skip the text up to the next tag, block or element, and return its
+    /// span.
+    pub(crate) fn skip_text(&mut self) -> Span {
+        let start = self.prev_token_end;
         loop {
             if self.at(Kind::LCurly) || self.at(Kind::LAngle) || self.at(Kind::Eof) {
                 let end = self.cur_token().start;
                 self.prev_token_end = end;
-                let text = &self.source_text[(start as usize)..(end as usize)];
-                return self.ast.text(Span::new(start, end), Atom::from(text));
+                return Span::new(start, end);
             }
             self.bump_any();
         }
     }
+
+    fn text(&self, span: Span) -> Text<'a> {
+        let text = &self.source_text[(span.start as usize)..(span.end as usize)];
+        self.ast.text(span, Atom::from(text))
+    }
 }
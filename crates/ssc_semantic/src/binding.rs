@@ -0,0 +1,69 @@
+use bitflags::bitflags;
+
+/// How a binding takes part in the component's reactivity.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BindingKind {
+    /// A plain JavaScript binding.
+    #[default]
+    Normal,
+    /// A prop declared with `$props()` or `export let`.
+    Prop,
+    /// A prop defaulting to `$bindable()`.
+    BindableProp,
+    /// The rest element of a `$props()` destructure.
+    RestProp,
+    /// `$state(...)`
+    State,
+    /// `$state.raw(...)`
+    RawState,
+    /// `$derived(...)` or `$derived.by(...)`
+    Derived,
+    /// The context or index of an `{#each}` block.
+    Each,
+    /// A `{#snippet}` or one of its parameters.
+    Snippet,
+    /// Implicitly declared by a `$:` statement.
+    LegacyReactive,
+    /// Declared in the template by `{@const}`, `let:`, or an `{#await}` block.
+    Template,
+    /// The value of a store, `$count` for a top-level `count` store.
+    StoreSub,
+}
+
+impl BindingKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Prop => "prop",
+            Self::BindableProp => "bindable_prop",
+            Self::RestProp => "rest_prop",
+            Self::State => "state",
+            Self::RawState => "raw_state",
+            Self::Derived => "derived",
+            Self::Each => "each",
+            Self::Snippet => "snippet",
+            Self::LegacyReactive => "legacy_reactive",
+            Self::Template => "template",
+            Self::StoreSub => "store_sub",
+        }
+    }
+
+    /// Whether reading the binding can change over the component's lifetime.
+    pub fn is_reactive(self) -> bool {
+        !matches!(self, Self::Normal | Self::Snippet)
+    }
+}
+
+bitflags! {
+    /// How a binding is used by the component, see [`crate::Semantic::binding_flags`].
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct BindingFlags: u8 {
+        /// The binding itself is assigned to or updated.
+        const Reassigned = 1 << 0;
+        /// The binding or one of its members is assigned to or updated.
+        const Mutated    = 1 << 1;
+        /// Reading the binding can give different values over the
+        /// component's lifetime.
+        const Reactive   = 1 << 2;
+    }
+}
@@ -0,0 +1,2193 @@
+//! Semantic Builder
+
+use std::{
+    collections::{hash_map::Entry, VecDeque},
+    mem,
+    ops::Range,
+};
+
+use oxc_ast::{
+    ast::{
+        Argument, ArrayExpression, ArrayExpressionElement, AssignmentExpression,
+        AssignmentTargetPropertyIdentifier, BindingIdentifier, BindingPattern, BindingPatternKind,
+        CallExpression, CatchParameter, Class, ClassType, Declaration, ExportNamedDeclaration,
+        Expression, ForStatementLeft, FormalParameters, Function, IdentifierReference,
+        ImportDeclaration, LabeledStatement, MemberExpression, ObjectExpression,
+        ObjectPropertyKind, Program, SimpleAssignmentTarget, Statement, UpdateExpression,
+        VariableDeclaration, VariableDeclarationKind, VariableDeclarator,
+    },
+    syntax_directed_operations::BoundNames,
+    visit::walk,
+    Visit,
+};
+use oxc_diagnostics::{Error, OxcDiagnostic};
+use oxc_index::Idx;
+use oxc_span::{Atom, GetSpan, Span};
+use oxc_syntax::{
+    operator::AssignmentOperator,
+    symbol::{SymbolFlags, SymbolId},
+};
+#[allow(clippy::wildcard_imports)]
+use ssc_ast::ast::*;
+
+use crate::{
+    binding::BindingKind,
+    diagnostics,
+    metadata::{self, NodeMetadata},
+    reference::{Reference, ReferenceFlag, ReferenceId},
+    rune::Rune,
+    scope::{ScopeFlags, ScopeId, ScopeTree},
+    snippet::Snippet,
+    symbol::{EachSource, SymbolTable},
+    template::{EachBlockFlags, TemplateNodes},
+    Semantic,
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+pub struct SemanticBuilder<'a> {
+    source_text: &'a str,
+
+    scopes: ScopeTree<'a>,
+    symbols: SymbolTable<'a>,
+    template_nodes: TemplateNodes,
+
+    current_scope_id: ScopeId,
+    /// The innermost block being visited.
+    current_block_id: Option<BlockId>,
+    instance_scope_id: Option<ScopeId>,
+
+    /// Flags for the symbols declared by the binding identifiers being visited.
+    current_symbol_flags: SymbolFlags,
+    current_binding_kind: BindingKind,
+    /// Flag for the identifier references being visited.
+    current_reference_flag: ReferenceFlag,
+
+    /// Whether the component uses runes, set by `<svelte:options runes>` or a
+    /// call to a rune.
+    runes: bool,
+    /// Runes mode requested by the compile options, see
+    /// [`SemanticBuilder::with_runes`].
+    runes_option: Option<bool>,
+    /// Runes mode set explicitly, by `<svelte:options runes>` or the compile
+    /// options, rather than inferred from the use of runes.
+    explicit_runes: Option<bool>,
+    /// Deprecated legacy constructs, `<slot>` or `on:` directives, warned
+    /// about if the component ends up in runes mode.
+    legacy_constructs: Vec<OxcDiagnostic>,
+    /// Legacy declarations, `$:`, `export let` and `$$props`, with the runes
+    /// replacing them, reported if the component ends up in runes mode.
+    legacy_declarations: Vec<(OxcDiagnostic, &'static [Rune])>,
+    /// Calls to runes, reported if the component is explicitly in legacy mode.
+    rune_calls: Vec<(Span, Rune)>,
+    /// Callees of the `$bindable()` calls defaulting a `$props()` property,
+    /// the only place it can be called.
+    bindable_defaults: FxHashSet<Span>,
+    /// References that are the target of a `bind:` directive.
+    bind_references: FxHashSet<ReferenceId>,
+    /// References to the object of an assigned member expression, `a` in `a.b = 1`.
+    member_mutations: Vec<ReferenceId>,
+
+    /// Bindings imported from `svelte/store`.
+    store_imports: FxHashSet<SymbolId>,
+    /// Variables initialized by a call, and the reference to the callee.
+    call_initialized: Vec<(SymbolId, ReferenceId)>,
+    /// Variables initialized with a value that can't be a store, e.g. a literal.
+    plain_values: FxHashSet<SymbolId>,
+
+    /// Template expressions, and the indices of the references they contain.
+    template_expressions: Vec<(Span, Range<usize>)>,
+    /// The `ExpressionTag`s and `SpreadAttribute`s, with the span of their
+    /// expression, dynamic if it has dependencies.
+    expression_tags: Vec<(NodeId, Span)>,
+    metadata: NodeMetadata,
+
+    /// Symbols declared by `{@const}` tags, reported if they are never read.
+    const_symbols: Vec<SymbolId>,
+
+    snippets: Vec<Snippet>,
+    /// Scopes of the fragments of components, whose immediate snippets are
+    /// props.
+    component_scopes: FxHashSet<ScopeId>,
+    /// Scopes of the fragments of `<svelte:boundary>`, whose immediate
+    /// snippets, e.g. `failed`, are props too.
+    boundary_scopes: FxHashSet<ScopeId>,
+    /// Whether the fragment built next is the one of a `<svelte:boundary>`.
+    boundary_fragment: bool,
+    /// Whether the element being visited is in a custom element, whose
+    /// children can be placed in its named slots.
+    in_custom_element: bool,
+    /// Whether the elements being built are in `<svelte:head>` or `<svg>`,
+    /// where `<script>` and `<style>` elements are expected.
+    in_head_or_svg: bool,
+    /// Namespace of the elements being built, `Svg`, `Mathml` or empty for
+    /// HTML.
+    namespace: RegularElementFlags,
+    /// Names of the props passed to the component whose fragment is built
+    /// next.
+    component_props: Option<Vec<Atom<'a>>>,
+    /// `{#each}` blocks that are the only child of their element.
+    controlled_each_blocks: FxHashSet<Span>,
+    /// The indexes of the keyed `{#each}` blocks, reactive if they're read.
+    keyed_each_indexes: Vec<(BlockId, SymbolId)>,
+    /// The `{@render}` tags, with the reference to their callee if it's an
+    /// identifier.
+    render_tags: Vec<(Span, Option<ReferenceId>)>,
+
+    /// Targets of `$: name = ...` statements, declared implicitly if nothing
+    /// else declares them.
+    reactive_assignments: Vec<(Span, Atom<'a>)>,
+    /// `$derived` declarations, `$:` statements and `derived` stores, checked
+    /// for cycles.
+    derivations: Vec<Derivation>,
+    /// `$effect`s and `$:` statements, checked for writes to what they read.
+    effects: Vec<Effect>,
+
+    errors: Vec<OxcDiagnostic>,
+}
+
+/// A value computed from others: a `$derived` declaration, a `$:` statement or
+/// a `derived` store.
+struct Derivation {
+    /// The declared variable, `None` for `$:` statements, which compute the
+    /// variables they assign.
+    target: Option<SymbolId>,
+    /// The callee of a `derived(...)` initializer, which only derives a store
+    /// if it's imported from `svelte/store`.
+    store_callee: Option<ReferenceId>,
+    /// Indices of the references it contains.
+    references: Range<usize>,
+}
+
+/// The body of an `$effect` or a `$:` statement, which re-runs when what it
+/// reads changes.
+struct Effect {
+    /// `None` for `$:` statements.
+    rune: Option<Rune>,
+    /// The scope the effect is created in.
+    scope_id: ScopeId,
+    /// Indices of the references it contains.
+    references: Range<usize>,
+}
+
+pub struct SemanticBuilderReturn<'a> {
+    pub semantic: Semantic<'a>,
+    pub errors: Vec<Error>,
+}
+
+impl<'a> SemanticBuilder<'a> {
+    pub fn new(source_text: &'a str) -> Self {
+        let mut scopes = ScopeTree::default();
+        let current_scope_id = scopes.add_scope(None, ScopeFlags::Top | ScopeFlags::StrictMode);
+        Self {
+            source_text,
+            scopes,
+            symbols: SymbolTable::default(),
+            template_nodes: TemplateNodes::default(),
+            current_scope_id,
+            current_block_id: None,
+            instance_scope_id: None,
+            current_symbol_flags: SymbolFlags::None,
+            current_binding_kind: BindingKind::Normal,
+            current_reference_flag: ReferenceFlag::Read,
+            runes: false,
+            runes_option: None,
+            explicit_runes: None,
+            legacy_constructs: vec![],
+            legacy_declarations: vec![],
+            rune_calls: vec![],
+            bindable_defaults: FxHashSet::default(),
+            bind_references: FxHashSet::default(),
+            member_mutations: vec![],
+            store_imports: FxHashSet::default(),
+            call_initialized: vec![],
+            plain_values: FxHashSet::default(),
+            template_expressions: vec![],
+            expression_tags: vec![],
+            metadata: NodeMetadata::default(),
+            const_symbols: vec![],
+            snippets: vec![],
+            component_scopes: FxHashSet::default(),
+            boundary_scopes: FxHashSet::default(),
+            boundary_fragment: false,
+            in_custom_element: false,
+            in_head_or_svg: false,
+            namespace: RegularElementFlags::empty(),
+            component_props: None,
+            controlled_each_blocks: FxHashSet::default(),
+            keyed_each_indexes: vec![],
+            render_tags: vec![],
+            reactive_assignments: vec![],
+            derivations: vec![],
+            effects: vec![],
+            errors: vec![],
+        }
+    }
+
+    /// Force runes mode on or off, as the `runes` compile option does.
+    /// `<svelte:options runes>` takes precedence over it.
+    #[must_use]
+    pub fn with_runes(mut self, runes: Option<bool>) -> Self {
+        self.runes_option = runes;
+        self
+    }
+
+    pub fn build(mut self, root: &Root<'a>) -> SemanticBuilderReturn<'a> {
+        self.explicit_runes =
+            root.options.as_ref().and_then(|options| options.runes).or(self.runes_option);
+        self.runes = self.explicit_runes.unwrap_or(false);
+        if let Some(module) = &root.module {
+            self.visit_statements(&module.program.body);
+        }
+
+        let instance_scope_id = self.enter_child_scope(ScopeFlags::Top | ScopeFlags::StrictMode);
+        self.instance_scope_id = Some(instance_scope_id);
+        if let Some(instance) = &root.instance {
+            self.visit_statements(&instance.program.body);
+        }
+        for (span, name) in mem::take(&mut self.reactive_assignments) {
+            if self.scopes.find_binding(instance_scope_id, &name).is_none() {
+                self.declare(
+                    span,
+                    name,
+                    SymbolFlags::FunctionScopedVariable,
+                    BindingKind::LegacyReactive,
+                    instance_scope_id,
+                );
+            }
+        }
+
+        self.build_fragment(&root.fragment, false);
+
+        self.resolve_references();
+        self.resolve_store_subscriptions();
+        self.check_reactive_cycles();
+        self.check_effect_writes();
+        self.check_snippets();
+        self.resolve_each_indexes();
+        let dynamic_render_tags = self.resolve_render_tags();
+        self.check_unused_consts();
+        self.check_unused_snippets();
+        self.check_template_assignments();
+        self.check_runes_mode(root.options.as_ref());
+        self.check_legacy_options(root.options.as_ref());
+
+        let mut semantic = Semantic {
+            source_text: self.source_text,
+            runes: self.runes,
+            scopes: self.scopes,
+            symbols: self.symbols,
+            template_nodes: self.template_nodes,
+            snippets: self.snippets,
+            dynamic_render_tags,
+            dependencies: FxHashMap::default(),
+            metadata: self.metadata,
+        };
+        for (span, references) in self.template_expressions {
+            let mut dependencies = vec![];
+            for index in references {
+                let reference = semantic.symbols.get_reference(ReferenceId::from_usize(index));
+                if let Some(symbol_id) = reference.symbol_id() {
+                    if semantic.is_reactive(symbol_id) && !dependencies.contains(&symbol_id) {
+                        dependencies.push(symbol_id);
+                    }
+                }
+            }
+            semantic.dependencies.insert(span, dependencies);
+        }
+        for (node_id, expression_span) in self.expression_tags {
+            if semantic.dependencies.get(&expression_span).is_some_and(|deps| !deps.is_empty()) {
+                *semantic
+                    .metadata
+                    .expression_flags
+                    .entry(node_id)
+                    .or_insert(ExpressionTagFlags::empty()) |= ExpressionTagFlags::Dynamic;
+            }
+        }
+
+        let ignores = root.svelte_ignores();
+        let errors = self
+            .errors
+            .into_iter()
+            .filter(|error| !ignores.is_diagnostic_ignored(error))
+            .map(Error::from)
+            .collect();
+        SemanticBuilderReturn { semantic, errors }
+    }
+
+    fn enter_child_scope(&mut self, flags: ScopeFlags) -> ScopeId {
+        self.current_scope_id = self.scopes.add_scope(Some(self.current_scope_id), flags);
+        self.current_scope_id
+    }
+
+    fn leave_current_scope(&mut self) {
+        if let Some(parent_id) = self.scopes.get_parent_id(self.current_scope_id) {
+            self.current_scope_id = parent_id;
+        }
+    }
+
+    fn is_instance_top_level(&self) -> bool {
+        self.instance_scope_id == Some(self.current_scope_id)
+    }
+
+    fn declare(
+        &mut self,
+        span: Span,
+        name: Atom<'a>,
+        flags: SymbolFlags,
+        kind: BindingKind,
+        scope_id: ScopeId,
+    ) -> SymbolId {
+        if let Some(symbol_id) = self.scopes.get_binding(scope_id, &name) {
+            let is_block_scoped = |flags: SymbolFlags| {
+                flags.intersects(
+                    SymbolFlags::BlockScopedVariable
+                        | SymbolFlags::ConstVariable
+                        | SymbolFlags::Class
+                        | SymbolFlags::ImportBinding,
+                )
+            };
+            if is_block_scoped(flags) || is_block_scoped(self.symbols.get_flag(symbol_id)) {
+                self.errors.push(diagnostics::redeclaration(
+                    &name,
+                    self.symbols.get_span(symbol_id),
+                    span,
+                ));
+            }
+            return symbol_id;
+        }
+        let symbol_id = self.symbols.create_symbol(span, name.clone(), flags, kind, scope_id);
+        self.scopes.add_binding(scope_id, name, symbol_id);
+        symbol_id
+    }
+
+    fn declare_binding_identifier(&mut self, ident: &BindingIdentifier<'a>) {
+        let flags = self.current_symbol_flags;
+        let scope_id = if flags.contains(SymbolFlags::FunctionScopedVariable) {
+            self.scopes.get_var_scope_id(self.current_scope_id)
+        } else {
+            self.current_scope_id
+        };
+        let symbol_id = self.declare(
+            ident.span,
+            ident.name.clone(),
+            flags,
+            self.current_binding_kind,
+            scope_id,
+        );
+        ident.symbol_id.set(Some(symbol_id));
+    }
+
+    /// Declare every binding in `pattern`, visiting default values as references.
+    fn declare_pattern(
+        &mut self,
+        pattern: &BindingPattern<'a>,
+        flags: SymbolFlags,
+        kind: BindingKind,
+    ) {
+        let prev_flags = mem::replace(&mut self.current_symbol_flags, flags);
+        let prev_kind = mem::replace(&mut self.current_binding_kind, kind);
+        self.visit_binding_pattern(pattern);
+        self.current_symbol_flags = prev_flags;
+        self.current_binding_kind = prev_kind;
+    }
+
+    fn set_binding_kind(&mut self, pattern: &BindingPattern<'a>, kind: BindingKind) {
+        pattern.bound_names(&mut |ident| {
+            if let Some(symbol_id) = ident.symbol_id.get() {
+                self.symbols.kinds[symbol_id] = kind;
+            }
+        });
+    }
+
+    /// Give the bindings of a `$props()` destructure their prop kind.
+    fn set_props_binding_kinds(&mut self, pattern: &BindingPattern<'a>) {
+        let BindingPatternKind::ObjectPattern(object) = &pattern.kind else {
+            // `let props = $props()`
+            self.set_binding_kind(pattern, BindingKind::RestProp);
+            return;
+        };
+        for property in &object.properties {
+            let kind = match &property.value.kind {
+                BindingPatternKind::AssignmentPattern(assignment)
+                    if Rune::from_expression(&assignment.right) == Some(Rune::Bindable) =>
+                {
+                    if let Expression::CallExpression(call) = &assignment.right {
+                        self.bindable_defaults.insert(call.callee.span());
+                    }
+                    BindingKind::BindableProp
+                }
+                _ => BindingKind::Prop,
+            };
+            self.set_binding_kind(&property.value, kind);
+        }
+        if let Some(rest) = &object.rest {
+            self.set_binding_kind(&rest.argument, BindingKind::RestProp);
+        }
+    }
+
+    /// Record what the initial value of a variable tells about it being a store.
+    fn record_initializer(&mut self, symbol_id: SymbolId, init: &Expression<'a>) {
+        match init.get_inner_expression() {
+            Expression::CallExpression(call) => {
+                if let Some(reference_id) = call
+                    .callee
+                    .get_identifier_reference()
+                    .and_then(|ident| ident.reference_id.get())
+                {
+                    self.call_initialized.push((symbol_id, reference_id));
+                }
+            }
+            Expression::ObjectExpression(object) => {
+                let has_subscribe = object.properties.iter().any(|property| match property {
+                    ObjectPropertyKind::ObjectProperty(property) => {
+                        property.key.is_specific_static_name("subscribe")
+                    }
+                    // The spread object could have a `subscribe` method
+                    ObjectPropertyKind::SpreadProperty(_) => true,
+                });
+                if !has_subscribe {
+                    self.plain_values.insert(symbol_id);
+                }
+            }
+            Expression::ArrayExpression(_)
+            | Expression::ArrowFunctionExpression(_)
+            | Expression::FunctionExpression(_)
+            | Expression::TemplateLiteral(_) => {
+                self.plain_values.insert(symbol_id);
+            }
+            init if init.is_literal() => {
+                self.plain_values.insert(symbol_id);
+            }
+            _ => {}
+        }
+    }
+
+    /// Record that the object `member` is accessed on is mutated.
+    fn add_member_mutation(&mut self, member: &MemberExpression<'a>) {
+        if let Some(reference_id) =
+            root_identifier(member.object()).and_then(|ident| ident.reference_id.get())
+        {
+            self.member_mutations.push(reference_id);
+        }
+    }
+
+    fn add_reference(&mut self, span: Span, name: Atom<'a>, flag: ReferenceFlag) -> ReferenceId {
+        self.symbols.create_reference(Reference::new(span, name, self.current_scope_id, flag))
+    }
+
+    /// Add a reference to a name used by the template, e.g. a component tag or
+    /// the action of a `use:` directive. Only the object of a member access is
+    /// referenced.
+    fn add_template_reference(&mut self, start: u32, name: &'a str) {
+        let name = name.split('.').next().unwrap_or(name);
+        #[allow(clippy::cast_possible_truncation)]
+        let span = Span::new(start, start + name.len() as u32);
+        self.add_reference(span, Atom::from(name), ReferenceFlag::Read);
+    }
+
+    /// Visit an expression of the template, recording the references it
+    /// contains to compute its dependencies.
+    fn build_expression(&mut self, expression: &Expression<'a>) {
+        self.record_dependencies(expression.span(), |builder| builder.visit_expression(expression));
+    }
+
+    /// Visit the expression of the `ExpressionTag` or `SpreadAttribute` with
+    /// `node_id`, recording its flags.
+    fn build_expression_tag(&mut self, node_id: NodeId, expression: &Expression<'a>) {
+        if metadata::has_call(expression) {
+            self.metadata.expression_flags.insert(node_id, ExpressionTagFlags::CallExpression);
+        }
+        self.expression_tags.push((node_id, expression.span()));
+        self.build_expression(expression);
+    }
+
+    fn record_dependencies<F: FnOnce(&mut Self)>(&mut self, span: Span, f: F) {
+        let start = self.symbols.references.len();
+        f(self);
+        self.template_expressions.push((span, start..self.symbols.references.len()));
+    }
+
+    fn resolve_references(&mut self) {
+        for index in 0..self.symbols.references.len() {
+            let reference_id = ReferenceId::from_usize(index);
+            let reference = &mut self.symbols.references[reference_id];
+            if let Some(symbol_id) =
+                self.scopes.find_binding(reference.scope_id(), reference.name())
+            {
+                reference.set_symbol_id(symbol_id);
+                self.symbols.resolved_references[symbol_id].push(reference_id);
+            }
+        }
+    }
+
+    /// Declare `$name` for every top-level `name` subscribed to, and validate
+    /// the subscriptions.
+    fn resolve_store_subscriptions(&mut self) {
+        for (symbol_id, reference_id) in mem::take(&mut self.call_initialized) {
+            let callee = self.symbols.references[reference_id].symbol_id();
+            if callee.is_some_and(|callee| self.store_imports.contains(&callee)) {
+                self.symbols.stores.insert(symbol_id);
+            }
+        }
+
+        let Some(instance_scope_id) = self.instance_scope_id else { return };
+        for index in 0..self.symbols.references.len() {
+            let reference_id = ReferenceId::from_usize(index);
+            let reference = &self.symbols.references[reference_id];
+            let name = reference.name().clone();
+            let span = reference.span();
+            // `$$props` and friends, and runes, are not subscriptions
+            if reference.symbol_id().is_some()
+                || !name.starts_with('$')
+                || name.starts_with("$$")
+                || Rune::from_name(&name).is_some()
+            {
+                continue;
+            }
+            let store_name = &name[1..];
+            let Some(store) = self.scopes.find_binding(instance_scope_id, store_name) else {
+                if self.scopes.find_binding(reference.scope_id(), store_name).is_some() {
+                    self.errors.push(diagnostics::store_invalid_scoped_subscription(span));
+                }
+                continue;
+            };
+            let symbol_id =
+                if let Some(symbol_id) = self.scopes.get_binding(instance_scope_id, &name) {
+                    symbol_id
+                } else {
+                    if self.plain_values.contains(&store) && !self.symbols.is_mutated(store) {
+                        self.errors.push(diagnostics::store_invalid_value(
+                            store_name,
+                            self.symbols.get_span(store),
+                            span,
+                        ));
+                    }
+                    self.declare(
+                        self.symbols.get_span(store),
+                        name,
+                        SymbolFlags::empty(),
+                        BindingKind::StoreSub,
+                        instance_scope_id,
+                    )
+                };
+            self.symbols.references[reference_id].set_symbol_id(symbol_id);
+            self.symbols.resolved_references[symbol_id].push(reference_id);
+        }
+    }
+
+    /// Report cycles among derivations, which can never settle. `$:`
+    /// statements don't depend on the variables they assign, so that
+    /// `$: count = count + 1` isn't one.
+    fn check_reactive_cycles(&mut self) {
+        let Some(instance_scope_id) = self.instance_scope_id else { return };
+        // Dependencies of every derived variable, with the first reference to
+        // each, in source order
+        let mut graph: Vec<(SymbolId, Vec<(SymbolId, Span)>)> = vec![];
+        for derivation in mem::take(&mut self.derivations) {
+            if let Some(callee) = derivation.store_callee {
+                let callee = self.symbols.references[callee].symbol_id();
+                if !callee.is_some_and(|callee| self.store_imports.contains(&callee)) {
+                    continue;
+                }
+            }
+            let references = derivation
+                .references
+                .map(|index| &self.symbols.references[ReferenceId::from_usize(index)])
+                .filter_map(|reference| {
+                    reference.symbol_id().map(|symbol_id| (reference, symbol_id))
+                })
+                .collect::<Vec<_>>();
+            let targets = derivation.target.map_or_else(
+                || {
+                    let mut targets = vec![];
+                    for &(reference, symbol_id) in &references {
+                        if reference.is_write()
+                            && self.symbols.get_scope_id(symbol_id) == instance_scope_id
+                            && !targets.contains(&symbol_id)
+                        {
+                            targets.push(symbol_id);
+                        }
+                    }
+                    targets
+                },
+                |target| vec![target],
+            );
+            let mut dependencies: Vec<(SymbolId, Span)> = vec![];
+            for &(reference, symbol_id) in &references {
+                // `$store` depends on `store`
+                let symbol_id = if self.symbols.get_kind(symbol_id) == BindingKind::StoreSub {
+                    let name = &self.symbols.get_name(symbol_id)[1..];
+                    let Some(store) = self.scopes.find_binding(instance_scope_id, name) else {
+                        continue;
+                    };
+                    store
+                } else {
+                    symbol_id
+                };
+                let is_own_assignment = derivation.target.is_none() && targets.contains(&symbol_id);
+                if reference.is_read()
+                    && !is_own_assignment
+                    && !dependencies.iter().any(|(dependency, _)| *dependency == symbol_id)
+                {
+                    dependencies.push((symbol_id, reference.span()));
+                }
+            }
+            for target in targets {
+                match graph.iter_mut().find(|(symbol_id, _)| *symbol_id == target) {
+                    Some((_, existing)) => existing.extend(dependencies.iter().copied()),
+                    None => graph.push((target, dependencies.clone())),
+                }
+            }
+        }
+
+        let mut reported = FxHashSet::default();
+        for &(start, _) in &graph {
+            if reported.contains(&start) {
+                continue;
+            }
+            // Shortest path back to `start`, breadth first
+            let mut previous: FxHashMap<SymbolId, (SymbolId, Span)> = FxHashMap::default();
+            let mut queue = VecDeque::from([start]);
+            let mut closing = None;
+            'search: while let Some(symbol_id) = queue.pop_front() {
+                let Some((_, dependencies)) = graph.iter().find(|(id, _)| *id == symbol_id) else {
+                    continue;
+                };
+                for &(dependency, span) in dependencies {
+                    if dependency == start {
+                        closing = Some((symbol_id, span));
+                        break 'search;
+                    }
+                    if let Entry::Vacant(entry) = previous.entry(dependency) {
+                        entry.insert((symbol_id, span));
+                        queue.push_back(dependency);
+                    }
+                }
+            }
+            let Some((mut symbol_id, span)) = closing else { continue };
+            let mut cycle = vec![(symbol_id, span)];
+            while symbol_id != start {
+                let (from, span) = previous[&symbol_id];
+                cycle.push((from, span));
+                symbol_id = from;
+            }
+            cycle.reverse();
+            reported.extend(cycle.iter().map(|(symbol_id, _)| *symbol_id));
+            let names = cycle
+                .iter()
+                .map(|(symbol_id, _)| self.symbols.get_name(*symbol_id).as_str())
+                .collect::<Vec<_>>();
+            let steps = cycle
+                .iter()
+                .enumerate()
+                .map(|(index, (_, span))| (names[index], names[(index + 1) % names.len()], *span))
+                .collect::<Vec<_>>();
+            self.errors.push(diagnostics::reactive_declaration_cycle(&steps));
+        }
+    }
+
+    /// Warn about effects writing to state they read. An `$effect` re-runs
+    /// itself, in an update loop, and a `$:` statement doesn't depend on what
+    /// it assigns, so it doesn't re-run when that changes.
+    ///
+    /// Only the references made while the effect runs count, not those of the
+    /// functions it creates, e.g. event handlers or timers.
+    fn check_effect_writes(&mut self) {
+        let Some(instance_scope_id) = self.instance_scope_id else { return };
+        for effect in mem::take(&mut self.effects) {
+            // `$:` statements are reported as legacy in runes mode
+            if effect.rune.is_none() && self.runes {
+                continue;
+            }
+            // The function passed to `$effect` runs, not those it contains
+            let own_functions = usize::from(effect.rune.is_some());
+            // Symbols with their first read and first write
+            let mut accesses: Vec<(SymbolId, Option<Span>, Option<Span>)> = vec![];
+            for index in effect.references {
+                let reference = &self.symbols.references[ReferenceId::from_usize(index)];
+                let Some(symbol_id) = reference.symbol_id() else { continue };
+                let is_state = self.symbols.get_scope_id(symbol_id) == instance_scope_id
+                    && (effect.rune.is_none() || self.symbols.get_kind(symbol_id).is_reactive());
+                let functions = self
+                    .scopes
+                    .ancestors(reference.scope_id())
+                    .take_while(|scope_id| *scope_id != effect.scope_id)
+                    .filter(|scope_id| self.scopes.get_flags(*scope_id).is_function())
+                    .count();
+                if !is_state || functions > own_functions {
+                    continue;
+                }
+                let index =
+                    if let Some(index) = accesses.iter().position(|(id, ..)| *id == symbol_id) {
+                        index
+                    } else {
+                        accesses.push((symbol_id, None, None));
+                        accesses.len() - 1
+                    };
+                let (_, read, write) = &mut accesses[index];
+                if reference.is_read() {
+                    read.get_or_insert(reference.span());
+                }
+                if reference.is_write() {
+                    write.get_or_insert(reference.span());
+                }
+            }
+            for (symbol_id, read, write) in accesses {
+                let (Some(read), Some(write)) = (read, write) else { continue };
+                let name = self.symbols.get_name(symbol_id);
+                self.errors.push(match effect.rune {
+                    Some(rune) => {
+                        diagnostics::effect_self_invalidation(rune.as_str(), name, read, write)
+                    }
+                    None => diagnostics::reactive_statement_self_assignment(name, read, write),
+                });
+            }
+        }
+    }
+
+    /// Report legacy constructs in runes mode, and runes in legacy mode.
+    fn check_runes_mode(&mut self, options: Option<&SvelteOptions>) {
+        if self.runes {
+            self.errors.append(&mut self.legacy_constructs);
+            for (diagnostic, replacements) in mem::take(&mut self.legacy_declarations) {
+                // Point at the rune replacing the declaration if it's called
+                let rune_call = self
+                    .rune_calls
+                    .iter()
+                    .find(|(_, rune)| replacements.contains(rune))
+                    .or_else(|| self.rune_calls.first());
+                let diagnostic = match (self.explicit_runes, rune_call) {
+                    (None, Some(&(span, rune))) => {
+                        diagnostics::runes_mode_inferred(diagnostic, rune.as_str(), span)
+                    }
+                    _ => diagnostics::runes_mode_explicit(
+                        diagnostic,
+                        options
+                            .filter(|options| options.runes.is_some())
+                            .map(|options| options.span),
+                    ),
+                };
+                self.errors.push(diagnostic);
+            }
+            for (span, rune) in &self.rune_calls {
+                if *rune == Rune::Bindable && !self.bindable_defaults.contains(span) {
+                    self.errors.push(diagnostics::bindable_invalid_location(*span));
+                }
+            }
+            return;
+        }
+        if self.explicit_runes != Some(false) {
+            return;
+        }
+        for (span, rune) in mem::take(&mut self.rune_calls) {
+            // `$state()` calls the `state` store if there's one
+            let store_name = rune.as_str()[1..].split('.').next().unwrap_or_default();
+            let is_store = self
+                .instance_scope_id
+                .is_some_and(|scope_id| self.scopes.find_binding(scope_id, store_name).is_some());
+            if !is_store {
+                self.errors.push(diagnostics::rune_invalid_usage(rune.as_str(), span));
+            }
+        }
+    }
+
+    /// Warn about the `accessors` and `immutable` options in runes mode,
+    /// where they have no effect.
+    fn check_legacy_options(&mut self, options: Option<&SvelteOptions>) {
+        let Some(options) = options.filter(|_| self.runes) else { return };
+        let legacy = [("accessors", options.accessors), ("immutable", options.immutable)];
+        for (name, _) in legacy.into_iter().filter(|(_, enabled)| *enabled == Some(true)) {
+            self.errors.push(diagnostics::options_deprecated_in_runes(name, options.span));
+        }
+    }
+
+    /// Report assignments and bindings to template declared bindings that
+    /// can't be written to.
+    fn check_template_assignments(&mut self) {
+        for reference_id in mem::take(&mut self.member_mutations) {
+            if let Some(symbol_id) = self.symbols.references[reference_id].symbol_id() {
+                self.symbols.member_mutations.insert(symbol_id);
+            }
+        }
+        for (reference_id, reference) in self.symbols.references.iter_enumerated() {
+            let Some(symbol_id) = reference.symbol_id() else { continue };
+            if !reference.is_write() {
+                continue;
+            }
+            let span = reference.span();
+            let is_binding = self.bind_references.contains(&reference_id);
+            let flags = self.symbols.get_flag(symbol_id);
+            let declaration = self.symbols.get_span(symbol_id);
+            let diagnostic =
+                match (self.symbols.get_kind(symbol_id), self.symbols.get_each_source(symbol_id)) {
+                    (BindingKind::Each, Some(_)) if self.runes => {
+                        diagnostics::each_item_invalid_assignment(declaration, span)
+                    }
+                    (BindingKind::Each, Some(EachSource::Expression)) => {
+                        diagnostics::each_item_invalid_source(declaration, span)
+                    }
+                    (BindingKind::Each, Some(EachSource::Reference(_))) => continue,
+                    (BindingKind::Snippet, _)
+                        if flags.contains(SymbolFlags::FunctionScopedVariable) =>
+                    {
+                        diagnostics::snippet_parameter_assignment(declaration, span)
+                    }
+                    _ if flags.contains(SymbolFlags::ConstVariable) => {
+                        diagnostics::constant_assignment(
+                            reference.name(),
+                            declaration,
+                            span,
+                            is_binding,
+                        )
+                    }
+                    _ => continue,
+                };
+            self.errors.push(diagnostic);
+        }
+    }
+
+    /// Report the `{@const}`s of the children of a component read by the
+    /// snippets passed to it, which are rendered apart from the children, and
+    /// find the hoistable snippets.
+    fn check_snippets(&mut self) {
+        let module_scope_id = self.scopes.root_scope_id();
+        let mut snippet_scopes = FxHashMap::default();
+        let mut snippet_symbols = FxHashMap::default();
+        for (index, snippet) in self.snippets.iter_mut().enumerate() {
+            snippet_scopes.insert(snippet.scope_id, index);
+            snippet_symbols.insert(snippet.symbol_id, index);
+            let fragment_scope_id = self.scopes.get_parent_id(snippet.scope_id);
+            snippet.hoistable = fragment_scope_id
+                .and_then(|scope_id| self.scopes.get_parent_id(scope_id))
+                .is_some_and(|scope_id| Some(scope_id) == self.instance_scope_id);
+        }
+        if snippet_scopes.is_empty() {
+            return;
+        }
+        // Snippets rendering other snippets, hoistable only if those are
+        let mut dependencies = vec![];
+        for reference in &self.symbols.references.raw {
+            let symbol_id = reference.symbol_id();
+            let symbol_scope_id = symbol_id.map(|symbol_id| self.symbols.get_scope_id(symbol_id));
+            // Whether the symbol is declared in the scopes walked so far, and
+            // so inside of the snippets found from there on
+            let mut declared_inside = false;
+            for scope_id in self.scopes.ancestors(reference.scope_id()) {
+                declared_inside |= symbol_scope_id == Some(scope_id);
+                let Some(&index) = snippet_scopes.get(&scope_id) else { continue };
+                if declared_inside {
+                    continue;
+                }
+                let snippet = &mut self.snippets[index];
+                let Some(symbol_id) = symbol_id else {
+                    // `$$props` and friends
+                    if reference.name().starts_with("$$") {
+                        snippet.hoistable = false;
+                    }
+                    continue;
+                };
+                if let Some(&dependency) = snippet_symbols.get(&symbol_id) {
+                    dependencies.push((index, dependency));
+                } else if symbol_scope_id != Some(module_scope_id) {
+                    snippet.hoistable = false;
+                }
+                if snippet.is_prop
+                    && symbol_scope_id == self.scopes.get_parent_id(snippet.scope_id)
+                    && self.const_symbols.contains(&symbol_id)
+                {
+                    self.errors.push(diagnostics::const_tag_invalid_reference(
+                        reference.name(),
+                        self.symbols.get_span(symbol_id),
+                        reference.span(),
+                    ));
+                }
+            }
+        }
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (index, dependency) in &dependencies {
+                if self.snippets[*index].hoistable && !self.snippets[*dependency].hoistable {
+                    self.snippets[*index].hoistable = false;
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    /// Flag the keyed `{#each}` blocks whose index is read.
+    fn resolve_each_indexes(&mut self) {
+        for (block_id, symbol_id) in mem::take(&mut self.keyed_each_indexes) {
+            if self.symbols.get_resolved_references(symbol_id).next().is_some() {
+                if let Some(flags) = self.template_nodes.each_flags.get_mut(&block_id) {
+                    *flags |= EachBlockFlags::IndexReactive;
+                }
+            }
+        }
+    }
+
+    /// The flags of `block` known before resolving references, all but
+    /// [`EachBlockFlags::IndexReactive`].
+    fn each_block_flags(&self, block: &EachBlock<'a>) -> EachBlockFlags {
+        let mut flags = EachBlockFlags::empty();
+        if block.key.is_some() {
+            flags |= EachBlockFlags::Keyed;
+            let is_animated = block.body.nodes.iter().any(|node| {
+                let FragmentNode::Element(element) = node else { return false };
+                element.attributes().iter().any(|attribute| {
+                    matches!(
+                        attribute.as_directive_attribute(),
+                        Some(DirectiveAttribute::AnimateDirective(_))
+                    )
+                })
+            });
+            if is_animated {
+                flags |= EachBlockFlags::Animated;
+            }
+        }
+        // In runes mode, an item that is its own key is replaced rather than
+        // updated when it changes
+        let key_is_item = matches!(
+            (&block.key, &block.context.kind),
+            (Some(Expression::Identifier(key)), BindingPatternKind::BindingIdentifier(item))
+                if key.name == item.name
+        );
+        if !(key_is_item && self.runes) {
+            flags |= EachBlockFlags::ItemReactive;
+        }
+        if self.controlled_each_blocks.contains(&block.span) {
+            flags |= EachBlockFlags::Controlled;
+        }
+        flags
+    }
+
+    /// The spans of the `{@render}` tags whose callee isn't a `{#snippet}`.
+    fn resolve_render_tags(&mut self) -> FxHashSet<Span> {
+        let mut dynamic = FxHashSet::default();
+        for (span, reference_id) in mem::take(&mut self.render_tags) {
+            let symbol_id = reference_id
+                .and_then(|reference_id| self.symbols.references[reference_id].symbol_id());
+            let is_snippet = symbol_id.is_some_and(|symbol_id| {
+                self.snippets.iter().any(|snippet| snippet.symbol_id == symbol_id)
+            });
+            if !is_snippet {
+                dynamic.insert(span);
+            }
+        }
+        dynamic
+    }
+
+    /// Report the snippets passed to a component that conflict with its
+    /// `props` or its other children.
+    fn check_component_snippets(&mut self, fragment: &Fragment<'a>, props: &[Atom<'a>]) {
+        let mut children = None;
+        let mut has_content = false;
+        for node in &fragment.nodes {
+            match node {
+                FragmentNode::Block(Block::SnippetBlock(block)) => {
+                    let name = &block.expression;
+                    if props.contains(&name.name) {
+                        self.errors
+                            .push(diagnostics::snippet_shadowing_prop(&name.name, name.span));
+                    }
+                    if name.name == "children" {
+                        children = Some(name.span);
+                    }
+                }
+                FragmentNode::Text(text) if text.data.trim().is_empty() => {}
+                FragmentNode::Comment(_) => {}
+                _ => has_content = true,
+            }
+        }
+        if let (Some(span), true) = (children, has_content) {
+            self.errors.push(diagnostics::snippet_conflict(span));
+        }
+    }
+
+    fn check_unused_consts(&mut self) {
+        for symbol_id in mem::take(&mut self.const_symbols) {
+            if self.symbols.get_resolved_references(symbol_id).next().is_none() {
+                self.errors.push(diagnostics::unused_const_tag(
+                    self.symbols.get_name(symbol_id),
+                    self.symbols.get_span(symbol_id),
+                ));
+            }
+        }
+    }
+
+    /// Warn on the snippets that are never rendered, passed to a component or
+    /// exported by the module script. Snippets only rendering themselves are
+    /// unused too.
+    fn check_unused_snippets(&mut self) {
+        let module_scope_id = self.scopes.root_scope_id();
+        // `export { row }` of the module script, which can't see the template
+        let exported = self
+            .symbols
+            .references
+            .raw
+            .iter()
+            .filter(|reference| {
+                reference.symbol_id().is_none() && reference.scope_id() == module_scope_id
+            })
+            .map(|reference| reference.name().as_str())
+            .collect::<FxHashSet<_>>();
+        for snippet in &self.snippets {
+            if snippet.is_prop {
+                continue;
+            }
+            let is_rendered =
+                self.symbols.get_resolved_references(snippet.symbol_id).any(|reference| {
+                    !self
+                        .scopes
+                        .ancestors(reference.scope_id())
+                        .any(|scope_id| scope_id == snippet.scope_id)
+                });
+            let name = self.symbols.get_name(snippet.symbol_id);
+            if !is_rendered && !exported.contains(name.as_str()) {
+                self.errors.push(diagnostics::unused_snippet(name.as_str(), snippet.span));
+            }
+        }
+    }
+
+    /// Build a fragment in a scope of its own. `consts_allowed` is set for the
+    /// fragments `{@const}` can be an immediate child of.
+    fn build_fragment(&mut self, fragment: &Fragment<'a>, consts_allowed: bool) {
+        let component_props = self.component_props.take();
+        let scope_id = self.enter_child_scope(ScopeFlags::empty());
+        if let Some(props) = component_props {
+            self.component_scopes.insert(scope_id);
+            self.check_component_snippets(fragment, &props);
+        }
+        if mem::take(&mut self.boundary_fragment) {
+            self.boundary_scopes.insert(scope_id);
+        }
+        for node in &fragment.nodes {
+            match node {
+                FragmentNode::Text(_) | FragmentNode::Comment(_) => {}
+                FragmentNode::Tag(Tag::ConstTag(tag)) if !consts_allowed => {
+                    self.errors.push(diagnostics::const_tag_invalid_placement(tag.span));
+                    self.build_const_tag(tag);
+                }
+                FragmentNode::Tag(tag) => self.build_tag(tag),
+                FragmentNode::Element(element) => self.build_element(element),
+                FragmentNode::Block(block) => self.build_block(block),
+            }
+        }
+        self.leave_current_scope();
+    }
+
+    fn build_tag(&mut self, tag: &Tag<'a>) {
+        match tag {
+            Tag::ExpressionTag(tag) => self.build_expression_tag(tag.node_id, &tag.expression),
+            Tag::HtmlTag(tag) => self.build_expression(&tag.expression),
+            Tag::ConstTag(tag) => self.build_const_tag(tag),
+            Tag::DebugTag(tag) => {
+                for ident in &tag.identifiers {
+                    self.visit_identifier_reference(ident);
+                }
+            }
+            Tag::RenderTag(tag) => match &tag.expression {
+                RenderTagExpression::Call(call) | RenderTagExpression::Chain(call) => {
+                    for argument in &call.arguments {
+                        if let Argument::SpreadElement(spread) = argument {
+                            self.errors
+                                .push(diagnostics::render_tag_invalid_spread_argument(spread.span));
+                        }
+                    }
+                    if call.callee.as_member_expression().is_some_and(|member| {
+                        matches!(member.static_property_name(), Some("apply" | "bind" | "call"))
+                    }) {
+                        self.errors
+                            .push(diagnostics::render_tag_invalid_call_expression(call.span));
+                    }
+                    self.record_dependencies(call.span, |builder| {
+                        builder.visit_call_expression(call);
+                    });
+                    let reference_id = call
+                        .callee
+                        .get_identifier_reference()
+                        .and_then(|ident| ident.reference_id.get());
+                    self.render_tags.push((tag.span, reference_id));
+                }
+            },
+        }
+    }
+
+    /// Declare the bindings of a `{@const}` in the scope of its fragment.
+    fn build_const_tag(&mut self, tag: &ConstTag<'a>) {
+        let prev_kind = mem::replace(&mut self.current_binding_kind, BindingKind::Template);
+        self.visit_variable_declaration(&tag.declaration);
+        self.current_binding_kind = prev_kind;
+        tag.declaration.bound_names(&mut |ident| {
+            if let Some(symbol_id) = ident.symbol_id.get() {
+                self.const_symbols.push(symbol_id);
+            }
+        });
+    }
+
+    fn build_element(&mut self, element: &Element<'a>) {
+        let parent_is_component = self.component_scopes.contains(&self.current_scope_id);
+        let (attributes, fragment) = match element {
+            Element::Component(component) => {
+                self.add_template_reference(component.span.start + 1, component.name.as_str());
+                (&component.attributes, &component.fragment)
+            }
+            Element::SvelteComponent(component) => {
+                self.build_expression(&component.expression);
+                (&component.attributes, &component.fragment)
+            }
+            Element::SvelteElement(element) => {
+                self.build_expression(&element.expression);
+                if self.namespace == RegularElementFlags::Svg {
+                    self.metadata
+                        .svelte_element_flags
+                        .insert(element.node_id, SvelteElementFlags::Svg);
+                }
+                (&element.attributes, &element.fragment)
+            }
+            Element::TitleElement(element) => (&element.attributes, &element.fragment),
+            Element::SlotElement(element) => {
+                self.template_nodes.slot_spans.push(element.span);
+                self.template_nodes.slot_parents.push(self.current_block_id);
+                (&element.attributes, &element.fragment)
+            }
+            Element::RegularElement(element) => {
+                self.check_misplaced_section(element);
+                self.check_unsafe_attributes(element);
+                self.build_element_flags(element);
+                let mut children = element.fragment.nodes.iter().filter(|node| match node {
+                    FragmentNode::Text(text) => !text.data.trim().is_empty(),
+                    FragmentNode::Comment(_) => false,
+                    _ => true,
+                });
+                if let (Some(FragmentNode::Block(Block::EachBlock(block))), None) =
+                    (children.next(), children.next())
+                {
+                    self.controlled_each_blocks.insert(block.span);
+                }
+                (&element.attributes, &element.fragment)
+            }
+            Element::SvelteBody(element) => (&element.attributes, &element.fragment),
+            Element::SvelteBoundary(element) => {
+                self.check_svelte_boundary(&element.attributes);
+                self.boundary_fragment = true;
+                (&element.attributes, &element.fragment)
+            }
+            Element::SvelteDocument(element) => (&element.attributes, &element.fragment),
+            Element::SvelteFragment(element) => (&element.attributes, &element.fragment),
+            Element::SvelteHead(element) => (&element.attributes, &element.fragment),
+            Element::SvelteOptionsRaw(element) => (&element.attributes, &element.fragment),
+            Element::SvelteSelf(element) => (&element.attributes, &element.fragment),
+            Element::SvelteWindow(element) => (&element.attributes, &element.fragment),
+        };
+
+        // `let:` directives declare bindings for the rest of the element, its
+        // other attributes included
+        let let_directives = attributes
+            .iter()
+            .filter_map(|attribute| match attribute.as_directive_attribute() {
+                Some(DirectiveAttribute::LetDirective(directive)) => Some(directive),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let has_let_scope = !let_directives.is_empty();
+        if has_let_scope {
+            let scope_id = self.enter_child_scope(ScopeFlags::empty());
+            for directive in let_directives {
+                self.declare_let_directive(directive, scope_id);
+            }
+        }
+        for attribute in attributes {
+            self.build_attribute(attribute);
+        }
+        self.check_legacy_element(element, attributes);
+        self.check_event_directives(element, attributes);
+        self.check_slots(element, attributes, parent_is_component);
+        // Components and slotted content receive props their consts can derive from
+        let consts_allowed = matches!(
+            element,
+            Element::Component(_)
+                | Element::SvelteComponent(_)
+                | Element::SvelteSelf(_)
+                | Element::SvelteFragment(_)
+        ) || attributes.iter().any(|attribute| {
+            matches!(attribute, ElementAttribute::Attribute(attribute) if attribute.name == "slot")
+        });
+        if matches!(
+            element,
+            Element::Component(_) | Element::SvelteComponent(_) | Element::SvelteSelf(_)
+        ) {
+            self.component_props = Some(component_props(attributes));
+        }
+        let parent_in_custom_element = self.in_custom_element;
+        let parent_in_head_or_svg = self.in_head_or_svg;
+        let parent_namespace = self.namespace;
+        match element {
+            Element::RegularElement(element) => {
+                self.in_custom_element |= element.name.contains('-');
+                self.in_head_or_svg |= element.name == "svg";
+                // The children of `<foreignObject>` are HTML
+                self.namespace = if element.name == "foreignObject" {
+                    RegularElementFlags::empty()
+                } else {
+                    self.element_namespace(element)
+                };
+            }
+            Element::SvelteHead(_) => self.in_head_or_svg = true,
+            _ => {}
+        }
+        self.build_fragment(fragment, consts_allowed);
+        self.in_custom_element = parent_in_custom_element;
+        self.in_head_or_svg = parent_in_head_or_svg;
+        self.namespace = parent_namespace;
+        if has_let_scope {
+            self.leave_current_scope();
+        }
+    }
+
+    /// The namespace of `element`, `Svg`, `Mathml` or empty for HTML.
+    fn element_namespace(&self, element: &RegularElement<'a>) -> RegularElementFlags {
+        match element.name.as_str() {
+            "svg" => RegularElementFlags::Svg,
+            "math" => RegularElementFlags::Mathml,
+            _ => self.namespace,
+        }
+    }
+
+    /// Record the namespace of `element`, and whether it has spread
+    /// attributes.
+    fn build_element_flags(&mut self, element: &RegularElement<'a>) {
+        let mut flags = self.element_namespace(element);
+        if element
+            .attributes
+            .iter()
+            .any(|attribute| matches!(attribute, ElementAttribute::SpreadAttribute(_)))
+        {
+            flags |= RegularElementFlags::Spread;
+        }
+        if !flags.is_empty() {
+            self.metadata.element_flags.insert(element.node_id, flags);
+        }
+    }
+
+    /// Warn on `<script>` and `<style>` elements of the markup, which are
+    /// rendered as elements of the page rather than being the component's
+    /// script or styles, unless they're in `<svelte:head>` or `<svg>`, or the
+    /// script isn't JavaScript, e.g. JSON-LD.
+    fn check_misplaced_section(&mut self, element: &RegularElement<'a>) {
+        if self.in_head_or_svg {
+            return;
+        }
+        match element.name.as_str() {
+            "script" => {
+                let is_javascript =
+                    find_attribute(&element.attributes, "type").map_or(true, |attribute| {
+                        static_value(attribute).is_some_and(|value| {
+                            matches!(
+                                value.trim().to_ascii_lowercase().as_str(),
+                                "" | "module" | "text/javascript" | "application/javascript"
+                            )
+                        })
+                    });
+                if is_javascript {
+                    self.errors.push(diagnostics::script_misplaced(element.span));
+                }
+            }
+            "style" => self.errors.push(diagnostics::style_misplaced(element.span)),
+            _ => {}
+        }
+    }
+
+    /// Warn about attributes that open the page to other sites or to script
+    /// injection: links opening a new tab with access to `window.opener`,
+    /// `javascript:` URLs and `srcdoc` iframes rendering unsandboxed markup.
+    fn check_unsafe_attributes(&mut self, element: &RegularElement<'a>) {
+        let attributes = &element.attributes;
+        for attribute in attributes {
+            let ElementAttribute::Attribute(attribute) = attribute else { continue };
+            if matches!(
+                attribute.name.as_str(),
+                "href" | "src" | "action" | "formaction" | "xlink:href"
+            ) && is_javascript_url(attribute)
+            {
+                self.errors.push(diagnostics::javascript_url(attribute.span));
+            }
+        }
+        let has_spread = attributes
+            .iter()
+            .any(|attribute| matches!(attribute, ElementAttribute::SpreadAttribute(_)));
+        if has_spread {
+            return;
+        }
+        match element.name.as_str() {
+            "a" | "area" => {
+                let Some(target) = find_attribute(attributes, "target") else { return };
+                if !static_value(target).is_some_and(|value| value.eq_ignore_ascii_case("_blank")) {
+                    return;
+                }
+                match find_attribute(attributes, "rel") {
+                    None => {
+                        self.errors.push(diagnostics::target_blank_without_rel(target.span, true));
+                    }
+                    Some(rel) => {
+                        let value = match &rel.value {
+                            None => "",
+                            // A dynamic `rel` may set `noopener`
+                            Some(_) => {
+                                let Some(value) = static_value(rel) else { return };
+                                value
+                            }
+                        };
+                        let is_safe = value.split_ascii_whitespace().any(|keyword| {
+                            keyword.eq_ignore_ascii_case("noopener")
+                                || keyword.eq_ignore_ascii_case("noreferrer")
+                        });
+                        if !is_safe {
+                            self.errors
+                                .push(diagnostics::target_blank_without_rel(target.span, false));
+                        }
+                    }
+                }
+            }
+            "iframe" => {
+                let Some(srcdoc) = find_attribute(attributes, "srcdoc") else { return };
+                if static_value(srcdoc).is_none()
+                    && srcdoc.value.is_some()
+                    && find_attribute(attributes, "sandbox").is_none()
+                {
+                    self.errors.push(diagnostics::srcdoc_unsandboxed(srcdoc.span));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Only `onerror` and `failed` can be set on `<svelte:boundary>`, to an
+    /// expression. The `failed` snippet is usually passed as a child instead.
+    fn check_svelte_boundary(&mut self, attributes: &[ElementAttribute<'a>]) {
+        for attribute in attributes {
+            let ElementAttribute::Attribute(attribute) = attribute else {
+                self.errors.push(diagnostics::svelte_boundary_invalid_attribute(attribute.span()));
+                continue;
+            };
+            if !matches!(attribute.name.as_str(), "onerror" | "failed") {
+                self.errors.push(diagnostics::svelte_boundary_invalid_attribute(attribute.span));
+                continue;
+            }
+            let is_expression = attribute.value.as_ref().is_some_and(|value| {
+                matches!(value.sequence.as_slice(), [AttributeSequenceValue::ExpressionTag(_)])
+            });
+            if !is_expression {
+                self.errors
+                    .push(diagnostics::svelte_boundary_invalid_attribute_value(attribute.span));
+            }
+        }
+    }
+
+    /// Validate the names of `<slot>` elements, and the `slot` attributes
+    /// placing content in the named slots of components.
+    fn check_slots(
+        &mut self,
+        element: &Element<'a>,
+        attributes: &[ElementAttribute<'a>],
+        parent_is_component: bool,
+    ) {
+        if let Element::SlotElement(_) = element {
+            if let Some(attribute) = find_attribute(attributes, "name") {
+                match static_value(attribute) {
+                    None => {
+                        self.errors.push(diagnostics::slot_element_invalid_name(attribute.span))
+                    }
+                    Some("default") => {
+                        self.errors
+                            .push(diagnostics::slot_element_invalid_name_default(attribute.span));
+                    }
+                    Some(_) => {}
+                }
+            }
+        } else if let Some(attribute) = find_attribute(attributes, "slot") {
+            if static_value(attribute).is_none() {
+                self.errors.push(diagnostics::slot_attribute_invalid(attribute.span));
+            }
+            if !parent_is_component && !self.in_custom_element {
+                self.errors.push(diagnostics::slot_attribute_invalid_placement(attribute.span));
+            }
+        }
+
+        let (name, fragment) = match element {
+            Element::Component(component) => (component.name.as_str(), &component.fragment),
+            Element::SvelteComponent(component) => ("svelte:component", &component.fragment),
+            Element::SvelteSelf(element) => ("svelte:self", &element.fragment),
+            _ => return,
+        };
+        let mut slots = FxHashMap::default();
+        let mut default_content = false;
+        for node in &fragment.nodes {
+            let slot = match node {
+                FragmentNode::Element(child) => find_attribute(child.attributes(), "slot"),
+                FragmentNode::Text(text) if text.data.trim().is_empty() => continue,
+                FragmentNode::Comment(_) => continue,
+                _ => None,
+            };
+            let Some(slot) = slot else {
+                default_content = true;
+                continue;
+            };
+            // Reported when checking the child
+            let Some(slot) = static_value(slot) else { continue };
+            let span = node.span();
+            match slots.entry(slot) {
+                Entry::Occupied(_) => {
+                    self.errors.push(diagnostics::slot_attribute_duplicate(slot, name, span));
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(span);
+                }
+            }
+        }
+        if let (true, Some(span)) = (default_content, slots.get("default")) {
+            self.errors.push(diagnostics::slot_default_duplicate(*span));
+        }
+    }
+
+    /// Collect the deprecated `on:` directives of DOM elements and `<slot>`s,
+    /// reported in runes mode.
+    fn check_legacy_element(&mut self, element: &Element<'a>, attributes: &[ElementAttribute<'a>]) {
+        match element {
+            Element::SlotElement(slot) => {
+                self.legacy_constructs.push(diagnostics::slot_element_deprecated(slot.span));
+            }
+            Element::SvelteComponent(component) => {
+                self.legacy_constructs
+                    .push(diagnostics::svelte_component_deprecated(component.span));
+            }
+            Element::SvelteSelf(element) => {
+                self.legacy_constructs.push(diagnostics::svelte_self_deprecated(element.span));
+            }
+            Element::RegularElement(_)
+            | Element::SvelteElement(_)
+            | Element::SvelteBody(_)
+            | Element::SvelteDocument(_)
+            | Element::SvelteWindow(_) => {
+                for attribute in attributes {
+                    if let Some(DirectiveAttribute::OnDirective(directive)) =
+                        attribute.as_directive_attribute()
+                    {
+                        self.legacy_constructs.push(diagnostics::event_directive_deprecated(
+                            &directive.name,
+                            directive.span,
+                        ));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Check the `on:` directives of `element`: modifiers that can't be
+    /// combined, modifiers that do nothing on component events, where only
+    /// `once` applies, and the same handler attached twice to an event.
+    fn check_event_directives(
+        &mut self,
+        element: &Element<'a>,
+        attributes: &[ElementAttribute<'a>],
+    ) {
+        let is_component = matches!(
+            element,
+            Element::Component(_) | Element::SvelteComponent(_) | Element::SvelteSelf(_)
+        );
+        let source_text = self.source_text;
+        let mut handlers: Vec<(&OnDirective<'a>, Vec<&str>, &str)> = vec![];
+        for attribute in attributes {
+            let Some(DirectiveAttribute::OnDirective(directive)) =
+                attribute.as_directive_attribute()
+            else {
+                continue;
+            };
+            let modifiers = modifier_spans(directive);
+            if is_component {
+                for (modifier, span) in &modifiers {
+                    if *modifier != "once" {
+                        self.errors.push(diagnostics::component_event_modifier(modifier, *span));
+                    }
+                }
+            } else {
+                let find = |name| modifiers.iter().find(|(modifier, _)| *modifier == name);
+                if let Some(passive) = find("passive") {
+                    for other in ["preventDefault", "nonpassive"] {
+                        if let Some(other) = find(other) {
+                            self.errors
+                                .push(diagnostics::event_modifiers_conflict(*passive, *other));
+                        }
+                    }
+                }
+            }
+
+            let mut names = modifiers.iter().map(|(modifier, _)| *modifier).collect::<Vec<_>>();
+            names.sort_unstable();
+            names.dedup();
+            let handler = directive.expression.as_ref().map_or("", |expression| {
+                let span = expression.span();
+                &source_text[span.start as usize..span.end as usize]
+            });
+            let duplicate = handlers.iter().find(|(other, other_names, other_handler)| {
+                other.name == directive.name && *other_names == names && *other_handler == handler
+            });
+            if let Some((other, ..)) = duplicate {
+                self.errors.push(diagnostics::event_handler_duplicate(
+                    &directive.name,
+                    other.span,
+                    directive.span,
+                ));
+            }
+            handlers.push((directive, names, handler));
+        }
+    }
+
+    fn declare_let_directive(&mut self, directive: &LetDirective<'a>, scope_id: ScopeId) {
+        let flags = SymbolFlags::BlockScopedVariable | SymbolFlags::ConstVariable;
+        let Some(expression) = &directive.expression else {
+            #[allow(clippy::cast_possible_truncation)]
+            let start = directive.span.start + "let:".len() as u32;
+            #[allow(clippy::cast_possible_truncation)]
+            let span = Span::new(start, start + directive.name.len() as u32);
+            self.declare(span, directive.name.clone(), flags, BindingKind::Template, scope_id);
+            return;
+        };
+        let mut identifiers = vec![];
+        let mut defaults = vec![];
+        match expression {
+            LetDirectiveExpression::Identifier(ident) => identifiers.push(ident),
+            LetDirectiveExpression::ArrayExpression(array) => {
+                collect_array_let_identifiers(array, &mut identifiers, &mut defaults);
+            }
+            LetDirectiveExpression::ObjectExpression(object) => {
+                collect_object_let_identifiers(object, &mut identifiers, &mut defaults);
+            }
+        }
+        // Defaults are evaluated in the scope the slot props are received in
+        for default in defaults {
+            self.build_expression(default);
+        }
+        for ident in identifiers {
+            self.declare(ident.span, ident.name.clone(), flags, BindingKind::Template, scope_id);
+        }
+    }
+
+    fn build_attribute_value(&mut self, value: &AttributeValue<'a>) {
+        for part in &value.sequence {
+            if let AttributeSequenceValue::ExpressionTag(tag) = part {
+                self.build_expression_tag(tag.node_id, &tag.expression);
+            }
+        }
+    }
+
+    fn build_attribute(&mut self, attribute: &ElementAttribute<'a>) {
+        let directive = match attribute {
+            ElementAttribute::Attribute(attribute) => {
+                if let Some(value) = &attribute.value {
+                    self.build_attribute_value(value);
+                }
+                return;
+            }
+            ElementAttribute::SpreadAttribute(attribute) => {
+                self.build_expression_tag(attribute.node_id, &attribute.expression);
+                return;
+            }
+            ElementAttribute::DirectiveAttribute(directive) => directive,
+        };
+        let (span, name, expression) = match directive {
+            DirectiveAttribute::AnimateDirective(directive) => {
+                (directive.span, &directive.name, &directive.expression)
+            }
+            DirectiveAttribute::TransitionDirective(directive) => {
+                (directive.span, &directive.name, &directive.expression)
+            }
+            DirectiveAttribute::UseDirective(directive) => {
+                (directive.span, &directive.name, &directive.expression)
+            }
+            DirectiveAttribute::BindDirective(directive) => {
+                if let Some(block_id) = self.current_block_id {
+                    self.metadata.bind_parent_blocks.insert(directive.node_id, block_id);
+                }
+                let object = match &directive.expression {
+                    BindDirectiveExpression::Identifier(ident) => {
+                        self.record_dependencies(ident.span, |builder| {
+                            let reference_id = builder.add_reference(
+                                ident.span,
+                                ident.name.clone(),
+                                ReferenceFlag::ReadWrite,
+                            );
+                            ident.reference_id.set(Some(reference_id));
+                            builder.bind_references.insert(reference_id);
+                        });
+                        Some(ident)
+                    }
+                    BindDirectiveExpression::MemberExpression(member) => {
+                        self.record_dependencies(member.span(), |builder| {
+                            builder.visit_member_expression(member);
+                        });
+                        self.add_member_mutation(member);
+                        root_identifier(member.object())
+                    }
+                };
+                if directive.name == "group" {
+                    if let Some(reference_id) = object.and_then(|ident| ident.reference_id.get()) {
+                        self.metadata.binding_groups.insert(directive.node_id, reference_id);
+                    }
+                }
+                return;
+            }
+            DirectiveAttribute::ClassDirective(directive) => {
+                self.build_expression(&directive.expression);
+                return;
+            }
+            DirectiveAttribute::OnDirective(directive) => {
+                if let Some(expression) = &directive.expression {
+                    self.build_expression(expression);
+                }
+                return;
+            }
+            DirectiveAttribute::StyleDirective(directive) => {
+                // `style:color` is short for `style:color={color}`
+                let dynamic = directive.value.as_ref().map_or(true, |value| {
+                    value.sequence.iter().any(|part| {
+                        matches!(part, AttributeSequenceValue::ExpressionTag(tag) if !tag.expression.is_literal())
+                    })
+                });
+                if dynamic {
+                    self.metadata.dynamic_style_directives.insert(directive.node_id);
+                }
+                if let Some(value) = &directive.value {
+                    self.build_attribute_value(value);
+                }
+                return;
+            }
+            DirectiveAttribute::LetDirective(_) => return,
+        };
+
+        // The directive refers to the function named after the colon
+        let source = &self.source_text[span.start as usize..span.end as usize];
+        if let Some(colon) = source.find(':') {
+            #[allow(clippy::cast_possible_truncation)]
+            self.add_template_reference(span.start + colon as u32 + 1, name.as_str());
+        }
+        if let Some(expression) = expression {
+            self.build_expression(expression);
+        }
+    }
+
+    fn build_block(&mut self, block: &Block<'a>) {
+        self.template_nodes.block_spans.push(block.span());
+        let block_id = self.template_nodes.block_parents.push(self.current_block_id);
+        let parent_block_id = mem::replace(&mut self.current_block_id, Some(block_id));
+        let flags = SymbolFlags::BlockScopedVariable | SymbolFlags::ConstVariable;
+        match block {
+            Block::IfBlock(block) => {
+                self.build_expression(&block.test);
+                self.build_fragment(&block.consequent, true);
+                if let Some(alternate) = &block.alternate {
+                    self.build_fragment(alternate, true);
+                }
+            }
+            Block::EachBlock(block) => {
+                self.build_expression(&block.expression);
+                let source = root_identifier(&block.expression)
+                    .and_then(|ident| ident.reference_id.get())
+                    .map_or(EachSource::Expression, EachSource::Reference);
+                let scope_id = self.enter_child_scope(ScopeFlags::empty());
+                self.declare_pattern(&block.context, flags, BindingKind::Each);
+                block.context.bound_names(&mut |ident| {
+                    if let Some(symbol_id) = ident.symbol_id.get() {
+                        self.symbols.each_sources.insert(symbol_id, source);
+                    }
+                });
+                if let Some(index) = &block.index {
+                    let symbol_id = self.declare(
+                        index.span,
+                        index.name.clone(),
+                        flags,
+                        BindingKind::Each,
+                        scope_id,
+                    );
+                    if block.key.is_some() {
+                        self.keyed_each_indexes.push((block_id, symbol_id));
+                    }
+                }
+                let each_flags = self.each_block_flags(block);
+                self.template_nodes.each_flags.insert(block_id, each_flags);
+                if let Some(key) = &block.key {
+                    self.build_expression(key);
+                }
+                self.build_fragment(&block.body, true);
+                self.leave_current_scope();
+                if let Some(fallback) = &block.fallback {
+                    self.build_fragment(fallback, true);
+                }
+            }
+            Block::AwaitBlock(block) => {
+                self.build_expression(&block.expression);
+                if let Some(pending) = &block.pending {
+                    self.build_fragment(pending, false);
+                }
+                for (pattern, fragment) in
+                    [(&block.value, &block.then), (&block.error, &block.catch)]
+                {
+                    let Some(fragment) = fragment else { continue };
+                    self.enter_child_scope(ScopeFlags::empty());
+                    if let Some(pattern) = pattern {
+                        self.declare_pattern(pattern, flags, BindingKind::Template);
+                    }
+                    self.build_fragment(fragment, true);
+                    self.leave_current_scope();
+                }
+            }
+            Block::KeyBlock(block) => {
+                self.build_expression(&block.expression);
+                self.build_fragment(&block.fragment, false);
+            }
+            Block::SnippetBlock(block) => {
+                let name = &block.expression;
+                let fragment_scope_id = self.current_scope_id;
+                let symbol_id = self.declare(
+                    name.span,
+                    name.name.clone(),
+                    flags,
+                    BindingKind::Snippet,
+                    fragment_scope_id,
+                );
+                let scope_id = self.enter_child_scope(ScopeFlags::Function | ScopeFlags::Arrow);
+                self.snippets.push(Snippet {
+                    span: block.span,
+                    symbol_id,
+                    scope_id,
+                    is_prop: self.component_scopes.contains(&fragment_scope_id)
+                        || self.boundary_scopes.contains(&fragment_scope_id),
+                    hoistable: false,
+                });
+                for parameter in &block.parameters {
+                    self.declare_pattern(
+                        parameter,
+                        SymbolFlags::FunctionScopedVariable,
+                        BindingKind::Snippet,
+                    );
+                }
+                self.build_fragment(&block.body, true);
+                self.leave_current_scope();
+            }
+        }
+        self.current_block_id = parent_block_id;
+    }
+}
+
+/// The attribute of `attributes` named `name`.
+fn find_attribute<'b, 'a>(
+    attributes: &'b [ElementAttribute<'a>],
+    name: &str,
+) -> Option<&'b Attribute<'a>> {
+    attributes.iter().find_map(|attribute| match attribute {
+        ElementAttribute::Attribute(attribute) if attribute.name == name => Some(attribute),
+        _ => None,
+    })
+}
+
+/// The value of `attribute` if it's text, `None` if it's an expression or
+/// has no value.
+fn static_value<'b>(attribute: &'b Attribute) -> Option<&'b str> {
+    match attribute.value.as_ref()?.sequence.as_slice() {
+        [AttributeSequenceValue::Text(text)] => Some(text.data.as_str()),
+        _ => None,
+    }
+}
+
+/// The modifiers of `directive` with their spans, the whole directive's
+/// if it has no source.
+#[allow(clippy::cast_possible_truncation)]
+fn modifier_spans<'d>(directive: &'d OnDirective) -> Vec<(&'d str, Span)> {
+    let Some(modifiers) = directive.parts.modifiers else {
+        return directive
+            .modifiers
+            .iter()
+            .map(|modifier| (modifier.as_str(), directive.span))
+            .collect();
+    };
+    let mut start = modifiers.start + 1;
+    directive
+        .modifiers
+        .iter()
+        .map(|modifier| {
+            let span = Span::new(start, start + modifier.len() as u32);
+            start = span.end + 1;
+            (modifier.as_str(), span)
+        })
+        .collect()
+}
+
+/// Whether `attribute` is a `javascript:` URL, judging by its leading text,
+/// so `href="javascript:{code}"` is one too.
+fn is_javascript_url(attribute: &Attribute) -> bool {
+    let Some(value) = &attribute.value else { return false };
+    let Some(AttributeSequenceValue::Text(text)) = value.sequence.first() else { return false };
+    let url = text.data.trim_start();
+    url.get(.."javascript:".len()).is_some_and(|scheme| scheme.eq_ignore_ascii_case("javascript:"))
+}
+
+/// The names of the props passed by the `attributes` of a component.
+fn component_props<'a>(attributes: &[ElementAttribute<'a>]) -> Vec<Atom<'a>> {
+    attributes
+        .iter()
+        .filter_map(|attribute| match attribute {
+            ElementAttribute::Attribute(attribute) => Some(attribute.name.clone()),
+            ElementAttribute::DirectiveAttribute(DirectiveAttribute::BindDirective(directive)) => {
+                Some(directive.name.clone())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// The identifier a member access chain starts from, `a` in `a.b[c].d`.
+fn root_identifier<'b, 'a>(expression: &'b Expression<'a>) -> Option<&'b IdentifierReference<'a>> {
+    match expression.get_inner_expression() {
+        Expression::Identifier(ident) => Some(ident),
+        expression => root_identifier(expression.as_member_expression()?.object()),
+    }
+}
+
+/// Identifiers bound by a destructuring `let:` directive, e.g. `a` and `b` in
+/// `let:item={{ a, b }}`, and the default values of the destructure.
+fn collect_let_identifiers<'b, 'a>(
+    expression: &'b Expression<'a>,
+    identifiers: &mut Vec<&'b IdentifierReference<'a>>,
+    defaults: &mut Vec<&'b Expression<'a>>,
+) {
+    match expression {
+        Expression::Identifier(ident) => identifiers.push(ident),
+        Expression::ArrayExpression(array) => {
+            collect_array_let_identifiers(array, identifiers, defaults);
+        }
+        Expression::ObjectExpression(object) => {
+            collect_object_let_identifiers(object, identifiers, defaults);
+        }
+        // `[a = 1]`
+        Expression::AssignmentExpression(assignment)
+            if assignment.operator == AssignmentOperator::Assign =>
+        {
+            if let Some(SimpleAssignmentTarget::AssignmentTargetIdentifier(ident)) =
+                assignment.left.as_simple_assignment_target()
+            {
+                identifiers.push(ident);
+                defaults.push(&assignment.right);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn collect_array_let_identifiers<'b, 'a>(
+    array: &'b ArrayExpression<'a>,
+    identifiers: &mut Vec<&'b IdentifierReference<'a>>,
+    defaults: &mut Vec<&'b Expression<'a>>,
+) {
+    for element in &array.elements {
+        match element {
+            ArrayExpressionElement::SpreadElement(spread) => {
+                collect_let_identifiers(&spread.argument, identifiers, defaults);
+            }
+            element => {
+                if let Some(expression) = element.as_expression() {
+                    collect_let_identifiers(expression, identifiers, defaults);
+                }
+            }
+        }
+    }
+}
+
+fn collect_object_let_identifiers<'b, 'a>(
+    object: &'b ObjectExpression<'a>,
+    identifiers: &mut Vec<&'b IdentifierReference<'a>>,
+    defaults: &mut Vec<&'b Expression<'a>>,
+) {
+    for property in &object.properties {
+        match property {
+            ObjectPropertyKind::ObjectProperty(property) => {
+                collect_let_identifiers(&property.value, identifiers, defaults);
+            }
+            ObjectPropertyKind::SpreadProperty(spread) => {
+                collect_let_identifiers(&spread.argument, identifiers, defaults);
+            }
+        }
+    }
+}
+
+impl<'a> Visit<'a> for SemanticBuilder<'a> {
+    fn enter_scope(&mut self, flags: ScopeFlags) {
+        self.enter_child_scope(flags);
+    }
+
+    fn leave_scope(&mut self) {
+        self.leave_current_scope();
+    }
+
+    fn visit_program(&mut self, program: &Program<'a>) {
+        self.visit_statements(&program.body);
+    }
+
+    fn visit_binding_identifier(&mut self, ident: &BindingIdentifier<'a>) {
+        // Function and class declarations are declared before their scope is entered
+        if ident.symbol_id.get().is_none() {
+            self.declare_binding_identifier(ident);
+        }
+    }
+
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        if matches!(ident.name.as_str(), "$$props" | "$$restProps") {
+            self.legacy_declarations
+                .push((diagnostics::legacy_props_invalid(&ident.name, ident.span), &[Rune::Props]));
+        }
+        let reference_id =
+            self.add_reference(ident.span, ident.name.clone(), self.current_reference_flag);
+        ident.reference_id.set(Some(reference_id));
+    }
+
+    fn visit_expression(&mut self, expr: &Expression<'a>) {
+        let prev_flag = mem::replace(&mut self.current_reference_flag, ReferenceFlag::Read);
+        walk::walk_expression(self, expr);
+        self.current_reference_flag = prev_flag;
+    }
+
+    fn visit_member_expression(&mut self, expr: &MemberExpression<'a>) {
+        let prev_flag = mem::replace(&mut self.current_reference_flag, ReferenceFlag::Read);
+        walk::walk_member_expression(self, expr);
+        self.current_reference_flag = prev_flag;
+    }
+
+    fn visit_assignment_expression(&mut self, expr: &AssignmentExpression<'a>) {
+        let flag = if expr.operator == AssignmentOperator::Assign {
+            ReferenceFlag::Write
+        } else {
+            ReferenceFlag::ReadWrite
+        };
+        let prev_flag = mem::replace(&mut self.current_reference_flag, flag);
+        self.visit_assignment_target(&expr.left);
+        self.current_reference_flag = prev_flag;
+        if let Some(member) =
+            expr.left.as_simple_assignment_target().and_then(|target| target.as_member_expression())
+        {
+            self.add_member_mutation(member);
+        }
+        self.visit_expression(&expr.right);
+    }
+
+    fn visit_update_expression(&mut self, expr: &UpdateExpression<'a>) {
+        let prev_flag = mem::replace(&mut self.current_reference_flag, ReferenceFlag::ReadWrite);
+        self.visit_simple_assignment_target(&expr.argument);
+        self.current_reference_flag = prev_flag;
+        if let Some(member) = expr.argument.as_member_expression() {
+            self.add_member_mutation(member);
+        }
+    }
+
+    fn visit_call_expression(&mut self, expr: &CallExpression<'a>) {
+        let rune = Rune::from_call(expr);
+        if let Some(rune) = rune {
+            if rune == Rune::Bindable && expr.arguments.len() > 1 {
+                self.errors.push(diagnostics::rune_invalid_arguments_length(
+                    rune.as_str(),
+                    "zero or one arguments",
+                    expr.span,
+                ));
+            }
+            self.rune_calls.push((expr.callee.span(), rune));
+            if self.explicit_runes.is_none() {
+                self.runes = true;
+            }
+        }
+        let start = self.symbols.references.len();
+        walk::walk_call_expression(self, expr);
+        if let Some(rune @ (Rune::Effect | Rune::EffectPre)) = rune {
+            let references = start..self.symbols.references.len();
+            let scope_id = self.current_scope_id;
+            self.effects.push(Effect { rune: Some(rune), scope_id, references });
+        }
+    }
+
+    fn visit_assignment_target_property_identifier(
+        &mut self,
+        ident: &AssignmentTargetPropertyIdentifier<'a>,
+    ) {
+        self.visit_identifier_reference(&ident.binding);
+        if let Some(init) = &ident.init {
+            self.visit_expression(init);
+        }
+    }
+
+    fn visit_for_statement_left(&mut self, left: &ForStatementLeft<'a>) {
+        let prev_flag = mem::replace(&mut self.current_reference_flag, ReferenceFlag::Write);
+        walk::walk_for_statement_left(self, left);
+        self.current_reference_flag = prev_flag;
+    }
+
+    fn visit_variable_declaration(&mut self, decl: &VariableDeclaration<'a>) {
+        let flags = match decl.kind {
+            VariableDeclarationKind::Var => SymbolFlags::FunctionScopedVariable,
+            VariableDeclarationKind::Let => SymbolFlags::BlockScopedVariable,
+            VariableDeclarationKind::Const => {
+                SymbolFlags::BlockScopedVariable | SymbolFlags::ConstVariable
+            }
+        };
+        let prev_flags = mem::replace(&mut self.current_symbol_flags, flags);
+        walk::walk_variable_declaration(self, decl);
+        self.current_symbol_flags = prev_flags;
+    }
+
+    fn visit_variable_declarator(&mut self, declarator: &VariableDeclarator<'a>) {
+        let rune = if self.is_instance_top_level() {
+            declarator.init.as_ref().and_then(Rune::from_expression)
+        } else {
+            None
+        };
+        let kind = match rune {
+            Some(Rune::State) => BindingKind::State,
+            Some(Rune::StateRaw) => BindingKind::RawState,
+            Some(Rune::Derived | Rune::DerivedBy) => BindingKind::Derived,
+            _ => self.current_binding_kind,
+        };
+        let prev_kind = mem::replace(&mut self.current_binding_kind, kind);
+        self.visit_binding_pattern(&declarator.id);
+        self.current_binding_kind = prev_kind;
+        if rune == Some(Rune::Props) {
+            self.set_props_binding_kinds(&declarator.id);
+        }
+        if let Some(init) = &declarator.init {
+            let start = self.symbols.references.len();
+            self.visit_expression(init);
+            let target = match &declarator.id.kind {
+                BindingPatternKind::BindingIdentifier(ident) if self.is_instance_top_level() => {
+                    ident.symbol_id.get()
+                }
+                _ => None,
+            };
+            let store_callee = match init.get_inner_expression() {
+                Expression::CallExpression(call) => call
+                    .callee
+                    .get_identifier_reference()
+                    .filter(|callee| callee.name == "derived")
+                    .and_then(|callee| callee.reference_id.get()),
+                _ => None,
+            };
+            if target.is_some()
+                && (matches!(rune, Some(Rune::Derived | Rune::DerivedBy)) || store_callee.is_some())
+            {
+                let references = start..self.symbols.references.len();
+                self.derivations.push(Derivation { target, store_callee, references });
+            }
+            if let BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind {
+                if let Some(symbol_id) = ident.symbol_id.get() {
+                    self.record_initializer(symbol_id, init);
+                }
+            }
+        }
+    }
+
+    fn visit_function(&mut self, func: &Function<'a>, flags: Option<ScopeFlags>) {
+        let prev_flags = mem::replace(&mut self.current_symbol_flags, SymbolFlags::Function);
+        let prev_kind = mem::replace(&mut self.current_binding_kind, BindingKind::Normal);
+        if func.is_declaration() {
+            if let Some(id) = &func.id {
+                self.declare_binding_identifier(id);
+            }
+        }
+        walk::walk_function(self, func, flags);
+        self.current_symbol_flags = prev_flags;
+        self.current_binding_kind = prev_kind;
+    }
+
+    fn visit_formal_parameters(&mut self, params: &FormalParameters<'a>) {
+        let prev_flags =
+            mem::replace(&mut self.current_symbol_flags, SymbolFlags::FunctionScopedVariable);
+        let prev_kind = mem::replace(&mut self.current_binding_kind, BindingKind::Normal);
+        walk::walk_formal_parameters(self, params);
+        self.current_symbol_flags = prev_flags;
+        self.current_binding_kind = prev_kind;
+    }
+
+    fn visit_class(&mut self, class: &Class<'a>) {
+        let prev_flags = mem::replace(&mut self.current_symbol_flags, SymbolFlags::Class);
+        if class.r#type == ClassType::ClassDeclaration {
+            if let Some(id) = &class.id {
+                self.declare_binding_identifier(id);
+            }
+        }
+        walk::walk_class(self, class);
+        self.current_symbol_flags = prev_flags;
+    }
+
+    fn visit_catch_parameter(&mut self, param: &CatchParameter<'a>) {
+        let flags = SymbolFlags::CatchVariable | SymbolFlags::BlockScopedVariable;
+        let prev_flags = mem::replace(&mut self.current_symbol_flags, flags);
+        walk::walk_catch_parameter(self, param);
+        self.current_symbol_flags = prev_flags;
+    }
+
+    fn visit_import_declaration(&mut self, decl: &ImportDeclaration<'a>) {
+        let prev_flags = mem::replace(&mut self.current_symbol_flags, SymbolFlags::ImportBinding);
+        walk::walk_import_declaration(self, decl);
+        self.current_symbol_flags = prev_flags;
+        if decl.source.value == "svelte/store" {
+            decl.bound_names(&mut |ident| {
+                if let Some(symbol_id) = ident.symbol_id.get() {
+                    self.store_imports.insert(symbol_id);
+                }
+            });
+        }
+    }
+
+    fn visit_export_named_declaration(&mut self, decl: &ExportNamedDeclaration<'a>) {
+        walk::walk_export_named_declaration(self, decl);
+        let Some(declaration) = &decl.declaration else { return };
+        let is_prop = self.is_instance_top_level()
+            && matches!(
+                declaration,
+                Declaration::VariableDeclaration(decl) if decl.kind != VariableDeclarationKind::Const
+            );
+        if is_prop {
+            self.legacy_declarations
+                .push((diagnostics::legacy_export_invalid(decl.span), &[Rune::Props]));
+        }
+        declaration.bound_names(&mut |ident| {
+            if let Some(symbol_id) = ident.symbol_id.get() {
+                self.symbols.flags[symbol_id] |= SymbolFlags::Export;
+                if is_prop {
+                    self.symbols.kinds[symbol_id] = BindingKind::Prop;
+                }
+            }
+        });
+    }
+
+    fn visit_labeled_statement(&mut self, stmt: &LabeledStatement<'a>) {
+        if !(self.is_instance_top_level() && stmt.label.name == "$") {
+            walk::walk_labeled_statement(self, stmt);
+            return;
+        }
+        self.legacy_declarations.push((
+            diagnostics::legacy_reactive_statement_invalid(stmt.span),
+            &[Rune::Derived, Rune::DerivedBy, Rune::Effect, Rune::EffectPre],
+        ));
+        if let Statement::ExpressionStatement(stmt) = &stmt.body {
+            if let Expression::AssignmentExpression(expr) = &stmt.expression {
+                if let Some(SimpleAssignmentTarget::AssignmentTargetIdentifier(ident)) =
+                    expr.left.as_simple_assignment_target()
+                {
+                    self.reactive_assignments.push((ident.span, ident.name.clone()));
+                }
+            }
+        }
+        let start = self.symbols.references.len();
+        walk::walk_labeled_statement(self, stmt);
+        let references = start..self.symbols.references.len();
+        let scope_id = self.current_scope_id;
+        self.effects.push(Effect { rune: None, scope_id, references: references.clone() });
+        self.derivations.push(Derivation { target: None, store_callee: None, references });
+    }
+}
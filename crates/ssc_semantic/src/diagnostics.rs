@@ -0,0 +1,363 @@
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_span::Span;
+
+pub fn redeclaration(name: &str, first: Span, second: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error(format!("Identifier `{name}` has already been declared")).with_labels([
+        first.label(format!("`{name}` has already been declared here")),
+        second.label("It can not be redeclared here"),
+    ])
+}
+
+pub fn const_tag_invalid_placement(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error(
+        "`{@const}` must be the immediate child of `{#snippet}`, `{#if}`, `{:else if}`, `{:else}`, `{#each}`, `{:then}`, `{:catch}`, `<svelte:fragment>` or `<Component>`",
+    )
+    .with_label(span)
+}
+
+pub fn unused_const_tag(name: &str, span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::warn(format!("`{name}` is declared by `{{@const}}` but never used"))
+        .with_error_code("svelte", "unused_const_tag")
+        .with_label(span)
+}
+
+pub fn unused_snippet(name: &str, span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::warn(format!("`{{#snippet {name}}}` is declared but never rendered"))
+        .with_error_code("svelte", "unused_snippet")
+        .with_label(span)
+        .with_help("Render it with `{@render}`, pass it to a component, or remove it")
+}
+
+pub fn constant_assignment(
+    name: &str,
+    declaration: Span,
+    span: Span,
+    binding: bool,
+) -> OxcDiagnostic {
+    let (action, past) = if binding { ("bind", "bound") } else { ("assign", "assigned") };
+    OxcDiagnostic::error(format!("Cannot {action} to constant `{name}`")).with_labels([
+        declaration.label(format!("`{name}` is declared as a constant here")),
+        span.label(format!("It cannot be {past} here")),
+    ])
+}
+
+pub fn each_item_invalid_assignment(declaration: Span, span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error("Cannot reassign or bind to each block argument in runes mode")
+        .with_labels([
+            declaration.label("Declared by the each block here"),
+            span.label("Reassigned here"),
+        ])
+        .with_help("Use the array and index variables instead, e.g. `array[i] = value` rather than `entry = value`")
+}
+
+pub fn each_item_invalid_source(declaration: Span, span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error(
+        "Cannot reassign or bind to an each block argument whose collection is not a variable",
+    )
+    .with_labels([
+        declaration.label("Declared by an each block over an expression here"),
+        span.label("Reassigned here"),
+    ])
+}
+
+pub fn snippet_parameter_assignment(declaration: Span, span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error("Cannot reassign or bind to snippet parameter").with_labels([
+        declaration.label("Declared as a snippet parameter here"),
+        span.label("Reassigned here"),
+    ])
+}
+
+pub fn snippet_shadowing_prop(name: &str, span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error(format!("This snippet is shadowing the prop `{name}` with the same name"))
+        .with_label(span)
+}
+
+pub fn snippet_conflict(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error("Cannot use explicit children snippet property alongside child content")
+        .with_label(span)
+}
+
+pub fn const_tag_invalid_reference(name: &str, declaration: Span, span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error(format!(
+        "The `{{@const {name} = ...}}` declaration is not available in this snippet"
+    ))
+    .with_labels([
+        declaration.label("Declared in the children of the component here"),
+        span.label("Read here"),
+    ])
+    .with_help("The children of a component are a snippet of their own, declare it in this snippet instead")
+}
+
+pub fn render_tag_invalid_spread_argument(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error("Cannot use spread arguments in `{@render ...}` tags").with_label(span)
+}
+
+pub fn render_tag_invalid_call_expression(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error("Calling a snippet function using apply, bind or call is not allowed")
+        .with_label(span)
+}
+
+pub fn slot_element_invalid_name(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error("slot attribute must be a static value").with_label(span)
+}
+
+pub fn slot_element_invalid_name_default(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error("`default` is a reserved word — it cannot be used as a slot name")
+        .with_label(span)
+}
+
+pub fn slot_attribute_invalid(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error("slot attribute must be a static value").with_label(span)
+}
+
+pub fn script_misplaced(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::warn(
+        "`<script>` inside of markup is rendered as an element of the page, it isn't the script of the component and can't access its state",
+    )
+    .with_error_code("svelte", "script_misplaced")
+    .with_label(span)
+    .with_help("Move it to the top level of the component to make it the component's script")
+}
+
+pub fn style_misplaced(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::warn(
+        "`<style>` inside of markup is inserted in the page as is, its rules aren't scoped to the component",
+    )
+    .with_error_code("svelte", "style_misplaced")
+    .with_label(span)
+    .with_help("Move it to the top level of the component to scope its rules, and use `:global(...)` for the rules that must apply to the whole page")
+}
+
+/// A link opening a new tab without `rel="noopener"`, the fix adding it if
+/// the element has no `rel` to merge with.
+pub fn target_blank_without_rel(span: Span, fixable: bool) -> OxcDiagnostic {
+    let diagnostic = OxcDiagnostic::warn(
+        "Using `target=\"_blank\"` without `rel=\"noopener noreferrer\"` lets the opened page access `window.opener`",
+    )
+    .with_error_code("svelte", "target_blank_without_rel");
+    if fixable {
+        diagnostic.with_labels([
+            span.into(),
+            span.label("Replace with `target=\"_blank\" rel=\"noopener noreferrer\"`"),
+        ])
+    } else {
+        diagnostic.with_label(span).with_help("Add `noopener` to the `rel` attribute")
+    }
+}
+
+pub fn javascript_url(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::warn("`javascript:` URLs run their code with the privileges of the page")
+        .with_error_code("svelte", "javascript_url")
+        .with_label(span)
+        .with_help("Use an event attribute instead, e.g. `onclick={...}`")
+}
+
+pub fn srcdoc_unsandboxed(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::warn(
+        "A dynamic `srcdoc` renders its markup with the privileges of the page unless the `<iframe>` is sandboxed",
+    )
+    .with_error_code("svelte", "srcdoc_unsandboxed")
+    .with_labels([
+        span.into(),
+        Span::new(span.start, span.start).label("Replace with `sandbox `"),
+    ])
+}
+
+pub fn svelte_boundary_invalid_attribute(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error("Valid attributes on `<svelte:boundary>` are `onerror` and `failed`")
+        .with_label(span)
+}
+
+pub fn svelte_boundary_invalid_attribute_value(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error("Attribute value must be a non-string expression").with_label(span)
+}
+
+pub fn slot_attribute_invalid_placement(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error(
+        "Element with a slot='...' attribute must be a child of a component or a descendant of a custom element",
+    )
+    .with_label(span)
+}
+
+pub fn slot_attribute_duplicate(name: &str, component: &str, span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error(format!("Duplicate slot name '{name}' in <{component}>")).with_label(span)
+}
+
+pub fn slot_default_duplicate(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error("Found default slot content alongside an explicit slot=\"default\"")
+        .with_label(span)
+}
+
+pub fn store_invalid_scoped_subscription(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error(
+        "Cannot subscribe to stores that are not declared at the top level of the component",
+    )
+    .with_label(span)
+}
+
+pub fn store_invalid_value(name: &str, declaration: Span, subscription: Span) -> OxcDiagnostic {
+    OxcDiagnostic::warn(format!("`${name}` subscribes to `{name}`, which is not a store"))
+        .with_error_code("svelte", "store_invalid_value")
+        .with_labels([
+            declaration
+                .label(format!("`{name}` is declared with a value that has no `subscribe` method")),
+            subscription.label("Subscribed to here"),
+        ])
+}
+
+/// `steps` are the variables of the cycle, each with the variable it reads
+/// and where.
+pub fn reactive_declaration_cycle(steps: &[(&str, &str, Span)]) -> OxcDiagnostic {
+    let mut cycle = steps.iter().map(|(name, ..)| *name).collect::<Vec<_>>();
+    cycle.extend(steps.first().map(|(name, ..)| *name));
+    OxcDiagnostic::error(format!("Cyclical dependency detected: {}", cycle.join(" → ")))
+        .with_labels(
+            steps.iter().map(|(name, dependency, span)| {
+                span.label(format!("`{name}` reads `{dependency}`"))
+            }),
+        )
+}
+
+pub fn effect_self_invalidation(rune: &str, name: &str, read: Span, write: Span) -> OxcDiagnostic {
+    OxcDiagnostic::warn(format!(
+        "`{rune}` reads and writes `{name}`, so every run schedules another one"
+    ))
+    .with_error_code("svelte", "effect_self_invalidation")
+    .with_labels([
+        read.label(format!("`{name}` is read here")),
+        write.label(format!("`{name}` is written here")),
+    ])
+    .with_help(format!(
+        "Derive the value with `$derived` instead, or read it with `untrack(() => {name})`"
+    ))
+}
+
+pub fn reactive_statement_self_assignment(name: &str, read: Span, write: Span) -> OxcDiagnostic {
+    OxcDiagnostic::warn(format!(
+        "`$:` statement reads `{name}`, which it assigns, so it doesn't re-run when `{name}` changes"
+    ))
+    .with_error_code("svelte", "reactive_statement_self_assignment")
+    .with_labels([
+        read.label(format!("`{name}` is read here")),
+        write.label(format!("`{name}` is assigned here")),
+    ])
+}
+
+pub fn legacy_reactive_statement_invalid(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error("`$:` is not allowed in runes mode, use `$derived` or `$effect` instead")
+        .with_label(span)
+}
+
+pub fn legacy_export_invalid(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error("Cannot use `export let` in runes mode — use `$props()` instead")
+        .with_label(span)
+}
+
+pub fn legacy_props_invalid(name: &str, span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error(format!("Cannot use `{name}` in runes mode")).with_label(span)
+}
+
+/// `diagnostic`, for a legacy declaration in a component put in runes mode by
+/// the `rune` call at `span`.
+pub fn runes_mode_inferred(diagnostic: OxcDiagnostic, rune: &str, span: Span) -> OxcDiagnostic {
+    let mut labels = diagnostic.labels.clone().unwrap_or_default();
+    labels.push(span.label(format!("Runes mode is inferred from this `{rune}` call")));
+    diagnostic.with_labels(labels).with_help(
+        "A component calling runes is compiled in runes mode, where legacy declarations are not allowed. Migrate them to runes, or add `<svelte:options runes={false} />` to keep the component in legacy mode",
+    )
+}
+
+/// `diagnostic`, for a legacy declaration in a component set to runes mode by
+/// the `<svelte:options>` at `span`, or the compile options.
+pub fn runes_mode_explicit(diagnostic: OxcDiagnostic, span: Option<Span>) -> OxcDiagnostic {
+    let Some(span) = span else {
+        return diagnostic.with_help("Runes mode is enabled by the `runes` compile option");
+    };
+    let mut labels = diagnostic.labels.clone().unwrap_or_default();
+    labels.push(span.label("Runes mode is enabled here"));
+    diagnostic.with_labels(labels)
+}
+
+pub fn rune_invalid_usage(rune: &str, span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error(format!("Cannot use `{rune}` rune in non-runes mode")).with_label(span)
+}
+
+pub fn rune_invalid_arguments_length(rune: &str, args: &str, span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error(format!("`{rune}` must be called with {args}")).with_label(span)
+}
+
+pub fn bindable_invalid_location(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error("`$bindable()` can only be used inside a `$props()` declaration")
+        .with_label(span)
+        .with_help("Use it as the default value of a property, e.g. `let { value = $bindable() } = $props()`")
+}
+
+pub fn options_deprecated_in_runes(name: &str, span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::warn(format!(
+        "The `{name}` option has been deprecated. It will have no effect in runes mode"
+    ))
+    .with_error_code("svelte", "options_deprecated_in_runes")
+    .with_label(span)
+}
+
+pub fn event_directive_deprecated(name: &str, span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::warn(format!(
+        "Using `on:{name}` to listen to the {name} event is deprecated. Use the event attribute `on{name}` instead"
+    ))
+    .with_error_code("svelte", "event_directive_deprecated")
+    .with_label(span)
+}
+
+pub fn event_modifiers_conflict(first: (&str, Span), second: (&str, Span)) -> OxcDiagnostic {
+    OxcDiagnostic::error(format!(
+        "The `{}` and `{}` modifiers cannot be used together",
+        first.0, second.0
+    ))
+    .with_labels([first.1.into(), second.1.into()])
+}
+
+pub fn component_event_modifier(modifier: &str, span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::warn(format!(
+        "The `{modifier}` modifier has no effect on component events, only `once` does"
+    ))
+    .with_error_code("svelte", "component_event_modifier")
+    .with_label(span)
+    .with_help("Move the modifier to the `on:` directive of the element dispatching the event")
+}
+
+pub fn event_handler_duplicate(name: &str, first: Span, second: Span) -> OxcDiagnostic {
+    OxcDiagnostic::warn(format!(
+        "The same `{name}` handler is attached twice with the same modifiers"
+    ))
+    .with_error_code("svelte", "event_handler_duplicate")
+    .with_labels([
+        first.label("It is attached here"),
+        second.label("and again here, so it runs twice for each event"),
+    ])
+}
+
+pub fn slot_element_deprecated(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::warn(
+        "Using `<slot>` to render parent content is deprecated. Use `{@render ...}` tags instead",
+    )
+    .with_error_code("svelte", "slot_element_deprecated")
+    .with_label(span)
+}
+
+pub fn svelte_component_deprecated(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::warn(
+        "`<svelte:component>` is deprecated in runes mode — components are dynamic by default",
+    )
+    .with_error_code("svelte", "svelte_component_deprecated")
+    .with_label(span)
+    .with_help("Use `<Component />` with a variable holding the component instead")
+}
+
+pub fn svelte_self_deprecated(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::warn("`<svelte:self>` is deprecated — use self-imports instead")
+        .with_error_code("svelte", "svelte_self_deprecated")
+        .with_label(span)
+        .with_help(
+            "Import the component from its own file, e.g. `import Self from './Self.svelte'`",
+        )
+}
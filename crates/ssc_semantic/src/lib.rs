@@ -0,0 +1,1085 @@
+//! Semantic analysis of Svelte components
+//!
+//! Builds the scope tree and symbol table for the module script, the instance
+//! script and the template, and resolves every reference to its declaration.
+//!
+//! ## Cargo Features
+//! * `"serialize"` enables serializing the AST to JSON with the metadata of
+//!   its nodes, see [`Semantic::root_to_value`]
+
+mod binding;
+mod builder;
+mod diagnostics;
+pub mod metadata;
+pub mod reference;
+mod rune;
+pub mod scope;
+#[cfg(feature = "serialize")]
+mod serialize;
+mod snippet;
+pub mod symbol;
+pub mod template;
+
+use oxc_span::{Atom, Span};
+use oxc_syntax::symbol::SymbolId;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+pub use crate::{
+    binding::{BindingFlags, BindingKind},
+    builder::{SemanticBuilder, SemanticBuilderReturn},
+    metadata::NodeMetadata,
+    reference::{Reference, ReferenceFlag, ReferenceId},
+    rune::Rune,
+    scope::{ScopeFlags, ScopeId, ScopeTree},
+    snippet::Snippet,
+    symbol::{EachSource, SymbolTable},
+    template::{EachBlockFlags, TemplateNodes},
+};
+
+pub struct Semantic<'a> {
+    source_text: &'a str,
+    runes: bool,
+    scopes: ScopeTree<'a>,
+    symbols: SymbolTable<'a>,
+    template_nodes: TemplateNodes,
+    snippets: Vec<Snippet>,
+    /// Spans of the `{@render}` tags whose callee isn't a `{#snippet}`.
+    dynamic_render_tags: FxHashSet<Span>,
+    /// Reactive bindings read by each template expression, by span.
+    dependencies: FxHashMap<Span, Vec<SymbolId>>,
+    metadata: NodeMetadata,
+}
+
+impl<'a> Semantic<'a> {
+    pub fn source_text(&self) -> &'a str {
+        self.source_text
+    }
+
+    /// Whether the component is in runes mode, because of
+    /// `<svelte:options runes>` or because it calls a rune.
+    pub fn is_runes(&self) -> bool {
+        self.runes
+    }
+
+    pub fn scopes(&self) -> &ScopeTree<'a> {
+        &self.scopes
+    }
+
+    pub fn symbols(&self) -> &SymbolTable<'a> {
+        &self.symbols
+    }
+
+    /// The blocks and `<slot>` elements of the template, by id.
+    pub fn template_nodes(&self) -> &TemplateNodes {
+        &self.template_nodes
+    }
+
+    /// The flags of the template nodes, by span.
+    pub fn metadata(&self) -> &NodeMetadata {
+        &self.metadata
+    }
+
+    /// The snippets of the template, in source order.
+    pub fn snippets(&self) -> &[Snippet] {
+        &self.snippets
+    }
+
+    /// The snippet `symbol_id` is the name of, if any.
+    pub fn get_snippet(&self, symbol_id: SymbolId) -> Option<&Snippet> {
+        self.snippets.iter().find(|snippet| snippet.symbol_id == symbol_id)
+    }
+
+    /// Whether the `{@render}` tag at `span` can render different snippets
+    /// over time, because its callee isn't a `{#snippet}` of the template,
+    /// e.g. a prop. Other tags can call their snippet directly.
+    pub fn is_dynamic_render_tag(&self, span: Span) -> bool {
+        self.dynamic_render_tags.contains(&span)
+    }
+
+    /// Whether reading the binding can give different values over the
+    /// component's lifetime, e.g. state, props, or, outside of runes mode,
+    /// variables that are reassigned.
+    pub fn is_reactive(&self, symbol_id: SymbolId) -> bool {
+        let kind = self.symbols.get_kind(symbol_id);
+        kind.is_reactive()
+            || (!self.runes
+                && kind == BindingKind::Normal
+                && self.symbols.is_deeply_mutated(symbol_id))
+    }
+
+    /// Whether the binding is reassigned, mutated, and reactive.
+    pub fn binding_flags(&self, symbol_id: SymbolId) -> BindingFlags {
+        let mut flags = BindingFlags::empty();
+        flags.set(BindingFlags::Reassigned, self.symbols.is_mutated(symbol_id));
+        flags.set(BindingFlags::Mutated, self.symbols.is_deeply_mutated(symbol_id));
+        flags.set(BindingFlags::Reactive, self.is_reactive(symbol_id));
+        flags
+    }
+
+    /// The reactive bindings read by the template expression at `span`, e.g.
+    /// the expression of an `ExpressionTag`, an attribute, a directive or a
+    /// block, in order of first use.
+    ///
+    /// `None` if there's no template expression at `span`.
+    pub fn dependencies(&self, span: Span) -> Option<&[SymbolId]> {
+        self.dependencies.get(&span).map(Vec::as_slice)
+    }
+
+    /// Every template expression, by span, along with its dependencies.
+    pub fn expression_dependencies(&self) -> &FxHashMap<Span, Vec<SymbolId>> {
+        &self.dependencies
+    }
+
+    /// Names of the props a parent can `bind:` to: the props defaulting to
+    /// `$bindable()` in runes mode, and all props otherwise.
+    pub fn bindable_props(&self) -> impl Iterator<Item = &Atom<'a>> + '_ {
+        self.symbols.iter().filter_map(|symbol_id| {
+            let bindable = match self.symbols.get_kind(symbol_id) {
+                BindingKind::BindableProp => true,
+                BindingKind::Prop => !self.runes,
+                _ => false,
+            };
+            bindable.then(|| self.symbols.get_name(symbol_id))
+        })
+    }
+
+    /// The reference covering `offset`, if any.
+    pub fn reference_at(&self, offset: u32) -> Option<ReferenceId> {
+        self.symbols.references.iter_enumerated().find_map(|(reference_id, reference)| {
+            let span = reference.span();
+            (span.start <= offset && offset < span.end).then_some(reference_id)
+        })
+    }
+
+    /// The symbol declared or referenced at `offset`, if any.
+    pub fn symbol_at(&self, offset: u32) -> Option<SymbolId> {
+        let declared = self.symbols.iter().find(|symbol_id| {
+            let span = self.symbols.get_span(*symbol_id);
+            span.start <= offset && offset < span.end
+        });
+        declared.or_else(|| {
+            self.reference_at(offset)
+                .and_then(|reference_id| self.symbols.get_reference(reference_id).symbol_id())
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use ssc_ast::ast::{
+        Block, DirectiveAttribute, ElementAttribute, ExpressionTag, ExpressionTagFlags,
+        FragmentNode, RegularElement, RegularElementFlags, SvelteElement, Tag,
+    };
+    use ssc_parser::Parser;
+
+    use super::*;
+
+    fn with_semantic(source: &str, f: impl FnOnce(&Semantic, &[oxc_diagnostics::Error])) {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let ret = SemanticBuilder::new(source).build(&ret.root);
+        f(&ret.semantic, &ret.errors);
+    }
+
+    fn kind_of(semantic: &Semantic, name: &str) -> BindingKind {
+        let symbols = semantic.symbols();
+        let symbol_id = symbols.iter().find(|id| symbols.get_name(*id).as_str() == name).unwrap();
+        symbols.get_kind(symbol_id)
+    }
+
+    #[test]
+    fn binding_kinds() {
+        let source = r"<script>
+    let { a, b = $bindable(), ...rest } = $props();
+    let count = $state(0);
+    let items = $state.raw([]);
+    let double = $derived(count * 2);
+    function increment() { count += 1; }
+</script>
+{#each items as item, i}{item}{i}{/each}
+{#snippet row(x)}{x}{/snippet}
+{@render row(1)}";
+        with_semantic(source, |semantic, errors| {
+            assert!(errors.is_empty());
+            assert_eq!(kind_of(semantic, "a"), BindingKind::Prop);
+            assert_eq!(kind_of(semantic, "b"), BindingKind::BindableProp);
+            assert_eq!(kind_of(semantic, "rest"), BindingKind::RestProp);
+            assert_eq!(kind_of(semantic, "count"), BindingKind::State);
+            assert_eq!(kind_of(semantic, "items"), BindingKind::RawState);
+            assert_eq!(kind_of(semantic, "double"), BindingKind::Derived);
+            assert_eq!(kind_of(semantic, "increment"), BindingKind::Normal);
+            assert_eq!(kind_of(semantic, "item"), BindingKind::Each);
+            assert_eq!(kind_of(semantic, "i"), BindingKind::Each);
+            assert_eq!(kind_of(semantic, "row"), BindingKind::Snippet);
+
+            let symbols = semantic.symbols();
+            let count =
+                symbols.iter().find(|id| symbols.get_name(*id).as_str() == "count").unwrap();
+            assert_eq!(symbols.get_resolved_references(count).count(), 2);
+            assert!(symbols.is_mutated(count));
+        });
+    }
+
+    #[test]
+    fn binding_flags() {
+        let source = r"<script>
+    let count = 0;
+    let user = { name: '' };
+    let total = 0;
+    export let name;
+    count += 1;
+    user.name = 'a';
+</script>
+{count}{user.name}{total}{name}";
+        with_semantic(source, |semantic, errors| {
+            assert!(errors.is_empty());
+            let symbols = semantic.symbols();
+            let flags = |name: &str| {
+                let symbol_id =
+                    symbols.iter().find(|id| symbols.get_name(*id).as_str() == name).unwrap();
+                semantic.binding_flags(symbol_id)
+            };
+            assert_eq!(flags("count"), BindingFlags::all());
+            assert_eq!(flags("user"), BindingFlags::Mutated | BindingFlags::Reactive);
+            assert_eq!(flags("total"), BindingFlags::empty());
+            assert_eq!(flags("name"), BindingFlags::Reactive);
+        });
+    }
+
+    #[test]
+    fn resolve_template_references() {
+        let source = r"<script>
+    import Button from './Button.svelte';
+    export let name;
+    $: upper = name.toUpperCase();
+</script>
+<Button on:click={() => name = ''}>{upper}</Button>";
+        with_semantic(source, |semantic, errors| {
+            assert!(errors.is_empty());
+            assert_eq!(kind_of(semantic, "name"), BindingKind::Prop);
+            assert_eq!(kind_of(semantic, "upper"), BindingKind::LegacyReactive);
+
+            let offset = u32::try_from(source.find("<Button").unwrap() + 1).unwrap();
+            let button = semantic.symbol_at(offset).unwrap();
+            assert_eq!(semantic.symbols().get_name(button).as_str(), "Button");
+            assert_eq!(semantic.symbols().get_resolved_references(button).count(), 1);
+        });
+    }
+
+    #[test]
+    fn element_namespaces() {
+        use ssc_ast::ast::{RegularElement, RegularElementFlags};
+
+        let source = "<svg><circle /><foreignObject><p></p></foreignObject></svg><math><mi /></math><div></div>";
+        let allocator = Allocator::default();
+        let root = Parser::new(&allocator, source).parse().root;
+        let semantic = SemanticBuilder::new(source).build(&root).semantic;
+        let flags = root
+            .find_all::<RegularElement>()
+            .into_iter()
+            .map(|found| {
+                (
+                    found.node.name.as_str(),
+                    semantic.metadata().get_element_flags(found.node.node_id),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            flags,
+            [
+                ("svg", RegularElementFlags::Svg),
+                ("circle", RegularElementFlags::Svg),
+                ("foreignObject", RegularElementFlags::Svg),
+                ("p", RegularElementFlags::empty()),
+                ("math", RegularElementFlags::Mathml),
+                ("mi", RegularElementFlags::Mathml),
+                ("div", RegularElementFlags::empty()),
+            ]
+        );
+    }
+
+    #[test]
+    fn dynamic_style_directives() {
+        use ssc_ast::ast::{DirectiveAttribute, RegularElement};
+
+        let source = r#"<div style:color style:--gap="1px" style:font-size="{size}px" style:-webkit-mask={"none"} style:width|important={w}></div>"#;
+        let allocator = Allocator::default();
+        let root = Parser::new(&allocator, source).parse().root;
+        let semantic = SemanticBuilder::new(source).build(&root).semantic;
+        let div = root.find_all::<RegularElement>()[0].node;
+        let dynamic = div
+            .attributes
+            .iter()
+            .map(|attribute| match attribute.as_directive_attribute() {
+                Some(DirectiveAttribute::StyleDirective(style)) => {
+                    semantic.metadata().is_dynamic_style_directive(style.node_id)
+                }
+                _ => panic!("{attribute:?}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(dynamic, [true, false, true, false, true]);
+    }
+
+    #[test]
+    fn template_scopes() {
+        let source = r"<List let:item={{ name, tags: [first = name, ...others] }} title={name}>
+    {name}{first}{others}
+</List>
+{#snippet row({ id, label = id }, [x])}{label}{x}{/snippet}
+{name}{label}{@render row({ id: 1 }, [2])}";
+        with_semantic(source, |semantic, errors| {
+            assert!(errors.is_empty());
+            for name in ["name", "first", "others"] {
+                assert_eq!(kind_of(semantic, name), BindingKind::Template);
+            }
+            for name in ["id", "label", "x"] {
+                assert_eq!(kind_of(semantic, name), BindingKind::Snippet);
+            }
+
+            let symbols = semantic.symbols();
+            let resolved = |name: &str| {
+                let symbol_id =
+                    symbols.iter().find(|id| symbols.get_name(*id).as_str() == name).unwrap();
+                symbols.get_resolved_references(symbol_id).count()
+            };
+            assert_eq!(resolved("name"), 3);
+            assert_eq!(resolved("id"), 1);
+            assert_eq!(resolved("label"), 1);
+            let unresolved = symbols
+                .references
+                .iter()
+                .filter(|reference| reference.symbol_id().is_none())
+                .map(|reference| reference.name().as_str())
+                .collect::<Vec<_>>();
+            assert_eq!(unresolved, ["List", "name", "label"]);
+        });
+    }
+
+    #[test]
+    fn const_tags() {
+        let source = r"{#each items as item}{@const total = item.price * item.count}{total}{/each}
+<Card>{@const title = name.trim()}{title}</Card>
+{#if ok}{@const unused = 1}{/if}
+<div>{@const misplaced = 1}{misplaced}</div>";
+        with_semantic(source, |semantic, errors| {
+            let messages = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+            assert_eq!(messages.len(), 2, "{messages:?}");
+            assert!(messages[0].contains("immediate child"));
+            assert!(messages[1].contains("`unused`"));
+            assert_eq!(kind_of(semantic, "total"), BindingKind::Template);
+        });
+    }
+
+    #[test]
+    fn each_item_mutations() {
+        let source = r"<script>let items = [];</script>
+{#each items as item, i}<input bind:value={item.name}>{i}{/each}
+{#each items.filter(Boolean) as entry}<input bind:value={entry}>{/each}
+{#each items as row, j}<button on:click={() => j = 0}>{row}</button>{/each}
+{#snippet cell(value)}<input bind:value={value}>{/snippet}
+{@render cell(1)}";
+        with_semantic(source, |semantic, errors| {
+            let messages = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+            assert_eq!(messages.len(), 3, "{messages:?}");
+            assert!(messages[0].contains("collection is not a variable"));
+            assert!(messages[1].contains("constant `j`"));
+            assert!(messages[2].contains("snippet parameter"));
+            assert!(!semantic.is_runes());
+
+            let symbols = semantic.symbols();
+            let symbol = |name: &str| {
+                symbols.iter().find(|id| symbols.get_name(*id).as_str() == name).unwrap()
+            };
+            assert!(symbols.is_deeply_mutated(symbol("item")));
+            assert!(!symbols.is_mutated(symbol("item")));
+            assert!(matches!(
+                symbols.get_each_source(symbol("item")),
+                Some(EachSource::Reference(reference_id))
+                    if symbols.get_reference(reference_id).symbol_id() == Some(symbol("items"))
+            ));
+            assert_eq!(symbols.get_each_source(symbol("entry")), Some(EachSource::Expression));
+            assert_eq!(symbols.get_each_source(symbol("i")), None);
+        });
+
+        let source = r"<script>let items = $state([]);</script>
+{#each items as item}<input bind:value={item}>{/each}";
+        with_semantic(source, |semantic, errors| {
+            assert!(semantic.is_runes());
+            assert_eq!(errors.len(), 1);
+            assert!(errors[0].to_string().contains("runes mode"));
+            let labels = errors[0].labels().unwrap().collect::<Vec<_>>();
+            assert_eq!(labels[0].offset(), source.find("item}").unwrap());
+            assert_eq!(labels[1].offset(), source.rfind("item").unwrap());
+        });
+    }
+
+    #[test]
+    fn runes_mode() {
+        let legacy = r"<script>
+    export let a;
+    $: b = a * 2;
+</script>
+{$$props.c}";
+        with_semantic(legacy, |semantic, errors| {
+            assert!(!semantic.is_runes());
+            assert!(errors.is_empty(), "{errors:?}");
+        });
+        let source = format!("<svelte:options runes />{legacy}");
+        with_semantic(&source, |semantic, errors| {
+            assert!(semantic.is_runes());
+            let messages = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+            assert_eq!(messages.len(), 3, "{messages:?}");
+            assert!(messages[0].contains("`export let`"));
+            assert!(messages[1].contains("`$:`"));
+            assert!(messages[2].contains("`$$props`"));
+        });
+        with_semantic("<script>let a = $state(0); $: b = a;</script>", |semantic, errors| {
+            assert!(semantic.is_runes());
+            assert_eq!(errors.len(), 1);
+        });
+
+        let source = r"<svelte:options runes={false} />
+<script>
+    import { writable } from 'svelte/store';
+    const effect = writable(() => {});
+    let a = $state(0);
+    $effect();
+</script>";
+        with_semantic(source, |semantic, errors| {
+            assert!(!semantic.is_runes());
+            assert_eq!(errors.len(), 1, "{errors:?}");
+            assert_eq!(errors[0].to_string(), "Cannot use `$state` rune in non-runes mode");
+        });
+
+        let allocator = Allocator::default();
+        let source = "<script>let a = $state(0);</script>";
+        let ret = Parser::new(&allocator, source).parse();
+        let ret = SemanticBuilder::new(source).with_runes(Some(false)).build(&ret.root);
+        assert!(!ret.semantic.is_runes());
+        assert_eq!(ret.errors.len(), 1);
+    }
+
+    #[test]
+    fn runes_mode_conflicts() {
+        let source = "<script>let { a } = $props(); let b = $state(a); export let c;</script>";
+        with_semantic(source, |_, errors| {
+            assert_eq!(errors.len(), 1, "{errors:?}");
+            let labels = errors[0].labels.as_ref().unwrap();
+            assert_eq!(labels.len(), 2);
+            assert_eq!(&source[labels[0].offset()..][..12], "export let c");
+            assert_eq!(&source[labels[1].offset()..][..labels[1].len()], "$props");
+            assert_eq!(labels[1].label(), Some("Runes mode is inferred from this `$props` call"));
+            assert!(errors[0].help.is_some());
+        });
+
+        let source = "<script>let a = $state(0); $: b = a; const c = $derived(a);</script>";
+        with_semantic(source, |_, errors| {
+            assert_eq!(errors.len(), 1, "{errors:?}");
+            let labels = errors[0].labels.as_ref().unwrap();
+            assert_eq!(&source[labels[1].offset()..][..labels[1].len()], "$derived");
+        });
+
+        let source = "<svelte:options runes /><script>export let a;</script>";
+        with_semantic(source, |_, errors| {
+            assert_eq!(errors.len(), 1, "{errors:?}");
+            let labels = errors[0].labels.as_ref().unwrap();
+            assert_eq!(labels[1].offset(), 0);
+            assert_eq!(labels[1].label(), Some("Runes mode is enabled here"));
+        });
+    }
+
+    #[test]
+    fn legacy_options() {
+        let source = "<svelte:options accessors immutable={false} /><script>export let a;</script>";
+        with_semantic(source, |_, errors| assert!(errors.is_empty(), "{errors:?}"));
+        let source = "<svelte:options accessors immutable /><script>let a = $state(0);</script>";
+        with_semantic(source, |_, errors| {
+            assert_eq!(errors.len(), 2, "{errors:?}");
+            assert!(errors[0].to_string().contains("`accessors` option"));
+        });
+    }
+
+    #[test]
+    fn expression_dependencies() {
+        let source = r#"<script>
+    import { format } from './format';
+    let count = $state(0);
+    let { label } = $props();
+    const step = 1;
+</script>
+<p title="{label}: {count}">{format(count + step)}{label}{count}</p>
+<button onclick={() => count += step} class:active={count > 0}>{step}</button>
+{#each [label] as item}{item}{/each}"#;
+        with_semantic(source, |semantic, errors| {
+            assert!(errors.is_empty(), "{errors:?}");
+            let names = |expression: &str, nth: usize| {
+                let start = source.match_indices(expression).nth(nth).unwrap().0;
+                #[allow(clippy::cast_possible_truncation)]
+                let span = Span::new(start as u32, (start + expression.len()) as u32);
+                semantic
+                    .dependencies(span)
+                    .unwrap()
+                    .iter()
+                    .map(|symbol_id| semantic.symbols().get_name(*symbol_id).as_str())
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(names("format(count + step)", 0), ["count"]);
+            assert_eq!(names("label", 1), ["label"]);
+            assert_eq!(names("count", 1), ["count"]);
+            assert_eq!(names("() => count += step", 0), ["count"]);
+            assert_eq!(names("count > 0", 0), ["count"]);
+            assert_eq!(names("step", 3), [] as [&str; 0]);
+            assert_eq!(names("item", 1), ["item"]);
+            assert!(semantic.dependencies(Span::new(0, 1)).is_none());
+        });
+    }
+
+    #[test]
+    fn store_subscriptions() {
+        let source = r"<script>
+    import { writable } from 'svelte/store';
+    const count = writable(0);
+    const plain = { value: 1 };
+    $count += 1;
+    function f(local) { return $local; }
+</script>
+{$count}{$plain}{$unknown}";
+        with_semantic(source, |semantic, errors| {
+            let messages = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+            assert_eq!(messages.len(), 2, "{messages:?}");
+            assert!(messages[0].contains("top level"));
+            assert!(messages[1].contains("`plain`, which is not a store"));
+
+            let symbols = semantic.symbols();
+            let symbol = |name: &str| {
+                symbols.iter().find(|id| symbols.get_name(*id).as_str() == name).unwrap()
+            };
+            assert!(symbols.is_store(symbol("count")));
+            assert!(!symbols.is_store(symbol("plain")));
+            assert_eq!(kind_of(semantic, "$count"), BindingKind::StoreSub);
+            assert_eq!(symbols.get_resolved_references(symbol("$count")).count(), 2);
+            assert!(symbols.is_mutated(symbol("$count")));
+            assert!(!symbols.iter().any(|id| symbols.get_name(id).as_str() == "$unknown"));
+        });
+    }
+
+    #[test]
+    fn reactive_cycles() {
+        let source = r"<script>
+    import { derived } from 'svelte/store';
+    let count = 0;
+    $: a = b + count;
+    $: b = c * 2;
+    $: c = a;
+    $: count = count + 1;
+    const d = derived(e, (value) => value);
+    const e = derived(d, ($d) => $d);
+</script>";
+        with_semantic(source, |_, errors| {
+            let errors = errors
+                .iter()
+                .filter(|error| error.severity() == Some(oxc_diagnostics::Severity::Error))
+                .collect::<Vec<_>>();
+            let messages = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+            assert_eq!(
+                messages,
+                [
+                    "Cyclical dependency detected: a → b → c → a",
+                    "Cyclical dependency detected: d → e → d"
+                ]
+            );
+            let labels = errors[0].labels().unwrap().collect::<Vec<_>>();
+            assert_eq!(labels.len(), 3);
+            assert_eq!(labels[0].label(), Some("`a` reads `b`"));
+            assert_eq!(&source[labels[2].offset()..labels[2].offset() + labels[2].len()], "a");
+        });
+        let source = r"<script>
+    let a = $state(0);
+    let b = $derived(a + c);
+    let c = $derived.by(() => b * 2);
+    let d = $derived(a * 2);
+</script>";
+        with_semantic(source, |_, errors| {
+            assert_eq!(errors.len(), 1);
+            assert_eq!(errors[0].to_string(), "Cyclical dependency detected: b → c → b");
+        });
+    }
+
+    #[test]
+    fn effect_writes() {
+        let source = r"<script>
+    let count = $state(0);
+    let other = $state(0);
+    let plain = 0;
+    $effect(() => {
+        if (count < 10) count++;
+        other = count;
+        plain = plain + 1;
+        setTimeout(() => { other = other + 1; });
+    });
+</script>";
+        with_semantic(source, |_, errors| {
+            assert_eq!(errors.len(), 1, "{errors:?}");
+            assert!(errors[0].to_string().contains("`$effect` reads and writes `count`"));
+            let labels = errors[0].labels().unwrap().collect::<Vec<_>>();
+            assert_eq!(labels[0].offset(), source.find("count <").unwrap());
+            assert_eq!(labels[1].offset(), source.find("count++").unwrap());
+        });
+        let source = r"<script>
+    export let value;
+    let clamped;
+    $: clamped = Math.min(value, 10);
+    $: if (value > 10) value = 10;
+</script>";
+        with_semantic(source, |_, errors| {
+            assert_eq!(errors.len(), 1, "{errors:?}");
+            assert!(errors[0].to_string().contains("`$:` statement reads `value`"));
+        });
+    }
+
+    #[test]
+    fn bindable_props() {
+        let source = r"<script>
+    let { a = $bindable(), b = $bindable(1, 2), c, d: { e = $bindable() } } = $props();
+    let f = $bindable();
+</script>";
+        with_semantic(source, |semantic, errors| {
+            let messages = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+            assert_eq!(
+                messages,
+                [
+                    "`$bindable` must be called with zero or one arguments",
+                    "`$bindable()` can only be used inside a `$props()` declaration",
+                    "`$bindable()` can only be used inside a `$props()` declaration",
+                ]
+            );
+            let props = semantic.bindable_props().map(Atom::as_str).collect::<Vec<_>>();
+            assert_eq!(props, ["a", "b"]);
+        });
+        with_semantic("<script>export let a; let b;</script>", |semantic, _| {
+            let props = semantic.bindable_props().map(Atom::as_str).collect::<Vec<_>>();
+            assert_eq!(props, ["a"]);
+        });
+    }
+
+    #[test]
+    fn template_nodes() {
+        let source = r#"{#if a}<slot />{:else}{#each items as item}<input bind:group={item} /><slot name="x" />{/each}{/if}{#key a}{/key}"#;
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source).parse();
+        let root = ret.root;
+        let semantic = SemanticBuilder::new(source).build(&root).semantic;
+        let nodes = semantic.template_nodes();
+
+        let blocks = nodes.blocks().collect::<Vec<_>>();
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(nodes.get_block_parent(blocks[1]), Some(blocks[0]));
+        assert_eq!(nodes.get_block_parent(blocks[2]), None);
+        assert!(matches!(nodes.get_block(&root, blocks[1]), Some(Block::EachBlock(_))));
+        assert_eq!(nodes.get_block_id(nodes.get_block_span(blocks[2])), Some(blocks[2]));
+        assert_eq!(nodes.get_block_id(Span::new(1, 2)), None);
+
+        let slots = nodes.slots().collect::<Vec<_>>();
+        assert_eq!(slots.len(), 2);
+        assert_eq!(nodes.get_slot_parent(slots[0]), Some(blocks[0]));
+        assert_eq!(nodes.get_slot_parent(slots[1]), Some(blocks[1]));
+        let slot = nodes.get_slot(&root, slots[1]).unwrap();
+        assert_eq!(nodes.get_slot_id(slot.span), Some(slots[1]));
+
+        let input = root.find_all::<RegularElement>();
+        let Some(ElementAttribute::DirectiveAttribute(DirectiveAttribute::BindDirective(bind))) =
+            input[0].node.attributes.first()
+        else {
+            panic!("{:?}", input[0].node.attributes);
+        };
+        assert_eq!(semantic.metadata().get_bind_parent_block(bind.node_id), Some(blocks[1]));
+    }
+
+    #[test]
+    fn node_metadata() {
+        let source = r#"<script>let { a } = $props(); let b = 1; let group = $state([]);</script>
+<svg {...a}><circle /><foreignObject><p>{a.c()}{b}</p></foreignObject><svelte:element this="g" /></svg>
+<math><mi style:color style:--gap="1px" style:width="{b}px" style:height={"1px"} /></math>
+<input type="checkbox" bind:group={group[0]} />"#;
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source).parse();
+        let root = ret.root;
+        let semantic = SemanticBuilder::new(source).build(&root).semantic;
+        let metadata = semantic.metadata();
+
+        let flags = root
+            .find_all::<RegularElement>()
+            .into_iter()
+            .map(|found| (found.node.name.as_str(), metadata.get_element_flags(found.node.node_id)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            flags,
+            [
+                ("svg", RegularElementFlags::Svg | RegularElementFlags::Spread),
+                ("circle", RegularElementFlags::Svg),
+                ("foreignObject", RegularElementFlags::Svg),
+                ("p", RegularElementFlags::empty()),
+                ("math", RegularElementFlags::Mathml),
+                ("mi", RegularElementFlags::Mathml),
+                ("input", RegularElementFlags::empty()),
+            ]
+        );
+        let elements = root.find_all::<RegularElement>();
+        let attributes = |index: usize| elements[index].node.attributes.iter();
+        let svelte_element = &root.find_all::<SvelteElement>()[0];
+        assert!(metadata.get_svelte_element_flags(svelte_element.node.node_id).has_svg());
+
+        let tags = elements[3]
+            .node
+            .fragment
+            .find_all::<ExpressionTag>()
+            .into_iter()
+            .map(|found| metadata.get_expression_flags(found.node.node_id))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tags,
+            [
+                ExpressionTagFlags::Dynamic | ExpressionTagFlags::CallExpression,
+                ExpressionTagFlags::empty(),
+            ]
+        );
+        let Some(ElementAttribute::SpreadAttribute(spread)) = attributes(0).next() else {
+            panic!("{:?}", elements[0].node.attributes);
+        };
+        assert_eq!(metadata.get_expression_flags(spread.node_id), ExpressionTagFlags::Dynamic);
+
+        let styles = attributes(5)
+            .map(|attribute| match attribute.as_directive_attribute() {
+                Some(DirectiveAttribute::StyleDirective(style)) => {
+                    metadata.is_dynamic_style_directive(style.node_id)
+                }
+                _ => panic!("{attribute:?}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(styles, [true, false, true, false]);
+
+        let Some(DirectiveAttribute::BindDirective(bind)) =
+            attributes(6).find_map(ElementAttribute::as_directive_attribute)
+        else {
+            panic!("{:?}", elements[6].node.attributes);
+        };
+        let reference_id = metadata.get_binding_group(bind.node_id).unwrap();
+        assert_eq!(semantic.symbols().get_reference(reference_id).name().as_str(), "group");
+        assert_eq!(metadata.get_bind_parent_block(bind.node_id), None);
+    }
+
+    #[test]
+    fn node_metadata_of_nodes_with_the_same_span() {
+        // Like the nodes a transform builds
+        let source = "<script>let { a } = $props();</script>{a()}{1}";
+        let allocator = Allocator::default();
+        let mut root = Parser::new(&allocator, source).parse().root;
+        for node in root.fragment.nodes.iter_mut() {
+            if let FragmentNode::Tag(Tag::ExpressionTag(tag)) = node {
+                tag.span = Span::default();
+            }
+        }
+        let semantic = SemanticBuilder::new(source).build(&root).semantic;
+        let flags = root
+            .find_all::<ExpressionTag>()
+            .into_iter()
+            .map(|found| semantic.metadata().get_expression_flags(found.node.node_id))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            flags,
+            [
+                ExpressionTagFlags::Dynamic | ExpressionTagFlags::CallExpression,
+                ExpressionTagFlags::empty(),
+            ]
+        );
+    }
+
+    #[test]
+    fn redeclaration() {
+        with_semantic("<script>let a; const a = 1;</script>", |_, errors| {
+            assert_eq!(errors.len(), 1);
+        });
+        with_semantic("<script>var a; var a;</script>", |_, errors| {
+            assert!(errors.is_empty());
+        });
+    }
+
+    #[test]
+    fn snippets() {
+        let source = r"<script module>const base = 1;</script>
+<script>let count = $state(0);</script>
+{@render header()}{@render total()}{@render row(1)}
+{#snippet header()}<h1>{base}</h1>{@render title()}{/snippet}
+{#snippet title()}{base}{/snippet}
+{#snippet counter()}{count}{/snippet}
+{#snippet total()}{@render counter()}{/snippet}
+{#snippet row(x)}{x}{/snippet}
+<Table header={1}>
+    {@const sum = 1}
+    {#snippet header()}{sum}{/snippet}
+    {#snippet children()}{/snippet}
+    <p>{sum}</p>
+</Table>";
+        with_semantic(source, |semantic, errors| {
+            let messages = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+            assert_eq!(messages.len(), 3, "{messages:?}");
+            assert!(messages[0].contains("shadowing the prop `header`"));
+            assert!(messages[1].contains("explicit children snippet"));
+            assert!(messages[2].contains("`{@const sum = ...}`"));
+
+            let snippets = semantic
+                .snippets()
+                .iter()
+                .map(|snippet| {
+                    let name = semantic.symbols().get_name(snippet.symbol_id).as_str();
+                    (name, snippet.is_prop, snippet.hoistable)
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(
+                snippets,
+                [
+                    ("header", false, true),
+                    ("title", false, true),
+                    ("counter", false, false),
+                    ("total", false, false),
+                    ("row", false, true),
+                    ("header", true, false),
+                    ("children", true, false),
+                ]
+            );
+
+            // Rendered before its declaration
+            let symbols = semantic.symbols();
+            let header = semantic.snippets()[0].symbol_id;
+            assert_eq!(symbols.get_resolved_references(header).count(), 1);
+            assert_eq!(semantic.get_snippet(header), semantic.snippets().first());
+        });
+    }
+
+    #[test]
+    fn each_block_flags() {
+        let source = r"<script>let items = $state([]);</script>
+<ul>
+    {#each items as item, i (item.id)}<li animate:flip>{i}</li>{/each}
+</ul>
+{#each items as item, i (item)}{item}{/each}
+{#each items as item}{item}{/each}";
+        with_semantic(source, |semantic, errors| {
+            assert!(errors.is_empty(), "{errors:?}");
+            let nodes = semantic.template_nodes();
+            let flags =
+                nodes.blocks().filter_map(|id| nodes.get_each_flags(id)).collect::<Vec<_>>();
+            assert_eq!(
+                flags,
+                [
+                    EachBlockFlags::Keyed
+                        | EachBlockFlags::IndexReactive
+                        | EachBlockFlags::ItemReactive
+                        | EachBlockFlags::Controlled
+                        | EachBlockFlags::Animated,
+                    EachBlockFlags::Keyed,
+                    EachBlockFlags::ItemReactive,
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn render_tags() {
+        let source = r"<script>let { children, row } = $props();</script>
+{@render item(1)}
+{@render children?.()}
+{@render row.call(null)}
+{@render item(...[1])}
+{#snippet item(x)}{x}{/snippet}";
+        with_semantic(source, |semantic, errors| {
+            let messages = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+            assert_eq!(messages.len(), 2, "{messages:?}");
+            assert!(messages[0].contains("apply, bind or call"));
+            assert!(messages[1].contains("spread arguments"));
+
+            let dynamic = source
+                .lines()
+                .filter(|line| line.starts_with("{@render"))
+                .map(|line| {
+                    #[allow(clippy::cast_possible_truncation)]
+                    let start = source.find(line).unwrap() as u32;
+                    #[allow(clippy::cast_possible_truncation)]
+                    let span = Span::new(start, start + line.len() as u32);
+                    semantic.is_dynamic_render_tag(span)
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(dynamic, [false, true, true, false]);
+        });
+    }
+
+    #[test]
+    fn slots() {
+        let source = r#"<slot name="default"></slot><slot name={name}></slot><slot name="footer"></slot>
+<Card>
+    <h1 slot="title">a</h1>
+    <h2 slot="title">b</h2>
+    <p slot="default">c</p>
+    <p slot={name}>d</p>
+    e
+</Card>
+<div><p slot="x">f</p></div>
+<my-element><div><p slot="x">g</p></div></my-element>"#;
+        with_semantic(source, |_, errors| {
+            let messages = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+            let expected = [
+                "`default` is a reserved word",
+                "slot attribute must be a static value",
+                "Duplicate slot name 'title' in <Card>",
+                "Found default slot content",
+                "slot attribute must be a static value",
+                "must be a child of a component",
+            ];
+            assert_eq!(messages.len(), expected.len(), "{messages:?}");
+            for (message, expected) in messages.iter().zip(expected) {
+                assert!(message.contains(expected), "{message}");
+            }
+        });
+    }
+
+    #[test]
+    fn svelte_component_deprecated() {
+        let source = "<script>let { icon } = $props();</script><svelte:component this={icon} />";
+        with_semantic(source, |_, errors| {
+            assert_eq!(errors.len(), 1);
+            assert!(errors[0].to_string().contains("components are dynamic by default"));
+        });
+        with_semantic("<svelte:component this={icon} />", |_, errors| assert!(errors.is_empty()));
+    }
+
+    #[test]
+    fn unused_snippets() {
+        let source = r"<script module>export { shared };</script>
+{#snippet shared()}a{/snippet}
+{#snippet row(x)}{x}{/snippet}
+{#snippet tree(depth)}{#if depth}{@render tree(depth - 1)}{/if}{/snippet}
+{#snippet unused()}b{/snippet}
+<List>{#snippet item()}c{/snippet}</List>
+{#each [1] as x}{@render row(x)}{/each}";
+        with_semantic(source, |_, errors| {
+            let messages = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+            assert_eq!(messages.len(), 2, "{messages:?}");
+            assert!(messages[0].contains("`{#snippet tree}` is declared but never rendered"));
+            assert!(messages[1].contains("`{#snippet unused}`"));
+        });
+
+        let source = "<!-- svelte-ignore unused_snippet -->\n{#snippet unused()}b{/snippet}";
+        with_semantic(source, |_, errors| assert!(errors.is_empty(), "{errors:?}"));
+    }
+
+    #[test]
+    fn misplaced_sections() {
+        let source = r#"<div><script>let a = 1;</script></div>{#if a}<style>p { color: red; }</style>{/if}<div><script type="application/ld+json">{"a": 1}</script></div><svelte:head><script src="a.js"></script><style>body { margin: 0; }</style></svelte:head><svg><style>circle { fill: red; }</style></svg>"#;
+        with_semantic(source, |_, errors| {
+            let messages = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+            assert_eq!(messages.len(), 2, "{messages:?}");
+            assert!(messages[0].contains("`<script>` inside of markup"), "{}", messages[0]);
+            assert!(messages[1].contains("`<style>` inside of markup"), "{}", messages[1]);
+        });
+    }
+
+    #[test]
+    fn security_rules() {
+        let source = r#"<script>let { url, html, rel } = $props();</script>
+<a href="https://a.dev" target="_blank">a</a>
+<a href={url} target="_blank" rel="noopener">a</a>
+<a href={url} target="_blank" rel="external">a</a>
+<a href={url} target="_blank" {rel}>a</a>
+<a href=" JavaScript:void(0)">a</a>
+<form action="javascript:{html}"></form>
+<iframe title="a" srcdoc={html}></iframe>
+<iframe title="a" srcdoc={html} sandbox=""></iframe>
+<iframe title="a" srcdoc="<p>a</p>"></iframe>"#;
+        with_semantic(source, |_, errors| {
+            let messages = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+            let expected = [
+                "without `rel=\"noopener noreferrer\"`",
+                "without `rel=\"noopener noreferrer\"`",
+                "`javascript:` URLs",
+                "`javascript:` URLs",
+                "A dynamic `srcdoc`",
+            ];
+            assert_eq!(messages.len(), expected.len(), "{messages:?}");
+            for (message, expected) in messages.iter().zip(expected) {
+                assert!(message.contains(expected), "{message}");
+            }
+            let fix = |index: usize| errors[index].labels.as_ref().unwrap()[1].label();
+            assert_eq!(
+                fix(0),
+                Some("Replace with `target=\"_blank\" rel=\"noopener noreferrer\"`")
+            );
+            assert_eq!(errors[1].labels.as_ref().unwrap().len(), 1);
+            assert_eq!(fix(4), Some("Replace with `sandbox `"));
+        });
+    }
+
+    #[test]
+    fn event_directives() {
+        let source = r#"<script>import Child from './Child.svelte'; function a() {} function b() {}</script>
+<button on:click={a} on:click={b} on:click|once={a} on:click|once={a}>a</button>
+<div on:touchstart|passive|preventDefault={a} on:wheel|nonpassive|passive={b} on:scroll on:scroll></div>
+<Child on:close|once={a} on:close|self|preventDefault={b} />"#;
+        with_semantic(source, |_, errors| {
+            let messages = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+            let expected = [
+                "The same `click` handler is attached twice",
+                "The `passive` and `preventDefault` modifiers cannot be used together",
+                "The `passive` and `nonpassive` modifiers cannot be used together",
+                "The same `scroll` handler is attached twice",
+                "The `self` modifier has no effect on component events",
+                "The `preventDefault` modifier has no effect on component events",
+            ];
+            assert_eq!(messages.len(), expected.len(), "{messages:?}");
+            for (message, expected) in messages.iter().zip(expected) {
+                assert!(message.contains(expected), "{message}");
+            }
+            let span = |index: usize, label: usize| {
+                let label = &errors[index].labels.as_ref().unwrap()[label];
+                &source[label.offset()..label.offset() + label.len()]
+            };
+            assert_eq!(span(0, 0), "on:click|once={a}");
+            assert_eq!(span(1, 0), "passive");
+            assert_eq!(span(1, 1), "preventDefault");
+            assert_eq!(span(5, 0), "preventDefault");
+        });
+    }
+
+    #[test]
+    fn svelte_boundary() {
+        let source = "<svelte:boundary onerror={report}>{#snippet failed(error, reset)}<button onclick={reset}>{error}</button>{/snippet}<p>a</p></svelte:boundary>";
+        with_semantic(source, |_, errors| assert!(errors.is_empty(), "{errors:?}"));
+        let source =
+            "<svelte:boundary class=\"a\" failed=\"b\" {...props}><p>a</p></svelte:boundary>";
+        with_semantic(source, |_, errors| {
+            let messages = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+            let expected = [
+                "Valid attributes on `<svelte:boundary>`",
+                "must be a non-string expression",
+                "Valid attributes on `<svelte:boundary>`",
+            ];
+            assert_eq!(messages.len(), expected.len(), "{messages:?}");
+            for (message, expected) in messages.iter().zip(expected) {
+                assert!(message.contains(expected), "{message}");
+            }
+        });
+    }
+
+    #[test]
+    fn svelte_self_deprecated() {
+        let source = "<script>let { depth } = $props();</script>{#if depth}<svelte:self depth={depth - 1} />{/if}";
+        with_semantic(source, |_, errors| {
+            assert_eq!(errors.len(), 1);
+            assert!(errors[0].to_string().contains("use self-imports"));
+        });
+        with_semantic("{#if depth}<svelte:self depth={depth - 1} />{/if}", |_, errors| {
+            assert!(errors.is_empty());
+        });
+    }
+}
@@ -0,0 +1,79 @@
+use oxc_ast::{
+    ast::{ArrowFunctionExpression, CallExpression, Expression, Function},
+    visit::walk,
+    Visit,
+};
+use oxc_syntax::{reference::ReferenceId, scope::ScopeFlags};
+use rustc_hash::{FxHashMap, FxHashSet};
+use ssc_ast::ast::{BlockId, ExpressionTagFlags, NodeId, RegularElementFlags, SvelteElementFlags};
+
+/// Node Metadata Table
+///
+/// What the analysis computes about the nodes of the template, keyed by the
+/// [`NodeId`] the parser gave them, so the AST is left as the parser built it.
+#[derive(Debug, Default)]
+pub struct NodeMetadata {
+    /// The flags of the `ExpressionTag`s and `SpreadAttribute`s.
+    pub expression_flags: FxHashMap<NodeId, ExpressionTagFlags>,
+    pub element_flags: FxHashMap<NodeId, RegularElementFlags>,
+    pub svelte_element_flags: FxHashMap<NodeId, SvelteElementFlags>,
+    /// The innermost block each `bind:` directive is nested in.
+    pub bind_parent_blocks: FxHashMap<NodeId, BlockId>,
+    /// The reference to the variable each `bind:group` directive groups its
+    /// inputs by, `group` in `bind:group={group[i]}`.
+    pub binding_groups: FxHashMap<NodeId, ReferenceId>,
+    /// The `style:` directives whose value isn't a constant.
+    pub dynamic_style_directives: FxHashSet<NodeId>,
+}
+
+impl NodeMetadata {
+    /// The flags of the `ExpressionTag` or `SpreadAttribute` with `node_id`.
+    pub fn get_expression_flags(&self, node_id: NodeId) -> ExpressionTagFlags {
+        self.expression_flags.get(&node_id).copied().unwrap_or(ExpressionTagFlags::empty())
+    }
+
+    /// The flags of the `RegularElement` with `node_id`.
+    pub fn get_element_flags(&self, node_id: NodeId) -> RegularElementFlags {
+        self.element_flags.get(&node_id).copied().unwrap_or(RegularElementFlags::empty())
+    }
+
+    /// The flags of the `<svelte:element>` with `node_id`.
+    pub fn get_svelte_element_flags(&self, node_id: NodeId) -> SvelteElementFlags {
+        self.svelte_element_flags.get(&node_id).copied().unwrap_or(SvelteElementFlags::empty())
+    }
+
+    pub fn get_bind_parent_block(&self, node_id: NodeId) -> Option<BlockId> {
+        self.bind_parent_blocks.get(&node_id).copied()
+    }
+
+    pub fn get_binding_group(&self, node_id: NodeId) -> Option<ReferenceId> {
+        self.binding_groups.get(&node_id).copied()
+    }
+
+    pub fn is_dynamic_style_directive(&self, node_id: NodeId) -> bool {
+        self.dynamic_style_directives.contains(&node_id)
+    }
+}
+
+/// Whether evaluating `expression` calls a function, the calls in the
+/// functions it declares aside.
+pub(crate) fn has_call(expression: &Expression) -> bool {
+    let mut finder = CallFinder { found: false };
+    finder.visit_expression(expression);
+    finder.found
+}
+
+struct CallFinder {
+    found: bool,
+}
+
+impl<'a> Visit<'a> for CallFinder {
+    fn visit_call_expression(&mut self, expr: &CallExpression<'a>) {
+        self.found = true;
+        walk::walk_call_expression(self, expr);
+    }
+
+    fn visit_function(&mut self, _func: &Function<'a>, _flags: Option<ScopeFlags>) {}
+
+    fn visit_arrow_expression(&mut self, _expr: &ArrowFunctionExpression<'a>) {}
+}
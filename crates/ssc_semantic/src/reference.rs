@@ -0,0 +1,52 @@
+use oxc_span::{Atom, Span};
+pub use oxc_syntax::reference::{ReferenceFlag, ReferenceId};
+use oxc_syntax::{scope::ScopeId, symbol::SymbolId};
+
+#[derive(Debug, Clone)]
+pub struct Reference<'a> {
+    span: Span,
+    name: Atom<'a>,
+    /// The scope the reference appears in.
+    scope_id: ScopeId,
+    /// `None` for references to globals.
+    symbol_id: Option<SymbolId>,
+    flag: ReferenceFlag,
+}
+
+impl<'a> Reference<'a> {
+    pub(crate) fn new(span: Span, name: Atom<'a>, scope_id: ScopeId, flag: ReferenceFlag) -> Self {
+        Self { span, name, scope_id, symbol_id: None, flag }
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn name(&self) -> &Atom<'a> {
+        &self.name
+    }
+
+    pub fn scope_id(&self) -> ScopeId {
+        self.scope_id
+    }
+
+    pub fn symbol_id(&self) -> Option<SymbolId> {
+        self.symbol_id
+    }
+
+    pub(crate) fn set_symbol_id(&mut self, symbol_id: SymbolId) {
+        self.symbol_id = Some(symbol_id);
+    }
+
+    pub fn flag(&self) -> ReferenceFlag {
+        self.flag
+    }
+
+    pub fn is_read(&self) -> bool {
+        self.flag.is_read()
+    }
+
+    pub fn is_write(&self) -> bool {
+        self.flag.is_write()
+    }
+}
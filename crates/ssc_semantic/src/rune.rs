@@ -0,0 +1,90 @@
+use oxc_ast::ast::{CallExpression, Expression};
+
+/// A call to one of Svelte's runes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rune {
+    State,
+    StateRaw,
+    StateSnapshot,
+    Derived,
+    DerivedBy,
+    Props,
+    Bindable,
+    Effect,
+    EffectPre,
+    EffectRoot,
+    EffectTracking,
+    Inspect,
+    InspectTrace,
+    Host,
+}
+
+impl Rune {
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "$state" => Self::State,
+            "$state.raw" => Self::StateRaw,
+            "$state.snapshot" => Self::StateSnapshot,
+            "$derived" => Self::Derived,
+            "$derived.by" => Self::DerivedBy,
+            "$props" => Self::Props,
+            "$bindable" => Self::Bindable,
+            "$effect" => Self::Effect,
+            "$effect.pre" => Self::EffectPre,
+            "$effect.root" => Self::EffectRoot,
+            "$effect.tracking" => Self::EffectTracking,
+            "$inspect" => Self::Inspect,
+            "$inspect.trace" => Self::InspectTrace,
+            "$host" => Self::Host,
+            _ => return None,
+        })
+    }
+
+    /// Detect the rune called by `call`, e.g. `$state.raw` in `$state.raw([])`.
+    pub fn from_call(call: &CallExpression<'_>) -> Option<Self> {
+        match &call.callee {
+            Expression::Identifier(ident) => Self::from_name(&ident.name),
+            Expression::StaticMemberExpression(member) => {
+                let Expression::Identifier(object) = &member.object else { return None };
+                match (object.name.as_str(), member.property.name.as_str()) {
+                    ("$state", "raw") => Some(Self::StateRaw),
+                    ("$state", "snapshot") => Some(Self::StateSnapshot),
+                    ("$derived", "by") => Some(Self::DerivedBy),
+                    ("$effect", "pre") => Some(Self::EffectPre),
+                    ("$effect", "root") => Some(Self::EffectRoot),
+                    ("$effect", "tracking") => Some(Self::EffectTracking),
+                    ("$inspect", "trace") => Some(Self::InspectTrace),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Detect the rune called by `expr`, if it's a call expression.
+    pub fn from_expression(expr: &Expression<'_>) -> Option<Self> {
+        match expr {
+            Expression::CallExpression(call) => Self::from_call(call),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::State => "$state",
+            Self::StateRaw => "$state.raw",
+            Self::StateSnapshot => "$state.snapshot",
+            Self::Derived => "$derived",
+            Self::DerivedBy => "$derived.by",
+            Self::Props => "$props",
+            Self::Bindable => "$bindable",
+            Self::Effect => "$effect",
+            Self::EffectPre => "$effect.pre",
+            Self::EffectRoot => "$effect.root",
+            Self::EffectTracking => "$effect.tracking",
+            Self::Inspect => "$inspect",
+            Self::InspectTrace => "$inspect.trace",
+            Self::Host => "$host",
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use oxc_index::IndexVec;
+use oxc_span::Atom;
+pub use oxc_syntax::scope::{ScopeFlags, ScopeId};
+use oxc_syntax::symbol::SymbolId;
+use rustc_hash::FxHashMap;
+
+pub type Bindings<'a> = FxHashMap<Atom<'a>, SymbolId>;
+
+/// Scope Tree
+///
+/// `SoA` (Struct of Arrays) for memory efficiency.
+///
+/// The module script scope is the root. The instance script scope is its
+/// child, and the template scopes descend from the instance scope.
+#[derive(Debug, Default)]
+pub struct ScopeTree<'a> {
+    parent_ids: IndexVec<ScopeId, Option<ScopeId>>,
+    flags: IndexVec<ScopeId, ScopeFlags>,
+    bindings: IndexVec<ScopeId, Bindings<'a>>,
+}
+
+impl<'a> ScopeTree<'a> {
+    pub fn len(&self) -> usize {
+        self.parent_ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent_ids.is_empty()
+    }
+
+    pub fn root_scope_id(&self) -> ScopeId {
+        ScopeId::new(0)
+    }
+
+    pub fn get_parent_id(&self, scope_id: ScopeId) -> Option<ScopeId> {
+        self.parent_ids[scope_id]
+    }
+
+    /// Iterate over the scope and all its ancestors, innermost first.
+    pub fn ancestors(&self, scope_id: ScopeId) -> impl Iterator<Item = ScopeId> + '_ {
+        std::iter::successors(Some(scope_id), |scope_id| self.parent_ids[*scope_id])
+    }
+
+    pub fn get_flags(&self, scope_id: ScopeId) -> ScopeFlags {
+        self.flags[scope_id]
+    }
+
+    pub fn get_bindings(&self, scope_id: ScopeId) -> &Bindings<'a> {
+        &self.bindings[scope_id]
+    }
+
+    pub fn get_binding(&self, scope_id: ScopeId, name: &str) -> Option<SymbolId> {
+        self.bindings[scope_id].get(name).copied()
+    }
+
+    /// Find a binding by walking up from `scope_id`.
+    pub fn find_binding(&self, scope_id: ScopeId, name: &str) -> Option<SymbolId> {
+        self.ancestors(scope_id).find_map(|scope_id| self.get_binding(scope_id, name))
+    }
+
+    /// The closest scope `var` declarations are hoisted to.
+    pub fn get_var_scope_id(&self, scope_id: ScopeId) -> ScopeId {
+        self.ancestors(scope_id)
+            .find(|scope_id| self.get_flags(*scope_id).intersects(ScopeFlags::Var))
+            .unwrap_or(scope_id)
+    }
+
+    pub(crate) fn add_scope(&mut self, parent_id: Option<ScopeId>, flags: ScopeFlags) -> ScopeId {
+        let scope_id = self.parent_ids.push(parent_id);
+        self.flags.push(flags);
+        self.bindings.push(Bindings::default());
+        scope_id
+    }
+
+    pub(crate) fn add_binding(&mut self, scope_id: ScopeId, name: Atom<'a>, symbol_id: SymbolId) {
+        self.bindings[scope_id].insert(name, symbol_id);
+    }
+}
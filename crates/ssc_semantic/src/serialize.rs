@@ -0,0 +1,176 @@
+//! Serialization with metadata
+//!
+//! Serializes the AST to JSON with what the analysis computed about its
+//! nodes, see [`NodeMetadata`], so tools reading the AST don't have to
+//! compute it again.
+
+use oxc_index::Idx;
+use oxc_span::Span;
+use rustc_hash::FxHashMap;
+use serde_json::{json, Map, Value};
+use ssc_ast::{
+    ast::{NodeId, Root},
+    AstKind, Visit, VisitControl,
+};
+
+use crate::{metadata::NodeMetadata, Semantic};
+
+impl<'a> Semantic<'a> {
+    /// `root`, the root the semantic was built from, as JSON, with a
+    /// `metadata` object on its `ExpressionTag`, `SpreadAttribute`,
+    /// `RegularElement`, `SvelteElement`, `BindDirective` and
+    /// `StyleDirective` nodes.
+    ///
+    /// # Errors
+    ///
+    /// If `root` fails to serialize.
+    pub fn root_to_value(&self, root: &Root<'a>) -> serde_json::Result<Value> {
+        let mut value = serde_json::to_value(root)?;
+        let mut node_ids = NodeIds::default();
+        let _ = node_ids.visit_root(root);
+        self.add_metadata(&mut value, &node_ids);
+        Ok(value)
+    }
+
+    fn add_metadata(&self, value: &mut Value, node_ids: &NodeIds) {
+        match value {
+            Value::Object(object) => {
+                object.values_mut().for_each(|value| self.add_metadata(value, node_ids));
+                if let Some(metadata) = self.node_metadata(object, node_ids) {
+                    object.insert("metadata".to_string(), metadata);
+                }
+            }
+            Value::Array(values) => {
+                values.iter_mut().for_each(|value| self.add_metadata(value, node_ids));
+            }
+            _ => {}
+        }
+    }
+
+    fn node_metadata(&self, node: &Map<String, Value>, node_ids: &NodeIds) -> Option<Value> {
+        let position = |key| node.get(key)?.as_u64().and_then(|offset| u32::try_from(offset).ok());
+        let span = Span::new(position("start")?, position("end")?);
+        let kind = node.get("type")?.as_str()?;
+        let node_id = *node_ids.0.get(&(kind, span))?;
+        let metadata: &NodeMetadata = &self.metadata;
+        Some(match kind {
+            "ExpressionTag" | "SpreadAttribute" => {
+                let flags = metadata.get_expression_flags(node_id);
+                json!({ "dynamic": flags.has_dynamic(), "hasCall": flags.has_call_expression() })
+            }
+            "RegularElement" => {
+                let flags = metadata.get_element_flags(node_id);
+                json!({
+                    "svg": flags.has_svg(),
+                    "mathml": flags.has_mathml(),
+                    "hasSpread": flags.has_spread(),
+                })
+            }
+            "SvelteElement" => {
+                json!({ "svg": metadata.get_svelte_element_flags(node_id).has_svg() })
+            }
+            "BindDirective" => {
+                let binding_group = metadata.get_binding_group(node_id).map(|reference_id| {
+                    let reference = self.symbols.get_reference(reference_id);
+                    let span = reference.span();
+                    json!({ "name": reference.name().as_str(), "start": span.start, "end": span.end })
+                });
+                json!({
+                    "parentBlock": metadata.get_bind_parent_block(node_id).map(Idx::index),
+                    "bindingGroup": binding_group,
+                })
+            }
+            "StyleDirective" => json!({ "dynamic": metadata.is_dynamic_style_directive(node_id) }),
+            _ => return None,
+        })
+    }
+}
+
+/// The ids of the nodes with metadata, by type and span: the JSON has no
+/// ids, and no two nodes of a type share a span in a parsed root.
+#[derive(Default)]
+struct NodeIds(FxHashMap<(&'static str, Span), NodeId>);
+
+impl<'a> Visit<'a> for NodeIds {
+    fn enter_node(&mut self, kind: AstKind<'a>) -> VisitControl {
+        let (name, span, node_id) = match kind {
+            AstKind::ExpressionTag(tag) => ("ExpressionTag", tag.span, tag.node_id),
+            AstKind::SpreadAttribute(spread) => ("SpreadAttribute", spread.span, spread.node_id),
+            AstKind::RegularElement(element) => ("RegularElement", element.span, element.node_id),
+            AstKind::SvelteElement(element) => ("SvelteElement", element.span, element.node_id),
+            AstKind::BindDirective(bind) => ("BindDirective", bind.span, bind.node_id),
+            AstKind::StyleDirective(style) => ("StyleDirective", style.span, style.node_id),
+            _ => return VisitControl::Continue,
+        };
+        self.0.insert((name, span), node_id);
+        VisitControl::Continue
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use serde_json::{json, Value};
+    use ssc_parser::Parser;
+
+    use crate::SemanticBuilder;
+
+    /// The objects of `value` of type `kind`, in pre-order.
+    fn find<'v>(value: &'v Value, kind: &str, found: &mut Vec<&'v Value>) {
+        match value {
+            Value::Object(object) => {
+                if object.get("type").and_then(Value::as_str) == Some(kind) {
+                    found.push(value);
+                }
+                object.values().for_each(|value| find(value, kind, found));
+            }
+            Value::Array(values) => values.iter().for_each(|value| find(value, kind, found)),
+            _ => {}
+        }
+    }
+
+    fn find_all<'v>(value: &'v Value, kind: &str) -> Vec<&'v Value> {
+        let mut found = vec![];
+        find(value, kind, &mut found);
+        found
+    }
+
+    #[test]
+    fn root_to_value() {
+        let source = r#"<script>let { a } = $props(); let group = $state([]);</script>
+{#each a as item}<svg {...item}><text>{item.label()}</text></svg><input type="checkbox" bind:group={group} style:color />{/each}"#;
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source).parse();
+        let semantic = SemanticBuilder::new(source).build(&ret.root).semantic;
+        let value = semantic.root_to_value(&ret.root).unwrap();
+
+        let elements = find_all(&value, "RegularElement");
+        assert_eq!(
+            elements[0]["metadata"],
+            json!({ "svg": true, "mathml": false, "hasSpread": true })
+        );
+        assert_eq!(
+            elements[2]["metadata"],
+            json!({ "svg": false, "mathml": false, "hasSpread": false })
+        );
+        assert_eq!(
+            find_all(&value, "SpreadAttribute")[0]["metadata"],
+            json!({ "dynamic": true, "hasCall": false })
+        );
+        assert_eq!(
+            find_all(&value, "ExpressionTag")[0]["metadata"],
+            json!({ "dynamic": true, "hasCall": true })
+        );
+
+        let start = source.find("{group}").unwrap() + 1;
+        assert_eq!(
+            find_all(&value, "BindDirective")[0]["metadata"],
+            json!({
+                "parentBlock": 0,
+                "bindingGroup": { "name": "group", "start": start, "end": start + 5 },
+            })
+        );
+        assert_eq!(find_all(&value, "StyleDirective")[0]["metadata"], json!({ "dynamic": true }));
+        assert!(find_all(&value, "EachBlock")[0].get("metadata").is_none());
+    }
+}
@@ -0,0 +1,21 @@
+use oxc_span::Span;
+use oxc_syntax::{scope::ScopeId, symbol::SymbolId};
+
+/// A `{#snippet}` of the template.
+///
+/// Snippets are hoisted to the start of the fragment they're declared in, so
+/// they can be rendered before their declaration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snippet {
+    pub span: Span,
+    pub symbol_id: SymbolId,
+    /// The scope of its parameters and body.
+    pub scope_id: ScopeId,
+    /// Whether it's an immediate child of a component or of a
+    /// `<svelte:boundary>`, which receives it as the prop of the same name.
+    pub is_prop: bool,
+    /// Whether it's declared at the top level of the template and only reads
+    /// the module script, globals and other hoistable snippets, so it can be
+    /// declared outside of the component, once.
+    pub hoistable: bool,
+}
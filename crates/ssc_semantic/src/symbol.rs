@@ -0,0 +1,129 @@
+use oxc_index::IndexVec;
+use oxc_span::{Atom, Span};
+use oxc_syntax::{
+    reference::ReferenceId,
+    scope::ScopeId,
+    symbol::{SymbolFlags, SymbolId},
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{binding::BindingKind, reference::Reference};
+
+/// The collection an `{#each}` block item comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EachSource {
+    /// A collection read from a variable, e.g. `items` in
+    /// `{#each items.visible as item}`. Assigning to the item updates it.
+    Reference(ReferenceId),
+    /// A call or other expression there is no variable to update through.
+    Expression,
+}
+
+/// Symbol Table
+///
+/// `SoA` (Struct of Arrays) for memory efficiency.
+#[derive(Debug, Default)]
+pub struct SymbolTable<'a> {
+    pub spans: IndexVec<SymbolId, Span>,
+    pub names: IndexVec<SymbolId, Atom<'a>>,
+    pub flags: IndexVec<SymbolId, SymbolFlags>,
+    pub kinds: IndexVec<SymbolId, BindingKind>,
+    pub scope_ids: IndexVec<SymbolId, ScopeId>,
+    pub resolved_references: IndexVec<SymbolId, Vec<ReferenceId>>,
+    pub references: IndexVec<ReferenceId, Reference<'a>>,
+    /// Collections of the `{#each}` block items.
+    pub each_sources: FxHashMap<SymbolId, EachSource>,
+    /// Symbols a member of which is assigned or bound to, `a` in `a.b = 1`.
+    pub member_mutations: FxHashSet<SymbolId>,
+    /// Symbols initialized by a store creator imported from `svelte/store`,
+    /// `count` in `const count = writable(0)`.
+    pub stores: FxHashSet<SymbolId>,
+}
+
+impl<'a> SymbolTable<'a> {
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = SymbolId> + '_ {
+        self.spans.iter_enumerated().map(|(symbol_id, _)| symbol_id)
+    }
+
+    /// Span of the identifier declaring the symbol.
+    pub fn get_span(&self, symbol_id: SymbolId) -> Span {
+        self.spans[symbol_id]
+    }
+
+    pub fn get_name(&self, symbol_id: SymbolId) -> &Atom<'a> {
+        &self.names[symbol_id]
+    }
+
+    pub fn get_flag(&self, symbol_id: SymbolId) -> SymbolFlags {
+        self.flags[symbol_id]
+    }
+
+    pub fn get_kind(&self, symbol_id: SymbolId) -> BindingKind {
+        self.kinds[symbol_id]
+    }
+
+    pub fn get_scope_id(&self, symbol_id: SymbolId) -> ScopeId {
+        self.scope_ids[symbol_id]
+    }
+
+    pub fn get_reference(&self, reference_id: ReferenceId) -> &Reference<'a> {
+        &self.references[reference_id]
+    }
+
+    pub fn get_resolved_references(
+        &self,
+        symbol_id: SymbolId,
+    ) -> impl Iterator<Item = &Reference<'a>> + '_ {
+        self.resolved_references[symbol_id]
+            .iter()
+            .map(|reference_id| &self.references[*reference_id])
+    }
+
+    /// Whether any reference to the symbol writes to it.
+    pub fn is_mutated(&self, symbol_id: SymbolId) -> bool {
+        self.get_resolved_references(symbol_id).any(Reference::is_write)
+    }
+
+    /// Whether the symbol or any of its members is written to.
+    pub fn is_deeply_mutated(&self, symbol_id: SymbolId) -> bool {
+        self.is_mutated(symbol_id) || self.member_mutations.contains(&symbol_id)
+    }
+
+    /// The collection of an `{#each}` block item, `None` for other symbols.
+    pub fn get_each_source(&self, symbol_id: SymbolId) -> Option<EachSource> {
+        self.each_sources.get(&symbol_id).copied()
+    }
+
+    /// Whether the symbol is known to hold a store from `svelte/store`.
+    pub fn is_store(&self, symbol_id: SymbolId) -> bool {
+        self.stores.contains(&symbol_id)
+    }
+
+    pub(crate) fn create_symbol(
+        &mut self,
+        span: Span,
+        name: Atom<'a>,
+        flag: SymbolFlags,
+        kind: BindingKind,
+        scope_id: ScopeId,
+    ) -> SymbolId {
+        self.spans.push(span);
+        self.names.push(name);
+        self.flags.push(flag);
+        self.kinds.push(kind);
+        self.scope_ids.push(scope_id);
+        self.resolved_references.push(vec![])
+    }
+
+    pub(crate) fn create_reference(&mut self, reference: Reference<'a>) -> ReferenceId {
+        self.references.push(reference)
+    }
+}
@@ -0,0 +1,116 @@
+use bitflags::bitflags;
+use oxc_index::IndexVec;
+use oxc_span::{GetSpan, Span};
+use rustc_hash::FxHashMap;
+use ssc_ast::ast::{Block, BlockId, Element, FragmentNode, Root, SlotElement, SlotElementId};
+
+bitflags! {
+    /// How an `{#each}` block reconciles its items when its collection
+    /// changes, with the values of the flags of the `each` runtime call.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct EachBlockFlags: u8 {
+        /// The item has to be updated, it isn't a key compared by identity.
+        const ItemReactive  = 1 << 0;
+        /// The index is read, and has to be updated when an item moves.
+        const IndexReactive = 1 << 1;
+        /// Items are matched by key, and moved rather than updated in place.
+        const Keyed         = 1 << 2;
+        /// The block is the only child of its element, which can be cleared
+        /// at once.
+        const Controlled    = 1 << 3;
+        /// The items are animated with `animate:` when they move.
+        const Animated      = 1 << 4;
+    }
+}
+
+/// Template Node Table
+///
+/// The blocks and `<slot>` elements of the template by id, numbered in
+/// source order. [`NodeMetadata::bind_parent_blocks`] refers to these ids.
+///
+/// [`NodeMetadata::bind_parent_blocks`]: crate::metadata::NodeMetadata::bind_parent_blocks
+#[derive(Debug, Default)]
+pub struct TemplateNodes {
+    pub block_spans: IndexVec<BlockId, Span>,
+    /// The innermost block each block is nested in.
+    pub block_parents: IndexVec<BlockId, Option<BlockId>>,
+    pub slot_spans: IndexVec<SlotElementId, Span>,
+    /// The innermost block each `<slot>` is nested in.
+    pub slot_parents: IndexVec<SlotElementId, Option<BlockId>>,
+    /// The flags of the `{#each}` blocks.
+    pub each_flags: FxHashMap<BlockId, EachBlockFlags>,
+}
+
+impl TemplateNodes {
+    pub fn blocks(&self) -> impl Iterator<Item = BlockId> + '_ {
+        self.block_spans.iter_enumerated().map(|(block_id, _)| block_id)
+    }
+
+    pub fn get_block_span(&self, block_id: BlockId) -> Span {
+        self.block_spans[block_id]
+    }
+
+    pub fn get_block_parent(&self, block_id: BlockId) -> Option<BlockId> {
+        self.block_parents[block_id]
+    }
+
+    /// The id of the block at `span`.
+    pub fn get_block_id(&self, span: Span) -> Option<BlockId> {
+        // Blocks are numbered in source order, so their starts are sorted
+        let index =
+            self.block_spans.raw.binary_search_by_key(&span.start, |span| span.start).ok()?;
+        (self.block_spans.raw[index] == span).then(|| BlockId::from_usize(index))
+    }
+
+    /// The block `block_id` identifies in `root`, the root the table was
+    /// built from.
+    pub fn get_block<'r, 'a>(
+        &self,
+        root: &'r Root<'a>,
+        block_id: BlockId,
+    ) -> Option<&'r Block<'a>> {
+        let span = *self.block_spans.get(block_id)?;
+        root.descendants().find_map(|node| match node {
+            FragmentNode::Block(block) if block.span() == span => Some(block),
+            _ => None,
+        })
+    }
+
+    /// The flags of the `{#each}` block `block_id`, `None` for other blocks.
+    pub fn get_each_flags(&self, block_id: BlockId) -> Option<EachBlockFlags> {
+        self.each_flags.get(&block_id).copied()
+    }
+
+    pub fn slots(&self) -> impl Iterator<Item = SlotElementId> + '_ {
+        self.slot_spans.iter_enumerated().map(|(slot_id, _)| slot_id)
+    }
+
+    pub fn get_slot_span(&self, slot_id: SlotElementId) -> Span {
+        self.slot_spans[slot_id]
+    }
+
+    pub fn get_slot_parent(&self, slot_id: SlotElementId) -> Option<BlockId> {
+        self.slot_parents[slot_id]
+    }
+
+    /// The id of the `<slot>` at `span`.
+    pub fn get_slot_id(&self, span: Span) -> Option<SlotElementId> {
+        let index =
+            self.slot_spans.raw.binary_search_by_key(&span.start, |span| span.start).ok()?;
+        (self.slot_spans.raw[index] == span).then(|| SlotElementId::from_usize(index))
+    }
+
+    /// The `<slot>` `slot_id` identifies in `root`, the root the table was
+    /// built from.
+    pub fn get_slot<'r, 'a>(
+        &self,
+        root: &'r Root<'a>,
+        slot_id: SlotElementId,
+    ) -> Option<&'r SlotElement<'a>> {
+        let span = *self.slot_spans.get(slot_id)?;
+        root.descendants().find_map(|node| match node {
+            FragmentNode::Element(Element::SlotElement(slot)) if slot.span == span => Some(slot),
+            _ => None,
+        })
+    }
+}
@@ -0,0 +1,91 @@
+use oxc_ast::ast::Expression;
+use ssc_ast::ast::*;
+
+use crate::Transformer;
+
+impl<'a> Transformer<'a> {
+    /// Replace the `{#await}` blocks of `fragment` whose expression is a
+    /// literal, which never is a promise, by their `then` branch, when it
+    /// doesn't bind the value: `{#await 1}…{:then}<p>a</p>{/await}` becomes
+    /// `<p>a</p>`.
+    ///
+    /// The runtime, and the server, render the `then` branch at once for
+    /// values that aren't promises, and nothing if there isn't one. Branches
+    /// declaring `{@const}`s or snippets are kept, their names are scoped to
+    /// the branch.
+    pub(crate) fn lower_settled_await_blocks(&self, fragment: &mut Fragment<'a>) {
+        if !fragment.nodes.iter().any(is_settled_await_block) {
+            return;
+        }
+        let mut nodes = self.ast.new_vec_with_capacity(fragment.nodes.len());
+        for node in fragment.nodes.drain(..) {
+            if !is_settled_await_block(&node) {
+                nodes.push(node);
+                continue;
+            }
+            if let FragmentNode::Block(Block::AwaitBlock(block)) = node {
+                if let Some(mut then) = block.then {
+                    nodes.extend(then.nodes.drain(..));
+                }
+            }
+        }
+        fragment.nodes = nodes;
+    }
+}
+
+fn is_settled_await_block(node: &FragmentNode) -> bool {
+    let FragmentNode::Block(Block::AwaitBlock(block)) = node else { return false };
+    block.value.is_none()
+        && is_literal(&block.expression)
+        && !block.then.as_ref().is_some_and(has_declarations)
+}
+
+/// Whether `fragment` declares names scoped to it, with `{@const}` tags or
+/// snippets, which would leak into the enclosing fragment if it was
+/// inlined.
+pub(crate) fn has_declarations(fragment: &Fragment) -> bool {
+    fragment.nodes.iter().any(|node| {
+        matches!(
+            node,
+            FragmentNode::Tag(Tag::ConstTag(_)) | FragmentNode::Block(Block::SnippetBlock(_))
+        )
+    })
+}
+
+/// Whether `expression` is a literal, a constant that can't be a promise and
+/// is evaluated without side effects.
+pub(crate) fn is_literal(expression: &Expression) -> bool {
+    match expression {
+        Expression::StringLiteral(_)
+        | Expression::NumericLiteral(_)
+        | Expression::BigIntLiteral(_)
+        | Expression::BooleanLiteral(_)
+        | Expression::NullLiteral(_) => true,
+        Expression::TemplateLiteral(literal) => literal.expressions.is_empty(),
+        Expression::ParenthesizedExpression(expression) => is_literal(&expression.expression),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use ssc_codegen::{Codegen, CodegenOptions};
+    use ssc_parser::Parser;
+
+    use crate::Transformer;
+
+    #[test]
+    fn settled_await_blocks() {
+        let allocator = Allocator::default();
+        let source = "<div>{#await 1}<p>loading</p>{:then}<p>a</p>{/await}{#await `b`}<p>loading</p>{/await}{#await 'c' then value}{value}{/await}{#await promise}<p>loading</p>{:then}<p>d</p>{/await}{#await 2 then}{@const e = 1}{e}{/await}{#await 3 then}{#snippet f()}f{/snippet}{@render f()}{/await}</div>";
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let mut root = ret.root;
+        Transformer::new(&allocator).build(&mut root);
+        assert_eq!(
+            Codegen::<false>::new("", source, CodegenOptions::default()).build(&root).source_text,
+            "<div><p>a</p>{#await 'c' then value}{value}{/await}{#await promise}<p>loading</p>{:then}<p>d</p>{/await}{#await 2 then}{@const e = 1}{e}{/await}{#await 3 then}{#snippet f()}f{/snippet}{@render f()}{/await}</div>"
+        );
+    }
+}
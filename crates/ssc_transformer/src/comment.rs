@@ -0,0 +1,61 @@
+use oxc_span::{Atom, Span};
+use ssc_ast::ast::*;
+
+use crate::Transformer;
+
+impl<'a> Transformer<'a> {
+    /// Remove the HTML comments of `fragment`, joining the text around them,
+    /// `a <!-- b --> c` becomes `a  c`.
+    pub(crate) fn strip_comments(&self, fragment: &mut Fragment<'a>) {
+        if !fragment.nodes.iter().any(|node| matches!(node, FragmentNode::Comment(_))) {
+            return;
+        }
+        let mut nodes = self.ast.new_vec_with_capacity(fragment.nodes.len());
+        for node in fragment.nodes.drain(..) {
+            if matches!(node, FragmentNode::Comment(_)) {
+                continue;
+            }
+            if let (Some(FragmentNode::Text(previous)), FragmentNode::Text(text)) =
+                (nodes.last_mut(), &node)
+            {
+                let raw = format!("{}{}", previous.raw, text.raw);
+                let data = format!("{}{}", previous.data, text.data);
+                previous.span = Span::new(previous.span.start, text.span.end);
+                previous.raw = Atom::from(self.ast.new_str(&raw));
+                previous.data = Atom::from(self.ast.new_str(&data));
+                continue;
+            }
+            nodes.push(node);
+        }
+        fragment.nodes = nodes;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use ssc_codegen::{Codegen, CodegenOptions};
+    use ssc_parser::Parser;
+
+    use crate::Transformer;
+
+    fn transform(source: &str, preserve_comments: bool) -> String {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let mut root = ret.root;
+        Transformer::new(&allocator)
+            .with_preserve_whitespace(true)
+            .with_preserve_comments(preserve_comments)
+            .build(&mut root);
+        let options = CodegenOptions { preserve_comments: true, ..CodegenOptions::default() };
+        Codegen::<false>::new("", source, options).build(&root).source_text
+    }
+
+    #[test]
+    fn strip_comments() {
+        let source = "<p>a <!-- b --> c<!--d--></p><!-- e -->";
+        assert_eq!(transform(source, false), "<p>a  c</p>");
+        assert_eq!(transform(source, true), source);
+    }
+}
@@ -0,0 +1,104 @@
+use oxc_allocator::Vec;
+use oxc_ast::{
+    ast::{Expression, Statement},
+    visit::walk_mut::{walk_statement_mut, walk_statements_mut},
+    VisitMut,
+};
+use oxc_span::GetSpan;
+use ssc_ast::ast::*;
+
+use crate::Transformer;
+
+impl<'a> Transformer<'a> {
+    /// Remove the `$inspect` statements of the scripts of `root`, see
+    /// [`DevCodeStripper`].
+    pub(crate) fn strip_dev_scripts(&self, root: &mut Root<'a>) {
+        let mut stripper = DevCodeStripper { js: self.js };
+        for script in [&mut root.module, &mut root.instance].into_iter().flatten() {
+            stripper.visit_program(&mut script.program);
+        }
+    }
+
+    /// Remove the `{@debug}` tags of `fragment`.
+    pub(crate) fn strip_debug_tags(fragment: &mut Fragment<'a>) {
+        fragment.nodes.retain(|node| !matches!(node, FragmentNode::Tag(Tag::DebugTag(_))));
+    }
+}
+
+/// Removes the statements calling `$inspect`, `$inspect(...).with(...)` or
+/// `$inspect.trace()`, which only log in development. Their arguments go with
+/// them, so the values they read are no longer tracked.
+struct DevCodeStripper<'a> {
+    js: oxc_ast::AstBuilder<'a>,
+}
+
+impl<'a> VisitMut<'a> for DevCodeStripper<'a> {
+    fn visit_statements(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
+        stmts.retain(|stmt| !is_inspect_statement(stmt));
+        walk_statements_mut(self, stmts);
+    }
+
+    fn visit_statement(&mut self, stmt: &mut Statement<'a>) {
+        // The body of an `if` or a loop, which must stay a statement
+        if is_inspect_statement(stmt) {
+            *stmt = self.js.empty_statement(stmt.span());
+            return;
+        }
+        walk_statement_mut(self, stmt);
+    }
+}
+
+fn is_inspect_statement(stmt: &Statement<'_>) -> bool {
+    let Statement::ExpressionStatement(stmt) = stmt else { return false };
+    let Expression::CallExpression(call) = stmt.expression.get_inner_expression() else {
+        return false;
+    };
+    match &call.callee {
+        Expression::Identifier(ident) => ident.name == "$inspect",
+        Expression::StaticMemberExpression(member) => match &member.object {
+            // `$inspect.trace()`
+            Expression::Identifier(object) => {
+                object.name == "$inspect" && member.property.name == "trace"
+            }
+            // `$inspect(value).with(callback)`
+            Expression::CallExpression(object) => {
+                member.property.name == "with"
+                    && matches!(&object.callee, Expression::Identifier(ident) if ident.name == "$inspect")
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use ssc_codegen::{Codegen, CodegenOptions};
+    use ssc_parser::Parser;
+
+    use crate::Transformer;
+
+    fn transform(source: &str, dev: bool) -> String {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let mut root = ret.root;
+        Transformer::new(&allocator).with_dev(dev).build(&mut root);
+        Codegen::<false>::new("", source, CodegenOptions::default()).build(&root).source_text
+    }
+
+    #[test]
+    fn strip_dev_code() {
+        let source = "<script>let a = $state(0);$inspect(a);$inspect(a).with(console.trace);function f() { $inspect.trace(); if (a) $inspect(a); return a; }</script>{@debug a}<p>{a}</p>";
+        let output = transform(source, false);
+        assert!(!output.contains("$inspect"), "{output}");
+        assert!(!output.contains("@debug"), "{output}");
+        assert!(output.contains("return a"), "{output}");
+        assert!(output.ends_with("<p>{a}</p>"), "{output}");
+
+        let output = transform(source, true);
+        assert_eq!(output.matches("$inspect").count(), 4, "{output}");
+        assert!(output.contains("{@debug a}"), "{output}");
+    }
+}
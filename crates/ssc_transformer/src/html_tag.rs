@@ -0,0 +1,188 @@
+use oxc_allocator::Vec;
+use oxc_ast::ast::Expression;
+use oxc_span::{Atom, Span};
+use ssc_ast::ast::*;
+use ssc_parser::Parser;
+
+use crate::Transformer;
+
+impl<'a> Transformer<'a> {
+    /// Replace the `{@html}` tags of `fragment` whose expression is a
+    /// constant string of balanced markup by that markup, `{@html "<b>a</b>"}`
+    /// becomes `<b>a</b>`.
+    ///
+    /// The markup is rendered with the rest of the template instead of being
+    /// set at runtime, so it's in the initial HTML and doesn't flash on
+    /// hydration. Like `{@html}`, it mustn't be scoped by the stylesheet of
+    /// the component, so components with a `<style>` are left as is.
+    pub(crate) fn inline_static_html(&self, fragment: &mut Fragment<'a>) {
+        if self.styled
+            || !fragment.nodes.iter().any(|node| matches!(node, FragmentNode::Tag(Tag::HtmlTag(_))))
+        {
+            return;
+        }
+        let mut nodes = self.ast.new_vec_with_capacity(fragment.nodes.len());
+        for node in fragment.nodes.drain(..) {
+            if let FragmentNode::Tag(Tag::HtmlTag(tag)) = &node {
+                if let Some(markup) = static_markup(&tag.expression)
+                    .and_then(|markup| self.parse_markup(tag.span, markup.as_str()))
+                {
+                    nodes.extend(markup);
+                    continue;
+                }
+            }
+            nodes.push(node);
+        }
+        fragment.nodes = nodes;
+    }
+
+    /// The elements and text of `markup` if it parses to the same nodes as a
+    /// template than as HTML: its tags are balanced, lowercase HTML elements,
+    /// with plain attributes. The nodes all have the span of the `{@html}`
+    /// tag they come from.
+    ///
+    /// Markup with braces, which would be template expressions, comments,
+    /// scripts, styles or directive-like attributes is left to the runtime.
+    fn parse_markup(&self, span: Span, markup: &'a str) -> Option<Vec<'a, FragmentNode<'a>>> {
+        if markup.contains(['{', '}']) {
+            return None;
+        }
+        let ret = Parser::new(self.ast.allocator, markup).with_template(true).parse();
+        let mut nodes = ret.root.fragment.nodes;
+        (!ret.panicked && ret.errors.is_empty() && adopt_markup(span, markup, &mut nodes))
+            .then_some(nodes)
+    }
+}
+
+/// Whether `nodes`, parsed from `markup`, render the same in a template as in
+/// HTML, giving them `span`.
+fn adopt_markup(span: Span, markup: &str, nodes: &mut [FragmentNode]) -> bool {
+    nodes.iter_mut().all(|node| match node {
+        FragmentNode::Text(text) => {
+            text.span = span;
+            true
+        }
+        FragmentNode::Element(Element::RegularElement(element)) => {
+            let source = &markup[element.span.start as usize..element.span.end as usize];
+            if matches!(element.name.as_str(), "script" | "style")
+                // `<div />` is closed in templates, but not in HTML
+                || (!ssc_ast::html::is_void(&element.name) && source.ends_with("/>"))
+            {
+                return false;
+            }
+            element.span = span;
+            element.attributes.iter_mut().all(|attribute| adopt_attribute(span, attribute))
+                && adopt_markup(span, markup, &mut element.fragment.nodes)
+        }
+        _ => false,
+    })
+}
+
+/// Whether `attribute` is a plain attribute, with a text value if any, giving
+/// it `span`.
+fn adopt_attribute(span: Span, attribute: &mut ElementAttribute) -> bool {
+    let ElementAttribute::Attribute(attribute) = attribute else { return false };
+    attribute.span = span;
+    attribute.parts = AttributeParts::default();
+    let Some(value) = &mut attribute.value else { return true };
+    value.span = span;
+    value.sequence.iter_mut().all(|part| match part {
+        AttributeSequenceValue::Text(text) => {
+            text.span = span;
+            true
+        }
+        AttributeSequenceValue::ExpressionTag(_) => false,
+    })
+}
+
+/// The value of `expression` if it's a string literal, or a template literal
+/// without expressions.
+fn static_markup<'a>(expression: &Expression<'a>) -> Option<Atom<'a>> {
+    match expression {
+        Expression::StringLiteral(literal) => Some(literal.value.clone()),
+        Expression::TemplateLiteral(literal) if literal.expressions.is_empty() => {
+            literal.quasis.first()?.value.cooked.clone()
+        }
+        Expression::ParenthesizedExpression(expression) => static_markup(&expression.expression),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use ssc_ast::ast::*;
+    use ssc_codegen::{Codegen, CodegenOptions};
+    use ssc_parser::Parser;
+
+    use crate::Transformer;
+
+    fn transform(source: &str) -> String {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let mut root = ret.root;
+        Transformer::new(&allocator).build(&mut root);
+        Codegen::<false>::new("", source, CodegenOptions::default()).build(&root).source_text
+    }
+
+    #[test]
+    fn balanced_markup() {
+        for (markup, expected) in [
+            ("plain &amp; text", "plain &amp; text"),
+            (
+                "<b>a</b> <br><img src=x alt='a > b'><p class=\"a\" hidden>b<i>c</i></p >",
+                r#"<b>a</b> <br /><img src="x" alt="a > b" /><p class="a" hidden>b<i>c</i></p>"#,
+            ),
+        ] {
+            assert_eq!(transform(&format!("{{@html `{markup}`}}")), expected);
+        }
+        for markup in [
+            "<b>a",
+            "<b><i>a</b></i>",
+            "</b>",
+            "<div />",
+            "<br></br>",
+            "<Foo />",
+            "<svelte:head></svelte:head>",
+            "<!-- a -->",
+            "a < b",
+            "<script>a()</script>",
+            "<p>{a}</p>",
+            "<p on:click=\"a\"></p>",
+            "<p class=\"a></p>",
+        ] {
+            let source = format!("{{@html `{markup}`}}");
+            assert_eq!(transform(&source), source);
+        }
+    }
+
+    #[test]
+    fn inline_static_html() {
+        let allocator = Allocator::default();
+        let source = r#"<p>{@html "<b>a</b>  b"}{@html `<br>`}{@html "<b>a"}{@html content}{@html `<i>${a}</i>`}</p>"#;
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let mut root = ret.root;
+        Transformer::new(&allocator).build(&mut root);
+        assert_eq!(
+            Codegen::<false>::new("", source, CodegenOptions::default()).build(&root).source_text,
+            r#"<p><b>a</b>  b<br />{@html '<b>a'}{@html content}{@html `<i>${a}</i>`}</p>"#
+        );
+        // The markup is elements, not text to escape
+        let FragmentNode::Element(Element::RegularElement(p)) = &root.fragment.nodes[0] else {
+            unreachable!()
+        };
+        let FragmentNode::Element(Element::RegularElement(b)) = &p.fragment.nodes[0] else {
+            unreachable!()
+        };
+        assert_eq!(b.name, "b");
+        assert!(matches!(&b.fragment.nodes[..], [FragmentNode::Text(text)] if text.data == "a"));
+
+        // Selectors of the component would match the inlined elements
+        let source = "{@html \"<b>a</b>\"}<style>b { color: red; }</style>";
+        let mut root = Parser::new(&allocator, source).parse().root;
+        Transformer::new(&allocator).build(&mut root);
+        assert!(matches!(root.fragment.nodes[0], FragmentNode::Tag(Tag::HtmlTag(_))));
+    }
+}
@@ -0,0 +1,388 @@
+use oxc_allocator::Vec;
+use oxc_ast::ast::{
+    BindingPatternKind, Declaration, Expression, ImportDeclarationSpecifier, PropertyKey,
+    Statement, StringLiteral, VariableDeclaration, VariableDeclarationKind,
+};
+use oxc_span::Atom;
+use rustc_hash::FxHashMap;
+use ssc_ast::ast::*;
+
+use crate::Transformer;
+
+/// The components a [`Transformer`] can inline, usually backed by the
+/// project graph of the bundler.
+///
+/// Implemented for closures, `|specifier| parse(specifier)`.
+pub trait ComponentSource<'a> {
+    /// A new parse of the component imported from `specifier`, allocated in
+    /// the arena of the transformer, or `None` to keep it as a component.
+    ///
+    /// Every call must return a new parse, as each inlined instance takes
+    /// ownership of the nodes of one.
+    fn parse(&self, specifier: &str) -> Option<Root<'a>>;
+}
+
+impl<'a, F> ComponentSource<'a> for F
+where
+    F: Fn(&str) -> Option<Root<'a>>,
+{
+    fn parse(&self, specifier: &str) -> Option<Root<'a>> {
+        self(specifier)
+    }
+}
+
+/// The value of a prop passed to an inlined component.
+enum PropValue<'r, 'a> {
+    Text(Atom<'a>),
+    Expression(&'r Expression<'a>),
+    True,
+}
+
+impl<'a> Transformer<'a> {
+    /// The specifiers of the default imports of the component's scripts, by
+    /// local name, e.g. `Row` → `./Row.svelte`.
+    pub(crate) fn component_imports(root: &Root<'a>) -> FxHashMap<Atom<'a>, Atom<'a>> {
+        let mut imports = FxHashMap::default();
+        let programs = [&root.module, &root.instance].into_iter().flatten();
+        for statement in programs.flat_map(|script| script.program.body.iter()) {
+            let Statement::ImportDeclaration(import) = statement else { continue };
+            if import.import_kind.is_type() {
+                continue;
+            }
+            for specifier in import.specifiers.iter().flatten() {
+                if let ImportDeclarationSpecifier::ImportDefaultSpecifier(specifier) = specifier {
+                    imports.insert(specifier.local.name.clone(), import.source.value.clone());
+                }
+            }
+        }
+        imports
+    }
+
+    /// Replace the trivial components of `fragment` by their markup, see
+    /// [`Transformer::with_component_source`].
+    pub(crate) fn inline_components(&self, fragment: &mut Fragment<'a>) {
+        let Some(source) = self.component_source else { return };
+        if self.styled {
+            return;
+        }
+        if !fragment.nodes.iter().any(|node| self.inlinable_name(node).is_some()) {
+            return;
+        }
+        let mut nodes = self.ast.new_vec_with_capacity(fragment.nodes.len());
+        for node in fragment.nodes.drain(..) {
+            let inlined = self
+                .inlinable_name(&node)
+                .and_then(|specifier| source.parse(specifier))
+                .and_then(|child| match &node {
+                    FragmentNode::Element(Element::Component(component)) => {
+                        self.inline_component(component, child)
+                    }
+                    _ => None,
+                });
+            match inlined {
+                Some(inlined) => nodes.extend(inlined),
+                None => nodes.push(node),
+            }
+        }
+        fragment.nodes = nodes;
+    }
+
+    /// The specifier of the component `node` is an instance of, if it is
+    /// imported.
+    fn inlinable_name(&self, node: &FragmentNode<'a>) -> Option<&str> {
+        let FragmentNode::Element(Element::Component(component)) = node else { return None };
+        self.component_imports.get(&component.name).map(Atom::as_str)
+    }
+
+    /// The markup of `child` with the props of `component`, if both are
+    /// trivial: `child` only has static markup and `{prop}` tags, no
+    /// `<style>` and literal defaults, and `component` only passes props,
+    /// whose values are text or simple expressions.
+    fn inline_component(
+        &self,
+        component: &Component<'a>,
+        mut child: Root<'a>,
+    ) -> Option<Vec<'a, FragmentNode<'a>>> {
+        let has_children = component.fragment.nodes.iter().any(|node| match node {
+            FragmentNode::Text(text) => !text.data.trim().is_empty(),
+            FragmentNode::Comment(_) => false,
+            _ => true,
+        });
+        if has_children || child.css.is_some() || child.module.is_some() || child.options.is_some()
+        {
+            return None;
+        }
+        let mut props = FxHashMap::default();
+        for attribute in &component.attributes {
+            let ElementAttribute::Attribute(attribute) = attribute else { return None };
+            let value = match attribute.value.as_ref().map(|value| value.sequence.as_slice()) {
+                None => PropValue::True,
+                Some([AttributeSequenceValue::Text(text)]) => PropValue::Text(text.data.clone()),
+                Some([AttributeSequenceValue::ExpressionTag(tag)])
+                    if is_simple_expression(&tag.expression) =>
+                {
+                    PropValue::Expression(&tag.expression)
+                }
+                _ => return None,
+            };
+            props.insert(attribute.name.clone(), value);
+        }
+        let defaults = match &child.instance {
+            Some(script) => declared_props(&script.program.body)?,
+            None => FxHashMap::default(),
+        };
+        if props.keys().any(|name| !defaults.contains_key(name)) {
+            return None;
+        }
+        let mut values = FxHashMap::default();
+        for (name, default) in &defaults {
+            let value = match (props.get(name), default) {
+                (Some(PropValue::Text(text)), _) => self
+                    .js
+                    .literal_string_expression(StringLiteral::new(component.span, text.clone())),
+                (Some(PropValue::True), _) => self
+                    .js
+                    .literal_boolean_expression(self.js.boolean_literal(component.span, true)),
+                (Some(PropValue::Expression(expression)), _) => {
+                    self.clone_simple_expression(expression)?
+                }
+                (None, Some(default)) => self.clone_simple_expression(default)?,
+                (None, None) => continue,
+            };
+            values.insert(name.clone(), value);
+        }
+        if !self.substitute_props(&mut child.fragment, &values) {
+            return None;
+        }
+        Some(std::mem::replace(&mut child.fragment.nodes, self.ast.new_vec()))
+    }
+
+    /// Replace the `{prop}` tags of `fragment` by copies of the values of
+    /// `props`, or return `false` if it has anything else than static markup.
+    fn substitute_props(
+        &self,
+        fragment: &mut Fragment<'a>,
+        props: &FxHashMap<Atom<'a>, Expression<'a>>,
+    ) -> bool {
+        fragment.nodes.iter_mut().all(|node| match node {
+            FragmentNode::Text(_) | FragmentNode::Comment(_) => true,
+            FragmentNode::Tag(Tag::ExpressionTag(tag)) => {
+                self.substitute_prop(&mut tag.expression, props)
+            }
+            FragmentNode::Element(Element::RegularElement(element)) => {
+                element.attributes.iter_mut().all(|attribute| {
+                    let ElementAttribute::Attribute(attribute) = attribute else { return false };
+                    attribute.value.iter_mut().flat_map(|value| value.sequence.iter_mut()).all(
+                        |part| match part {
+                            AttributeSequenceValue::Text(_) => true,
+                            AttributeSequenceValue::ExpressionTag(tag) => {
+                                self.substitute_prop(&mut tag.expression, props)
+                            }
+                        },
+                    )
+                }) && self.substitute_props(&mut element.fragment, props)
+            }
+            _ => false,
+        })
+    }
+
+    fn substitute_prop(
+        &self,
+        expression: &mut Expression<'a>,
+        props: &FxHashMap<Atom<'a>, Expression<'a>>,
+    ) -> bool {
+        let Expression::Identifier(ident) = expression else { return false };
+        let Some(value) = props.get(&ident.name) else { return false };
+        match self.clone_simple_expression(value) {
+            Some(value) => {
+                *expression = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// A copy of `expression`, if it [is simple](is_simple_expression).
+    fn clone_simple_expression(&self, expression: &Expression<'a>) -> Option<Expression<'a>> {
+        Some(match expression {
+            Expression::StringLiteral(literal) => {
+                self.js.literal_string_expression(literal.as_ref().clone())
+            }
+            Expression::NumericLiteral(literal) => {
+                self.js.literal_number_expression(literal.as_ref().clone())
+            }
+            Expression::BooleanLiteral(literal) => {
+                self.js.literal_boolean_expression(literal.as_ref().clone())
+            }
+            Expression::NullLiteral(literal) => {
+                self.js.literal_null_expression(literal.as_ref().clone())
+            }
+            Expression::Identifier(ident) => self.js.identifier_reference_expression(
+                self.js.identifier_reference(ident.span, &ident.name),
+            ),
+            Expression::StaticMemberExpression(member) => self.js.static_member_expression(
+                member.span,
+                self.clone_simple_expression(&member.object)?,
+                member.property.clone(),
+                member.optional,
+            ),
+            _ => return None,
+        })
+    }
+}
+
+fn is_literal(expression: &Expression) -> bool {
+    matches!(
+        expression,
+        Expression::StringLiteral(_)
+            | Expression::NumericLiteral(_)
+            | Expression::BooleanLiteral(_)
+            | Expression::NullLiteral(_)
+    )
+}
+
+/// Whether `expression` is a literal, an identifier or a static member chain
+/// of one, e.g. `item.label`, which can be copied as is.
+fn is_simple_expression(expression: &Expression) -> bool {
+    match expression {
+        Expression::StringLiteral(_)
+        | Expression::NumericLiteral(_)
+        | Expression::BooleanLiteral(_)
+        | Expression::NullLiteral(_)
+        | Expression::Identifier(_) => true,
+        Expression::StaticMemberExpression(member) => is_simple_expression(&member.object),
+        _ => false,
+    }
+}
+
+/// The props declared by `body`, with their default values, if it only
+/// declares props, with `let { a, b = 1 } = $props()` or `export let`, and
+/// their defaults are literals. Other defaults, e.g. `b = a`, would be copied
+/// out of the scope of the child.
+fn declared_props<'r, 'a>(
+    body: &'r [Statement<'a>],
+) -> Option<FxHashMap<Atom<'a>, Option<&'r Expression<'a>>>> {
+    let mut props = FxHashMap::default();
+    for statement in body {
+        match statement {
+            Statement::VariableDeclaration(declaration) => {
+                let [declarator] = declaration.declarations.as_slice() else { return None };
+                if declaration.kind != VariableDeclarationKind::Let {
+                    return None;
+                }
+                let Some(Expression::CallExpression(call)) = &declarator.init else {
+                    return None;
+                };
+                let is_props = call.arguments.is_empty()
+                    && matches!(&call.callee, Expression::Identifier(ident) if ident.name == "$props");
+                let BindingPatternKind::ObjectPattern(pattern) = &declarator.id.kind else {
+                    return None;
+                };
+                if !is_props || pattern.rest.is_some() {
+                    return None;
+                }
+                for property in &pattern.properties {
+                    let PropertyKey::StaticIdentifier(key) = &property.key else { return None };
+                    let default = match &property.value.kind {
+                        BindingPatternKind::BindingIdentifier(ident) if ident.name == key.name => {
+                            None
+                        }
+                        BindingPatternKind::AssignmentPattern(pattern) if matches!(&pattern.left.kind, BindingPatternKind::BindingIdentifier(ident) if ident.name == key.name) => {
+                            Some(&pattern.right)
+                        }
+                        _ => return None,
+                    };
+                    props.insert(key.name.clone(), default);
+                }
+            }
+            Statement::ExportNamedDeclaration(export) => {
+                let Some(Declaration::VariableDeclaration(declaration)) = &export.declaration
+                else {
+                    return None;
+                };
+                export_let_props(declaration, &mut props)?;
+            }
+            _ => return None,
+        }
+    }
+    props.values().all(|default| default.map_or(true, is_literal)).then_some(props)
+}
+
+fn export_let_props<'r, 'a>(
+    declaration: &'r VariableDeclaration<'a>,
+    props: &mut FxHashMap<Atom<'a>, Option<&'r Expression<'a>>>,
+) -> Option<()> {
+    if declaration.kind != VariableDeclarationKind::Let {
+        return None;
+    }
+    for declarator in &declaration.declarations {
+        let BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind else {
+            return None;
+        };
+        props.insert(ident.name.clone(), declarator.init.as_ref());
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use ssc_codegen::{Codegen, CodegenOptions};
+    use ssc_parser::Parser;
+
+    use crate::Transformer;
+
+    #[test]
+    fn inline_components() {
+        let allocator = Allocator::default();
+        let source = r#"<script>import Row from "./Row.svelte"; import Card from "./Card.svelte";</script>{#each items as item}<Row label={item.label} selected /><Row label="x {y}" /><Card title={item.title} />{/each}"#;
+        let components = |specifier: &str| {
+            let source = match specifier {
+                "./Row.svelte" => {
+                    r#"<script>let { label, selected = false, kind = "row" } = $props();</script><li class={kind} aria-selected={selected}>{label}</li>"#
+                }
+                "./Card.svelte" => "<script>let { title } = $props();</script><h2>{title}</h2><style>h2 { color: red; }</style>",
+                _ => return None,
+            };
+            Some(Parser::new(&allocator, source).parse().root)
+        };
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let mut root = ret.root;
+        Transformer::new(&allocator).with_component_source(&components).build(&mut root);
+        let output =
+            Codegen::<false>::new("", source, CodegenOptions::default()).build(&root).source_text;
+        let template = output.split_once("</script>").unwrap().1.trim_start();
+        assert_eq!(
+            template,
+            r#"{#each items as item}<li class={'row'} aria-selected={true}>{item.label}</li><Row label="x {y}" /><Card title={item.title} />{/each}"#
+        );
+    }
+
+    #[test]
+    fn keep_components() {
+        let allocator = Allocator::default();
+        let components = |specifier: &str| {
+            let source = match specifier {
+                "./Row.svelte" => "<script>let { a, b = a } = $props();</script><li>{b}</li>",
+                "./Cell.svelte" => "<script>let { a } = $props();</script><td>{a}</td>",
+                _ => return None,
+            };
+            Some(Parser::new(&allocator, source).parse().root)
+        };
+        let sources = [
+            r#"<script>import Row from "./Row.svelte";</script><Row a={x} />"#,
+            r#"<script>import Cell from "./Cell.svelte";</script><Cell a={x} /><style>td { color: red; }</style>"#,
+        ];
+        for source in sources {
+            let ret = Parser::new(&allocator, source).parse();
+            assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+            let mut root = ret.root;
+            Transformer::new(&allocator).with_component_source(&components).build(&mut root);
+            let output = Codegen::<false>::new("", source, CodegenOptions::default())
+                .build(&root)
+                .source_text;
+            assert!(output.contains("a={x} />"), "{output}");
+        }
+    }
+}
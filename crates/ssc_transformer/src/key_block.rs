@@ -0,0 +1,72 @@
+use ssc_ast::{ast::*, Descend};
+
+use crate::{
+    await_block::{has_declarations, is_literal},
+    Transformer,
+};
+
+impl<'a> Transformer<'a> {
+    /// Replace the `{#key}` blocks of `fragment` whose key is a literal by
+    /// their content, `{#key 1}<p>a</p>{/key}` becomes `<p>a</p>`, as the key
+    /// never changes and the content is never re-created.
+    ///
+    /// Blocks whose elements have transitions are kept, so they keep playing
+    /// when the block is created and destroyed, rather than its parent, and
+    /// so are blocks declaring `{@const}`s or snippets, scoped to the block.
+    pub(crate) fn lower_static_key_blocks(&self, fragment: &mut Fragment<'a>) {
+        if !fragment.nodes.iter().any(is_static_key_block) {
+            return;
+        }
+        let mut nodes = self.ast.new_vec_with_capacity(fragment.nodes.len());
+        for node in fragment.nodes.drain(..) {
+            match node {
+                FragmentNode::Block(Block::KeyBlock(mut block)) if is_static_key(&block) => {
+                    nodes.extend(block.fragment.nodes.drain(..));
+                }
+                node => nodes.push(node),
+            }
+        }
+        fragment.nodes = nodes;
+    }
+}
+
+fn is_static_key_block(node: &FragmentNode) -> bool {
+    matches!(node, FragmentNode::Block(Block::KeyBlock(block)) if is_static_key(block))
+}
+
+fn is_static_key(block: &KeyBlock) -> bool {
+    is_literal(&block.expression)
+        && !has_declarations(&block.fragment)
+        && !block.fragment.iter_nodes(Descend::Elements).any(|node| {
+            let FragmentNode::Element(element) = node else { return false };
+            element.attributes().iter().any(|attribute| {
+                matches!(
+                    attribute.as_directive_attribute(),
+                    Some(DirectiveAttribute::TransitionDirective(_))
+                )
+            })
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use ssc_codegen::{Codegen, CodegenOptions};
+    use ssc_parser::Parser;
+
+    use crate::Transformer;
+
+    #[test]
+    fn static_key_blocks() {
+        let allocator = Allocator::default();
+        let source = "{#key 'a'}<p>a</p>{/key}{#key 1}<div><p transition:fade>b</p></div>{/key}{#key value}<p>c</p>{/key}{#key 2}{@const d = 1}{d}{/key}{#key 3}{#snippet e()}e{/snippet}{@render e()}{/key}";
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let mut root = ret.root;
+        Transformer::new(&allocator).build(&mut root);
+        assert_eq!(
+            Codegen::<false>::new("", source, CodegenOptions::default()).build(&root).source_text,
+            "<p>a</p>{#key 1}<div><p transition:fade>b</p></div>{/key}{#key value}<p>c</p>{/key}{#key 2}{@const d = 1}{d}{/key}{#key 3}{#snippet e()}e{/snippet}{@render e()}{/key}"
+        );
+    }
+}
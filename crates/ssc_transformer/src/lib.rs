@@ -1 +1,227 @@
+#![allow(clippy::wildcard_imports)]
 
+//! Template Transformer
+//!
+//! Lowers template constructs into simpler, equivalent ones before code
+//! generation.
+
+mod await_block;
+mod comment;
+mod dev;
+mod html_tag;
+mod inline;
+mod key_block;
+mod spread;
+mod svelte_component;
+mod svelte_element;
+mod svelte_head;
+mod svelte_self;
+mod typescript;
+mod whitespace;
+
+use std::ops::ControlFlow;
+
+use oxc_allocator::Allocator;
+use oxc_span::Atom;
+use rustc_hash::FxHashMap;
+use ssc_ast::{
+    ast::*,
+    visit::walk_mut::{walk_block_mut, walk_element_mut, walk_fragment_mut, walk_tag_mut},
+    AstBuilder, VisitMut,
+};
+
+use crate::svelte_self::SelfComponent;
+
+pub use crate::inline::ComponentSource;
+
+pub struct Transformer<'a> {
+    ast: AstBuilder<'a>,
+    js: oxc_ast::AstBuilder<'a>,
+    /// Keep the whitespace of the template as written, see
+    /// [`Transformer::with_preserve_whitespace`].
+    preserve_whitespace: bool,
+    /// Keep the HTML comments of the template, see
+    /// [`Transformer::with_preserve_comments`].
+    preserve_comments: bool,
+    /// Keep the development-only code, see [`Transformer::with_dev`].
+    dev: bool,
+    /// Whether the component is in runes mode, see [`Transformer::with_runes`].
+    runes: bool,
+    /// Whether the text being visited is in a `<pre>` or a `<textarea>`.
+    preformatted: bool,
+    /// Whether the component is `lang="ts"`, so its template expressions can
+    /// have types to strip.
+    typescript: bool,
+    /// Whether the component has a `<style>`, whose selectors would match
+    /// elements inlined from `{@html}` or from child components.
+    styled: bool,
+    /// Source of the components to inline, see
+    /// [`Transformer::with_component_source`].
+    component_source: Option<&'a dyn ComponentSource<'a>>,
+    /// Specifiers of the components imported by the scripts, by local name.
+    component_imports: FxHashMap<Atom<'a>, Atom<'a>>,
+    /// Path of the component, see [`Transformer::with_filename`].
+    filename: Option<&'a str>,
+    /// The component `<svelte:self>` is lowered to.
+    self_component: Option<SelfComponent<'a>>,
+    /// Whether a `<svelte:self>` was lowered, so the component must import
+    /// itself.
+    self_referenced: bool,
+}
+
+impl<'a> Transformer<'a> {
+    pub fn new(allocator: &'a Allocator) -> Self {
+        Self {
+            ast: AstBuilder::new(allocator),
+            js: oxc_ast::AstBuilder::new(allocator),
+            preserve_whitespace: false,
+            preserve_comments: false,
+            dev: false,
+            runes: false,
+            preformatted: false,
+            typescript: false,
+            styled: false,
+            component_source: None,
+            component_imports: FxHashMap::default(),
+            filename: None,
+            self_component: None,
+            self_referenced: false,
+        }
+    }
+
+    /// Keep the whitespace of the template as written instead of collapsing
+    /// it, as the `preserveWhitespace` compile option does.
+    /// `<svelte:options preserveWhitespace>` takes precedence over it.
+    #[must_use]
+    pub fn with_preserve_whitespace(mut self, preserve_whitespace: bool) -> Self {
+        self.preserve_whitespace = preserve_whitespace;
+        self
+    }
+
+    /// Keep the HTML comments of the template, as the `preserveComments`
+    /// compile option does. They're removed otherwise, along with the
+    /// `svelte-ignore` comments, once the component is analyzed.
+    #[must_use]
+    pub fn with_preserve_comments(mut self, preserve_comments: bool) -> Self {
+        self.preserve_comments = preserve_comments;
+        self
+    }
+
+    /// Keep `$inspect` calls and `{@debug}` tags, as the `dev` compile option
+    /// does. They're removed otherwise, since they only log in development.
+    #[must_use]
+    pub fn with_dev(mut self, dev: bool) -> Self {
+        self.dev = dev;
+        self
+    }
+
+    /// Whether the component is in runes mode, as found by the semantic
+    /// analysis, in which components are dynamic and `<svelte:component>` can
+    /// be lowered to a component. `<svelte:options runes>` takes precedence
+    /// over it.
+    #[must_use]
+    pub fn with_runes(mut self, runes: bool) -> Self {
+        self.runes = runes;
+        self
+    }
+
+    /// Inline the imported components `source` provides when they're
+    /// trivial, only rendering static markup and their props, and the
+    /// instance only passes them text or simple expressions, e.g.
+    /// `<Row label={item.label} />` is `<li class="row">{item.label}</li>`.
+    ///
+    /// Removes the overhead of component boundaries, e.g. in list rows. The
+    /// imports of inlined components are kept, for their other uses. Nothing
+    /// is inlined in a component with a `<style>`, nor from one.
+    #[must_use]
+    pub fn with_component_source(mut self, source: &'a dyn ComponentSource<'a>) -> Self {
+        self.component_source = Some(source);
+        self
+    }
+
+    /// The path of the component, from which `<svelte:self>` is lowered to a
+    /// component importing the file itself, as recommended in Svelte 5:
+    /// `<svelte:self />` in `Tree.svelte` becomes `<Tree />`, with
+    /// `import Tree from './Tree.svelte'`.
+    #[must_use]
+    pub fn with_filename(mut self, filename: &'a str) -> Self {
+        self.filename = Some(filename);
+        self
+    }
+
+    pub fn build(mut self, root: &mut Root<'a>) {
+        if let Some(preserve_whitespace) =
+            root.options.as_ref().and_then(|options| options.preserve_whitespace)
+        {
+            self.preserve_whitespace = preserve_whitespace;
+        }
+        if let Some(runes) = root.options.as_ref().and_then(|options| options.runes) {
+            self.runes = runes;
+        }
+        self.typescript = root.ts;
+        self.styled = root.css.is_some();
+        if self.component_source.is_some() {
+            self.component_imports = Self::component_imports(root);
+        }
+        if let Some(filename) = self.filename {
+            self.self_component = self.self_component(root, filename);
+        }
+        if !self.dev {
+            self.strip_dev_scripts(root);
+        }
+        self.merge_svelte_heads(&mut root.fragment);
+        let _ = self.visit_root(root);
+        self.import_self(root);
+    }
+}
+
+impl<'a> VisitMut<'a> for Transformer<'a> {
+    fn visit_fragment(&mut self, fragment: &mut Fragment<'a>) -> ControlFlow<()> {
+        if !self.dev {
+            Self::strip_debug_tags(fragment);
+        }
+        if !self.preserve_comments {
+            self.strip_comments(fragment);
+        }
+        self.inline_components(fragment);
+        self.lower_static_key_blocks(fragment);
+        self.lower_settled_await_blocks(fragment);
+        if !self.preserve_whitespace && !self.preformatted {
+            self.collapse_whitespace(fragment);
+        }
+        self.inline_static_html(fragment);
+        walk_fragment_mut(self, fragment)
+    }
+
+    fn visit_tag(&mut self, tag: &mut Tag<'a>) -> ControlFlow<()> {
+        if self.typescript {
+            self.strip_tag_types(tag);
+        }
+        walk_tag_mut(self, tag)
+    }
+
+    fn visit_block(&mut self, block: &mut Block<'a>) -> ControlFlow<()> {
+        if self.typescript {
+            self.strip_block_types(block);
+        }
+        walk_block_mut(self, block)
+    }
+
+    fn visit_element(&mut self, element: &mut Element<'a>) -> ControlFlow<()> {
+        if self.typescript {
+            self.strip_element_types(element);
+        }
+        self.lower_static_svelte_element(element);
+        self.lower_svelte_component(element);
+        self.lower_svelte_self(element);
+        self.lower_static_spreads(element.attributes_mut());
+        let Element::RegularElement(regular_element) = element else {
+            return walk_element_mut(self, element);
+        };
+        let preformatted = self.preformatted || whitespace::is_preformatted(regular_element);
+        let parent_preformatted = std::mem::replace(&mut self.preformatted, preformatted);
+        let flow = walk_element_mut(self, element);
+        self.preformatted = parent_preformatted;
+        flow
+    }
+}
@@ -0,0 +1,148 @@
+use oxc_allocator::Vec;
+use oxc_ast::ast::{Expression, ObjectExpression, ObjectPropertyKind, PropertyKey, PropertyKind};
+use oxc_span::{Atom, GetSpan};
+use rustc_hash::FxHashSet;
+use ssc_ast::ast::*;
+
+use crate::Transformer;
+
+impl<'a> Transformer<'a> {
+    /// Replace spreads of object literals with statically known keys by the
+    /// attributes they set, `{...{ id, class: x }}` becomes
+    /// `id={id} class={x}`.
+    ///
+    /// The last attribute of a name wins, as with the spread: a later
+    /// attribute overrides a key of the spread, and a key overrides an
+    /// earlier attribute.
+    pub(crate) fn lower_static_spreads(&self, attributes: &mut Vec<'a, ElementAttribute<'a>>) {
+        if !attributes.iter().any(is_static_spread) {
+            return;
+        }
+        let mut lowered = self.ast.new_vec_with_capacity(attributes.len());
+        for attribute in attributes.drain(..) {
+            let ElementAttribute::SpreadAttribute(mut spread) = attribute else {
+                lowered.push(attribute);
+                continue;
+            };
+            let Expression::ObjectExpression(object) = &mut spread.expression else {
+                lowered.push(ElementAttribute::SpreadAttribute(spread));
+                continue;
+            };
+            if !is_static_object(object) {
+                lowered.push(ElementAttribute::SpreadAttribute(spread));
+                continue;
+            }
+            // Every property is a plain one with a valid key, see `is_static_object`
+            for property in object.properties.iter_mut() {
+                let ObjectPropertyKind::ObjectProperty(property) = property else { continue };
+                let Some(name) = static_key(&property.key) else { continue };
+                let value = self.js.move_expression(&mut property.value);
+                let span = property.span;
+                let sequence = self.ast.new_vec_single(
+                    self.ast.attribute_sequence_expression_value(value.span(), value),
+                );
+                lowered.push(ElementAttribute::Attribute(self.ast.attribute(
+                    span,
+                    name,
+                    Some(self.ast.attribute_value(span, sequence)),
+                )));
+            }
+        }
+        let mut names = FxHashSet::default();
+        let mut last_wins = self.ast.new_vec_with_capacity(lowered.len());
+        for attribute in lowered.into_iter().rev() {
+            if let ElementAttribute::Attribute(attribute) = &attribute {
+                if !names.insert(attribute.name.clone()) {
+                    continue;
+                }
+            }
+            last_wins.push(attribute);
+        }
+        last_wins.reverse();
+        *attributes = last_wins;
+    }
+}
+
+fn is_static_spread(attribute: &ElementAttribute) -> bool {
+    matches!(
+        attribute,
+        ElementAttribute::SpreadAttribute(spread)
+            if matches!(&spread.expression, Expression::ObjectExpression(object) if is_static_object(object))
+    )
+}
+
+/// Whether spreading `object` is equivalent to setting each of its properties
+/// as an attribute, in order.
+///
+/// Getters, methods, computed and duplicate keys, and nested spreads all keep
+/// the generic spread.
+fn is_static_object(object: &ObjectExpression) -> bool {
+    let mut names = FxHashSet::default();
+    object.properties.iter().all(|property| match property {
+        ObjectPropertyKind::ObjectProperty(property) => {
+            property.kind == PropertyKind::Init
+                && !property.method
+                && !property.computed
+                && static_key(&property.key).is_some_and(|name| names.insert(name))
+        }
+        ObjectPropertyKind::SpreadProperty(_) => false,
+    })
+}
+
+/// The key of a property, if it can be used as an attribute name as is.
+fn static_key<'a>(key: &PropertyKey<'a>) -> Option<Atom<'a>> {
+    let name = match key {
+        PropertyKey::StaticIdentifier(ident) => ident.name.clone(),
+        PropertyKey::StringLiteral(literal) => literal.value.clone(),
+        _ => return None,
+    };
+    // Integer keys are reordered by JS objects, `__proto__` sets the
+    // prototype, and a `:` would turn the attribute into a directive
+    let valid = !name.is_empty()
+        && name.as_str() != "__proto__"
+        && !name.starts_with(|ch: char| ch.is_ascii_digit())
+        && !name.contains(|ch: char| {
+            ch.is_whitespace() || matches!(ch, ':' | '"' | '\'' | '>' | '/' | '=' | '{' | '}')
+        });
+    valid.then_some(name)
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use ssc_codegen::{Codegen, CodegenOptions};
+    use ssc_parser::Parser;
+
+    use crate::Transformer;
+
+    fn transform(source: &str) -> String {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let mut root = ret.root;
+        Transformer::new(&allocator).build(&mut root);
+        Codegen::<false>::new("", source, CodegenOptions::default()).build(&root).source_text
+    }
+
+    #[test]
+    fn static_spreads() {
+        assert_eq!(
+            transform(r#"<div {...{ class: x, "aria-label": label, id }} title="t"></div>"#),
+            r#"<div class={x} aria-label={label} id={id} title="t"></div>"#
+        );
+        assert_eq!(
+            transform(r#"<div title="a" {...rest} {...{ title: b, id }} id="c"></div>"#),
+            r#"<div {...rest} title={b} id="c"></div>"#
+        );
+        for source in [
+            "<div {...props}></div>",
+            "<div {...{ [key]: x }}></div>",
+            "<div {...{ a: 1, a: 2 }}></div>",
+            "<div {...{ ...rest, a }}></div>",
+            "<div {...{ get a() { return 1 } }}></div>",
+            r#"<div {...{ "on:click": f }}></div>"#,
+        ] {
+            assert!(transform(source).contains("{..."), "{source}");
+        }
+    }
+}
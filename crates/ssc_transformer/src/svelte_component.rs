@@ -0,0 +1,81 @@
+use std::mem;
+
+use oxc_ast::ast::Expression;
+use oxc_span::Atom;
+use ssc_ast::ast::*;
+
+use crate::Transformer;
+
+impl<'a> Transformer<'a> {
+    /// Replace a `<svelte:component>` whose `this` is a component name by a
+    /// component of that name in runes mode, where components are dynamic:
+    /// `<svelte:component this={Icon} />` becomes `<Icon />`, and
+    /// `this={icons.Check}` becomes `<icons.Check />`.
+    ///
+    /// Outside of runes mode a component tag doesn't follow changes of the
+    /// variable, so `<svelte:component>` is kept.
+    pub(crate) fn lower_svelte_component(&self, element: &mut Element<'a>) {
+        if !self.runes {
+            return;
+        }
+        let Element::SvelteComponent(component) = element else { return };
+        let Some(name) = self.component_name(&component.expression) else { return };
+        let attributes = mem::replace(&mut component.attributes, self.ast.new_vec());
+        let fragment =
+            mem::replace(&mut component.fragment, self.ast.fragment(self.ast.new_vec(), false));
+        *element = self.ast.component(component.span, name, attributes, fragment);
+    }
+
+    /// The tag name of the component `expression` evaluates to, if it's a
+    /// capitalized identifier, or a static member chain of identifiers.
+    fn component_name(&self, expression: &Expression<'a>) -> Option<Atom<'a>> {
+        match expression {
+            Expression::Identifier(ident)
+                if ident.name.starts_with(|ch: char| ch.is_ascii_uppercase()) =>
+            {
+                Some(ident.name.clone())
+            }
+            Expression::StaticMemberExpression(_) => {
+                let mut names = vec![];
+                let mut expression = expression;
+                while let Expression::StaticMemberExpression(member) = expression {
+                    names.push(member.property.name.as_str());
+                    expression = &member.object;
+                }
+                let Expression::Identifier(ident) = expression else { return None };
+                names.push(ident.name.as_str());
+                names.reverse();
+                Some(Atom::from(self.ast.new_str(&names.join("."))))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use ssc_codegen::{Codegen, CodegenOptions};
+    use ssc_parser::Parser;
+
+    use crate::Transformer;
+
+    fn transform(source: &str, runes: bool) -> String {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let mut root = ret.root;
+        Transformer::new(&allocator).with_runes(runes).build(&mut root);
+        Codegen::<false>::new("", source, CodegenOptions::default()).build(&root).source_text
+    }
+
+    #[test]
+    fn svelte_component() {
+        let source = "<svelte:component this={Icon} size={2}>a</svelte:component><svelte:component this={icons.Check} /><svelte:component this={icon} /><svelte:component this={get()} />";
+        assert_eq!(
+            transform(source, true),
+            "<Icon size={2}>a</Icon><icons.Check /><svelte:component this={icon} /><svelte:component this={get()} />"
+        );
+        assert_eq!(transform(source, false), source);
+    }
+}
@@ -0,0 +1,64 @@
+use std::mem;
+
+use oxc_ast::ast::Expression;
+use oxc_span::Atom;
+use ssc_ast::ast::*;
+
+use crate::Transformer;
+
+impl<'a> Transformer<'a> {
+    /// Replace a `<svelte:element>` whose `this` is a string literal by the
+    /// regular element it always renders, `<svelte:element this="h1">`
+    /// becomes `<h1>`.
+    pub(crate) fn lower_static_svelte_element(&self, element: &mut Element<'a>) {
+        let Element::SvelteElement(svelte_element) = element else { return };
+        let Expression::StringLiteral(literal) = &svelte_element.expression else { return };
+        let name = literal.value.clone();
+        if !is_static_tag_name(&name)
+            || (ssc_ast::html::is_void(&name) && !svelte_element.fragment.nodes.is_empty())
+        {
+            // Void elements with children are left to the runtime, which
+            // reports them in dev mode
+            return;
+        }
+        let attributes = mem::replace(&mut svelte_element.attributes, self.ast.new_vec());
+        let fragment = mem::replace(
+            &mut svelte_element.fragment,
+            self.ast.fragment(self.ast.new_vec(), false),
+        );
+        *element = self.ast.regular_element(svelte_element.span, name, attributes, fragment);
+    }
+}
+
+/// Whether `name` is a tag name that parses back to a regular element.
+///
+/// Uppercase names would be components, and names with a `:` special
+/// elements.
+fn is_static_tag_name(name: &Atom) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|ch| ch.is_ascii_lowercase())
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '.' | '_'))
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use ssc_codegen::{Codegen, CodegenOptions};
+    use ssc_parser::Parser;
+
+    use crate::Transformer;
+
+    #[test]
+    fn static_svelte_element() {
+        let allocator = Allocator::default();
+        let source = r#"<svelte:element this="svg" class="a"><svelte:element this={"circle"} /><foreignObject><p></p></foreignObject></svelte:element><svelte:element this={tag}></svelte:element><svelte:element this="Foo"></svelte:element><svelte:element this="br">x</svelte:element>"#;
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let mut root = ret.root;
+        Transformer::new(&allocator).build(&mut root);
+        assert_eq!(
+            Codegen::<false>::new("", source, CodegenOptions::default()).build(&root).source_text,
+            r#"<svg class="a"><circle></circle><foreignObject><p></p></foreignObject></svg><svelte:element this={tag} /><svelte:element this={'Foo'} /><svelte:element this={'br'}>x</svelte:element>"#
+        );
+    }
+}
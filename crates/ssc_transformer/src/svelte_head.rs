@@ -0,0 +1,112 @@
+use std::fmt::Write as _;
+
+use rustc_hash::FxHashSet;
+use ssc_ast::ast::*;
+
+use crate::Transformer;
+
+impl<'a> Transformer<'a> {
+    /// Merge the `<svelte:head>` elements of the top level `fragment` into
+    /// the first one, then drop the content the head would get twice: static
+    /// elements identical to an earlier one, e.g. the same
+    /// `<meta name="robots" content="noindex">`, and static `<title>`s
+    /// followed by another `<title>`, which the server and the client both
+    /// replace by the last one.
+    ///
+    /// Copies rendered by other instances of the component are left to the
+    /// runtime, which knows how many there are.
+    pub(crate) fn merge_svelte_heads(&self, fragment: &mut Fragment<'a>) {
+        let mut head_index = None;
+        let mut nodes = self.ast.new_vec_with_capacity(fragment.nodes.len());
+        for node in fragment.nodes.drain(..) {
+            let FragmentNode::Element(Element::SvelteHead(mut head)) = node else {
+                nodes.push(node);
+                continue;
+            };
+            let Some(index) = head_index else {
+                head_index = Some(nodes.len());
+                nodes.push(FragmentNode::Element(Element::SvelteHead(head)));
+                continue;
+            };
+            if let FragmentNode::Element(Element::SvelteHead(first)) = &mut nodes[index] {
+                first.fragment.nodes.extend(head.fragment.nodes.drain(..));
+            }
+        }
+        fragment.nodes = nodes;
+        let Some(index) = head_index else { return };
+        if let FragmentNode::Element(Element::SvelteHead(head)) = &mut fragment.nodes[index] {
+            dedupe_head(&mut head.fragment);
+        }
+    }
+}
+
+fn dedupe_head(fragment: &mut Fragment) {
+    let last_title = fragment
+        .nodes
+        .iter()
+        .rposition(|node| matches!(node, FragmentNode::Element(Element::TitleElement(_))));
+    let mut seen = FxHashSet::default();
+    let mut index = 0;
+    fragment.nodes.retain(|node| {
+        let current = index;
+        index += 1;
+        let FragmentNode::Element(element) = node else { return true };
+        match element {
+            Element::TitleElement(title) => {
+                Some(current) == last_title || !is_text(&title.fragment)
+            }
+            Element::RegularElement(element) => {
+                static_key(element).map_or(true, |key| seen.insert(key))
+            }
+            _ => true,
+        }
+    });
+}
+
+fn is_text(fragment: &Fragment) -> bool {
+    fragment.nodes.iter().all(|node| matches!(node, FragmentNode::Text(_)))
+}
+
+/// A key equal for static elements that render the same markup, `None` if
+/// the element has expressions, directives or child elements.
+fn static_key(element: &RegularElement) -> Option<String> {
+    let mut key = element.name.to_string();
+    for attribute in &element.attributes {
+        let ElementAttribute::Attribute(attribute) = attribute else { return None };
+        write!(key, " {}", attribute.name).unwrap();
+        let Some(value) = &attribute.value else { continue };
+        let [AttributeSequenceValue::Text(text)] = value.sequence.as_slice() else {
+            return None;
+        };
+        write!(key, "={:?}", text.data.as_str()).unwrap();
+    }
+    key.push('>');
+    for node in &element.fragment.nodes {
+        let FragmentNode::Text(text) = node else { return None };
+        key.push_str(&text.data);
+    }
+    Some(key)
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use ssc_codegen::{Codegen, CodegenOptions};
+    use ssc_parser::Parser;
+
+    use crate::Transformer;
+
+    #[test]
+    fn merge_svelte_heads() {
+        let allocator = Allocator::default();
+        let source = r#"<svelte:head><title>a</title><meta name="robots" content="noindex"></svelte:head><p>a</p><svelte:head><meta name="robots" content="noindex"><meta name="description" content={description}><title>{title}</title></svelte:head>"#;
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let mut root = ret.root;
+        Transformer::new(&allocator).build(&mut root);
+        assert_eq!(
+            Codegen::<false>::new("", source, CodegenOptions::default()).build(&root).source_text,
+            r#"<svelte:head><meta name="robots" content="noindex" /><meta name="description" content={description} /><title>{title}</title></svelte:head><p>a</p>"#
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use std::mem;
+
+use oxc_ast::{
+    ast::{
+        BindingIdentifier, ImportDeclarationSpecifier, ImportDefaultSpecifier, ImportOrExportKind,
+        ModuleDeclaration, Statement, StringLiteral,
+    },
+    syntax_directed_operations::BoundNames,
+};
+use oxc_span::{Atom, SourceType, SPAN};
+use ssc_ast::ast::*;
+
+use crate::Transformer;
+
+/// The component `<svelte:self>` is lowered to, see
+/// [`Transformer::with_filename`].
+pub(crate) struct SelfComponent<'a> {
+    pub name: Atom<'a>,
+    pub specifier: Atom<'a>,
+    /// Whether a script already imports the component under `name`.
+    pub imported: bool,
+}
+
+impl<'a> Transformer<'a> {
+    /// The component `<svelte:self>` of `root` refers to, named after
+    /// `filename` as the reference compiler does: `Tree.svelte` is `Tree`,
+    /// and `tree/index.svelte` is `Tree`.
+    ///
+    /// `None` if the name isn't a valid component name, or the scripts
+    /// declare it for something else.
+    pub(crate) fn self_component(
+        &self,
+        root: &Root<'a>,
+        filename: &str,
+    ) -> Option<SelfComponent<'a>> {
+        let mut parts = filename.rsplit(['/', '\\']);
+        let basename = parts.next()?;
+        let mut stem = basename.split('.').next()?;
+        if stem == "index" {
+            stem = parts.next().filter(|dir| !dir.is_empty() && *dir != "src").unwrap_or(stem);
+        }
+        let mut chars = stem.chars();
+        let first = chars.next().filter(char::is_ascii_alphabetic)?;
+        if !chars.all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '$')) {
+            return None;
+        }
+        let name = format!("{}{}", first.to_ascii_uppercase(), &stem[1..]);
+        let name = Atom::from(self.ast.new_str(&name));
+        let specifier = Atom::from(self.ast.new_str(&format!("./{basename}")));
+
+        let mut imported = false;
+        let mut declared = false;
+        let programs = [&root.module, &root.instance].into_iter().flatten();
+        for statement in programs.flat_map(|script| script.program.body.iter()) {
+            let mut check = |ident: &BindingIdentifier<'a>| declared |= ident.name == name;
+            match statement {
+                Statement::ImportDeclaration(import) => {
+                    let is_self_import = !import.import_kind.is_type()
+                        && import.source.value == specifier
+                        && import.specifiers.iter().flatten().any(|import_specifier| {
+                            matches!(
+                                import_specifier,
+                                ImportDeclarationSpecifier::ImportDefaultSpecifier(default)
+                                    if default.local.name == name
+                            )
+                        });
+                    if is_self_import {
+                        imported = true;
+                    } else {
+                        import.bound_names(&mut check);
+                    }
+                }
+                Statement::VariableDeclaration(decl) => decl.bound_names(&mut check),
+                Statement::FunctionDeclaration(function) => function.bound_names(&mut check),
+                Statement::ClassDeclaration(class) => class.bound_names(&mut check),
+                Statement::ExportNamedDeclaration(decl) => {
+                    if let Some(declaration) = &decl.declaration {
+                        declaration.bound_names(&mut check);
+                    }
+                }
+                _ => {}
+            }
+        }
+        if declared {
+            return None;
+        }
+        Some(SelfComponent { name, specifier, imported })
+    }
+
+    /// Replace a `<svelte:self>` by the component importing the file itself,
+    /// `<svelte:self depth={depth - 1} />` in `Tree.svelte` becomes
+    /// `<Tree depth={depth - 1} />`, which the client and the server render
+    /// like any other component.
+    pub(crate) fn lower_svelte_self(&mut self, element: &mut Element<'a>) {
+        let Some(component) = &self.self_component else { return };
+        let Element::SvelteSelf(svelte_self) = element else { return };
+        let name = component.name.clone();
+        self.self_referenced = true;
+        let attributes = mem::replace(&mut svelte_self.attributes, self.ast.new_vec());
+        let fragment =
+            mem::replace(&mut svelte_self.fragment, self.ast.fragment(self.ast.new_vec(), false));
+        *element = self.ast.component(svelte_self.span, name, attributes, fragment);
+    }
+
+    /// Import the component from its own file when `<svelte:self>` was
+    /// lowered, in the instance script, which is added if there is none.
+    pub(crate) fn import_self(&self, root: &mut Root<'a>) {
+        let Some(component) = &self.self_component else { return };
+        if !self.self_referenced || component.imported {
+            return;
+        }
+        let local = BindingIdentifier::new(SPAN, component.name.clone());
+        let specifier = ImportDeclarationSpecifier::ImportDefaultSpecifier(
+            self.js.alloc(ImportDefaultSpecifier { span: SPAN, local }),
+        );
+        let import = self.js.import_declaration(
+            SPAN,
+            Some(self.js.new_vec_single(specifier)),
+            StringLiteral::new(SPAN, component.specifier.clone()),
+            None,
+            ImportOrExportKind::Value,
+        );
+        let statement = self.js.module_declaration(ModuleDeclaration::ImportDeclaration(import));
+        if let Some(instance) = &mut root.instance {
+            instance.program.body.insert(0, statement);
+            return;
+        }
+        let program = self.js.program(
+            SPAN,
+            SourceType::default().with_typescript(root.ts),
+            self.js.new_vec(),
+            None,
+            self.js.new_vec_single(statement),
+        );
+        root.instance =
+            Some(self.ast.script(SPAN, ScriptContext::Default, program, self.ast.new_vec(), None));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use ssc_codegen::{Codegen, CodegenOptions};
+    use ssc_parser::Parser;
+
+    use crate::Transformer;
+
+    fn transform(source: &str, filename: &str) -> String {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let mut root = ret.root;
+        Transformer::new(&allocator).with_filename(filename).build(&mut root);
+        Codegen::<false>::new("", source, CodegenOptions::default()).build(&root).source_text
+    }
+
+    #[test]
+    fn svelte_self() {
+        let source = "{#if depth}<svelte:self depth={depth - 1}>a</svelte:self>{/if}";
+        let output = transform(source, "src/lib/Tree.svelte");
+        assert!(
+            output.contains("import Tree from ") && output.contains("/Tree.svelte"),
+            "{output}"
+        );
+        assert!(output.ends_with("{#if depth}<Tree depth={depth - 1}>a</Tree>{/if}"), "{output}");
+
+        let output = transform(source, "src/lib/folder/index.svelte");
+        assert!(
+            output.contains("import Folder from ") && output.contains("/index.svelte"),
+            "{output}"
+        );
+
+        // Already imported
+        let source =
+            "<script>import Tree from './Tree.svelte';</script>{#if depth}<svelte:self />{/if}";
+        let output = transform(source, "Tree.svelte");
+        assert_eq!(output.matches("import Tree").count(), 1, "{output}");
+        assert!(output.contains("<Tree />"), "{output}");
+
+        // The name is taken
+        let source = "<script>let Tree = 1;</script>{#if depth}<svelte:self />{/if}";
+        assert!(transform(source, "Tree.svelte").contains("<svelte:self />"));
+        assert!(
+            transform("{#if a}<svelte:self />{/if}", "+page.svelte").contains("<svelte:self />")
+        );
+    }
+}
@@ -0,0 +1,259 @@
+use oxc_ast::{
+    ast::{
+        ArrowFunctionExpression, BindingPattern, CallExpression, Expression, FormalParameter,
+        Function, NewExpression, SimpleAssignmentTarget, TaggedTemplateExpression,
+    },
+    visit::walk_mut::{
+        walk_arrow_expression_mut, walk_binding_pattern_mut, walk_call_expression_mut,
+        walk_expression_mut, walk_formal_parameter_mut, walk_function_mut, walk_new_expression_mut,
+        walk_simple_assignment_target_mut, walk_tagged_template_expression_mut,
+    },
+    VisitMut,
+};
+use oxc_syntax::scope::ScopeFlags;
+use ssc_ast::ast::*;
+
+use crate::Transformer;
+
+impl<'a> Transformer<'a> {
+    /// Strip the types of the expressions of `element` and its attributes,
+    /// see [`TypeStripper`].
+    pub(crate) fn strip_element_types(&self, element: &mut Element<'a>) {
+        let mut stripper = TypeStripper { js: self.js };
+        match element {
+            Element::SvelteComponent(element) => stripper.visit_expression(&mut element.expression),
+            Element::SvelteElement(element) => stripper.visit_expression(&mut element.expression),
+            _ => {}
+        }
+        for attribute in element.attributes_mut().iter_mut() {
+            match attribute {
+                ElementAttribute::Attribute(attribute) => {
+                    if let Some(value) = &mut attribute.value {
+                        stripper.visit_attribute_value(value);
+                    }
+                }
+                ElementAttribute::SpreadAttribute(spread) => {
+                    stripper.visit_expression(&mut spread.expression);
+                }
+                ElementAttribute::DirectiveAttribute(directive) => match directive {
+                    DirectiveAttribute::AnimateDirective(AnimateDirective {
+                        expression, ..
+                    })
+                    | DirectiveAttribute::OnDirective(OnDirective { expression, .. })
+                    | DirectiveAttribute::TransitionDirective(TransitionDirective {
+                        expression,
+                        ..
+                    })
+                    | DirectiveAttribute::UseDirective(UseDirective { expression, .. }) => {
+                        if let Some(expression) = expression {
+                            stripper.visit_expression(expression);
+                        }
+                    }
+                    DirectiveAttribute::ClassDirective(directive) => {
+                        stripper.visit_expression(&mut directive.expression);
+                    }
+                    DirectiveAttribute::StyleDirective(directive) => {
+                        if let Some(value) = &mut directive.value {
+                            stripper.visit_attribute_value(value);
+                        }
+                    }
+                    // Only identifiers, member and literal expressions
+                    DirectiveAttribute::BindDirective(_) | DirectiveAttribute::LetDirective(_) => {}
+                },
+            }
+        }
+    }
+
+    pub(crate) fn strip_tag_types(&self, tag: &mut Tag<'a>) {
+        let mut stripper = TypeStripper { js: self.js };
+        match tag {
+            Tag::ExpressionTag(tag) => stripper.visit_expression(&mut tag.expression),
+            Tag::HtmlTag(tag) => stripper.visit_expression(&mut tag.expression),
+            Tag::ConstTag(tag) => stripper.visit_variable_declaration(&mut tag.declaration),
+            Tag::RenderTag(tag) => match &mut tag.expression {
+                RenderTagExpression::Call(call) | RenderTagExpression::Chain(call) => {
+                    stripper.visit_call_expression(call);
+                }
+            },
+            Tag::DebugTag(_) => {}
+        }
+    }
+
+    pub(crate) fn strip_block_types(&self, block: &mut Block<'a>) {
+        let mut stripper = TypeStripper { js: self.js };
+        match block {
+            Block::EachBlock(block) => {
+                stripper.visit_expression(&mut block.expression);
+                stripper.visit_binding_pattern(&mut block.context);
+                if let Some(key) = &mut block.key {
+                    stripper.visit_expression(key);
+                }
+            }
+            Block::IfBlock(block) => stripper.visit_expression(&mut block.test),
+            Block::AwaitBlock(block) => {
+                stripper.visit_expression(&mut block.expression);
+                for pattern in [&mut block.value, &mut block.error].into_iter().flatten() {
+                    stripper.visit_binding_pattern(pattern);
+                }
+            }
+            Block::KeyBlock(block) => stripper.visit_expression(&mut block.expression),
+            Block::SnippetBlock(block) => {
+                for parameter in block.parameters.iter_mut() {
+                    stripper.visit_binding_pattern(parameter);
+                }
+            }
+        }
+    }
+}
+
+/// Removes the TypeScript syntax of template expressions, e.g. `{a as B}` is
+/// `{a}` and `{#snippet row(item: Item)}` is `{#snippet row(item)}`, which
+/// leaves plain JS with the same runtime behavior.
+struct TypeStripper<'a> {
+    js: oxc_ast::AstBuilder<'a>,
+}
+
+impl<'a> TypeStripper<'a> {
+    fn visit_attribute_value(&mut self, value: &mut AttributeValue<'a>) {
+        for part in value.sequence.iter_mut() {
+            if let AttributeSequenceValue::ExpressionTag(tag) = part {
+                self.visit_expression(&mut tag.expression);
+            }
+        }
+    }
+}
+
+impl<'a> VisitMut<'a> for TypeStripper<'a> {
+    fn visit_expression(&mut self, expr: &mut Expression<'a>) {
+        loop {
+            let inner = match expr {
+                Expression::TSAsExpression(e) => &mut e.expression,
+                Expression::TSSatisfiesExpression(e) => &mut e.expression,
+                Expression::TSNonNullExpression(e) => &mut e.expression,
+                Expression::TSTypeAssertion(e) => &mut e.expression,
+                Expression::TSInstantiationExpression(e) => &mut e.expression,
+                _ => break,
+            };
+            *expr = self.js.move_expression(inner);
+        }
+        walk_expression_mut(self, expr);
+    }
+
+    fn visit_simple_assignment_target(&mut self, target: &mut SimpleAssignmentTarget<'a>) {
+        let inner = match target {
+            SimpleAssignmentTarget::TSAsExpression(e) => Some(&mut e.expression),
+            SimpleAssignmentTarget::TSSatisfiesExpression(e) => Some(&mut e.expression),
+            SimpleAssignmentTarget::TSNonNullExpression(e) => Some(&mut e.expression),
+            SimpleAssignmentTarget::TSTypeAssertion(e) => Some(&mut e.expression),
+            _ => None,
+        };
+        if let Some(inner) = inner {
+            self.visit_expression(inner);
+            // Only identifiers and member expressions are valid targets
+            *target = match self.js.move_expression(inner) {
+                Expression::Identifier(ident) => {
+                    SimpleAssignmentTarget::AssignmentTargetIdentifier(ident)
+                }
+                Expression::ComputedMemberExpression(e) => {
+                    SimpleAssignmentTarget::ComputedMemberExpression(e)
+                }
+                Expression::StaticMemberExpression(e) => {
+                    SimpleAssignmentTarget::StaticMemberExpression(e)
+                }
+                Expression::PrivateFieldExpression(e) => {
+                    SimpleAssignmentTarget::PrivateFieldExpression(e)
+                }
+                expression => {
+                    *inner = expression;
+                    return;
+                }
+            };
+            return;
+        }
+        walk_simple_assignment_target_mut(self, target);
+    }
+
+    fn visit_binding_pattern(&mut self, pat: &mut BindingPattern<'a>) {
+        pat.type_annotation = None;
+        pat.optional = false;
+        walk_binding_pattern_mut(self, pat);
+    }
+
+    fn visit_formal_parameter(&mut self, param: &mut FormalParameter<'a>) {
+        param.accessibility = None;
+        param.readonly = false;
+        param.r#override = false;
+        walk_formal_parameter_mut(self, param);
+    }
+
+    fn visit_function(&mut self, func: &mut Function<'a>, flags: Option<ScopeFlags>) {
+        func.type_parameters = None;
+        func.this_param = None;
+        func.return_type = None;
+        walk_function_mut(self, func, flags);
+    }
+
+    fn visit_arrow_expression(&mut self, expr: &mut ArrowFunctionExpression<'a>) {
+        expr.type_parameters = None;
+        expr.return_type = None;
+        walk_arrow_expression_mut(self, expr);
+    }
+
+    fn visit_call_expression(&mut self, expr: &mut CallExpression<'a>) {
+        expr.type_parameters = None;
+        walk_call_expression_mut(self, expr);
+    }
+
+    fn visit_new_expression(&mut self, expr: &mut NewExpression<'a>) {
+        expr.type_parameters = None;
+        walk_new_expression_mut(self, expr);
+    }
+
+    fn visit_tagged_template_expression(&mut self, expr: &mut TaggedTemplateExpression<'a>) {
+        expr.type_parameters = None;
+        walk_tagged_template_expression_mut(self, expr);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use ssc_codegen::{Codegen, CodegenOptions};
+    use ssc_parser::Parser;
+
+    use crate::Transformer;
+
+    fn transform(template: &str) -> String {
+        let allocator = Allocator::default();
+        let source = format!("<script lang=\"ts\"></script>{template}");
+        let ret = Parser::new(&allocator, &source).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let mut root = ret.root;
+        Transformer::new(&allocator).build(&mut root);
+        let output =
+            Codegen::<false>::new("", &source, CodegenOptions::default()).build(&root).source_text;
+        output.split_once("</script>").unwrap().1.trim_start().to_string()
+    }
+
+    #[test]
+    fn strip_types() {
+        assert_eq!(
+            transform("<p title={a as string}>{b!.c satisfies C}{f<T>(x)}</p>"),
+            "<p title={a}>{b.c}{f(x)}</p>"
+        );
+        assert_eq!(
+            transform(
+                "{#each items as item: Item}{@const n: number = item.n}<button on:click={(e: Event): void => (x! = n)}></button>{/each}"
+            ),
+            "{#each items as item}{@const n = item.n}<button on:click={(e) => x = n}></button>{/each}"
+        );
+        assert_eq!(
+            transform("{#snippet row(item: Item, i?: number)}{/snippet}"),
+            "{#snippet row(item, i)}{/snippet}"
+        );
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, "<p>{a as string}</p>").parse();
+        assert!(!ret.errors.is_empty());
+    }
+}
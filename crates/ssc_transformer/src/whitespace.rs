@@ -0,0 +1,102 @@
+use oxc_span::Atom;
+use ssc_ast::{ast::*, html};
+
+use crate::Transformer;
+
+impl<'a> Transformer<'a> {
+    /// Collapse the whitespace of the text of `fragment` the way HTML renders
+    /// it: runs of whitespace become a single space, whitespace at the start
+    /// and end of the fragment is removed, and text left empty is dropped.
+    pub(crate) fn collapse_whitespace(&self, fragment: &mut Fragment<'a>) {
+        let last = fragment.nodes.len().saturating_sub(1);
+        for (index, node) in fragment.nodes.iter_mut().enumerate() {
+            let FragmentNode::Text(text) = node else { continue };
+            let mut data = collapse(&text.data);
+            if index == 0 {
+                data = data.trim_start_matches(' ').to_string();
+            }
+            if index == last {
+                data.truncate(data.trim_end_matches(' ').len());
+            }
+            if data != text.data.as_str() {
+                let data = Atom::from(self.ast.new_str(&data));
+                text.data = data.clone();
+                text.raw = data;
+            }
+        }
+        fragment
+            .nodes
+            .retain(|node| !matches!(node, FragmentNode::Text(text) if text.data.is_empty()));
+    }
+}
+
+/// Whether the whitespace of the children of `element` is kept as is, either
+/// because it's rendered or because the content is raw text, e.g. a script
+/// where a newline ends a line comment.
+pub(crate) fn is_preformatted(element: &RegularElement) -> bool {
+    html::is_preformatted(&element.name) || html::is_raw_text(&element.name)
+}
+
+fn collapse(data: &str) -> String {
+    let mut collapsed = String::with_capacity(data.len());
+    let mut whitespace = false;
+    for ch in data.chars() {
+        if matches!(ch, ' ' | '\t' | '\n' | '\r' | '\x0c') {
+            whitespace = true;
+            continue;
+        }
+        if whitespace {
+            collapsed.push(' ');
+            whitespace = false;
+        }
+        collapsed.push(ch);
+    }
+    if whitespace {
+        collapsed.push(' ');
+    }
+    collapsed
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use ssc_codegen::{Codegen, CodegenOptions};
+    use ssc_parser::Parser;
+
+    use crate::Transformer;
+
+    fn transform(source: &str, preserve_whitespace: bool) -> String {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let mut root = ret.root;
+        Transformer::new(&allocator).with_preserve_whitespace(preserve_whitespace).build(&mut root);
+        Codegen::<false>::new("", source, CodegenOptions::default()).build(&root).source_text
+    }
+
+    #[test]
+    fn collapse_whitespace() {
+        let source = "\n<p>\n  Hello,\t{name}  !\n</p>\n\n<pre>  a\n  b</pre>\n";
+        assert_eq!(transform(source, false), "<p>Hello, {name} !</p> <pre>  a\n  b</pre>");
+        assert_eq!(transform(source, true), source.trim_end());
+
+        let source = "<svelte:options preserveWhitespace />\n<p> a </p>";
+        assert_eq!(transform(source, false), source);
+        let source = "<svelte:options preserveWhitespace={false} />\n<p> a </p>";
+        assert_eq!(
+            transform(source, true),
+            "<svelte:options preserveWhitespace={false} /> <p>a</p>"
+        );
+    }
+
+    #[test]
+    fn raw_text_elements() {
+        let source = "<svelte:head>\n  <script>\n    // a\n    b();\n  </script>\n</svelte:head>";
+        assert_eq!(
+            transform(source, false),
+            "<svelte:head><script>\n    // a\n    b();\n  </script></svelte:head>"
+        );
+        let source = "<div> <style>\n  p { color: red }\n</style> </div>";
+        assert_eq!(transform(source, false), "<div><style>\n  p { color: red }\n</style></div>");
+    }
+}
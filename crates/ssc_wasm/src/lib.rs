@@ -12,6 +12,7 @@ use ssc::{
     codegen::{Codegen, CodegenOptions},
     diagnostics::Error,
     parser::Parser,
+    transformer::Transformer,
 };
 use tsify::Tsify;
 use wasm_bindgen::prelude::*;
@@ -35,17 +36,24 @@ pub struct Ssc {
     #[serde(rename = "codegenText")]
     pub codegen_text: String,
 
-    comments: Vec<Comment>,
+    comments: Vec<TriviaComment>,
 
     diagnostics: RefCell<Vec<Error>>,
 
     #[serde(skip)]
     serializer: serde_wasm_bindgen::Serializer,
+
+    /// The arena of every run, reset instead of dropped between runs so the
+    /// wasm memory stops growing after the first few edits.
+    #[serde(skip)]
+    allocator: Allocator,
 }
 
+/// A comment of the trivias, not to be confused with the `Comment` nodes of
+/// the AST.
 #[derive(Clone, Tsify, Serialize)]
 #[tsify(into_wasm_abi)]
-pub struct Comment {
+pub struct TriviaComment {
     pub value: String,
     pub start: u32,
     pub end: u32,
@@ -77,6 +85,18 @@ impl Ssc {
         self.source_text = source_text;
     }
 
+    /// Release the memory kept for the next run.
+    #[wasm_bindgen]
+    pub fn reset(&mut self) {
+        self.allocator = Allocator::default();
+    }
+
+    /// Bytes of memory kept for the next run.
+    #[wasm_bindgen(getter = allocatedBytes)]
+    pub fn allocated_bytes(&self) -> usize {
+        self.allocator.allocated_bytes()
+    }
+
     /// Returns Array of String
     /// # Errors
     /// # Panics
@@ -120,10 +140,21 @@ impl Ssc {
     ) -> Result<(), serde_wasm_bindgen::Error> {
         self.diagnostics = RefCell::default();
 
-        let allocator = Allocator::default();
+        let mut allocator = std::mem::take(&mut self.allocator);
+        allocator.reset();
+        let ret = self.run_with(&allocator, *codegen_options);
+        self.allocator = allocator;
+        ret
+    }
+
+    fn run_with(
+        &mut self,
+        allocator: &Allocator,
+        codegen_options: SscCodegenOptions,
+    ) -> Result<(), serde_wasm_bindgen::Error> {
         let source_text = &self.source_text;
 
-        let ret = Parser::new(&allocator, source_text).parse();
+        let ret = Parser::new(allocator, source_text).parse();
 
         self.comments = self.map_comments(&ret.trivias);
         self.save_diagnostics(ret.errors.into_iter().map(Error::from).collect::<Vec<_>>());
@@ -134,8 +165,16 @@ impl Ssc {
 
         self.ast = root.serialize(&self.serializer)?;
 
+        if codegen_options.transform {
+            Transformer::new(allocator)
+                .with_preserve_whitespace(codegen_options.preserve_whitespace)
+                .with_preserve_comments(codegen_options.preserve_comments)
+                .build(root);
+        }
+
         let options = CodegenOptions {
             enable_typescript: codegen_options.enable_typescript,
+            preserve_comments: codegen_options.preserve_comments,
             ..CodegenOptions::default()
         };
         self.codegen_text = if codegen_options.whitespace {
@@ -151,10 +190,10 @@ impl Ssc {
         self.diagnostics.borrow_mut().extend(diagnostics);
     }
 
-    fn map_comments(&self, trivias: &Trivias) -> Vec<Comment> {
+    fn map_comments(&self, trivias: &Trivias) -> Vec<TriviaComment> {
         trivias
             .comments()
-            .map(|span| Comment {
+            .map(|span| TriviaComment {
                 value: span.source_text(&self.source_text).to_string(),
                 start: span.start,
                 end: span.end,
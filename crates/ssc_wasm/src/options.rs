@@ -7,6 +7,14 @@ pub struct SscCodegenOptions {
     #[wasm_bindgen(js_name = enableTypescript)]
     pub enable_typescript: bool,
     pub whitespace: bool,
+    #[wasm_bindgen(js_name = preserveComments)]
+    pub preserve_comments: bool,
+    /// Print the template lowered by the transformer, as it's compiled.
+    pub transform: bool,
+    /// Keep the whitespace of the lowered template as written instead of
+    /// collapsing it.
+    #[wasm_bindgen(js_name = preserveWhitespace)]
+    pub preserve_whitespace: bool,
 }
 
 #[wasm_bindgen]
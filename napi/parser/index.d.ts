@@ -8,6 +8,12 @@ export interface ParseResult {
   comments: Array<Comment>
   errors: Array<string>
 }
+/** [`ParseResult`] with the AST encoded as MessagePack instead of JSON. */
+export interface ParseResultMsgpack {
+  root: Buffer
+  comments: Array<Comment>
+  errors: Array<string>
+}
 export interface Comment {
   value: string
   start: number
@@ -30,9 +36,107 @@ export function parseWithoutReturn(sourceText: string): void
  * * Serde JSON serialization
  */
 export function parseSync(sourceText: string): ParseResult
+/**
+ * Like `parseSync`, but the nodes the analysis computes flags for carry
+ * them in a `metadata` object, e.g. whether an expression tag is dynamic,
+ * the namespace of an element, or the binding group of a `bind:group`.
+ *
+ * Only the parser's diagnostics are returned.
+ *
+ * # Panics
+ *
+ * * Serde JSON serialization
+ */
+export function parseSyncWithMetadata(sourceText: string): ParseResult
+/**
+ * Like `parseSync`, but the AST is encoded as MessagePack, which is much
+ * cheaper to produce and decode for large components.
+ *
+ * Decode `root` with `decode` from `msgpack.js`.
+ *
+ * # Panics
+ *
+ * * MessagePack serialization
+ */
+export function parseSyncMsgpack(sourceText: string): ParseResultMsgpack
 /**
  * # Panics
  *
  * * Tokio crashes
  */
 export function parseAsync(sourceText: string): Promise<ParseResult>
+export interface CompileOptions {
+  /** Name of the compiled file, used as the source in source maps. */
+  filename?: string
+  /** Comma separated list of the artifacts to produce, e.g. `css,map`. */
+  emit?: string
+  /** Source map of the source to its original sources, as JSON. */
+  sourcemap?: string
+  /** Force runes mode on or off, inferred from the use of runes otherwise. */
+  runes?: boolean
+  /** Keep the whitespace of the template as written instead of collapsing it. */
+  preserveWhitespace?: boolean
+  /** Keep the HTML comments of the template in the generated code. */
+  preserveComments?: boolean
+  removeUnusedCss?: boolean
+  banner?: string
+  footer?: string
+}
+export interface CompileResult {
+  css?: string
+  /** The source map of `css`, as JSON. */
+  cssMap?: string
+  cssFilename?: string
+  cssImport?: string
+  /** The hydration metadata, as JSON. */
+  hydration?: string
+  /** The server module of a static component. */
+  server?: string
+  js?: string
+  errors: Array<string>
+  warnings: Array<string>
+}
+export interface CompileFile {
+  sourceText: string
+  options?: CompileOptions
+}
+/**
+ * Compile a component, blocking the calling thread.
+ *
+ * # Errors
+ *
+ * * `options.emit` is invalid
+ */
+export function compileSync(sourceText: string, options?: CompileOptions | undefined | null): CompileResult
+/**
+ * Like `compileSync`, but the result is encoded as MessagePack.
+ *
+ * Decode it with `decode` from `msgpack.js`.
+ *
+ * # Errors
+ *
+ * * `options.emit` is invalid
+ *
+ * # Panics
+ *
+ * * MessagePack serialization
+ */
+export function compileSyncMsgpack(sourceText: string, options?: CompileOptions | undefined | null): Buffer
+/**
+ * Compile a component on a libuv worker thread, without blocking the event
+ * loop.
+ *
+ * # Errors
+ *
+ * * `options.emit` is invalid
+ */
+export function compile(sourceText: string, options?: CompileOptions | undefined | null): Promise<CompileResult>
+/**
+ * Compile several components in parallel, on every core, off the event
+ * loop. Results are in the order of `files`.
+ *
+ * # Errors
+ *
+ * * The `emit` option of a file is invalid
+ */
+export function compileMany(files: Array<CompileFile>): Promise<Array<CompileResult>>
@@ -310,8 +310,14 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { parseWithoutReturn, parseSync, parseAsync } = nativeBinding
+const { parseWithoutReturn, parseSync, parseSyncWithMetadata, parseSyncMsgpack, parseAsync, compileSync, compileSyncMsgpack, compile, compileMany } = nativeBinding
 
 module.exports.parseWithoutReturn = parseWithoutReturn
 module.exports.parseSync = parseSync
+module.exports.parseSyncWithMetadata = parseSyncWithMetadata
+module.exports.parseSyncMsgpack = parseSyncMsgpack
 module.exports.parseAsync = parseAsync
+module.exports.compileSync = compileSync
+module.exports.compileSyncMsgpack = compileSyncMsgpack
+module.exports.compile = compile
+module.exports.compileMany = compileMany
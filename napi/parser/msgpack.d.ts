@@ -0,0 +1,4 @@
+import type { Root } from "./ast";
+
+/** Decode the MessagePack `root` returned by `parseSyncMsgpack`. */
+export function decode(buffer: Uint8Array | ArrayBuffer): Root
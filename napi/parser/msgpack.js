@@ -0,0 +1,82 @@
+// Decoder for the MessagePack output of `parseSyncMsgpack` and
+// `compileSyncMsgpack`.
+//
+// Only the subset rmp-serde produces for them is supported: nil, booleans,
+// integers, floats, strings, binary, arrays and maps. Maps decode to plain
+// objects, so the result has the same shape as `JSON.parse(parseSync(..).root)`.
+
+const textDecoder = new TextDecoder()
+
+function decode(buffer) {
+  const bytes = buffer instanceof Uint8Array ? buffer : new Uint8Array(buffer)
+  const view = new DataView(bytes.buffer, bytes.byteOffset, bytes.byteLength)
+  let pos = 0
+
+  function str(len) {
+    const value = textDecoder.decode(bytes.subarray(pos, pos + len))
+    pos += len
+    return value
+  }
+
+  function bin(len) {
+    const value = bytes.slice(pos, pos + len)
+    pos += len
+    return value
+  }
+
+  function array(len) {
+    const value = new Array(len)
+    for (let i = 0; i < len; i++) value[i] = read()
+    return value
+  }
+
+  function map(len) {
+    const value = {}
+    for (let i = 0; i < len; i++) {
+      const key = read()
+      value[key] = read()
+    }
+    return value
+  }
+
+  function read() {
+    const byte = bytes[pos++]
+    if (byte <= 0x7f) return byte
+    if (byte <= 0x8f) return map(byte & 0x0f)
+    if (byte <= 0x9f) return array(byte & 0x0f)
+    if (byte <= 0xbf) return str(byte & 0x1f)
+    if (byte >= 0xe0) return byte - 0x100
+
+    let value
+    switch (byte) {
+      case 0xc0: return null
+      case 0xc2: return false
+      case 0xc3: return true
+      case 0xc4: return bin(bytes[pos++])
+      case 0xc5: value = view.getUint16(pos); pos += 2; return bin(value)
+      case 0xc6: value = view.getUint32(pos); pos += 4; return bin(value)
+      case 0xca: value = view.getFloat32(pos); pos += 4; return value
+      case 0xcb: value = view.getFloat64(pos); pos += 8; return value
+      case 0xcc: return bytes[pos++]
+      case 0xcd: value = view.getUint16(pos); pos += 2; return value
+      case 0xce: value = view.getUint32(pos); pos += 4; return value
+      case 0xcf: value = Number(view.getBigUint64(pos)); pos += 8; return value
+      case 0xd0: value = view.getInt8(pos); pos += 1; return value
+      case 0xd1: value = view.getInt16(pos); pos += 2; return value
+      case 0xd2: value = view.getInt32(pos); pos += 4; return value
+      case 0xd3: value = Number(view.getBigInt64(pos)); pos += 8; return value
+      case 0xd9: return str(bytes[pos++])
+      case 0xda: value = view.getUint16(pos); pos += 2; return str(value)
+      case 0xdb: value = view.getUint32(pos); pos += 4; return str(value)
+      case 0xdc: value = view.getUint16(pos); pos += 2; return array(value)
+      case 0xdd: value = view.getUint32(pos); pos += 4; return array(value)
+      case 0xde: value = view.getUint16(pos); pos += 2; return map(value)
+      case 0xdf: value = view.getUint32(pos); pos += 4; return map(value)
+      default: throw new Error(`Unsupported MessagePack type 0x${byte.toString(16)} at ${pos - 1}`)
+    }
+  }
+
+  return read()
+}
+
+module.exports = { decode }
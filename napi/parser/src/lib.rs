@@ -1,9 +1,18 @@
+use std::{num::NonZeroUsize, thread};
+
+use napi::{
+    bindgen_prelude::{AsyncTask, Buffer},
+    Env, Task,
+};
 use napi_derive::napi;
 
 use oxc_allocator::Allocator;
-use oxc_diagnostics::Error;
+use oxc_diagnostics::{Error, Severity};
+use serde::Serialize;
+use ssc::compiler::{self, Compiler, Emit};
 pub use ssc_ast::ast::Root;
 use ssc_parser::{Parser, ParserReturn};
+use ssc_semantic::SemanticBuilder;
 
 #[napi(object)]
 pub struct ParseResult {
@@ -12,6 +21,14 @@ pub struct ParseResult {
     pub errors: Vec<String>,
 }
 
+/// [`ParseResult`] with the AST encoded as MessagePack instead of JSON.
+#[napi(object)]
+pub struct ParseResultMsgpack {
+    pub root: Buffer,
+    pub comments: Vec<Comment>,
+    pub errors: Vec<String>,
+}
+
 #[napi(object)]
 pub struct Comment {
     pub value: String,
@@ -47,13 +64,55 @@ pub fn parse_sync(source_text: String) -> ParseResult {
     let allocator = Allocator::default();
     let ret = parse(&allocator, &source_text);
     let root = serde_json::to_string(&ret.root).unwrap();
+    let (comments, errors) = comments_and_errors(ret, &source_text);
+    ParseResult { root, comments, errors }
+}
+
+/// Like `parseSync`, but the nodes the analysis computes flags for carry
+/// them in a `metadata` object, e.g. whether an expression tag is dynamic,
+/// the namespace of an element, or the binding group of a `bind:group`.
+///
+/// Only the parser's diagnostics are returned.
+///
+/// # Panics
+///
+/// * Serde JSON serialization
+#[allow(clippy::needless_pass_by_value)]
+#[napi]
+pub fn parse_sync_with_metadata(source_text: String) -> ParseResult {
+    let allocator = Allocator::default();
+    let ret = parse(&allocator, &source_text);
+    let semantic = SemanticBuilder::new(&source_text).build(&ret.root).semantic;
+    let root = semantic.root_to_value(&ret.root).unwrap().to_string();
+    let (comments, errors) = comments_and_errors(ret, &source_text);
+    ParseResult { root, comments, errors }
+}
+
+/// Like `parseSync`, but the AST is encoded as MessagePack, which is much
+/// cheaper to produce and decode for large components.
+///
+/// Decode `root` with `decode` from `msgpack.js`.
+///
+/// # Panics
+///
+/// * MessagePack serialization
+#[allow(clippy::needless_pass_by_value)]
+#[napi]
+pub fn parse_sync_msgpack(source_text: String) -> ParseResultMsgpack {
+    let allocator = Allocator::default();
+    let ret = parse(&allocator, &source_text);
+    let root = rmp_serde::to_vec_named(&ret.root).unwrap().into();
+    let (comments, errors) = comments_and_errors(ret, &source_text);
+    ParseResultMsgpack { root, comments, errors }
+}
 
+fn comments_and_errors(ret: ParserReturn, source_text: &str) -> (Vec<Comment>, Vec<String>) {
     let errors = if ret.errors.is_empty() {
         vec![]
     } else {
         ret.errors
             .into_iter()
-            .map(|diagnostic| Error::from(diagnostic).with_source_code(source_text.clone()))
+            .map(|diagnostic| Error::from(diagnostic).with_source_code(source_text.to_string()))
             .map(|error| format!("{error:?}"))
             .collect()
     };
@@ -62,13 +121,13 @@ pub fn parse_sync(source_text: String) -> ParseResult {
         .trivias
         .comments()
         .map(|span| Comment {
-            value: span.source_text(&source_text).to_string(),
+            value: span.source_text(source_text).to_string(),
             start: span.start,
             end: span.end,
         })
         .collect::<Vec<Comment>>();
 
-    ParseResult { root, comments, errors }
+    (comments, errors)
 }
 
 /// # Panics
@@ -79,3 +138,219 @@ pub fn parse_sync(source_text: String) -> ParseResult {
 pub async fn parse_async(source_text: String) -> ParseResult {
     tokio::spawn(async move { parse_sync(source_text) }).await.unwrap()
 }
+
+#[napi(object)]
+#[derive(Default, Clone)]
+pub struct CompileOptions {
+    /// Name of the compiled file, used as the source in source maps.
+    pub filename: Option<String>,
+    /// Comma separated list of the artifacts to produce, e.g. `css,map`.
+    pub emit: Option<String>,
+    /// Source map of the source to its original sources, as JSON.
+    pub sourcemap: Option<String>,
+    /// Force runes mode on or off, inferred from the use of runes otherwise.
+    pub runes: Option<bool>,
+    /// Keep the whitespace of the template as written instead of collapsing it.
+    pub preserve_whitespace: Option<bool>,
+    /// Keep the HTML comments of the template in the generated code.
+    pub preserve_comments: Option<bool>,
+    pub remove_unused_css: Option<bool>,
+    pub banner: Option<String>,
+    pub footer: Option<String>,
+}
+
+#[napi(object)]
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompileResult {
+    pub css: Option<String>,
+    /// The source map of `css`, as JSON.
+    pub css_map: Option<String>,
+    pub css_filename: Option<String>,
+    pub css_import: Option<String>,
+    /// The hydration metadata, as JSON.
+    pub hydration: Option<String>,
+    /// The server module of a static component.
+    pub server: Option<String>,
+    pub js: Option<String>,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+#[napi(object)]
+pub struct CompileFile {
+    pub source_text: String,
+    pub options: Option<CompileOptions>,
+}
+
+fn compile_options(options: Option<CompileOptions>) -> napi::Result<compiler::CompileOptions> {
+    let options = options.unwrap_or_default();
+    let mut compile_options = compiler::CompileOptions {
+        filename: options.filename.unwrap_or_default(),
+        sourcemap: options.sourcemap,
+        runes: options.runes,
+        preserve_whitespace: options.preserve_whitespace.unwrap_or_default(),
+        preserve_comments: options.preserve_comments.unwrap_or_default(),
+        remove_unused_css: options.remove_unused_css.unwrap_or_default(),
+        banner: options.banner,
+        footer: options.footer,
+        ..compiler::CompileOptions::default()
+    };
+    if let Some(emit) = options.emit {
+        compile_options.emit = emit.parse::<Emit>().map_err(napi::Error::from_reason)?;
+    }
+    Ok(compile_options)
+}
+
+fn compile_source(source_text: &str, options: compiler::CompileOptions) -> CompileResult {
+    let allocator = Allocator::default();
+    let ret = Compiler::new(&allocator, source_text, options).compile();
+    let (warnings, errors): (Vec<_>, Vec<_>) = ret
+        .errors
+        .into_iter()
+        .partition(|error| matches!(error.severity(), Some(Severity::Warning | Severity::Advice)));
+    let format = |errors: Vec<Error>| {
+        errors
+            .into_iter()
+            .map(|error| format!("{:?}", error.with_source_code(source_text.to_string())))
+            .collect()
+    };
+    let css_map = ret.css.as_ref().and_then(|css| css.source_map.as_ref()?.to_json_string().ok());
+    CompileResult {
+        css: ret.css.map(|css| css.source_text),
+        css_map,
+        css_filename: ret.css_filename,
+        css_import: ret.css_import,
+        hydration: ret.hydration.as_ref().map(compiler::Hydration::to_json),
+        server: ret.server.map(|server| server.source_text),
+        js: ret.js.map(|js| js.source_text),
+        errors: format(errors),
+        warnings: format(warnings),
+    }
+}
+
+/// Compile a component, blocking the calling thread.
+///
+/// # Errors
+///
+/// * `options.emit` is invalid
+#[allow(clippy::needless_pass_by_value)]
+#[napi]
+pub fn compile_sync(
+    source_text: String,
+    options: Option<CompileOptions>,
+) -> napi::Result<CompileResult> {
+    Ok(compile_source(&source_text, compile_options(options)?))
+}
+
+/// Like `compileSync`, but the result is encoded as MessagePack.
+///
+/// Decode it with `decode` from `msgpack.js`.
+///
+/// # Errors
+///
+/// * `options.emit` is invalid
+///
+/// # Panics
+///
+/// * MessagePack serialization
+#[allow(clippy::needless_pass_by_value)]
+#[napi]
+pub fn compile_sync_msgpack(
+    source_text: String,
+    options: Option<CompileOptions>,
+) -> napi::Result<Buffer> {
+    let result = compile_source(&source_text, compile_options(options)?);
+    Ok(rmp_serde::to_vec_named(&result).unwrap().into())
+}
+
+pub struct CompileTask {
+    source_text: String,
+    options: compiler::CompileOptions,
+}
+
+#[napi]
+impl Task for CompileTask {
+    type Output = CompileResult;
+    type JsValue = CompileResult;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        Ok(compile_source(&self.source_text, std::mem::take(&mut self.options)))
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+/// Compile a component on a libuv worker thread, without blocking the event
+/// loop.
+///
+/// # Errors
+///
+/// * `options.emit` is invalid
+#[napi(ts_return_type = "Promise<CompileResult>")]
+pub fn compile(
+    source_text: String,
+    options: Option<CompileOptions>,
+) -> napi::Result<AsyncTask<CompileTask>> {
+    Ok(AsyncTask::new(CompileTask { source_text, options: compile_options(options)? }))
+}
+
+pub struct CompileManyTask {
+    files: Vec<(String, compiler::CompileOptions)>,
+}
+
+#[napi]
+impl Task for CompileManyTask {
+    type Output = Vec<CompileResult>;
+    type JsValue = Vec<CompileResult>;
+
+    /// Split the files between as many threads as there are cores.
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        let files = std::mem::take(&mut self.files);
+        if files.is_empty() {
+            return Ok(vec![]);
+        }
+        let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        let chunk_size = files.len().div_ceil(threads);
+        let results = thread::scope(|scope| {
+            let workers = files
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|(source_text, options)| {
+                                compile_source(source_text, options.clone())
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+            workers.into_iter().map(thread::ScopedJoinHandle::join).collect::<Result<Vec<_>, _>>()
+        });
+        results
+            .map(|results| results.into_iter().flatten().collect())
+            .map_err(|_| napi::Error::from_reason("A compile worker panicked"))
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+/// Compile several components in parallel, on every core, off the event
+/// loop. Results are in the order of `files`.
+///
+/// # Errors
+///
+/// * The `emit` option of a file is invalid
+#[napi(ts_return_type = "Promise<Array<CompileResult>>")]
+pub fn compile_many(files: Vec<CompileFile>) -> napi::Result<AsyncTask<CompileManyTask>> {
+    let files = files
+        .into_iter()
+        .map(|file| Ok((file.source_text, compile_options(file.options)?)))
+        .collect::<napi::Result<_>>()?;
+    Ok(AsyncTask::new(CompileManyTask { files }))
+}
@@ -1,5 +1,6 @@
 import ssc from './index.js';
 import assert from 'assert';
+import { decode } from './msgpack.js';
 
 console.log(`Testing on ${process.platform}-${process.arch}`)
 
@@ -14,8 +15,28 @@ const sourceText = "<!-- comment --> foo";
 
 test(ssc.parseSync(sourceText));
 
+const msgpack = ssc.parseSyncMsgpack(sourceText);
+assert.deepStrictEqual(decode(msgpack.root), JSON.parse(ssc.parseSync(sourceText).root));
+
+const component = "<p>hi</p><style>p { color: red; }</style>";
+const compiled = ssc.compileSync(component, { filename: "App.svelte" });
+assert(compiled.css.startsWith("p.svelte-"));
+assert(compiled.errors.length == 0);
+
+const compiledMsgpack = decode(ssc.compileSyncMsgpack(component, { filename: "App.svelte" }));
+assert.strictEqual(compiledMsgpack.css, compiled.css);
+assert.strictEqual(compiledMsgpack.cssFilename, compiled.cssFilename);
+assert.deepStrictEqual(compiledMsgpack.errors, []);
+
 async function main() {
   test(await ssc.parseAsync(sourceText));
+  assert.deepStrictEqual(await ssc.compile(component, { filename: "App.svelte" }), compiled);
+  const many = await ssc.compileMany([
+    { sourceText: component, options: { filename: "App.svelte" } },
+    { sourceText: "<p>{</p>" },
+  ]);
+  assert.deepStrictEqual(many[0], compiled);
+  assert(many[1].errors.length > 0);
 }
 
 main()
@@ -95,7 +95,7 @@ function writeManifest() {
   const content = JSON.stringify(manifestData, null, 2);
   fs.writeFileSync(manifestPath, content);
 
-  let files = ["index.js", "index.d.ts"];
+  let files = ["index.js", "index.d.ts", "ast.d.ts", "msgpack.js", "msgpack.d.ts"];
   for (const file of files) {
     fs.copyFileSync(resolve(BINARY_ROOT, file), resolve(packageRoot, file));
   }
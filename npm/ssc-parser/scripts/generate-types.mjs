@@ -0,0 +1,94 @@
+// Assemble the TypeScript declarations of the AST for the napi and wasm
+// packages.
+//
+// The AST types are generated by Tsify, along with the hand-written
+// `TS_APPEND_CONTENT` sections of `ssc_ast` and `ssc_css_ast`, into the
+// declarations `wasm-pack` emits for the wasm parser. This copies them to
+// `napi/parser/ast.d.ts`, so the JSON `root` of `parseSync` and the decoded
+// MessagePack of `parseSyncMsgpack` are typed too.
+//
+// For the wasm package, `ast.d.ts` re-exports them from the declarations of
+// its Node.js build, which are the same as the ones of its web build, along
+// with the type of the decoded MessagePack of its `parseSyncMsgpack`.
+//
+// Run `pnpm run build-node` in `wasm/parser` first.
+
+import { resolve } from "node:path";
+import { fileURLToPath } from "node:url";
+import * as fs from "node:fs";
+
+const REPO_ROOT = resolve(fileURLToPath(import.meta.url), "../../../..");
+const WASM_DECLARATIONS = resolve(REPO_ROOT, "npm/parser-wasm/node/ssc_parser_wasm.d.ts");
+const OUTPUT_PATH = resolve(REPO_ROOT, "napi/parser/ast.d.ts");
+const WASM_OUTPUT_PATH = resolve(REPO_ROOT, "npm/parser-wasm/ast.d.ts");
+
+// Types of the wasm package's own API, declared by `index.d.ts` in the napi
+// package
+const EXCLUDED = new Set(["ParseResult", "Diagnostic", "TriviaComment"]);
+
+/** The name of the type declared by `declaration`. */
+function declaredName(declaration) {
+  return /^export (?:type|interface) (\w+)/.exec(declaration)[1];
+}
+
+/**
+ * The top-level `export type` and `export interface` declarations of `source`,
+ * but the ones of `excluded`.
+ */
+function typeDeclarations(source, excluded) {
+  const declarations = [];
+  const lines = source.split("\n");
+  for (let index = 0; index < lines.length; index++) {
+    const match = /^export (?:type|interface) (\w+)/.exec(lines[index]);
+    if (!match) {
+      continue;
+    }
+    // A declaration ends on the line where its braces and brackets close
+    const start = index;
+    let depth = 0;
+    for (;;) {
+      for (const char of lines[index]) {
+        if ("{([".includes(char)) depth++;
+        if ("})]".includes(char)) depth--;
+      }
+      if (depth <= 0 || index + 1 === lines.length) {
+        break;
+      }
+      index++;
+    }
+    if (!excluded.has(match[1])) {
+      declarations.push(lines.slice(start, index + 1).join("\n"));
+    }
+  }
+  return declarations;
+}
+
+const source = fs.readFileSync(WASM_DECLARATIONS).toString("utf-8");
+const declarations = typeDeclarations(source, EXCLUDED);
+if (!declarations.some((declaration) => /^export (?:type|interface) Root\b/.test(declaration))) {
+  throw new Error(`No AST declarations in ${WASM_DECLARATIONS}`);
+}
+
+const header = `/* tslint:disable */
+/* eslint-disable */
+
+/* auto-generated by npm/ssc-parser/scripts/generate-types.mjs */
+`;
+console.log(`Write ${declarations.length} declarations to ${OUTPUT_PATH}`);
+fs.writeFileSync(OUTPUT_PATH, `${header}\n${declarations.join("\n\n")}\n`);
+
+const wasmNames = typeDeclarations(source, new Set()).map(declaredName);
+const wasmDeclarations = `export type {
+${wasmNames.map((name) => `  ${name},`).join("\n")}
+} from "./node/ssc_parser_wasm";
+
+import type { Diagnostic, Root } from "./node/ssc_parser_wasm";
+
+/** The decoded MessagePack returned by \`parseSyncMsgpack\`. */
+export interface ParseResultMsgpack {
+  root: Root;
+  errors: Diagnostic[];
+}
+`;
+console.log(`Write ${wasmNames.length} re-exports to ${WASM_OUTPUT_PATH}`);
+fs.writeFileSync(WASM_OUTPUT_PATH, `${header}\n${wasmDeclarations}`);
@@ -2,11 +2,46 @@
 #![allow(non_snake_case)]
 #![allow(clippy::needless_pass_by_value)]
 
+use std::cell::RefCell;
+
 use serde::Serialize;
 use tsify::Tsify;
 use wasm_bindgen::prelude::*;
 
-use ssc::{allocator::Allocator, parser::Parser};
+use ssc::{
+    allocator::Allocator,
+    ast::ast::Root,
+    diagnostics::{OxcDiagnostic, Severity},
+    parser::Parser,
+};
+
+thread_local! {
+    /// The arena of every parse. It's reset, not dropped, between calls, so
+    /// its largest chunk is reused instead of growing the wasm memory, which
+    /// never shrinks.
+    static ALLOCATOR: RefCell<Allocator> = RefCell::default();
+}
+
+/// Run `f` with the reset arena.
+fn with_allocator<T, F: FnOnce(&Allocator) -> T>(f: F) -> T {
+    ALLOCATOR.with_borrow_mut(|allocator| {
+        allocator.reset();
+        f(allocator)
+    })
+}
+
+/// Release the memory kept for the next parse, e.g. after parsing an
+/// unusually large component.
+#[wasm_bindgen]
+pub fn reset() {
+    ALLOCATOR.with_borrow_mut(|allocator| *allocator = Allocator::default());
+}
+
+/// Bytes of memory kept for the next parse.
+#[wasm_bindgen(js_name = allocatedBytes)]
+pub fn allocated_bytes() -> usize {
+    ALLOCATOR.with_borrow(|allocator| allocator.allocated_bytes())
+}
 
 #[derive(Default, Tsify)]
 #[wasm_bindgen(getter_with_clone)]
@@ -37,37 +72,71 @@ pub struct Diagnostic {
 /// * Serde JSON serialization
 #[wasm_bindgen(js_name = parseSync)]
 pub fn parse_sync(source_text: String) -> Result<ParseResult, serde_wasm_bindgen::Error> {
-    let allocator = Allocator::default();
+    with_allocator(|allocator| parse_with(allocator, &source_text))
+}
 
-    let ret = Parser::new(&allocator, &source_text).parse();
+fn parse_with(
+    allocator: &Allocator,
+    source_text: &str,
+) -> Result<ParseResult, serde_wasm_bindgen::Error> {
+    let ret = Parser::new(allocator, source_text).parse();
 
     let serializer = serde_wasm_bindgen::Serializer::json_compatible();
 
     let root = ret.root.serialize(&serializer)?;
 
-    let errors = if ret.errors.is_empty() {
-        vec![]
-    } else {
-        ret.errors
-            .iter()
-            .flat_map(|error| {
-                let Some(labels) = &error.labels else { return vec![] };
-                labels
-                    .iter()
-                    .map(|label| {
-                        Diagnostic {
-                            start: label.offset(),
-                            end: label.offset() + label.len(),
-                            severity: "Error".to_string(),
-                            message: format!("{error}"),
-                        }
-                        .serialize(&serializer)
-                        .unwrap()
-                    })
-                    .collect::<Vec<JsValue>>()
-            })
-            .collect::<Vec<JsValue>>()
-    };
+    let errors = diagnostics(&ret.errors)
+        .into_iter()
+        .map(|diagnostic| diagnostic.serialize(&serializer))
+        .collect::<Result<Vec<JsValue>, _>>()?;
 
     Ok(ParseResult { root, errors })
 }
+
+/// A [`Diagnostic`] for every label of `errors`.
+fn diagnostics(errors: &[OxcDiagnostic]) -> Vec<Diagnostic> {
+    errors
+        .iter()
+        .flat_map(|error| {
+            let labels = error.labels.iter().flatten();
+            labels.map(move |label| Diagnostic {
+                start: label.offset(),
+                end: label.offset() + label.len(),
+                severity: severity(error.severity).to_string(),
+                message: format!("{error}"),
+            })
+        })
+        .collect()
+}
+
+/// The name of `severity`, `Error`, `Warning` or `Advice`.
+fn severity(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "Error",
+        Severity::Warning => "Warning",
+        Severity::Advice => "Advice",
+    }
+}
+
+/// [`ParseResult`] encoded as MessagePack.
+#[derive(Serialize)]
+struct ParseResultMsgpack<'a, 'b> {
+    root: &'b Root<'a>,
+    errors: Vec<Diagnostic>,
+}
+
+/// Parse and return `{ root, errors }`, like `parseSync`, encoded as
+/// MessagePack, which is much cheaper to transfer than a JS object for large
+/// components.
+///
+/// # Errors
+///
+/// * MessagePack serialization failed
+#[wasm_bindgen(js_name = parseSyncMsgpack)]
+pub fn parse_sync_msgpack(source_text: String) -> Result<Vec<u8>, JsError> {
+    with_allocator(|allocator| {
+        let ret = Parser::new(allocator, &source_text).parse();
+        let result = ParseResultMsgpack { root: &ret.root, errors: diagnostics(&ret.errors) };
+        Ok(rmp_serde::to_vec_named(&result)?)
+    })
+}
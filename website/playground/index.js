@@ -585,6 +585,18 @@ async function main() {
     playground.codegenOptions.whitespace = checked;
     playground.updateView("codegen");
   };
+
+  document.getElementById("transform").onchange = function () {
+    const checked = document.getElementById("transform-checkbox").checked;
+    playground.codegenOptions.transform = checked;
+    playground.updateView("codegen");
+  };
+
+  document.getElementById("preserve-whitespace").onchange = function () {
+    const checked = document.getElementById("preserve-whitespace-checkbox").checked;
+    playground.codegenOptions.preserveWhitespace = checked;
+    playground.updateView("codegen");
+  };
 }
 
 // port from https://github.com/fkling/astexplorer/blob/541552fe45885c225fbb67d54dc4c6d6107b65b5/website/src/components/SplitPane.js#L26-L55